@@ -97,30 +97,9 @@ COMMENT ON TABLE login_logs IS '登录日志表';
 COMMENT ON COLUMN login_logs.login_success IS '登录是否成功';
 COMMENT ON COLUMN login_logs.failure_reason IS '登录失败原因';
 
--- 创建默认管理员账户（密码: admin123）
--- 注意：这个密码哈希是 'admin123' 的 bcrypt 哈希值
-INSERT INTO users (username, email, password_hash, full_name, is_admin, is_active) 
-VALUES (
-    'admin',
-    'admin@rocket-taro.com',
-    '$2a$10$Tz0HqGNzgv8fQXTqGDKSUu.kzPQ3jLZ6dKS8tJHb7jGV.yfWmZo3e',
-    '系统管理员',
-    true,
-    true
-) ON CONFLICT (username) DO NOTHING;
-
--- 创建测试用户账户（密码: test123）
-INSERT INTO users (username, email, password_hash, full_name, is_admin, is_active)
-VALUES (
-    'test',
-    'test@rocket-taro.com',
-    '$2a$10$EGP8UUdpXYwJrGf.VRkzTu3Tz5QK8YpY4LG9OJcgL.dKqQzWmFJO2',
-    '测试用户',
-    false,
-    true
-) ON CONFLICT (username) DO NOTHING;
+-- 管理员账户不在此创建，请使用服务端的 --create-initial-admin 生成随机密码
 
 -- 显示创建结果
 SELECT 'Auth tables created successfully' AS status;
-SELECT 'Default users:' AS info;
+SELECT 'Existing users:' AS info;
 SELECT username, email, full_name, is_admin, created_at FROM users;
\ No newline at end of file
@@ -57,6 +57,11 @@ app.config.globalProperties.$routerHandler = routerHandler
 // 挂载应用
 app.mount('#app')
 
+// 已登录时订阅服务端推送（会话过期提醒等）
+if (localStorage.getItem('auth_token')) {
+  apiClient.mobileSubscribePush()
+}
+
 // 开发环境下的调试工具
 if (import.meta.env.MODE === 'development') {
   // 将一些实例暴露到全局，方便调试
@@ -9,6 +9,7 @@ export const useUserStore = defineStore('user', () => {
   // 状态
   const user = ref(null)
   const userList = ref([])
+  const announcements = ref([])
   const isLoading = ref(false)
   const error = ref(null)
 
@@ -50,6 +51,7 @@ export const useUserStore = defineStore('user', () => {
    */
   function clearUser() {
     user.value = null
+    announcements.value = []
     localStorage.removeItem('user_info')
     localStorage.removeItem('auth_token')
   }
@@ -62,6 +64,22 @@ export const useUserStore = defineStore('user', () => {
     userList.value = list || []
   }
 
+  /**
+   * 设置当前公告（服务端通过 ProcessData('announcements') 下发）
+   * @param {Array} list - 公告列表
+   */
+  function setAnnouncements(list) {
+    announcements.value = list || []
+  }
+
+  /**
+   * 移除已关闭的公告
+   * @param {string} id - 公告ID
+   */
+  function removeAnnouncement(id) {
+    announcements.value = announcements.value.filter(item => item.id !== id)
+  }
+
   /**
    * 添加用户到列表
    * @param {Object} userData - 用户数据
@@ -178,6 +196,7 @@ export const useUserStore = defineStore('user', () => {
     // 状态
     user,
     userList,
+    announcements,
     isLoading,
     error,
     
@@ -191,6 +210,8 @@ export const useUserStore = defineStore('user', () => {
     updateUser,
     clearUser,
     setUserList,
+    setAnnouncements,
+    removeAnnouncement,
     addUserToList,
     removeUserFromList,
     updateUserInList,
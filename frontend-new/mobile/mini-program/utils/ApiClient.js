@@ -10,6 +10,14 @@ class ApiClient {
         this.defaultHeaders = {
             'Content-Type': 'application/json',
         }
+        // 声明支持的路由指令类型，服务端据此降级不支持的指令
+        if (routerHandler?.getSupportedCommands) {
+            this.defaultHeaders['X-Client-Capabilities'] = routerHandler.getSupportedCommands().join(',')
+        }
+        // 声明支持的路由指令版本，低于服务端版本时收到旧格式的指令
+        if (routerHandler?.getSupportedVersion) {
+            this.defaultHeaders['X-Route-Command-Version'] = String(routerHandler.getSupportedVersion())
+        }
         this.requestInterceptors = []
         this.responseInterceptors = []
     }
@@ -175,7 +183,10 @@ class ApiClient {
 
         try {
             // 执行路由指令
-            await this.routerHandler.execute(responseData.route_command)
+            const record = await this.routerHandler.execute(responseData.route_command)
+            if (responseData.execution_id && record) {
+                this.acknowledgeRouteCommand(responseData.execution_id, record)
+            }
         } catch (error) {
             console.error('ApiClient: Route command execution failed:', error)
             
@@ -184,6 +195,25 @@ class ApiClient {
         }
     }
 
+    /**
+     * 向服务端确认路由指令执行结果，用于统计流程完成率
+     */
+    acknowledgeRouteCommand(executionId, record) {
+        this.wxRequest({
+            url: this.buildFullURL('/api/route-commands/ack'),
+            method: 'POST',
+            header: this.defaultHeaders,
+            data: {
+                executionId,
+                status: record.status,
+                durationMs: record.duration !== null ? Math.round(record.duration) : null,
+                error: record.error
+            }
+        }).catch(error => {
+            console.warn('ApiClient: Route command acknowledgment failed:', error)
+        })
+    }
+
     /**
      * 构建完整的请求URL
      */
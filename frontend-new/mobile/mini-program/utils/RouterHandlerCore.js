@@ -26,6 +26,7 @@ class RouterHandlerCore {
     /**
      * 执行路由指令（支持版本化指令）
      * @param {Object} routeCommand - 路由指令对象（可能是版本化的）
+     * @returns {Promise<Object|undefined>} 本次执行记录，供调用方向服务端确认执行结果
      */
     async execute(routeCommand) {
         if (!routeCommand) {
@@ -42,6 +43,7 @@ class RouterHandlerCore {
         }
 
         const startTime = Date.now()
+        let record
         
         try {
             // 检查是否为版本化指令
@@ -56,7 +58,7 @@ class RouterHandlerCore {
             const duration = endTime - startTime
             
             // 记录成功执行
-            this.recordExecution(executionId, routeCommand, 'success', null, { duration })
+            record = this.recordExecution(executionId, routeCommand, 'success', null, { duration })
 
         } catch (error) {
             const endTime = Date.now()
@@ -65,7 +67,7 @@ class RouterHandlerCore {
             console.error(`❌ RouterHandler: Command execution failed [${executionId}]:`, error)
             
             // 记录失败执行
-            this.recordExecution(executionId, routeCommand, 'error', error.message, { duration })
+            record = this.recordExecution(executionId, routeCommand, 'error', error.message, { duration })
             
             // 尝试执行回退指令
             await this.handleExecutionError(routeCommand, error, executionId)
@@ -75,20 +77,24 @@ class RouterHandlerCore {
                 console.groupEnd()
             }
         }
+
+        return record
     }
 
     /**
      * 检查是否为版本化指令
      */
     isVersionedCommand(command) {
-        return command.hasOwnProperty('version') && command.hasOwnProperty('command')
+        // 服务端下发的版本化指令把 type/payload 展开在同一层
+        return command.hasOwnProperty('version') && (command.hasOwnProperty('command') || command.hasOwnProperty('type'))
     }
 
     /**
      * 执行版本化指令
      */
     async executeVersionedCommand(versionedCommand, executionId) {
-        const { version, command, fallback, metadata } = versionedCommand
+        const { version, fallback, metadata } = versionedCommand
+        const command = versionedCommand.command || versionedCommand
 
         // 检查版本兼容性
         if (!this.checkVersionCompatibility(version)) {
@@ -126,6 +132,13 @@ class RouterHandlerCore {
         return serverMajor === clientMajor
     }
 
+    /**
+     * 本客户端支持的指令类型，通过 X-Client-Capabilities 请求头告知服务端
+     */
+    getSupportedCommands() {
+        return ['NavigateTo', 'ShowDialog', 'ProcessData', 'Sequence', 'Conditional', 'Delay', 'Parallel', 'Retry', 'RequestPayment']
+    }
+
     /**
      * 执行具体指令
      */
@@ -155,6 +168,9 @@ class RouterHandlerCore {
             case 'Retry':
                 return this.handleRetry(routeCommand.payload, executionId)
             
+            case 'RequestPayment':
+                return this.handleRequestPayment(routeCommand.payload, executionId)
+            
             default:
                 throw new Error(`Unknown route command type: ${routeCommand.type}`)
         }
@@ -328,6 +344,10 @@ class RouterHandlerCore {
                 }
                 break
             
+            case 'announcements':
+                this.globalData.announcements = data || []
+                break
+            
             case 'cache':
                 if (!this.globalData.cache) {
                     this.globalData.cache = {}
@@ -454,6 +474,30 @@ class RouterHandlerCore {
         throw lastError
     }
 
+    /**
+     * 处理调起支付指令
+     */
+    async handleRequestPayment({ params, on_success, on_fail }, executionId) {
+        if (this.debugMode) {
+            console.log(`[${executionId}] Requesting payment: ${params.package}`)
+        }
+
+        try {
+            await this.platformAdapter.requestPayment(params)
+        } catch (error) {
+            // 用户取消或支付失败
+            console.warn(`[${executionId}] Payment not completed:`, error)
+            if (on_fail) {
+                await this.execute(on_fail)
+            }
+            return
+        }
+
+        if (on_success) {
+            await this.execute(on_success)
+        }
+    }
+
     /**
      * 评估条件表达式
      */
@@ -555,6 +599,8 @@ class RouterHandlerCore {
         if (this.debugMode) {
             console.log(`[📈 ${executionId}] Execution recorded:`, record)
         }
+
+        return record
     }
 
     /**
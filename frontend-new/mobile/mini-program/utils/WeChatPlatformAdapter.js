@@ -584,6 +584,21 @@ class WeChatPlatformAdapter {
         })
     }
 
+    /**
+     * 调起微信支付
+     * @param {Object} params - 服务端返回的 timeStamp、nonceStr、package、signType、paySign
+     * @returns {Promise<Object>}
+     */
+    async requestPayment(params) {
+        return new Promise((resolve, reject) => {
+            wx.requestPayment({
+                ...params,
+                success: resolve,
+                fail: reject
+            })
+        })
+    }
+
     /**
      * 返回上一页
      * @param {number} delta - 返回层数
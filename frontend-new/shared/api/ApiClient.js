@@ -50,6 +50,22 @@ class ApiClient {
         return this.mobileInterceptor.post('/auth/logout')
     }
 
+    /**
+     * C端延长当前会话
+     * @returns {Promise<Object>} 响应数据
+     */
+    async mobileExtendSession() {
+        return this.mobileInterceptor.post('/auth/extend-session')
+    }
+
+    /**
+     * C端订阅服务端推送（会话过期提醒、在其他设备登录被踢出、留言回复提醒等）
+     * @returns {EventSource|null} 推送连接
+     */
+    mobileSubscribePush() {
+        return this.mobileInterceptor.openPushChannel()
+    }
+
     /**
      * C端获取用户信息
      * @returns {Promise<Object>} 响应数据
@@ -67,6 +83,117 @@ class ApiClient {
         return this.mobileInterceptor.put('/user/info', userInfo)
     }
 
+    /**
+     * C端修改资料（非微信用户）
+     * @param {Object} profile - { full_name, avatar_url }
+     * @param {number} version - 用户信息中的 version，已被修改时返回 409
+     * @returns {Promise<Object>} 响应数据
+     */
+    async mobileUpdateProfile(profile, version) {
+        return this.mobileInterceptor.patch('/auth/profile', profile, {
+            headers: { 'If-Match': `"${version}"` }
+        })
+    }
+
+    /**
+     * C端获取偏好设置
+     * @returns {Promise<Object>} 响应数据
+     */
+    async mobileGetSettings() {
+        return this.mobileInterceptor.get('/settings')
+    }
+
+    /**
+     * C端更新偏好设置（只需传入要修改的设置项）
+     * @param {Object} settings - 设置项，如 { theme: 'dark' }
+     * @returns {Promise<Object>} 响应数据
+     */
+    async mobileUpdateSettings(settings) {
+        return this.mobileInterceptor.patch('/settings', settings)
+    }
+
+    /**
+     * C端保存表单草稿（登出时有草稿会提示未保存的数据）
+     * @param {string} formKey - 表单键，如 'profile.edit'
+     * @param {Object} data - 表单内容
+     * @returns {Promise<Object>} 响应数据
+     */
+    async mobileSaveDraft(formKey, data) {
+        return this.mobileInterceptor.put(`/drafts/${formKey}`, data)
+    }
+
+    /**
+     * C端获取表单草稿，不存在时 data 为 null
+     * @param {string} formKey - 表单键
+     * @returns {Promise<Object>} 响应数据
+     */
+    async mobileGetDraft(formKey) {
+        return this.mobileInterceptor.get(`/drafts/${formKey}`)
+    }
+
+    /**
+     * C端删除表单草稿（提交或放弃表单后调用）
+     * @param {string} formKey - 表单键
+     * @returns {Promise<Object>} 响应数据
+     */
+    async mobileDeleteDraft(formKey) {
+        return this.mobileInterceptor.delete(`/drafts/${formKey}`)
+    }
+
+    /**
+     * C端申请修改邮箱，确认链接会发送到当前邮箱和新邮箱
+     * @param {string} newEmail - 新邮箱
+     * @param {string} currentPassword - 当前密码
+     * @returns {Promise<Object>} 响应数据
+     */
+    async mobileRequestEmailChange(newEmail, currentPassword) {
+        return this.mobileInterceptor.post('/auth/email-change', {
+            new_email: newEmail,
+            current_password: currentPassword
+        })
+    }
+
+    /**
+     * C端确认邮件中的修改邮箱链接（无需登录）
+     * @param {string} token - 链接中的 token 参数
+     * @returns {Promise<Object>} 响应数据，completed 为 true 时邮箱已修改
+     */
+    async mobileConfirmEmailChange(token) {
+        return this.mobileInterceptor.post('/auth/email-change/confirm', { token })
+    }
+
+    /**
+     * C端使用微信找回凭据重置密码（无需登录），成功后需使用新密码重新登录
+     * @param {string} recoveryToken - 微信找回密码确认后导航参数中的 recovery_token
+     * @param {string} newPassword - 新密码（6-30个字符）
+     * @returns {Promise<Object>} 响应数据
+     */
+    async mobileResetRecoveredPassword(recoveryToken, newPassword) {
+        return this.mobileInterceptor.post('/auth/recovery/reset', {
+            recovery_token: recoveryToken,
+            new_password: newPassword
+        })
+    }
+
+    /**
+     * C端（小程序）管理员扫描网页登录二维码
+     * @param {string} loginId - 二维码内容中的 id 参数
+     * @returns {Promise<Object>} 响应数据，路由指令进入确认登录页
+     */
+    async mobileScanQrLogin(loginId) {
+        return this.mobileInterceptor.post(`/auth/qr-login/${loginId}/scan`)
+    }
+
+    /**
+     * C端（小程序）管理员确认或拒绝网页扫码登录
+     * @param {string} loginId - 扫码登录 ID
+     * @param {boolean} approve - 是否允许登录
+     * @returns {Promise<Object>} 响应数据
+     */
+    async mobileConfirmQrLogin(loginId, approve) {
+        return this.mobileInterceptor.post(`/auth/qr-login/${loginId}/confirm`, { approve })
+    }
+
     /**
      * C端获取用户数据列表
      * @param {Object} params - 查询参数
@@ -76,15 +203,43 @@ class ApiClient {
         return this.mobileInterceptor.get('/user-data', params)
     }
 
+    /**
+     * C端上传附件，返回的 id 在添加用户数据时放入 attachment_ids
+     * @param {File|Blob} file - 文件
+     * @returns {Promise<Object>} 响应数据，data 为 { id, file_name, content_type, file_size, url }
+     */
+    async mobileUploadFile(file) {
+        const formData = new FormData()
+        formData.append('file', file)
+        return this.mobileInterceptor.upload('/uploads', formData)
+    }
+
     /**
      * C端添加用户数据
-     * @param {Object} data - 用户数据
-     * @returns {Promise<Object>} 响应数据
+     * @param {Object} data - 用户数据，attachment_ids 为已上传附件的 id 列表
+     * @returns {Promise<Object>} 响应数据，校验失败时 code 为 422，errors 为 [{ field, message }]
      */
     async mobileAddUserData(data) {
         return this.mobileInterceptor.post('/user-data', data)
     }
 
+    /**
+     * C端获取自己提交的用户数据（按登录邮箱匹配），包含处理状态 status
+     * @returns {Promise<Object>} 响应数据
+     */
+    async mobileGetMyUserData() {
+        return this.mobileInterceptor.get('/user-data/mine')
+    }
+
+    /**
+     * C端查看自己提交的用户数据的回复
+     * @param {string} id - 用户数据ID
+     * @returns {Promise<Object>} 响应数据，data 为用户数据及 replies 回复列表
+     */
+    async mobileGetUserDataReplies(id) {
+        return this.mobileInterceptor.get(`/user-data/${id}/replies`)
+    }
+
     /**
      * C端更新用户数据
      * @param {string} id - 数据ID
@@ -117,6 +272,47 @@ class ApiClient {
         return this.adminInterceptor.post('/auth/login', credentials)
     }
 
+    /**
+     * B端获取扫码登录二维码，qr_content 用于生成二维码，poll_token 用于查询扫码状态
+     * @returns {Promise<Object>} 响应数据
+     */
+    async adminCreateQrLogin() {
+        return this.adminInterceptor.post('/auth/qr-login')
+    }
+
+    /**
+     * B端轮询扫码状态，确认后返回会话和登录路由指令
+     * @param {string} loginId - 扫码登录 ID
+     * @param {string} pollToken - 获取二维码时返回的 poll_token
+     * @returns {Promise<Object>} 响应数据
+     */
+    async adminPollQrLogin(loginId, pollToken) {
+        return this.adminInterceptor.post(`/auth/qr-login/${loginId}/poll`, { poll_token: pollToken })
+    }
+
+    /**
+     * B端订阅扫码状态推送（qr_login 事件），确认后推送中包含会话和登录路由指令
+     * @param {string} loginId - 扫码登录 ID
+     * @param {string} pollToken - 获取二维码时返回的 poll_token
+     * @returns {EventSource|null} 推送连接，不支持 EventSource 时返回 null，需改用轮询
+     */
+    adminSubscribeQrLogin(loginId, pollToken) {
+        if (typeof EventSource === 'undefined') {
+            return null
+        }
+        const query = `poll_token=${encodeURIComponent(pollToken)}`
+        return new EventSource(this.adminInterceptor.buildFullURL(`/auth/qr-login/${loginId}/stream?${query}`))
+    }
+
+    /**
+     * B端在当前会话重新验证密码，之后一段时间内可以执行敏感操作（清除缓存等）
+     * @param {string} password - 当前密码
+     * @returns {Promise<Object>} 响应数据，包含 valid_until
+     */
+    async adminReauthenticate(password) {
+        return this.adminInterceptor.post('/auth/reauth', { password })
+    }
+
     /**
      * B端管理员登出
      * @returns {Promise<Object>} 响应数据
@@ -179,6 +375,50 @@ class ApiClient {
         return this.adminInterceptor.get('/user-data', params)
     }
 
+    /**
+     * B端查看变更历史（按时间倒序分页）
+     * @param {string} entity - 数据类型：users / user-data
+     * @param {string} id - 数据ID
+     * @param {Object} params - 分页参数 { cursor, limit }
+     * @returns {Promise<Object>} 响应数据
+     */
+    async adminGetHistory(entity, id, params = {}) {
+        return this.adminInterceptor.get(`/admin/history/${entity}/${id}`, params)
+    }
+
+    /**
+     * B端查看用户数据及回复
+     * @param {string} id - 用户数据ID
+     * @returns {Promise<Object>} 响应数据
+     */
+    async adminGetUserDataReplies(id) {
+        return this.adminInterceptor.get(`/admin/user-data/${id}/replies`)
+    }
+
+    /**
+     * B端回复用户数据，未指定状态时新提交转为处理中
+     * @param {string} id - 用户数据ID
+     * @param {string} content - 回复内容
+     * @param {string} [status] - 处理状态：new / in_progress / resolved
+     * @returns {Promise<Object>} 响应数据
+     */
+    async adminReplyUserData(id, content, status) {
+        return this.adminInterceptor.post(`/admin/user-data/${id}/replies`, { content, status })
+    }
+
+    /**
+     * B端更新用户数据处理状态
+     * @param {string} id - 用户数据ID
+     * @param {string} status - 处理状态：new / in_progress / resolved
+     * @param {number} version - 读取数据时的 version，已被修改时返回 409
+     * @returns {Promise<Object>} 响应数据
+     */
+    async adminUpdateUserDataStatus(id, status, version) {
+        return this.adminInterceptor.put(`/admin/user-data/${id}/status`, { status }, {
+            headers: { 'If-Match': `"${version}"` }
+        })
+    }
+
     // ========================
     // 公共API方法
     // ========================
@@ -11,6 +11,16 @@ class RequestInterceptor {
         this.defaultHeaders = {
             'Content-Type': 'application/json',
         }
+        // 声明支持的路由指令类型，服务端据此降级不支持的指令
+        if (routerHandler?.getSupportedCommands) {
+            this.defaultHeaders['X-Client-Capabilities'] = routerHandler.getSupportedCommands().join(',')
+        }
+        // 声明支持的路由指令版本，低于服务端版本时收到旧格式的指令
+        if (routerHandler?.getSupportedVersion) {
+            this.defaultHeaders['X-Route-Command-Version'] = String(routerHandler.getSupportedVersion())
+        }
+        // 会话过期提醒确认后通过本拦截器延长会话
+        routerHandler?.setSessionExtender?.(() => this.post('/auth/extend-session'))
     }
 
     /**
@@ -40,6 +50,18 @@ class RequestInterceptor {
         return this.request('POST', url, data, options)
     }
 
+    /**
+     * 上传文件（multipart/form-data，由浏览器设置 Content-Type 和分隔符）
+     * @param {string} url - 请求URL
+     * @param {FormData} formData - 表单数据
+     * @param {Object} options - 请求选项
+     * @returns {Promise<Object>} 响应数据
+     */
+    async upload(url, formData, options = {}) {
+        const { 'Content-Type': _contentType, ...headers } = this.defaultHeaders
+        return this.request('POST', url, null, { ...options, headers: { ...headers, ...options.headers }, body: formData })
+    }
+
     /**
      * 发送GET请求
      * @param {string} url - 请求URL
@@ -71,6 +93,17 @@ class RequestInterceptor {
         return this.request('PUT', url, data, options)
     }
 
+    /**
+     * 发送PATCH请求
+     * @param {string} url - 请求URL
+     * @param {Object} data - 请求数据
+     * @param {Object} options - 请求选项
+     * @returns {Promise<Object>} 响应数据
+     */
+    async patch(url, data = {}, options = {}) {
+        return this.request('PATCH', url, data, options)
+    }
+
     /**
      * 发送DELETE请求
      * @param {string} url - 请求URL
@@ -93,22 +126,23 @@ class RequestInterceptor {
         const fullUrl = this.buildFullURL(url)
         
         const config = {
+            ...options,
             method,
             headers: {
                 ...this.defaultHeaders,
                 ...options.headers
-            },
-            ...options
+            }
         }
 
-        if (data && (method === 'POST' || method === 'PUT')) {
+        if (data && (method === 'POST' || method === 'PUT' || method === 'PATCH')) {
             config.body = JSON.stringify(data)
         }
 
         try {
             const response = await fetch(fullUrl, config)
             
-            if (!response.ok) {
+            // 版本冲突（409）的响应带有"重新加载"确认指令，按普通响应处理
+            if (!response.ok && response.status !== 409) {
                 throw new Error(`HTTP error! status: ${response.status}`)
             }
 
@@ -149,7 +183,10 @@ class RequestInterceptor {
 
         try {
             // 执行路由指令
-            await this.routerHandler.execute(responseData.route_command)
+            const record = await this.routerHandler.execute(responseData.route_command)
+            if (responseData.execution_id && record) {
+                this.acknowledgeRouteCommand(responseData.execution_id, record)
+            }
         } catch (error) {
             console.error('RequestInterceptor: Route command execution failed:', error)
             
@@ -158,6 +195,50 @@ class RequestInterceptor {
         }
     }
 
+    /**
+     * 向服务端确认路由指令执行结果，用于统计流程完成率
+     * @param {string} executionId - 服务端下发的执行ID
+     * @param {Object} record - RouterHandler 的执行记录
+     */
+    acknowledgeRouteCommand(executionId, record) {
+        fetch(this.buildFullURL('/api/route-commands/ack'), {
+            method: 'POST',
+            headers: this.defaultHeaders,
+            body: JSON.stringify({
+                executionId,
+                status: record.status,
+                durationMs: record.duration !== null ? Math.round(record.duration) : null,
+                error: record.error
+            })
+        }).catch(error => {
+            console.warn('RequestInterceptor: Route command acknowledgment failed:', error)
+        })
+    }
+
+    /**
+     * 订阅服务端推送（Server-Sent Events），执行推送消息中的路由指令
+     * @param {string[]} events - 订阅的事件名
+     * @returns {EventSource|null} 推送连接，不支持 EventSource 时返回 null
+     */
+    openPushChannel(events = ['session_expiring', 'session_evicted', 'user_data_reply']) {
+        if (!this.routerHandler || typeof EventSource === 'undefined') {
+            return null
+        }
+
+        const source = new EventSource(this.buildFullURL('/push/stream'), { withCredentials: true })
+        events.forEach(event => {
+            source.addEventListener(event, async (message) => {
+                try {
+                    const { route_command } = JSON.parse(message.data)
+                    await this.routerHandler.execute(route_command)
+                } catch (error) {
+                    console.error(`RequestInterceptor: Push message '${event}' handling failed:`, error)
+                }
+            })
+        })
+        return source
+    }
+
     /**
      * 构建完整的请求URL
      * @param {string} url - 原始URL
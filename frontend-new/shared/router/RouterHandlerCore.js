@@ -25,6 +25,7 @@ class RouterHandlerCore {
     /**
      * 执行路由指令（支持版本化指令）
      * @param {Object} routeCommand - 路由指令对象（可能是版本化的）
+     * @returns {Promise<Object|undefined>} 本次执行记录，供调用方向服务端确认执行结果
      */
     async execute(routeCommand) {
         if (!routeCommand) {
@@ -47,6 +48,7 @@ class RouterHandlerCore {
         }
 
         const startTime = performance.now()
+        let record
         
         try {
             // 检查是否为版本化指令
@@ -61,7 +63,7 @@ class RouterHandlerCore {
             const duration = endTime - startTime
             
             // 记录成功执行
-            this.recordExecution(executionId, routeCommand, 'success', null, { duration })
+            record = this.recordExecution(executionId, routeCommand, 'success', null, { duration })
 
         } catch (error) {
             const endTime = performance.now()
@@ -70,7 +72,7 @@ class RouterHandlerCore {
             console.error(`❌ RouterHandler: Command execution failed [${executionId}]:`, error)
             
             // 记录失败执行
-            this.recordExecution(executionId, routeCommand, 'error', error.message, { duration })
+            record = this.recordExecution(executionId, routeCommand, 'error', error.message, { duration })
             
             // 尝试执行回退指令
             await this.handleExecutionError(routeCommand, error, executionId)
@@ -86,6 +88,8 @@ class RouterHandlerCore {
                 console.groupEnd()
             }
         }
+
+        return record
     }
 
     /**
@@ -94,7 +98,8 @@ class RouterHandlerCore {
      * @returns {boolean}
      */
     isVersionedCommand(command) {
-        return command.hasOwnProperty('version') && command.hasOwnProperty('command')
+        // 服务端下发的版本化指令把 type/payload 展开在同一层
+        return command.hasOwnProperty('version') && (command.hasOwnProperty('command') || command.hasOwnProperty('type'))
     }
 
     /**
@@ -103,7 +108,8 @@ class RouterHandlerCore {
      * @param {string} executionId - 执行ID
      */
     async executeVersionedCommand(versionedCommand, executionId) {
-        const { version, command, fallback, metadata } = versionedCommand
+        const { version, fallback, metadata } = versionedCommand
+        const command = versionedCommand.command || versionedCommand
 
         // 检查版本兼容性
         if (!this.checkVersionCompatibility(version)) {
@@ -148,6 +154,15 @@ class RouterHandlerCore {
         return serverMajor === clientMajor
     }
 
+    /**
+     * 本客户端支持的指令类型，通过 X-Client-Capabilities 请求头告知服务端，
+     * 服务端会把其他类型的指令降级为这些指令
+     * @returns {string[]}
+     */
+    getSupportedCommands() {
+        return ['NavigateTo', 'ShowDialog', 'ProcessData', 'Sequence', 'Conditional', 'Delay', 'Parallel', 'Retry']
+    }
+
     /**
      * 执行具体指令
      * @param {Object} routeCommand - 路由指令
@@ -312,6 +327,17 @@ class RouterHandlerCore {
                 }
                 break
             
+            case 'announcements':
+                this.store.setAnnouncements?.(data || [])
+                break
+            
+            case 'session':
+                // 会话过期提醒确认后延长会话
+                if (data?.action === 'extend') {
+                    await this.sessionExtender?.()
+                }
+                break
+            
             case 'cache':
                 // 处理缓存相关数据
                 if (this.store.updateCache) {
@@ -576,6 +602,8 @@ class RouterHandlerCore {
         if (this.debugMode) {
             console.log(`[📈 ${executionId}] Execution recorded:`, record)
         }
+
+        return record
     }
 
     /**
@@ -671,6 +699,14 @@ class RouterHandlerCore {
         this.debugMode = enabled
     }
 
+    /**
+     * 设置延长会话的方法，由 ProcessData('session', { action: 'extend' }) 调用
+     * @param {Function} extender - 返回 Promise 的延长会话方法
+     */
+    setSessionExtender(extender) {
+        this.sessionExtender = extender
+    }
+
     /**
      * 更新store引用
      * @param {Object} store - 新的store对象
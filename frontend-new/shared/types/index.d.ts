@@ -8,7 +8,7 @@
 // ========================
 
 export interface RouteCommand {
-  type: 'NavigateTo' | 'ShowDialog' | 'ProcessData' | 'Sequence' | 'Conditional' | 'Delay' | 'Parallel' | 'Retry'
+  type: 'NavigateTo' | 'ShowDialog' | 'ProcessData' | 'Sequence' | 'Conditional' | 'Delay' | 'Parallel' | 'Retry' | 'RequestPayment'
   payload: any
 }
 
@@ -64,6 +64,18 @@ export interface RetryPayload {
   delay_ms: number
 }
 
+export interface RequestPaymentPayload {
+  params: {
+    timeStamp: string
+    nonceStr: string
+    package: string
+    signType: string
+    paySign: string
+  }
+  on_success?: RouteCommand
+  on_fail?: RouteCommand
+}
+
 export interface VersionedRouteCommand {
   version: number
   command: RouteCommand
@@ -72,6 +84,11 @@ export interface VersionedRouteCommand {
 }
 
 export interface RouteCommandMetadata {
+  /** 服务端分配的指令ID（UUID），与 execution_id 相同 */
+  id?: string
+  description?: string
+  /** 执行失败后可以直接重试 */
+  retryable?: boolean
   timeout_ms?: number
   priority?: number
   tags?: string[]
@@ -85,7 +102,10 @@ export interface ApiResponse<T = any> {
   code: number
   message: string
   data?: T
-  route_command?: RouteCommand | VersionedRouteCommand
+  /** 服务端下发的指令为 type/payload 与 version、fallback、metadata 展开在同一层的版本化指令 */
+  route_command?: RouteCommand | VersionedRouteCommand | (RouteCommand & Omit<VersionedRouteCommand, 'command'>)
+  /** 路由指令执行ID，执行完成后通过 /api/route-commands/ack 确认 */
+  execution_id?: string
 }
 
 export interface AdminApiResponse<T = any> {
@@ -252,6 +272,17 @@ export interface AppSettings {
   autoLogin: boolean
 }
 
+/** 服务端保存的用户偏好设置（GET/PATCH /api/settings，登录时通过 ProcessData('settings') 下发） */
+export interface UserSettings {
+  locale: 'zh-CN' | 'en-US'
+  theme: 'light' | 'dark' | 'system'
+  notifications: {
+    enabled: boolean
+    order_updates: boolean
+    marketing: boolean
+  }
+}
+
 // ========================
 // 组件Props类型
 // ========================
@@ -0,0 +1,34 @@
+use std::process::Command;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 编译内部 gRPC 接口定义，使用随依赖提供的 protoc，构建环境无需安装
+    let mut config = tonic_prost_build::Config::new();
+    config.protoc_executable(protoc_bin_vendored::protoc_bin_path()?);
+    tonic_prost_build::configure()
+        .build_client(false)
+        .compile_with_config(config, &["proto/internal/v1/internal.proto"], &["proto"])?;
+
+    embed_git_hash();
+    Ok(())
+}
+
+// 写入当前 git 提交供运行状态展示；没有 .git 的构建环境（如 Docker）可通过 GIT_COMMIT_HASH 环境变量传入
+fn embed_git_hash() {
+    println!("cargo:rerun-if-env-changed=GIT_COMMIT_HASH");
+    if std::env::var("GIT_COMMIT_HASH").is_ok_and(|hash| !hash.is_empty()) {
+        return;
+    }
+
+    let git = |args: &[&str]| {
+        Command::new("git").args(args).output().ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    if let Some(git_dir) = git(&["rev-parse", "--absolute-git-dir"]) {
+        println!("cargo:rerun-if-changed={}/HEAD", git_dir);
+        println!("cargo:rerun-if-changed={}/refs/heads", git_dir);
+    }
+    if let Some(hash) = git(&["rev-parse", "--short=12", "HEAD"]) {
+        println!("cargo:rustc-env=GIT_COMMIT_HASH={}", hash);
+    }
+}
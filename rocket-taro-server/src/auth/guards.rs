@@ -1,9 +1,21 @@
-use rocket::{Request, State, request::{self, FromRequest}, http::Status};
-use crate::database::{DbPool, auth::validate_session};
+use rocket::{Request, State, request::{self, FromRequest}, http::{CookieJar, Status}};
+use crate::database::{DbPool, DbHealth, auth::{validate_session, touch_session, logout_session, rebind_session}, audit::record_audit_event};
 use crate::models::auth::{User, UserSession};
-use crate::cache::{RedisPool, session::SessionCache};
+use crate::models::audit::AuditEvent;
+use crate::models::session_binding::{BindingMismatch, BindingMode, ClientFingerprint};
+use crate::cache::{RedisPool, session::SessionCache, capabilities::ClientCapabilitiesCache, reauth::ReauthCache};
+use crate::config::{CookieConfig, Platform, PresenceConfig, RecentAuthConfig, SessionBindingConfig, SessionExpiryConfig, TrustedProxies, cookie::SESSION_COOKIE};
+use crate::models::client_capabilities::ClientCapabilities;
+use crate::fairings::{request_log::record_user, impersonation_audit::record_impersonation};
+use crate::use_cases::{consent_use_case::ConsentUseCase, identity_use_case::IdentityUseCase, presence_use_case::PresenceUseCase};
+use crate::config::IdentityVerificationConfig;
+use crate::identity::IdentityVerifier;
+use crate::models::identity::{IdentityRequirement, VerificationBlock};
+use std::sync::Arc;
 use std::net::IpAddr;
-use tracing::{debug, warn};
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use tracing::{debug, info, warn};
 
 #[derive(Debug)]
 pub struct AuthenticatedUser {
@@ -17,106 +29,325 @@ pub enum AuthError {
     Invalid,
     Expired,
     DatabaseError,
+    DatabaseUnavailable,
+    ReauthRequired,
+    /// 模拟登录会话不能执行的操作
+    Impersonating,
+    /// 有未同意的生效协议版本
+    ConsentRequired,
+    /// 未完成实名认证或未达到年龄限制
+    VerificationRequired,
 }
 
-// 认证用户请求守卫
+// 从Cookie或Authorization头获取会话令牌
+fn session_token(req: &Request<'_>) -> Option<String> {
+    req.cookies()
+        .get_private(SESSION_COOKIE)
+        .map(|cookie| cookie.value().to_string())
+        .or_else(|| {
+            req.headers()
+                .get_one("Authorization")
+                .and_then(|auth| auth.strip_prefix("Bearer "))
+                .map(|token| token.to_string())
+        })
+}
+
+fn session_expiry_config(req: &Request<'_>) -> SessionExpiryConfig {
+    req.rocket().state::<SessionExpiryConfig>().cloned().unwrap_or_default()
+}
+
+// 空闲过期的会话从缓存和数据库中删除
+async fn expire_idle_session(req: &Request<'_>, token: &str) {
+    info!("Session expired after idle timeout");
+    if let Some(redis_pool) = req.guard::<&State<RedisPool>>().await.succeeded() {
+        if let Err(e) = SessionCache::new(redis_pool.inner().clone()).invalidate_session(token).await {
+            warn!("Failed to invalidate idle session cache: {}", e);
+        }
+    }
+    if let Some(db_pool) = req.guard::<&State<DbPool>>().await.succeeded() {
+        if let Err(e) = logout_session(db_pool, token).await {
+            warn!("Failed to delete idle session: {}", e);
+        }
+    }
+}
+
+// 认证用户请求守卫，认证后检查请求是否来自会话绑定的客户端
 #[rocket::async_trait]
 impl<'r> FromRequest<'r> for AuthenticatedUser {
     type Error = AuthError;
 
     async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
-        // 从Cookie或Authorization头获取会话令牌
-        let session_token = req.cookies()
-            .get_private("session_token")
-            .and_then(|cookie| Some(cookie.value().to_string()))
-            .or_else(|| {
-                req.headers()
-                    .get_one("Authorization")
-                    .and_then(|auth| {
-                        if auth.starts_with("Bearer ") {
-                            Some(auth[7..].to_string())
-                        } else {
-                            None
-                        }
-                    })
-            });
-
-        if let Some(token) = session_token {
-            // 优先从Redis缓存获取会话信息
-            if let Some(redis_pool) = req.guard::<&State<RedisPool>>().await.succeeded() {
-                let session_cache = SessionCache::new(redis_pool.inner().clone());
-                
-                match session_cache.get_user_session_by_token(&token).await {
-                    Ok(Some(cached_session)) => {
-                        debug!("Session found in cache for token");
-                        // 更新会话访问时间
-                        if let Err(e) = session_cache.update_session_access(&token).await {
-                            debug!("Failed to update session access time: {}", e);
-                        }
-                        
-                        // 转换缓存的数据为原始类型
-                        let user = User {
-                            id: cached_session.user.id,
-                            username: cached_session.user.username,
-                            email: cached_session.user.email,
-                            full_name: cached_session.user.full_name,
-                            avatar_url: cached_session.user.avatar_url,
-                            is_active: cached_session.user.is_active,
-                            is_admin: cached_session.user.is_admin,
-                            is_guest: cached_session.user.is_guest,
-                            wx_openid: cached_session.user.wx_openid,
-                            wx_unionid: cached_session.user.wx_unionid,
-                            wx_session_key: cached_session.user.wx_session_key,
-                            last_login_at: None, // 缓存中不存储这些时间字段
-                            created_at: cached_session.session.created_at,
-                            updated_at: cached_session.session.created_at,
-                        };
-                        
-                        let session = UserSession {
-                            id: cached_session.session.id,
-                            user_id: cached_session.session.user_id,
-                            session_token: cached_session.session.session_token,
-                            user_agent: cached_session.session.user_agent,
-                            ip_address: cached_session.session.ip_address,
-                            expires_at: cached_session.session.expires_at,
-                            created_at: cached_session.session.created_at,
-                        };
-                        
-                        return request::Outcome::Success(AuthenticatedUser { user, session });
+        match authenticate(req).await {
+            request::Outcome::Success(auth_user) => verify_session_binding(req, auth_user).await,
+            outcome => outcome,
+        }
+    }
+}
+
+// 不检查会话绑定的认证用户，只用于重新验证身份和退出登录，避免 challenge 模式下无法完成验证
+pub struct SessionUser(pub AuthenticatedUser);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for SessionUser {
+    type Error = AuthError;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        authenticate(req).await.map(SessionUser)
+    }
+}
+
+// 通过会话令牌认证用户：优先读取缓存，未命中时查询数据库
+async fn authenticate(req: &Request<'_>) -> request::Outcome<AuthenticatedUser, AuthError> {
+    if let Some(token) = session_token(req) {
+        // 优先从Redis缓存获取会话信息，Redis 熔断期间直接查询数据库
+        let redis_pool = req.guard::<&State<RedisPool>>().await.succeeded()
+            .filter(|redis_pool| redis_pool.is_available());
+        if let Some(redis_pool) = redis_pool {
+            let session_cache = SessionCache::new(redis_pool.inner().clone());
+            
+            match session_cache.get_user_session_by_token(&token).await {
+                Ok(Some(cached_session)) => {
+                    debug!("Session found in cache for token");
+                    let expiry_config = session_expiry_config(req);
+                    let last_access = session_cache.get_session_last_access(&token).await
+                        .unwrap_or_default()
+                        .and_then(|timestamp| DateTime::from_timestamp(timestamp, 0))
+                        .or(cached_session.session.last_accessed_at)
+                        .unwrap_or(cached_session.session.created_at);
+                    if expiry_config.is_idle(last_access, Utc::now()) {
+                        expire_idle_session(req, &token).await;
+                        return request::Outcome::Error((Status::Unauthorized, AuthError::Expired));
                     }
-                    Ok(None) => {
-                        debug!("Session not found in cache, checking database");
+
+                    // 更新会话访问时间，按间隔写回数据库
+                    if let Err(e) = session_cache.update_session_access(&token).await {
+                        debug!("Failed to update session access time: {}", e);
                     }
-                    Err(e) => {
-                        warn!("Cache lookup failed, falling back to database: {}", e);
+                    if let Ok(true) = session_cache.claim_access_write(&token, expiry_config.access_write_interval_secs).await {
+                        if let Some(db_pool) = req.guard::<&State<DbPool>>().await.succeeded() {
+                            let db_pool = db_pool.inner().clone();
+                            let session_id = cached_session.session.id;
+                            tokio::spawn(async move {
+                                if let Err(e) = touch_session(&db_pool, session_id).await {
+                                    debug!("Failed to write session access time: {}", e);
+                                }
+                            });
+                        }
                     }
+                    
+                    // 转换缓存的数据为原始类型
+                    let user = User {
+                        id: cached_session.user.id,
+                        username: cached_session.user.username,
+                        email: cached_session.user.email,
+                        full_name: cached_session.user.full_name,
+                        avatar_url: cached_session.user.avatar_url,
+                        is_active: cached_session.user.is_active,
+                        is_admin: cached_session.user.is_admin,
+                        is_guest: cached_session.user.is_guest,
+                        wx_openid: cached_session.user.wx_openid,
+                        wx_unionid: cached_session.user.wx_unionid,
+                        wx_session_key: cached_session.user.wx_session_key,
+                        last_login_at: None, // 缓存中不存储这些时间字段
+                        created_at: cached_session.session.created_at,
+                        updated_at: cached_session.session.created_at,
+                        version: cached_session.user.version,
+                    };
+                    
+                    let session = cached_session.session.into_session(&token);
+                    record_session_user(req, &user, &session);
+                    
+                    return request::Outcome::Success(AuthenticatedUser { user, session });
+                }
+                Ok(None) => {
+                    debug!("Session not found in cache, checking database");
+                }
+                Err(e) => {
+                    warn!("Cache lookup failed, falling back to database: {}", e);
                 }
             }
-            
-            // 缓存未命中或失败，回退到数据库验证
-            if let Some(db_pool) = req.guard::<&State<DbPool>>().await.succeeded() {
-                match validate_session(db_pool, &token).await {
-                    Ok(Some((user, session))) => {
-                        debug!("Session validated from database");
-                        // 尝试缓存会话信息
-                        if let Some(redis_pool) = req.guard::<&State<RedisPool>>().await.succeeded() {
-                            let session_cache = SessionCache::new(redis_pool.inner().clone());
-                            if let Err(e) = session_cache.cache_user_session(&user, &session).await {
-                                debug!("Failed to cache user session after database validation: {}", e);
-                            }
+        }
+        
+        // 数据库熔断期间只使用缓存认证，缓存未命中时直接返回 503
+        if let Some(db_health) = req.guard::<&State<DbHealth>>().await.succeeded() {
+            if !db_health.is_available() {
+                warn!("Database unavailable, session not found in cache");
+                return request::Outcome::Error((Status::ServiceUnavailable, AuthError::DatabaseUnavailable));
+            }
+        }
+
+        // 缓存未命中或失败，回退到数据库验证
+        if let Some(db_pool) = req.guard::<&State<DbPool>>().await.succeeded() {
+            match validate_session(db_pool, &token).await {
+                Ok(Some((user, session))) => {
+                    debug!("Session validated from database");
+                    if session_expiry_config(req).is_idle(session.last_accessed_at, Utc::now()) {
+                        expire_idle_session(req, &token).await;
+                        return request::Outcome::Error((Status::Unauthorized, AuthError::Expired));
+                    }
+                    // 尝试缓存会话信息
+                    if let Some(redis_pool) = req.guard::<&State<RedisPool>>().await.succeeded() {
+                        let session_cache = SessionCache::new(redis_pool.inner().clone());
+                        if let Err(e) = session_cache.cache_user_session(&user, &session).await {
+                            debug!("Failed to cache user session after database validation: {}", e);
                         }
-                        request::Outcome::Success(AuthenticatedUser { user, session })
                     }
-                    Ok(None) => request::Outcome::Error((Status::Unauthorized, AuthError::Invalid)),
-                    Err(_) => request::Outcome::Error((Status::InternalServerError, AuthError::DatabaseError)),
+                    record_session_user(req, &user, &session);
+                    request::Outcome::Success(AuthenticatedUser { user, session })
                 }
-            } else {
-                request::Outcome::Error((Status::InternalServerError, AuthError::DatabaseError))
+                Ok(None) => request::Outcome::Error((Status::Unauthorized, AuthError::Invalid)),
+                Err(_) => request::Outcome::Error((Status::InternalServerError, AuthError::DatabaseError)),
             }
         } else {
-            request::Outcome::Error((Status::Unauthorized, AuthError::Missing))
+            request::Outcome::Error((Status::InternalServerError, AuthError::DatabaseError))
+        }
+    } else {
+        request::Outcome::Error((Status::Unauthorized, AuthError::Missing))
+    }
+}
+
+// 记录本次请求认证出的用户，模拟登录会话同时记录原管理员供审计
+fn record_session_user(req: &Request<'_>, user: &User, session: &UserSession) {
+    record_user(req, user.id);
+    match session.impersonator_id {
+        Some(admin_id) => record_impersonation(req, admin_id, user.id, session.id),
+        // 模拟登录的请求不算用户本人在线
+        None => record_presence(req, user.id),
+    }
+}
+
+// 在后台更新在线用户表，不增加请求延迟
+fn record_presence(req: &Request<'_>, user_id: uuid::Uuid) {
+    let Some(redis_pool) = req.rocket().state::<RedisPool>().filter(|redis_pool| redis_pool.is_available()) else {
+        return;
+    };
+    let config = req.rocket().state::<PresenceConfig>().cloned().unwrap_or_default();
+    let platform = Platform::from_user_agent(req.headers().get_one("User-Agent").unwrap_or("unknown"));
+    let use_case = PresenceUseCase::new(redis_pool.clone(), config);
+    tokio::spawn(async move {
+        if let Err(e) = use_case.record(user_id, platform, Utc::now()).await {
+            debug!("Failed to record presence: {}", e);
+        }
+    });
+}
+
+// 会话绑定检查：请求的 IP 网段或 User-Agent 类别与会话创建时不一致时，按配置记录、要求重新验证或拒绝
+async fn verify_session_binding(req: &Request<'_>, mut auth_user: AuthenticatedUser) -> request::Outcome<AuthenticatedUser, AuthError> {
+    let default_config = SessionBindingConfig::default();
+    let config = req.rocket().state::<SessionBindingConfig>().unwrap_or(&default_config);
+    if config.mode == BindingMode::Off {
+        return request::Outcome::Success(auth_user);
+    }
+
+    let ip_address = request_ip(req);
+    let user_agent = req.headers().get_one("User-Agent");
+    let session = &auth_user.session;
+    let bound = config.fingerprint(session.ip_address.as_deref().and_then(|ip| ip.parse().ok()), session.user_agent.as_deref());
+    let current = config.fingerprint(ip_address, user_agent);
+    let mismatches = bound.mismatches(&current);
+    if mismatches.is_empty() {
+        return request::Outcome::Success(auth_user);
+    }
+
+    // 游客和微信账户没有密码，无法重新验证，只记录
+    let mode = match config.mode {
+        BindingMode::Challenge if auth_user.user.is_guest => BindingMode::Log,
+        mode => mode,
+    };
+
+    // 重新验证过的会话绑定到当前客户端
+    if mode == BindingMode::Challenge && reauth_is_recent(req, auth_user.session.id).await {
+        rebind(req, &mut auth_user, ip_address, user_agent).await;
+        record_binding_event(req, config, &auth_user, "session.rebound", &mismatches, &bound, &current, ip_address).await;
+        return request::Outcome::Success(auth_user);
+    }
+
+    warn!(session_id = %auth_user.session.id, mode = %mode.as_str(), "Session binding mismatch: {:?}", mismatches);
+    record_binding_event(req, config, &auth_user, "session.binding_mismatch", &mismatches, &bound, &current, ip_address).await;
+    match mode {
+        BindingMode::Off | BindingMode::Log => request::Outcome::Success(auth_user),
+        BindingMode::Challenge => request::Outcome::Error((Status::PreconditionRequired, AuthError::ReauthRequired)),
+        BindingMode::Reject => request::Outcome::Error((Status::Unauthorized, AuthError::Invalid)),
+    }
+}
+
+// 更新会话绑定的客户端并刷新会话缓存
+async fn rebind(req: &Request<'_>, auth_user: &mut AuthenticatedUser, ip_address: Option<IpAddr>, user_agent: Option<&str>) {
+    let Some(db_pool) = req.guard::<&State<DbPool>>().await.succeeded() else {
+        return;
+    };
+    if let Err(e) = rebind_session(db_pool, auth_user.session.id, ip_address, user_agent).await {
+        warn!(session_id = %auth_user.session.id, "Failed to rebind session: {}", e);
+        return;
+    }
+    auth_user.session.ip_address = ip_address.map(|ip| ip.to_string());
+    auth_user.session.user_agent = user_agent.map(str::to_string);
+
+    if let Some(redis_pool) = req.guard::<&State<RedisPool>>().await.succeeded() {
+        let session_cache = SessionCache::new(redis_pool.inner().clone());
+        if let Err(e) = session_cache.cache_user_session(&auth_user.user, &auth_user.session).await {
+            warn!(session_id = %auth_user.session.id, "Failed to cache rebound session: {}", e);
         }
     }
+    info!(session_id = %auth_user.session.id, "Session rebound to new client after reauthentication");
+}
+
+// 记录会话绑定审计事件，同一会话的不一致按间隔只记录一次；只记录网段和类别，不记录完整 User-Agent
+#[allow(clippy::too_many_arguments)]
+async fn record_binding_event(
+    req: &Request<'_>,
+    config: &SessionBindingConfig,
+    auth_user: &AuthenticatedUser,
+    action: &str,
+    mismatches: &[BindingMismatch],
+    bound: &ClientFingerprint,
+    current: &ClientFingerprint,
+    ip_address: Option<IpAddr>,
+) {
+    let Some(db_pool) = req.guard::<&State<DbPool>>().await.succeeded() else {
+        return;
+    };
+    if action == "session.binding_mismatch" {
+        if let Some(redis_pool) = req.guard::<&State<RedisPool>>().await.succeeded() {
+            let session_cache = SessionCache::new(redis_pool.inner().clone());
+            match session_cache.claim_binding_alert(auth_user.session.id, config.alert_interval_secs).await {
+                Ok(false) => return,
+                Ok(true) => {}
+                Err(e) => debug!("Failed to throttle session binding alert: {}", e),
+            }
+        }
+    }
+
+    let event = AuditEvent::new(action, "session")
+        .actor(auth_user.user.id)
+        .target(auth_user.session.id)
+        .ip(ip_address)
+        .details(json!({
+            "mode": config.mode,
+            "mismatches": mismatches,
+            "bound": bound,
+            "current": current,
+        }));
+    if let Err(e) = record_audit_event(db_pool, &event).await {
+        warn!(session_id = %auth_user.session.id, "Failed to record session binding audit event: {}", e);
+    }
+}
+
+// 会话是否在有效期内重新验证过密码，读取失败时按未验证处理
+async fn reauth_is_recent(req: &Request<'_>, session_id: uuid::Uuid) -> bool {
+    let Some(redis_pool) = req.guard::<&State<RedisPool>>().await.succeeded() else {
+        return false;
+    };
+    let default_config = RecentAuthConfig::default();
+    let config = req.rocket().state::<RecentAuthConfig>().unwrap_or(&default_config);
+
+    let confirmed_at = ReauthCache::new(redis_pool.inner().clone())
+        .confirmed_at(session_id).await
+        .unwrap_or_else(|e| {
+            warn!("Failed to load reauthentication time: {}", e);
+            None
+        });
+    confirmed_at.is_some_and(|confirmed_at| config.is_recent(confirmed_at, Utc::now()))
 }
 
 // 可选认证用户请求守卫
@@ -156,6 +387,164 @@ impl<'r> FromRequest<'r> for AdminUser {
     }
 }
 
+// 敏感操作守卫：要求会话在最近一段时间内验证过密码，否则返回 428，由 catcher 下发重新验证指令
+pub struct RecentAuth(pub AuthenticatedUser);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for RecentAuth {
+    type Error = AuthError;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let auth_user = match AuthenticatedUser::from_request(req).await {
+            request::Outcome::Success(auth_user) => auth_user,
+            request::Outcome::Error(e) => return request::Outcome::Error(e),
+            request::Outcome::Forward(f) => return request::Outcome::Forward(f),
+        };
+
+        // 模拟登录的管理员不知道用户密码，敏感操作一律拒绝
+        if auth_user.session.impersonator_id.is_some() {
+            return request::Outcome::Error((Status::Forbidden, AuthError::Impersonating));
+        }
+
+        if reauth_is_recent(req, auth_user.session.id).await {
+            request::Outcome::Success(RecentAuth(auth_user))
+        } else {
+            debug!("Reauthentication required for session: {}", auth_user.session.id);
+            request::Outcome::Error((Status::PreconditionRequired, AuthError::ReauthRequired))
+        }
+    }
+}
+
+// 已同意全部生效协议的认证用户：有未同意的版本时返回 451，由 catcher 下发跳转协议同意页的指令；
+// 模拟登录的管理员不能代替用户同意，不做检查；查询失败时放行
+pub struct ConsentedUser(pub AuthenticatedUser);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for ConsentedUser {
+    type Error = AuthError;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let auth_user = match AuthenticatedUser::from_request(req).await {
+            request::Outcome::Success(auth_user) => auth_user,
+            request::Outcome::Error(e) => return request::Outcome::Error(e),
+            request::Outcome::Forward(f) => return request::Outcome::Forward(f),
+        };
+        if auth_user.session.impersonator_id.is_some() {
+            return request::Outcome::Success(ConsentedUser(auth_user));
+        }
+
+        let Some(db_pool) = req.guard::<&State<DbPool>>().await.succeeded() else {
+            return request::Outcome::Success(ConsentedUser(auth_user));
+        };
+        match ConsentUseCase::new(db_pool.inner().clone()).pending(auth_user.user.id).await {
+            Ok(pending) if !pending.is_empty() => {
+                debug!("Policy consent required for user: {}", auth_user.user.id);
+                let policies: Vec<String> = pending.into_iter().map(|document| document.policy_type).collect();
+                req.local_cache(|| PendingPolicies(policies));
+                request::Outcome::Error((Status::UnavailableForLegalReasons, AuthError::ConsentRequired))
+            }
+            Ok(_) => request::Outcome::Success(ConsentedUser(auth_user)),
+            Err(e) => {
+                warn!("Failed to check policy consent, allowing request: {}", e);
+                request::Outcome::Success(ConsentedUser(auth_user))
+            }
+        }
+    }
+}
+
+/// ConsentedUser 拒绝请求时缓存的未同意协议类型，供 451 catcher 生成跳转参数
+#[derive(Debug, Clone, Default)]
+pub struct PendingPolicies(pub Vec<String>);
+
+// 本人登录的会话：模拟登录会话返回 403，用于注销账户、修改邮箱、支付等敏感操作
+pub struct OwnerSession(pub AuthenticatedUser);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for OwnerSession {
+    type Error = AuthError;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        match AuthenticatedUser::from_request(req).await {
+            request::Outcome::Success(auth_user) if auth_user.session.impersonator_id.is_some() => {
+                debug!("Sensitive action rejected for impersonation session: {}", auth_user.session.id);
+                request::Outcome::Error((Status::Forbidden, AuthError::Impersonating))
+            }
+            outcome => outcome.map(OwnerSession),
+        }
+    }
+}
+
+// 本人会话且满足实名认证要求：不满足时返回 403，由 catcher 下发说明原因并跳转实名认证页的指令；
+// 未开启 enforce 时直接放行，查询失败时返回 503
+async fn identity_gate(req: &Request<'_>, requirement: IdentityRequirement) -> request::Outcome<AuthenticatedUser, AuthError> {
+    let auth_user = match OwnerSession::from_request(req).await {
+        request::Outcome::Success(OwnerSession(auth_user)) => auth_user,
+        request::Outcome::Error(e) => return request::Outcome::Error(e),
+        request::Outcome::Forward(f) => return request::Outcome::Forward(f),
+    };
+
+    let (Some(db_pool), Some(verifier), Some(config)) = (
+        req.rocket().state::<DbPool>(),
+        req.rocket().state::<Arc<dyn IdentityVerifier>>(),
+        req.rocket().state::<IdentityVerificationConfig>(),
+    ) else {
+        return request::Outcome::Success(auth_user);
+    };
+    match IdentityUseCase::new(db_pool.clone(), verifier.clone(), config.clone()).check(auth_user.user.id, requirement).await {
+        Ok(None) => request::Outcome::Success(auth_user),
+        Ok(Some(block)) => {
+            debug!(?block, "Identity verification required for user: {}", auth_user.user.id);
+            req.local_cache(|| IdentityBlock(Some(block)));
+            request::Outcome::Error((Status::Forbidden, AuthError::VerificationRequired))
+        }
+        Err(e) => {
+            warn!("Failed to check identity verification: {}", e);
+            request::Outcome::Error((Status::ServiceUnavailable, AuthError::DatabaseError))
+        }
+    }
+}
+
+// 已完成实名认证的本人会话，用于下单等需要实名的操作
+pub struct RealNameVerified(pub AuthenticatedUser);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for RealNameVerified {
+    type Error = AuthError;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        identity_gate(req, IdentityRequirement::RealName).await.map(RealNameVerified)
+    }
+}
+
+// 已完成实名认证且达到年龄限制的本人会话，用于支付等有年龄限制的操作
+pub struct AdultVerified(pub AuthenticatedUser);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for AdultVerified {
+    type Error = AuthError;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        identity_gate(req, IdentityRequirement::Adult).await.map(AdultVerified)
+    }
+}
+
+/// 实名认证守卫拒绝请求时缓存的原因，供 403 catcher 生成说明和跳转指令
+#[derive(Debug, Clone, Default)]
+pub struct IdentityBlock(pub Option<VerificationBlock>);
+
+/// 获取客户端IP地址：直连来源是 `[proxy]` 中的可信代理时才采用代理设置的请求头
+pub fn request_ip(req: &Request<'_>) -> Option<IpAddr> {
+    let proxies = match req.rocket().state::<TrustedProxies>() {
+        Some(proxies) => proxies,
+        None => req.local_cache(TrustedProxies::default),
+    };
+    proxies.client_ip(
+        req.remote().map(|remote| remote.ip()),
+        req.headers().get_one("X-Real-IP"),
+        req.headers().get_one("X-Forwarded-For"),
+    )
+}
+
 // 请求信息获取守卫
 pub struct RequestInfo {
     pub ip_address: Option<IpAddr>,
@@ -167,15 +556,7 @@ impl<'r> FromRequest<'r> for RequestInfo {
     type Error = ();
 
     async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
-        // 获取客户端IP地址
-        let ip_address = req.headers().get_one("X-Real-IP")
-            .and_then(|ip_str| ip_str.parse().ok())
-            .or_else(|| {
-                req.headers().get_one("X-Forwarded-For")
-                    .and_then(|forwarded| forwarded.split(',').next())
-                    .and_then(|ip_str| ip_str.trim().parse().ok())
-            })
-            .or_else(|| req.client_ip());
+        let ip_address = request_ip(req);
         
         // 获取User-Agent
         let user_agent = req.headers().get_one("User-Agent").map(|s| s.to_string());
@@ -185,4 +566,159 @@ impl<'r> FromRequest<'r> for RequestInfo {
             user_agent,
         })
     }
-}
\ No newline at end of file
+}
+/// 按 `[cookie]` 配置下发和删除会话 Cookie，所有登录、登出接口都通过它设置 Cookie
+pub struct SessionCookies<'r> {
+    jar: &'r CookieJar<'r>,
+    config: &'r CookieConfig,
+}
+
+impl SessionCookies<'_> {
+    pub fn set(&self, session_token: &str) {
+        self.jar.add_private(self.config.session_cookie(session_token));
+    }
+
+    pub fn clear(&self) {
+        self.jar.remove_private(self.config.removal_cookie());
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for SessionCookies<'r> {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let config = match req.rocket().state::<CookieConfig>() {
+            Some(config) => config,
+            None => req.local_cache(CookieConfig::default),
+        };
+        request::Outcome::Success(SessionCookies { jar: req.cookies(), config })
+    }
+}
+
+/// 批量请求转发给子请求的原始请求头，来源 IP 以 X-Real-IP 传递，子请求以当前请求的 span 为上级
+pub struct ForwardedHeaders(pub Vec<(&'static str, String)>);
+
+/// 原样转发的请求头
+const FORWARDED_HEADERS: &[&str] = &[
+    "Cookie",
+    "Authorization",
+    "User-Agent",
+    "Accept-Language",
+    "Accept-Profile",
+    "X-Client-Capabilities",
+    "X-Route-Command-Version",
+    "X-Mock-User",
+];
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for ForwardedHeaders {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let mut headers: Vec<(&'static str, String)> = FORWARDED_HEADERS.iter()
+            .filter_map(|name| req.headers().get_one(name).map(|value| (*name, value.to_string())))
+            .collect();
+        if let Some(ip) = request_ip(req) {
+            headers.push(("X-Real-IP", ip.to_string()));
+        }
+        headers.extend(crate::telemetry::trace_headers());
+        request::Outcome::Success(ForwardedHeaders(headers))
+    }
+}
+
+/// 客户端提供的 Idempotency-Key 请求头（未提供时为 None）
+pub struct IdempotencyKey(pub Option<String>);
+
+/// Idempotency-Key 最大长度
+const MAX_IDEMPOTENCY_KEY_LENGTH: usize = 128;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for IdempotencyKey {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        match req.headers().get_one("Idempotency-Key").map(str::trim) {
+            None | Some("") => request::Outcome::Success(IdempotencyKey(None)),
+            Some(key) if key.len() <= MAX_IDEMPOTENCY_KEY_LENGTH && key.chars().all(|c| c.is_ascii_graphic()) => {
+                request::Outcome::Success(IdempotencyKey(Some(key.to_string())))
+            }
+            Some(_) => {
+                warn!("Rejected malformed Idempotency-Key header");
+                request::Outcome::Error((Status::BadRequest, ()))
+            }
+        }
+    }
+}
+
+/// 客户端生成并持久保存的设备标识（X-Device-Id 请求头，未提供或格式不对时为 None），
+/// 仅用于注册频率等风控统计，不参与鉴权
+pub struct DeviceId(pub Option<String>);
+
+/// 设备标识最大长度
+const MAX_DEVICE_ID_LENGTH: usize = 128;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for DeviceId {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let device_id = req.headers().get_one("X-Device-Id")
+            .map(str::trim)
+            .filter(|id| !id.is_empty() && id.len() <= MAX_DEVICE_ID_LENGTH)
+            .filter(|id| id.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+            .map(str::to_string);
+        request::Outcome::Success(DeviceId(device_id))
+    }
+}
+
+/// 客户端通过 If-Match 请求头提交的版本号（未提供时为 None），支持 `3`、`"3"` 和 `W/"3"`
+pub struct IfMatch(pub Option<i32>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for IfMatch {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let Some(header) = req.headers().get_one("If-Match").map(str::trim).filter(|value| !value.is_empty()) else {
+            return request::Outcome::Success(IfMatch(None));
+        };
+        let tag = header.strip_prefix("W/").unwrap_or(header);
+        let tag = tag.strip_prefix('"').and_then(|tag| tag.strip_suffix('"')).unwrap_or(tag);
+        match tag.parse::<i32>() {
+            Ok(version) => request::Outcome::Success(IfMatch(Some(version))),
+            Err(_) => {
+                warn!("Rejected malformed If-Match header");
+                request::Outcome::Error((Status::BadRequest, ()))
+            }
+        }
+    }
+}
+
+// 客户端路由指令能力：X-Client-Capabilities 请求头优先，其次是会话握手时保存的能力，都没有时视为支持全部指令
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for ClientCapabilities {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let capabilities = req.local_cache_async(async {
+            if let Some(header) = req.headers().get_one("X-Client-Capabilities") {
+                return ClientCapabilities::from_header(header);
+            }
+
+            let (Some(token), Some(redis_pool)) = (session_token(req), req.guard::<&State<RedisPool>>().await.succeeded()) else {
+                return ClientCapabilities::default();
+            };
+            match ClientCapabilitiesCache::new(redis_pool.inner().clone()).get(&token).await {
+                Ok(Some(capabilities)) => capabilities,
+                Ok(None) => ClientCapabilities::default(),
+                Err(e) => {
+                    debug!("Failed to load client capabilities from cache: {}", e);
+                    ClientCapabilities::default()
+                }
+            }
+        }).await;
+
+        request::Outcome::Success(capabilities.clone())
+    }
+}
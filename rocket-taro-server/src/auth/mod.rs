@@ -1,3 +1,5 @@
 pub mod guards;
+pub mod password;
 
-pub use guards::{AuthenticatedUser, OptionalUser, RequestInfo};
\ No newline at end of file
+pub use guards::{AuthenticatedUser, SessionUser, OwnerSession, OptionalUser, RecentAuth, RequestInfo, ForwardedHeaders, IdempotencyKey, DeviceId, IfMatch, SessionCookies, ConsentedUser, RealNameVerified, AdultVerified, request_ip};
+pub use password::PasswordHasher;
\ No newline at end of file
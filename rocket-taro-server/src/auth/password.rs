@@ -0,0 +1,241 @@
+use std::fmt;
+use std::sync::Arc;
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use argon2::password_hash::{PasswordHash, PasswordHasher as _, PasswordVerifier, SaltString, rand_core::OsRng};
+
+use crate::config::{PasswordAlgorithm, PasswordConfig};
+
+#[derive(Debug)]
+pub enum PasswordError {
+    Bcrypt(bcrypt::BcryptError),
+    Argon2(argon2::password_hash::Error),
+}
+
+impl fmt::Display for PasswordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PasswordError::Bcrypt(e) => write!(f, "bcrypt error: {}", e),
+            PasswordError::Argon2(e) => write!(f, "argon2id error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for PasswordError {}
+
+fn bcrypt_cost(hash: &str) -> Option<u32> {
+    hash.split('$').nth(2)?.parse().ok()
+}
+
+/// 密码哈希器：按配置生成新哈希，同时可验证 Argon2id（PHC 格式）和旧版 bcrypt 哈希
+#[derive(Debug, Clone)]
+pub struct PasswordHasher {
+    config: PasswordConfig,
+    /// 随机密码按当前配置生成的哈希，用于让不存在的用户也执行一次同等耗时的验证
+    dummy_hash: Arc<str>,
+}
+
+impl PasswordHasher {
+    pub fn new(config: PasswordConfig) -> Self {
+        let mut hasher = Self { config, dummy_hash: Arc::from("") };
+        let random_password = hex::encode(rand::random::<[u8; 16]>());
+        match hasher.hash(&random_password) {
+            Ok(hash) => hasher.dummy_hash = Arc::from(hash),
+            Err(e) => tracing::error!("Failed to generate dummy password hash: {}", e),
+        }
+        hasher
+    }
+
+    fn argon2(&self) -> Result<Argon2<'static>, PasswordError> {
+        let params = Params::new(
+            self.config.argon2_memory_kib,
+            self.config.argon2_iterations,
+            self.config.argon2_parallelism,
+            None,
+        ).map_err(|e| PasswordError::Argon2(e.into()))?;
+        Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+    }
+
+    /// 使用配置的算法和参数生成密码哈希
+    pub fn hash(&self, password: &str) -> Result<String, PasswordError> {
+        match self.config.algorithm {
+            PasswordAlgorithm::Argon2id => {
+                let salt = SaltString::generate(&mut OsRng);
+                self.argon2()?
+                    .hash_password(password.as_bytes(), &salt)
+                    .map(|hash| hash.to_string())
+                    .map_err(PasswordError::Argon2)
+            }
+            PasswordAlgorithm::Bcrypt => {
+                bcrypt::hash(password, self.config.bcrypt_cost).map_err(PasswordError::Bcrypt)
+            }
+        }
+    }
+
+    /// 验证密码，哈希格式无法识别（如微信、游客用户的空密码）时返回 false
+    ///
+    /// 无法识别的哈希同样执行一次 dummy 验证，响应时间不会暴露账户没有密码。
+    pub fn verify(&self, password: &str, hash: &str) -> bool {
+        match Self::verify_hash(password, hash) {
+            Some(valid) => valid,
+            None => self.verify_dummy(password),
+        }
+    }
+
+    /// 用户不存在时调用：按当前配置执行一次耗时相同的验证，结果始终为 false
+    pub fn verify_dummy(&self, password: &str) -> bool {
+        let _ = Self::verify_hash(password, &self.dummy_hash);
+        false
+    }
+
+    /// 按哈希格式验证，格式无法识别时返回 None
+    fn verify_hash(password: &str, hash: &str) -> Option<bool> {
+        if hash.starts_with("$2") {
+            return Some(bcrypt::verify(password, hash).unwrap_or(false));
+        }
+        match PasswordHash::new(hash) {
+            // 使用哈希中记录的参数验证，配置调整后旧哈希仍可登录
+            Ok(parsed) if parsed.algorithm == Algorithm::Argon2id.ident() => {
+                Some(Argon2::default().verify_password(password.as_bytes(), &parsed).is_ok())
+            }
+            _ => None,
+        }
+    }
+
+    /// 在阻塞线程池中生成哈希，避免 bcrypt / Argon2 的计算占用异步工作线程
+    pub async fn hash_blocking(&self, password: &str) -> Result<String, PasswordError> {
+        let (hasher, password) = (self.clone(), password.to_string());
+        run_blocking(move || hasher.hash(&password)).await
+    }
+
+    /// 在阻塞线程池中验证密码，结果与 `verify` 相同
+    pub async fn verify_blocking(&self, password: &str, hash: &str) -> bool {
+        let (hasher, password, hash) = (self.clone(), password.to_string(), hash.to_string());
+        run_blocking(move || hasher.verify(&password, &hash)).await
+    }
+
+    /// 在阻塞线程池中执行 `verify_dummy`
+    pub async fn verify_dummy_blocking(&self, password: &str) -> bool {
+        let (hasher, password) = (self.clone(), password.to_string());
+        run_blocking(move || hasher.verify_dummy(&password)).await
+    }
+
+    /// 哈希的算法或参数与当前配置不一致时需要在登录成功后重新哈希
+    pub fn needs_rehash(&self, hash: &str) -> bool {
+        match self.config.algorithm {
+            PasswordAlgorithm::Argon2id => {
+                let Ok(parsed) = PasswordHash::new(hash) else {
+                    return true;
+                };
+                if parsed.algorithm != Algorithm::Argon2id.ident() || parsed.version != Some(Version::V0x13.into()) {
+                    return true;
+                }
+                Params::try_from(&parsed).map_or(true, |params| {
+                    params.m_cost() != self.config.argon2_memory_kib
+                        || params.t_cost() != self.config.argon2_iterations
+                        || params.p_cost() != self.config.argon2_parallelism
+                })
+            }
+            PasswordAlgorithm::Bcrypt => {
+                !hash.starts_with("$2") || bcrypt_cost(hash) != Some(self.config.bcrypt_cost)
+            }
+        }
+    }
+}
+
+/// 在阻塞线程池中执行，任务 panic 时在调用方继续 panic，与同步调用的行为一致
+async fn run_blocking<T: Send + 'static>(task: impl FnOnce() -> T + Send + 'static) -> T {
+    match tokio::task::spawn_blocking(task).await {
+        Ok(value) => value,
+        Err(e) => match e.try_into_panic() {
+            Ok(panic) => std::panic::resume_unwind(panic),
+            Err(e) => panic!("password hashing task failed: {}", e),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 旧版初始化脚本写入的 "password" 的 bcrypt 哈希
+    const LEGACY_BCRYPT_HASH: &str = "$2b$10$92IXUNpkjO0rOQ5byMi.Ye4oKoEa3Ro9llC/.og/at2.uheWG/igi";
+
+    fn test_config(algorithm: PasswordAlgorithm) -> PasswordConfig {
+        PasswordConfig {
+            algorithm,
+            argon2_memory_kib: 1024,
+            argon2_iterations: 1,
+            argon2_parallelism: 1,
+            bcrypt_cost: 4,
+        }
+    }
+
+    #[test]
+    fn test_argon2id_hash_and_verify() {
+        let hasher = PasswordHasher::new(test_config(PasswordAlgorithm::Argon2id));
+        let hash = hasher.hash("correct horse").unwrap();
+
+        assert!(hash.starts_with("$argon2id$v=19$m=1024,t=1,p=1$"));
+        assert!(hasher.verify("correct horse", &hash));
+        assert!(!hasher.verify("wrong horse", &hash));
+        assert!(!hasher.needs_rehash(&hash));
+        assert_ne!(hash, hasher.hash("correct horse").unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_blocking_variants() {
+        let hasher = PasswordHasher::new(test_config(PasswordAlgorithm::Bcrypt));
+        let hash = hasher.hash_blocking("correct horse").await.unwrap();
+
+        assert!(hasher.verify_blocking("correct horse", &hash).await);
+        assert!(!hasher.verify_blocking("wrong horse", &hash).await);
+        assert!(!hasher.verify_dummy_blocking("correct horse").await);
+    }
+
+    #[test]
+    fn test_legacy_bcrypt_needs_rehash() {
+        let hasher = PasswordHasher::new(test_config(PasswordAlgorithm::Argon2id));
+
+        assert!(hasher.verify("password", LEGACY_BCRYPT_HASH));
+        assert!(!hasher.verify("admin123", LEGACY_BCRYPT_HASH));
+        assert!(hasher.needs_rehash(LEGACY_BCRYPT_HASH));
+    }
+
+    #[test]
+    fn test_changed_parameters_need_rehash() {
+        let hash = PasswordHasher::new(test_config(PasswordAlgorithm::Argon2id)).hash("password").unwrap();
+        let stronger = PasswordHasher::new(PasswordConfig {
+            argon2_memory_kib: 2048,
+            ..test_config(PasswordAlgorithm::Argon2id)
+        });
+
+        assert!(stronger.verify("password", &hash));
+        assert!(stronger.needs_rehash(&hash));
+
+        let bcrypt = PasswordHasher::new(test_config(PasswordAlgorithm::Bcrypt));
+        assert!(bcrypt.needs_rehash(&hash));
+        assert!(bcrypt.needs_rehash(LEGACY_BCRYPT_HASH));
+        assert!(!bcrypt.needs_rehash(&bcrypt.hash("password").unwrap()));
+    }
+
+    #[test]
+    fn test_unrecognized_hash_rejected() {
+        let hasher = PasswordHasher::new(test_config(PasswordAlgorithm::Argon2id));
+
+        assert!(!hasher.verify("", ""));
+        assert!(!hasher.verify("password", "$argon2i$v=19$m=1024,t=1,p=1$c29tZXNhbHQ$RdescudvJCsgt3ub+b+dWRWJTmaaJObG"));
+        assert!(!hasher.verify("password", "plaintext"));
+    }
+
+    #[test]
+    fn test_dummy_verification_uses_configured_algorithm() {
+        let hasher = PasswordHasher::new(test_config(PasswordAlgorithm::Argon2id));
+        assert!(hasher.dummy_hash.starts_with("$argon2id$v=19$m=1024,t=1,p=1$"));
+        assert!(!hasher.verify_dummy("password"));
+
+        let bcrypt = PasswordHasher::new(test_config(PasswordAlgorithm::Bcrypt));
+        assert!(bcrypt.dummy_hash.starts_with("$2b$04$"));
+        assert!(!bcrypt.verify_dummy(""));
+    }
+}
@@ -0,0 +1,27 @@
+use crate::models::admin_stats::{AdminStats, StatsRange};
+use crate::cache::{RedisPool, cache_key};
+use tracing::debug;
+
+pub struct AdminStatsCache {
+    redis: RedisPool,
+}
+
+impl AdminStatsCache {
+    pub fn new(redis: RedisPool) -> Self {
+        Self { redis }
+    }
+
+    // 缓存统计结果
+    pub async fn cache_stats(&self, stats: &AdminStats) -> Result<(), redis::RedisError> {
+        let key = cache_key("admin_stats", &stats.range.cache_id());
+        debug!("Caching admin stats for range: {}", stats.range.cache_id());
+        self.redis.set(&key, stats, self.redis.ttl().admin_stats).await
+    }
+
+    // 获取缓存的统计结果
+    pub async fn get_stats(&self, range: &StatsRange) -> Result<Option<AdminStats>, redis::RedisError> {
+        let key = cache_key("admin_stats", &range.cache_id());
+        debug!("Getting cached admin stats for range: {}", range.cache_id());
+        self.redis.get(&key).await
+    }
+}
@@ -0,0 +1,29 @@
+use chrono::NaiveDate;
+use crate::cache::{CacheCategory, CacheKey, RedisPool};
+use tracing::debug;
+
+/// 接口每日配额计数：每个分组、调用方、UTC 日期一个计数器，当天结束后自动过期
+pub struct ApiQuotaCache {
+    redis: RedisPool,
+}
+
+impl ApiQuotaCache {
+    pub fn new(redis: RedisPool) -> Self {
+        Self { redis }
+    }
+
+    fn key(group: &str, caller: &str, day: NaiveDate) -> CacheKey {
+        CacheCategory::ApiQuota.key(format!("{}:{}:{}", group, caller, day.format("%Y%m%d")))
+    }
+
+    // 计入一次请求，返回当天已用次数（含本次）；首次计数时设置到周期结束的过期时间
+    pub async fn consume(&self, group: &str, caller: &str, day: NaiveDate, ttl_seconds: usize) -> Result<u64, redis::RedisError> {
+        let key = Self::key(group, caller, day);
+        let used = self.redis.increment(&key, 1).await?;
+        if used == 1 {
+            debug!("Starting quota window for {}", key);
+            self.redis.expire(&key, ttl_seconds).await?;
+        }
+        Ok(used.max(0) as u64)
+    }
+}
@@ -0,0 +1,42 @@
+use crate::models::auth::AvailabilityField;
+use crate::cache::{RedisPool, cache_key};
+use tracing::debug;
+
+/// 用户名/邮箱可用性的短期负缓存：只缓存"不存在"的查询结果，
+/// 注册表单输入时的连续查询不必每次访问数据库；提交注册时仍以数据库为准
+pub struct AvailabilityCache {
+    redis: RedisPool,
+}
+
+impl AvailabilityCache {
+    pub fn new(redis: RedisPool) -> Self {
+        Self { redis }
+    }
+
+    // 邮箱不区分大小写，用户名区分大小写（与数据库查询一致）
+    fn key(field: AvailabilityField, value: &str) -> String {
+        let category = format!("available_{}", field.as_str());
+        match field {
+            AvailabilityField::Username => cache_key(&category, value),
+            AvailabilityField::Email => cache_key(&category, &value.to_lowercase()),
+        }
+    }
+
+    // 最近是否确认过该值未被占用
+    pub async fn is_known_available(&self, field: AvailabilityField, value: &str) -> Result<bool, redis::RedisError> {
+        debug!("Checking availability cache for {}: {}", field.as_str(), value);
+        self.redis.exists(&Self::key(field, value)).await
+    }
+
+    // 记录该值未被占用
+    pub async fn mark_available(&self, field: AvailabilityField, value: &str) -> Result<(), redis::RedisError> {
+        debug!("Caching availability for {}: {}", field.as_str(), value);
+        self.redis.set(&Self::key(field, value), &true, self.redis.ttl().availability).await
+    }
+
+    // 注册或修改资料后清除对应的负缓存
+    pub async fn invalidate(&self, field: AvailabilityField, value: &str) -> Result<bool, redis::RedisError> {
+        debug!("Invalidating availability cache for {}: {}", field.as_str(), value);
+        self.redis.delete(&Self::key(field, value)).await
+    }
+}
@@ -0,0 +1,31 @@
+use crate::models::client_capabilities::ClientCapabilities;
+use crate::cache::{RedisPool, cache_key};
+use tracing::debug;
+
+/// 按会话保存客户端声明的路由指令能力，有效期与会话缓存一致
+pub struct ClientCapabilitiesCache {
+    redis: RedisPool,
+}
+
+impl ClientCapabilitiesCache {
+    pub fn new(redis: RedisPool) -> Self {
+        Self { redis }
+    }
+
+    // 缓存键只使用令牌摘要，与会话缓存一致
+    fn key(&self, session_token: &str) -> String {
+        cache_key("client_capabilities", &self.redis.token_digest(session_token))
+    }
+
+    // 获取会话声明的能力
+    pub async fn get(&self, session_token: &str) -> Result<Option<ClientCapabilities>, redis::RedisError> {
+        debug!("Getting client capabilities for session");
+        self.redis.get(&self.key(session_token)).await
+    }
+
+    // 保存会话声明的能力
+    pub async fn set(&self, session_token: &str, capabilities: &ClientCapabilities) -> Result<(), redis::RedisError> {
+        debug!("Caching client capabilities for session: {:?}", capabilities.commands);
+        self.redis.set(&self.key(session_token), capabilities, self.redis.ttl().user_session).await
+    }
+}
@@ -1,8 +1,9 @@
 use uuid::Uuid;
 use serde::{Serialize, Deserialize};
-use crate::models::user_data::UserData;
-use crate::cache::{RedisPool, cache_key, ttl};
+use crate::models::{upload::Attachment, user_data::{UserData, UserDataSearchResult, UserDataStatus}};
+use crate::cache::{RedisPool, CacheCategory, CacheKey, refresh::CacheRead};
 use tracing::{debug, info};
+use sha1::Digest;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CachedUserData {
@@ -11,6 +12,12 @@ pub struct CachedUserData {
     pub email: String,
     pub phone: Option<String>,
     pub message: Option<String>,
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
+    #[serde(default)]
+    pub status: UserDataStatus,
+    #[serde(default)]
+    pub version: i32,
 }
 
 impl From<UserData> for CachedUserData {
@@ -21,6 +28,9 @@ impl From<UserData> for CachedUserData {
             email: data.email,
             phone: data.phone,
             message: data.message,
+            attachments: data.attachments,
+            status: data.status,
+            version: data.version,
         }
     }
 }
@@ -36,41 +46,41 @@ impl DataCache {
 
     // 缓存单个用户数据
     pub async fn cache_user_data(&self, data: &UserData) -> Result<(), redis::RedisError> {
-        let key = cache_key("user_data", &data.id.to_string());
+        let key = CacheCategory::UserData.key(data.id);
         let cached_data = CachedUserData::from(data.clone());
         
         debug!("Caching user data for id: {}", data.id);
-        self.redis.set(&key, &cached_data, ttl::USER_DATA).await
+        self.redis.set(&key, &cached_data, self.redis.ttl().user_data).await
     }
 
     // 获取单个用户数据
     pub async fn get_user_data(&self, data_id: Uuid) -> Result<Option<CachedUserData>, redis::RedisError> {
-        let key = cache_key("user_data", &data_id.to_string());
+        let key = CacheCategory::UserData.key(data_id);
         debug!("Getting cached user data for id: {}", data_id);
         self.redis.get(&key).await
     }
 
     // 缓存所有用户数据列表
     pub async fn cache_all_user_data(&self, data_list: &[UserData]) -> Result<(), redis::RedisError> {
-        let key = cache_key("all_user_data", "list");
+        let key = CacheCategory::AllUserData.key("list");
         let cached_data: Vec<CachedUserData> = data_list.iter()
             .map(|data| CachedUserData::from(data.clone()))
             .collect();
         
         debug!("Caching all user data list ({} items)", data_list.len());
-        self.redis.set(&key, &cached_data, ttl::USER_DATA).await
+        self.redis.set_refreshable(&key, &cached_data, self.redis.ttl().user_data).await
     }
 
-    // 获取所有用户数据列表
-    pub async fn get_all_user_data(&self) -> Result<Option<Vec<CachedUserData>>, redis::RedisError> {
-        let key = cache_key("all_user_data", "list");
+    // 获取所有用户数据列表，软过期后由一个请求刷新
+    pub async fn get_all_user_data(&self) -> Result<CacheRead<Vec<CachedUserData>>, redis::RedisError> {
+        let key = CacheCategory::AllUserData.key("list");
         debug!("Getting cached all user data list");
-        self.redis.get(&key).await
+        self.redis.get_refreshable(&key).await
     }
 
     // 删除单个用户数据缓存
     pub async fn invalidate_user_data(&self, data_id: Uuid) -> Result<(), redis::RedisError> {
-        let key = cache_key("user_data", &data_id.to_string());
+        let key = CacheCategory::UserData.key(data_id);
         debug!("Invalidating user data cache for id: {}", data_id);
         self.redis.delete(&key).await?;
         
@@ -80,12 +90,54 @@ impl DataCache {
 
     // 删除所有用户数据列表缓存
     pub async fn invalidate_all_user_data(&self) -> Result<(), redis::RedisError> {
-        let key = cache_key("all_user_data", "list");
+        let key = CacheCategory::AllUserData.key("list");
         debug!("Invalidating all user data list cache");
         self.redis.delete(&key).await?;
         Ok(())
     }
 
+    // 缓存搜索结果（按规范化后的搜索词和数量）
+    pub async fn cache_search_results(&self, normalized_query: &str, limit: i64, result: &UserDataSearchResult) -> Result<(), redis::RedisError> {
+        let key = Self::search_key(normalized_query, limit);
+        debug!("Caching user data search results for: {}", normalized_query);
+        self.redis.set_refreshable(&key, result, self.redis.ttl().user_data).await
+    }
+
+    // 获取缓存的搜索结果，软过期后由一个请求刷新
+    pub async fn get_search_results(&self, normalized_query: &str, limit: i64) -> Result<CacheRead<UserDataSearchResult>, redis::RedisError> {
+        let key = Self::search_key(normalized_query, limit);
+        debug!("Getting cached user data search results for: {}", normalized_query);
+        self.redis.get_refreshable(&key).await
+    }
+
+    // 删除所有搜索结果缓存
+    pub async fn invalidate_search_results(&self) -> Result<u64, redis::RedisError> {
+        let pattern = CacheCategory::UserDataSearch.pattern();
+        debug!("Invalidating user data search cache");
+        self.redis.delete_pattern(&pattern).await
+    }
+
+    // 记录邮箱的提交，窗口期内已有提交时返回 false
+    pub async fn claim_submission(&self, email: &str, window_secs: u64) -> Result<bool, redis::RedisError> {
+        self.redis.set_nx(&Self::submission_key(email), &chrono::Utc::now(), window_secs as usize).await
+    }
+
+    // 保存失败时释放提交记录，允许立即重试
+    pub async fn release_submission(&self, email: &str) -> Result<(), redis::RedisError> {
+        self.redis.delete(&Self::submission_key(email)).await.map(|_| ())
+    }
+
+    // 邮箱不区分大小写，键中只保存摘要
+    fn submission_key(email: &str) -> CacheKey {
+        let digest = sha1::Sha1::digest(email.to_lowercase().as_bytes());
+        CacheCategory::UserDataSubmission.key(hex::encode(digest))
+    }
+
+    fn search_key(normalized_query: &str, limit: i64) -> CacheKey {
+        let digest = sha1::Sha1::digest(normalized_query.as_bytes());
+        CacheCategory::UserDataSearch.key(format!("{}:{}", limit, hex::encode(digest)))
+    }
+
     // 预热缓存 - 用于系统启动时预加载常用数据
     pub async fn warm_up_cache(&self, data_list: &[UserData]) -> Result<(), redis::RedisError> {
         info!("Starting cache warm-up for user data");
@@ -136,8 +188,8 @@ impl DataCache {
 
     // 获取缓存统计信息
     pub async fn get_cache_stats(&self) -> Result<CacheStats, redis::RedisError> {
-        let user_data_pattern = cache_key("user_data", "*");
-        let all_data_key = cache_key("all_user_data", "list");
+        let user_data_pattern = CacheCategory::UserData.pattern();
+        let all_data_key = CacheCategory::AllUserData.key("list");
         
         let user_data_keys = self.redis.keys(&user_data_pattern).await?;
         let has_all_data = self.redis.exists(&all_data_key).await?;
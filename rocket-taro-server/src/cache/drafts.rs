@@ -0,0 +1,42 @@
+use uuid::Uuid;
+use crate::models::draft::FormDraft;
+use crate::cache::{RedisPool, cache_key};
+use tracing::debug;
+
+pub struct DraftCache {
+    redis: RedisPool,
+}
+
+impl DraftCache {
+    pub fn new(redis: RedisPool) -> Self {
+        Self { redis }
+    }
+
+    fn key(user_id: Uuid, form_key: &str) -> String {
+        cache_key("form_draft", &format!("{}:{}", user_id, form_key))
+    }
+
+    // 保存草稿，每次保存都会重置过期时间
+    pub async fn set(&self, user_id: Uuid, draft: &FormDraft) -> Result<(), redis::RedisError> {
+        debug!("Saving form draft {} for user: {}", draft.form_key, user_id);
+        self.redis.set(&Self::key(user_id, &draft.form_key), draft, self.redis.ttl().form_draft).await
+    }
+
+    // 获取草稿
+    pub async fn get(&self, user_id: Uuid, form_key: &str) -> Result<Option<FormDraft>, redis::RedisError> {
+        debug!("Getting form draft {} for user: {}", form_key, user_id);
+        self.redis.get(&Self::key(user_id, form_key)).await
+    }
+
+    // 删除草稿，返回是否存在
+    pub async fn delete(&self, user_id: Uuid, form_key: &str) -> Result<bool, redis::RedisError> {
+        debug!("Deleting form draft {} for user: {}", form_key, user_id);
+        self.redis.delete(&Self::key(user_id, form_key)).await
+    }
+
+    // 用户是否有任何草稿
+    pub async fn has_any(&self, user_id: Uuid) -> Result<bool, redis::RedisError> {
+        let keys = self.redis.keys(&Self::key(user_id, "*")).await?;
+        Ok(!keys.is_empty())
+    }
+}
@@ -0,0 +1,219 @@
+use chrono::{DateTime, Utc};
+use redis::{ErrorKind, RedisError};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use crate::config::CacheBreakerConfig;
+use crate::database::health::CircuitState;
+
+/// Redis 健康状态快照，用于 /readyz
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheHealthSnapshot {
+    pub state: CircuitState,
+    /// 当前统计窗口内的请求数和连接类错误数
+    pub requests: u32,
+    pub errors: u32,
+    /// 最近一次连接类错误
+    pub last_error: Option<String>,
+    /// 熔断开始时间
+    pub opened_at: Option<DateTime<Utc>>,
+}
+
+struct BreakerState {
+    state: CircuitState,
+    window_started: Instant,
+    requests: u32,
+    errors: u32,
+    last_error: Option<String>,
+    opened_at: Option<DateTime<Utc>>,
+    /// 熔断打开或探测开始的时间，用于判断冷却是否结束
+    changed_at: Instant,
+}
+
+/// Redis 熔断器：按错误率熔断，RedisPool 每次访问前检查并记录结果，请求守卫和 /readyz 读取
+#[derive(Clone)]
+pub struct CacheHealth {
+    config: Arc<CacheBreakerConfig>,
+    inner: Arc<Mutex<BreakerState>>,
+}
+
+impl CacheHealth {
+    pub fn new(config: CacheBreakerConfig) -> Self {
+        let now = Instant::now();
+        Self {
+            config: Arc::new(config),
+            inner: Arc::new(Mutex::new(BreakerState {
+                state: CircuitState::Closed,
+                window_started: now,
+                requests: 0,
+                errors: 0,
+                last_error: None,
+                opened_at: None,
+                changed_at: now,
+            })),
+        }
+    }
+
+    /// Redis 是否可用（熔断器关闭），守卫据此跳过缓存直接访问数据库
+    pub fn is_available(&self) -> bool {
+        self.inner.lock().unwrap().state == CircuitState::Closed
+    }
+
+    pub fn snapshot(&self) -> CacheHealthSnapshot {
+        let state = self.inner.lock().unwrap();
+        CacheHealthSnapshot {
+            state: state.state,
+            requests: state.requests,
+            errors: state.errors,
+            last_error: state.last_error.clone(),
+            opened_at: state.opened_at,
+        }
+    }
+
+    /// 是否允许访问 Redis：熔断期间拒绝，冷却结束后放行一个探测请求（探测未完成时冷却结束再放行下一个）
+    pub fn allow(&self) -> bool {
+        if !self.config.enabled {
+            return true;
+        }
+        let mut state = self.inner.lock().unwrap();
+        match state.state {
+            CircuitState::Closed => true,
+            CircuitState::Open | CircuitState::HalfOpen => {
+                if state.changed_at.elapsed() < self.cooldown() {
+                    return false;
+                }
+                state.state = CircuitState::HalfOpen;
+                state.changed_at = Instant::now();
+                true
+            }
+        }
+    }
+
+    /// 记录命令结果，只有连接类错误计入错误率
+    pub fn record<T>(&self, result: &Result<T, RedisError>) {
+        match result {
+            Err(e) if is_outage(e) => self.record_error(e.to_string()),
+            _ => self.record_success(),
+        }
+    }
+
+    fn record_success(&self) {
+        let mut state = self.inner.lock().unwrap();
+        if state.state != CircuitState::Closed {
+            info!("Redis reachable again, closing cache circuit breaker");
+            state.state = CircuitState::Closed;
+            state.opened_at = None;
+            state.window_started = Instant::now();
+            state.requests = 0;
+            state.errors = 0;
+        }
+        self.roll_window(&mut state);
+        state.requests += 1;
+    }
+
+    fn record_error(&self, error: String) {
+        let mut state = self.inner.lock().unwrap();
+        self.roll_window(&mut state);
+        state.requests += 1;
+        state.errors += 1;
+        state.last_error = Some(error);
+
+        let tripped = match state.state {
+            // 探测失败，重新熔断
+            CircuitState::HalfOpen => true,
+            CircuitState::Closed => {
+                state.requests >= self.config.min_requests.max(1)
+                    && f64::from(state.errors) / f64::from(state.requests) >= self.config.error_rate
+            }
+            CircuitState::Open => false,
+        };
+        if tripped && self.config.enabled {
+            if state.state == CircuitState::Closed {
+                warn!(
+                    requests = state.requests,
+                    errors = state.errors,
+                    "Redis error rate exceeded threshold, opening cache circuit breaker"
+                );
+                state.opened_at = Some(Utc::now());
+            }
+            state.state = CircuitState::Open;
+            state.changed_at = Instant::now();
+        }
+    }
+
+    // 统计窗口结束后重新计数
+    fn roll_window(&self, state: &mut BreakerState) {
+        if state.window_started.elapsed() >= Duration::from_secs(self.config.window_secs.max(1)) {
+            state.window_started = Instant::now();
+            state.requests = 0;
+            state.errors = 0;
+        }
+    }
+
+    fn cooldown(&self) -> Duration {
+        Duration::from_secs(self.config.open_secs)
+    }
+}
+
+/// 熔断打开时 RedisPool 返回的错误
+pub fn circuit_open_error() -> RedisError {
+    RedisError::from((ErrorKind::IoError, "Redis circuit breaker is open"))
+}
+
+// 连接断开、超时、集群或主节点不可用等表示 Redis 不可达的错误
+fn is_outage(e: &RedisError) -> bool {
+    e.is_io_error()
+        || e.is_connection_dropped()
+        || e.is_connection_refusal()
+        || e.is_timeout()
+        || matches!(e.kind(), ErrorKind::ClusterDown | ErrorKind::MasterDown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn io_error() -> Result<(), RedisError> {
+        Err(RedisError::from(std::io::Error::new(std::io::ErrorKind::ConnectionReset, "reset")))
+    }
+
+    #[test]
+    fn test_breaker_opens_on_error_rate() {
+        let health = CacheHealth::new(CacheBreakerConfig { min_requests: 4, open_secs: 0, ..CacheBreakerConfig::default() });
+
+        health.record(&Ok::<_, RedisError>(()));
+        health.record(&Ok::<_, RedisError>(()));
+        health.record(&io_error());
+        assert!(health.is_available());
+        // 非连接类错误不计入错误率
+        health.record(&Err::<(), _>(RedisError::from((ErrorKind::TypeError, "bad type"))));
+        assert!(health.is_available());
+
+        health.record(&io_error());
+        health.record(&io_error());
+        let snapshot = health.snapshot();
+        assert_eq!(snapshot.state, CircuitState::Open);
+        assert_eq!(snapshot.errors, 3);
+        assert!(snapshot.opened_at.is_some());
+
+        // 冷却结束后放行探测请求，探测失败重新熔断，成功则恢复
+        assert!(health.allow());
+        assert_eq!(health.snapshot().state, CircuitState::HalfOpen);
+        health.record(&io_error());
+        assert_eq!(health.snapshot().state, CircuitState::Open);
+        assert!(health.allow());
+        health.record(&Ok::<_, RedisError>(()));
+        assert!(health.is_available());
+        assert!(health.snapshot().opened_at.is_none());
+    }
+
+    #[test]
+    fn test_open_breaker_rejects_during_cooldown() {
+        let health = CacheHealth::new(CacheBreakerConfig { min_requests: 1, open_secs: 60, ..CacheBreakerConfig::default() });
+        health.record(&io_error());
+        assert!(!health.is_available());
+        assert!(!health.allow());
+    }
+}
@@ -0,0 +1,226 @@
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::cache::{RedisPool, cache_key};
+use crate::models::auth::LoginResponse;
+use crate::models::order::Order;
+use crate::models::payment::PaymentOrderResult;
+use crate::models::response::ApiResponse;
+use crate::models::user_data::UserData;
+use crate::models::wx_auth::WxLoginResponse;
+
+/// 幂等记录：处理中的请求只有指纹，完成后保存原始响应
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IdempotencyRecord {
+    fingerprint: String,
+    response: Option<serde_json::Value>,
+    /// 响应中签发的会话，会话令牌不保存，重放时为该会话重新下发令牌
+    #[serde(default)]
+    session_id: Option<Uuid>,
+}
+
+/// 幂等检查结果
+pub enum IdempotencyOutcome<T> {
+    /// 首次请求，继续处理（处理完成后调用 `finish`）
+    Proceed,
+    /// 重复请求，返回首次请求的响应；session_id 为首次请求签发的会话
+    Replay {
+        response: Box<ApiResponse<T>>,
+        session_id: Option<Uuid>,
+    },
+    /// 同一个 Key 的请求仍在处理中
+    InProgress,
+    /// 同一个 Key 被用于内容不同的请求
+    Mismatch,
+}
+
+impl<T> IdempotencyOutcome<T> {
+    /// 将无法继续处理的情况转换为错误响应（Proceed/Replay 由调用方处理）
+    pub fn into_rejection(self) -> ApiResponse<T> {
+        match self {
+            IdempotencyOutcome::Replay { response, .. } => *response,
+            IdempotencyOutcome::Mismatch => ApiResponse::error("Idempotency-Key 已用于其他请求"),
+            IdempotencyOutcome::Proceed | IdempotencyOutcome::InProgress => {
+                ApiResponse::error("请求正在处理中，请勿重复提交")
+            }
+        }
+    }
+}
+
+/// 保存幂等响应前去除不应写入 Redis 的字段（如会话令牌），重放时由调用方补回
+pub trait Redact {
+    /// data 为序列化后的响应数据，默认原样保存
+    fn redact(_data: &mut serde_json::Value) {}
+}
+
+impl Redact for Order {}
+impl Redact for PaymentOrderResult {}
+impl Redact for UserData {}
+/// 注册、微信登录的会话令牌不保存，重放时为记录中的会话重新下发令牌
+impl Redact for LoginResponse {
+    fn redact(data: &mut serde_json::Value) {
+        remove_session_token(data);
+    }
+}
+
+impl Redact for WxLoginResponse {
+    fn redact(data: &mut serde_json::Value) {
+        remove_session_token(data);
+    }
+}
+
+fn remove_session_token(data: &mut serde_json::Value) {
+    if let Some(data) = data.as_object_mut() {
+        data.remove("session_token");
+    }
+}
+
+/// 基于 Redis 的 Idempotency-Key 存储
+pub struct IdempotencyStore {
+    redis: RedisPool,
+    key: String,
+    fingerprint: String,
+    ttl: Option<usize>,
+}
+
+impl IdempotencyStore {
+    /// scope 区分不同接口（可包含用户ID），request 为请求体，用于计算指纹；
+    /// 请求体可能包含密码，指纹使用以 `cache.token_key` 为密钥的 HMAC，Redis 中的记录无法离线还原请求体
+    pub fn new<B: Serialize>(redis: RedisPool, scope: &str, idempotency_key: &str, request: &B) -> Self {
+        let body = serde_json::to_string(request).unwrap_or_default();
+        let fingerprint = redis.token_digest(&body);
+        Self {
+            redis,
+            key: cache_key("idempotency", &format!("{}:{}", scope, idempotency_key)),
+            fingerprint,
+            ttl: None,
+        }
+    }
+
+    /// 完成后的响应保留时长，默认为 `cache.ttl.idempotency`
+    pub fn with_ttl(mut self, ttl: usize) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// 占用 Key 或读取已保存的响应；Redis 不可用时放行请求
+    pub async fn begin<T: DeserializeOwned>(&self) -> IdempotencyOutcome<T> {
+        let pending = IdempotencyRecord {
+            fingerprint: self.fingerprint.clone(),
+            response: None,
+            session_id: None,
+        };
+
+        match self.redis.set_nx(&self.key, &pending, self.redis.ttl().idempotency_lock).await {
+            Ok(true) => return IdempotencyOutcome::Proceed,
+            Ok(false) => {}
+            Err(e) => {
+                warn!("Idempotency check unavailable, processing request anyway: {}", e);
+                return IdempotencyOutcome::Proceed;
+            }
+        }
+
+        let existing: Option<IdempotencyRecord> = self.redis.get(&self.key).await.unwrap_or(None);
+        let Some(existing) = existing else {
+            // 记录恰好过期，按首次请求处理
+            return IdempotencyOutcome::Proceed;
+        };
+
+        if existing.fingerprint != self.fingerprint {
+            return IdempotencyOutcome::Mismatch;
+        }
+
+        match existing.response.map(serde_json::from_value::<ApiResponse<T>>) {
+            Some(Ok(response)) => {
+                debug!("Replaying stored response for {}", self.key);
+                IdempotencyOutcome::Replay { response: Box::new(response), session_id: existing.session_id }
+            }
+            Some(Err(e)) => {
+                warn!("Stored idempotent response is unreadable for {}: {}", self.key, e);
+                IdempotencyOutcome::InProgress
+            }
+            None => IdempotencyOutcome::InProgress,
+        }
+    }
+
+    /// 请求处理完成：成功的响应去除敏感字段后保存下来供重放，失败时释放 Key 允许客户端重试
+    pub async fn finish<T: Serialize + Redact>(&self, response: &ApiResponse<T>) {
+        self.finish_with_session(response, None).await
+    }
+
+    /// 同 `finish`，同时记录响应中签发的会话，重放时由调用方为同一个会话重新下发令牌
+    pub async fn finish_with_session<T: Serialize + Redact>(&self, response: &ApiResponse<T>, session_id: Option<Uuid>) {
+        if response.data.is_none() {
+            if let Err(e) = self.redis.delete(&self.key).await {
+                warn!("Failed to release idempotency key {}: {}", self.key, e);
+            }
+            return;
+        }
+
+        let record = IdempotencyRecord {
+            fingerprint: self.fingerprint.clone(),
+            response: stored_response(response),
+            session_id,
+        };
+        let ttl = self.ttl.unwrap_or(self.redis.ttl().idempotency);
+        if let Err(e) = self.redis.set(&self.key, &record, ttl).await {
+            warn!("Failed to store idempotent response for {}: {}", self.key, e);
+        }
+    }
+}
+
+/// 要保存的响应，data 字段按类型去除敏感字段
+fn stored_response<T: Serialize + Redact>(response: &ApiResponse<T>) -> Option<serde_json::Value> {
+    let mut value = serde_json::to_value(response).ok()?;
+    if let Some(data) = value.get_mut("data") {
+        T::redact(data);
+    }
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Serialize)]
+    struct Ticket {
+        id: u32,
+        secret: String,
+    }
+
+    impl Redact for Ticket {
+        fn redact(data: &mut serde_json::Value) {
+            if let Some(data) = data.as_object_mut() {
+                data.remove("secret");
+            }
+        }
+    }
+
+    #[test]
+    fn test_stored_response_is_redacted() {
+        let stored = stored_response(&ApiResponse::success(Ticket { id: 7, secret: "s3cr3t".to_string() })).unwrap();
+        assert_eq!(stored["data"], json!({ "id": 7 }));
+        assert!(!stored.to_string().contains("s3cr3t"));
+    }
+
+    #[test]
+    fn test_login_response_drops_session_token() {
+        let mut data = json!({
+            "user": {
+                "id": uuid::Uuid::new_v4(), "username": "alice", "email": "alice@example.com",
+                "full_name": null, "avatar_url": null, "is_admin": false, "is_guest": false,
+                "wx_openid": null, "has_wx_session": false, "display_name": "alice", "version": 1
+            },
+            "session_token": "plaintext-token",
+            "expires_at": "2026-10-16T08:00:00Z"
+        });
+        LoginResponse::redact(&mut data);
+        assert!(data.get("session_token").is_none());
+
+        // 重放时能读取不含令牌的记录
+        let replayed: LoginResponse = serde_json::from_value(data).unwrap();
+        assert_eq!(replayed.session_token, "");
+    }
+}
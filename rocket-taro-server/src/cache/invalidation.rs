@@ -0,0 +1,52 @@
+use redis::RedisResult;
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::cache::{RedisPool, CacheCategory, session::SessionCache, user::CachedUser};
+use crate::models::cache_invalidation::CacheTarget;
+
+/// 按对象精确清除缓存，返回实际删除的键数量
+pub struct CacheInvalidator {
+    redis: RedisPool,
+}
+
+impl CacheInvalidator {
+    pub fn new(redis: RedisPool) -> Self {
+        Self { redis }
+    }
+
+    pub async fn invalidate(&self, target: &CacheTarget) -> RedisResult<u64> {
+        debug!("Invalidating cache for {}", target.describe());
+        match target {
+            CacheTarget::User(user_id) => self.user(*user_id).await,
+            CacheTarget::Username(username) => self.redis.delete_keys(&[CacheCategory::Username.key(username)]).await,
+            CacheTarget::Session(session_id) => SessionCache::new(self.redis.clone()).purge_session(*session_id).await,
+            CacheTarget::UserData(data_id) => self.user_data(*data_id).await,
+            CacheTarget::Category(category) => self.redis.delete_pattern(&category.pattern()).await,
+        }
+    }
+
+    // 用户信息、用户名映射、设置和会话（会话中保存了用户信息副本）
+    async fn user(&self, user_id: Uuid) -> RedisResult<u64> {
+        let user_key = CacheCategory::User.key(user_id);
+        let mut keys = vec![CacheCategory::UserSettings.key(user_id)];
+        if let Some(user) = self.redis.get::<CachedUser>(&user_key).await? {
+            keys.push(CacheCategory::Username.key(user.username));
+        }
+        keys.push(user_key);
+
+        let removed = self.redis.delete_keys(&keys).await?;
+        let sessions_removed = SessionCache::new(self.redis.clone()).purge_user_sessions(user_id).await?;
+        Ok(removed + sessions_removed)
+    }
+
+    // 单条用户数据，以及可能包含它的列表和搜索结果
+    async fn user_data(&self, data_id: Uuid) -> RedisResult<u64> {
+        let removed = self.redis.delete_keys(&[
+            CacheCategory::UserData.key(data_id),
+            CacheCategory::AllUserData.key("list"),
+        ]).await?;
+        let search_removed = self.redis.delete_pattern(&CacheCategory::UserDataSearch.pattern()).await?;
+        Ok(removed + search_removed)
+    }
+}
@@ -0,0 +1,165 @@
+use std::fmt;
+use std::ops::Deref;
+
+use super::CACHE_PREFIX;
+
+/// 定义缓存类别：变体与键中使用的类别名一一对应
+macro_rules! cache_categories {
+    ($($(#[doc = $doc:literal])* $variant:ident => $name:literal),* $(,)?) => {
+        /// 缓存类别注册表，键格式为 `rocket_taro:<category>:<id>`
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum CacheCategory {
+            $($(#[doc = $doc])* $variant),*
+        }
+
+        impl CacheCategory {
+            pub const ALL: &'static [CacheCategory] = &[$(CacheCategory::$variant),*];
+
+            pub const fn as_str(self) -> &'static str {
+                match self {
+                    $(CacheCategory::$variant => $name),*
+                }
+            }
+        }
+    };
+}
+
+cache_categories! {
+    /// 管理后台统计
+    AdminStats => "admin_stats",
+    /// 接口每日配额计数
+    ApiQuota => "api_quota",
+    /// 用户数据列表
+    AllUserData => "all_user_data",
+    /// 确认未被占用的邮箱
+    AvailableEmail => "available_email",
+    /// 确认未被占用的用户名
+    AvailableUsername => "available_username",
+    /// 会话声明的路由指令能力
+    ClientCapabilities => "client_capabilities",
+    /// 表单草稿
+    FormDraft => "form_draft",
+    /// 幂等记录
+    Idempotency => "idempotency",
+    /// 登录失败计数
+    LoginFailures => "login_failures",
+    /// 维护模式状态
+    Maintenance => "maintenance",
+    /// 找回密码凭证
+    PasswordRecovery => "password_recovery",
+    /// 在线用户（按平台的有序集合）
+    Presence => "presence",
+    /// 扫码登录票据
+    QrLogin => "qr_login",
+    /// 注册频率计数（按 IP、设备）
+    RegistrationVelocity => "registration_velocity",
+    /// 各平台公共配置快照
+    RemoteConfig => "remote_config",
+    /// 会话ID到会话信息
+    Session => "session",
+    /// 会话最后访问时间
+    SessionAccess => "session_access",
+    /// 访问时间写回数据库的节流标记
+    SessionAccessWrite => "session_access_write",
+    /// 会话绑定告警的节流标记
+    SessionBindingAlert => "session_binding_alert",
+    /// 会话过期提醒队列
+    SessionExpiry => "session_expiry",
+    /// 会话二次验证时间
+    SessionReauth => "session_reauth",
+    /// 令牌摘要到会话信息
+    SessionToken => "session_token",
+    /// 用户信息
+    User => "user",
+    /// 单条用户数据
+    UserData => "user_data",
+    /// 用户数据搜索结果
+    UserDataSearch => "user_data_search",
+    /// 用户数据提交频率限制
+    UserDataSubmission => "user_data_submission",
+    /// 令牌摘要到用户和会话信息
+    UserSession => "user_session",
+    /// 用户设置
+    UserSettings => "user_settings",
+    /// 用户名到用户ID
+    Username => "username",
+}
+
+impl CacheCategory {
+    /// 按类别名查找
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|category| category.as_str() == name)
+    }
+
+    pub fn key(self, identifier: impl fmt::Display) -> CacheKey {
+        CacheKey(format!("{}:{}:{}", CACHE_PREFIX, self.as_str(), identifier))
+    }
+
+    /// 匹配该类别下所有键的模式，用于 KEYS
+    pub fn pattern(self) -> String {
+        format!("{}:{}:*", CACHE_PREFIX, self.as_str())
+    }
+}
+
+impl fmt::Display for CacheCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 完整的缓存键，只能通过 CacheCategory::key 构造
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheKey(String);
+
+impl CacheKey {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for CacheKey {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for CacheKey {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for CacheKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<CacheKey> for String {
+    fn from(key: CacheKey) -> Self {
+        key.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_category_names_are_unique() {
+        let names: HashSet<_> = CacheCategory::ALL.iter().map(|category| category.as_str()).collect();
+        assert_eq!(names.len(), CacheCategory::ALL.len());
+        assert!(names.iter().all(|name| !name.is_empty() && !name.contains([':', '*'])));
+    }
+
+    #[test]
+    fn test_key_format() {
+        assert_eq!(CacheCategory::UserSession.key("abc").as_str(), "rocket_taro:user_session:abc");
+        assert_eq!(CacheCategory::UserData.pattern(), "rocket_taro:user_data:*");
+        assert_eq!(CacheCategory::from_name("user_settings"), Some(CacheCategory::UserSettings));
+        assert_eq!(CacheCategory::from_name("unknown"), None);
+    }
+}
@@ -0,0 +1,136 @@
+use futures_util::StreamExt;
+use moka::sync::Cache;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+use crate::cache::{CACHE_PREFIX, topology};
+use crate::config::{CacheConfig, LocalCacheConfig};
+use crate::metrics::MetricsRegistry;
+
+/// 本地缓存失效通知频道，消息为 JSON 格式的键列表
+pub const INVALIDATION_CHANNEL: &str = "rocket_taro:local_invalidation";
+
+/// 订阅断开后的重连间隔
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// Redis 前的进程内缓存，保存 Redis 中的原始 JSON 值
+pub struct LocalCache {
+    entries: Cache<String, Arc<str>>,
+    categories: Vec<String>,
+    metrics: Option<MetricsRegistry>,
+}
+
+impl LocalCache {
+    pub fn new(config: &LocalCacheConfig) -> Self {
+        Self {
+            entries: Cache::builder()
+                .max_capacity(config.max_entries)
+                .time_to_live(Duration::from_secs(config.ttl_secs.max(1)))
+                .build(),
+            categories: config.categories.clone(),
+            metrics: None,
+        }
+    }
+
+    /// 记录命中和未命中次数（`cache.local_hits` / `cache.local_misses`）
+    pub fn with_metrics(mut self, metrics: MetricsRegistry) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// 键所属类别是否使用本地缓存
+    pub fn covers(&self, key: &str) -> bool {
+        key_category(key).is_some_and(|category| self.categories.iter().any(|c| c == category))
+    }
+
+    pub fn get(&self, key: &str) -> Option<Arc<str>> {
+        let value = self.entries.get(key);
+        if let Some(metrics) = &self.metrics {
+            metrics.increment(if value.is_some() { "cache.local_hits" } else { "cache.local_misses" });
+        }
+        value
+    }
+
+    pub fn insert(&self, key: &str, value: &str) {
+        self.entries.insert(key.to_string(), Arc::from(value));
+    }
+
+    pub fn remove(&self, key: &str) {
+        self.entries.invalidate(key);
+    }
+
+    /// 订阅其他实例的失效通知；订阅中断期间可能错过通知，重连前清空本地缓存
+    pub fn spawn_invalidation_listener(self: &Arc<Self>, config: CacheConfig) {
+        let local = Arc::clone(self);
+        tokio::spawn(async move {
+            loop {
+                if let Err(e) = local.listen(&config).await {
+                    warn!("Local cache invalidation subscription failed: {}", e);
+                }
+                local.entries.invalidate_all();
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        });
+    }
+
+    async fn listen(&self, config: &CacheConfig) -> redis::RedisResult<()> {
+        let client = topology::pubsub_client(config).await?;
+        let mut pubsub = client.get_async_connection().await?.into_pubsub();
+        pubsub.subscribe(INVALIDATION_CHANNEL).await?;
+        info!("Subscribed to local cache invalidation channel");
+
+        let mut messages = pubsub.on_message();
+        while let Some(message) = messages.next().await {
+            let keys = message.get_payload::<String>().ok()
+                .and_then(|payload| serde_json::from_str::<Vec<String>>(&payload).ok());
+            match keys {
+                Some(keys) => {
+                    debug!("Dropping {} local cache entries on remote invalidation", keys.len());
+                    for key in &keys {
+                        self.remove(key);
+                    }
+                }
+                None => warn!("Ignoring malformed local cache invalidation message"),
+            }
+        }
+        Ok(())
+    }
+}
+
+// `rocket_taro:<category>:<id>` 中的类别
+fn key_category(key: &str) -> Option<&str> {
+    key.strip_prefix(CACHE_PREFIX)?.strip_prefix(':')?.split(':').next()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::cache_key;
+
+    #[test]
+    fn test_covers_configured_categories() {
+        let local = LocalCache::new(&LocalCacheConfig::default());
+        assert!(local.covers(&cache_key("user_session", "digest")));
+        assert!(local.covers(&cache_key("user", "id")));
+        assert!(!local.covers(&cache_key("user_settings", "id")));
+        assert!(!local.covers(&cache_key("session_access", "digest")));
+        assert!(!local.covers("other:user:id"));
+    }
+
+    #[test]
+    fn test_get_records_hits_and_misses() {
+        let metrics = MetricsRegistry::new();
+        let local = LocalCache::new(&LocalCacheConfig::default()).with_metrics(metrics.clone());
+        let key = cache_key("user", "id");
+
+        assert!(local.get(&key).is_none());
+        local.insert(&key, "{}");
+        assert_eq!(local.get(&key).as_deref(), Some("{}"));
+        local.remove(&key);
+        assert!(local.get(&key).is_none());
+
+        assert_eq!(metrics.get("cache.local_hits"), 1);
+        assert_eq!(metrics.get("cache.local_misses"), 2);
+    }
+}
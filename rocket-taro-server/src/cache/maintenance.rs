@@ -0,0 +1,43 @@
+use chrono::Utc;
+use crate::models::maintenance::MaintenanceState;
+use crate::cache::{RedisPool, cache_key};
+use tracing::debug;
+
+/// 维护模式开关，设置了结束时间时按剩余时长过期，否则一直有效直到关闭
+pub struct MaintenanceCache {
+    redis: RedisPool,
+}
+
+impl MaintenanceCache {
+    pub fn new(redis: RedisPool) -> Self {
+        Self { redis }
+    }
+
+    fn key() -> String {
+        cache_key("maintenance", "state")
+    }
+
+    // 获取当前维护状态，未开启时返回 None
+    pub async fn get(&self) -> Result<Option<MaintenanceState>, redis::RedisError> {
+        debug!("Getting maintenance state");
+        Ok(self.redis.get::<MaintenanceState>(&Self::key()).await?.filter(|state| state.enabled))
+    }
+
+    // 开启维护模式
+    pub async fn enable(&self, state: &MaintenanceState) -> Result<(), redis::RedisError> {
+        debug!("Enabling maintenance mode until {:?}", state.ends_at);
+        match state.ends_at {
+            Some(ends_at) => {
+                let remaining = (ends_at - Utc::now()).num_seconds().max(1) as usize;
+                self.redis.set(&Self::key(), state, remaining).await
+            }
+            None => self.redis.set_persistent(&Self::key(), state).await,
+        }
+    }
+
+    // 关闭维护模式
+    pub async fn disable(&self) -> Result<bool, redis::RedisError> {
+        debug!("Disabling maintenance mode");
+        self.redis.delete(&Self::key()).await
+    }
+}
@@ -1,12 +1,38 @@
 use rocket::{async_trait, Rocket, Build, fairing::{Fairing, Info, Kind}};
-use tracing::{info, error, debug};
+use std::sync::Arc;
+use tracing::{info, warn, error, debug};
+
+use crate::config::CacheConfig;
+use crate::metrics::MetricsRegistry;
 
 pub mod redis;
 pub mod user;
 pub mod session;
 pub mod data;
+pub mod remote_config;
+pub mod admin_stats;
+pub mod idempotency;
+pub mod topology;
+pub mod availability;
+pub mod capabilities;
+pub mod maintenance;
+pub mod session_expiry;
+pub mod settings;
+pub mod drafts;
+pub mod recovery;
+pub mod qr_login;
+pub mod reauth;
+pub mod invalidation;
+pub mod local;
+pub mod refresh;
+pub mod key;
+pub mod health;
+pub mod presence;
+pub mod api_quota;
+pub mod registration;
 
 pub use redis::RedisPool;
+pub use key::{CacheCategory, CacheKey};
 
 pub struct CacheFairing;
 
@@ -22,33 +48,38 @@ impl Fairing for CacheFairing {
     async fn on_ignite(&self, rocket: Rocket<Build>) -> rocket::fairing::Result {
         info!("Initializing Redis cache connection...");
         
-        // 优先使用环境变量，否则从配置文件读取
-        let redis_url: String = match std::env::var("REDIS_URL") {
-            Ok(url) => {
-                info!("Using Redis URL from environment variable");
-                url
-            }
-            Err(_) => {
-                let figment = rocket.figment();
-                match figment.extract_inner("cache.redis_url") {
-                    Ok(url) => {
-                        info!("Using Redis URL from configuration file");
-                        url
-                    }
-                    Err(e) => {
-                        error!("Failed to read Redis URL from both environment variable and configuration: {}", e);
-                        return Err(rocket);
-                    }
-                }
-            }
-        };
+        // REDIS_URL、REDIS_PASSWORD、CACHE_TOKEN_KEY 环境变量优先于配置文件
+        let config = CacheConfig::from_figment(rocket.figment());
+        // 会话缓存键和幂等请求指纹都依赖 token_key，随机密钥在重启或多实例间不一致，生产环境必须配置
+        if config.token_key.is_none() && rocket.figment().profile() == rocket::Config::RELEASE_PROFILE {
+            error!("cache.token_key (or CACHE_TOKEN_KEY) must be configured in the release profile");
+            return Err(rocket);
+        }
+        debug!("Connecting to Redis, topology: {:?}", config.topology);
 
-        debug!("Connecting to Redis at configured URL");
-        
-        match redis::RedisPool::new(&redis_url).await {
+        match redis::RedisPool::new(&config).await {
             Ok(pool) => {
                 info!("Redis cache connection established successfully");
-                Ok(rocket.manage(pool))
+                let pool = match &config.token_key {
+                    Some(key) => pool.with_token_key(key.as_bytes()),
+                    None => {
+                        warn!("cache.token_key is not configured, using a random key; cached sessions and idempotency records will not be shared across instances or restarts");
+                        pool
+                    }
+                };
+                let pool = if config.local.enabled {
+                    info!("Local cache enabled for categories {:?}, TTL {}s", config.local.categories, config.local.ttl_secs);
+                    let mut local_cache = local::LocalCache::new(&config.local);
+                    if let Some(metrics) = rocket.state::<MetricsRegistry>() {
+                        local_cache = local_cache.with_metrics(metrics.clone());
+                    }
+                    let local_cache = Arc::new(local_cache);
+                    local_cache.spawn_invalidation_listener(config.clone());
+                    pool.with_local_cache(local_cache)
+                } else {
+                    pool
+                };
+                Ok(rocket.manage(pool).manage(config))
             }
             Err(e) => {
                 error!("Failed to establish Redis connection: {}", e);
@@ -61,15 +92,8 @@ impl Fairing for CacheFairing {
 // 缓存键前缀
 pub const CACHE_PREFIX: &str = "rocket_taro";
 
-// 生成缓存键的工具函数
+// 生成缓存键的工具函数；用户、会话和用户数据缓存使用 CacheCategory::key，新增类别应登记到 CacheCategory
 pub fn cache_key(category: &str, identifier: &str) -> String {
     format!("{}:{}:{}", CACHE_PREFIX, category, identifier)
 }
 
-// 常用缓存过期时间（秒）
-pub mod ttl {
-    pub const USER_SESSION: usize = 7 * 24 * 3600; // 7天
-    pub const USER_INFO: usize = 30 * 60; // 30分钟
-    pub const USER_DATA: usize = 10 * 60; // 10分钟
-    pub const LOGIN_ATTEMPTS: usize = 15 * 60; // 15分钟
-}
\ No newline at end of file
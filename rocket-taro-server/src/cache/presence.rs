@@ -0,0 +1,48 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::cache::{CacheCategory, CacheKey, RedisPool};
+use crate::config::Platform;
+use tracing::debug;
+
+// 不区分平台的在线集合
+const ALL_PLATFORMS: &str = "all";
+
+/// 在线用户表：每个平台一个 Redis 有序集合（另有一个不区分平台的集合），成员为用户ID，分数为最后访问时间戳
+pub struct PresenceCache {
+    redis: RedisPool,
+}
+
+impl PresenceCache {
+    pub fn new(redis: RedisPool) -> Self {
+        Self { redis }
+    }
+
+    fn key(platform: Option<Platform>) -> CacheKey {
+        CacheCategory::Presence.key(platform.map_or(ALL_PLATFORMS, |platform| platform.as_str()))
+    }
+
+    // 记录用户在某平台的访问时间，并刷新集合的过期时间
+    pub async fn touch(&self, user_id: Uuid, platform: Platform, seen_at: DateTime<Utc>, key_ttl_secs: usize) -> Result<(), redis::RedisError> {
+        debug!("Recording presence for user: {}", user_id);
+        let member = user_id.to_string();
+        for key in [Self::key(None), Self::key(Some(platform))] {
+            self.redis.zadd(&key, &member, seen_at.timestamp()).await?;
+            self.redis.expire(&key, key_ttl_secs).await?;
+        }
+        Ok(())
+    }
+
+    // 删除最后访问早于 before 的成员
+    pub async fn prune(&self, before: DateTime<Utc>) -> Result<u64, redis::RedisError> {
+        let mut removed = 0;
+        for platform in std::iter::once(None).chain(Platform::ALL.into_iter().map(Some)) {
+            removed += self.redis.zrem_up_to(&Self::key(platform), before.timestamp() - 1).await?;
+        }
+        Ok(removed)
+    }
+
+    // 统计 since 之后有访问的用户数，platform 为 None 时不区分平台（同一用户多端在线只计一次）
+    pub async fn count(&self, platform: Option<Platform>, since: DateTime<Utc>) -> Result<u64, redis::RedisError> {
+        self.redis.zcount_from(&Self::key(platform), since.timestamp()).await
+    }
+}
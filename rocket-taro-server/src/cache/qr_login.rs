@@ -0,0 +1,36 @@
+use chrono::Utc;
+use uuid::Uuid;
+use crate::models::qr_login::QrLoginTicket;
+use crate::cache::{RedisPool, cache_key};
+use tracing::debug;
+
+pub struct QrLoginCache {
+    redis: RedisPool,
+}
+
+impl QrLoginCache {
+    pub fn new(redis: RedisPool) -> Self {
+        Self { redis }
+    }
+
+    fn key(login_id: Uuid) -> String {
+        cache_key("qr_login", &login_id.to_string())
+    }
+
+    // 保存扫码登录记录，有效期截止到记录的过期时间
+    pub async fn set(&self, ticket: &QrLoginTicket) -> Result<(), redis::RedisError> {
+        debug!("Saving QR login {} with status {:?}", ticket.login_id, ticket.status);
+        self.redis.set(&Self::key(ticket.login_id), ticket, ticket.remaining_secs(Utc::now())).await
+    }
+
+    // 获取扫码登录记录
+    pub async fn get(&self, login_id: Uuid) -> Result<Option<QrLoginTicket>, redis::RedisError> {
+        self.redis.get(&Self::key(login_id)).await
+    }
+
+    // 删除扫码登录记录，只有成功删除的一方能领取会话，记录不能重复使用
+    pub async fn take(&self, login_id: Uuid) -> Result<bool, redis::RedisError> {
+        debug!("Consuming QR login {}", login_id);
+        self.redis.delete(&Self::key(login_id)).await
+    }
+}
@@ -0,0 +1,30 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::cache::{RedisPool, cache_key};
+use tracing::debug;
+
+/// 会话最近一次密码验证的时间，用于敏感操作的二次验证
+pub struct ReauthCache {
+    redis: RedisPool,
+}
+
+impl ReauthCache {
+    pub fn new(redis: RedisPool) -> Self {
+        Self { redis }
+    }
+
+    fn key(session_id: Uuid) -> String {
+        cache_key("session_reauth", &session_id.to_string())
+    }
+
+    // 记录会话的密码验证时间
+    pub async fn record(&self, session_id: Uuid, confirmed_at: DateTime<Utc>) -> Result<(), redis::RedisError> {
+        debug!("Recording reauthentication for session: {}", session_id);
+        self.redis.set(&Self::key(session_id), &confirmed_at, self.redis.ttl().session_reauth).await
+    }
+
+    // 获取会话最近一次密码验证时间
+    pub async fn confirmed_at(&self, session_id: Uuid) -> Result<Option<DateTime<Utc>>, redis::RedisError> {
+        self.redis.get(&Self::key(session_id)).await
+    }
+}
@@ -0,0 +1,35 @@
+use crate::models::account_recovery::PasswordRecoveryTicket;
+use crate::cache::{RedisPool, cache_key};
+use tracing::debug;
+
+pub struct PasswordRecoveryCache {
+    redis: RedisPool,
+}
+
+impl PasswordRecoveryCache {
+    pub fn new(redis: RedisPool) -> Self {
+        Self { redis }
+    }
+
+    fn key(token_hash: &str) -> String {
+        cache_key("password_recovery", token_hash)
+    }
+
+    // 保存找回凭据
+    pub async fn set(&self, token_hash: &str, ticket: &PasswordRecoveryTicket) -> Result<(), redis::RedisError> {
+        debug!("Issuing password recovery ticket for user: {}", ticket.user_id);
+        self.redis.set(&Self::key(token_hash), ticket, self.redis.ttl().password_recovery).await
+    }
+
+    // 取出并删除找回凭据，只有成功删除的一方能使用，凭据不能重复使用
+    pub async fn take(&self, token_hash: &str) -> Result<Option<PasswordRecoveryTicket>, redis::RedisError> {
+        let key = Self::key(token_hash);
+        let Some(ticket) = self.redis.get::<PasswordRecoveryTicket>(&key).await? else {
+            return Ok(None);
+        };
+        if !self.redis.delete(&key).await? {
+            return Ok(None);
+        }
+        Ok(Some(ticket))
+    }
+}
@@ -1,62 +1,179 @@
-use redis::{Client, aio::ConnectionManager, AsyncCommands, RedisResult, RedisError};
+use redis::{AsyncCommands, RedisResult, RedisError};
 use serde::{Serialize, Deserialize};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::future::Future;
 use std::sync::Arc;
-use tracing::{error, debug, warn};
+use tracing::{Instrument, debug_span, error, debug, field::Empty, warn};
+
+use crate::config::{CacheConfig, CacheTtlConfig, ListCacheConfig};
+use super::health::{CacheHealth, circuit_open_error};
+use super::local::{INVALIDATION_CHANNEL, LocalCache};
+use super::refresh::{CacheRead, SoftEntry, refresh_lock_key};
+use super::topology::RedisConnection;
 
 #[derive(Clone)]
 pub struct RedisPool {
-    connection: Arc<ConnectionManager>,
+    connection: Arc<RedisConnection>,
+    token_key: Arc<[u8]>,
+    local: Option<Arc<LocalCache>>,
+    lists: Arc<ListCacheConfig>,
+    ttl: Arc<CacheTtlConfig>,
+    health: CacheHealth,
 }
 
 impl RedisPool {
-    pub async fn new(redis_url: &str) -> Result<Self, RedisError> {
-        debug!("Creating Redis client connection");
-        let client = Client::open(redis_url)?;
-        let connection = ConnectionManager::new(client).await?;
-        
+    pub async fn new(config: &CacheConfig) -> Result<Self, RedisError> {
+        debug!("Creating Redis client connection, topology: {:?}", config.topology);
+        let connection = RedisConnection::connect(config).await?;
+
         Ok(RedisPool {
             connection: Arc::new(connection),
+            token_key: Arc::from(rand::random::<[u8; 32]>().as_slice()),
+            local: None,
+            lists: Arc::new(config.lists.clone()),
+            ttl: Arc::new(config.ttl.clone()),
+            health: CacheHealth::new(config.breaker.clone()),
         })
     }
 
+    /// Redis 熔断状态；熔断期间所有命令直接返回错误或降级值，不访问 Redis
+    pub fn health(&self) -> &CacheHealth {
+        &self.health
+    }
+
+    /// Redis 是否可用，熔断期间调用方应跳过缓存
+    pub fn is_available(&self) -> bool {
+        self.health.is_available()
+    }
+
+    // 熔断打开时不获取连接
+    fn connection(&self) -> RedisResult<RedisConnection> {
+        if self.health.allow() {
+            Ok((*self.connection).clone())
+        } else {
+            Err(circuit_open_error())
+        }
+    }
+
+    // 在 redis span 中执行命令，并记录结果用于熔断器统计错误率
+    async fn observe<T>(&self, operation: &'static str, command: impl Future<Output = RedisResult<T>>) -> RedisResult<T> {
+        let span = debug_span!(
+            "redis",
+            otel.name = operation,
+            otel.kind = "client",
+            otel.status_code = Empty,
+            db.system = "redis",
+            db.operation = operation,
+        );
+        let result = command.instrument(span.clone()).await;
+        if result.is_err() {
+            crate::telemetry::record_error(&span);
+        }
+        self.health.record(&result);
+        result
+    }
+
+    /// 各类缓存的过期时间（`[default.cache.ttl]`）
+    pub fn ttl(&self) -> &CacheTtlConfig {
+        &self.ttl
+    }
+
+    /// 在 Redis 前增加进程内缓存，只作用于 LocalCache 覆盖的类别
+    pub fn with_local_cache(mut self, local: Arc<LocalCache>) -> Self {
+        self.local = Some(local);
+        self
+    }
+
+    /// 设置令牌摘要密钥，多实例共享同一 Redis 时必须使用相同的密钥
+    pub fn with_token_key(mut self, token_key: &[u8]) -> Self {
+        self.token_key = Arc::from(token_key);
+        self
+    }
+
+    /// 计算令牌的带密钥摘要（HMAC-SHA256），用于缓存键，避免 Redis 中出现可直接使用的令牌
+    pub fn token_digest(&self, token: &str) -> String {
+        token_digest(&self.token_key, token)
+    }
+
     pub async fn get<T>(&self, key: &str) -> RedisResult<Option<T>>
     where
         T: for<'de> Deserialize<'de>,
     {
         debug!("Getting cache value for key: {}", key);
-        let mut conn = (*self.connection).clone();
-        
-        match conn.get::<_, Option<String>>(key).await {
-            Ok(Some(value)) => {
-                match serde_json::from_str::<T>(&value) {
-                    Ok(data) => Ok(Some(data)),
-                    Err(e) => {
-                        warn!("Failed to deserialize cached data for key {}: {}", key, e);
-                        Ok(None)
+        let local = self.local.as_ref().filter(|local| local.covers(key));
+        let value = match local.and_then(|local| local.get(key)) {
+            Some(value) => value.to_string(),
+            None => match self.get_raw(key).await {
+                Some(value) => {
+                    if let Some(local) = local {
+                        local.insert(key, &value);
                     }
+                    value
                 }
+                None => return Ok(None),
+            },
+        };
+
+        match serde_json::from_str::<T>(&value) {
+            Ok(data) => Ok(Some(data)),
+            Err(e) => {
+                warn!("Failed to deserialize cached data for key {}: {}", key, e);
+                Ok(None)
             }
-            Ok(None) => Ok(None),
+        }
+    }
+
+    async fn get_raw(&self, key: &str) -> Option<String> {
+        let Ok(mut conn) = self.connection() else {
+            return None;
+        };
+
+        match self.observe("GET", conn.get::<_, Option<String>>(key)).await {
+            Ok(value) => value,
             Err(e) => {
                 error!("Redis GET error for key {}: {}", key, e);
-                Ok(None) // 优雅降级，返回None而不是错误
+                None // 优雅降级，返回None而不是错误
             }
         }
     }
 
+    // 写入或删除后丢弃本地副本，并通知其他实例
+    async fn invalidate_local(&self, keys: &[&str]) {
+        let Some(local) = &self.local else {
+            return;
+        };
+        let keys: Vec<&str> = keys.iter().copied().filter(|key| local.covers(key)).collect();
+        if keys.is_empty() {
+            return;
+        }
+        for key in &keys {
+            local.remove(key);
+        }
+
+        let payload = serde_json::to_string(&keys).unwrap_or_default();
+        let Ok(mut conn) = self.connection() else {
+            return;
+        };
+        if let Err(e) = self.observe("PUBLISH", conn.publish::<_, _, ()>(INVALIDATION_CHANNEL, payload)).await {
+            warn!("Failed to publish local cache invalidation for {} keys: {}", keys.len(), e);
+        }
+    }
+
     pub async fn set<T>(&self, key: &str, value: &T, ttl_seconds: usize) -> RedisResult<()>
     where
         T: Serialize,
     {
         debug!("Setting cache value for key: {} with TTL: {}s", key, ttl_seconds);
-        let mut conn = (*self.connection).clone();
         
         match serde_json::to_string(value) {
             Ok(serialized) => {
-                let result: RedisResult<()> = conn.set_ex(key, serialized, ttl_seconds as u64).await;
+                let mut conn = self.connection()?;
+                let result: RedisResult<()> = self.observe("SETEX", conn.set_ex(key, serialized, ttl_seconds as u64)).await;
                 if let Err(e) = &result {
                     error!("Redis SET error for key {}: {}", key, e);
                 }
+                self.invalidate_local(&[key]).await;
                 result
             }
             Err(e) => {
@@ -66,11 +183,102 @@ impl RedisPool {
         }
     }
 
+    /// 写入列表类缓存：过期时间加随机抖动，并记录软过期时间，同时释放刷新锁
+    pub async fn set_refreshable<T>(&self, key: &str, value: &T, ttl_seconds: usize) -> RedisResult<()>
+    where
+        T: Serialize,
+    {
+        let ttl_seconds = self.lists.jittered_ttl(ttl_seconds, rand::random::<f64>() * 2.0 - 1.0);
+        let entry = SoftEntry {
+            value,
+            refresh_after: chrono::Utc::now() + chrono::Duration::seconds(self.lists.refresh_after_secs(ttl_seconds)),
+        };
+        self.set(key, &entry, ttl_seconds).await?;
+        self.delete(&refresh_lock_key(key)).await?;
+        Ok(())
+    }
+
+    /// 读取列表类缓存；到软过期时间后只有取得刷新锁的请求得到 Refresh，其他请求继续使用旧值
+    pub async fn get_refreshable<T>(&self, key: &str) -> RedisResult<CacheRead<T>>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let Some(entry) = self.get::<SoftEntry<T>>(key).await? else {
+            return Ok(CacheRead::Miss);
+        };
+        if entry.refresh_after > chrono::Utc::now() {
+            return Ok(CacheRead::Fresh(entry.value));
+        }
+
+        let lock_secs = self.lists.refresh_lock_secs.max(1) as usize;
+        match self.set_nx(&refresh_lock_key(key), &chrono::Utc::now(), lock_secs).await {
+            Ok(true) => {
+                debug!("Refreshing cache ahead of expiry for key: {}", key);
+                Ok(CacheRead::Refresh(entry.value))
+            }
+            Ok(false) => Ok(CacheRead::Fresh(entry.value)),
+            Err(e) => {
+                warn!("Failed to acquire refresh lock for key {}: {}", key, e);
+                Ok(CacheRead::Fresh(entry.value))
+            }
+        }
+    }
+
+    // 写入不过期的值
+    pub async fn set_persistent<T>(&self, key: &str, value: &T) -> RedisResult<()>
+    where
+        T: Serialize,
+    {
+        debug!("Setting persistent cache value for key: {}", key);
+
+        let serialized = serde_json::to_string(value).map_err(|e| {
+            error!("Failed to serialize data for key {}: {}", key, e);
+            RedisError::from((redis::ErrorKind::TypeError, "Serialization failed"))
+        })?;
+
+        let mut conn = self.connection()?;
+        let result: RedisResult<()> = self.observe("SET", conn.set(key, serialized)).await;
+        if let Err(e) = &result {
+            error!("Redis SET error for key {}: {}", key, e);
+        }
+        self.invalidate_local(&[key]).await;
+        result
+    }
+
+    // 仅当键不存在时写入（SET NX EX），返回是否写入成功
+    pub async fn set_nx<T>(&self, key: &str, value: &T, ttl_seconds: usize) -> RedisResult<bool>
+    where
+        T: Serialize,
+    {
+        debug!("Setting cache value if absent for key: {} with TTL: {}s", key, ttl_seconds);
+
+        let serialized = serde_json::to_string(value).map_err(|e| {
+            error!("Failed to serialize data for key {}: {}", key, e);
+            RedisError::from((redis::ErrorKind::TypeError, "Serialization failed"))
+        })?;
+
+        let mut conn = self.connection()?;
+        let result: Option<String> = self.observe("SET", redis::cmd("SET")
+            .arg(key)
+            .arg(serialized)
+            .arg("NX")
+            .arg("EX")
+            .arg(ttl_seconds)
+            .query_async(&mut conn)).await?;
+        if result.is_some() {
+            self.invalidate_local(&[key]).await;
+        }
+        Ok(result.is_some())
+    }
+
     pub async fn delete(&self, key: &str) -> RedisResult<bool> {
         debug!("Deleting cache value for key: {}", key);
-        let mut conn = (*self.connection).clone();
-        
-        match conn.del::<_, i32>(key).await {
+        let result = match self.connection() {
+            Ok(mut conn) => self.observe("DEL", conn.del::<_, i32>(key)).await,
+            Err(e) => Err(e),
+        };
+        self.invalidate_local(&[key]).await;
+        match result {
             Ok(count) => Ok(count > 0),
             Err(e) => {
                 error!("Redis DELETE error for key {}: {}", key, e);
@@ -79,11 +287,35 @@ impl RedisPool {
         }
     }
 
+    /// 删除多个键，返回实际删除的数量
+    pub async fn delete_keys<K: AsRef<str>>(&self, keys: &[K]) -> RedisResult<u64> {
+        if keys.is_empty() {
+            return Ok(0);
+        }
+        debug!("Deleting {} cache keys", keys.len());
+
+        let keys: Vec<&str> = keys.iter().map(AsRef::as_ref).collect();
+        let result = match self.connection() {
+            Ok(mut conn) => self.observe("DEL", conn.del::<_, u64>(&keys)).await,
+            Err(e) => Err(e),
+        };
+        self.invalidate_local(&keys).await;
+        match result {
+            Ok(count) => Ok(count),
+            Err(e) => {
+                error!("Redis DELETE error for {} keys: {}", keys.len(), e);
+                Ok(0) // 优雅降级
+            }
+        }
+    }
+
     pub async fn exists(&self, key: &str) -> RedisResult<bool> {
         debug!("Checking existence of cache key: {}", key);
-        let mut conn = (*self.connection).clone();
+        let Ok(mut conn) = self.connection() else {
+            return Ok(false);
+        };
         
-        match conn.exists::<_, bool>(key).await {
+        match self.observe("EXISTS", conn.exists::<_, bool>(key)).await {
             Ok(exists) => Ok(exists),
             Err(e) => {
                 error!("Redis EXISTS error for key {}: {}", key, e);
@@ -94,9 +326,9 @@ impl RedisPool {
 
     pub async fn increment(&self, key: &str, delta: i64) -> RedisResult<i64> {
         debug!("Incrementing cache key: {} by {}", key, delta);
-        let mut conn = (*self.connection).clone();
+        let mut conn = self.connection()?;
         
-        match conn.incr(key, delta).await {
+        match self.observe("INCRBY", conn.incr(key, delta)).await {
             Ok(value) => Ok(value),
             Err(e) => {
                 error!("Redis INCR error for key {}: {}", key, e);
@@ -107,9 +339,11 @@ impl RedisPool {
 
     pub async fn expire(&self, key: &str, ttl_seconds: usize) -> RedisResult<bool> {
         debug!("Setting expiration for key: {} to {}s", key, ttl_seconds);
-        let mut conn = (*self.connection).clone();
+        let Ok(mut conn) = self.connection() else {
+            return Ok(false);
+        };
         
-        match conn.expire(key, ttl_seconds as i64).await {
+        match self.observe("EXPIRE", conn.expire(key, ttl_seconds as i64)).await {
             Ok(success) => Ok(success),
             Err(e) => {
                 error!("Redis EXPIRE error for key {}: {}", key, e);
@@ -118,11 +352,58 @@ impl RedisPool {
         }
     }
 
+    // 写入有序集合成员（已存在时更新分数）
+    pub async fn zadd(&self, key: &str, member: &str, score: i64) -> RedisResult<()> {
+        debug!("Adding member to sorted set {} with score {}", key, score);
+        let mut conn = self.connection()?;
+
+        let result: RedisResult<()> = self.observe("ZADD", conn.zadd(key, member, score)).await;
+        if let Err(e) = &result {
+            error!("Redis ZADD error for key {}: {}", key, e);
+        }
+        result
+    }
+
+    // 获取分数不超过 max 的成员及其分数，按分数升序
+    pub async fn zrange_up_to(&self, key: &str, max: i64, limit: isize) -> RedisResult<Vec<(String, i64)>> {
+        debug!("Getting sorted set members of {} with score <= {}", key, max);
+        let mut conn = self.connection()?;
+
+        self.observe("ZRANGEBYSCORE", conn.zrangebyscore_limit_withscores(key, "-inf", max, 0, limit)).await
+    }
+
+    // 删除分数不超过 max 的成员，返回删除的个数
+    pub async fn zrem_up_to(&self, key: &str, max: i64) -> RedisResult<u64> {
+        debug!("Removing sorted set members of {} with score <= {}", key, max);
+        let mut conn = self.connection()?;
+
+        self.observe("ZREMRANGEBYSCORE", conn.zrembyscore(key, "-inf", max)).await
+    }
+
+    // 统计分数不小于 min 的成员个数
+    pub async fn zcount_from(&self, key: &str, min: i64) -> RedisResult<u64> {
+        debug!("Counting sorted set members of {} with score >= {}", key, min);
+        let mut conn = self.connection()?;
+
+        self.observe("ZCOUNT", conn.zcount(key, min, "+inf")).await
+    }
+
+    // 删除有序集合成员，返回是否删除（多个实例同时处理时只有一个会成功）
+    pub async fn zrem(&self, key: &str, member: &str) -> RedisResult<bool> {
+        debug!("Removing member from sorted set {}", key);
+        let mut conn = self.connection()?;
+
+        let removed: i64 = self.observe("ZREM", conn.zrem(key, member)).await?;
+        Ok(removed > 0)
+    }
+
     pub async fn keys(&self, pattern: &str) -> RedisResult<Vec<String>> {
         debug!("Getting keys matching pattern: {}", pattern);
-        let mut conn = (*self.connection).clone();
+        let Ok(mut conn) = self.connection() else {
+            return Ok(Vec::new());
+        };
         
-        match conn.keys(pattern).await {
+        match self.observe("KEYS", conn.keys(pattern)).await {
             Ok(keys) => Ok(keys),
             Err(e) => {
                 error!("Redis KEYS error for pattern {}: {}", pattern, e);
@@ -135,24 +416,30 @@ impl RedisPool {
         debug!("Deleting keys matching pattern: {}", pattern);
         
         match self.keys(pattern).await {
-            Ok(keys) => {
-                if keys.is_empty() {
-                    return Ok(0);
-                }
-                
-                let mut conn = (*self.connection).clone();
-                match conn.del::<_, u64>(&keys).await {
-                    Ok(count) => Ok(count),
-                    Err(e) => {
-                        error!("Redis DELETE pattern error for pattern {}: {}", pattern, e);
-                        Ok(0) // 优雅降级
-                    }
-                }
-            }
+            Ok(keys) => self.delete_keys(&keys).await,
             Err(e) => {
                 error!("Failed to get keys for pattern {}: {}", pattern, e);
                 Ok(0)
             }
         }
     }
-}
\ No newline at end of file
+}
+fn token_digest(key: &[u8], token: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(token.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_digest_depends_on_key() {
+        let digest = token_digest(b"key-a", "token");
+        assert_eq!(digest.len(), 64);
+        assert_eq!(digest, token_digest(b"key-a", "token"));
+        assert_ne!(digest, token_digest(b"key-b", "token"));
+        assert_ne!(digest, token_digest(b"key-a", "other-token"));
+    }
+}
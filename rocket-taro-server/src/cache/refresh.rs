@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 带软过期时间的缓存值，由 RedisPool::set_refreshable 写入
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SoftEntry<T> {
+    pub value: T,
+    pub refresh_after: DateTime<Utc>,
+}
+
+/// RedisPool::get_refreshable 的读取结果
+#[derive(Debug, PartialEq)]
+pub enum CacheRead<T> {
+    /// 未到软过期时间，或已有其他请求在刷新
+    Fresh(T),
+    /// 已到软过期时间且当前请求取得刷新锁：应重新加载并写回，加载失败时可继续使用旧值
+    Refresh(T),
+    Miss,
+}
+
+/// 刷新锁的键，与数据键同属一个类别，按类别清除时一并删除
+pub fn refresh_lock_key(key: &str) -> String {
+    format!("{}:refresh_lock", key)
+}
@@ -0,0 +1,28 @@
+use crate::cache::{CacheCategory, CacheKey, RedisPool};
+use tracing::debug;
+
+/// 注册频率计数：每个 IP、设备一个计数器，窗口从第一次注册开始，到期后自动清零
+pub struct RegistrationVelocityCache {
+    redis: RedisPool,
+}
+
+impl RegistrationVelocityCache {
+    pub fn new(redis: RedisPool) -> Self {
+        Self { redis }
+    }
+
+    fn key(kind: &str, id: &str) -> CacheKey {
+        CacheCategory::RegistrationVelocity.key(format!("{}:{}", kind, id))
+    }
+
+    // 计入一次注册，返回窗口内的次数（含本次）
+    pub async fn record(&self, kind: &str, id: &str, window_seconds: usize) -> Result<u64, redis::RedisError> {
+        let key = Self::key(kind, id);
+        let count = self.redis.increment(&key, 1).await?;
+        if count == 1 {
+            debug!("Starting registration velocity window for {}", key);
+            self.redis.expire(&key, window_seconds).await?;
+        }
+        Ok(count.max(0) as u64)
+    }
+}
@@ -0,0 +1,35 @@
+use crate::config::Platform;
+use crate::models::remote_config::RemoteConfigSnapshot;
+use crate::cache::{RedisPool, cache_key};
+use tracing::debug;
+
+pub struct RemoteConfigCache {
+    redis: RedisPool,
+}
+
+impl RemoteConfigCache {
+    pub fn new(redis: RedisPool) -> Self {
+        Self { redis }
+    }
+
+    // 缓存平台配置快照
+    pub async fn cache_snapshot(&self, snapshot: &RemoteConfigSnapshot) -> Result<(), redis::RedisError> {
+        let key = cache_key("remote_config", snapshot.platform.as_str());
+        debug!("Caching remote config snapshot for platform: {} (v{})", snapshot.platform.as_str(), snapshot.version);
+        self.redis.set(&key, snapshot, self.redis.ttl().remote_config).await
+    }
+
+    // 获取平台配置快照
+    pub async fn get_snapshot(&self, platform: Platform) -> Result<Option<RemoteConfigSnapshot>, redis::RedisError> {
+        let key = cache_key("remote_config", platform.as_str());
+        debug!("Getting cached remote config snapshot for platform: {}", platform.as_str());
+        self.redis.get(&key).await
+    }
+
+    // 清除所有平台的配置快照
+    pub async fn invalidate_all(&self) -> Result<u64, redis::RedisError> {
+        let pattern = cache_key("remote_config", "*");
+        debug!("Invalidating all remote config snapshots");
+        self.redis.delete_pattern(&pattern).await
+    }
+}
@@ -2,30 +2,52 @@ use uuid::Uuid;
 use serde::{Serialize, Deserialize};
 use chrono::{DateTime, Utc};
 use crate::models::auth::{User, UserSession};
-use crate::cache::{RedisPool, cache_key, ttl};
+use crate::cache::{RedisPool, CacheCategory, session_expiry::SessionExpiryCache};
 use tracing::{debug, info};
 
+/// 缓存中不保存明文会话令牌，只保存令牌的带密钥摘要（同时用作缓存键）
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CachedSession {
     pub id: Uuid,
     pub user_id: Uuid,
-    pub session_token: String,
+    pub token_digest: String,
     pub user_agent: Option<String>,
     pub ip_address: Option<String>,
     pub expires_at: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub last_accessed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub impersonator_id: Option<Uuid>,
 }
 
-impl From<UserSession> for CachedSession {
-    fn from(session: UserSession) -> Self {
+impl CachedSession {
+    fn new(session: &UserSession, token_digest: String) -> Self {
         CachedSession {
             id: session.id,
             user_id: session.user_id,
-            session_token: session.session_token,
-            user_agent: session.user_agent,
-            ip_address: session.ip_address,
+            token_digest,
+            user_agent: session.user_agent.clone(),
+            ip_address: session.ip_address.clone(),
             expires_at: session.expires_at,
             created_at: session.created_at,
+            last_accessed_at: Some(session.last_accessed_at),
+            impersonator_id: session.impersonator_id,
+        }
+    }
+
+    /// 还原为会话，明文令牌由调用方（请求中携带的令牌）提供
+    pub fn into_session(self, session_token: &str) -> UserSession {
+        UserSession {
+            id: self.id,
+            user_id: self.user_id,
+            session_token: session_token.to_string(),
+            user_agent: self.user_agent,
+            ip_address: self.ip_address,
+            expires_at: self.expires_at,
+            last_accessed_at: self.last_accessed_at.unwrap_or(self.created_at),
+            created_at: self.created_at,
+            impersonator_id: self.impersonator_id,
         }
     }
 }
@@ -47,128 +69,184 @@ impl SessionCache {
 
     // 缓存会话信息
     pub async fn cache_session(&self, session: &UserSession) -> Result<(), redis::RedisError> {
-        let token_key = cache_key("session_token", &session.session_token);
-        let session_key = cache_key("session", &session.id.to_string());
-        let cached_session = CachedSession {
-            id: session.id,
-            user_id: session.user_id,
-            session_token: session.session_token.clone(),
-            user_agent: session.user_agent.clone(),
-            ip_address: session.ip_address.clone(),
-            expires_at: session.expires_at,
-            created_at: session.created_at,
-        };
+        let token_digest = self.redis.token_digest(&session.session_token);
+        let token_key = CacheCategory::SessionToken.key(&token_digest);
+        let session_key = CacheCategory::Session.key(session.id);
+        let cached_session = CachedSession::new(session, token_digest);
         
-        debug!("Caching session for token: {}", session.session_token);
+        debug!("Caching session: {}", session.id);
         
         // 缓存会话令牌到会话信息的映射
-        self.redis.set(&token_key, &cached_session, ttl::USER_SESSION).await?;
+        self.redis.set(&token_key, &cached_session, self.redis.ttl().user_session).await?;
         
         // 缓存会话ID到会话信息的映射
-        self.redis.set(&session_key, &cached_session, ttl::USER_SESSION).await?;
+        self.redis.set(&session_key, &cached_session, self.redis.ttl().user_session).await?;
         
         Ok(())
     }
 
     // 缓存用户会话组合信息
     pub async fn cache_user_session(&self, user: &User, session: &UserSession) -> Result<(), redis::RedisError> {
-        let key = cache_key("user_session", &session.session_token);
+        let token_digest = self.redis.token_digest(&session.session_token);
+        let key = CacheCategory::UserSession.key(&token_digest);
         let cached_user_session = CachedUserSession {
             user: crate::cache::user::CachedUser::from(user.clone()),
-            session: CachedSession {
-                id: session.id,
-                user_id: session.user_id,
-                session_token: session.session_token.clone(),
-                user_agent: session.user_agent.clone(),
-                ip_address: session.ip_address.clone(),
-                expires_at: session.expires_at,
-                created_at: session.created_at,
-            },
+            session: CachedSession::new(session, token_digest),
         };
         
-        debug!("Caching user session for token: {}", session.session_token);
-        self.redis.set(&key, &cached_user_session, ttl::USER_SESSION).await
+        debug!("Caching user session: {}", session.id);
+        self.redis.set(&key, &cached_user_session, self.redis.ttl().user_session).await?;
+
+        // 登记过期时间，用于推送过期提醒
+        SessionExpiryCache::new(self.redis.clone()).schedule(user.id, session.id, session.expires_at).await
     }
 
     // 通过会话令牌获取会话信息
     pub async fn get_session_by_token(&self, session_token: &str) -> Result<Option<CachedSession>, redis::RedisError> {
-        let key = cache_key("session_token", session_token);
-        debug!("Getting session by token: {}", session_token);
+        let key = CacheCategory::SessionToken.key(self.redis.token_digest(session_token));
+        debug!("Getting session by token");
         self.redis.get(&key).await
     }
 
     // 通过会话令牌获取用户会话组合信息
     pub async fn get_user_session_by_token(&self, session_token: &str) -> Result<Option<CachedUserSession>, redis::RedisError> {
-        let key = cache_key("user_session", session_token);
-        debug!("Getting user session by token: {}", session_token);
+        let key = CacheCategory::UserSession.key(self.redis.token_digest(session_token));
+        debug!("Getting user session by token");
         self.redis.get(&key).await
     }
 
     // 通过会话ID获取会话信息
     pub async fn get_session_by_id(&self, session_id: Uuid) -> Result<Option<CachedSession>, redis::RedisError> {
-        let key = cache_key("session", &session_id.to_string());
+        let key = CacheCategory::Session.key(session_id);
         debug!("Getting session by ID: {}", session_id);
         self.redis.get(&key).await
     }
 
     // 删除会话缓存
     pub async fn invalidate_session(&self, session_token: &str) -> Result<(), redis::RedisError> {
-        let token_key = cache_key("session_token", session_token);
-        let user_session_key = cache_key("user_session", session_token);
-        
-        debug!("Invalidating session cache for token: {}", session_token);
+        debug!("Invalidating session cache for token");
+        self.invalidate_session_digest(&self.redis.token_digest(session_token)).await?;
+        Ok(())
+    }
+
+    // 按会话ID删除会话缓存，返回缓存中是否有该会话
+    pub async fn invalidate_session_by_id(&self, session_id: Uuid) -> Result<bool, redis::RedisError> {
+        debug!("Invalidating session cache by ID: {}", session_id);
+        match self.get_session_by_id(session_id).await? {
+            Some(session) => {
+                self.invalidate_session_digest(&session.token_digest).await?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    // 按会话ID删除会话缓存，返回删除的键数量；只缓存了用户会话组合信息时扫描查找
+    pub async fn purge_session(&self, session_id: Uuid) -> Result<u64, redis::RedisError> {
+        if let Some(session) = self.get_session_by_id(session_id).await? {
+            return self.invalidate_session_digest(&session.token_digest).await;
+        }
+        for key in self.redis.keys(&CacheCategory::UserSession.pattern()).await? {
+            if let Some(user_session) = self.redis.get::<CachedUserSession>(&key).await? {
+                if user_session.session.id == session_id {
+                    return self.invalidate_session_digest(&user_session.session.token_digest).await;
+                }
+            }
+        }
+        Ok(0)
+    }
+
+    // 按令牌摘要删除会话缓存，返回删除的键数量
+    async fn invalidate_session_digest(&self, token_digest: &str) -> Result<u64, redis::RedisError> {
+        let token_key = CacheCategory::SessionToken.key(token_digest);
+        let user_session_key = CacheCategory::UserSession.key(token_digest);
         
+        let mut keys = vec![
+            token_key.clone(),
+            user_session_key.clone(),
+            CacheCategory::SessionAccess.key(token_digest),
+            CacheCategory::SessionAccessWrite.key(token_digest),
+            CacheCategory::ClientCapabilities.key(token_digest),
+        ];
+
         // 需要先获取会话信息以便删除session_id缓存
-        if let Some(session) = self.get_session_by_token(session_token).await? {
-            let session_key = cache_key("session", &session.id.to_string());
-            self.redis.delete(&session_key).await?;
+        if let Some(session) = self.redis.get::<CachedSession>(&token_key).await? {
+            keys.push(CacheCategory::Session.key(session.id));
+        }
+
+        // 取消过期提醒
+        if let Some(user_session) = self.redis.get::<CachedUserSession>(&user_session_key).await? {
+            SessionExpiryCache::new(self.redis.clone())
+                .unschedule(user_session.user.id, user_session.session.id).await?;
         }
         
-        self.redis.delete(&token_key).await?;
-        self.redis.delete(&user_session_key).await?;
-        
-        Ok(())
+        self.redis.delete_keys(&keys).await
     }
 
-    // 删除用户的所有会话缓存
+    // 删除用户的所有会话缓存，返回删除的会话数量
     pub async fn invalidate_user_sessions(&self, user_id: Uuid) -> Result<u64, redis::RedisError> {
-        let pattern = cache_key("user_session", "*");
+        let (deleted_count, _) = self.invalidate_user_session_keys(user_id).await?;
+        Ok(deleted_count)
+    }
+
+    // 删除用户的所有会话缓存，返回删除的键数量
+    pub async fn purge_user_sessions(&self, user_id: Uuid) -> Result<u64, redis::RedisError> {
+        let (_, deleted_keys) = self.invalidate_user_session_keys(user_id).await?;
+        Ok(deleted_keys)
+    }
+
+    // 返回删除的会话数量和键数量
+    async fn invalidate_user_session_keys(&self, user_id: Uuid) -> Result<(u64, u64), redis::RedisError> {
+        let pattern = CacheCategory::UserSession.pattern();
         debug!("Invalidating all sessions for user_id: {}", user_id);
         
         // 获取所有用户会话键
         let keys = self.redis.keys(&pattern).await?;
         let mut deleted_count = 0;
+        let mut deleted_keys = 0;
         
         for key in keys {
             if let Some(user_session) = self.redis.get::<CachedUserSession>(&key).await? {
                 if user_session.user.id == user_id {
                     // 删除相关的所有缓存
-                    self.invalidate_session(&user_session.session.session_token).await?;
+                    deleted_keys += self.invalidate_session_digest(&user_session.session.token_digest).await?;
                     deleted_count += 1;
                 }
             }
         }
         
         info!("Invalidated {} sessions for user_id: {}", deleted_count, user_id);
-        Ok(deleted_count)
+        Ok((deleted_count, deleted_keys))
     }
 
     // 更新会话最后访问时间
     pub async fn update_session_access(&self, session_token: &str) -> Result<(), redis::RedisError> {
-        let key = cache_key("session_access", session_token);
+        let key = CacheCategory::SessionAccess.key(self.redis.token_digest(session_token));
         let now = Utc::now().timestamp();
         
-        debug!("Updating session access time for token: {}", session_token);
-        self.redis.set(&key, &now, ttl::USER_SESSION).await
+        debug!("Updating session access time");
+        self.redis.set(&key, &now, self.redis.ttl().user_session).await
     }
 
     // 获取会话最后访问时间
     pub async fn get_session_last_access(&self, session_token: &str) -> Result<Option<i64>, redis::RedisError> {
-        let key = cache_key("session_access", session_token);
-        debug!("Getting session last access time for token: {}", session_token);
+        let key = CacheCategory::SessionAccess.key(self.redis.token_digest(session_token));
+        debug!("Getting session last access time");
         self.redis.get(&key).await
     }
 
+    // 访问时间写回数据库的节流：间隔内只有第一次返回 true
+    pub async fn claim_access_write(&self, session_token: &str, interval_secs: u64) -> Result<bool, redis::RedisError> {
+        let key = CacheCategory::SessionAccessWrite.key(self.redis.token_digest(session_token));
+        self.redis.set_nx(&key, &Utc::now().timestamp(), interval_secs.max(1) as usize).await
+    }
+
+    // 会话绑定不一致告警的节流：间隔内同一会话只有第一次返回 true
+    pub async fn claim_binding_alert(&self, session_id: Uuid, interval_secs: u64) -> Result<bool, redis::RedisError> {
+        let key = CacheCategory::SessionBindingAlert.key(session_id);
+        self.redis.set_nx(&key, &Utc::now().timestamp(), interval_secs.max(1) as usize).await
+    }
+
     // 清理过期会话缓存
     pub async fn cleanup_expired_sessions(&self) -> Result<u64, redis::RedisError> {
         debug!("Starting cleanup of expired session caches");
@@ -176,13 +254,13 @@ impl SessionCache {
         let mut cleaned_count = 0;
         
         // 获取所有会话令牌缓存
-        let pattern = cache_key("session_token", "*");
+        let pattern = CacheCategory::SessionToken.pattern();
         let keys = self.redis.keys(&pattern).await?;
         
         for key in keys {
             if let Some(session) = self.redis.get::<CachedSession>(&key).await? {
                 if session.expires_at < now {
-                    self.invalidate_session(&session.session_token).await?;
+                    self.invalidate_session_digest(&session.token_digest).await?;
                     cleaned_count += 1;
                 }
             }
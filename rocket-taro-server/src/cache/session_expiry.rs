@@ -0,0 +1,90 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::cache::{RedisPool, cache_key};
+use tracing::debug;
+
+/// 单次最多取出的即将过期会话数
+const DUE_BATCH_SIZE: isize = 200;
+
+/// 即将过期的会话
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpiringSession {
+    pub user_id: Uuid,
+    pub session_id: Uuid,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl ExpiringSession {
+    // 有序集合成员：用户ID:会话ID
+    fn member(user_id: Uuid, session_id: Uuid) -> String {
+        format!("{}:{}", user_id, session_id)
+    }
+
+    fn parse(member: &str, score: i64) -> Option<Self> {
+        let (user_id, session_id) = member.split_once(':')?;
+        Some(Self {
+            user_id: Uuid::parse_str(user_id).ok()?,
+            session_id: Uuid::parse_str(session_id).ok()?,
+            expires_at: DateTime::from_timestamp(score, 0)?,
+        })
+    }
+}
+
+/// 会话过期时间表（Redis 有序集合，分数为过期时间戳），用于推送过期提醒
+pub struct SessionExpiryCache {
+    redis: RedisPool,
+}
+
+impl SessionExpiryCache {
+    pub fn new(redis: RedisPool) -> Self {
+        Self { redis }
+    }
+
+    fn key() -> String {
+        cache_key("session_expiry", "schedule")
+    }
+
+    // 登记会话过期时间，重复登记时更新过期时间
+    pub async fn schedule(&self, user_id: Uuid, session_id: Uuid, expires_at: DateTime<Utc>) -> Result<(), redis::RedisError> {
+        debug!("Scheduling expiry warning for session: {}", session_id);
+        self.redis.zadd(&Self::key(), &ExpiringSession::member(user_id, session_id), expires_at.timestamp()).await
+    }
+
+    // 取消会话的过期提醒
+    pub async fn unschedule(&self, user_id: Uuid, session_id: Uuid) -> Result<(), redis::RedisError> {
+        debug!("Unscheduling expiry warning for session: {}", session_id);
+        self.redis.zrem(&Self::key(), &ExpiringSession::member(user_id, session_id)).await?;
+        Ok(())
+    }
+
+    // 获取在 before 之前过期的会话（不移除）
+    pub async fn due(&self, before: DateTime<Utc>) -> Result<Vec<ExpiringSession>, redis::RedisError> {
+        let members = self.redis.zrange_up_to(&Self::key(), before.timestamp(), DUE_BATCH_SIZE).await?;
+        Ok(members.iter()
+            .filter_map(|(member, score)| ExpiringSession::parse(member, *score))
+            .collect())
+    }
+
+    // 认领会话的提醒：从时间表中移除，返回是否由本次调用移除（多实例下只有一个实例发送提醒）
+    pub async fn claim(&self, session: &ExpiringSession) -> Result<bool, redis::RedisError> {
+        self.redis.zrem(&Self::key(), &ExpiringSession::member(session.user_id, session.session_id)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_member_round_trip() {
+        let (user_id, session_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let expires_at = DateTime::from_timestamp(1_800_000_000, 0).unwrap();
+        let member = ExpiringSession::member(user_id, session_id);
+
+        assert_eq!(
+            ExpiringSession::parse(&member, expires_at.timestamp()),
+            Some(ExpiringSession { user_id, session_id, expires_at })
+        );
+        assert_eq!(ExpiringSession::parse("not-a-member", 0), None);
+    }
+}
@@ -0,0 +1,33 @@
+use uuid::Uuid;
+use crate::models::settings::UserSettings;
+use crate::cache::{RedisPool, cache_key};
+use tracing::debug;
+
+pub struct UserSettingsCache {
+    redis: RedisPool,
+}
+
+impl UserSettingsCache {
+    pub fn new(redis: RedisPool) -> Self {
+        Self { redis }
+    }
+
+    // 缓存用户设置
+    pub async fn set(&self, user_id: Uuid, settings: &UserSettings) -> Result<(), redis::RedisError> {
+        debug!("Caching settings for user: {}", user_id);
+        self.redis.set(&cache_key("user_settings", &user_id.to_string()), settings, self.redis.ttl().user_settings).await
+    }
+
+    // 获取缓存的用户设置
+    pub async fn get(&self, user_id: Uuid) -> Result<Option<UserSettings>, redis::RedisError> {
+        debug!("Getting cached settings for user: {}", user_id);
+        self.redis.get(&cache_key("user_settings", &user_id.to_string())).await
+    }
+
+    // 删除缓存的用户设置
+    pub async fn invalidate(&self, user_id: Uuid) -> Result<(), redis::RedisError> {
+        debug!("Invalidating cached settings for user: {}", user_id);
+        self.redis.delete(&cache_key("user_settings", &user_id.to_string())).await?;
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 use uuid::Uuid;
 use serde::{Serialize, Deserialize};
 use crate::models::auth::User;
-use crate::cache::{RedisPool, cache_key, ttl};
+use crate::cache::{RedisPool, CacheCategory};
 use tracing::{debug, warn};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,6 +17,8 @@ pub struct CachedUser {
     pub wx_openid: Option<String>,
     pub wx_unionid: Option<String>,
     pub wx_session_key: Option<String>,
+    #[serde(default)]
+    pub version: i32,
 }
 
 impl From<User> for CachedUser {
@@ -33,6 +35,7 @@ impl From<User> for CachedUser {
             wx_openid: user.wx_openid,
             wx_unionid: user.wx_unionid,
             wx_session_key: user.wx_session_key,
+            version: user.version,
         }
     }
 }
@@ -48,30 +51,30 @@ impl UserCache {
 
     // 缓存用户信息
     pub async fn cache_user(&self, user: &User) -> Result<(), redis::RedisError> {
-        let key = cache_key("user", &user.id.to_string());
+        let key = CacheCategory::User.key(user.id);
         let cached_user = CachedUser::from(user.clone());
         
         debug!("Caching user info for user_id: {}", user.id);
-        self.redis.set(&key, &cached_user, ttl::USER_INFO).await
+        self.redis.set(&key, &cached_user, self.redis.ttl().user_info).await
     }
 
     // 获取缓存的用户信息
     pub async fn get_user(&self, user_id: Uuid) -> Result<Option<CachedUser>, redis::RedisError> {
-        let key = cache_key("user", &user_id.to_string());
+        let key = CacheCategory::User.key(user_id);
         debug!("Getting cached user info for user_id: {}", user_id);
         self.redis.get(&key).await
     }
 
     // 缓存用户名到用户ID的映射
     pub async fn cache_username_mapping(&self, username: &str, user_id: Uuid) -> Result<(), redis::RedisError> {
-        let key = cache_key("username", username);
+        let key = CacheCategory::Username.key(username);
         debug!("Caching username mapping: {} -> {}", username, user_id);
-        self.redis.set(&key, &user_id.to_string(), ttl::USER_INFO).await
+        self.redis.set(&key, &user_id.to_string(), self.redis.ttl().user_info).await
     }
 
     // 获取用户名对应的用户ID
     pub async fn get_user_id_by_username(&self, username: &str) -> Result<Option<Uuid>, redis::RedisError> {
-        let key = cache_key("username", username);
+        let key = CacheCategory::Username.key(username);
         debug!("Getting user_id for username: {}", username);
         
         match self.redis.get::<String>(&key).await? {
@@ -90,7 +93,7 @@ impl UserCache {
 
     // 删除用户缓存
     pub async fn invalidate_user(&self, user_id: Uuid) -> Result<(), redis::RedisError> {
-        let user_key = cache_key("user", &user_id.to_string());
+        let user_key = CacheCategory::User.key(user_id);
         debug!("Invalidating user cache for user_id: {}", user_id);
         self.redis.delete(&user_key).await?;
         Ok(())
@@ -98,7 +101,7 @@ impl UserCache {
 
     // 删除用户名映射缓存
     pub async fn invalidate_username(&self, username: &str) -> Result<(), redis::RedisError> {
-        let username_key = cache_key("username", username);
+        let username_key = CacheCategory::Username.key(username);
         debug!("Invalidating username cache for username: {}", username);
         self.redis.delete(&username_key).await?;
         Ok(())
@@ -106,18 +109,18 @@ impl UserCache {
 
     // 记录登录失败次数
     pub async fn record_login_failure(&self, username: &str) -> Result<i64, redis::RedisError> {
-        let key = cache_key("login_failures", username);
+        let key = CacheCategory::LoginFailures.key(username);
         debug!("Recording login failure for username: {}", username);
         
         let count = self.redis.increment(&key, 1).await?;
         // 设置过期时间
-        self.redis.expire(&key, ttl::LOGIN_ATTEMPTS).await?;
+        self.redis.expire(&key, self.redis.ttl().login_attempts).await?;
         Ok(count)
     }
 
     // 获取登录失败次数
     pub async fn get_login_failures(&self, username: &str) -> Result<i64, redis::RedisError> {
-        let key = cache_key("login_failures", username);
+        let key = CacheCategory::LoginFailures.key(username);
         debug!("Getting login failure count for username: {}", username);
         
         match self.redis.get::<i64>(&key).await? {
@@ -128,7 +131,7 @@ impl UserCache {
 
     // 清除登录失败记录
     pub async fn clear_login_failures(&self, username: &str) -> Result<(), redis::RedisError> {
-        let key = cache_key("login_failures", username);
+        let key = CacheCategory::LoginFailures.key(username);
         debug!("Clearing login failures for username: {}", username);
         self.redis.delete(&key).await?;
         Ok(())
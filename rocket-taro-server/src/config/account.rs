@@ -0,0 +1,28 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 账户生命周期配置（Rocket.toml 中的 `[default.account]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AccountConfig {
+    /// 注销后的保留天数，超过后执行个人信息匿名化
+    pub deletion_grace_days: i64,
+    /// 匿名化任务执行间隔（秒）
+    pub anonymization_interval_secs: u64,
+}
+
+impl Default for AccountConfig {
+    fn default() -> Self {
+        Self {
+            deletion_grace_days: 30,
+            anonymization_interval_secs: 3600,
+        }
+    }
+}
+
+impl AccountConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "account")
+    }
+}
@@ -0,0 +1,41 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 账户标记计算配置（Rocket.toml 中的 `[default.account_flags]`），
+/// 每个开关对应一个标记提供者，关闭后该标记保持默认值
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AccountFlagsConfig {
+    /// VIP 标记
+    pub vip: bool,
+    /// 新用户标记
+    pub new_user: bool,
+    /// 未读通知标记（需要查询数据库）
+    pub notifications: bool,
+    /// 资料完善标记
+    pub profile_completion: bool,
+    /// 安全等级
+    pub security_score: bool,
+    /// 协议同意标记（需要查询数据库），有未同意的生效协议时登录后先进入协议同意页
+    pub policy_consent: bool,
+}
+
+impl Default for AccountFlagsConfig {
+    fn default() -> Self {
+        Self {
+            vip: true,
+            new_user: true,
+            notifications: true,
+            profile_completion: true,
+            security_score: true,
+            policy_consent: true,
+        }
+    }
+}
+
+impl AccountFlagsConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "account_flags")
+    }
+}
@@ -0,0 +1,111 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::models::api_quota::QuotaRole;
+
+/// 一组共享每日配额的接口
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuotaGroup {
+    /// 分组名，出现在响应和管理端覆盖中
+    pub name: String,
+    pub path_prefixes: Vec<String>,
+    /// 每个调用方每天（UTC）的请求次数上限，0 表示不限制
+    pub daily_limit: u64,
+    /// 按角色（anonymous / guest / user / admin）覆盖的上限，0 表示不限制
+    #[serde(default)]
+    pub role_limits: BTreeMap<String, u64>,
+}
+
+impl QuotaGroup {
+    /// 角色在配置中的上限（不含管理端覆盖）
+    pub fn limit_for(&self, role: QuotaRole) -> u64 {
+        self.role_limits.get(role.as_str()).copied().unwrap_or(self.daily_limit)
+    }
+}
+
+/// 高开销接口的每日配额（Rocket.toml 中的 `[default.api_quotas]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApiQuotasConfig {
+    pub enabled: bool,
+    /// 配额分组，按最长前缀匹配
+    pub groups: Vec<QuotaGroup>,
+}
+
+impl Default for ApiQuotasConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            groups: vec![
+                QuotaGroup {
+                    name: "export".to_string(),
+                    path_prefixes: vec!["/api/auth/my-data".to_string(), "/api/admin/user-data/export".to_string()],
+                    daily_limit: 20,
+                    role_limits: BTreeMap::from([("admin".to_string(), 0)]),
+                },
+                QuotaGroup {
+                    name: "search".to_string(),
+                    path_prefixes: vec!["/api/user-data/search".to_string()],
+                    daily_limit: 500,
+                    role_limits: BTreeMap::from([("anonymous".to_string(), 100)]),
+                },
+                QuotaGroup {
+                    name: "batch".to_string(),
+                    path_prefixes: vec!["/api/batch".to_string()],
+                    daily_limit: 2000,
+                    role_limits: BTreeMap::new(),
+                },
+            ],
+        }
+    }
+}
+
+impl ApiQuotasConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "api_quotas")
+    }
+
+    /// 路径所属的分组，多个分组匹配时取前缀最长的
+    pub fn group_for(&self, path: &str) -> Option<&QuotaGroup> {
+        self.groups.iter()
+            .filter_map(|group| {
+                group.path_prefixes.iter()
+                    .filter(|prefix| path.starts_with(prefix.as_str()))
+                    .map(|prefix| (group, prefix.len()))
+                    .max_by_key(|(_, length)| *length)
+            })
+            .max_by_key(|(_, length)| *length)
+            .map(|(group, _)| group)
+    }
+
+    pub fn group(&self, name: &str) -> Option<&QuotaGroup> {
+        self.groups.iter().find(|group| group.name == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocket::figment::providers::{Format, Toml};
+
+    #[test]
+    fn test_group_and_role_limits() {
+        let figment = Figment::new().merge(Toml::string(r#"
+            [api_quotas]
+            groups = [
+                { name = "search", path_prefixes = ["/api/user-data/search"], daily_limit = 50, role_limits = { admin = 0 } },
+            ]
+        "#));
+        let config = ApiQuotasConfig::from_figment(&figment);
+        let group = config.group_for("/api/user-data/search").unwrap();
+        assert_eq!(group.limit_for(QuotaRole::User), 50);
+        assert_eq!(group.limit_for(QuotaRole::Admin), 0);
+        assert!(config.group_for("/api/user-data").is_none());
+
+        let defaults = ApiQuotasConfig::default();
+        assert_eq!(defaults.group_for("/api/auth/my-data/abc/download").map(|group| group.name.as_str()), Some("export"));
+        assert_eq!(defaults.group("search").unwrap().limit_for(QuotaRole::Anonymous), 100);
+    }
+}
@@ -0,0 +1,59 @@
+use rocket::data::{ByteUnit, ToByteUnit};
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 头像上传和处理配置（Rocket.toml 中的 `[default.avatars]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AvatarConfig {
+    /// 原图和处理后头像的存放目录
+    pub directory: String,
+    /// 原图大小上限，需不大于 `[default.limits]` 中的 `file`
+    pub max_file_size: ByteUnit,
+    /// 允许上传的图片类型
+    pub allowed_content_types: Vec<String>,
+    /// 处理后头像的边长（像素），按中心裁剪为正方形
+    pub size: u32,
+    /// 原图宽高上限（像素），超过时不解码
+    pub max_source_dimension: u32,
+    /// 处理任务执行间隔（秒）
+    pub process_interval_secs: u64,
+    /// 每次处理的最大头像数
+    pub process_batch_size: i64,
+    /// 审核服务不可用等临时失败时的重试间隔（秒）
+    pub retry_delay_secs: i64,
+    /// 最多处理次数，仍失败时标记为 failed
+    pub max_attempts: i32,
+}
+
+impl Default for AvatarConfig {
+    fn default() -> Self {
+        Self {
+            directory: "data/avatars".to_string(),
+            max_file_size: 5.mebibytes(),
+            allowed_content_types: ["image/jpeg", "image/png", "image/webp"]
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+            size: 256,
+            max_source_dimension: 8192,
+            process_interval_secs: 5,
+            process_batch_size: 10,
+            retry_delay_secs: 60,
+            max_attempts: 3,
+        }
+    }
+}
+
+impl AvatarConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "avatars")
+    }
+
+    /// 图片类型是否允许上传（忽略参数）
+    pub fn is_allowed(&self, content_type: &str) -> bool {
+        let essence = content_type.split(';').next().unwrap_or_default().trim();
+        self.allowed_content_types.iter().any(|allowed| allowed.eq_ignore_ascii_case(essence))
+    }
+}
@@ -0,0 +1,33 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 批量请求接口（Rocket.toml 中的 `[default.batch]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BatchConfig {
+    pub enabled: bool,
+    /// 单次批量请求最多包含的子请求数
+    pub max_requests: usize,
+    /// 单个子请求的超时时间
+    pub request_timeout_secs: u64,
+    /// 允许批量调用的路径前缀
+    pub path_prefixes: Vec<String>,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_requests: 10,
+            request_timeout_secs: 10,
+            path_prefixes: vec!["/api/".to_string()],
+        }
+    }
+}
+
+impl BatchConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "batch")
+    }
+}
@@ -0,0 +1,122 @@
+use rocket::data::{ByteUnit, ToByteUnit};
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 请求体类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BodyKind {
+    Json,
+    Multipart,
+}
+
+impl BodyKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BodyKind::Json => "json",
+            BodyKind::Multipart => "multipart",
+        }
+    }
+}
+
+/// 按路径前缀设置的请求体上限，未设置的类型使用全局上限
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BodyLimitGroup {
+    pub prefix: String,
+    #[serde(default)]
+    pub json: Option<ByteUnit>,
+    #[serde(default)]
+    pub multipart: Option<ByteUnit>,
+}
+
+/// 请求体大小限制配置（Rocket.toml 中的 `[default.body_limits]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BodyLimitsConfig {
+    /// 全局 JSON 请求体上限
+    pub json: ByteUnit,
+    /// 全局 multipart 请求体上限
+    pub multipart: ByteUnit,
+    /// 路由分组，按最长前缀匹配
+    pub groups: Vec<BodyLimitGroup>,
+}
+
+impl Default for BodyLimitsConfig {
+    fn default() -> Self {
+        Self {
+            json: 1.mebibytes(),
+            multipart: 10.mebibytes(),
+            groups: vec![
+                BodyLimitGroup {
+                    prefix: "/api/metrics/".to_string(),
+                    json: Some(16.kibibytes()),
+                    multipart: Some(0.bytes()),
+                },
+                BodyLimitGroup {
+                    prefix: "/api/route-commands/ack".to_string(),
+                    json: Some(4.kibibytes()),
+                    multipart: Some(0.bytes()),
+                },
+            ],
+        }
+    }
+}
+
+impl BodyLimitsConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "body_limits")
+    }
+
+    /// 路径对应的请求体上限，返回匹配的分组前缀（未匹配时为 None）和上限
+    pub fn limit_for(&self, path: &str, kind: BodyKind) -> (Option<&str>, ByteUnit) {
+        let group = self.groups.iter()
+            .filter(|group| path.starts_with(group.prefix.as_str()))
+            .max_by_key(|group| group.prefix.len());
+
+        let global = match kind {
+            BodyKind::Json => self.json,
+            BodyKind::Multipart => self.multipart,
+        };
+        let limit = group
+            .and_then(|group| match kind {
+                BodyKind::Json => group.json,
+                BodyKind::Multipart => group.multipart,
+            })
+            .unwrap_or(global);
+        (group.map(|group| group.prefix.as_str()), limit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocket::figment::providers::{Format, Toml};
+
+    #[test]
+    fn test_limit_for_uses_longest_prefix() {
+        let mut config = BodyLimitsConfig::default();
+        config.groups.push(BodyLimitGroup {
+            prefix: "/api/metrics/performance".to_string(),
+            json: Some(64.kibibytes()),
+            multipart: None,
+        });
+
+        assert_eq!(config.limit_for("/api/metrics/route-command-error", BodyKind::Json), (Some("/api/metrics/"), 16.kibibytes()));
+        assert_eq!(config.limit_for("/api/metrics/performance", BodyKind::Json), (Some("/api/metrics/performance"), 64.kibibytes()));
+        assert_eq!(config.limit_for("/api/metrics/performance", BodyKind::Multipart).1, 10.mebibytes());
+        assert_eq!(config.limit_for("/api/user-data", BodyKind::Json), (None, 1.mebibytes()));
+    }
+
+    #[test]
+    fn test_parse_config() {
+        let figment = Figment::new().merge(Toml::string(r#"
+            [body_limits]
+            json = "256 KiB"
+            groups = [{ prefix = "/api/uploads/", multipart = "20 MiB" }]
+        "#));
+        let config = BodyLimitsConfig::from_figment(&figment);
+        assert_eq!(config.json, 256.kibibytes());
+        assert_eq!(config.multipart, 10.mebibytes());
+        assert_eq!(config.limit_for("/api/uploads/avatar", BodyKind::Multipart).1, 20.mebibytes());
+    }
+}
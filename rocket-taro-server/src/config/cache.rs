@@ -0,0 +1,274 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// Redis 部署拓扑
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RedisTopology {
+    /// 单节点，使用 redis_url
+    Single,
+    /// 哨兵模式，nodes 为哨兵地址，主节点由哨兵发现
+    Sentinel,
+    /// 集群模式，nodes 为任意几个集群节点地址
+    Cluster,
+}
+
+/// 缓存配置（Rocket.toml 中的 `[default.cache]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// 单节点连接地址，`rediss://` 使用 TLS；环境变量 REDIS_URL 优先
+    pub redis_url: String,
+    /// 会话令牌缓存键的 HMAC 密钥；环境变量 CACHE_TOKEN_KEY 优先
+    pub token_key: Option<String>,
+    /// 部署拓扑
+    pub topology: RedisTopology,
+    /// 哨兵或集群节点地址（`redis://` 或 `rediss://`）
+    pub nodes: Vec<String>,
+    /// 哨兵监控的主节点名称
+    pub sentinel_master: String,
+    /// 哨兵模式下连接主节点是否使用 TLS
+    pub sentinel_master_tls: bool,
+    /// ACL 用户名，设置后覆盖地址中的用户名
+    pub username: Option<String>,
+    /// 密码，设置后覆盖地址中的密码；环境变量 REDIS_PASSWORD 优先
+    pub password: Option<String>,
+    /// TLS 连接不验证服务器证书，仅用于自签名证书的测试环境
+    pub tls_insecure: bool,
+    /// Redis 前的进程内缓存
+    pub local: LocalCacheConfig,
+    /// 列表缓存的过期抖动与提前刷新
+    pub lists: ListCacheConfig,
+    /// 各类缓存的过期时间
+    pub ttl: CacheTtlConfig,
+    /// Redis 熔断
+    pub breaker: CacheBreakerConfig,
+}
+
+/// Redis 熔断配置（`[default.cache.breaker]`）：统计窗口内连接类错误比例超过阈值时熔断，
+/// 熔断期间不访问 Redis，冷却后放行一个探测请求，成功则恢复
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheBreakerConfig {
+    pub enabled: bool,
+    /// 统计窗口（秒）
+    pub window_secs: u64,
+    /// 窗口内请求数达到该值才计算错误率
+    pub min_requests: u32,
+    /// 熔断的错误率阈值
+    pub error_rate: f64,
+    /// 熔断后多久放行探测请求（秒）
+    pub open_secs: u64,
+}
+
+impl Default for CacheBreakerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            window_secs: 10,
+            min_requests: 20,
+            error_rate: 0.5,
+            open_secs: 5,
+        }
+    }
+}
+
+/// 各类缓存的过期时间（秒），`[default.cache.ttl]`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheTtlConfig {
+    /// 会话及会话相关的缓存（访问时间、客户端能力）
+    pub user_session: usize,
+    /// 用户信息和用户名映射
+    pub user_info: usize,
+    /// 用户数据、数据列表和搜索结果
+    pub user_data: usize,
+    /// 登录失败计数的统计窗口
+    pub login_attempts: usize,
+    pub remote_config: usize,
+    pub admin_stats: usize,
+    /// 幂等记录保留时长
+    pub idempotency: usize,
+    /// 幂等请求处理中的锁
+    pub idempotency_lock: usize,
+    /// 用户名/邮箱可用性的负缓存
+    pub availability: usize,
+    pub user_settings: usize,
+    pub form_draft: usize,
+    /// 找回密码凭证有效期
+    pub password_recovery: usize,
+    /// 二次验证记录，默认与会话缓存一致
+    pub session_reauth: usize,
+    /// 微信登录 code 的防重放记录，与 code 的有效期一致
+    pub wx_login_code: usize,
+}
+
+impl Default for CacheTtlConfig {
+    fn default() -> Self {
+        Self {
+            user_session: 7 * 24 * 3600, // 7天
+            user_info: 30 * 60,          // 30分钟
+            user_data: 10 * 60,          // 10分钟
+            login_attempts: 15 * 60,     // 15分钟
+            remote_config: 5 * 60,       // 5分钟
+            admin_stats: 5 * 60,         // 5分钟
+            idempotency: 24 * 3600,      // 24小时
+            idempotency_lock: 60,        // 1分钟
+            availability: 30,            // 30秒
+            user_settings: 30 * 60,      // 30分钟
+            form_draft: 7 * 24 * 3600,   // 7天
+            password_recovery: 10 * 60,  // 10分钟
+            session_reauth: 7 * 24 * 3600,
+            wx_login_code: 5 * 60,       // 5分钟
+        }
+    }
+}
+
+/// 进程内缓存配置（`[default.cache.local]`）：只缓存读多写少的类别，
+/// 写入或删除时通过 Redis 发布订阅通知其他实例丢弃本地副本，短 TTL 兜底订阅中断期间的不一致
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LocalCacheConfig {
+    pub enabled: bool,
+    /// 最多缓存的键数量
+    pub max_entries: u64,
+    /// 本地副本有效期（秒）
+    pub ttl_secs: u64,
+    /// 使用本地缓存的类别（`rocket_taro:<category>:*`）
+    pub categories: Vec<String>,
+}
+
+impl Default for LocalCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_entries: 10_000,
+            ttl_secs: 5,
+            categories: vec!["user_session".to_string(), "user".to_string()],
+        }
+    }
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            redis_url: String::new(),
+            token_key: None,
+            topology: RedisTopology::Single,
+            nodes: Vec::new(),
+            sentinel_master: "mymaster".to_string(),
+            sentinel_master_tls: false,
+            username: None,
+            password: None,
+            tls_insecure: false,
+            local: LocalCacheConfig::default(),
+            lists: ListCacheConfig::default(),
+            ttl: CacheTtlConfig::default(),
+            breaker: CacheBreakerConfig::default(),
+        }
+    }
+}
+
+/// 列表缓存配置（`[default.cache.lists]`）：过期时间加随机抖动，避免各实例同时失效；
+/// 超过软过期时间后由一个请求刷新，其他请求继续使用旧值直到真正过期
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ListCacheConfig {
+    /// 过期时间的随机浮动比例，0.1 表示 ±10%
+    pub ttl_jitter_ratio: f64,
+    /// 软过期时间占过期时间的比例，1.0 表示不提前刷新
+    pub refresh_ahead_ratio: f64,
+    /// 刷新锁有效期（秒），刷新失败时锁过期后由其他请求重试
+    pub refresh_lock_secs: u64,
+}
+
+impl Default for ListCacheConfig {
+    fn default() -> Self {
+        Self {
+            ttl_jitter_ratio: 0.1,
+            refresh_ahead_ratio: 0.8,
+            refresh_lock_secs: 30,
+        }
+    }
+}
+
+impl ListCacheConfig {
+    /// 按抖动比例调整过期时间，sample 为 [-1, 1] 内的随机数
+    pub fn jittered_ttl(&self, ttl_seconds: usize, sample: f64) -> usize {
+        let ratio = self.ttl_jitter_ratio.clamp(0.0, 0.5);
+        let jittered = ttl_seconds as f64 * (1.0 + ratio * sample.clamp(-1.0, 1.0));
+        (jittered.round() as usize).max(1)
+    }
+
+    /// 写入后多少秒进入软过期
+    pub fn refresh_after_secs(&self, ttl_seconds: usize) -> i64 {
+        (ttl_seconds as f64 * self.refresh_ahead_ratio.clamp(0.0, 1.0)).round() as i64
+    }
+}
+
+impl CacheConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值；REDIS_URL、REDIS_PASSWORD、CACHE_TOKEN_KEY 覆盖对应配置
+    pub fn from_figment(figment: &Figment) -> Self {
+        let mut config: Self = super::section(figment, "cache");
+
+        if let Ok(redis_url) = std::env::var("REDIS_URL") {
+            config.redis_url = redis_url;
+        }
+        if let Ok(password) = std::env::var("REDIS_PASSWORD") {
+            config.password = Some(password);
+        }
+        if let Ok(token_key) = std::env::var("CACHE_TOKEN_KEY") {
+            config.token_key = Some(token_key);
+        }
+        config.token_key = config.token_key.filter(|key| !key.is_empty());
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocket::figment::providers::{Format, Toml};
+
+    #[test]
+    fn test_local_cache_config() {
+        let figment = Figment::new().merge(Toml::string(r#"
+            [cache.local]
+            ttl_secs = 2
+            categories = ["user_session"]
+        "#));
+        let config = CacheConfig::from_figment(&figment);
+        assert!(config.local.enabled);
+        assert_eq!(config.local.ttl_secs, 2);
+        assert_eq!(config.local.max_entries, 10_000);
+        assert_eq!(config.local.categories, vec!["user_session".to_string()]);
+    }
+
+    #[test]
+    fn test_ttl_config_defaults() {
+        let figment = Figment::new().merge(Toml::string(r#"
+            [cache.ttl]
+            user_data = 120
+        "#));
+        let ttl = CacheConfig::from_figment(&figment).ttl;
+        assert_eq!(ttl.user_data, 120);
+        assert_eq!(ttl.user_info, 30 * 60);
+        assert_eq!(ttl.session_reauth, ttl.user_session);
+        assert_eq!(ttl.wx_login_code, 5 * 60);
+    }
+
+    #[test]
+    fn test_list_cache_ttl() {
+        let lists = ListCacheConfig::default();
+        assert_eq!(lists.jittered_ttl(600, 0.0), 600);
+        assert_eq!(lists.jittered_ttl(600, 1.0), 660);
+        assert_eq!(lists.jittered_ttl(600, -1.0), 540);
+        assert_eq!(lists.jittered_ttl(600, 5.0), 660);
+        assert_eq!(lists.jittered_ttl(0, -1.0), 1);
+        assert_eq!(lists.refresh_after_secs(600), 480);
+
+        let no_jitter = ListCacheConfig { ttl_jitter_ratio: 0.0, refresh_ahead_ratio: 1.0, ..ListCacheConfig::default() };
+        assert_eq!(no_jitter.jittered_ttl(600, 1.0), 600);
+        assert_eq!(no_jitter.refresh_after_secs(600), 600);
+    }
+}
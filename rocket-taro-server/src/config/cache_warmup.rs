@@ -0,0 +1,70 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 启动时可预热的数据集
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarmupDataset {
+    /// 用户数据列表及每条数据
+    UserData,
+    /// 各平台的公共配置快照（/api/public/config）
+    RemoteConfig,
+}
+
+impl WarmupDataset {
+    pub const ALL: [WarmupDataset; 2] = [WarmupDataset::UserData, WarmupDataset::RemoteConfig];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WarmupDataset::UserData => "user_data",
+            WarmupDataset::RemoteConfig => "remote_config",
+        }
+    }
+}
+
+/// 启动缓存预热配置（Rocket.toml 中的 `[default.cache_warmup]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheWarmupConfig {
+    pub enabled: bool,
+    pub datasets: Vec<WarmupDataset>,
+}
+
+impl Default for CacheWarmupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            datasets: WarmupDataset::ALL.to_vec(),
+        }
+    }
+}
+
+impl CacheWarmupConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "cache_warmup")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocket::figment::providers::{Format, Toml};
+
+    #[test]
+    fn test_from_figment() {
+        let figment = Figment::new().merge(Toml::string(r#"
+            [cache_warmup]
+            datasets = ["remote_config"]
+        "#));
+        let config = CacheWarmupConfig::from_figment(&figment);
+        assert!(config.enabled);
+        assert_eq!(config.datasets, vec![WarmupDataset::RemoteConfig]);
+
+        let figment = Figment::new().merge(Toml::string(r#"
+            [cache_warmup]
+            datasets = ["sessions"]
+        "#));
+        assert_eq!(CacheWarmupConfig::from_figment(&figment).datasets, WarmupDataset::ALL.to_vec());
+    }
+}
@@ -0,0 +1,106 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// 同时处理的请求数上限，按路径前缀分组共享
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConcurrencyGroup {
+    /// 分组名，出现在日志和 503 响应中
+    pub name: String,
+    pub path_prefixes: Vec<String>,
+    pub max_concurrent: usize,
+}
+
+/// 高开销接口的并发限制（Rocket.toml 中的 `[default.concurrency_limits]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConcurrencyLimitsConfig {
+    pub enabled: bool,
+    /// 达到上限后排队等待的最长时间（毫秒），超时返回 503
+    pub queue_timeout_ms: u64,
+    /// 503 响应中 Retry-After 建议的重试间隔（秒）
+    pub retry_after_secs: u64,
+    /// 路由分组，按最长前缀匹配
+    pub groups: Vec<ConcurrencyGroup>,
+}
+
+impl Default for ConcurrencyLimitsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            queue_timeout_ms: 1000,
+            retry_after_secs: 2,
+            groups: vec![
+                ConcurrencyGroup {
+                    name: "password".to_string(),
+                    path_prefixes: vec![
+                        "/api/auth/login".to_string(),
+                        "/api/auth/register".to_string(),
+                        "/api/auth/reauth".to_string(),
+                        "/api/auth/recovery/reset".to_string(),
+                        "/api/auth/email-change".to_string(),
+                    ],
+                    max_concurrent: 16,
+                },
+                ConcurrencyGroup {
+                    name: "wechat".to_string(),
+                    path_prefixes: vec!["/api/auth/wx-login".to_string()],
+                    max_concurrent: 32,
+                },
+            ],
+        }
+    }
+}
+
+impl ConcurrencyLimitsConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "concurrency_limits")
+    }
+
+    pub fn queue_timeout(&self) -> Duration {
+        Duration::from_millis(self.queue_timeout_ms)
+    }
+
+    /// 路径所属分组的下标，多个分组匹配时取前缀最长的
+    pub fn group_for(&self, path: &str) -> Option<usize> {
+        self.groups.iter()
+            .enumerate()
+            .filter_map(|(index, group)| {
+                group.path_prefixes.iter()
+                    .filter(|prefix| path.starts_with(prefix.as_str()))
+                    .map(|prefix| (index, prefix.len()))
+                    .max_by_key(|(_, length)| *length)
+            })
+            .max_by_key(|(_, length)| *length)
+            .map(|(index, _)| index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocket::figment::providers::{Format, Toml};
+
+    #[test]
+    fn test_group_for() {
+        let figment = Figment::new().merge(Toml::string(r#"
+            [concurrency_limits]
+            queue_timeout_ms = 500
+            groups = [
+                { name = "auth", path_prefixes = ["/api/auth/"], max_concurrent = 64 },
+                { name = "wechat", path_prefixes = ["/api/auth/wx-login"], max_concurrent = 8 },
+            ]
+        "#));
+        let config = ConcurrencyLimitsConfig::from_figment(&figment);
+        assert_eq!(config.queue_timeout(), Duration::from_millis(500));
+        assert_eq!(config.retry_after_secs, 2);
+        assert_eq!(config.group_for("/api/auth/wx-login"), Some(1));
+        assert_eq!(config.group_for("/api/auth/login"), Some(0));
+        assert_eq!(config.group_for("/api/user-data"), None);
+
+        let defaults = ConcurrencyLimitsConfig::default();
+        assert_eq!(defaults.group_for("/api/auth/login").map(|index| defaults.groups[index].name.as_str()), Some("password"));
+        assert_eq!(defaults.group_for("/api/auth/logout"), None);
+    }
+}
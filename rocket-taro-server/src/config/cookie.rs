@@ -0,0 +1,113 @@
+use rocket::figment::Figment;
+use rocket::http::{Cookie, SameSite};
+use rocket::time::{Duration, OffsetDateTime};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+/// 会话 Cookie 名称，请求守卫从中读取会话令牌
+pub const SESSION_COOKIE: &str = "session_token";
+
+/// 会话 Cookie 的安全属性（Rocket.toml 中的 `[default.cookie]`），生产、预发环境通过 Rocket profile 分别配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CookieConfig {
+    /// 只通过 HTTPS 发送
+    pub secure: bool,
+    pub same_site: CookieSameSite,
+    /// Cookie 所属域名，为空时只发送给当前主机；需要在子域名间共享时设置为上级域名
+    pub domain: Option<String>,
+    /// Cookie 有效期（秒）
+    pub ttl_secs: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CookieSameSite {
+    Strict,
+    Lax,
+    /// 允许跨站请求携带，浏览器要求同时设置 secure
+    None,
+}
+
+impl Default for CookieConfig {
+    fn default() -> Self {
+        Self {
+            secure: false,
+            same_site: CookieSameSite::Lax,
+            domain: None,
+            ttl_secs: 8 * 3600,
+        }
+    }
+}
+
+impl From<CookieSameSite> for SameSite {
+    fn from(same_site: CookieSameSite) -> Self {
+        match same_site {
+            CookieSameSite::Strict => SameSite::Strict,
+            CookieSameSite::Lax => SameSite::Lax,
+            CookieSameSite::None => SameSite::None,
+        }
+    }
+}
+
+impl CookieConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值；same_site = "none" 时强制 secure
+    pub fn from_figment(figment: &Figment) -> Self {
+        let mut config: Self = super::section(figment, "cookie");
+
+        if config.same_site == CookieSameSite::None && !config.secure {
+            warn!("cookie.same_site = \"none\" requires secure, enabling cookie.secure");
+            config.secure = true;
+        }
+        config.domain = config.domain.filter(|domain| !domain.is_empty());
+        config
+    }
+
+    /// 登录后下发的会话 Cookie
+    pub fn session_cookie(&self, session_token: &str) -> Cookie<'static> {
+        let mut cookie = self.base_cookie(session_token.to_string());
+        cookie.set_expires(OffsetDateTime::now_utc() + Duration::seconds(self.ttl_secs as i64));
+        cookie
+    }
+
+    /// 用于删除会话 Cookie，域名和路径须与下发时一致，否则浏览器不会删除
+    pub fn removal_cookie(&self) -> Cookie<'static> {
+        self.base_cookie(String::new())
+    }
+
+    fn base_cookie(&self, value: String) -> Cookie<'static> {
+        let mut cookie = Cookie::new(SESSION_COOKIE, value);
+        cookie.set_same_site(SameSite::from(self.same_site));
+        cookie.set_secure(self.secure);
+        cookie.set_http_only(true);
+        cookie.set_path("/");
+        if let Some(domain) = &self.domain {
+            cookie.set_domain(domain.clone());
+        }
+        cookie
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocket::figment::providers::{Format, Toml};
+
+    #[test]
+    fn test_from_figment() {
+        let figment = Figment::new().merge(Toml::string(r#"
+            [cookie]
+            same_site = "none"
+            domain = "example.com"
+            ttl_secs = 3600
+        "#));
+        let config = CookieConfig::from_figment(&figment);
+        assert!(config.secure);
+
+        let cookie = config.session_cookie("token");
+        assert_eq!(cookie.same_site(), Some(SameSite::None));
+        assert_eq!(cookie.secure(), Some(true));
+        assert_eq!(cookie.domain(), Some("example.com"));
+        assert_eq!(config.removal_cookie().domain(), Some("example.com"));
+    }
+}
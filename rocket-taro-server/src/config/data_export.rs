@@ -0,0 +1,31 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 用户数据导出配置（Rocket.toml 中的 `[default.data_export]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DataExportConfig {
+    /// 生成导出文件时使用的本地临时目录，生成后保存到 `[default.storage]` 配置的存储
+    pub directory: String,
+    /// 导出文件保留时长（小时），过期后由存储清理任务删除
+    pub retention_hours: i64,
+    /// 导出任务轮询间隔（秒）
+    pub poll_interval_secs: u64,
+}
+
+impl Default for DataExportConfig {
+    fn default() -> Self {
+        Self {
+            directory: "data/exports".to_string(),
+            retention_hours: 72,
+            poll_interval_secs: 30,
+        }
+    }
+}
+
+impl DataExportConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "data_export")
+    }
+}
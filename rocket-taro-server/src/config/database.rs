@@ -0,0 +1,76 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 数据库 TLS 模式，与 libpq 的 sslmode 含义一致
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SslMode {
+    /// 不使用 TLS
+    Disable,
+    /// 服务器支持时使用 TLS，不验证证书
+    Prefer,
+    /// 必须使用 TLS，不验证证书
+    Require,
+    /// 必须使用 TLS，验证证书链但不验证主机名
+    VerifyCa,
+    /// 必须使用 TLS，验证证书链和主机名
+    VerifyFull,
+}
+
+impl SslMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SslMode::Disable => "disable",
+            SslMode::Prefer => "prefer",
+            SslMode::Require => "require",
+            SslMode::VerifyCa => "verify-ca",
+            SslMode::VerifyFull => "verify-full",
+        }
+    }
+}
+
+/// 数据库连接配置（Rocket.toml 中的 `[default.databases]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DatabaseConfig {
+    /// 连接字符串，环境变量 DATABASE_URL 优先
+    pub database_url: String,
+    /// TLS 模式
+    pub sslmode: SslMode,
+    /// 用于验证服务器证书的 CA 证书（PEM，可包含多个证书），为空时使用内置的公共根证书
+    pub ca_cert_path: Option<String>,
+    /// 连接断开后首次重连失败的等待时间（毫秒），之后每次翻倍
+    pub reconnect_min_backoff_ms: u64,
+    /// 重连等待时间上限（秒）
+    pub reconnect_max_backoff_secs: u64,
+    /// 单条语句超时（毫秒），超时由服务器取消，0 表示不限制
+    pub statement_timeout_ms: u64,
+    /// 慢查询阈值（毫秒），超过时记录日志并计数，0 表示不记录
+    pub slow_query_threshold_ms: u64,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            database_url: "host=192.168.5.222 port=5432 user=user_ck password=ck320621 dbname=postgres".to_string(),
+            sslmode: SslMode::Disable,
+            ca_cert_path: None,
+            reconnect_min_backoff_ms: 500,
+            reconnect_max_backoff_secs: 30,
+            statement_timeout_ms: 30000,
+            slow_query_threshold_ms: 500,
+        }
+    }
+}
+
+impl DatabaseConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值；DATABASE_URL 覆盖连接字符串
+    pub fn from_figment(figment: &Figment) -> Self {
+        let mut config: Self = super::section(figment, "databases");
+
+        if let Ok(database_url) = std::env::var("DATABASE_URL") {
+            config.database_url = database_url;
+        }
+        config
+    }
+}
@@ -0,0 +1,31 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 前端开发模拟模式配置（Rocket.toml 中的 `[default.dev_mock]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DevMockConfig {
+    /// 启用后不连接 PostgreSQL、Redis 和微信，只挂载内存实现的模拟接口
+    pub enabled: bool,
+    /// 请求未携带 X-Mock-User 头和会话令牌时使用的用户，为空则视为未登录
+    pub default_user: Option<String>,
+    /// 具有管理员权限的模拟用户名
+    pub admin_users: Vec<String>,
+}
+
+impl Default for DevMockConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            default_user: None,
+            admin_users: vec!["admin".to_string()],
+        }
+    }
+}
+
+impl DevMockConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值（不启用）
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "dev_mock")
+    }
+}
@@ -0,0 +1,34 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 修改邮箱配置（Rocket.toml 中的 `[default.email_change]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EmailChangeConfig {
+    /// 确认链接有效期（分钟）
+    pub token_ttl_minutes: i64,
+    /// 确认页面地址，邮件中的链接为 `{confirm_url}?token=...`
+    pub confirm_url: String,
+}
+
+impl Default for EmailChangeConfig {
+    fn default() -> Self {
+        Self {
+            token_ttl_minutes: 60,
+            confirm_url: "http://localhost:8000/email-change/confirm".to_string(),
+        }
+    }
+}
+
+impl EmailChangeConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "email_change")
+    }
+
+    /// 邮件中的确认链接
+    pub fn confirm_link(&self, token: &str) -> String {
+        let separator = if self.confirm_url.contains('?') { '&' } else { '?' };
+        format!("{}{}token={}", self.confirm_url, separator, token)
+    }
+}
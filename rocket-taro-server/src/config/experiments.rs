@@ -0,0 +1,134 @@
+use chrono::{DateTime, Utc};
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// 登录引导实验：新用户、首次登录的欢迎提示使用轻提示（toast）还是对话框（dialog）
+pub const LOGIN_ONBOARDING_EXPERIMENT: &str = "login_onboarding";
+
+/// A/B 实验（Rocket.toml 中的 `[default.experiments]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExperimentsConfig {
+    /// 关闭时所有用户都走对照组（未分组）的流程
+    pub enabled: bool,
+    pub definitions: Vec<ExperimentDefinition>,
+}
+
+/// 一个实验：只在 starts_at 到 ends_at 之间分组，时间之外走对照组的流程
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExperimentDefinition {
+    pub name: String,
+    /// 开始时间（RFC 3339），不设置表示立即开始
+    pub starts_at: Option<DateTime<Utc>>,
+    /// 结束时间（RFC 3339），不设置表示不结束
+    pub ends_at: Option<DateTime<Utc>>,
+    pub variants: Vec<ExperimentVariant>,
+}
+
+/// 实验分组，用户按权重比例分配
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExperimentVariant {
+    pub name: String,
+    pub weight: u32,
+}
+
+impl Default for ExperimentsConfig {
+    fn default() -> Self {
+        Self { enabled: true, definitions: Vec::new() }
+    }
+}
+
+impl ExperimentDefinition {
+    /// 当前时间是否在实验时间内
+    pub fn is_running(&self, now: DateTime<Utc>) -> bool {
+        self.starts_at.is_none_or(|starts_at| now >= starts_at) && self.ends_at.is_none_or(|ends_at| now < ends_at)
+    }
+
+    /// 按实验名和用户ID的哈希把用户分到固定的分组，同一用户在实验期间总是同一分组
+    pub fn variant_for(&self, user_id: Uuid) -> Option<&ExperimentVariant> {
+        let total: u64 = self.variants.iter().map(|variant| u64::from(variant.weight)).sum();
+        if total == 0 {
+            return None;
+        }
+        let digest = Sha256::digest(format!("{}:{}", self.name, user_id).as_bytes());
+        let mut bucket = u64::from_be_bytes(digest[..8].try_into().expect("sha256 digest is 32 bytes")) % total;
+        self.variants.iter().find(|variant| {
+            let weight = u64::from(variant.weight);
+            if bucket < weight {
+                return true;
+            }
+            bucket -= weight;
+            false
+        })
+    }
+}
+
+impl ExperimentsConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "experiments")
+    }
+
+    pub fn definition(&self, name: &str) -> Option<&ExperimentDefinition> {
+        self.definitions.iter().find(|definition| definition.name == name)
+    }
+
+    /// 用户在实验中的分组；实验关闭、未配置或不在实验时间内时返回 None
+    pub fn assign(&self, name: &str, user_id: Uuid, now: DateTime<Utc>) -> Option<&str> {
+        if !self.enabled {
+            return None;
+        }
+        self.definition(name)
+            .filter(|definition| definition.is_running(now))
+            .and_then(|definition| definition.variant_for(user_id))
+            .map(|variant| variant.name.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocket::figment::providers::{Format, Toml};
+
+    fn config() -> ExperimentsConfig {
+        let figment = Figment::new().merge(Toml::string(r#"
+            [experiments]
+            [[experiments.definitions]]
+            name = "login_onboarding"
+            starts_at = "2026-10-01T00:00:00Z"
+            ends_at = "2026-11-01T00:00:00Z"
+            variants = [{ name = "toast", weight = 1 }, { name = "dialog", weight = 3 }]
+        "#));
+        ExperimentsConfig::from_figment(&figment)
+    }
+
+    #[test]
+    fn test_assignment_is_stable_and_weighted() {
+        let config = config();
+        let now = "2026-10-16T00:00:00Z".parse().unwrap();
+        let user_id = Uuid::new_v4();
+        let variant = config.assign(LOGIN_ONBOARDING_EXPERIMENT, user_id, now);
+        assert!(variant.is_some());
+        assert_eq!(config.assign(LOGIN_ONBOARDING_EXPERIMENT, user_id, now), variant);
+
+        let dialog = (0..4000)
+            .filter(|_| config.assign(LOGIN_ONBOARDING_EXPERIMENT, Uuid::new_v4(), now) == Some("dialog"))
+            .count();
+        assert!((2700..3300).contains(&dialog), "dialog assigned {} of 4000", dialog);
+    }
+
+    #[test]
+    fn test_no_assignment_outside_time_box() {
+        let config = config();
+        let user_id = Uuid::new_v4();
+        assert_eq!(config.assign(LOGIN_ONBOARDING_EXPERIMENT, user_id, "2026-09-30T23:59:59Z".parse().unwrap()), None);
+        assert_eq!(config.assign(LOGIN_ONBOARDING_EXPERIMENT, user_id, "2026-11-01T00:00:00Z".parse().unwrap()), None);
+        assert_eq!(config.assign("unknown", user_id, "2026-10-16T00:00:00Z".parse().unwrap()), None);
+
+        let disabled = ExperimentsConfig { enabled: false, ..config };
+        assert_eq!(disabled.assign(LOGIN_ONBOARDING_EXPERIMENT, user_id, "2026-10-16T00:00:00Z".parse().unwrap()), None);
+    }
+}
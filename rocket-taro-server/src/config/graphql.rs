@@ -0,0 +1,55 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 管理后台 GraphQL 接口（Rocket.toml 中的 `[default.graphql]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GraphqlConfig {
+    pub enabled: bool,
+    /// 查询的最大嵌套深度
+    pub max_depth: usize,
+    /// 查询的最大复杂度（每个字段计 1，列表字段按 limit 放大）
+    pub max_complexity: usize,
+    /// 是否允许内省查询（GraphiQL 等工具需要）
+    pub introspection: bool,
+    /// 持久化查询（Apollo APQ）在进程内缓存的查询数量
+    pub persisted_query_cache_size: usize,
+}
+
+impl Default for GraphqlConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_depth: 10,
+            max_complexity: 2000,
+            introspection: true,
+            persisted_query_cache_size: 512,
+        }
+    }
+}
+
+impl GraphqlConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "graphql")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocket::figment::providers::{Format, Toml};
+
+    #[test]
+    fn test_from_figment() {
+        let figment = Figment::new().merge(Toml::string(r#"
+            [graphql]
+            introspection = false
+            max_depth = 6
+        "#));
+        let config = GraphqlConfig::from_figment(&figment);
+        assert!(!config.introspection);
+        assert_eq!(config.max_depth, 6);
+        assert_eq!(config.max_complexity, 2000);
+    }
+}
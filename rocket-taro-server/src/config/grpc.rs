@@ -0,0 +1,56 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 内部 gRPC 接口（Rocket.toml 中的 `[default.grpc]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GrpcConfig {
+    pub enabled: bool,
+    /// 监听地址，默认只监听本机；对其他内网服务开放时改为内网地址
+    pub address: String,
+    pub port: u16,
+    /// 调用方在 `authorization: Bearer <token>` 中携带的令牌，为空时不启动 gRPC 服务
+    pub auth_token: String,
+}
+
+impl Default for GrpcConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            address: "127.0.0.1".to_string(),
+            port: 50051,
+            auth_token: String::new(),
+        }
+    }
+}
+
+impl GrpcConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值；GRPC_AUTH_TOKEN 覆盖 auth_token
+    pub fn from_figment(figment: &Figment) -> Self {
+        let mut config: Self = super::section(figment, "grpc");
+
+        if let Ok(auth_token) = std::env::var("GRPC_AUTH_TOKEN") {
+            config.auth_token = auth_token;
+        }
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocket::figment::providers::{Format, Toml};
+
+    #[test]
+    fn test_from_figment() {
+        let figment = Figment::new().merge(Toml::string(r#"
+            [grpc]
+            enabled = true
+            port = 50100
+        "#));
+        let config = GrpcConfig::from_figment(&figment);
+        assert!(config.enabled);
+        assert_eq!(config.port, 50100);
+        assert_eq!(config.address, "127.0.0.1");
+    }
+}
@@ -0,0 +1,88 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// 对外 HTTP 请求的共享客户端（Rocket.toml 中的 `[default.http_client]`），
+/// 微信接口、微信支付、Webhook 投递和邮件告警共用
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HttpClientConfig {
+    /// 建立连接（含 TLS 握手）的超时（秒）
+    pub connect_timeout_secs: u64,
+    /// 单次请求的总超时（秒），各调用方配置了更短的超时时以调用方为准
+    pub timeout_secs: u64,
+    /// 空闲连接保留时长（秒）
+    pub pool_idle_timeout_secs: u64,
+    /// 每个主机保留的空闲连接数上限
+    pub pool_max_idle_per_host: usize,
+    /// 代理地址，如 `http://proxy.internal:3128`；为空时沿用 HTTP_PROXY / HTTPS_PROXY 环境变量
+    pub proxy: Option<String>,
+    /// 不经过代理的主机，逗号分隔，格式同 NO_PROXY 环境变量
+    pub no_proxy: Option<String>,
+    /// 幂等请求失败后的最大重试次数，0 表示不重试
+    pub max_retries: u32,
+    /// 首次重试的最长等待时间（毫秒），之后按指数增长，实际等待时间在其中随机
+    pub retry_base_ms: u64,
+    /// 重试等待时间上限（毫秒）
+    pub retry_max_ms: u64,
+}
+
+impl Default for HttpClientConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout_secs: 5,
+            timeout_secs: 30,
+            pool_idle_timeout_secs: 90,
+            pool_max_idle_per_host: 16,
+            proxy: None,
+            no_proxy: None,
+            max_retries: 2,
+            retry_base_ms: 200,
+            retry_max_ms: 2000,
+        }
+    }
+}
+
+impl HttpClientConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "http_client")
+    }
+
+    /// 第 `attempt` 次重试（从 0 开始）前的等待时间：上限按指数增长，`sample` 为 [0, 1) 的随机数
+    pub fn retry_delay(&self, attempt: u32, sample: f64) -> Duration {
+        let ceiling = self.retry_base_ms.saturating_mul(1 << attempt.min(16)).min(self.retry_max_ms);
+        Duration::from_millis((ceiling as f64 * sample.clamp(0.0, 1.0)) as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocket::figment::providers::{Format, Toml};
+
+    #[test]
+    fn test_from_figment() {
+        let figment = Figment::new().merge(Toml::string(r#"
+            [http_client]
+            connect_timeout_secs = 2
+            proxy = "http://proxy.internal:3128"
+            max_retries = 0
+        "#));
+        let config = HttpClientConfig::from_figment(&figment);
+        assert_eq!(config.connect_timeout_secs, 2);
+        assert_eq!(config.proxy.as_deref(), Some("http://proxy.internal:3128"));
+        assert_eq!(config.max_retries, 0);
+        assert_eq!(config.timeout_secs, 30);
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        let config = HttpClientConfig::default();
+        assert_eq!(config.retry_delay(0, 0.0), Duration::ZERO);
+        assert_eq!(config.retry_delay(0, 0.5), Duration::from_millis(100));
+        assert_eq!(config.retry_delay(2, 1.0), Duration::from_millis(800));
+        assert_eq!(config.retry_delay(10, 1.0), Duration::from_millis(2000));
+        assert_eq!(config.retry_delay(40, 1.0), Duration::from_millis(2000));
+    }
+}
@@ -0,0 +1,53 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 实名认证服务客户端实现
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IdentityClientKind {
+    /// 通过 HTTP 调用第三方实名认证服务
+    Http,
+    /// 只校验身份证号格式和校验位，用于本地开发和测试
+    Mock,
+}
+
+/// 实名认证和年龄限制（Rocket.toml 中的 `[default.identity_verification]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IdentityVerificationConfig {
+    /// 认证服务客户端实现
+    pub client: IdentityClientKind,
+    /// 认证服务地址，接收 `{ real_name, id_number }` JSON，查询结果时请求 `{api_url}/{reference}`
+    pub api_url: String,
+    /// 认证服务的 Bearer 令牌
+    pub api_key: String,
+    /// 单次请求超时（秒）
+    pub request_timeout_secs: u64,
+    /// 是否拦截未实名或未成年用户；关闭时守卫直接放行，认证接口仍可使用
+    pub enforce: bool,
+    /// 年龄限制（周岁）
+    pub min_age: u32,
+    /// 每个用户每天最多提交次数，0 表示不限制
+    pub max_attempts_per_day: u32,
+}
+
+impl Default for IdentityVerificationConfig {
+    fn default() -> Self {
+        Self {
+            client: IdentityClientKind::Mock,
+            api_url: String::new(),
+            api_key: String::new(),
+            request_timeout_secs: 10,
+            enforce: false,
+            min_age: 18,
+            max_attempts_per_day: 5,
+        }
+    }
+}
+
+impl IdentityVerificationConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "identity_verification")
+    }
+}
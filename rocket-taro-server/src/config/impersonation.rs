@@ -0,0 +1,32 @@
+use chrono::Duration;
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 管理员模拟登录配置（Rocket.toml 中的 `[default.impersonation]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImpersonationConfig {
+    pub enabled: bool,
+    /// 模拟登录会话有效期（分钟），不能延长
+    pub session_ttl_minutes: i64,
+}
+
+impl Default for ImpersonationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            session_ttl_minutes: 60,
+        }
+    }
+}
+
+impl ImpersonationConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "impersonation")
+    }
+
+    pub fn session_ttl(&self) -> Duration {
+        Duration::minutes(self.session_ttl_minutes.max(1))
+    }
+}
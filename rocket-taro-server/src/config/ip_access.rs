@@ -0,0 +1,152 @@
+use ipnet::IpNet;
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use tracing::warn;
+
+/// 管理接口来源 IP 限制配置（Rocket.toml 中的 `[default.ip_access]`，运行期间定时重新加载）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IpAccessConfig {
+    /// 受限制的接口路径前缀
+    pub protected_prefixes: Vec<String>,
+    /// 允许访问的 CIDR 或 IP，为空时不限制来源
+    pub allow: Vec<String>,
+    /// 禁止访问的 CIDR 或 IP，优先于 allow
+    pub deny: Vec<String>,
+    /// 重新加载配置的间隔（秒）
+    pub reload_interval_secs: u64,
+}
+
+impl Default for IpAccessConfig {
+    fn default() -> Self {
+        Self {
+            protected_prefixes: vec!["/api/admin/".to_string(), "/api/cache/".to_string(), "/api/graphql".to_string()],
+            allow: Vec::new(),
+            deny: Vec::new(),
+            reload_interval_secs: 30,
+        }
+    }
+}
+
+impl IpAccessConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "ip_access")
+    }
+}
+
+/// 请求被拦截的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IpBlockReason {
+    /// 命中禁止列表
+    Denied,
+    /// 不在允许列表中
+    NotAllowed,
+    /// 无法确定来源 IP
+    UnknownAddress,
+}
+
+impl IpBlockReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IpBlockReason::Denied => "denied",
+            IpBlockReason::NotAllowed => "not_allowed",
+            IpBlockReason::UnknownAddress => "unknown_address",
+        }
+    }
+}
+
+/// 解析后的访问规则
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IpAccessRules {
+    pub protected_prefixes: Vec<String>,
+    pub allow: Vec<IpNet>,
+    pub deny: Vec<IpNet>,
+}
+
+impl IpAccessRules {
+    /// 解析配置，无效的条目记录警告后忽略
+    pub fn from_config(config: &IpAccessConfig) -> Self {
+        Self {
+            protected_prefixes: config.protected_prefixes.clone(),
+            allow: parse_networks("ip_access.allow", &config.allow),
+            deny: parse_networks("ip_access.deny", &config.deny),
+        }
+    }
+
+    /// 路径是否受来源 IP 限制
+    pub fn is_protected(&self, path: &str) -> bool {
+        self.protected_prefixes.iter().any(|prefix| path.starts_with(prefix.as_str()))
+    }
+
+    /// 检查来源 IP：先匹配禁止列表，允许列表非空时必须命中允许列表
+    pub fn check(&self, ip: Option<IpAddr>) -> Result<(), IpBlockReason> {
+        if self.allow.is_empty() && self.deny.is_empty() {
+            return Ok(());
+        }
+        let Some(ip) = ip.map(|ip| ip.to_canonical()) else {
+            return Err(IpBlockReason::UnknownAddress);
+        };
+        if self.deny.iter().any(|net| net.contains(&ip)) {
+            return Err(IpBlockReason::Denied);
+        }
+        if !self.allow.is_empty() && !self.allow.iter().any(|net| net.contains(&ip)) {
+            return Err(IpBlockReason::NotAllowed);
+        }
+        Ok(())
+    }
+}
+
+/// 单个 IP 视为 /32（IPv6 为 /128）
+pub(crate) fn parse_networks(list: &str, entries: &[String]) -> Vec<IpNet> {
+    entries.iter()
+        .filter_map(|entry| {
+            let entry = entry.trim();
+            let parsed = entry.parse::<IpNet>()
+                .or_else(|_| entry.parse::<IpAddr>().map(IpNet::from));
+            if parsed.is_err() {
+                warn!("Ignoring invalid {} entry: {}", list, entry);
+            }
+            parsed.ok()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(allow: &[&str], deny: &[&str]) -> IpAccessRules {
+        IpAccessRules::from_config(&IpAccessConfig {
+            allow: allow.iter().map(|s| s.to_string()).collect(),
+            deny: deny.iter().map(|s| s.to_string()).collect(),
+            ..IpAccessConfig::default()
+        })
+    }
+
+    fn ip(s: &str) -> Option<IpAddr> {
+        Some(s.parse().unwrap())
+    }
+
+    #[test]
+    fn test_empty_lists_allow_everyone() {
+        let rules = rules(&[], &[]);
+        assert!(rules.check(ip("203.0.113.7")).is_ok());
+        assert!(rules.check(None).is_ok());
+        assert!(rules.is_protected("/api/admin/stats"));
+        assert!(!rules.is_protected("/api/auth/login"));
+    }
+
+    #[test]
+    fn test_allow_and_deny_lists() {
+        let rules = rules(&["10.0.0.0/8", "192.168.1.20", "not-a-cidr"], &["10.0.9.0/24"]);
+        assert_eq!(rules.allow.len(), 2);
+        assert!(rules.check(ip("10.1.2.3")).is_ok());
+        assert!(rules.check(ip("192.168.1.20")).is_ok());
+        assert!(rules.check(ip("::ffff:10.1.2.3")).is_ok());
+        assert_eq!(rules.check(ip("10.0.9.1")), Err(IpBlockReason::Denied));
+        assert_eq!(rules.check(ip("192.168.1.21")), Err(IpBlockReason::NotAllowed));
+        assert_eq!(rules.check(None), Err(IpBlockReason::UnknownAddress));
+    }
+}
@@ -0,0 +1,75 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+use crate::models::log_archive::ArchiveTable;
+
+/// 单个日志表的归档策略
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivePolicy {
+    /// 超过该天数的记录移入按月分区的归档表，0 表示不归档
+    pub archive_after_days: i64,
+    /// 保留天数，0 表示不清理；归档表按整月删除分区
+    pub retention_days: i64,
+}
+
+/// 日志归档配置（Rocket.toml 中的 `[default.log_archive]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LogArchiveConfig {
+    /// 归档任务执行间隔（秒）
+    pub interval_secs: u64,
+    /// 每批移动或删除的记录数
+    pub batch_size: i64,
+    /// 每个表每轮最多执行的批次，剩余记录留到下一轮
+    pub max_batches: u32,
+    pub login_logs: ArchivePolicy,
+    pub audit_logs: ArchivePolicy,
+}
+
+impl Default for LogArchiveConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: 3600,
+            batch_size: 5000,
+            max_batches: 20,
+            // 管理后台统计最长查询90天的登录记录，在线表至少保留90天
+            login_logs: ArchivePolicy { archive_after_days: 90, retention_days: 180 },
+            audit_logs: ArchivePolicy { archive_after_days: 90, retention_days: 0 },
+        }
+    }
+}
+
+impl LogArchiveConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "log_archive")
+    }
+
+    pub fn policy(&self, table: ArchiveTable) -> &ArchivePolicy {
+        match table {
+            ArchiveTable::LoginLogs => &self.login_logs,
+            ArchiveTable::AuditLogs => &self.audit_logs,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocket::figment::providers::{Format, Toml};
+
+    #[test]
+    fn test_from_figment() {
+        let figment = Figment::new().merge(Toml::string(r#"
+            [log_archive]
+            batch_size = 1000
+            audit_logs = { archive_after_days = 30, retention_days = 730 }
+        "#));
+        let config = LogArchiveConfig::from_figment(&figment);
+        assert_eq!(config.batch_size, 1000);
+        assert_eq!(config.interval_secs, 3600);
+        assert_eq!(config.policy(ArchiveTable::AuditLogs).archive_after_days, 30);
+        assert_eq!(config.policy(ArchiveTable::AuditLogs).retention_days, 730);
+        assert_eq!(config.policy(ArchiveTable::LoginLogs).retention_days, 180);
+    }
+}
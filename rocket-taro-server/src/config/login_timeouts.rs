@@ -0,0 +1,44 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// 登录链路中每次访问依赖的时间预算（Rocket.toml 中的 `[default.login_timeouts]`），单位毫秒
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoginTimeoutConfig {
+    /// 账户锁定检查、微信登录防重放等 Redis 操作
+    pub redis_ms: u64,
+    /// 查询用户、创建会话等数据库操作
+    pub postgres_ms: u64,
+    /// 微信 code2session 接口，包含 HTTP 客户端的重试
+    pub wechat_ms: u64,
+}
+
+impl Default for LoginTimeoutConfig {
+    fn default() -> Self {
+        Self {
+            redis_ms: 300,
+            postgres_ms: 3000,
+            wechat_ms: 8000,
+        }
+    }
+}
+
+impl LoginTimeoutConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "login_timeouts")
+    }
+
+    pub fn redis(&self) -> Duration {
+        Duration::from_millis(self.redis_ms)
+    }
+
+    pub fn postgres(&self) -> Duration {
+        Duration::from_millis(self.postgres_ms)
+    }
+
+    pub fn wechat(&self) -> Duration {
+        Duration::from_millis(self.wechat_ms)
+    }
+}
@@ -0,0 +1,47 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 邮件发送客户端实现
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MailClientKind {
+    /// 通过 HTTP 邮件网关发送
+    Http,
+    /// 只写入日志，用于本地开发和测试
+    Log,
+}
+
+/// 邮件配置（Rocket.toml 中的 `[default.mail]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MailConfig {
+    /// 发送客户端实现
+    pub client: MailClientKind,
+    /// 邮件网关地址，接收 `{ from, to, subject, text }` JSON
+    pub api_url: String,
+    /// 邮件网关的 Bearer 令牌
+    pub api_key: String,
+    /// 发件人地址
+    pub from: String,
+    /// 单次请求超时（秒）
+    pub request_timeout_secs: u64,
+}
+
+impl Default for MailConfig {
+    fn default() -> Self {
+        Self {
+            client: MailClientKind::Log,
+            api_url: String::new(),
+            api_key: String::new(),
+            from: "no-reply@rocket-taro.com".to_string(),
+            request_timeout_secs: 10,
+        }
+    }
+}
+
+impl MailConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "mail")
+    }
+}
@@ -0,0 +1,55 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 维护模式配置（Rocket.toml 中的 `[default.maintenance]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MaintenanceConfig {
+    /// 维护期间仍可访问的接口：以 `/` 结尾的按前缀匹配，其余按完整路径匹配
+    pub allowlist: Vec<String>,
+}
+
+impl Default for MaintenanceConfig {
+    fn default() -> Self {
+        Self {
+            allowlist: vec![
+                "/api/auth/login".to_string(),
+                "/api/auth/status".to_string(),
+                "/api/admin/".to_string(),
+                "/api/metrics/".to_string(),
+            ],
+        }
+    }
+}
+
+impl MaintenanceConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "maintenance")
+    }
+
+    /// 路径是否在白名单中
+    pub fn is_allowed(&self, path: &str) -> bool {
+        self.allowlist.iter().any(|entry| {
+            if entry.ends_with('/') {
+                path.starts_with(entry.as_str())
+            } else {
+                path == entry
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allowlist_matching() {
+        let config = MaintenanceConfig::default();
+        assert!(config.is_allowed("/api/auth/login"));
+        assert!(config.is_allowed("/api/admin/maintenance"));
+        assert!(!config.is_allowed("/api/auth/login/extra"));
+        assert!(!config.is_allowed("/api/user-data"));
+    }
+}
@@ -1,3 +1,138 @@
 pub mod route_config;
+pub mod route_keys;
+pub mod account;
+pub mod data_export;
+pub mod wechat_pay;
+pub mod order;
+pub mod webhook;
+pub mod side_effect;
+pub mod password;
+pub mod wechat;
+pub mod dev_mock;
+pub mod database;
+pub mod cache;
+pub mod account_flags;
+pub mod maintenance;
+pub mod session_expiry;
+pub mod ip_access;
+pub mod body_limits;
+pub mod request_log;
+pub mod mail;
+pub mod email_change;
+pub mod qr_login;
+pub mod session_limits;
+pub mod recent_auth;
+pub mod user_data;
+pub mod upload;
+pub mod log_archive;
+pub mod session_binding;
+pub mod impersonation;
+pub mod cache_warmup;
+pub mod response_profile;
+pub mod batch;
+pub mod graphql;
+pub mod grpc;
+pub mod telemetry;
+pub mod slo;
+pub mod startup_check;
+pub mod cookie;
+pub mod http_client;
+pub mod login_timeouts;
+pub mod concurrency_limits;
+pub mod presence;
+pub mod api_quotas;
+pub mod registration_guard;
+pub mod identity;
+pub mod moderation;
+pub mod avatar;
+pub mod storage;
+pub mod route_trace;
+pub mod route_command_metadata;
+pub mod experiments;
+pub mod proxy;
 
-pub use route_config::*;
\ No newline at end of file
+pub use route_config::*;
+pub use account::AccountConfig;
+pub use data_export::DataExportConfig;
+pub use wechat_pay::WechatPayConfig;
+pub use order::OrderConfig;
+pub use webhook::WebhookConfig;
+pub use side_effect::SideEffectConfig;
+pub use password::{PasswordConfig, PasswordAlgorithm};
+pub use wechat::{WechatConfig, WxApiClientKind, WatermarkConfig, WatermarkMode};
+pub use dev_mock::DevMockConfig;
+pub use database::{DatabaseConfig, SslMode};
+pub use cache::{CacheBreakerConfig, CacheConfig, CacheTtlConfig, ListCacheConfig, LocalCacheConfig, RedisTopology};
+pub use account_flags::AccountFlagsConfig;
+pub use maintenance::MaintenanceConfig;
+pub use session_expiry::SessionExpiryConfig;
+pub use ip_access::{IpAccessConfig, IpAccessRules};
+pub use body_limits::{BodyLimitsConfig, BodyKind};
+pub use request_log::RequestLogConfig;
+pub use mail::{MailConfig, MailClientKind};
+pub use email_change::EmailChangeConfig;
+pub use qr_login::QrLoginConfig;
+pub use session_limits::SessionLimitsConfig;
+pub use recent_auth::RecentAuthConfig;
+pub use user_data::UserDataConfig;
+pub use upload::UploadConfig;
+pub use log_archive::LogArchiveConfig;
+pub use session_binding::SessionBindingConfig;
+pub use impersonation::ImpersonationConfig;
+pub use cache_warmup::{CacheWarmupConfig, WarmupDataset};
+pub use response_profile::ResponseProfileConfig;
+pub use batch::BatchConfig;
+pub use graphql::GraphqlConfig;
+pub use grpc::GrpcConfig;
+pub use telemetry::TelemetryConfig;
+pub use slo::{SloConfig, SloObjective};
+pub use startup_check::StartupCheckConfig;
+pub use cookie::CookieConfig;
+pub use http_client::HttpClientConfig;
+pub use login_timeouts::LoginTimeoutConfig;
+pub use concurrency_limits::ConcurrencyLimitsConfig;
+pub use presence::PresenceConfig;
+pub use api_quotas::ApiQuotasConfig;
+pub use registration_guard::RegistrationGuardConfig;
+pub use identity::{IdentityVerificationConfig, IdentityClientKind};
+pub use moderation::{ModerationConfig, ModerationProviderKind};
+pub use avatar::AvatarConfig;
+pub use storage::{StorageConfig, StorageBackendKind, S3StorageConfig};
+pub use route_trace::RouteTraceConfig;
+pub use route_command_metadata::RouteCommandMetadataConfig;
+pub use experiments::ExperimentsConfig;
+pub use proxy::{ProxyConfig, TrustedProxies};
+
+use rocket::figment::Figment;
+use serde::de::DeserializeOwned;
+use tracing::warn;
+
+/// 读取 Rocket 配置中的 `[default.<name>]`，缺失或格式错误时使用默认值
+pub fn section<T: Default + DeserializeOwned>(figment: &Figment, name: &str) -> T {
+    if !figment.contains(name) {
+        return T::default();
+    }
+    figment.extract_inner(name).unwrap_or_else(|e| {
+        warn!("Invalid [{}] configuration, using defaults: {}", name, e);
+        T::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocket::figment::providers::{Format, Toml};
+
+    #[test]
+    fn test_section_falls_back_to_default() {
+        let figment = Figment::new().merge(Toml::string(r#"
+            [proxy]
+            trusted_proxies = "not-a-list"
+        "#));
+        let config: ProxyConfig = section(&figment, "proxy");
+        assert_eq!(config.trusted_proxies, ProxyConfig::default().trusted_proxies);
+
+        let config: GraphqlConfig = section(&figment, "graphql");
+        assert_eq!(config.max_depth, GraphqlConfig::default().max_depth);
+    }
+}
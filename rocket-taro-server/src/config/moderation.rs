@@ -0,0 +1,62 @@
+use rocket::data::{ByteUnit, ToByteUnit};
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 内容审核服务实现
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModerationProviderKind {
+    /// 微信内容安全接口（msgSecCheck / imgSecCheck），使用 `[default.wechat]` 的 AppID 和 AppSecret
+    Wechat,
+    /// 本地关键词列表，不审核图片，用于本地开发和没有接入第三方服务的部署
+    Keyword,
+}
+
+/// 用户提交内容审核（Rocket.toml 中的 `[default.moderation]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModerationConfig {
+    pub enabled: bool,
+    /// 审核服务实现
+    pub provider: ModerationProviderKind,
+    /// 关键词审核：包含即拒绝（不区分大小写）
+    pub blocked_keywords: Vec<String>,
+    /// 关键词审核：包含时放行并加入复核队列
+    pub review_keywords: Vec<String>,
+    /// 审核服务不可用时是否放行；放行的文本加入复核队列，图片不再复核
+    pub fail_open: bool,
+    /// 超过该大小的图片不送审（微信接口限制 1MB）
+    pub image_max_size: ByteUnit,
+    /// 复核任务执行间隔（秒）
+    pub recheck_interval_secs: u64,
+    /// 加入队列后首次复核及每次重试的间隔（秒）
+    pub recheck_delay_secs: i64,
+    /// 最多复核次数，仍无结论时标记为 failed 等待人工处理
+    pub recheck_max_attempts: i32,
+    /// 每次复核的最大记录数
+    pub recheck_batch_size: i64,
+}
+
+impl Default for ModerationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            provider: ModerationProviderKind::Keyword,
+            blocked_keywords: Vec::new(),
+            review_keywords: Vec::new(),
+            fail_open: true,
+            image_max_size: 1.mebibytes(),
+            recheck_interval_secs: 60,
+            recheck_delay_secs: 300,
+            recheck_max_attempts: 5,
+            recheck_batch_size: 50,
+        }
+    }
+}
+
+impl ModerationConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "moderation")
+    }
+}
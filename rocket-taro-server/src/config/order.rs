@@ -0,0 +1,37 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 订单配置（Rocket.toml 中的 `[default.order]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OrderConfig {
+    /// 未支付订单的自动关闭时长（分钟）
+    pub payment_timeout_minutes: i64,
+    /// 单个订单最多包含的商品数
+    pub max_items: usize,
+    /// 单个订单金额上限（分）
+    pub max_amount: i64,
+    /// 超时关闭任务的执行间隔（秒）
+    pub expiry_interval_secs: u64,
+    /// 超时关闭任务每次最多处理的订单数
+    pub expiry_batch_size: i64,
+}
+
+impl Default for OrderConfig {
+    fn default() -> Self {
+        Self {
+            payment_timeout_minutes: 30,
+            max_items: 50,
+            max_amount: 10_000_000,
+            expiry_interval_secs: 60,
+            expiry_batch_size: 200,
+        }
+    }
+}
+
+impl OrderConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "order")
+    }
+}
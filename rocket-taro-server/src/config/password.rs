@@ -0,0 +1,45 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 新密码使用的哈希算法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PasswordAlgorithm {
+    Argon2id,
+    Bcrypt,
+}
+
+/// 密码哈希配置（Rocket.toml 中的 `[default.password]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PasswordConfig {
+    /// 新密码及登录时重新哈希使用的算法
+    pub algorithm: PasswordAlgorithm,
+    /// Argon2id 内存开销（KiB）
+    pub argon2_memory_kib: u32,
+    /// Argon2id 迭代次数
+    pub argon2_iterations: u32,
+    /// Argon2id 并行度
+    pub argon2_parallelism: u32,
+    /// bcrypt 开销因子（4-31）
+    pub bcrypt_cost: u32,
+}
+
+impl Default for PasswordConfig {
+    fn default() -> Self {
+        Self {
+            algorithm: PasswordAlgorithm::Argon2id,
+            argon2_memory_kib: 19456,
+            argon2_iterations: 2,
+            argon2_parallelism: 1,
+            bcrypt_cost: 12,
+        }
+    }
+}
+
+impl PasswordConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "password")
+    }
+}
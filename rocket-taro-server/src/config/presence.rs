@@ -0,0 +1,55 @@
+use chrono::{DateTime, Duration, Utc};
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 在线用户统计（Rocket.toml 中的 `[default.presence]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PresenceConfig {
+    pub enabled: bool,
+    /// 最近多长时间内（秒）有请求的用户视为在线
+    pub online_window_secs: i64,
+}
+
+impl Default for PresenceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            online_window_secs: 300,
+        }
+    }
+}
+
+impl PresenceConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "presence")
+    }
+
+    /// 最后访问早于该时间的用户不再计为在线
+    pub fn online_since(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        now - Duration::seconds(self.online_window_secs.max(1))
+    }
+
+    /// 在线集合的过期时间（秒）：窗口内没有任何请求时整个集合自动删除
+    pub fn key_ttl_secs(&self) -> usize {
+        self.online_window_secs.max(1) as usize * 2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocket::figment::providers::{Format, Toml};
+
+    #[test]
+    fn test_online_window() {
+        let figment = Figment::new().merge(Toml::string("[presence]\nonline_window_secs = 60"));
+        let config = PresenceConfig::from_figment(&figment);
+        assert!(config.enabled);
+        assert_eq!(config.key_ttl_secs(), 120);
+
+        let now = DateTime::from_timestamp(1_800_000_000, 0).unwrap();
+        assert_eq!(config.online_since(now).timestamp(), 1_799_999_940);
+    }
+}
@@ -0,0 +1,99 @@
+use ipnet::IpNet;
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+
+use super::ip_access::parse_networks;
+
+/// 反向代理配置（Rocket.toml 中的 `[default.proxy]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProxyConfig {
+    /// 可信代理的 CIDR 或 IP，只有直连来源在其中时才采用 X-Real-IP / X-Forwarded-For；
+    /// 默认只信任本机，批量请求的子请求经本机回环地址转发
+    pub trusted_proxies: Vec<String>,
+}
+
+impl Default for ProxyConfig {
+    fn default() -> Self {
+        Self { trusted_proxies: vec!["127.0.0.1/32".to_string(), "::1/128".to_string()] }
+    }
+}
+
+impl ProxyConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "proxy")
+    }
+}
+
+/// 解析后的可信代理列表，请求守卫据此确定客户端 IP
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrustedProxies(Vec<IpNet>);
+
+impl Default for TrustedProxies {
+    fn default() -> Self {
+        Self::from_config(&ProxyConfig::default())
+    }
+}
+
+impl TrustedProxies {
+    /// 解析配置，无效的条目记录警告后忽略
+    pub fn from_config(config: &ProxyConfig) -> Self {
+        Self(parse_networks("proxy.trusted_proxies", &config.trusted_proxies))
+    }
+
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        let ip = ip.to_canonical();
+        self.0.iter().any(|net| net.contains(&ip))
+    }
+
+    /// 客户端 IP：直连来源不是可信代理时直接使用直连来源，转发请求头可被客户端伪造；
+    /// 是可信代理时优先取 X-Real-IP，其次取 X-Forwarded-For 中从右往左第一个不是可信代理的地址
+    pub fn client_ip(&self, peer: Option<IpAddr>, real_ip: Option<&str>, forwarded_for: Option<&str>) -> Option<IpAddr> {
+        let peer = peer?;
+        if !self.contains(peer) {
+            return Some(peer);
+        }
+        if let Some(ip) = real_ip.and_then(|ip| ip.trim().parse().ok()) {
+            return Some(ip);
+        }
+        let forwarded: Vec<IpAddr> = forwarded_for
+            .map(|forwarded| forwarded.split(',').filter_map(|ip| ip.trim().parse().ok()).collect())
+            .unwrap_or_default();
+        forwarded.iter().rev()
+            .find(|ip| !self.contains(**ip))
+            .or(forwarded.first())
+            .copied()
+            .or(Some(peer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(s: &str) -> Option<IpAddr> {
+        Some(s.parse().unwrap())
+    }
+
+    #[test]
+    fn test_headers_ignored_from_untrusted_peer() {
+        let proxies = TrustedProxies::default();
+        assert_eq!(proxies.client_ip(ip("203.0.113.7"), Some("10.0.0.1"), Some("10.0.0.2")), ip("203.0.113.7"));
+        assert_eq!(proxies.client_ip(None, Some("10.0.0.1"), None), None);
+    }
+
+    #[test]
+    fn test_headers_honored_from_trusted_peer() {
+        let proxies = TrustedProxies::from_config(&ProxyConfig {
+            trusted_proxies: vec!["127.0.0.1".to_string(), "10.0.0.0/8".to_string(), "not-a-cidr".to_string()],
+        });
+        assert_eq!(proxies.client_ip(ip("10.1.2.3"), Some("198.51.100.4"), None), ip("198.51.100.4"));
+        assert_eq!(proxies.client_ip(ip("::ffff:127.0.0.1"), Some("198.51.100.4"), None), ip("198.51.100.4"));
+        // 客户端自带的 X-Forwarded-For 排在左侧，取最右侧不是可信代理的地址
+        assert_eq!(proxies.client_ip(ip("10.1.2.3"), None, Some("1.2.3.4, 198.51.100.4, 10.9.9.9")), ip("198.51.100.4"));
+        assert_eq!(proxies.client_ip(ip("10.1.2.3"), None, Some("10.0.0.7, 10.9.9.9")), ip("10.0.0.7"));
+        assert_eq!(proxies.client_ip(ip("10.1.2.3"), Some("garbage"), Some("garbage")), ip("10.1.2.3"));
+    }
+}
@@ -0,0 +1,34 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 管理后台扫码登录配置（Rocket.toml 中的 `[default.qr_login]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QrLoginConfig {
+    /// 二维码有效期（秒），过期后网页端需要重新获取
+    pub token_ttl_secs: u64,
+    /// 二维码内容前缀，二维码内容为 `{qr_url}?id=...`，小程序扫码后解析 id
+    pub qr_url: String,
+}
+
+impl Default for QrLoginConfig {
+    fn default() -> Self {
+        Self {
+            token_ttl_secs: 120,
+            qr_url: "rocket-taro://qr-login".to_string(),
+        }
+    }
+}
+
+impl QrLoginConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "qr_login")
+    }
+
+    /// 二维码内容
+    pub fn qr_content(&self, login_id: &str) -> String {
+        let separator = if self.qr_url.contains('?') { '&' } else { '?' };
+        format!("{}{}id={}", self.qr_url, separator, login_id)
+    }
+}
@@ -0,0 +1,50 @@
+use chrono::{DateTime, Duration, Utc};
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 敏感操作的二次验证配置（Rocket.toml 中的 `[default.recent_auth]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecentAuthConfig {
+    /// 密码验证后多长时间内（秒）可以执行敏感操作，超过后需重新验证
+    pub max_age_secs: i64,
+}
+
+impl Default for RecentAuthConfig {
+    fn default() -> Self {
+        Self { max_age_secs: 300 }
+    }
+}
+
+impl RecentAuthConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "recent_auth")
+    }
+
+    /// 验证时间是否仍在有效期内
+    pub fn is_recent(&self, confirmed_at: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        confirmed_at <= now && now - confirmed_at <= Duration::seconds(self.max_age_secs)
+    }
+
+    /// 二次验证的失效时间
+    pub fn valid_until(&self, confirmed_at: DateTime<Utc>) -> DateTime<Utc> {
+        confirmed_at + Duration::seconds(self.max_age_secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_recent() {
+        let config = RecentAuthConfig::default();
+        let now = Utc::now();
+        assert!(config.is_recent(now - Duration::seconds(60), now));
+        assert!(config.is_recent(now - Duration::seconds(300), now));
+        assert!(!config.is_recent(now - Duration::seconds(301), now));
+        // 时间在未来的记录视为无效
+        assert!(!config.is_recent(now + Duration::seconds(60), now));
+    }
+}
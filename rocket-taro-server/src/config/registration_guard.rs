@@ -0,0 +1,93 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 注册防自动化（Rocket.toml 中的 `[default.registration_guard]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RegistrationGuardConfig {
+    pub enabled: bool,
+    /// 是否校验蜜罐字段，真实用户看不到该字段，填写了即视为机器人
+    pub honeypot_enabled: bool,
+    /// 计数窗口（秒），从窗口内第一次注册开始计算
+    pub window_secs: u64,
+    /// 窗口内同一 IP 最多注册次数，超过直接拒绝，0 表示不限制
+    pub max_per_ip: u64,
+    /// 窗口内同一设备最多注册次数，超过直接拒绝，0 表示不限制
+    pub max_per_device: u64,
+    /// 同一 IP 达到该次数后新账号进入人工审核队列，0 表示不标记
+    pub review_per_ip: u64,
+    /// 同一设备达到该次数后新账号进入人工审核队列，0 表示不标记
+    pub review_per_device: u64,
+    /// 客户端上报的填表耗时（毫秒）低于该值时进入人工审核队列，0 表示不检查
+    pub min_fill_ms: u64,
+    /// 禁止注册的一次性邮箱域名，子域名同样拦截
+    pub blocked_email_domains: Vec<String>,
+}
+
+impl Default for RegistrationGuardConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            honeypot_enabled: true,
+            window_secs: 3600,
+            max_per_ip: 10,
+            max_per_device: 5,
+            review_per_ip: 3,
+            review_per_device: 2,
+            min_fill_ms: 2000,
+            blocked_email_domains: [
+                "mailinator.com",
+                "yopmail.com",
+                "guerrillamail.com",
+                "10minutemail.com",
+                "temp-mail.org",
+                "trashmail.com",
+                "sharklasers.com",
+                "getnada.com",
+            ].into_iter().map(String::from).collect(),
+        }
+    }
+}
+
+impl RegistrationGuardConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "registration_guard")
+    }
+
+    /// 邮箱域名是否在一次性邮箱列表中（不区分大小写，包括子域名）
+    pub fn is_blocked_email(&self, email: &str) -> bool {
+        let Some((_, domain)) = email.trim().rsplit_once('@') else {
+            return false;
+        };
+        let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+        self.blocked_email_domains.iter().any(|blocked| {
+            let blocked = blocked.trim().to_ascii_lowercase();
+            !blocked.is_empty()
+                && (domain == blocked || domain.strip_suffix(&blocked).is_some_and(|prefix| prefix.ends_with('.')))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocket::figment::providers::{Format, Toml};
+
+    #[test]
+    fn test_blocked_email_domains() {
+        let figment = Figment::new().merge(Toml::string(
+            "[registration_guard]\nblocked_email_domains = [\"Mailinator.com\", \"\"]",
+        ));
+        let config = RegistrationGuardConfig::from_figment(&figment);
+        assert!(config.enabled);
+        assert_eq!(config.max_per_ip, 10);
+
+        assert!(config.is_blocked_email("bot@mailinator.com"));
+        assert!(config.is_blocked_email("bot@MAILINATOR.COM."));
+        assert!(config.is_blocked_email("bot@eu.mailinator.com"));
+        assert!(!config.is_blocked_email("someone@notmailinator.com"));
+        assert!(!config.is_blocked_email("someone@example.com"));
+        assert!(!config.is_blocked_email(""));
+    }
+}
@@ -0,0 +1,73 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// API 请求日志配置（Rocket.toml 中的 `[default.request_log]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RequestLogConfig {
+    pub enabled: bool,
+    /// 记录的路径前缀，静态文件等其他路径不记录
+    pub path_prefixes: Vec<String>,
+    /// 不记录的路径前缀，例如健康检查
+    pub exclude_prefixes: Vec<String>,
+    /// 正常请求的采样比例（0.0 - 1.0），5xx 和慢请求始终记录
+    pub sample_rate: f64,
+    /// 超过该耗时（毫秒）的请求视为慢请求
+    pub slow_request_ms: u64,
+}
+
+impl Default for RequestLogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            path_prefixes: vec!["/api/".to_string()],
+            exclude_prefixes: vec!["/api/health".to_string()],
+            sample_rate: 1.0,
+            slow_request_ms: 1000,
+        }
+    }
+}
+
+impl RequestLogConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "request_log")
+    }
+
+    /// 路径是否需要记录
+    pub fn is_logged_path(&self, path: &str) -> bool {
+        self.enabled
+            && self.path_prefixes.iter().any(|prefix| path.starts_with(prefix.as_str()))
+            && !self.exclude_prefixes.iter().any(|prefix| path.starts_with(prefix.as_str()))
+    }
+
+    /// 是否记录本次请求，`sample` 为 [0, 1) 区间的随机数
+    pub fn should_log(&self, status: u16, latency_ms: u64, sample: f64) -> bool {
+        status >= 500 || latency_ms >= self.slow_request_ms || sample < self.sample_rate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_logged_paths() {
+        let config = RequestLogConfig::default();
+        assert!(config.is_logged_path("/api/auth/login"));
+        assert!(!config.is_logged_path("/api/health"));
+        assert!(!config.is_logged_path("/assets/index.js"));
+
+        let disabled = RequestLogConfig { enabled: false, ..RequestLogConfig::default() };
+        assert!(!disabled.is_logged_path("/api/auth/login"));
+    }
+
+    #[test]
+    fn test_sampling_keeps_errors_and_slow_requests() {
+        let config = RequestLogConfig { sample_rate: 0.1, ..RequestLogConfig::default() };
+        assert!(config.should_log(200, 5, 0.05));
+        assert!(!config.should_log(200, 5, 0.5));
+        assert!(config.should_log(503, 5, 0.5));
+        assert!(config.should_log(200, 1500, 0.5));
+    }
+}
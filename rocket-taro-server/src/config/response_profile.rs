@@ -0,0 +1,59 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+use crate::config::Platform;
+use crate::models::response_profile::ResponseProfile;
+
+/// 按平台的响应信封形式（Rocket.toml 中的 `[default.response_profile]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResponseProfileConfig {
+    pub enabled: bool,
+    pub miniprogram: ResponseProfile,
+    pub h5: ResponseProfile,
+    pub admin: ResponseProfile,
+}
+
+impl Default for ResponseProfileConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            miniprogram: ResponseProfile::Combined,
+            h5: ResponseProfile::Combined,
+            admin: ResponseProfile::Data,
+        }
+    }
+}
+
+impl ResponseProfileConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "response_profile")
+    }
+
+    pub fn for_platform(&self, platform: Platform) -> ResponseProfile {
+        match platform {
+            Platform::Miniprogram => self.miniprogram,
+            Platform::H5 => self.h5,
+            Platform::Admin => self.admin,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocket::figment::providers::{Format, Toml};
+
+    #[test]
+    fn test_from_figment() {
+        let figment = Figment::new().merge(Toml::string(r#"
+            [response_profile]
+            h5 = "command"
+        "#));
+        let config = ResponseProfileConfig::from_figment(&figment);
+        assert_eq!(config.for_platform(Platform::H5), ResponseProfile::Command);
+        assert_eq!(config.for_platform(Platform::Admin), ResponseProfile::Data);
+        assert_eq!(config.for_platform(Platform::Miniprogram), ResponseProfile::Combined);
+    }
+}
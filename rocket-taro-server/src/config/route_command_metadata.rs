@@ -0,0 +1,83 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::models::route_command::VersionedRouteCommand;
+
+/// 下发的路由指令自动附带的元数据（Rocket.toml 中的 `[default.route_command_metadata]`）
+///
+/// 指令类型与序列化的 type 字段一致，如 `NavigateTo`、`ProcessData`。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RouteCommandMetadataConfig {
+    /// 未单独配置的指令类型的超时（毫秒），0 表示不限
+    pub default_timeout_ms: u64,
+    /// 各指令类型的超时（毫秒），0 表示不限；需要用户操作的指令（对话框、支付）不应设置超时
+    pub timeouts: HashMap<String, u64>,
+    /// 执行失败后前端可以直接重试的指令类型（重复执行没有副作用）
+    pub retryable: Vec<String>,
+}
+
+impl Default for RouteCommandMetadataConfig {
+    fn default() -> Self {
+        Self {
+            default_timeout_ms: 0,
+            timeouts: HashMap::from([
+                ("NavigateTo".to_string(), 5000),
+                ("ProcessData".to_string(), 2000),
+            ]),
+            retryable: vec!["NavigateTo".to_string(), "ProcessData".to_string()],
+        }
+    }
+}
+
+impl RouteCommandMetadataConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "route_command_metadata")
+    }
+
+    /// 按指令类型设置超时和是否可重试，指令ID在生成指令时已分配
+    pub fn apply_to(&self, command: &mut VersionedRouteCommand) {
+        let command_type = command.command.type_name();
+        let timeout_ms = self.timeouts.get(command_type).copied().unwrap_or(self.default_timeout_ms);
+        command.metadata.timeout_ms = Some(timeout_ms).filter(|timeout| *timeout > 0);
+        command.metadata.retryable = self.retryable.iter().any(|retryable| retryable == command_type);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::route_command::RouteCommand;
+    use rocket::figment::providers::{Format, Toml};
+
+    #[test]
+    fn test_apply_metadata_by_command_type() {
+        let figment = Figment::new().merge(Toml::string(r#"
+            [route_command_metadata]
+            default_timeout_ms = 10000
+            timeouts = { NavigateTo = 3000, ShowDialog = 0 }
+            retryable = ["NavigateTo"]
+        "#));
+        let config = RouteCommandMetadataConfig::from_figment(&figment);
+
+        let metadata_for = |command: RouteCommand| {
+            let mut command = VersionedRouteCommand::generated(command);
+            config.apply_to(&mut command);
+            command.metadata
+        };
+
+        let navigate = metadata_for(RouteCommand::navigate_to("/pages/home/home"));
+        assert_eq!(navigate.timeout_ms, Some(3000));
+        assert!(navigate.retryable);
+
+        let dialog = metadata_for(RouteCommand::alert("提示", "内容"));
+        assert_eq!(dialog.timeout_ms, None);
+        assert!(!dialog.retryable);
+
+        let sequence = metadata_for(RouteCommand::sequence(vec![]));
+        assert_eq!(sequence.timeout_ms, Some(10000));
+        assert_ne!(sequence.id, navigate.id);
+    }
+}
@@ -4,15 +4,18 @@ use std::path::Path;
 use anyhow::{Context, Result};
 
 /// 平台类型枚举
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Platform {
+    #[default]
     Miniprogram,
     H5,
     Admin,
 }
 
 impl Platform {
+    pub const ALL: [Platform; 3] = [Platform::Miniprogram, Platform::H5, Platform::Admin];
+
     /// 从字符串解析平台类型
     pub fn from_str(s: &str) -> Option<Platform> {
         match s.to_lowercase().as_str() {
@@ -23,10 +26,20 @@ impl Platform {
         }
     }
     
+    /// 平台的规范名称（与 routes.toml 及序列化格式一致）
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Platform::Miniprogram => "miniprogram",
+            Platform::H5 => "h5",
+            Platform::Admin => "admin",
+        }
+    }
+    
     /// 从 User-Agent 检测平台
     pub fn from_user_agent(user_agent: &str) -> Platform {
         let ua = user_agent.to_lowercase();
-        if ua.contains("miniprogram") || ua.contains("micromessenger") {
+        // 微信登录未携带 User-Agent 时会话记录为 "WeChat Mini Program"
+        if ua.contains("miniprogram") || ua.contains("mini program") || ua.contains("micromessenger") {
             Platform::Miniprogram
         } else if ua.contains("mobile") || ua.contains("android") || ua.contains("iphone") {
             Platform::H5
@@ -36,12 +49,6 @@ impl Platform {
     }
 }
 
-impl Default for Platform {
-    fn default() -> Self {
-        Platform::Miniprogram
-    }
-}
-
 /// 单个路由的平台配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RouteEntry {
@@ -111,7 +118,7 @@ impl RouteConfig {
     
     /// 获取路由，使用默认平台
     pub fn get_route_default(&self, route_key: &str) -> Option<String> {
-        self.get_route(route_key, self.config.defaults.platform.clone())
+        self.get_route(route_key, self.config.defaults.platform)
     }
     
     /// 获取所有可用的路由键
@@ -202,5 +209,6 @@ mod tests {
             Platform::from_user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36"),
             Platform::Admin
         );
+        assert_eq!(Platform::from_user_agent("WeChat Mini Program"), Platform::Miniprogram);
     }
 }
\ No newline at end of file
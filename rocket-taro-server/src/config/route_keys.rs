@@ -0,0 +1,52 @@
+//! 代码中引用的 routes.toml 路由键，新增引用时在此登记，测试会校验其在各平台均已配置
+
+pub const AUTH_LOGIN: &str = "auth.login";
+pub const AUTH_RESET_PASSWORD: &str = "auth.reset_password";
+pub const AUTH_QR_LOGIN_CONFIRM: &str = "auth.qr_login_confirm";
+pub const AUTH_REAUTH: &str = "auth.reauth";
+pub const AUTH_CONSENT: &str = "auth.consent";
+pub const HOME_MAIN: &str = "home.main";
+pub const HOME_INDEX: &str = "home.index";
+pub const USER_COMPLETE_PROFILE: &str = "user.complete_profile";
+pub const USER_IDENTITY_VERIFICATION: &str = "user.identity_verification";
+pub const ORDER_LIST: &str = "order.list";
+pub const ORDER_DETAIL: &str = "order.detail";
+pub const PAYMENT_RESULT: &str = "payment.result";
+pub const ERROR_MAINTENANCE: &str = "error.maintenance";
+
+/// 全部引用的路由键
+pub const ALL: &[&str] = &[
+    AUTH_LOGIN,
+    AUTH_RESET_PASSWORD,
+    AUTH_QR_LOGIN_CONFIRM,
+    AUTH_REAUTH,
+    AUTH_CONSENT,
+    HOME_MAIN,
+    HOME_INDEX,
+    USER_COMPLETE_PROFILE,
+    USER_IDENTITY_VERIFICATION,
+    ORDER_LIST,
+    ORDER_DETAIL,
+    PAYMENT_RESULT,
+    ERROR_MAINTENANCE,
+];
+
+/// 未配置时降级为弹窗或提示的路由键，其余路由键缺失时无法生成路由指令
+pub const OPTIONAL: &[&str] = &[PAYMENT_RESULT, ERROR_MAINTENANCE];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Platform, RouteConfig};
+
+    #[test]
+    fn test_all_keys_resolve_in_routes_toml() {
+        let route_config = RouteConfig::from_file("routes.toml").unwrap();
+        for key in ALL {
+            for platform in Platform::ALL {
+                assert!(route_config.get_route(key, platform).is_some(), "{} is missing for {}", key, platform.as_str());
+            }
+        }
+        assert!(OPTIONAL.iter().all(|key| ALL.contains(key)));
+    }
+}
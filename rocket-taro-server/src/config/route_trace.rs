@@ -0,0 +1,34 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 路由决策追踪配置（Rocket.toml 中的 `[default.route_trace]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RouteTraceConfig {
+    /// 是否按执行ID记录路由决策器命中的规则和输入
+    pub enabled: bool,
+    /// 是否在响应的 route_command.metadata.description 中返回决策过程（包含用户状态，只在调试时开启）
+    pub include_in_response: bool,
+    /// 决策记录保留天数
+    pub retention_days: i64,
+    /// 过期记录清理间隔（秒）
+    pub cleanup_interval_secs: u64,
+}
+
+impl Default for RouteTraceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            include_in_response: false,
+            retention_days: 14,
+            cleanup_interval_secs: 3600,
+        }
+    }
+}
+
+impl RouteTraceConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "route_trace")
+    }
+}
@@ -0,0 +1,91 @@
+use std::net::IpAddr;
+
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+use crate::models::session_binding::{BindingMode, ClientFingerprint, ip_prefix, user_agent_family};
+
+/// 会话绑定配置（Rocket.toml 中的 `[default.session_binding]`）
+///
+/// 请求的 IP 网段或 User-Agent 类别与会话创建时不一致时按 `mode` 处理。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionBindingConfig {
+    pub mode: BindingMode,
+    /// 是否比较 IP 网段
+    pub check_ip_address: bool,
+    /// 是否比较 User-Agent 类别
+    pub check_user_agent: bool,
+    /// IPv4 网段前缀长度
+    pub ipv4_prefix_len: u8,
+    /// IPv6 网段前缀长度
+    pub ipv6_prefix_len: u8,
+    /// 同一会话重复不一致时审计日志的最小间隔（秒）
+    pub alert_interval_secs: u64,
+}
+
+impl Default for SessionBindingConfig {
+    fn default() -> Self {
+        Self {
+            mode: BindingMode::Log,
+            check_ip_address: true,
+            check_user_agent: true,
+            ipv4_prefix_len: 16,
+            ipv6_prefix_len: 48,
+            alert_interval_secs: 3600,
+        }
+    }
+}
+
+impl SessionBindingConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "session_binding")
+    }
+
+    /// 按配置计算客户端特征，未启用的项为 None
+    pub fn fingerprint(&self, ip_address: Option<IpAddr>, user_agent: Option<&str>) -> ClientFingerprint {
+        ClientFingerprint {
+            user_agent_family: user_agent.filter(|_| self.check_user_agent).map(user_agent_family),
+            ip_prefix: ip_address
+                .filter(|_| self.check_ip_address)
+                .map(|ip| ip_prefix(ip, self.ipv4_prefix_len, self.ipv6_prefix_len)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::session_binding::BindingMismatch;
+    use rocket::figment::providers::{Format, Toml};
+
+    #[test]
+    fn test_from_figment() {
+        let figment = Figment::new().merge(Toml::string(r#"
+            [session_binding]
+            mode = "challenge"
+            check_user_agent = false
+            ipv4_prefix_len = 24
+        "#));
+        let config = SessionBindingConfig::from_figment(&figment);
+        assert_eq!(config.mode, BindingMode::Challenge);
+        assert!(config.check_ip_address);
+        assert!(!config.check_user_agent);
+        assert_eq!(config.ipv4_prefix_len, 24);
+        assert_eq!(config.ipv6_prefix_len, 48);
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        let config = SessionBindingConfig { check_user_agent: false, ..Default::default() };
+        let bound = config.fingerprint("10.1.2.3".parse().ok(), Some("Mozilla/5.0 Firefox/120.0"));
+        assert_eq!(bound.user_agent_family, None);
+        assert_eq!(bound.ip_prefix.as_deref(), Some("10.1.0.0/16"));
+
+        let same_network = config.fingerprint("10.1.200.9".parse().ok(), Some("curl/8.4.0"));
+        assert!(bound.mismatches(&same_network).is_empty());
+        let other_network = config.fingerprint("10.2.0.1".parse().ok(), None);
+        assert_eq!(bound.mismatches(&other_network), [BindingMismatch::IpAddress]);
+    }
+}
@@ -0,0 +1,56 @@
+use chrono::{DateTime, Duration, Utc};
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 会话过期提醒和空闲过期配置（Rocket.toml 中的 `[default.session_expiry]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionExpiryConfig {
+    /// 会话过期前多久推送提醒（秒）
+    pub warn_before_secs: i64,
+    /// 提醒任务的执行间隔（秒）
+    pub check_interval_secs: u64,
+    /// 超过该时长（秒）没有访问的会话失效，0 表示不按空闲时间过期
+    pub idle_timeout_secs: i64,
+    /// 缓存命中时把访问时间写回数据库的最小间隔（秒）
+    pub access_write_interval_secs: u64,
+}
+
+impl Default for SessionExpiryConfig {
+    fn default() -> Self {
+        Self {
+            warn_before_secs: 300,
+            check_interval_secs: 30,
+            idle_timeout_secs: 259200,
+            access_write_interval_secs: 60,
+        }
+    }
+}
+
+impl SessionExpiryConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "session_expiry")
+    }
+
+    /// 会话最后访问时间距今是否已超过空闲时长
+    pub fn is_idle(&self, last_accessed_at: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        self.idle_timeout_secs > 0 && now - last_accessed_at > Duration::seconds(self.idle_timeout_secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_idle() {
+        let now = Utc::now();
+        let config = SessionExpiryConfig { idle_timeout_secs: 3600, ..Default::default() };
+        assert!(!config.is_idle(now - Duration::minutes(59), now));
+        assert!(config.is_idle(now - Duration::minutes(61), now));
+
+        let disabled = SessionExpiryConfig { idle_timeout_secs: 0, ..Default::default() };
+        assert!(!disabled.is_idle(now - Duration::days(30), now));
+    }
+}
@@ -0,0 +1,63 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::Platform;
+
+/// 同时在线会话数限制（Rocket.toml 中的 `[default.session_limits]`）
+///
+/// 新登录超出限制时踢出最早的会话；平台按会话的 User-Agent 判断。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionLimitsConfig {
+    /// 每个用户的最大会话数，0 表示不限制
+    pub max_active_sessions: usize,
+    /// 各平台的最大会话数，未配置的平台只受总数限制
+    pub platforms: HashMap<Platform, usize>,
+}
+
+impl Default for SessionLimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_active_sessions: 3,
+            platforms: HashMap::from([(Platform::Admin, 1)]),
+        }
+    }
+}
+
+impl SessionLimitsConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "session_limits")
+    }
+
+    /// 平台的会话数限制，未配置或为 0 时不限制
+    pub fn platform_limit(&self, platform: Platform) -> Option<usize> {
+        self.platforms.get(&platform).copied().filter(|limit| *limit > 0)
+    }
+
+    /// 总会话数限制，为 0 时不限制
+    pub fn total_limit(&self) -> Option<usize> {
+        Some(self.max_active_sessions).filter(|limit| *limit > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocket::figment::providers::{Format, Toml};
+
+    #[test]
+    fn test_from_figment() {
+        let figment = Figment::new().merge(Toml::string(r#"
+            [session_limits]
+            max_active_sessions = 0
+            platforms = { admin = 2, h5 = 0 }
+        "#));
+        let config = SessionLimitsConfig::from_figment(&figment);
+        assert_eq!(config.total_limit(), None);
+        assert_eq!(config.platform_limit(Platform::Admin), Some(2));
+        assert_eq!(config.platform_limit(Platform::H5), None);
+        assert_eq!(config.platform_limit(Platform::Miniprogram), None);
+    }
+}
@@ -0,0 +1,40 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 副作用发件箱配置（Rocket.toml 中的 `[default.side_effects]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SideEffectConfig {
+    /// 后台执行任务轮询间隔（秒）
+    pub poll_interval_secs: u64,
+    /// 每次领取的记录数上限
+    pub batch_size: i64,
+    /// 最大尝试次数，超过后标记为 dead
+    pub max_attempts: i32,
+    /// 首次重试等待时间（秒），之后按指数退避
+    pub retry_base_secs: i64,
+    /// 重试等待时间上限（秒）
+    pub retry_max_secs: i64,
+    /// 已执行记录保留天数
+    pub retention_days: i64,
+}
+
+impl Default for SideEffectConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval_secs: 5,
+            batch_size: 100,
+            max_attempts: 10,
+            retry_base_secs: 5,
+            retry_max_secs: 3600,
+            retention_days: 7,
+        }
+    }
+}
+
+impl SideEffectConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "side_effects")
+    }
+}
@@ -0,0 +1,146 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 接口 SLO 与燃烧率告警（Rocket.toml 中的 `[default.slo]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SloConfig {
+    pub enabled: bool,
+    /// 统计桶的时长（秒），滚动窗口按桶累加
+    pub bucket_secs: u64,
+    /// 燃烧率计算和告警检查的间隔（秒）
+    pub evaluation_interval_secs: u64,
+    /// 燃烧率序列每个点统计的窗口（秒）
+    pub series_window_secs: u64,
+    /// 燃烧率序列保留的点数
+    pub series_length: usize,
+    /// 多窗口燃烧率告警规则
+    pub alerts: Vec<BurnRateAlert>,
+    /// 按路由组定义的目标，按顺序匹配，请求只计入第一个匹配的路由组
+    pub objectives: Vec<SloObjective>,
+}
+
+/// 一个路由组的 SLO 目标
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SloObjective {
+    pub name: String,
+    /// 匹配的路径前缀
+    pub path_prefixes: Vec<String>,
+    /// 匹配的请求方法，为空时匹配全部方法
+    pub methods: Vec<String>,
+    /// 可用性目标：非 5xx 响应的比例
+    pub availability_target: f64,
+    /// 延迟阈值（毫秒）
+    pub latency_threshold_ms: u64,
+    /// 延迟目标：耗时不超过阈值的请求比例
+    pub latency_target: f64,
+    /// 达标率统计的滚动窗口（秒）
+    pub window_secs: u64,
+}
+
+/// 长短两个窗口的燃烧率同时超过阈值时告警
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BurnRateAlert {
+    pub name: String,
+    pub long_window_secs: u64,
+    pub short_window_secs: u64,
+    /// 燃烧率阈值：错误预算消耗速度是刚好用完预算时的多少倍
+    pub burn_rate: f64,
+}
+
+impl Default for SloConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            bucket_secs: 60,
+            evaluation_interval_secs: 60,
+            series_window_secs: 300,
+            series_length: 120,
+            alerts: vec![
+                BurnRateAlert { name: "fast".to_string(), long_window_secs: 3600, short_window_secs: 300, burn_rate: 14.4 },
+                BurnRateAlert { name: "slow".to_string(), long_window_secs: 21600, short_window_secs: 1800, burn_rate: 6.0 },
+            ],
+            objectives: vec![
+                SloObjective {
+                    name: "auth".to_string(),
+                    path_prefixes: vec!["/api/auth/".to_string()],
+                    ..SloObjective::default()
+                },
+                SloObjective {
+                    name: "payments".to_string(),
+                    path_prefixes: vec!["/api/orders".to_string(), "/api/payments".to_string()],
+                    latency_threshold_ms: 1000,
+                    ..SloObjective::default()
+                },
+                SloObjective {
+                    name: "api".to_string(),
+                    path_prefixes: vec!["/api/".to_string()],
+                    availability_target: 0.995,
+                    latency_threshold_ms: 1000,
+                    latency_target: 0.95,
+                    ..SloObjective::default()
+                },
+            ],
+        }
+    }
+}
+
+impl Default for SloObjective {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            path_prefixes: Vec::new(),
+            methods: Vec::new(),
+            availability_target: 0.999,
+            latency_threshold_ms: 500,
+            latency_target: 0.99,
+            window_secs: 86400,
+        }
+    }
+}
+
+impl SloObjective {
+    /// 请求是否属于该路由组
+    pub fn matches(&self, method: &str, path: &str) -> bool {
+        self.path_prefixes.iter().any(|prefix| path.starts_with(prefix.as_str()))
+            && (self.methods.is_empty() || self.methods.iter().any(|m| m.eq_ignore_ascii_case(method)))
+    }
+}
+
+impl SloConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "slo")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocket::figment::providers::{Format, Toml};
+
+    #[test]
+    fn test_from_figment() {
+        let figment = Figment::new().merge(Toml::string(r#"
+            [slo]
+            bucket_secs = 30
+
+            [[slo.objectives]]
+            name = "login"
+            path_prefixes = ["/api/auth/login", "/api/auth/wx-login"]
+            methods = ["POST"]
+            latency_threshold_ms = 800
+        "#));
+        let config = SloConfig::from_figment(&figment);
+        assert_eq!(config.bucket_secs, 30);
+        assert_eq!(config.alerts.len(), 2);
+
+        let login = &config.objectives[0];
+        assert_eq!(config.objectives.len(), 1);
+        assert_eq!(login.availability_target, 0.999);
+        assert!(login.matches("post", "/api/auth/login"));
+        assert!(!login.matches("GET", "/api/auth/login"));
+        assert!(!login.matches("POST", "/api/auth/logout"));
+    }
+}
@@ -0,0 +1,50 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 启动自检（Rocket.toml 中的 `[default.startup_check]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StartupCheckConfig {
+    /// 严格模式：启动时执行自检，有失败项时拒绝启动
+    pub strict: bool,
+    /// 单项检查的超时（秒）
+    pub timeout_secs: u64,
+    /// 是否向微信服务器校验 AppID 和 AppSecret，无法访问外网的环境可关闭
+    pub wechat: bool,
+}
+
+impl Default for StartupCheckConfig {
+    fn default() -> Self {
+        Self {
+            strict: false,
+            timeout_secs: 10,
+            wechat: true,
+        }
+    }
+}
+
+impl StartupCheckConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "startup_check")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocket::figment::providers::{Format, Toml};
+
+    #[test]
+    fn test_from_figment() {
+        let figment = Figment::new().merge(Toml::string(r#"
+            [startup_check]
+            strict = true
+            wechat = false
+        "#));
+        let config = StartupCheckConfig::from_figment(&figment);
+        assert!(config.strict);
+        assert!(!config.wechat);
+        assert_eq!(config.timeout_secs, 10);
+    }
+}
@@ -0,0 +1,81 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 生成文件的存储后端
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackendKind {
+    /// 本地目录
+    Local,
+    /// S3 兼容的对象存储（AWS S3、MinIO、腾讯云 COS 等）
+    S3,
+}
+
+/// S3 兼容对象存储的连接配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct S3StorageConfig {
+    /// 服务地址，如 `https://s3.amazonaws.com`、`http://127.0.0.1:9000`
+    pub endpoint: String,
+    pub region: String,
+    pub bucket: String,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    /// 为 true 时使用 `{endpoint}/{bucket}/{key}` 形式的地址（MinIO 等），否则使用 `{bucket}.{endpoint 主机}`
+    pub path_style: bool,
+    /// 上传、删除请求超时时间（秒）
+    pub request_timeout_secs: u64,
+}
+
+impl Default for S3StorageConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: String::new(),
+            region: "us-east-1".to_string(),
+            bucket: String::new(),
+            access_key_id: String::new(),
+            secret_access_key: String::new(),
+            path_style: true,
+            request_timeout_secs: 30,
+        }
+    }
+}
+
+/// 生成文件（数据导出等）的存储配置（Rocket.toml 中的 `[default.storage]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StorageConfig {
+    pub backend: StorageBackendKind,
+    /// 本地存储的根目录
+    pub local_directory: String,
+    /// 本地存储下载链接的签名密钥，未配置时使用随机密钥（重启后已签发的链接失效，多实例间不通用）
+    pub signing_key: Option<String>,
+    /// 签名下载链接的有效期（秒）
+    pub signed_url_ttl_secs: u64,
+    /// 过期文件清理任务执行间隔（秒）
+    pub cleanup_interval_secs: u64,
+    /// 每次清理的最大文件数
+    pub cleanup_batch_size: i64,
+    pub s3: S3StorageConfig,
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            backend: StorageBackendKind::Local,
+            local_directory: "data".to_string(),
+            signing_key: None,
+            signed_url_ttl_secs: 300,
+            cleanup_interval_secs: 600,
+            cleanup_batch_size: 100,
+            s3: S3StorageConfig::default(),
+        }
+    }
+}
+
+impl StorageConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "storage")
+    }
+}
@@ -0,0 +1,62 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 链路追踪导出（Rocket.toml 中的 `[default.telemetry]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TelemetryConfig {
+    /// 是否通过 OTLP 导出 span；关闭时只输出日志
+    pub enabled: bool,
+    /// OTLP gRPC 接收地址（Jaeger、Tempo 或 OpenTelemetry Collector）
+    pub endpoint: String,
+    /// 上报的服务名
+    pub service_name: String,
+    /// 没有上游 traceparent 时新建链路的采样比例（0.0 - 1.0），有上游时沿用上游的采样决定
+    pub sample_ratio: f64,
+    /// 单次导出超时（秒）
+    pub export_timeout_secs: u64,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: "http://localhost:4317".to_string(),
+            service_name: "rocket-taro-server".to_string(),
+            sample_ratio: 1.0,
+            export_timeout_secs: 10,
+        }
+    }
+}
+
+impl TelemetryConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值；OTEL_EXPORTER_OTLP_ENDPOINT 覆盖 endpoint
+    pub fn from_figment(figment: &Figment) -> Self {
+        let mut config: Self = super::section(figment, "telemetry");
+
+        if let Ok(endpoint) = std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT") {
+            config.endpoint = endpoint;
+        }
+        config.sample_ratio = config.sample_ratio.clamp(0.0, 1.0);
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocket::figment::providers::{Format, Toml};
+
+    #[test]
+    fn test_from_figment() {
+        let figment = Figment::new().merge(Toml::string(r#"
+            [telemetry]
+            enabled = true
+            sample_ratio = 1.5
+        "#));
+        let config = TelemetryConfig::from_figment(&figment);
+        assert!(config.enabled);
+        assert_eq!(config.sample_ratio, 1.0);
+        assert_eq!(config.service_name, "rocket-taro-server");
+    }
+}
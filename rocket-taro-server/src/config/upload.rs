@@ -0,0 +1,64 @@
+use rocket::data::{ByteUnit, ToByteUnit};
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 文件上传配置（Rocket.toml 中的 `[default.uploads]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UploadConfig {
+    /// 上传文件存放目录
+    pub directory: String,
+    /// 单个文件大小上限，需不大于 `[default.limits]` 中的 `file`
+    pub max_file_size: ByteUnit,
+    /// 允许上传的文件类型
+    pub allowed_content_types: Vec<String>,
+    /// 每条用户数据最多附带的文件数
+    pub max_attachments: usize,
+    /// 上传后未关联到用户数据的文件保留时长（小时），过期后由后台任务删除
+    pub orphan_retention_hours: i64,
+    /// 清理任务执行间隔（秒）
+    pub cleanup_interval_secs: u64,
+}
+
+impl Default for UploadConfig {
+    fn default() -> Self {
+        Self {
+            directory: "data/uploads".to_string(),
+            max_file_size: 5.mebibytes(),
+            allowed_content_types: ["image/jpeg", "image/png", "image/gif", "image/webp", "application/pdf", "text/plain"]
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+            max_attachments: 5,
+            orphan_retention_hours: 24,
+            cleanup_interval_secs: 3600,
+        }
+    }
+}
+
+impl UploadConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "uploads")
+    }
+
+    /// 文件类型是否允许上传（忽略 charset 等参数）
+    pub fn is_allowed(&self, content_type: &str) -> bool {
+        let essence = content_type.split(';').next().unwrap_or_default().trim();
+        self.allowed_content_types.iter().any(|allowed| allowed.eq_ignore_ascii_case(essence))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_allowed() {
+        let config = UploadConfig::default();
+        assert!(config.is_allowed("image/png"));
+        assert!(config.is_allowed("text/plain; charset=utf-8"));
+        assert!(!config.is_allowed("text/html"));
+        assert!(!config.is_allowed("application/x-msdownload"));
+    }
+}
@@ -0,0 +1,23 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 用户数据提交配置（Rocket.toml 中的 `[default.user_data]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UserDataConfig {
+    /// 同一邮箱两次提交的最小间隔（秒），间隔内的重复提交被拒绝，0 表示不检查
+    pub duplicate_window_secs: u64,
+}
+
+impl Default for UserDataConfig {
+    fn default() -> Self {
+        Self { duplicate_window_secs: 60 }
+    }
+}
+
+impl UserDataConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "user_data")
+    }
+}
@@ -0,0 +1,46 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// Webhook 投递配置（Rocket.toml 中的 `[default.webhook]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebhookConfig {
+    /// 投递任务轮询间隔（秒）
+    pub poll_interval_secs: u64,
+    /// 每次分发的事件数及投递的记录数上限
+    pub batch_size: i64,
+    /// 同时进行的投递请求数
+    pub concurrency: usize,
+    /// 单次请求超时（秒）
+    pub request_timeout_secs: u64,
+    /// 最大投递次数，超过后标记为 dead
+    pub max_attempts: i32,
+    /// 首次重试等待时间（秒），之后按指数退避
+    pub retry_base_secs: i64,
+    /// 重试等待时间上限（秒）
+    pub retry_max_secs: i64,
+    /// 事件及投递记录保留天数
+    pub retention_days: i64,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval_secs: 10,
+            batch_size: 100,
+            concurrency: 8,
+            request_timeout_secs: 10,
+            max_attempts: 8,
+            retry_base_secs: 30,
+            retry_max_secs: 6 * 3600,
+            retention_days: 30,
+        }
+    }
+}
+
+impl WebhookConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "webhook")
+    }
+}
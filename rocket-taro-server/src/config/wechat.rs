@@ -0,0 +1,80 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 微信接口客户端实现
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WxApiClientKind {
+    /// 调用微信服务器
+    Http,
+    /// 本地模拟，按 code 返回确定的 openid，用于测试和无微信凭据的本地开发
+    Mock,
+}
+
+/// 水印校验不通过时的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WatermarkMode {
+    /// 拒绝加密数据，不更新用户资料
+    Strict,
+    /// 只记录警告，继续使用加密数据
+    Lenient,
+}
+
+/// 加密数据水印校验（`[default.wechat.watermark]`）：AppID 必须匹配，时间戳须在有效期内
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatermarkConfig {
+    pub mode: WatermarkMode,
+    /// 加密数据的最长有效期（秒），0 表示不检查时效
+    pub max_age_secs: i64,
+    /// 允许水印时间比服务器时间超前的秒数
+    pub clock_skew_secs: i64,
+}
+
+impl Default for WatermarkConfig {
+    fn default() -> Self {
+        Self {
+            mode: WatermarkMode::Strict,
+            max_age_secs: 600,
+            clock_skew_secs: 60,
+        }
+    }
+}
+
+/// 微信小程序配置（Rocket.toml 中的 `[default.wechat]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WechatConfig {
+    /// 小程序 AppID
+    pub app_id: String,
+    /// 小程序 AppSecret
+    pub app_secret: String,
+    /// 接口客户端实现
+    pub client: WxApiClientKind,
+    /// 微信接口地址
+    pub api_base: String,
+    /// 单次请求超时（秒）
+    pub request_timeout_secs: u64,
+    pub watermark: WatermarkConfig,
+}
+
+impl Default for WechatConfig {
+    fn default() -> Self {
+        Self {
+            app_id: String::new(),
+            app_secret: String::new(),
+            client: WxApiClientKind::Http,
+            api_base: "https://api.weixin.qq.com".to_string(),
+            request_timeout_secs: 10,
+            watermark: WatermarkConfig::default(),
+        }
+    }
+}
+
+impl WechatConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "wechat")
+    }
+}
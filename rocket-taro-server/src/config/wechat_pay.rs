@@ -0,0 +1,55 @@
+use rocket::figment::Figment;
+use serde::{Deserialize, Serialize};
+
+/// 微信支付 v3 配置（Rocket.toml 中的 `[default.wechat_pay]`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WechatPayConfig {
+    /// 是否启用微信支付
+    pub enabled: bool,
+    /// 小程序 AppID
+    pub app_id: String,
+    /// 商户号
+    pub mch_id: String,
+    /// 商户API证书序列号
+    pub merchant_serial_no: String,
+    /// 商户API私钥（PEM）文件路径
+    pub private_key_path: String,
+    /// 微信支付平台证书（PEM）文件路径，用于验证回调签名
+    pub platform_certificate_path: String,
+    /// APIv3 密钥，用于解密回调内容
+    pub api_v3_key: String,
+    /// 支付结果回调地址
+    pub notify_url: String,
+    /// 微信支付 API 地址
+    pub api_base: String,
+    /// 单笔订单金额上限（分）
+    pub max_amount: i64,
+    /// 回调时间戳允许的最大偏差（秒）
+    pub notify_tolerance_secs: i64,
+}
+
+impl Default for WechatPayConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            app_id: String::new(),
+            mch_id: String::new(),
+            merchant_serial_no: String::new(),
+            private_key_path: "certs/apiclient_key.pem".to_string(),
+            platform_certificate_path: "certs/wechatpay_platform.pem".to_string(),
+            api_v3_key: String::new(),
+            notify_url: String::new(),
+            api_base: "https://api.mch.weixin.qq.com".to_string(),
+            max_amount: 10_000_000, // 10万元
+            notify_tolerance_secs: 300,
+        }
+    }
+}
+
+impl WechatPayConfig {
+    /// 从 Rocket 配置读取，缺失或格式错误时使用默认值（不启用）
+    pub fn from_figment(figment: &Figment) -> Self {
+        super::section(figment, "wechat_pay")
+    }
+}
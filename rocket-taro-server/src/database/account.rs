@@ -0,0 +1,208 @@
+use tokio_postgres::Error;
+use chrono::{DateTime, Utc, Duration};
+use uuid::Uuid;
+use tracing::info;
+
+use crate::database::DbPool;
+
+// 软删除用户：停用账户并记录注销时间
+pub async fn soft_delete_user(pool: &DbPool, user_id: Uuid) -> Result<Option<DateTime<Utc>>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        "UPDATE users SET is_active = false, deleted_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP
+         WHERE id = $1 AND deleted_at IS NULL
+         RETURNING deleted_at",
+        &[&user_id],
+    ).await?;
+
+    Ok(row.map(|row| row.get(0)))
+}
+
+// 删除用户的所有会话
+pub async fn delete_user_sessions(pool: &DbPool, user_id: Uuid) -> Result<u64, Error> {
+    let client = pool.lock().await;
+
+    client.execute(
+        "DELETE FROM user_sessions WHERE user_id = $1",
+        &[&user_id],
+    ).await
+}
+
+// 查找已超过保留期、尚未匿名化的注销用户
+pub async fn find_users_pending_anonymization(
+    pool: &DbPool,
+    grace_period: Duration,
+    limit: i64,
+) -> Result<Vec<Uuid>, Error> {
+    let client = pool.lock().await;
+    let deleted_before = Utc::now() - grace_period;
+
+    let rows = client.query(
+        "SELECT id FROM users
+         WHERE deleted_at IS NOT NULL AND deleted_at < $1 AND anonymized_at IS NULL
+         ORDER BY deleted_at LIMIT $2",
+        &[&deleted_before, &limit],
+    ).await?;
+
+    Ok(rows.iter().map(|row| row.get(0)).collect())
+}
+
+// 匿名化用户个人信息（用户表、提交数据、登录日志、审计日志、实名认证、注册审核）
+pub async fn anonymize_user(pool: &DbPool, user_id: Uuid) -> Result<bool, Error> {
+    let mut client = pool.lock().await;
+    let transaction = client.transaction().await?;
+
+    let original = transaction.query_opt(
+        "SELECT username, email FROM users WHERE id = $1 AND anonymized_at IS NULL FOR UPDATE",
+        &[&user_id],
+    ).await?;
+
+    let Some(original) = original else {
+        return Ok(false);
+    };
+    let original_username: String = original.get(0);
+    let original_email: String = original.get(1);
+
+    let anonymous_username = format!("deleted_{}", user_id.simple());
+    let anonymous_email = format!("{}@deleted.invalid", user_id.simple());
+
+    transaction.execute(
+        "UPDATE users SET
+            username = $2,
+            email = $3,
+            password_hash = '',
+            full_name = NULL,
+            avatar_url = NULL,
+            wx_openid = NULL,
+            wx_unionid = NULL,
+            wx_session_key = NULL,
+            anonymized_at = CURRENT_TIMESTAMP,
+            updated_at = CURRENT_TIMESTAMP
+         WHERE id = $1",
+        &[&user_id, &anonymous_username, &anonymous_email],
+    ).await?;
+
+    // 用户提交的数据按邮箱关联，提交时的大小写可能与账户邮箱不同
+    transaction.execute(
+        "UPDATE user_data SET name = '已注销用户', email = $2, phone = NULL WHERE lower(email) = lower($1)",
+        &[&original_email, &anonymous_email],
+    ).await?;
+
+    transaction.execute(
+        "UPDATE login_logs SET username = $2, ip_address = NULL, user_agent = NULL
+         WHERE user_id = $1 OR username = $3",
+        &[&user_id, &anonymous_username, &original_username],
+    ).await?;
+    transaction.execute(
+        "UPDATE login_logs_archive SET username = $2, ip_address = NULL, user_agent = NULL
+         WHERE user_id = $1 OR username = $3",
+        &[&user_id, &anonymous_username, &original_username],
+    ).await?;
+
+    // 实名认证记录（脱敏姓名、证件号、出生日期）没有保留的必要，注销是软删除，外键的级联删除不会触发
+    transaction.execute("DELETE FROM identity_verifications WHERE user_id = $1", &[&user_id]).await?;
+
+    // 注册审核记录保留审核结论，去掉注册时的设备信息
+    transaction.execute(
+        "UPDATE registration_reviews SET ip_address = NULL, device_id = NULL WHERE user_id = $1",
+        &[&user_id],
+    ).await?;
+
+    // 审计日志保留操作记录本身，去掉来源 IP 和详情中的账户标识
+    for table in ["audit_logs", "audit_logs_archive"] {
+        transaction.execute(
+            &format!(
+                "UPDATE {} SET ip_address = NULL, details = details - ARRAY['username', 'email', 'pending_email']
+                 WHERE actor_id = $1 OR (target_type = 'user' AND target_id = $1::TEXT)",
+                table,
+            ),
+            &[&user_id],
+        ).await?;
+    }
+
+    // 变更历史中保留了修改前的个人信息（包括本次匿名化写入的历史），一并删除
+    transaction.execute("DELETE FROM users_history WHERE row_id = $1", &[&user_id]).await?;
+    transaction.execute(
+        "DELETE FROM user_data_history
+         WHERE row_id IN (SELECT id FROM user_data WHERE email = $2) OR lower(old_data->>'email') = lower($1)",
+        &[&original_email, &anonymous_email],
+    ).await?;
+
+    transaction.commit().await?;
+
+    info!("User anonymized: {}", user_id);
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DatabaseConfig;
+    use crate::database::{DbHealth, create_connection};
+    use crate::metrics::MetricsRegistry;
+
+    // 连接 TEST_DATABASE_URL 指定的测试数据库，启动时建表和迁移
+    async fn test_pool() -> DbPool {
+        let database_url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL is not set");
+        let config = DatabaseConfig { database_url, ..DatabaseConfig::default() };
+        create_connection(&config, DbHealth::default(), MetricsRegistry::new()).await.unwrap()
+    }
+
+    // 创建一个已软删除的用户
+    async fn deleted_user(pool: &DbPool) -> Uuid {
+        let user_id = Uuid::new_v4();
+        let client = pool.lock().await;
+        client.execute(
+            "INSERT INTO users (id, username, email, password_hash, is_active, deleted_at)
+             VALUES ($1, $2, $3, 'hash', false, CURRENT_TIMESTAMP)",
+            &[&user_id, &format!("user_{}", user_id.simple()), &format!("{}@example.com", user_id.simple())],
+        ).await.unwrap();
+        user_id
+    }
+
+    #[tokio::test]
+    #[ignore] // 需要真实的数据库连接：TEST_DATABASE_URL=... cargo test -- --ignored
+    async fn test_anonymize_user_deletes_identity_verification() {
+        let pool = test_pool().await;
+        let user_id = deleted_user(&pool).await;
+        pool.lock().await.execute(
+            "INSERT INTO identity_verifications (user_id, status, masked_name, masked_id_number, birth_date, provider, provider_reference)
+             VALUES ($1, 'verified', '张*', '110101********1234', '1990-01-01', 'mock', 'ref-1')",
+            &[&user_id],
+        ).await.unwrap();
+
+        assert!(anonymize_user(&pool, user_id).await.unwrap());
+        assert!(!anonymize_user(&pool, user_id).await.unwrap());
+
+        let client = pool.lock().await;
+        let remaining: i64 = client.query_one(
+            "SELECT COUNT(*) FROM identity_verifications WHERE user_id = $1",
+            &[&user_id],
+        ).await.unwrap().get(0);
+        assert_eq!(remaining, 0);
+    }
+
+    #[tokio::test]
+    #[ignore] // 需要真实的数据库连接：TEST_DATABASE_URL=... cargo test -- --ignored
+    async fn test_anonymize_user_clears_registration_review_device() {
+        let pool = test_pool().await;
+        let user_id = deleted_user(&pool).await;
+        pool.lock().await.execute(
+            "INSERT INTO registration_reviews (user_id, reasons, ip_address, device_id, status)
+             VALUES ($1, ARRAY['ip_velocity'], '203.0.113.7', 'device-1', 'approved')",
+            &[&user_id],
+        ).await.unwrap();
+
+        assert!(anonymize_user(&pool, user_id).await.unwrap());
+
+        let client = pool.lock().await;
+        let row = client.query_one(
+            "SELECT ip_address IS NULL, device_id IS NULL, status FROM registration_reviews WHERE user_id = $1",
+            &[&user_id],
+        ).await.unwrap();
+        assert!(row.get::<_, bool>(0));
+        assert!(row.get::<_, bool>(1));
+        assert_eq!(row.get::<_, String>(2), "approved");
+    }
+}
@@ -0,0 +1,192 @@
+use tokio_postgres::Error;
+use chrono::{DateTime, NaiveDate, Utc};
+
+use crate::database::DbPool;
+use crate::models::admin_stats::{StatsRange, AuthMethodBreakdown, IndexUsage};
+
+// 登录成功条件（列名由迁移 007 统一）
+const LOGIN_SUCCESS_EXPR: &str = "l.success";
+
+// 用户登录方式：游客 / 微信 / 密码
+const AUTH_METHOD_EXPR: &str =
+    "CASE WHEN u.is_guest THEN 'guest' WHEN u.wx_openid IS NOT NULL THEN 'wechat' ELSE 'password' END";
+
+/// 单日登录统计：(日期, 活跃用户数, 成功次数, 失败次数)
+pub type DailyLoginRow = (NaiveDate, i64, i64, i64);
+
+// 按天统计登录情况
+pub async fn daily_login_counts(pool: &DbPool, range: &StatsRange) -> Result<Vec<DailyLoginRow>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!(
+            "SELECT (l.created_at AT TIME ZONE 'UTC')::date AS day,
+                    COUNT(DISTINCT l.user_id) FILTER (WHERE {success}),
+                    COUNT(*) FILTER (WHERE {success}),
+                    COUNT(*) FILTER (WHERE NOT {success})
+             FROM login_logs l
+             WHERE l.created_at >= $1 AND l.created_at < $2
+             GROUP BY day",
+            success = LOGIN_SUCCESS_EXPR
+        ),
+        &[&range.start(), &range.end()],
+    ).await?;
+
+    Ok(rows.iter().map(|row| (row.get(0), row.get(1), row.get(2), row.get(3))).collect())
+}
+
+// 按天统计某张表的新增行数（users / user_data）
+pub async fn daily_created_counts(pool: &DbPool, table: DailyCountTable, range: &StatsRange) -> Result<Vec<(NaiveDate, i64)>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!(
+            "SELECT (created_at AT TIME ZONE 'UTC')::date AS day, COUNT(*)
+             FROM {} WHERE created_at >= $1 AND created_at < $2
+             GROUP BY day",
+            table.as_str()
+        ),
+        &[&range.start(), &range.end()],
+    ).await?;
+
+    Ok(rows.iter().map(|row| (row.get(0), row.get(1))).collect())
+}
+
+/// 可按天统计新增量的表
+#[derive(Debug, Clone, Copy)]
+pub enum DailyCountTable {
+    Users,
+    UserData,
+}
+
+impl DailyCountTable {
+    fn as_str(&self) -> &'static str {
+        match self {
+            DailyCountTable::Users => "users",
+            DailyCountTable::UserData => "user_data",
+        }
+    }
+}
+
+// 范围内去重后的活跃用户数
+pub async fn count_active_users(pool: &DbPool, range: &StatsRange) -> Result<i64, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_one(
+        &format!(
+            "SELECT COUNT(DISTINCT l.user_id) FROM login_logs l
+             WHERE l.created_at >= $1 AND l.created_at < $2 AND {}",
+            LOGIN_SUCCESS_EXPR
+        ),
+        &[&range.start(), &range.end()],
+    ).await?;
+
+    Ok(row.get(0))
+}
+
+// 范围内新注册用户的登录方式分布
+pub async fn registrations_by_method(pool: &DbPool, range: &StatsRange) -> Result<AuthMethodBreakdown, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!(
+            "SELECT {} AS method, COUNT(*) FROM users u
+             WHERE u.created_at >= $1 AND u.created_at < $2
+             GROUP BY method",
+            AUTH_METHOD_EXPR
+        ),
+        &[&range.start(), &range.end()],
+    ).await?;
+
+    Ok(to_breakdown(rows.iter().map(|row| (row.get(0), row.get(1)))))
+}
+
+// 范围内活跃用户的登录方式分布
+pub async fn active_users_by_method(pool: &DbPool, range: &StatsRange) -> Result<AuthMethodBreakdown, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!(
+            "SELECT {} AS method, COUNT(DISTINCT u.id) FROM login_logs l
+             JOIN users u ON u.id = l.user_id
+             WHERE l.created_at >= $1 AND l.created_at < $2 AND {}
+             GROUP BY method",
+            AUTH_METHOD_EXPR, LOGIN_SUCCESS_EXPR
+        ),
+        &[&range.start(), &range.end()],
+    ).await?;
+
+    Ok(to_breakdown(rows.iter().map(|row| (row.get(0), row.get(1)))))
+}
+
+// 指定时间之后有访问的未过期会话的用户数（去重）
+pub async fn count_recently_active_users(pool: &DbPool, since: DateTime<Utc>) -> Result<i64, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_one(
+        "SELECT COUNT(DISTINCT user_id) FROM user_sessions
+         WHERE last_accessed_at >= $1 AND expires_at > CURRENT_TIMESTAMP",
+        &[&since],
+    ).await?;
+
+    Ok(row.get(0))
+}
+
+// 未过期的会话数
+pub async fn count_active_sessions(pool: &DbPool) -> Result<i64, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_one(
+        "SELECT COUNT(*) FROM user_sessions WHERE expires_at > CURRENT_TIMESTAMP",
+        &[],
+    ).await?;
+
+    Ok(row.get(0))
+}
+
+// 当前有效用户总数和提交数据总量
+pub async fn overall_totals(pool: &DbPool) -> Result<(i64, i64), Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_one(
+        "SELECT (SELECT COUNT(*) FROM users WHERE deleted_at IS NULL),
+                (SELECT COUNT(*) FROM user_data)",
+        &[],
+    ).await?;
+
+    Ok((row.get(0), row.get(1)))
+}
+
+// 当前库中所有用户表索引的扫描次数和大小，按表名、索引名排序
+pub async fn index_usage(pool: &DbPool) -> Result<Vec<IndexUsage>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        "SELECT relname::text, indexrelname::text, idx_scan, idx_tup_read, idx_tup_fetch, pg_relation_size(indexrelid)
+         FROM pg_stat_user_indexes
+         WHERE schemaname = current_schema()
+         ORDER BY relname, indexrelname",
+        &[],
+    ).await?;
+
+    Ok(rows.iter().map(|row| IndexUsage {
+        table_name: row.get(0),
+        index_name: row.get(1),
+        scans: row.get(2),
+        tuples_read: row.get(3),
+        tuples_fetched: row.get(4),
+        size_bytes: row.get(5),
+    }).collect())
+}
+
+fn to_breakdown<'a>(rows: impl Iterator<Item = (&'a str, i64)>) -> AuthMethodBreakdown {
+    let mut breakdown = AuthMethodBreakdown::default();
+    for (method, count) in rows {
+        match method {
+            "guest" => breakdown.guest = count,
+            "wechat" => breakdown.wechat = count,
+            _ => breakdown.password = count,
+        }
+    }
+    breakdown
+}
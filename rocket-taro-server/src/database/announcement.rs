@@ -0,0 +1,161 @@
+use chrono::{DateTime, Utc};
+use tokio_postgres::{Error, Row};
+use uuid::Uuid;
+
+use crate::database::DbPool;
+use crate::models::announcement::{Announcement, AnnouncementLevel, AnnouncementRequest};
+
+const ANNOUNCEMENT_COLUMNS: &str =
+    "id, title, content, level, dismissible, audience, starts_at, ends_at, created_by, created_at, updated_at";
+
+fn row_to_announcement(row: &Row) -> Announcement {
+    let level: String = row.get(3);
+    let audience: serde_json::Value = row.get(5);
+    Announcement {
+        id: row.get(0),
+        title: row.get(1),
+        content: row.get(2),
+        level: AnnouncementLevel::from_str(&level).unwrap_or_default(),
+        dismissible: row.get(4),
+        audience: serde_json::from_value(audience).unwrap_or_default(),
+        starts_at: row.get(6),
+        ends_at: row.get(7),
+        created_by: row.get(8),
+        created_at: row.get(9),
+        updated_at: row.get(10),
+    }
+}
+
+fn audience_json(request: &AnnouncementRequest) -> serde_json::Value {
+    serde_json::to_value(&request.audience).unwrap_or_else(|_| serde_json::json!({}))
+}
+
+// 获取全部公告（管理端使用）
+pub async fn list_announcements(pool: &DbPool) -> Result<Vec<Announcement>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!("SELECT {} FROM announcements ORDER BY starts_at DESC, created_at DESC", ANNOUNCEMENT_COLUMNS),
+        &[],
+    ).await?;
+
+    Ok(rows.iter().map(row_to_announcement).collect())
+}
+
+// 获取当前有效且用户未关闭的公告，受众过滤由调用方完成
+pub async fn list_active_announcements(pool: &DbPool, user_id: Uuid, now: DateTime<Utc>) -> Result<Vec<Announcement>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!(
+            "SELECT {} FROM announcements a
+             WHERE a.starts_at <= $2 AND (a.ends_at IS NULL OR a.ends_at > $2)
+               AND NOT EXISTS (
+                   SELECT 1 FROM announcement_dismissals d
+                   WHERE d.announcement_id = a.id AND d.user_id = $1
+               )
+             ORDER BY a.starts_at DESC",
+            ANNOUNCEMENT_COLUMNS
+        ),
+        &[&user_id, &now],
+    ).await?;
+
+    Ok(rows.iter().map(row_to_announcement).collect())
+}
+
+// 根据ID获取公告
+pub async fn get_announcement(pool: &DbPool, id: Uuid) -> Result<Option<Announcement>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        &format!("SELECT {} FROM announcements WHERE id = $1", ANNOUNCEMENT_COLUMNS),
+        &[&id],
+    ).await?;
+
+    Ok(row.as_ref().map(row_to_announcement))
+}
+
+// 创建公告
+pub async fn create_announcement(
+    pool: &DbPool,
+    request: &AnnouncementRequest,
+    starts_at: DateTime<Utc>,
+    created_by: Uuid,
+) -> Result<Announcement, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_one(
+        &format!(
+            "INSERT INTO announcements (title, content, level, dismissible, audience, starts_at, ends_at, created_by)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+             RETURNING {}",
+            ANNOUNCEMENT_COLUMNS
+        ),
+        &[
+            &request.title.trim(),
+            &request.content.trim(),
+            &request.level.as_str(),
+            &request.dismissible,
+            &audience_json(request),
+            &starts_at,
+            &request.ends_at,
+            &created_by,
+        ],
+    ).await?;
+
+    Ok(row_to_announcement(&row))
+}
+
+// 更新公告，不存在时返回 None
+pub async fn update_announcement(
+    pool: &DbPool,
+    id: Uuid,
+    request: &AnnouncementRequest,
+    starts_at: DateTime<Utc>,
+) -> Result<Option<Announcement>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        &format!(
+            "UPDATE announcements
+             SET title = $2, content = $3, level = $4, dismissible = $5, audience = $6,
+                 starts_at = $7, ends_at = $8, updated_at = CURRENT_TIMESTAMP
+             WHERE id = $1
+             RETURNING {}",
+            ANNOUNCEMENT_COLUMNS
+        ),
+        &[
+            &id,
+            &request.title.trim(),
+            &request.content.trim(),
+            &request.level.as_str(),
+            &request.dismissible,
+            &audience_json(request),
+            &starts_at,
+            &request.ends_at,
+        ],
+    ).await?;
+
+    Ok(row.as_ref().map(row_to_announcement))
+}
+
+// 删除公告（关闭记录级联删除），返回是否删除
+pub async fn delete_announcement(pool: &DbPool, id: Uuid) -> Result<bool, Error> {
+    let client = pool.lock().await;
+
+    let deleted = client.execute("DELETE FROM announcements WHERE id = $1", &[&id]).await?;
+    Ok(deleted > 0)
+}
+
+// 记录用户关闭公告，重复关闭不报错
+pub async fn dismiss_announcement(pool: &DbPool, user_id: Uuid, announcement_id: Uuid) -> Result<(), Error> {
+    let client = pool.lock().await;
+
+    client.execute(
+        "INSERT INTO announcement_dismissals (user_id, announcement_id) VALUES ($1, $2)
+         ON CONFLICT (user_id, announcement_id) DO NOTHING",
+        &[&user_id, &announcement_id],
+    ).await?;
+
+    Ok(())
+}
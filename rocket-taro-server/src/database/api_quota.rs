@@ -0,0 +1,74 @@
+use tokio_postgres::Error;
+use uuid::Uuid;
+
+use crate::models::api_quota::QuotaOverride;
+use super::DbPool;
+use super::row::{FromRow, impl_from_row};
+
+impl_from_row!(QuotaOverride { group_name, subject, daily_limit, updated_by, updated_at });
+
+// 查询分组中给定对象（用户、角色）的覆盖
+pub async fn find_quota_overrides(pool: &DbPool, group_name: &str, subjects: &[String]) -> Result<Vec<QuotaOverride>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!(
+            "SELECT {} FROM api_quota_overrides WHERE group_name = $1 AND subject = ANY($2)",
+            QuotaOverride::select_columns(),
+        ),
+        &[&group_name, &subjects],
+    ).await?;
+    rows.iter().map(QuotaOverride::from_row).collect()
+}
+
+// 全部覆盖（管理端使用）
+pub async fn list_quota_overrides(pool: &DbPool) -> Result<Vec<QuotaOverride>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!(
+            "SELECT {} FROM api_quota_overrides ORDER BY group_name, subject",
+            QuotaOverride::select_columns(),
+        ),
+        &[],
+    ).await?;
+    rows.iter().map(QuotaOverride::from_row).collect()
+}
+
+// 写入覆盖，已存在时更新上限
+pub async fn upsert_quota_override(
+    pool: &DbPool,
+    group_name: &str,
+    subject: &str,
+    daily_limit: i64,
+    updated_by: Uuid,
+) -> Result<QuotaOverride, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_one(
+        &format!(
+            "INSERT INTO api_quota_overrides (group_name, subject, daily_limit, updated_by)
+             VALUES ($1, $2, $3, $4)
+             ON CONFLICT (group_name, subject)
+             DO UPDATE SET daily_limit = EXCLUDED.daily_limit, updated_by = EXCLUDED.updated_by, updated_at = CURRENT_TIMESTAMP
+             RETURNING {}",
+            QuotaOverride::select_columns(),
+        ),
+        &[&group_name, &subject, &daily_limit, &updated_by],
+    ).await?;
+    QuotaOverride::from_row(&row)
+}
+
+// 删除覆盖，返回被删除的行
+pub async fn delete_quota_override(pool: &DbPool, group_name: &str, subject: &str) -> Result<Option<QuotaOverride>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        &format!(
+            "DELETE FROM api_quota_overrides WHERE group_name = $1 AND subject = $2 RETURNING {}",
+            QuotaOverride::select_columns(),
+        ),
+        &[&group_name, &subject],
+    ).await?;
+    row.as_ref().map(QuotaOverride::from_row).transpose()
+}
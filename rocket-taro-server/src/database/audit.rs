@@ -0,0 +1,49 @@
+use tokio_postgres::Error;
+use std::net::IpAddr;
+use tracing::debug;
+
+use crate::database::DbPool;
+use crate::models::audit::{AuditEvent, AuditLog};
+
+// 记录审计事件
+pub async fn record_audit_event(pool: &DbPool, event: &AuditEvent) -> Result<(), Error> {
+    let client = pool.lock().await;
+
+    debug!("Recording audit event: {} on {}", event.action, event.target_type);
+
+    client.execute(
+        "INSERT INTO audit_logs (actor_id, action, target_type, target_id, ip_address, details)
+         VALUES ($1, $2, $3, $4, $5, $6)",
+        &[&event.actor_id, &event.action, &event.target_type, &event.target_id, &event.ip_address, &event.details],
+    ).await?;
+
+    Ok(())
+}
+
+// 查询某个对象的审计日志
+pub async fn list_audit_logs_for_target(
+    pool: &DbPool,
+    target_type: &str,
+    target_id: &str,
+    limit: i64,
+) -> Result<Vec<AuditLog>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        "SELECT id, actor_id, action, target_type, target_id, ip_address, details, created_at
+         FROM audit_logs WHERE target_type = $1 AND target_id = $2
+         ORDER BY created_at DESC LIMIT $3",
+        &[&target_type, &target_id, &limit],
+    ).await?;
+
+    Ok(rows.iter().map(|row| AuditLog {
+        id: row.get(0),
+        actor_id: row.get(1),
+        action: row.get(2),
+        target_type: row.get(3),
+        target_id: row.get(4),
+        ip_address: row.get::<_, Option<IpAddr>>(5).map(|ip| ip.to_string()),
+        details: row.get(6),
+        created_at: row.get(7),
+    }).collect())
+}
@@ -1,14 +1,56 @@
-use tokio_postgres::{Client, Error};
-use std::sync::Arc;
+use tokio_postgres::{Error, GenericClient, Row};
 use std::net::IpAddr;
-use tokio::sync::Mutex;
-use chrono::{Utc, Duration};
+use std::sync::LazyLock;
+use chrono::{DateTime, Utc, Duration};
 use uuid::Uuid;
+use serde_json::json;
 use tracing::{info, warn, debug};
 
-use crate::models::auth::{User, UserSession, LoginRequest, RegisterRequest, PasswordHash, generate_session_token};
+use crate::models::auth::{User, UserSession, ActiveSession, RegisterRequest, generate_session_token, hash_session_token};
+use crate::models::webhook::WebhookEventType;
+use crate::models::side_effect::SideEffect;
+use crate::database::webhook::enqueue_webhook_event;
+use crate::database::side_effect::enqueue_side_effects;
 
-pub type DbPool = Arc<Mutex<Client>>;
+pub use crate::database::DbPool;
+use crate::database::row::{FromRow, impl_from_row};
+
+impl_from_row!(User {
+    id, username, email, full_name, avatar_url, is_active, is_admin, is_guest,
+    wx_openid, wx_unionid, wx_session_key, last_login_at, created_at, updated_at, version,
+});
+
+impl_from_row!(ActiveSession { id, user_agent, created_at });
+
+/// 会话有效期（天）
+pub const SESSION_TTL_DAYS: i64 = 7;
+
+// 会话与用户连接查询时用户列的别名前缀，避免与会话的同名列冲突
+const SESSION_USER_PREFIX: &str = "user_";
+
+// 登录和会话校验是最高频的查询，SQL 只生成一次并使用预编译语句执行
+static FIND_USER_CREDENTIALS_SQL: LazyLock<String> = LazyLock::new(|| format!(
+    "SELECT {}, password_hash FROM users WHERE username = $1 AND is_active = true",
+    User::select_columns(),
+));
+
+static VALIDATE_SESSION_SQL: LazyLock<String> = LazyLock::new(|| format!(
+    "SELECT s.id, s.user_id, s.user_agent, s.ip_address, s.expires_at, s.created_at, s.last_accessed_at, s.impersonator_id, {}
+     FROM user_sessions s
+     JOIN users u ON s.user_id = u.id
+     WHERE s.token_hash = $1 AND s.expires_at > CURRENT_TIMESTAMP AND u.is_active = true",
+    User::aliased_columns("u", SESSION_USER_PREFIX),
+));
+
+static FIND_VALID_SESSION_SQL: LazyLock<String> = LazyLock::new(|| format!(
+    "SELECT s.id, s.user_id, s.user_agent, s.ip_address, s.expires_at, s.created_at, s.last_accessed_at, s.impersonator_id, {}
+     FROM user_sessions s
+     JOIN users u ON s.user_id = u.id
+     WHERE s.id = $1 AND s.expires_at > CURRENT_TIMESTAMP AND u.is_active = true",
+    User::aliased_columns("u", SESSION_USER_PREFIX),
+));
+
+const TOUCH_SESSION_SQL: &str = "UPDATE user_sessions SET last_accessed_at = CURRENT_TIMESTAMP WHERE id = $1";
 
 // 检查用户名是否已存在
 pub async fn check_username_exists(
@@ -25,97 +67,156 @@ pub async fn check_username_exists(
     Ok(row.is_some())
 }
 
+// 检查邮箱是否已被注册（不区分大小写）
+pub async fn check_email_exists(
+    pool: &DbPool,
+    email: &str,
+) -> Result<bool, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        "SELECT id FROM users WHERE lower(email) = lower($1) LIMIT 1",
+        &[&email],
+    ).await?;
+
+    Ok(row.is_some())
+}
+
+// 返回候选用户名中已被占用的部分，用于生成推荐用户名
+pub async fn find_existing_usernames(
+    pool: &DbPool,
+    candidates: &[String],
+) -> Result<Vec<String>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        "SELECT username FROM users WHERE username = ANY($1)",
+        &[&candidates],
+    ).await?;
+
+    Ok(rows.iter().map(|row| row.get(0)).collect())
+}
+
 // 创建新用户
 pub async fn create_user(
     pool: &DbPool,
     register_req: &RegisterRequest,
+    password_hash: &str,
 ) -> Result<User, Error> {
-    let client = pool.lock().await;
-    
-    let password_hash = PasswordHash::new(&register_req.password)
-        .expect("Password hash should not fail");
-    
     let now = Utc::now();
     let user_id = Uuid::new_v4();
     
-    let row = client.query_one(
-        "INSERT INTO users (id, username, email, password_hash, full_name, avatar_url, is_active, is_admin, is_guest, wx_openid, wx_unionid, wx_session_key, created_at, updated_at) 
-         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14) 
-         RETURNING id, username, email, full_name, avatar_url, is_active, is_admin, is_guest, wx_openid, wx_unionid, wx_session_key, last_login_at, created_at, updated_at",
-        &[&user_id, &register_req.username, &register_req.email, &password_hash.hash, 
+    let mut client = pool.lock().await;
+    let transaction = client.transaction().await?;
+
+    let row = transaction.query_one(
+        &format!(
+            "INSERT INTO users (id, username, email, password_hash, full_name, avatar_url, is_active, is_admin, is_guest, wx_openid, wx_unionid, wx_session_key, created_at, updated_at) 
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14) 
+             RETURNING {}",
+            User::select_columns(),
+        ),
+        &[&user_id, &register_req.username, &register_req.email, &password_hash, 
           &None::<String>, &None::<String>, &true, &false, &false, &None::<String>, &None::<String>, &None::<String>, &now, &now],
     ).await?;
 
+    // 注册事件写入 Webhook 发件箱，与用户记录同时提交
+    enqueue_webhook_event(&transaction, WebhookEventType::UserRegistered, &json!({
+        "user_id": user_id,
+        "username": register_req.username,
+        "email": register_req.email,
+        "registration_method": "password",
+        "created_at": now,
+    })).await?;
+    transaction.commit().await?;
+
     info!("User created successfully: {}", register_req.username);
     
-    Ok(User {
-        id: row.get(0),
-        username: row.get(1),
-        email: row.get(2),
-        full_name: row.get(3),
-        avatar_url: row.get(4),
-        is_active: row.get(5),
-        is_admin: row.get(6),
-        is_guest: row.get(7),
-        wx_openid: row.get(8),
-        wx_unionid: row.get(9),
-        wx_session_key: row.get(10),
-        last_login_at: row.get(11),
-        created_at: row.get(12),
-        updated_at: row.get(13),
-    })
+    User::from_row(&row)
+}
+
+// 检查是否已存在管理员账户
+pub async fn admin_exists(pool: &DbPool) -> Result<bool, Error> {
+    let client = pool.lock().await;
+    
+    let row = client.query_one(
+        "SELECT EXISTS(SELECT 1 FROM users WHERE is_admin = true)",
+        &[],
+    ).await?;
+    
+    Ok(row.get(0))
 }
 
-// 用户认证相关数据库操作
-pub async fn authenticate_user(
+// 创建管理员账户；用户名或邮箱已被占用时不修改现有账户，返回 None
+pub async fn create_admin_user(
     pool: &DbPool,
-    login_req: &LoginRequest,
+    username: &str,
+    email: &str,
+    password_hash: &str,
 ) -> Result<Option<User>, Error> {
     let client = pool.lock().await;
     
-    debug!("Authenticating user: {}", login_req.username);
-    
     let row = client.query_opt(
-        "SELECT id, username, email, password_hash, full_name, avatar_url, is_active, is_admin, is_guest, wx_openid, wx_unionid, wx_session_key, last_login_at, created_at, updated_at 
-         FROM users WHERE username = $1 AND is_active = true",
-        &[&login_req.username],
+        &format!(
+            "INSERT INTO users (username, email, password_hash, full_name, is_active, is_admin, is_guest)
+             VALUES ($1, $2, $3, $4, true, true, false)
+             ON CONFLICT DO NOTHING
+             RETURNING {}",
+            User::select_columns(),
+        ),
+        &[&username, &email, &password_hash, &"系统管理员"],
     ).await?;
 
-    if let Some(row) = row {
-        debug!("User found: {}", login_req.username);
-        let password_hash: String = row.get(3);
-        let hash = PasswordHash { hash: password_hash.clone() };
-        
-        debug!("Verifying password for user: {}", login_req.username);
-        let password_valid = hash.verify(&login_req.password);
-        
-        if password_valid {
-            info!("Authentication successful for user: {}", login_req.username);
-            let user = User {
-                id: row.get(0),
-                username: row.get(1),
-                email: row.get(2),
-                full_name: row.get(4),
-                avatar_url: row.get(5),
-                is_active: row.get(6),
-                is_admin: row.get(7),
-                is_guest: row.get(8),
-                wx_openid: row.get(9),
-                wx_unionid: row.get(10),
-                wx_session_key: row.get(11),
-                last_login_at: row.get(12),
-                created_at: row.get(13),
-                updated_at: row.get(14),
-            };
-            return Ok(Some(user));
-        } else {
-            warn!("Password verification failed for user: {}", login_req.username);
-        }
-    } else {
-        warn!("User not found: {}", login_req.username);
-    }
+    row.as_ref().map(User::from_row).transpose()
+}
+
+// 按用户名查询启用用户及其密码哈希，密码由调用方验证
+pub async fn find_user_credentials(
+    pool: &DbPool,
+    username: &str,
+) -> Result<Option<(User, String)>, Error> {
+    let client = pool.lock().await;
     
-    Ok(None)
+    debug!("Loading credentials for user: {}", username);
+    
+    let row = client.query_opt_prepared(&FIND_USER_CREDENTIALS_SQL, &[&username]).await?;
+
+    row.map(|row| Ok((User::from_row(&row)?, row.try_get("password_hash")?))).transpose()
+}
+
+// 更新密码哈希；登录时按新算法或参数重新哈希，仅在哈希未被并发修改时写入
+pub async fn update_password_hash(
+    pool: &DbPool,
+    user_id: Uuid,
+    old_hash: &str,
+    new_hash: &str,
+) -> Result<bool, Error> {
+    let client = pool.lock().await;
+    
+    let updated = client.execute(
+        "UPDATE users SET password_hash = $3, updated_at = CURRENT_TIMESTAMP WHERE id = $1 AND password_hash = $2",
+        &[&user_id, &old_hash, &new_hash],
+    ).await?;
+    
+    Ok(updated > 0)
+}
+
+// 找回密码时重置密码哈希，只重置已设置密码的有效账户，返回用户名
+pub async fn reset_password_hash(
+    pool: &DbPool,
+    user_id: Uuid,
+    new_hash: &str,
+) -> Result<Option<String>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        "UPDATE users SET password_hash = $2, updated_at = CURRENT_TIMESTAMP
+         WHERE id = $1 AND is_active = true AND password_hash <> ''
+         RETURNING username",
+        &[&user_id, &new_hash],
+    ).await?;
+
+    Ok(row.map(|row| row.get(0)))
 }
 
 // 创建用户会话
@@ -128,13 +229,74 @@ pub async fn create_user_session(
     debug!("Creating user session for user_id: {}", user_id);
     let client = pool.lock().await;
     
+    insert_user_session(&**client, user_id, user_agent, ip_address, None, Duration::days(SESSION_TTL_DAYS)).await
+}
+
+// 创建管理员模拟登录会话，有效期由调用方指定
+pub async fn create_impersonation_session(
+    pool: &DbPool,
+    user_id: Uuid,
+    admin_id: Uuid,
+    ttl: Duration,
+    user_agent: Option<String>,
+    ip_address: Option<IpAddr>,
+) -> Result<UserSession, Error> {
+    debug!("Creating impersonation session for user_id: {} by admin: {}", user_id, admin_id);
+    let client = pool.lock().await;
+
+    insert_user_session(&**client, user_id, user_agent, ip_address, Some(admin_id), ttl).await
+}
+
+// 创建登录会话，登录日志、缓存写入等记账操作写入副作用发件箱，与会话同时提交
+pub async fn create_login_session(
+    pool: &DbPool,
+    user: &User,
+    user_agent: Option<String>,
+    ip_address: Option<IpAddr>,
+    note: Option<String>,
+) -> Result<UserSession, Error> {
+    debug!("Creating login session for user: {}", user.username);
+    let mut client = pool.lock().await;
+    let transaction = client.transaction().await?;
+
+    let session = insert_user_session(&transaction, user.id, user_agent.clone(), ip_address, None, Duration::days(SESSION_TTL_DAYS)).await?;
+    enqueue_side_effects(&transaction, &[
+        SideEffect::LoginLog {
+            user_id: Some(user.id),
+            username: user.username.clone(),
+            success: true,
+            ip_address,
+            user_agent,
+            note,
+        },
+        SideEffect::CacheUserSession {
+            user_id: user.id,
+            session_id: session.id,
+        },
+        SideEffect::ClearLoginFailures {
+            username: user.username.clone(),
+        },
+    ]).await?;
+    transaction.commit().await?;
+
+    Ok(session)
+}
+
+async fn insert_user_session(
+    client: &(impl GenericClient + Sync),
+    user_id: Uuid,
+    user_agent: Option<String>,
+    ip_address: Option<IpAddr>,
+    impersonator_id: Option<Uuid>,
+    ttl: Duration,
+) -> Result<UserSession, Error> {
     let session_token = generate_session_token();
-    let expires_at = Utc::now() + Duration::days(7); // 7天有效期
     let now = Utc::now();
+    let expires_at = now + ttl;
     let row = client.query_one(
-        "INSERT INTO user_sessions (user_id, session_token, user_agent, ip_address, expires_at, created_at) 
-         VALUES ($1, $2, $3, $4, $5, $6) RETURNING id",
-        &[&user_id, &session_token, &user_agent, &ip_address, &expires_at, &now],
+        "INSERT INTO user_sessions (user_id, token_hash, user_agent, ip_address, expires_at, created_at, impersonator_id) 
+         VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING id",
+        &[&user_id, &hash_session_token(&session_token), &user_agent, &ip_address, &expires_at, &now, &impersonator_id],
     ).await?;
     
     let session_id: Uuid = row.get(0);
@@ -148,6 +310,8 @@ pub async fn create_user_session(
         ip_address: ip_address.map(|ip| ip.to_string()),
         expires_at,
         created_at: now,
+        last_accessed_at: now,
+        impersonator_id,
     })
 }
 
@@ -158,48 +322,15 @@ pub async fn validate_session(
 ) -> Result<Option<(User, UserSession)>, Error> {
     let client = pool.lock().await;
     
-    let row = client.query_opt(
-        "SELECT s.id, s.user_id, s.session_token, s.user_agent, s.ip_address, s.expires_at, s.created_at,
-                u.id, u.username, u.email, u.full_name, u.avatar_url, u.is_active, u.is_admin, u.is_guest, u.wx_openid, u.wx_unionid, u.wx_session_key, u.last_login_at, u.created_at, u.updated_at
-         FROM user_sessions s
-         JOIN users u ON s.user_id = u.id
-         WHERE s.session_token = $1 AND s.expires_at > CURRENT_TIMESTAMP AND u.is_active = true",
-        &[&session_token],
-    ).await?;
+    let row = client.query_opt_prepared(&VALIDATE_SESSION_SQL, &[&hash_session_token(session_token)]).await?;
 
     if let Some(row) = row {
-        let session = UserSession {
-            id: row.get(0),
-            user_id: row.get(1),
-            session_token: row.get(2),
-            user_agent: row.get(3),
-            ip_address: row.get::<_, Option<IpAddr>>(4).map(|ip| ip.to_string()),
-            expires_at: row.get(5),
-            created_at: row.get(6),
-        };
-
-        let user = User {
-            id: row.get(7),
-            username: row.get(8),
-            email: row.get(9),
-            full_name: row.get(10),
-            avatar_url: row.get(11),
-            is_active: row.get(12),
-            is_admin: row.get(13),
-            is_guest: row.get(14),
-            wx_openid: row.get(15),
-            wx_unionid: row.get(16),
-            wx_session_key: row.get(17),
-            last_login_at: row.get(18),
-            created_at: row.get(19),
-            updated_at: row.get(20),
-        };
+        // 数据库只保存摘要，返回调用方提供的明文令牌
+        let session = session_from_row(&row, session_token.to_string())?;
+        let user = User::from_row_prefixed(&row, SESSION_USER_PREFIX)?;
 
         // 更新最后访问时间
-        if let Err(e) = client.execute(
-            "UPDATE user_sessions SET last_accessed_at = CURRENT_TIMESTAMP WHERE id = $1",
-            &[&session.id],
-        ).await {
+        if let Err(e) = client.execute_prepared(TOUCH_SESSION_SQL, &[&session.id]).await {
             warn!("Failed to update last_accessed_at: {}", e);
         }
 
@@ -209,6 +340,78 @@ pub async fn validate_session(
     Ok(None)
 }
 
+// 按会话ID读取未过期的会话和用户，不更新访问时间；数据库不保存明文令牌，返回的会话令牌为空
+pub async fn find_valid_session(
+    pool: &DbPool,
+    session_id: Uuid,
+) -> Result<Option<(User, UserSession)>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt_prepared(&FIND_VALID_SESSION_SQL, &[&session_id]).await?;
+    row.map(|row| Ok((User::from_row_prefixed(&row, SESSION_USER_PREFIX)?, session_from_row(&row, String::new())?)))
+        .transpose()
+}
+
+// 为未过期的会话更换令牌，用于幂等重放时重新下发首次请求签发的会话；会话已失效或账户已停用时返回 None
+pub async fn rotate_session_token(
+    pool: &DbPool,
+    session_id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<UserSession>, Error> {
+    let session_token = generate_session_token();
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        "UPDATE user_sessions s SET token_hash = $3
+         FROM users u
+         WHERE s.id = $1 AND s.user_id = $2 AND u.id = s.user_id AND s.expires_at > CURRENT_TIMESTAMP AND u.is_active = true
+         RETURNING s.id, s.user_id, s.user_agent, s.ip_address, s.expires_at, s.created_at, s.last_accessed_at, s.impersonator_id",
+        &[&session_id, &user_id, &hash_session_token(&session_token)],
+    ).await?;
+    row.map(|row| session_from_row(&row, session_token)).transpose()
+}
+
+fn session_from_row(row: &Row, session_token: String) -> Result<UserSession, Error> {
+    Ok(UserSession {
+        id: row.try_get("id")?,
+        user_id: row.try_get("user_id")?,
+        session_token,
+        user_agent: row.try_get("user_agent")?,
+        ip_address: row.try_get::<_, Option<IpAddr>>("ip_address")?.map(|ip| ip.to_string()),
+        expires_at: row.try_get("expires_at")?,
+        created_at: row.try_get("created_at")?,
+        // 本次访问之前的时间，供调用方判断是否空闲过期
+        last_accessed_at: row.try_get("last_accessed_at")?,
+        impersonator_id: row.try_get("impersonator_id")?,
+    })
+}
+
+// 更新会话最后访问时间（缓存命中的请求按间隔写回）
+pub async fn touch_session(pool: &DbPool, session_id: Uuid) -> Result<(), Error> {
+    let client = pool.lock().await;
+
+    client.execute_prepared(TOUCH_SESSION_SQL, &[&session_id]).await?;
+
+    Ok(())
+}
+
+// 更新会话绑定的客户端（重新验证身份后）
+pub async fn rebind_session(
+    pool: &DbPool,
+    session_id: Uuid,
+    ip_address: Option<IpAddr>,
+    user_agent: Option<&str>,
+) -> Result<(), Error> {
+    let client = pool.lock().await;
+
+    client.execute(
+        "UPDATE user_sessions SET ip_address = $2, user_agent = $3 WHERE id = $1",
+        &[&session_id, &ip_address, &user_agent],
+    ).await?;
+
+    Ok(())
+}
+
 // 更新用户最后登录时间
 pub async fn update_last_login(
     pool: &DbPool,
@@ -232,36 +435,76 @@ pub async fn logout_session(
     let client = pool.lock().await;
     
     let rows_affected = client.execute(
-        "DELETE FROM user_sessions WHERE session_token = $1",
-        &[&session_token],
+        "DELETE FROM user_sessions WHERE token_hash = $1",
+        &[&hash_session_token(session_token)],
     ).await?;
     
     Ok(rows_affected > 0)
 }
 
-// 记录登录日志
-pub async fn log_login_attempt(
-    pool: &DbPool,
-    user_id: Option<Uuid>,
-    username: &str,
-    success: bool,
-    ip_address: Option<IpAddr>,
-    user_agent: Option<String>,
-    failure_reason: Option<String>,
-) -> Result<(), Error> {
+// 查询用户未过期的会话（不含管理员模拟登录会话），按创建时间从早到晚排序
+pub async fn list_active_sessions(pool: &DbPool, user_id: Uuid) -> Result<Vec<ActiveSession>, Error> {
     let client = pool.lock().await;
-    
-    let ip_str = ip_address.map(|ip| ip.to_string());
-    
-    client.execute(
-        "INSERT INTO login_logs (user_id, username, login_success, ip_address, user_agent, failure_reason) 
-         VALUES ($1, $2, $3, $4, $5, $6)",
-        &[&user_id, &username, &success, &ip_str, &user_agent, &failure_reason],
+
+    let rows = client.query(
+        &format!(
+            "SELECT {} FROM user_sessions
+             WHERE user_id = $1 AND is_active = true AND expires_at > CURRENT_TIMESTAMP
+               AND impersonator_id IS NULL
+             ORDER BY created_at, id",
+            ActiveSession::select_columns(),
+        ),
+        &[&user_id],
     ).await?;
-    
-    Ok(())
+
+    rows.iter().map(ActiveSession::from_row).collect()
+}
+
+// 批量查询多个用户未过期的会话，返回 (用户ID, 会话)，按创建时间从早到晚排序
+pub async fn list_active_sessions_for_users(pool: &DbPool, user_ids: &[Uuid]) -> Result<Vec<(Uuid, ActiveSession)>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!(
+            "SELECT user_id, {} FROM user_sessions
+             WHERE user_id = ANY($1) AND is_active = true AND expires_at > CURRENT_TIMESTAMP
+               AND impersonator_id IS NULL
+             ORDER BY created_at, id",
+            ActiveSession::select_columns(),
+        ),
+        &[&user_ids],
+    ).await?;
+
+    rows.iter().map(|row| Ok((row.try_get("user_id")?, ActiveSession::from_row(row)?))).collect()
+}
+
+// 删除用户的指定会话，返回实际删除的会话ID
+pub async fn delete_sessions(pool: &DbPool, user_id: Uuid, session_ids: &[Uuid]) -> Result<Vec<Uuid>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        "DELETE FROM user_sessions WHERE user_id = $1 AND id = ANY($2) RETURNING id",
+        &[&user_id, &session_ids],
+    ).await?;
+
+    Ok(rows.iter().map(|row| row.get(0)).collect())
 }
 
+// 延长会话有效期，返回新的过期时间；会话不存在或已过期时返回 None
+pub async fn extend_session(
+    pool: &DbPool,
+    session_token: &str,
+) -> Result<Option<DateTime<Utc>>, Error> {
+    let client = pool.lock().await;
+    let expires_at = Utc::now() + Duration::days(SESSION_TTL_DAYS);
+
+    let updated = client.execute(
+        "UPDATE user_sessions SET expires_at = $2 WHERE token_hash = $1 AND expires_at > CURRENT_TIMESTAMP",
+        &[&hash_session_token(session_token), &expires_at],
+    ).await?;
+
+    Ok((updated > 0).then_some(expires_at))
+}
 
 // 认证游客用户（无密码验证）
 pub async fn authenticate_guest_user(
@@ -273,30 +516,13 @@ pub async fn authenticate_guest_user(
     debug!("Authenticating guest user: {}", username);
     
     let row = client.query_opt(
-        "SELECT id, username, email, password_hash, full_name, avatar_url, is_active, is_admin, is_guest, wx_openid, wx_unionid, wx_session_key, last_login_at, created_at, updated_at 
-         FROM users WHERE username = $1 AND is_active = true AND is_guest = true",
+        &format!("SELECT {} FROM users WHERE username = $1 AND is_active = true AND is_guest = true", User::select_columns()),
         &[&username],
     ).await?;
 
     if let Some(row) = row {
         info!("Guest user found: {}", username);
-        let user = User {
-            id: row.get(0),
-            username: row.get(1),
-            email: row.get(2),
-            full_name: row.get(4),
-            avatar_url: row.get(5),
-            is_active: row.get(6),
-            is_admin: row.get(7),
-            is_guest: row.get(8),
-            wx_openid: row.get(9),
-            wx_unionid: row.get(10),
-            wx_session_key: row.get(11),
-            last_login_at: row.get(12),
-            created_at: row.get(13),
-            updated_at: row.get(14),
-        };
-        return Ok(Some(user));
+        return User::from_row(&row).map(Some);
     }
     
     debug!("Guest user not found: {}", username);
@@ -316,31 +542,19 @@ pub async fn create_guest_user(pool: &DbPool) -> Result<User, Error> {
     let user_id = Uuid::new_v4();
     
     let row = client.query_one(
-        "INSERT INTO users (id, username, email, password_hash, full_name, avatar_url, is_active, is_admin, is_guest, created_at, updated_at) 
-         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11) 
-         RETURNING id, username, email, full_name, avatar_url, is_active, is_admin, is_guest, last_login_at, created_at, updated_at",
+        &format!(
+            "INSERT INTO users (id, username, email, password_hash, full_name, avatar_url, is_active, is_admin, is_guest, created_at, updated_at) 
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11) 
+             RETURNING {}",
+            User::select_columns(),
+        ),
         &[&user_id, &username, &email, &"", &Some("游客用户".to_string()), 
           &None::<String>, &true, &false, &true, &now, &now],
     ).await?;
 
     info!("Guest user created successfully: {}", username);
     
-    Ok(User {
-        id: row.get(0),
-        username: row.get(1),
-        email: row.get(2),
-        full_name: row.get(3),
-        avatar_url: row.get(4),
-        is_active: row.get(5),
-        is_admin: row.get(6),
-        is_guest: row.get(7),
-        wx_openid: None,
-        wx_unionid: None,
-        wx_session_key: None,
-        last_login_at: row.get(8),
-        created_at: row.get(9),
-        updated_at: row.get(10),
-    })
+    User::from_row(&row)
 }
 
 // 清理过期会话
@@ -353,4 +567,68 @@ pub async fn cleanup_expired_sessions(pool: &DbPool) -> Result<u64, Error> {
     ).await?;
     
     Ok(rows_affected)
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DatabaseConfig;
+    use crate::database::{DbHealth, create_connection, profile::find_active_user};
+    use crate::metrics::MetricsRegistry;
+
+    // 连接 TEST_DATABASE_URL 指定的测试数据库，启动时建表和迁移
+    async fn test_pool() -> DbPool {
+        let database_url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL is not set");
+        let config = DatabaseConfig { database_url, ..DatabaseConfig::default() };
+        create_connection(&config, DbHealth::default(), MetricsRegistry::new()).await.unwrap()
+    }
+
+    #[tokio::test]
+    #[ignore] // 需要真实的数据库连接：TEST_DATABASE_URL=... cargo test -- --ignored
+    async fn test_login_session_outbox_has_no_plaintext_token() {
+        let pool = test_pool().await;
+        let user_id = Uuid::new_v4();
+        pool.lock().await.execute(
+            "INSERT INTO users (id, username, email, password_hash) VALUES ($1, $2, $3, 'hash')",
+            &[&user_id, &format!("user_{}", user_id.simple()), &format!("{}@example.com", user_id.simple())],
+        ).await.unwrap();
+        let user = find_active_user(&pool, user_id).await.unwrap().unwrap();
+
+        let session = create_login_session(&pool, &user, None, None, None).await.unwrap();
+
+        let payloads: Vec<serde_json::Value> = pool.lock().await.query(
+            "SELECT payload FROM side_effect_outbox WHERE payload->>'type' = 'cache_user_session' AND payload->>'user_id' = $1",
+            &[&user_id.to_string()],
+        ).await.unwrap().iter().map(|row| row.get(0)).collect();
+        assert_eq!(payloads.len(), 1);
+        assert_eq!(payloads[0]["session_id"], serde_json::json!(session.id));
+        assert!(!payloads[0].to_string().contains(&session.session_token));
+
+        let (found_user, found_session) = find_valid_session(&pool, session.id).await.unwrap().unwrap();
+        assert_eq!(found_user.id, user_id);
+        assert_eq!(found_session.id, session.id);
+        assert!(found_session.session_token.is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore] // 需要真实的数据库连接：TEST_DATABASE_URL=... cargo test -- --ignored
+    async fn test_rotate_session_token_keeps_session() {
+        let pool = test_pool().await;
+        let user_id = Uuid::new_v4();
+        pool.lock().await.execute(
+            "INSERT INTO users (id, username, email, password_hash) VALUES ($1, $2, $3, 'hash')",
+            &[&user_id, &format!("user_{}", user_id.simple()), &format!("{}@example.com", user_id.simple())],
+        ).await.unwrap();
+        let user = find_active_user(&pool, user_id).await.unwrap().unwrap();
+        let session = create_login_session(&pool, &user, None, None, None).await.unwrap();
+
+        let rotated = rotate_session_token(&pool, session.id, user_id).await.unwrap().unwrap();
+        assert_eq!(rotated.id, session.id);
+        assert_ne!(rotated.session_token, session.session_token);
+        assert!(validate_session(&pool, &session.session_token).await.unwrap().is_none());
+        assert_eq!(validate_session(&pool, &rotated.session_token).await.unwrap().unwrap().1.id, session.id);
+
+        let sessions: i64 = pool.lock().await.query_one("SELECT COUNT(*) FROM user_sessions WHERE user_id = $1", &[&user_id]).await.unwrap().get(0);
+        assert_eq!(sessions, 1);
+        assert!(rotate_session_token(&pool, session.id, Uuid::new_v4()).await.unwrap().is_none());
+    }
+}
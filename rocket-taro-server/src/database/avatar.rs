@@ -0,0 +1,158 @@
+use tokio_postgres::Error;
+use uuid::Uuid;
+
+use crate::models::avatar::Avatar;
+use super::DbPool;
+use super::row::{FromRow, impl_from_row};
+
+impl_from_row!(Avatar {
+    id,
+    user_id,
+    status,
+    source_path,
+    source_content_type,
+    file_path,
+    width,
+    height,
+    file_size,
+    failure_reason,
+    attempts,
+    next_attempt_at,
+    created_at,
+    processed_at,
+});
+
+/// 处理完成的头像文件
+pub struct ProcessedAvatarFile<'a> {
+    pub file_path: &'a str,
+    pub width: i32,
+    pub height: i32,
+    pub file_size: i64,
+    pub avatar_url: &'a str,
+}
+
+// 记录上传的头像；同一用户尚未处理完的头像只保留最新的，返回被替换的原图路径
+pub async fn insert_avatar(pool: &DbPool, avatar: &Avatar) -> Result<Vec<String>, Error> {
+    let mut client = pool.lock().await;
+    let transaction = client.transaction().await?;
+
+    let rows = transaction.query(
+        "DELETE FROM avatars WHERE user_id = $1 AND status = 'pending' RETURNING source_path",
+        &[&avatar.user_id],
+    ).await?;
+    transaction.execute(
+        "INSERT INTO avatars (id, user_id, status, source_path, source_content_type, next_attempt_at, created_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7)",
+        &[
+            &avatar.id,
+            &avatar.user_id,
+            &avatar.status,
+            &avatar.source_path,
+            &avatar.source_content_type,
+            &avatar.next_attempt_at,
+            &avatar.created_at,
+        ],
+    ).await?;
+
+    transaction.commit().await?;
+    Ok(rows.iter().map(|row| row.get(0)).collect())
+}
+
+// 按ID查询头像
+pub async fn find_avatar(pool: &DbPool, id: Uuid) -> Result<Option<Avatar>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        &format!("SELECT {} FROM avatars WHERE id = $1", Avatar::select_columns()),
+        &[&id],
+    ).await?;
+    row.as_ref().map(Avatar::from_row).transpose()
+}
+
+// 领取到期的待处理头像，同时把下次处理时间推后 retry_delay_secs 秒，多实例不会重复领取
+pub async fn claim_due_avatars(pool: &DbPool, limit: i64, retry_delay_secs: i64) -> Result<Vec<Avatar>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!(
+            "UPDATE avatars SET
+                 attempts = attempts + 1,
+                 next_attempt_at = CURRENT_TIMESTAMP + make_interval(secs => $2::BIGINT)
+             WHERE id IN (
+                 SELECT id FROM avatars
+                 WHERE status = 'pending' AND next_attempt_at <= CURRENT_TIMESTAMP
+                 ORDER BY next_attempt_at
+                 LIMIT $1
+                 FOR UPDATE SKIP LOCKED
+             )
+             RETURNING {}",
+            Avatar::select_columns(),
+        ),
+        &[&limit, &retry_delay_secs],
+    ).await?;
+    rows.iter().map(Avatar::from_row).collect()
+}
+
+// 记录处理失败；status 仍为 pending 时只更新原因，等待下次处理
+pub async fn fail_avatar(pool: &DbPool, id: Uuid, status: &str, failure_reason: &str) -> Result<(), Error> {
+    let client = pool.lock().await;
+
+    client.execute(
+        "UPDATE avatars SET
+             status = $2,
+             failure_reason = $3,
+             processed_at = CASE WHEN $2::VARCHAR = 'pending' THEN NULL ELSE CURRENT_TIMESTAMP END
+         WHERE id = $1 AND status = 'pending'",
+        &[&id, &status, &failure_reason],
+    ).await?;
+    Ok(())
+}
+
+// 头像处理完成：设为用户头像并删除之前的头像，返回需要删除的文件路径；
+// 处理期间已被新上传的头像替换时返回 None
+pub async fn publish_avatar(pool: &DbPool, id: Uuid, user_id: Uuid, file: &ProcessedAvatarFile<'_>) -> Result<Option<Vec<String>>, Error> {
+    let mut client = pool.lock().await;
+    let transaction = client.transaction().await?;
+
+    let updated = transaction.execute(
+        "UPDATE avatars SET
+             status = 'ready',
+             file_path = $2,
+             width = $3,
+             height = $4,
+             file_size = $5,
+             failure_reason = NULL,
+             processed_at = CURRENT_TIMESTAMP
+         WHERE id = $1 AND status = 'pending'",
+        &[&id, &file.file_path, &file.width, &file.height, &file.file_size],
+    ).await?;
+    if updated == 0 {
+        return Ok(None);
+    }
+
+    let rows = transaction.query(
+        "DELETE FROM avatars WHERE user_id = $1 AND status = 'ready' AND id <> $2 RETURNING file_path",
+        &[&user_id, &id],
+    ).await?;
+    transaction.execute(
+        "UPDATE users SET avatar_url = $2, updated_at = CURRENT_TIMESTAMP, version = version + 1 WHERE id = $1",
+        &[&user_id, &file.avatar_url],
+    ).await?;
+
+    transaction.commit().await?;
+    Ok(Some(rows.iter().filter_map(|row| row.get(0)).collect()))
+}
+
+// 删除用户的全部头像（账户匿名化时），返回需要删除的原图和头像文件路径
+pub async fn delete_user_avatars(pool: &DbPool, user_id: Uuid) -> Result<Vec<String>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        "DELETE FROM avatars WHERE user_id = $1 RETURNING source_path, file_path",
+        &[&user_id],
+    ).await?;
+    Ok(rows.iter()
+        .flat_map(|row| [Some(row.get::<_, String>(0)), row.get::<_, Option<String>>(1)])
+        .flatten()
+        .collect())
+}
@@ -0,0 +1,164 @@
+use std::net::IpAddr;
+use tokio_postgres::Error;
+use uuid::Uuid;
+
+use crate::models::consent::{PolicyDocument, UserConsent};
+use super::DbPool;
+use super::row::{FromRow, impl_from_row};
+
+impl_from_row!(PolicyDocument {
+    id, policy_type, version, title, content, is_active, published_at, created_by, created_at,
+});
+
+impl_from_row!(UserConsent {
+    id,
+    user_id,
+    document_id,
+    #[sql = "(SELECT d.policy_type FROM policy_documents d WHERE d.id = user_consents.document_id)"]
+    policy_type,
+    #[sql = "(SELECT d.version FROM policy_documents d WHERE d.id = user_consents.document_id)"]
+    version,
+    ip_address: Option<IpAddr> => |ip: Option<IpAddr>| ip.map(|ip| ip.to_string()),
+    user_agent,
+    accepted_at,
+});
+
+// 用户尚未同意的生效协议版本
+pub async fn pending_policy_documents(pool: &DbPool, user_id: Uuid) -> Result<Vec<PolicyDocument>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!(
+            "SELECT {} FROM policy_documents
+             WHERE is_active
+               AND NOT EXISTS (
+                   SELECT 1 FROM user_consents c WHERE c.user_id = $1 AND c.document_id = policy_documents.id
+               )
+             ORDER BY policy_type",
+            PolicyDocument::select_columns(),
+        ),
+        &[&user_id],
+    ).await?;
+    rows.iter().map(PolicyDocument::from_row).collect()
+}
+
+// 某类协议当前生效的版本
+pub async fn active_policy_document(pool: &DbPool, policy_type: &str) -> Result<Option<PolicyDocument>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        &format!(
+            "SELECT {} FROM policy_documents WHERE policy_type = $1 AND is_active",
+            PolicyDocument::select_columns(),
+        ),
+        &[&policy_type],
+    ).await?;
+    row.as_ref().map(PolicyDocument::from_row).transpose()
+}
+
+// 全部协议版本（管理端），最新的在前
+pub async fn list_policy_documents(pool: &DbPool) -> Result<Vec<PolicyDocument>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!(
+            "SELECT {} FROM policy_documents ORDER BY policy_type, created_at DESC",
+            PolicyDocument::select_columns(),
+        ),
+        &[],
+    ).await?;
+    rows.iter().map(PolicyDocument::from_row).collect()
+}
+
+// 新增协议版本（未生效），同类型同版本号已存在时返回 None
+pub async fn create_policy_document(
+    pool: &DbPool,
+    policy_type: &str,
+    version: &str,
+    title: &str,
+    content: &str,
+    created_by: Uuid,
+) -> Result<Option<PolicyDocument>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        &format!(
+            "INSERT INTO policy_documents (policy_type, version, title, content, created_by)
+             VALUES ($1, $2, $3, $4, $5)
+             ON CONFLICT (policy_type, version) DO NOTHING
+             RETURNING {}",
+            PolicyDocument::select_columns(),
+        ),
+        &[&policy_type, &version, &title, &content, &created_by],
+    ).await?;
+    row.as_ref().map(PolicyDocument::from_row).transpose()
+}
+
+// 让某个版本生效，同类型的其他版本同时失效；版本不存在时返回 None
+pub async fn activate_policy_document(pool: &DbPool, id: Uuid) -> Result<Option<PolicyDocument>, Error> {
+    let mut client = pool.lock().await;
+    let transaction = client.transaction().await?;
+
+    let policy_type: Option<String> = transaction.query_opt(
+        "SELECT policy_type FROM policy_documents WHERE id = $1 FOR UPDATE",
+        &[&id],
+    ).await?.map(|row| row.get(0));
+    let Some(policy_type) = policy_type else {
+        return Ok(None);
+    };
+
+    transaction.execute(
+        "UPDATE policy_documents SET is_active = false WHERE policy_type = $1 AND is_active AND id <> $2",
+        &[&policy_type, &id],
+    ).await?;
+    let row = transaction.query_one(
+        &format!(
+            "UPDATE policy_documents SET is_active = true, published_at = COALESCE(published_at, CURRENT_TIMESTAMP)
+             WHERE id = $1
+             RETURNING {}",
+            PolicyDocument::select_columns(),
+        ),
+        &[&id],
+    ).await?;
+    let document = PolicyDocument::from_row(&row)?;
+
+    transaction.commit().await?;
+    Ok(Some(document))
+}
+
+// 记录用户同意的版本，只记录当前生效的版本，已同意过的不重复记录
+pub async fn record_consents(
+    pool: &DbPool,
+    user_id: Uuid,
+    document_ids: &[Uuid],
+    ip_address: Option<IpAddr>,
+    user_agent: Option<&str>,
+) -> Result<Vec<UserConsent>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!(
+            "INSERT INTO user_consents (user_id, document_id, ip_address, user_agent)
+             SELECT $1, d.id, $3, $4 FROM policy_documents d WHERE d.id = ANY($2) AND d.is_active
+             ON CONFLICT (user_id, document_id) DO NOTHING
+             RETURNING {}",
+            UserConsent::select_columns(),
+        ),
+        &[&user_id, &document_ids, &ip_address, &user_agent],
+    ).await?;
+    rows.iter().map(UserConsent::from_row).collect()
+}
+
+// 用户的同意记录，最新的在前
+pub async fn list_user_consents(pool: &DbPool, user_id: Uuid) -> Result<Vec<UserConsent>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!(
+            "SELECT {} FROM user_consents WHERE user_id = $1 ORDER BY accepted_at DESC",
+            UserConsent::select_columns(),
+        ),
+        &[&user_id],
+    ).await?;
+    rows.iter().map(UserConsent::from_row).collect()
+}
@@ -0,0 +1,181 @@
+use tokio_postgres::{Error, Row};
+use chrono::{DateTime, Utc, Duration};
+use std::net::IpAddr;
+use uuid::Uuid;
+
+use crate::database::DbPool;
+use crate::models::data_export::{DataExport, DataExportStatus, ArchivedProfile, ArchivedSession};
+
+const DATA_EXPORT_COLUMNS: &str =
+    "id, user_id, status, file_path, file_size, error_message, created_at, completed_at, expires_at";
+
+fn row_to_data_export(row: &Row) -> DataExport {
+    DataExport {
+        id: row.get(0),
+        user_id: row.get(1),
+        status: DataExportStatus::parse(row.get(2)),
+        file_path: row.get(3),
+        file_size: row.get(4),
+        error_message: row.get(5),
+        created_at: row.get(6),
+        completed_at: row.get(7),
+        expires_at: row.get(8),
+    }
+}
+
+// 创建导出任务
+pub async fn create_data_export(pool: &DbPool, user_id: Uuid) -> Result<DataExport, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_one(
+        &format!("INSERT INTO data_exports (user_id) VALUES ($1) RETURNING {}", DATA_EXPORT_COLUMNS),
+        &[&user_id],
+    ).await?;
+
+    Ok(row_to_data_export(&row))
+}
+
+// 查询用户最近一次导出任务
+pub async fn find_latest_data_export(pool: &DbPool, user_id: Uuid) -> Result<Option<DataExport>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        &format!("SELECT {} FROM data_exports WHERE user_id = $1 ORDER BY created_at DESC LIMIT 1", DATA_EXPORT_COLUMNS),
+        &[&user_id],
+    ).await?;
+
+    Ok(row.as_ref().map(row_to_data_export))
+}
+
+// 按ID查询用户的导出任务
+pub async fn find_data_export(pool: &DbPool, user_id: Uuid, export_id: Uuid) -> Result<Option<DataExport>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        &format!("SELECT {} FROM data_exports WHERE id = $1 AND user_id = $2", DATA_EXPORT_COLUMNS),
+        &[&export_id, &user_id],
+    ).await?;
+
+    Ok(row.as_ref().map(row_to_data_export))
+}
+
+// 领取待处理的导出任务（包括处理超时的任务），并标记为处理中
+pub async fn claim_pending_data_exports(
+    pool: &DbPool,
+    stale_after: Duration,
+    limit: i64,
+) -> Result<Vec<DataExport>, Error> {
+    let client = pool.lock().await;
+    let stale_before = Utc::now() - stale_after;
+
+    let rows = client.query(
+        &format!(
+            "UPDATE data_exports SET status = 'processing', started_at = CURRENT_TIMESTAMP
+             WHERE id IN (
+                 SELECT id FROM data_exports
+                 WHERE status = 'pending' OR (status = 'processing' AND started_at < $1)
+                 ORDER BY created_at LIMIT $2
+                 FOR UPDATE SKIP LOCKED
+             )
+             RETURNING {}",
+            DATA_EXPORT_COLUMNS
+        ),
+        &[&stale_before, &limit],
+    ).await?;
+
+    Ok(rows.iter().map(row_to_data_export).collect())
+}
+
+// 标记导出完成
+pub async fn mark_data_export_ready(
+    pool: &DbPool,
+    export_id: Uuid,
+    file_path: &str,
+    file_size: i64,
+    expires_at: DateTime<Utc>,
+) -> Result<(), Error> {
+    let client = pool.lock().await;
+
+    client.execute(
+        "UPDATE data_exports SET status = 'ready', file_path = $2, file_size = $3,
+            completed_at = CURRENT_TIMESTAMP, expires_at = $4
+         WHERE id = $1",
+        &[&export_id, &file_path, &file_size, &expires_at],
+    ).await?;
+
+    Ok(())
+}
+
+// 标记导出失败
+pub async fn mark_data_export_failed(pool: &DbPool, export_id: Uuid, error_message: &str) -> Result<(), Error> {
+    let client = pool.lock().await;
+
+    client.execute(
+        "UPDATE data_exports SET status = 'failed', error_message = $2, completed_at = CURRENT_TIMESTAMP
+         WHERE id = $1",
+        &[&export_id, &error_message],
+    ).await?;
+
+    Ok(())
+}
+
+// 将已过期的导出任务标记为过期，返回文件在存储中的 key
+pub async fn expire_data_exports(pool: &DbPool) -> Result<Vec<String>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        "UPDATE data_exports SET status = 'expired'
+         WHERE status = 'ready' AND expires_at < CURRENT_TIMESTAMP
+         RETURNING file_path",
+        &[],
+    ).await?;
+
+    Ok(rows.iter().filter_map(|row| row.get::<_, Option<String>>(0)).collect())
+}
+
+// 查询导出所需的用户资料
+pub async fn get_archived_profile(pool: &DbPool, user_id: Uuid) -> Result<Option<ArchivedProfile>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        "SELECT id, username, email, full_name, avatar_url, is_admin, is_guest,
+                wx_openid, wx_unionid, last_login_at, created_at, updated_at
+         FROM users WHERE id = $1",
+        &[&user_id],
+    ).await?;
+
+    Ok(row.map(|row| ArchivedProfile {
+        id: row.get(0),
+        username: row.get(1),
+        email: row.get(2),
+        full_name: row.get(3),
+        avatar_url: row.get(4),
+        is_admin: row.get(5),
+        is_guest: row.get(6),
+        wx_openid: row.get(7),
+        wx_unionid: row.get(8),
+        last_login_at: row.get(9),
+        created_at: row.get(10),
+        updated_at: row.get(11),
+    }))
+}
+
+// 查询用户的会话记录
+pub async fn list_archived_sessions(pool: &DbPool, user_id: Uuid) -> Result<Vec<ArchivedSession>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        "SELECT id, user_agent, ip_address, is_active, created_at, expires_at
+         FROM user_sessions WHERE user_id = $1 ORDER BY created_at DESC",
+        &[&user_id],
+    ).await?;
+
+    Ok(rows.iter().map(|row| ArchivedSession {
+        id: row.get(0),
+        user_agent: row.get(1),
+        ip_address: row.get::<_, Option<IpAddr>>(2).map(|ip| ip.to_string()),
+        is_active: row.get(3),
+        created_at: row.get(4),
+        expires_at: row.get(5),
+    }).collect())
+}
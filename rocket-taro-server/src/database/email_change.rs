@@ -0,0 +1,136 @@
+use chrono::{DateTime, Utc};
+use tokio_postgres::{Error, Row};
+use tracing::{debug, info};
+use uuid::Uuid;
+
+use crate::database::DbPool;
+use crate::models::email_change::{EmailChangeConfirmation, PendingEmailChange};
+
+const EMAIL_CHANGE_COLUMNS: &str = "user_id, pending_email, session_id, current_token_hash IS NOT NULL, current_confirmed_at, new_confirmed_at, expires_at, created_at";
+
+fn row_to_pending(row: &Row) -> PendingEmailChange {
+    PendingEmailChange {
+        user_id: row.get(0),
+        pending_email: row.get(1),
+        session_id: row.get(2),
+        requires_current: row.get(3),
+        current_confirmed_at: row.get(4),
+        new_confirmed_at: row.get(5),
+        expires_at: row.get(6),
+        created_at: row.get(7),
+    }
+}
+
+// 保存邮箱修改申请，已有的申请（包括已确认的部分）被覆盖
+pub async fn create_email_change(
+    pool: &DbPool,
+    user_id: Uuid,
+    pending_email: &str,
+    session_id: Uuid,
+    current_token_hash: Option<&str>,
+    new_token_hash: &str,
+    expires_at: DateTime<Utc>,
+) -> Result<PendingEmailChange, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_one(
+        &format!(
+            "INSERT INTO email_change_requests (user_id, pending_email, session_id, current_token_hash, new_token_hash, expires_at)
+             VALUES ($1, $2, $3, $4, $5, $6)
+             ON CONFLICT (user_id) DO UPDATE SET
+                pending_email = EXCLUDED.pending_email,
+                session_id = EXCLUDED.session_id,
+                current_token_hash = EXCLUDED.current_token_hash,
+                current_confirmed_at = NULL,
+                new_token_hash = EXCLUDED.new_token_hash,
+                new_confirmed_at = NULL,
+                expires_at = EXCLUDED.expires_at,
+                created_at = CURRENT_TIMESTAMP
+             RETURNING {}",
+            EMAIL_CHANGE_COLUMNS,
+        ),
+        &[&user_id, &pending_email, &session_id, &current_token_hash, &new_token_hash, &expires_at],
+    ).await?;
+
+    info!(user_id = %user_id, "Email change request stored");
+    Ok(row_to_pending(&row))
+}
+
+// 获取用户未过期的邮箱修改申请
+pub async fn get_email_change(pool: &DbPool, user_id: Uuid) -> Result<Option<PendingEmailChange>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        &format!(
+            "SELECT {} FROM email_change_requests WHERE user_id = $1 AND expires_at > CURRENT_TIMESTAMP",
+            EMAIL_CHANGE_COLUMNS,
+        ),
+        &[&user_id],
+    ).await?;
+
+    Ok(row.as_ref().map(row_to_pending))
+}
+
+// 删除邮箱修改申请
+pub async fn delete_email_change(pool: &DbPool, user_id: Uuid) -> Result<bool, Error> {
+    let client = pool.lock().await;
+
+    let rows_affected = client.execute(
+        "DELETE FROM email_change_requests WHERE user_id = $1",
+        &[&user_id],
+    ).await?;
+
+    Ok(rows_affected > 0)
+}
+
+// 按令牌摘要确认；新旧邮箱都确认后在同一事务中修改邮箱、删除申请并使发起会话以外的会话失效
+pub async fn confirm_email_change(pool: &DbPool, token_hash: &str) -> Result<Option<EmailChangeConfirmation>, Error> {
+    let mut client = pool.lock().await;
+    let transaction = client.transaction().await?;
+
+    let Some(row) = transaction.query_opt(
+        "SELECT user_id, current_token_hash = $1 FROM email_change_requests
+         WHERE (current_token_hash = $1 OR new_token_hash = $1) AND expires_at > CURRENT_TIMESTAMP
+         FOR UPDATE",
+        &[&token_hash],
+    ).await? else {
+        return Ok(None);
+    };
+    let user_id: Uuid = row.get(0);
+    let is_current: Option<bool> = row.get(1);
+
+    let confirmed_column = if is_current == Some(true) { "current_confirmed_at" } else { "new_confirmed_at" };
+    debug!(user_id = %user_id, column = confirmed_column, "Confirming email change token");
+    let row = transaction.query_one(
+        &format!(
+            "UPDATE email_change_requests SET {0} = COALESCE({0}, CURRENT_TIMESTAMP) WHERE user_id = $1 RETURNING {1}",
+            confirmed_column,
+            EMAIL_CHANGE_COLUMNS,
+        ),
+        &[&user_id],
+    ).await?;
+    let pending = row_to_pending(&row);
+
+    if !pending.awaiting().is_empty() {
+        transaction.commit().await?;
+        return Ok(Some(EmailChangeConfirmation::Pending(pending)));
+    }
+
+    transaction.execute(
+        "UPDATE users SET email = $2, updated_at = CURRENT_TIMESTAMP, version = version + 1 WHERE id = $1 AND deleted_at IS NULL",
+        &[&user_id, &pending.pending_email],
+    ).await?;
+    transaction.execute("DELETE FROM email_change_requests WHERE user_id = $1", &[&user_id]).await?;
+    let revoked_sessions = transaction.execute(
+        "DELETE FROM user_sessions WHERE user_id = $1 AND id IS DISTINCT FROM $2",
+        &[&user_id, &pending.session_id],
+    ).await?;
+    transaction.commit().await?;
+
+    info!(user_id = %user_id, revoked_sessions = %revoked_sessions, "Email change applied");
+    Ok(Some(EmailChangeConfirmation::Applied {
+        user_id,
+        email: pending.pending_email,
+        revoked_sessions,
+    }))
+}
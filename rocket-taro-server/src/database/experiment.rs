@@ -0,0 +1,45 @@
+use tokio_postgres::Error;
+use uuid::Uuid;
+
+use crate::models::experiment::{ExperimentAssignment, ExposureCount};
+use super::DbPool;
+
+// 记录实验曝光：每个用户在每个实验中一行，分组以首次曝光为准，之后只累加次数
+pub async fn record_exposures(pool: &DbPool, user_id: Uuid, assignments: &[ExperimentAssignment]) -> Result<(), Error> {
+    let client = pool.lock().await;
+
+    let experiments: Vec<&str> = assignments.iter().map(|assignment| assignment.experiment.as_str()).collect();
+    let variants: Vec<&str> = assignments.iter().map(|assignment| assignment.variant.as_str()).collect();
+    client.execute(
+        "INSERT INTO experiment_exposures (experiment, user_id, variant)
+         SELECT e.experiment, $1, e.variant
+         FROM UNNEST($2::VARCHAR[], $3::VARCHAR[]) AS e(experiment, variant)
+         ON CONFLICT (experiment, user_id) DO UPDATE SET
+             exposures = experiment_exposures.exposures + 1,
+             last_exposed_at = CURRENT_TIMESTAMP",
+        &[&user_id, &experiments, &variants],
+    ).await?;
+
+    Ok(())
+}
+
+// 按实验和分组统计曝光的用户数和次数
+pub async fn count_exposures(pool: &DbPool, experiments: &[&str]) -> Result<Vec<ExposureCount>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        "SELECT experiment, variant, COUNT(*) AS users, COALESCE(SUM(exposures), 0)::BIGINT AS exposures
+         FROM experiment_exposures
+         WHERE experiment = ANY($1::VARCHAR[])
+         GROUP BY experiment, variant
+         ORDER BY experiment, variant",
+        &[&experiments],
+    ).await?;
+
+    Ok(rows.iter().map(|row| ExposureCount {
+        experiment: row.get("experiment"),
+        variant: row.get("variant"),
+        users: row.get("users"),
+        exposures: row.get("exposures"),
+    }).collect())
+}
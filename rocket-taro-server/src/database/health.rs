@@ -0,0 +1,120 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::{Arc, RwLock};
+
+/// 数据库熔断状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// 连接正常
+    Closed,
+    /// 连接已断开，请求直接降级，不访问数据库
+    Open,
+    /// 正在尝试重连
+    HalfOpen,
+}
+
+/// 数据库健康状态快照，用于 /readyz
+#[derive(Debug, Clone, Serialize)]
+pub struct DbHealthSnapshot {
+    pub state: CircuitState,
+    /// 连续重连失败次数
+    pub consecutive_failures: u32,
+    /// 最近一次连接错误
+    pub last_error: Option<String>,
+    /// 熔断开始时间
+    pub opened_at: Option<DateTime<Utc>>,
+}
+
+/// 数据库连接熔断器，由连接监视任务更新，请求守卫和 /readyz 读取
+#[derive(Clone)]
+pub struct DbHealth {
+    inner: Arc<RwLock<DbHealthSnapshot>>,
+}
+
+impl Default for DbHealth {
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(DbHealthSnapshot {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                last_error: None,
+                opened_at: None,
+            })),
+        }
+    }
+}
+
+impl DbHealth {
+    /// 数据库是否可用（熔断器关闭）
+    pub fn is_available(&self) -> bool {
+        self.inner.read().unwrap().state == CircuitState::Closed
+    }
+
+    pub fn snapshot(&self) -> DbHealthSnapshot {
+        self.inner.read().unwrap().clone()
+    }
+
+    /// 连接断开，打开熔断器
+    pub fn record_disconnect(&self, error: Option<String>) {
+        let mut state = self.inner.write().unwrap();
+        state.state = CircuitState::Open;
+        state.opened_at = Some(Utc::now());
+        if error.is_some() {
+            state.last_error = error;
+        }
+    }
+
+    /// 开始重连
+    pub fn record_attempt(&self) {
+        self.inner.write().unwrap().state = CircuitState::HalfOpen;
+    }
+
+    /// 重连失败，保持熔断
+    pub fn record_failure(&self, error: String) {
+        let mut state = self.inner.write().unwrap();
+        state.state = CircuitState::Open;
+        state.consecutive_failures += 1;
+        state.last_error = Some(error);
+    }
+
+    /// 重连成功，关闭熔断器
+    pub fn record_success(&self) {
+        let mut state = self.inner.write().unwrap();
+        state.state = CircuitState::Closed;
+        state.consecutive_failures = 0;
+        state.opened_at = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_circuit_transitions() {
+        let health = DbHealth::default();
+        assert!(health.is_available());
+
+        health.record_disconnect(Some("connection closed".to_string()));
+        assert!(!health.is_available());
+        assert!(health.snapshot().opened_at.is_some());
+
+        health.record_attempt();
+        assert_eq!(health.snapshot().state, CircuitState::HalfOpen);
+        assert!(!health.is_available());
+
+        health.record_failure("connection refused".to_string());
+        let snapshot = health.snapshot();
+        assert_eq!(snapshot.state, CircuitState::Open);
+        assert_eq!(snapshot.consecutive_failures, 1);
+        assert_eq!(snapshot.last_error.as_deref(), Some("connection refused"));
+
+        health.record_attempt();
+        health.record_success();
+        let snapshot = health.snapshot();
+        assert!(health.is_available());
+        assert_eq!(snapshot.consecutive_failures, 0);
+        assert!(snapshot.opened_at.is_none());
+    }
+}
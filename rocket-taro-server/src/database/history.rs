@@ -0,0 +1,57 @@
+use tokio_postgres::{Error, Row, Transaction};
+use uuid::Uuid;
+
+use crate::database::DbPool;
+use crate::models::history::{HistoryEntity, RowHistory, changed_fields};
+use crate::utils::pagination::PageRequest;
+
+const HISTORY_COLUMNS: &str = "id, row_id, operation, old_data, new_data, changed_by, changed_at";
+
+// 设置当前事务的操作者，事务内触发器写入的历史记录 changed_by 为该用户
+pub async fn set_actor(transaction: &Transaction<'_>, actor_id: Uuid) -> Result<(), Error> {
+    transaction.execute(
+        "SELECT set_config('app.actor_id', $1, true)",
+        &[&actor_id.to_string()],
+    ).await?;
+    Ok(())
+}
+
+fn row_to_history(entity: HistoryEntity, row: &Row) -> RowHistory {
+    let old_data: serde_json::Value = row.get(3);
+    let new_data: Option<serde_json::Value> = row.get(4);
+    RowHistory {
+        id: row.get(0),
+        entity,
+        row_id: row.get(1),
+        operation: row.get(2),
+        changed_fields: new_data.as_ref().map(|new_data| changed_fields(&old_data, new_data)).unwrap_or_default(),
+        old_data,
+        new_data,
+        changed_by: row.get(5),
+        changed_at: row.get(6),
+    }
+}
+
+// 按游标分页查询一行数据的变更历史（按时间倒序）
+pub async fn list_row_history(
+    pool: &DbPool,
+    entity: HistoryEntity,
+    row_id: Uuid,
+    page: &PageRequest,
+) -> Result<Vec<RowHistory>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!(
+            "SELECT {} FROM {}
+             WHERE row_id = $1 AND ($2::timestamptz IS NULL OR (changed_at, id) < ($2, $3))
+             ORDER BY changed_at DESC, id DESC
+             LIMIT $4",
+            HISTORY_COLUMNS,
+            entity.history_table(),
+        ),
+        &[&row_id, &page.cursor_created_at(), &page.cursor_id(), &page.fetch_limit()],
+    ).await?;
+
+    Ok(rows.iter().map(|row| row_to_history(entity, row)).collect())
+}
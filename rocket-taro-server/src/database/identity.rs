@@ -0,0 +1,120 @@
+use chrono::NaiveDate;
+use tokio_postgres::Error;
+use uuid::Uuid;
+
+use crate::models::identity::IdentityVerification;
+use super::DbPool;
+use super::row::{FromRow, impl_from_row};
+
+impl_from_row!(IdentityVerification {
+    user_id,
+    status,
+    masked_name,
+    masked_id_number,
+    birth_date,
+    provider,
+    provider_reference,
+    failure_reason,
+    #[sql = "CASE WHEN last_attempt_at::date = CURRENT_DATE THEN attempts ELSE 0 END"]
+    attempts,
+    last_attempt_at,
+    verified_at,
+    updated_at,
+});
+
+/// 认证服务返回的结果
+pub struct IdentityResult<'a> {
+    pub status: &'a str,
+    pub birth_date: Option<NaiveDate>,
+    pub provider_reference: Option<&'a str>,
+    pub failure_reason: Option<&'a str>,
+}
+
+// 用户的实名认证记录，attempts 为当天的提交次数
+pub async fn get_identity_verification(pool: &DbPool, user_id: Uuid) -> Result<Option<IdentityVerification>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        &format!(
+            "SELECT {} FROM identity_verifications WHERE user_id = $1",
+            IdentityVerification::select_columns(),
+        ),
+        &[&user_id],
+    ).await?;
+    row.as_ref().map(IdentityVerification::from_row).transpose()
+}
+
+// 记录一次提交及其结果，跨天后提交次数重新计算
+pub async fn record_identity_submission(
+    pool: &DbPool,
+    user_id: Uuid,
+    masked_name: &str,
+    masked_id_number: &str,
+    provider: &str,
+    result: &IdentityResult<'_>,
+) -> Result<IdentityVerification, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_one(
+        &format!(
+            "INSERT INTO identity_verifications (
+                 user_id, status, masked_name, masked_id_number, birth_date, provider,
+                 provider_reference, failure_reason, attempts, last_attempt_at, verified_at
+             )
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, 1, CURRENT_TIMESTAMP,
+                     CASE WHEN $2::VARCHAR = 'verified' THEN CURRENT_TIMESTAMP END)
+             ON CONFLICT (user_id) DO UPDATE SET
+                 status = EXCLUDED.status,
+                 masked_name = EXCLUDED.masked_name,
+                 masked_id_number = EXCLUDED.masked_id_number,
+                 birth_date = EXCLUDED.birth_date,
+                 provider = EXCLUDED.provider,
+                 provider_reference = EXCLUDED.provider_reference,
+                 failure_reason = EXCLUDED.failure_reason,
+                 attempts = CASE WHEN identity_verifications.last_attempt_at::date = CURRENT_DATE
+                                 THEN identity_verifications.attempts + 1 ELSE 1 END,
+                 last_attempt_at = EXCLUDED.last_attempt_at,
+                 verified_at = EXCLUDED.verified_at,
+                 updated_at = CURRENT_TIMESTAMP
+             RETURNING {}",
+            IdentityVerification::select_columns(),
+        ),
+        &[
+            &user_id,
+            &result.status,
+            &masked_name,
+            &masked_id_number,
+            &result.birth_date,
+            &provider,
+            &result.provider_reference,
+            &result.failure_reason,
+        ],
+    ).await?;
+    IdentityVerification::from_row(&row)
+}
+
+// 更新异步核验的结果，只更新仍在等待中的记录，记录已变化时返回 None
+pub async fn update_pending_identity(
+    pool: &DbPool,
+    user_id: Uuid,
+    result: &IdentityResult<'_>,
+) -> Result<Option<IdentityVerification>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        &format!(
+            "UPDATE identity_verifications SET
+                 status = $2,
+                 birth_date = COALESCE($3, birth_date),
+                 provider_reference = COALESCE($4, provider_reference),
+                 failure_reason = $5,
+                 verified_at = CASE WHEN $2::VARCHAR = 'verified' THEN CURRENT_TIMESTAMP END,
+                 updated_at = CURRENT_TIMESTAMP
+             WHERE user_id = $1 AND status = 'pending'
+             RETURNING {}",
+            IdentityVerification::select_columns(),
+        ),
+        &[&user_id, &result.status, &result.birth_date, &result.provider_reference, &result.failure_reason],
+    ).await?;
+    row.as_ref().map(IdentityVerification::from_row).transpose()
+}
@@ -0,0 +1,95 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use tokio_postgres::Error;
+
+use crate::models::log_archive::{ArchiveTable, next_month};
+use super::DbPool;
+
+// 在线表与归档表共有的列
+fn columns(table: ArchiveTable) -> &'static str {
+    match table {
+        ArchiveTable::LoginLogs => "id, user_id, username, success, ip_address, user_agent, failure_reason, created_at",
+        ArchiveTable::AuditLogs => "id, actor_id, action, target_type, target_id, ip_address, details, created_at",
+    }
+}
+
+// 在线表中早于指定时间的最早记录时间，用于确定需要创建的分区
+pub async fn oldest_log_before(pool: &DbPool, table: ArchiveTable, before: DateTime<Utc>) -> Result<Option<DateTime<Utc>>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_one(
+        &format!("SELECT MIN(created_at) FROM {} WHERE created_at < $1", table.table_name()),
+        &[&before],
+    ).await?;
+    Ok(row.get(0))
+}
+
+// 创建某月的归档分区（已存在时跳过），分区边界按 UTC 月份
+pub async fn create_archive_partition(pool: &DbPool, table: ArchiveTable, month: NaiveDate) -> Result<(), Error> {
+    let client = pool.lock().await;
+
+    client.batch_execute(&format!(
+        "CREATE TABLE IF NOT EXISTS {} PARTITION OF {} FOR VALUES FROM ('{} 00:00:00+00') TO ('{} 00:00:00+00')",
+        table.partition_name(month),
+        table.archive_table_name(),
+        month,
+        next_month(month),
+    )).await
+}
+
+// 将一批早于指定时间的记录移入归档表，返回移动的行数
+pub async fn archive_logs(pool: &DbPool, table: ArchiveTable, before: DateTime<Utc>, batch_size: i64) -> Result<u64, Error> {
+    let client = pool.lock().await;
+
+    let columns = columns(table);
+    client.execute(
+        &format!(
+            "WITH moved AS (
+                 DELETE FROM {table} WHERE id IN (
+                     SELECT id FROM {table} WHERE created_at < $1 ORDER BY created_at LIMIT $2
+                 )
+                 RETURNING {columns}
+             )
+             INSERT INTO {archive} ({columns}) SELECT {columns} FROM moved",
+            table = table.table_name(),
+            archive = table.archive_table_name(),
+        ),
+        &[&before, &batch_size],
+    ).await
+}
+
+// 分批删除在线表中早于指定时间的记录（不归档时按保留期清理），返回删除的行数
+pub async fn purge_logs(pool: &DbPool, table: ArchiveTable, before: DateTime<Utc>, batch_size: i64) -> Result<u64, Error> {
+    let client = pool.lock().await;
+
+    client.execute(
+        &format!(
+            "DELETE FROM {table} WHERE id IN (
+                 SELECT id FROM {table} WHERE created_at < $1 ORDER BY created_at LIMIT $2
+             )",
+            table = table.table_name(),
+        ),
+        &[&before, &batch_size],
+    ).await
+}
+
+// 归档表的所有分区名
+pub async fn list_archive_partitions(pool: &DbPool, table: ArchiveTable) -> Result<Vec<String>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        "SELECT c.relname FROM pg_inherits i
+         JOIN pg_class c ON c.oid = i.inhrelid
+         JOIN pg_class p ON p.oid = i.inhparent
+         WHERE p.relname = $1
+         ORDER BY c.relname",
+        &[&table.archive_table_name()],
+    ).await?;
+    Ok(rows.iter().map(|row| row.get(0)).collect())
+}
+
+// 删除某月的归档分区
+pub async fn drop_archive_partition(pool: &DbPool, table: ArchiveTable, month: NaiveDate) -> Result<(), Error> {
+    let client = pool.lock().await;
+
+    client.batch_execute(&format!("DROP TABLE IF EXISTS {}", table.partition_name(month))).await
+}
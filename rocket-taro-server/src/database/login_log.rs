@@ -0,0 +1,74 @@
+use tokio_postgres::Error;
+use uuid::Uuid;
+
+use crate::models::login_log::{LoginLog, NewLoginLog};
+use crate::utils::pagination::PageRequest;
+use super::DbPool;
+use super::row::{FromRow, impl_from_row};
+
+impl_from_row!(LoginLog {
+    id, user_id, username, success, ip_address, user_agent, failure_reason, created_at,
+});
+
+// 写入登录记录
+pub async fn insert_login_log(pool: &DbPool, log: &NewLoginLog) -> Result<(), Error> {
+    let client = pool.lock().await;
+
+    client.execute(
+        "INSERT INTO login_logs (user_id, username, success, ip_address, user_agent, failure_reason)
+         VALUES ($1, $2, $3, $4, $5, $6)",
+        &[&log.user_id, &log.username, &log.success, &log.ip_address, &log.user_agent, &log.failure_reason],
+    ).await?;
+    Ok(())
+}
+
+// 查询用户最近的登录记录（按时间倒序）
+pub async fn list_user_login_logs(pool: &DbPool, user_id: Uuid, limit: i64) -> Result<Vec<LoginLog>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!(
+            "SELECT {} FROM login_logs WHERE user_id = $1 ORDER BY created_at DESC LIMIT $2",
+            LoginLog::select_columns(),
+        ),
+        &[&user_id, &limit],
+    ).await?;
+    rows.iter().map(LoginLog::from_row).collect()
+}
+
+// 批量查询多个用户最近的登录记录，每个用户最多 per_user 条（按时间倒序）
+pub async fn list_recent_login_logs_for_users(pool: &DbPool, user_ids: &[Uuid], per_user: i64) -> Result<Vec<LoginLog>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!(
+            "SELECT {columns} FROM (
+                 SELECT *, ROW_NUMBER() OVER (PARTITION BY user_id ORDER BY created_at DESC, id DESC) AS position
+                 FROM login_logs WHERE user_id = ANY($1)
+             ) recent
+             WHERE position <= $2
+             ORDER BY created_at DESC, id DESC",
+            columns = LoginLog::select_columns(),
+        ),
+        &[&user_ids, &per_user],
+    ).await?;
+    rows.iter().map(LoginLog::from_row).collect()
+}
+
+// 按游标分页查询所有登录记录（最新的在前），success 为 None 时不按结果筛选
+pub async fn list_login_logs_page(pool: &DbPool, success: Option<bool>, page: &PageRequest) -> Result<Vec<LoginLog>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!(
+            "SELECT {} FROM login_logs
+             WHERE ($1::bool IS NULL OR success = $1)
+               AND ($2::timestamptz IS NULL OR (created_at, id) < ($2, $3))
+             ORDER BY created_at DESC, id DESC
+             LIMIT $4",
+            LoginLog::select_columns(),
+        ),
+        &[&success, &page.cursor_created_at(), &page.cursor_id(), &page.fetch_limit()],
+    ).await?;
+    rows.iter().map(LoginLog::from_row).collect()
+}
@@ -0,0 +1,31 @@
+-- Migration: Full-text search over user_data
+-- Date: 2026-10-16
+-- Description: Adds a generated tsvector column (name/email/message, weighted A/B/C)
+--              and a GIN index used by GET /api/user-data/search.
+--              Uses the 'simple' configuration so mixed Chinese/English text is
+--              tokenized on whitespace and punctuation without stemming.
+
+-- Step 1: Add generated search vector column
+ALTER TABLE user_data ADD COLUMN IF NOT EXISTS search_vector tsvector
+    GENERATED ALWAYS AS (
+        setweight(to_tsvector('simple', coalesce(name, '')), 'A') ||
+        setweight(to_tsvector('simple', coalesce(email, '')), 'B') ||
+        setweight(to_tsvector('simple', coalesce(message, '')), 'C')
+    ) STORED;
+
+-- Step 2: Create GIN index for search queries
+CREATE INDEX IF NOT EXISTS idx_user_data_search ON user_data USING GIN (search_vector);
+
+-- Verification query:
+-- SELECT column_name, data_type, is_generated
+-- FROM information_schema.columns
+-- WHERE table_name = 'user_data' AND column_name = 'search_vector';
+--
+-- Expected result:
+-- column_name   | data_type | is_generated
+-- search_vector | tsvector  | ALWAYS
+
+-- Rollback SQL (if needed):
+-- DROP INDEX IF EXISTS idx_user_data_search;
+-- ALTER TABLE user_data DROP COLUMN IF EXISTS search_vector;
+-- DELETE FROM schema_migrations WHERE version = 2;
@@ -0,0 +1,20 @@
+-- Migration: Hash session tokens at rest
+-- Date: 2026-10-16
+-- Description: Stores only the SHA-256 hex digest of session tokens in user_sessions.
+--              Renames session_token to token_hash and hashes existing rows in place,
+--              so sessions issued before the upgrade stay valid. Databases created
+--              after this change already have token_hash and are left untouched.
+
+DO $$
+BEGIN
+    IF EXISTS (
+        SELECT 1 FROM information_schema.columns
+        WHERE table_name = 'user_sessions' AND column_name = 'session_token'
+    ) THEN
+        ALTER TABLE user_sessions RENAME COLUMN session_token TO token_hash;
+        UPDATE user_sessions SET token_hash = encode(sha256(convert_to(token_hash, 'UTF8')), 'hex');
+    END IF;
+END $$;
+
+-- Verification query:
+-- SELECT count(*) FROM user_sessions WHERE length(token_hash) <> 64;
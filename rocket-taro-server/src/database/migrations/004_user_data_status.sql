@@ -0,0 +1,19 @@
+-- Migration: Processing status for user_data
+-- Date: 2026-10-16
+-- Description: Adds a status column (new / in_progress / resolved) updated by admins
+--              when following up on a submission, and an index for looking up a
+--              user's own submissions by email (case-insensitive).
+
+-- Step 1: Add status column, existing rows start as 'new'
+ALTER TABLE user_data ADD COLUMN IF NOT EXISTS status VARCHAR(20) NOT NULL DEFAULT 'new';
+
+-- Step 2: Index for GET /api/user-data/mine
+CREATE INDEX IF NOT EXISTS idx_user_data_email ON user_data (lower(email), created_at DESC);
+
+-- Verification query:
+-- SELECT status, count(*) FROM user_data GROUP BY status;
+
+-- Rollback SQL (if needed):
+-- DROP INDEX IF EXISTS idx_user_data_email;
+-- ALTER TABLE user_data DROP COLUMN IF EXISTS status;
+-- DELETE FROM schema_migrations WHERE version = 4;
@@ -0,0 +1,76 @@
+-- Migration: Row-level change history for users and user_data
+-- Date: 2026-10-16
+-- Description: Records the before/after state of every UPDATE and DELETE on users and
+--              user_data into users_history / user_data_history via a shared trigger
+--              function. Trigger arguments list columns that are never stored
+--              (credentials, generated columns) or whose changes alone are not worth
+--              recording (login timestamps). The acting user is read from the
+--              transaction-local setting app.actor_id when the application sets it.
+
+-- Step 1: History tables
+CREATE TABLE IF NOT EXISTS users_history (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    row_id UUID NOT NULL,
+    operation VARCHAR(10) NOT NULL,
+    old_data JSONB NOT NULL,
+    new_data JSONB,
+    changed_by UUID,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp()
+);
+CREATE INDEX IF NOT EXISTS idx_users_history_row ON users_history (row_id, changed_at DESC, id DESC);
+
+CREATE TABLE IF NOT EXISTS user_data_history (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    row_id UUID NOT NULL,
+    operation VARCHAR(10) NOT NULL,
+    old_data JSONB NOT NULL,
+    new_data JSONB,
+    changed_by UUID,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp()
+);
+CREATE INDEX IF NOT EXISTS idx_user_data_history_row ON user_data_history (row_id, changed_at DESC, id DESC);
+
+-- Step 2: Shared trigger function, writes to <table>_history
+CREATE OR REPLACE FUNCTION record_row_history() RETURNS trigger AS $$
+DECLARE
+    excluded TEXT[] := COALESCE(TG_ARGV::TEXT[], '{}');
+    old_data JSONB := to_jsonb(OLD) - excluded;
+    new_data JSONB;
+    actor UUID := NULLIF(current_setting('app.actor_id', true), '')::UUID;
+BEGIN
+    IF TG_OP = 'UPDATE' THEN
+        new_data := to_jsonb(NEW) - excluded;
+        IF new_data = old_data THEN
+            RETURN NULL;
+        END IF;
+    END IF;
+
+    EXECUTE format(
+        'INSERT INTO %I (row_id, operation, old_data, new_data, changed_by) VALUES ($1, $2, $3, $4, $5)',
+        TG_TABLE_NAME || '_history'
+    ) USING OLD.id, lower(TG_OP), old_data, new_data, actor;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Step 3: Triggers
+DROP TRIGGER IF EXISTS users_history_trigger ON users;
+CREATE TRIGGER users_history_trigger
+    AFTER UPDATE OR DELETE ON users
+    FOR EACH ROW EXECUTE FUNCTION record_row_history('password_hash', 'wx_session_key', 'last_login_at', 'updated_at');
+
+DROP TRIGGER IF EXISTS user_data_history_trigger ON user_data;
+CREATE TRIGGER user_data_history_trigger
+    AFTER UPDATE OR DELETE ON user_data
+    FOR EACH ROW EXECUTE FUNCTION record_row_history('search_vector');
+
+-- Verification query:
+-- SELECT tgname FROM pg_trigger WHERE tgname LIKE '%_history_trigger';
+
+-- Rollback SQL (if needed):
+-- DROP TRIGGER IF EXISTS users_history_trigger ON users;
+-- DROP TRIGGER IF EXISTS user_data_history_trigger ON user_data;
+-- DROP FUNCTION IF EXISTS record_row_history();
+-- DROP TABLE IF EXISTS users_history;
+-- DROP TABLE IF EXISTS user_data_history;
+-- DELETE FROM schema_migrations WHERE version = 5;
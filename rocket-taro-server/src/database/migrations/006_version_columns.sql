@@ -0,0 +1,20 @@
+-- Migration: Optimistic locking version columns
+-- Date: 2026-10-16
+-- Description: Adds a version counter to users and user_data. Updates that change
+--              user-editable content increment it, and clients send the version
+--              they last read (If-Match header or "version" field) so concurrent
+--              edits are rejected with 409 instead of silently overwriting.
+
+-- Step 1: Add version columns, existing rows start at version 1
+ALTER TABLE users ADD COLUMN IF NOT EXISTS version INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE user_data ADD COLUMN IF NOT EXISTS version INTEGER NOT NULL DEFAULT 1;
+
+-- Verification query:
+-- SELECT table_name, column_name, column_default
+-- FROM information_schema.columns
+-- WHERE column_name = 'version' AND table_name IN ('users', 'user_data');
+
+-- Rollback SQL (if needed):
+-- ALTER TABLE users DROP COLUMN IF EXISTS version;
+-- ALTER TABLE user_data DROP COLUMN IF EXISTS version;
+-- DELETE FROM schema_migrations WHERE version = 6;
@@ -0,0 +1,80 @@
+-- Migration: Reconcile login_logs schema
+-- Date: 2026-10-16
+-- Description: init_auth_tables created login_logs with is_success / error_message
+--              while inserts used login_success / failure_reason, and some
+--              databases store ip_address as text. Unify on success /
+--              failure_reason / INET and add indexes for per-user lookups and
+--              the retention job.
+
+-- Step 1: Rename or merge the result column into "success"
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM information_schema.columns
+                   WHERE table_name = 'login_logs' AND column_name = 'success') THEN
+        ALTER TABLE login_logs ADD COLUMN success BOOLEAN;
+    END IF;
+
+    IF EXISTS (SELECT 1 FROM information_schema.columns
+               WHERE table_name = 'login_logs' AND column_name = 'is_success') THEN
+        UPDATE login_logs SET success = COALESCE(success, is_success);
+        ALTER TABLE login_logs DROP COLUMN is_success;
+    END IF;
+
+    IF EXISTS (SELECT 1 FROM information_schema.columns
+               WHERE table_name = 'login_logs' AND column_name = 'login_success') THEN
+        UPDATE login_logs SET success = COALESCE(success, login_success);
+        ALTER TABLE login_logs DROP COLUMN login_success;
+    END IF;
+END $$;
+
+UPDATE login_logs SET success = false WHERE success IS NULL;
+ALTER TABLE login_logs ALTER COLUMN success SET NOT NULL;
+
+-- Step 2: Merge error_message into failure_reason
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM information_schema.columns
+                   WHERE table_name = 'login_logs' AND column_name = 'failure_reason') THEN
+        ALTER TABLE login_logs ADD COLUMN failure_reason TEXT;
+    END IF;
+
+    IF EXISTS (SELECT 1 FROM information_schema.columns
+               WHERE table_name = 'login_logs' AND column_name = 'error_message') THEN
+        UPDATE login_logs SET failure_reason = COALESCE(failure_reason, error_message);
+        ALTER TABLE login_logs DROP COLUMN error_message;
+    END IF;
+END $$;
+
+-- Step 3: Convert a text ip_address column to INET, values that are not valid addresses become NULL
+CREATE OR REPLACE FUNCTION pg_temp.try_inet(value TEXT) RETURNS INET AS $$
+BEGIN
+    RETURN NULLIF(value, '')::inet;
+EXCEPTION WHEN others THEN
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DO $$
+BEGIN
+    IF EXISTS (SELECT 1 FROM information_schema.columns
+               WHERE table_name = 'login_logs' AND column_name = 'ip_address' AND data_type <> 'inet') THEN
+        ALTER TABLE login_logs ALTER COLUMN ip_address TYPE INET USING pg_temp.try_inet(ip_address::text);
+    END IF;
+END $$;
+
+-- Step 4: Indexes for per-user history and retention cleanup
+CREATE INDEX IF NOT EXISTS idx_login_logs_user_id ON login_logs(user_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_login_logs_created_at ON login_logs(created_at);
+
+-- Verification query:
+-- SELECT column_name, data_type, is_nullable
+-- FROM information_schema.columns
+-- WHERE table_name = 'login_logs'
+-- ORDER BY ordinal_position;
+
+-- Rollback SQL (if needed):
+-- DROP INDEX IF EXISTS idx_login_logs_user_id;
+-- DROP INDEX IF EXISTS idx_login_logs_created_at;
+-- ALTER TABLE login_logs RENAME COLUMN success TO is_success;
+-- ALTER TABLE login_logs RENAME COLUMN failure_reason TO error_message;
+-- DELETE FROM schema_migrations WHERE version = 7;
@@ -0,0 +1,25 @@
+-- Migration: Session last access time
+-- Date: 2026-10-16
+-- Description: validate_session has always updated user_sessions.last_accessed_at,
+--              but the column was never created. Add it (existing sessions start
+--              from their creation time) so idle sessions can be expired and
+--              recently active users can be counted.
+
+-- Step 1: Add the column and backfill existing sessions
+ALTER TABLE user_sessions ADD COLUMN IF NOT EXISTS last_accessed_at TIMESTAMPTZ;
+UPDATE user_sessions SET last_accessed_at = created_at WHERE last_accessed_at IS NULL;
+ALTER TABLE user_sessions ALTER COLUMN last_accessed_at SET DEFAULT CURRENT_TIMESTAMP;
+ALTER TABLE user_sessions ALTER COLUMN last_accessed_at SET NOT NULL;
+
+-- Step 2: Index for recently active user counts
+CREATE INDEX IF NOT EXISTS idx_user_sessions_last_accessed_at ON user_sessions(last_accessed_at);
+
+-- Verification query:
+-- SELECT column_name, data_type, column_default, is_nullable
+-- FROM information_schema.columns
+-- WHERE table_name = 'user_sessions' AND column_name = 'last_accessed_at';
+
+-- Rollback SQL (if needed):
+-- DROP INDEX IF EXISTS idx_user_sessions_last_accessed_at;
+-- ALTER TABLE user_sessions DROP COLUMN IF EXISTS last_accessed_at;
+-- DELETE FROM schema_migrations WHERE version = 8;
@@ -0,0 +1,51 @@
+-- Migration: Monthly archive tables for login_logs and audit_logs
+-- Date: 2026-10-16
+-- Description: login_logs and audit_logs are append-only and grow without bound.
+--              Rows past the archive horizon are moved by the log archive job into
+--              *_archive tables partitioned by month (partitions are created by the
+--              job as needed), so expired months can be dropped as whole partitions.
+
+-- Step 1: Archive table for login_logs
+CREATE TABLE IF NOT EXISTS login_logs_archive (
+    id UUID NOT NULL,
+    user_id UUID,
+    username VARCHAR(50) NOT NULL,
+    success BOOLEAN NOT NULL,
+    ip_address INET,
+    user_agent TEXT,
+    failure_reason TEXT,
+    created_at TIMESTAMPTZ NOT NULL,
+    archived_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+) PARTITION BY RANGE (created_at);
+
+CREATE INDEX IF NOT EXISTS idx_login_logs_archive_user_id ON login_logs_archive(user_id, created_at);
+
+-- Step 2: Archive table for audit_logs
+CREATE TABLE IF NOT EXISTS audit_logs_archive (
+    id UUID NOT NULL,
+    actor_id UUID,
+    action VARCHAR(100) NOT NULL,
+    target_type VARCHAR(50) NOT NULL,
+    target_id VARCHAR(255),
+    ip_address INET,
+    details JSONB NOT NULL DEFAULT '{}'::jsonb,
+    created_at TIMESTAMPTZ NOT NULL,
+    archived_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+) PARTITION BY RANGE (created_at);
+
+CREATE INDEX IF NOT EXISTS idx_audit_logs_archive_target ON audit_logs_archive(target_type, target_id, created_at);
+
+-- The index for selecting audit rows past the archive horizon
+-- (idx_audit_logs_created_at) is created with audit_logs in 021.
+
+-- Verification query:
+-- SELECT p.relname AS archive_table, c.relname AS partition
+-- FROM pg_inherits i
+-- JOIN pg_class c ON c.oid = i.inhrelid
+-- JOIN pg_class p ON p.oid = i.inhparent
+-- WHERE p.relname IN ('login_logs_archive', 'audit_logs_archive');
+
+-- Rollback SQL (if needed):
+-- DROP TABLE IF EXISTS audit_logs_archive;
+-- DROP TABLE IF EXISTS login_logs_archive;
+-- DELETE FROM schema_migrations WHERE version = 9;
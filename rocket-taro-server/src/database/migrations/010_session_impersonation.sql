@@ -0,0 +1,21 @@
+-- Migration: Admin impersonation sessions
+-- Date: 2026-10-16
+-- Description: Sessions created by POST /api/admin/impersonate/<user_id> belong to
+--              the impersonated user but record the admin who created them, so
+--              guards can restrict sensitive actions and every change made through
+--              the session can be audited under the original admin.
+
+-- Step 1: Record the impersonating admin on the session
+ALTER TABLE user_sessions ADD COLUMN IF NOT EXISTS impersonator_id UUID REFERENCES users(id) ON DELETE CASCADE;
+
+-- Step 2: Index for listing impersonation sessions by admin
+CREATE INDEX IF NOT EXISTS idx_user_sessions_impersonator_id ON user_sessions(impersonator_id)
+    WHERE impersonator_id IS NOT NULL;
+
+-- Verification query:
+-- SELECT id, user_id, impersonator_id, expires_at FROM user_sessions WHERE impersonator_id IS NOT NULL;
+
+-- Rollback SQL (if needed):
+-- DROP INDEX IF EXISTS idx_user_sessions_impersonator_id;
+-- ALTER TABLE user_sessions DROP COLUMN IF EXISTS impersonator_id;
+-- DELETE FROM schema_migrations WHERE version = 10;
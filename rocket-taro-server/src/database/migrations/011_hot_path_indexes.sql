@@ -0,0 +1,25 @@
+-- Migration: Composite indexes for session and login hot paths
+-- Date: 2026-10-16
+-- Description: Session validation filters on token_hash (formerly session_token,
+--              see migration 003) together with expires_at, session listing and
+--              limits filter on user_id with expires_at, and login history by
+--              username is ordered by created_at. user_data(created_at DESC) is
+--              already covered by idx_user_data_created_at, created at startup.
+--              Usage of these indexes is reported by GET /api/admin/db/index-usage.
+
+-- Step 1: Session lookups by token and by user, restricted to unexpired sessions
+CREATE INDEX IF NOT EXISTS idx_user_sessions_token_hash_expires_at ON user_sessions(token_hash, expires_at);
+CREATE INDEX IF NOT EXISTS idx_user_sessions_user_id_expires_at ON user_sessions(user_id, expires_at);
+
+-- Step 2: Login history by username, newest first
+CREATE INDEX IF NOT EXISTS idx_login_logs_username_created_at ON login_logs(username, created_at DESC);
+
+-- Verification query:
+-- SELECT indexrelname, idx_scan FROM pg_stat_user_indexes
+-- WHERE relname IN ('user_sessions', 'login_logs', 'user_data') ORDER BY relname, indexrelname;
+
+-- Rollback SQL (if needed):
+-- DROP INDEX IF EXISTS idx_user_sessions_token_hash_expires_at;
+-- DROP INDEX IF EXISTS idx_user_sessions_user_id_expires_at;
+-- DROP INDEX IF EXISTS idx_login_logs_username_created_at;
+-- DELETE FROM schema_migrations WHERE version = 11;
@@ -0,0 +1,25 @@
+-- Migration: Per-user and per-role API quota overrides
+-- Date: 2026-10-16
+-- Description: Daily quotas for expensive endpoints are configured per group in
+--              [default.api_quotas]. Admins can override the limit for a single
+--              user (subject "user:<id>") or a role (subject "role:<name>");
+--              user overrides take precedence over role overrides. A limit of 0
+--              means unlimited.
+
+-- Step 1: Override table, one row per group and subject
+CREATE TABLE IF NOT EXISTS api_quota_overrides (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    group_name VARCHAR(50) NOT NULL,
+    subject VARCHAR(100) NOT NULL,
+    daily_limit BIGINT NOT NULL CHECK (daily_limit >= 0),
+    updated_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (group_name, subject)
+);
+
+-- Verification query:
+-- SELECT group_name, subject, daily_limit FROM api_quota_overrides ORDER BY group_name, subject;
+
+-- Rollback SQL (if needed):
+-- DROP TABLE IF EXISTS api_quota_overrides;
+-- DELETE FROM schema_migrations WHERE version = 12;
@@ -0,0 +1,32 @@
+-- Migration: Review queue for suspicious registrations
+-- Date: 2026-10-16
+-- Description: Registrations that pass the anti-automation checks but look
+--              suspicious (high per-IP/per-device velocity, form submitted too
+--              fast) are queued here for an admin to approve or reject. One row
+--              per user; rejecting deactivates the account.
+
+-- Step 1: Review queue
+CREATE TABLE IF NOT EXISTS registration_reviews (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL UNIQUE REFERENCES users(id) ON DELETE CASCADE,
+    reasons TEXT[] NOT NULL,
+    ip_address INET,
+    device_id VARCHAR(128),
+    status VARCHAR(20) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'approved', 'rejected')),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    reviewed_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    reviewed_at TIMESTAMPTZ,
+    note TEXT
+);
+
+-- Step 2: Admin queue is listed by status, newest first
+CREATE INDEX IF NOT EXISTS idx_registration_reviews_status_created_at
+    ON registration_reviews (status, created_at DESC);
+
+-- Verification query:
+-- SELECT status, COUNT(*) FROM registration_reviews GROUP BY status;
+
+-- Rollback SQL (if needed):
+-- DROP TABLE IF EXISTS registration_reviews;
+-- DELETE FROM schema_migrations WHERE version = 13;
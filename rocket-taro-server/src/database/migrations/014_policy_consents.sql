@@ -0,0 +1,46 @@
+-- Migration: Versioned policy documents and user consent records
+-- Date: 2026-10-16
+-- Description: Terms of service and privacy policy are stored as versioned
+--              documents; at most one version per policy type is active. Users
+--              must accept every active version before using guarded endpoints.
+--              Each acceptance is recorded with the client IP, user agent and
+--              timestamp.
+
+-- Step 1: Policy documents, one row per type and version
+CREATE TABLE IF NOT EXISTS policy_documents (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    policy_type VARCHAR(20) NOT NULL CHECK (policy_type IN ('terms', 'privacy')),
+    version VARCHAR(30) NOT NULL,
+    title VARCHAR(200) NOT NULL,
+    content TEXT NOT NULL,
+    is_active BOOLEAN NOT NULL DEFAULT false,
+    published_at TIMESTAMPTZ,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (policy_type, version)
+);
+
+-- Step 2: Only one active version per policy type
+CREATE UNIQUE INDEX IF NOT EXISTS idx_policy_documents_active_type
+    ON policy_documents (policy_type) WHERE is_active;
+
+-- Step 3: Acceptance records, one row per user and document version
+CREATE TABLE IF NOT EXISTS user_consents (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    document_id UUID NOT NULL REFERENCES policy_documents(id) ON DELETE CASCADE,
+    ip_address INET,
+    user_agent TEXT,
+    accepted_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (user_id, document_id)
+);
+
+-- Verification query:
+-- SELECT d.policy_type, d.version, COUNT(c.id) AS accepted
+-- FROM policy_documents d LEFT JOIN user_consents c ON c.document_id = d.id
+-- GROUP BY d.policy_type, d.version ORDER BY d.policy_type, d.version;
+
+-- Rollback SQL (if needed):
+-- DROP TABLE IF EXISTS user_consents;
+-- DROP TABLE IF EXISTS policy_documents;
+-- DELETE FROM schema_migrations WHERE version = 14;
@@ -0,0 +1,31 @@
+-- Migration: Real-name identity verification status
+-- Date: 2026-10-16
+-- Description: One verification record per user. Names and ID numbers are sent
+--              to the external verification provider only; this table keeps
+--              masked copies, the birth date used for the age gate and the
+--              provider reference for asynchronous results. Attempts are
+--              counted per calendar day to limit repeated submissions.
+
+-- Step 1: Verification record per user
+CREATE TABLE IF NOT EXISTS identity_verifications (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    status VARCHAR(20) NOT NULL DEFAULT 'unverified'
+        CHECK (status IN ('unverified', 'pending', 'verified', 'rejected')),
+    masked_name VARCHAR(100),
+    masked_id_number VARCHAR(40),
+    birth_date DATE,
+    provider VARCHAR(30),
+    provider_reference VARCHAR(100),
+    failure_reason VARCHAR(200),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_attempt_at TIMESTAMPTZ,
+    verified_at TIMESTAMPTZ,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Verification query:
+-- SELECT status, COUNT(*) FROM identity_verifications GROUP BY status ORDER BY status;
+
+-- Rollback SQL (if needed):
+-- DROP TABLE IF EXISTS identity_verifications;
+-- DELETE FROM schema_migrations WHERE version = 15;
@@ -0,0 +1,40 @@
+-- Migration: Asynchronous re-check queue for moderated user content
+-- Date: 2026-10-16
+-- Description: Text submissions that the moderation provider flags for review,
+--              or that were accepted while the provider was unavailable, are
+--              queued here with the submitted content. A background job
+--              re-checks them and clears the content if it turns out to be
+--              risky and has not been changed since.
+
+-- Step 1: Re-check queue
+CREATE TABLE IF NOT EXISTS moderation_checks (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    subject_type VARCHAR(30) NOT NULL CHECK (subject_type IN ('user_data_message', 'profile_name')),
+    subject_id UUID NOT NULL,
+    user_id UUID REFERENCES users(id) ON DELETE CASCADE,
+    content TEXT NOT NULL,
+    reason VARCHAR(20) NOT NULL CHECK (reason IN ('review', 'unavailable')),
+    status VARCHAR(20) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'passed', 'removed', 'failed')),
+    label VARCHAR(50),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    next_check_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    checked_at TIMESTAMPTZ
+);
+
+-- Step 2: Due pending checks are claimed in next_check_at order
+CREATE INDEX IF NOT EXISTS idx_moderation_checks_due
+    ON moderation_checks (next_check_at) WHERE status = 'pending';
+
+-- Step 3: Admin listing by status
+CREATE INDEX IF NOT EXISTS idx_moderation_checks_status
+    ON moderation_checks (status, created_at DESC);
+
+-- Verification query:
+-- SELECT subject_type, status, COUNT(*) FROM moderation_checks GROUP BY subject_type, status ORDER BY 1, 2;
+
+-- Rollback SQL (if needed):
+-- DROP TABLE IF EXISTS moderation_checks;
+-- DELETE FROM schema_migrations WHERE version = 16;
@@ -0,0 +1,40 @@
+-- Migration: Locally uploaded avatars and their processing status
+-- Date: 2026-10-16
+-- Description: Avatar uploads are stored as-is and queued for a background
+--              pipeline (EXIF stripping, resize, moderation, WebP conversion).
+--              Only avatars with status 'ready' are served; users.avatar_url
+--              points at the processed file once it is ready.
+
+-- Step 1: Avatar uploads
+CREATE TABLE IF NOT EXISTS avatars (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'ready', 'rejected', 'failed')),
+    source_path VARCHAR(500) NOT NULL,
+    source_content_type VARCHAR(100) NOT NULL,
+    file_path VARCHAR(500),
+    width INTEGER,
+    height INTEGER,
+    file_size BIGINT,
+    failure_reason TEXT,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    processed_at TIMESTAMPTZ
+);
+
+-- Step 2: Due pending avatars are claimed in next_attempt_at order
+CREATE INDEX IF NOT EXISTS idx_avatars_due
+    ON avatars (next_attempt_at) WHERE status = 'pending';
+
+-- Step 3: A user's avatars (replacing the previous one, superseding pending uploads)
+CREATE INDEX IF NOT EXISTS idx_avatars_user
+    ON avatars (user_id, status);
+
+-- Verification query:
+-- SELECT status, COUNT(*) FROM avatars GROUP BY status ORDER BY 1;
+
+-- Rollback SQL (if needed):
+-- DROP TABLE IF EXISTS avatars;
+-- DELETE FROM schema_migrations WHERE version = 17;
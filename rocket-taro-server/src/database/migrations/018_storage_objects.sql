@@ -0,0 +1,53 @@
+-- Migration: Storage objects for generated files
+-- Date: 2026-10-16
+-- Description: Generated files (data exports, takeout archives) are kept in a
+--              pluggable storage backend (local directory or S3) and tracked
+--              here for per-user usage accounting and lifecycle cleanup.
+--              data_exports.file_path now holds the storage key instead of a
+--              local path; existing exports are rewritten to 'exports/<id>.json',
+--              which resolves to the old default location (data/exports) under
+--              the default local storage root.
+
+-- Step 1: Stored objects
+CREATE TABLE IF NOT EXISTS storage_objects (
+    key VARCHAR(500) PRIMARY KEY,
+    user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    kind VARCHAR(30) NOT NULL,
+    size BIGINT NOT NULL DEFAULT 0,
+    content_type VARCHAR(100) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    expires_at TIMESTAMPTZ
+);
+
+-- Step 2: Expired objects are claimed in expires_at order
+CREATE INDEX IF NOT EXISTS idx_storage_objects_expires
+    ON storage_objects (expires_at) WHERE expires_at IS NOT NULL;
+
+-- Step 3: Per-user usage
+CREATE INDEX IF NOT EXISTS idx_storage_objects_user
+    ON storage_objects (user_id);
+
+-- Step 4: Existing data exports become storage keys
+-- (a new database has no data_exports table yet, it is created in 024)
+DO $$
+BEGIN
+    IF to_regclass('data_exports') IS NOT NULL THEN
+        UPDATE data_exports SET file_path = 'exports/' || id || '.json'
+        WHERE file_path IS NOT NULL;
+
+        INSERT INTO storage_objects (key, user_id, kind, size, content_type, created_at, expires_at)
+        SELECT file_path, user_id, 'data_export', COALESCE(file_size, 0), 'application/json',
+               COALESCE(completed_at, created_at), expires_at
+        FROM data_exports
+        WHERE status = 'ready' AND file_path IS NOT NULL
+        ON CONFLICT (key) DO NOTHING;
+    END IF;
+END $$;
+
+-- Verification query:
+-- SELECT kind, COUNT(*), SUM(size) FROM storage_objects GROUP BY kind ORDER BY 1;
+
+-- Rollback SQL (if needed):
+-- UPDATE data_exports SET file_path = 'data/' || file_path WHERE file_path IS NOT NULL;
+-- DROP TABLE IF EXISTS storage_objects;
+-- DELETE FROM schema_migrations WHERE version = 18;
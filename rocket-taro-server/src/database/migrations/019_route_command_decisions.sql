@@ -0,0 +1,15 @@
+-- Migration: Route command decision trace
+-- Date: 2026-10-16
+-- Description: When [route_trace] is enabled, the rules the route command
+--              generator fired (with their input values) are recorded per
+--              issued command, queryable by execution_id, to answer
+--              "why was this user sent to page X".
+
+-- The route_command_decisions table references route_command_executions,
+-- so it is created right after that table in 029.
+
+-- Verification query:
+-- SELECT rule, COUNT(*) FROM route_command_decisions GROUP BY rule ORDER BY 2 DESC;
+
+-- Rollback SQL (if needed):
+-- DELETE FROM schema_migrations WHERE version = 19;
@@ -0,0 +1,28 @@
+-- Migration: Experiment exposures
+-- Date: 2026-10-16
+-- Description: One row per user and experiment ([experiments] in Rocket.toml),
+--              written when a route command chosen by the user's variant is
+--              issued. The variant is the one of the first exposure; later
+--              exposures only bump the counter. Feeds GET /api/admin/experiments.
+
+-- Step 1: Exposures
+CREATE TABLE IF NOT EXISTS experiment_exposures (
+    experiment VARCHAR(100) NOT NULL,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    variant VARCHAR(100) NOT NULL,
+    exposures INTEGER NOT NULL DEFAULT 1,
+    first_exposed_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_exposed_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (experiment, user_id)
+);
+
+-- Step 2: Per-variant counts
+CREATE INDEX IF NOT EXISTS idx_experiment_exposures_variant
+    ON experiment_exposures (experiment, variant);
+
+-- Verification query:
+-- SELECT experiment, variant, COUNT(*) AS users, SUM(exposures) FROM experiment_exposures GROUP BY 1, 2;
+
+-- Rollback SQL (if needed):
+-- DROP TABLE IF EXISTS experiment_exposures;
+-- DELETE FROM schema_migrations WHERE version = 20;
@@ -0,0 +1,33 @@
+-- Migration: Audit log
+-- Date: 2026-10-16
+-- Description: Admin and security-relevant actions (role changes, impersonation,
+--              payments, config changes) are appended here. Previously created at
+--              startup; existing databases already have the table and are left
+--              unchanged. Rows past the archive horizon are moved to
+--              audit_logs_archive (009).
+
+-- Step 1: Audit log table
+CREATE TABLE IF NOT EXISTS audit_logs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    actor_id UUID,
+    action VARCHAR(100) NOT NULL,
+    target_type VARCHAR(50) NOT NULL,
+    target_id VARCHAR(255),
+    ip_address INET,
+    details JSONB NOT NULL DEFAULT '{}'::jsonb,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Step 2: Admin queries filter by target
+CREATE INDEX IF NOT EXISTS idx_audit_logs_target
+    ON audit_logs (target_type, target_id, created_at);
+
+-- Step 3: Index for selecting rows past the archive horizon
+CREATE INDEX IF NOT EXISTS idx_audit_logs_created_at ON audit_logs (created_at);
+
+-- Verification query:
+-- SELECT action, COUNT(*) FROM audit_logs GROUP BY action ORDER BY 2 DESC;
+
+-- Rollback SQL (if needed):
+-- DROP TABLE IF EXISTS audit_logs;
+-- DELETE FROM schema_migrations WHERE version = 21;
@@ -0,0 +1,31 @@
+-- Migration: Remote configuration entries
+-- Date: 2026-10-16
+-- Description: Per-platform config values delivered to clients. Every write takes
+--              a new value from remote_config_version_seq; deletions are soft so
+--              clients see the removal. Previously created at startup; existing
+--              databases are left unchanged.
+
+-- Step 1: Version sequence shared by all entries
+CREATE SEQUENCE IF NOT EXISTS remote_config_version_seq;
+
+-- Step 2: Config entries, one per key and platform ('*' for all platforms)
+CREATE TABLE IF NOT EXISTS remote_configs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    config_key VARCHAR(100) NOT NULL,
+    platform VARCHAR(20) NOT NULL DEFAULT '*',
+    value JSONB NOT NULL,
+    version BIGINT NOT NULL DEFAULT nextval('remote_config_version_seq'),
+    is_deleted BOOLEAN NOT NULL DEFAULT false,
+    description TEXT,
+    updated_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (config_key, platform)
+);
+
+-- Verification query:
+-- SELECT config_key, platform, version FROM remote_configs WHERE NOT is_deleted ORDER BY version DESC;
+
+-- Rollback SQL (if needed):
+-- DROP TABLE IF EXISTS remote_configs;
+-- DROP SEQUENCE IF EXISTS remote_config_version_seq;
+-- DELETE FROM schema_migrations WHERE version = 22;
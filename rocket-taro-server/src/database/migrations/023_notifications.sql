@@ -0,0 +1,27 @@
+-- Migration: In-app notifications
+-- Date: 2026-10-16
+-- Description: Per-user inbox shown in the notification center. Previously created
+--              at startup; existing databases are left unchanged.
+
+-- Step 1: Notifications
+CREATE TABLE IF NOT EXISTS notifications (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    category VARCHAR(50) NOT NULL,
+    title VARCHAR(200) NOT NULL,
+    content TEXT NOT NULL,
+    data JSONB NOT NULL DEFAULT '{}'::jsonb,
+    is_read BOOLEAN NOT NULL DEFAULT false,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Step 2: A user's inbox, newest first (keyset pagination)
+CREATE INDEX IF NOT EXISTS idx_notifications_user
+    ON notifications (user_id, created_at DESC, id DESC);
+
+-- Verification query:
+-- SELECT category, COUNT(*) FILTER (WHERE NOT is_read) AS unread FROM notifications GROUP BY category;
+
+-- Rollback SQL (if needed):
+-- DROP TABLE IF EXISTS notifications;
+-- DELETE FROM schema_migrations WHERE version = 23;
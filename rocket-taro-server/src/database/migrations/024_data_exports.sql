@@ -0,0 +1,32 @@
+-- Migration: Personal data exports
+-- Date: 2026-10-16
+-- Description: Export requests processed by the background export job. file_path
+--              holds the storage key of the generated file (see 018). Previously
+--              created at startup; existing databases are left unchanged.
+
+-- Step 1: Export requests
+CREATE TABLE IF NOT EXISTS data_exports (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    file_path VARCHAR(500),
+    file_size BIGINT,
+    error_message TEXT,
+    started_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    completed_at TIMESTAMPTZ,
+    expires_at TIMESTAMPTZ
+);
+
+-- Step 2: A user's exports, newest first
+CREATE INDEX IF NOT EXISTS idx_data_exports_user ON data_exports (user_id, created_at DESC);
+
+-- Step 3: The export job picks up pending requests
+CREATE INDEX IF NOT EXISTS idx_data_exports_status ON data_exports (status);
+
+-- Verification query:
+-- SELECT status, COUNT(*) FROM data_exports GROUP BY status;
+
+-- Rollback SQL (if needed):
+-- DROP TABLE IF EXISTS data_exports;
+-- DELETE FROM schema_migrations WHERE version = 24;
@@ -0,0 +1,43 @@
+-- Migration: WeChat Pay payments and callback log
+-- Date: 2026-10-16
+-- Description: Payments follow created -> prepaid -> paid -> refunded (created /
+--              prepaid may become closed or failed). Every verified callback is
+--              logged for reconciliation. Previously created at startup; existing
+--              databases are left unchanged.
+
+-- Step 1: Payments
+CREATE TABLE IF NOT EXISTS payments (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id),
+    out_trade_no VARCHAR(32) NOT NULL UNIQUE,
+    description VARCHAR(127) NOT NULL,
+    amount BIGINT NOT NULL CHECK (amount > 0),
+    currency VARCHAR(8) NOT NULL DEFAULT 'CNY',
+    status VARCHAR(20) NOT NULL DEFAULT 'created',
+    prepay_id VARCHAR(64),
+    transaction_id VARCHAR(32),
+    paid_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Step 2: A user's payments, newest first
+CREATE INDEX IF NOT EXISTS idx_payments_user ON payments (user_id, created_at DESC);
+
+-- Step 3: Callback log, one row per WeChat notification id
+CREATE TABLE IF NOT EXISTS payment_notifications (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    notification_id VARCHAR(64) NOT NULL UNIQUE,
+    out_trade_no VARCHAR(32) NOT NULL,
+    event_type VARCHAR(64) NOT NULL,
+    payload JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Verification query:
+-- SELECT status, COUNT(*), SUM(amount) FROM payments GROUP BY status;
+
+-- Rollback SQL (if needed):
+-- DROP TABLE IF EXISTS payment_notifications;
+-- DROP TABLE IF EXISTS payments;
+-- DELETE FROM schema_migrations WHERE version = 25;
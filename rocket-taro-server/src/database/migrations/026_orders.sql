@@ -0,0 +1,39 @@
+-- Migration: Orders
+-- Date: 2026-10-16
+-- Description: Orders are priced server-side and paid through a payment
+--              (out_trade_no); unpaid orders are expired by the background job.
+--              Previously created at startup; existing databases are left unchanged.
+
+-- Step 1: Orders
+CREATE TABLE IF NOT EXISTS orders (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    order_no VARCHAR(32) NOT NULL UNIQUE,
+    user_id UUID NOT NULL REFERENCES users(id),
+    title VARCHAR(100) NOT NULL,
+    items JSONB NOT NULL DEFAULT '[]',
+    total_amount BIGINT NOT NULL CHECK (total_amount > 0),
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    out_trade_no VARCHAR(32),
+    remark TEXT,
+    expires_at TIMESTAMPTZ NOT NULL,
+    paid_at TIMESTAMPTZ,
+    cancelled_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Step 2: A user's orders, newest first (keyset pagination)
+CREATE INDEX IF NOT EXISTS idx_orders_user ON orders (user_id, created_at DESC, id DESC);
+
+-- Step 3: The expiry job only scans pending orders
+CREATE INDEX IF NOT EXISTS idx_orders_pending_expiry ON orders (expires_at) WHERE status = 'pending';
+
+-- Step 4: Payment callbacks look up the order by out_trade_no
+CREATE INDEX IF NOT EXISTS idx_orders_out_trade_no ON orders (out_trade_no) WHERE out_trade_no IS NOT NULL;
+
+-- Verification query:
+-- SELECT status, COUNT(*) FROM orders GROUP BY status;
+
+-- Rollback SQL (if needed):
+-- DROP TABLE IF EXISTS orders;
+-- DELETE FROM schema_migrations WHERE version = 26;
@@ -0,0 +1,31 @@
+-- Migration: Side-effect outbox
+-- Date: 2026-10-16
+-- Description: Non-critical work triggered by a request (login log, cache warm-up) is
+--              enqueued in the same transaction as the change and executed by the
+--              outbox worker with retries. Previously created at startup; existing
+--              databases are left unchanged.
+
+-- Step 1: Outbox
+CREATE TABLE IF NOT EXISTS side_effect_outbox (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    effect_type VARCHAR(64) NOT NULL,
+    payload JSONB NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    locked_at TIMESTAMPTZ,
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    completed_at TIMESTAMPTZ
+);
+
+-- Step 2: The worker only scans pending effects
+CREATE INDEX IF NOT EXISTS idx_side_effect_outbox_due
+    ON side_effect_outbox (next_attempt_at) WHERE status = 'pending';
+
+-- Verification query:
+-- SELECT effect_type, status, COUNT(*) FROM side_effect_outbox GROUP BY 1, 2;
+
+-- Rollback SQL (if needed):
+-- DROP TABLE IF EXISTS side_effect_outbox;
+-- DELETE FROM schema_migrations WHERE version = 28;
@@ -0,0 +1,47 @@
+-- Migration: Route command executions
+-- Date: 2026-10-16
+-- Description: Every issued route command gets an execution_id; the client acks
+--              it with the outcome so success rates and latency can be reported
+--              per flow. Previously created at startup; existing databases are
+--              left unchanged. The decision trace from 019 is created here
+--              since it references this table.
+
+-- Step 1: Executions
+CREATE TABLE IF NOT EXISTS route_command_executions (
+    execution_id UUID PRIMARY KEY,
+    flow VARCHAR(50) NOT NULL,
+    command_type VARCHAR(30) NOT NULL,
+    platform VARCHAR(20) NOT NULL,
+    user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    issued_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    acked_at TIMESTAMPTZ,
+    status VARCHAR(10),
+    duration_ms BIGINT,
+    error TEXT
+);
+
+-- Step 2: Reports and retention cleanup scan by issue time
+CREATE INDEX IF NOT EXISTS idx_route_command_executions_issued
+    ON route_command_executions (issued_at, flow);
+
+-- Step 3: Decision trace (019), in the order the rules fired
+CREATE TABLE IF NOT EXISTS route_command_decisions (
+    execution_id UUID NOT NULL REFERENCES route_command_executions(execution_id) ON DELETE CASCADE,
+    seq INTEGER NOT NULL,
+    rule VARCHAR(100) NOT NULL,
+    inputs JSONB NOT NULL DEFAULT '{}'::jsonb,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (execution_id, seq)
+);
+
+-- Step 4: Decision trace retention cleanup
+CREATE INDEX IF NOT EXISTS idx_route_command_decisions_created
+    ON route_command_decisions (created_at);
+
+-- Verification query:
+-- SELECT flow, status, COUNT(*) FROM route_command_executions GROUP BY 1, 2 ORDER BY 1, 2;
+
+-- Rollback SQL (if needed):
+-- DROP TABLE IF EXISTS route_command_decisions;
+-- DROP TABLE IF EXISTS route_command_executions;
+-- DELETE FROM schema_migrations WHERE version = 29;
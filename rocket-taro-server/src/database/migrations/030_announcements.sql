@@ -0,0 +1,39 @@
+-- Migration: Announcements and per-user dismissals
+-- Date: 2026-10-16
+-- Description: Admin-authored announcements shown to the users matching their
+--              audience within the display window; dismissible announcements are
+--              hidden per user once dismissed. Previously created at startup;
+--              existing databases are left unchanged.
+
+-- Step 1: Announcements
+CREATE TABLE IF NOT EXISTS announcements (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    title VARCHAR(100) NOT NULL,
+    content TEXT NOT NULL,
+    level VARCHAR(10) NOT NULL DEFAULT 'info',
+    dismissible BOOLEAN NOT NULL DEFAULT true,
+    audience JSONB NOT NULL DEFAULT '{}',
+    starts_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    ends_at TIMESTAMPTZ,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_announcements_window ON announcements (starts_at, ends_at);
+
+-- Step 2: Dismissals
+CREATE TABLE IF NOT EXISTS announcement_dismissals (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    announcement_id UUID NOT NULL REFERENCES announcements(id) ON DELETE CASCADE,
+    dismissed_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (user_id, announcement_id)
+);
+
+-- Verification query:
+-- SELECT a.title, COUNT(d.user_id) AS dismissals FROM announcements a LEFT JOIN announcement_dismissals d ON d.announcement_id = a.id GROUP BY a.id;
+
+-- Rollback SQL (if needed):
+-- DROP TABLE IF EXISTS announcement_dismissals;
+-- DROP TABLE IF EXISTS announcements;
+-- DELETE FROM schema_migrations WHERE version = 30;
@@ -0,0 +1,18 @@
+-- Migration: User settings
+-- Date: 2026-10-16
+-- Description: One JSON document of client preferences per user. Previously
+--              created at startup; existing databases are left unchanged.
+
+-- Step 1: Settings
+CREATE TABLE IF NOT EXISTS user_settings (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    settings JSONB NOT NULL DEFAULT '{}',
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Verification query:
+-- SELECT COUNT(*) FROM user_settings;
+
+-- Rollback SQL (if needed):
+-- DROP TABLE IF EXISTS user_settings;
+-- DELETE FROM schema_migrations WHERE version = 31;
@@ -0,0 +1,26 @@
+-- Migration: Pending email changes
+-- Date: 2026-10-16
+-- Description: An email change is applied once the new address (and the current
+--              one, if the account has one) is confirmed; only token hashes are
+--              stored. One pending request per user. Previously created at startup; existing
+--              databases are left unchanged.
+
+-- Step 1: Change requests
+CREATE TABLE IF NOT EXISTS email_change_requests (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    pending_email VARCHAR(255) NOT NULL,
+    session_id UUID,
+    current_token_hash VARCHAR(64) UNIQUE,
+    current_confirmed_at TIMESTAMPTZ,
+    new_token_hash VARCHAR(64) NOT NULL UNIQUE,
+    new_confirmed_at TIMESTAMPTZ,
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Verification query:
+-- SELECT COUNT(*) FILTER (WHERE expires_at > CURRENT_TIMESTAMP) AS pending FROM email_change_requests;
+
+-- Rollback SQL (if needed):
+-- DROP TABLE IF EXISTS email_change_requests;
+-- DELETE FROM schema_migrations WHERE version = 32;
@@ -0,0 +1,38 @@
+-- Migration: Uploaded files and user data attachments
+-- Date: 2026-10-16
+-- Description: Each uploaded file can be attached to at most one user data entry;
+--              files left without an attachment (including after the entry is
+--              deleted) are removed by the cleanup job. Previously created at
+--              startup; existing databases are left unchanged.
+
+-- Step 1: Uploads
+CREATE TABLE IF NOT EXISTS uploads (
+    id UUID PRIMARY KEY,
+    user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    file_name VARCHAR(255) NOT NULL,
+    content_type VARCHAR(100) NOT NULL,
+    file_size BIGINT NOT NULL,
+    file_path VARCHAR(500) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_uploads_created_at ON uploads (created_at);
+
+-- Step 2: Attachments
+CREATE TABLE IF NOT EXISTS user_data_attachments (
+    upload_id UUID PRIMARY KEY REFERENCES uploads(id) ON DELETE CASCADE,
+    user_data_id UUID NOT NULL REFERENCES user_data(id) ON DELETE CASCADE,
+    position SMALLINT NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_data_attachments_data
+    ON user_data_attachments (user_data_id, position);
+
+-- Verification query:
+-- SELECT COUNT(*) FROM uploads u WHERE NOT EXISTS (SELECT 1 FROM user_data_attachments a WHERE a.upload_id = u.id);
+
+-- Rollback SQL (if needed):
+-- DROP TABLE IF EXISTS user_data_attachments;
+-- DROP TABLE IF EXISTS uploads;
+-- DELETE FROM schema_migrations WHERE version = 33;
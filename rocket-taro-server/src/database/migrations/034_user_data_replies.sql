@@ -0,0 +1,23 @@
+-- Migration: Admin replies to user data
+-- Date: 2026-10-16
+-- Description: Replies posted by admins on a submitted user data entry. Previously
+--              created at startup; existing databases are left unchanged.
+
+-- Step 1: Replies
+CREATE TABLE IF NOT EXISTS user_data_replies (
+    id UUID PRIMARY KEY,
+    user_data_id UUID NOT NULL REFERENCES user_data(id) ON DELETE CASCADE,
+    admin_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    content TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_data_replies_data
+    ON user_data_replies (user_data_id, created_at);
+
+-- Verification query:
+-- SELECT user_data_id, COUNT(*) FROM user_data_replies GROUP BY user_data_id ORDER BY 2 DESC LIMIT 20;
+
+-- Rollback SQL (if needed):
+-- DROP TABLE IF EXISTS user_data_replies;
+-- DELETE FROM schema_migrations WHERE version = 34;
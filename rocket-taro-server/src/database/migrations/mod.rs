@@ -0,0 +1,277 @@
+use tokio_postgres::{Client, Error};
+use tracing::info;
+
+/// 数据库迁移脚本，按版本号顺序执行
+#[derive(Debug)]
+pub struct Migration {
+    pub version: i32,
+    pub name: &'static str,
+    pub sql: &'static str,
+}
+
+/// 所有迁移脚本（新增脚本时追加到末尾，版本号递增）
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "add_is_guest_column",
+        sql: include_str!("001_add_is_guest_column.sql"),
+    },
+    Migration {
+        version: 2,
+        name: "user_data_search",
+        sql: include_str!("002_user_data_search.sql"),
+    },
+    Migration {
+        version: 3,
+        name: "hash_session_tokens",
+        sql: include_str!("003_hash_session_tokens.sql"),
+    },
+    Migration {
+        version: 4,
+        name: "user_data_status",
+        sql: include_str!("004_user_data_status.sql"),
+    },
+    Migration {
+        version: 5,
+        name: "row_history",
+        sql: include_str!("005_row_history.sql"),
+    },
+    Migration {
+        version: 6,
+        name: "version_columns",
+        sql: include_str!("006_version_columns.sql"),
+    },
+    Migration {
+        version: 7,
+        name: "login_logs_schema",
+        sql: include_str!("007_login_logs_schema.sql"),
+    },
+    Migration {
+        version: 8,
+        name: "session_last_accessed",
+        sql: include_str!("008_session_last_accessed.sql"),
+    },
+    Migration {
+        version: 9,
+        name: "log_archives",
+        sql: include_str!("009_log_archives.sql"),
+    },
+    Migration {
+        version: 10,
+        name: "session_impersonation",
+        sql: include_str!("010_session_impersonation.sql"),
+    },
+    Migration {
+        version: 11,
+        name: "hot_path_indexes",
+        sql: include_str!("011_hot_path_indexes.sql"),
+    },
+    Migration {
+        version: 12,
+        name: "api_quota_overrides",
+        sql: include_str!("012_api_quota_overrides.sql"),
+    },
+    Migration {
+        version: 13,
+        name: "registration_reviews",
+        sql: include_str!("013_registration_reviews.sql"),
+    },
+    Migration {
+        version: 14,
+        name: "policy_consents",
+        sql: include_str!("014_policy_consents.sql"),
+    },
+    Migration {
+        version: 15,
+        name: "identity_verifications",
+        sql: include_str!("015_identity_verifications.sql"),
+    },
+    Migration {
+        version: 16,
+        name: "moderation_checks",
+        sql: include_str!("016_moderation_checks.sql"),
+    },
+    Migration {
+        version: 17,
+        name: "avatars",
+        sql: include_str!("017_avatars.sql"),
+    },
+    Migration {
+        version: 18,
+        name: "storage_objects",
+        sql: include_str!("018_storage_objects.sql"),
+    },
+    Migration {
+        version: 19,
+        name: "route_command_decisions",
+        sql: include_str!("019_route_command_decisions.sql"),
+    },
+    Migration {
+        version: 20,
+        name: "experiment_exposures",
+        sql: include_str!("020_experiment_exposures.sql"),
+    },
+    Migration {
+        version: 21,
+        name: "audit_logs",
+        sql: include_str!("021_audit_logs.sql"),
+    },
+    Migration {
+        version: 22,
+        name: "remote_configs",
+        sql: include_str!("022_remote_configs.sql"),
+    },
+    Migration {
+        version: 23,
+        name: "notifications",
+        sql: include_str!("023_notifications.sql"),
+    },
+    Migration {
+        version: 24,
+        name: "data_exports",
+        sql: include_str!("024_data_exports.sql"),
+    },
+    Migration {
+        version: 25,
+        name: "payments",
+        sql: include_str!("025_payments.sql"),
+    },
+    Migration {
+        version: 26,
+        name: "orders",
+        sql: include_str!("026_orders.sql"),
+    },
+    Migration {
+        version: 27,
+        name: "webhooks",
+        sql: include_str!("027_webhooks.sql"),
+    },
+    Migration {
+        version: 28,
+        name: "side_effect_outbox",
+        sql: include_str!("028_side_effect_outbox.sql"),
+    },
+    Migration {
+        version: 29,
+        name: "route_command_executions",
+        sql: include_str!("029_route_command_executions.sql"),
+    },
+    Migration {
+        version: 30,
+        name: "announcements",
+        sql: include_str!("030_announcements.sql"),
+    },
+    Migration {
+        version: 31,
+        name: "user_settings",
+        sql: include_str!("031_user_settings.sql"),
+    },
+    Migration {
+        version: 32,
+        name: "email_change_requests",
+        sql: include_str!("032_email_change_requests.sql"),
+    },
+    Migration {
+        version: 33,
+        name: "uploads",
+        sql: include_str!("033_uploads.sql"),
+    },
+    Migration {
+        version: 34,
+        name: "user_data_replies",
+        sql: include_str!("034_user_data_replies.sql"),
+    },
+];
+
+// 多实例同时启动时用于串行化迁移的 advisory lock 键
+const MIGRATION_LOCK_KEY: i64 = 0x5441_524f_4d49_4752;
+
+// 执行尚未应用的迁移，返回本次执行的版本号
+pub async fn run_migrations(client: &mut Client) -> Result<Vec<i32>, Error> {
+    client.execute(
+        "CREATE TABLE IF NOT EXISTS schema_migrations (
+            version INTEGER PRIMARY KEY,
+            name VARCHAR(100) NOT NULL,
+            applied_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+        )",
+        &[],
+    ).await?;
+
+    let mut applied = Vec::new();
+    for migration in MIGRATIONS {
+        let transaction = client.transaction().await?;
+        transaction.execute("SELECT pg_advisory_xact_lock($1)", &[&MIGRATION_LOCK_KEY]).await?;
+
+        let already_applied = transaction.query_opt(
+            "SELECT 1 FROM schema_migrations WHERE version = $1",
+            &[&migration.version],
+        ).await?.is_some();
+        if already_applied {
+            continue;
+        }
+
+        info!("Applying database migration {:03}_{}", migration.version, migration.name);
+        transaction.batch_execute(migration.sql).await?;
+        transaction.execute(
+            "INSERT INTO schema_migrations (version, name) VALUES ($1, $2)",
+            &[&migration.version, &migration.name],
+        ).await?;
+        transaction.commit().await?;
+
+        applied.push(migration.version);
+    }
+
+    Ok(applied)
+}
+
+/// 数据库与当前版本迁移脚本的差异
+#[derive(Debug)]
+pub struct MigrationStatus {
+    /// 尚未执行的迁移
+    pub pending: Vec<&'static Migration>,
+    /// 数据库中已执行、但当前版本没有的迁移（数据库由更新的版本迁移过）
+    pub unknown: Vec<i32>,
+}
+
+// 只读取迁移记录，不执行迁移；迁移记录表不存在时所有迁移都未执行
+pub async fn migration_status(client: &Client) -> Result<MigrationStatus, Error> {
+    let table_exists: bool = client.query_one(
+        "SELECT to_regclass('schema_migrations') IS NOT NULL",
+        &[],
+    ).await?.get(0);
+    let applied: Vec<i32> = if table_exists {
+        client.query("SELECT version FROM schema_migrations ORDER BY version", &[]).await?
+            .iter()
+            .map(|row| row.get(0))
+            .collect()
+    } else {
+        Vec::new()
+    };
+    Ok(compare(&applied))
+}
+
+fn compare(applied: &[i32]) -> MigrationStatus {
+    MigrationStatus {
+        pending: MIGRATIONS.iter().filter(|migration| !applied.contains(&migration.version)).collect(),
+        unknown: applied.iter().copied().filter(|version| MIGRATIONS.iter().all(|m| m.version != *version)).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versions_are_strictly_increasing() {
+        for pair in MIGRATIONS.windows(2) {
+            assert!(pair[0].version < pair[1].version, "migration {} is out of order", pair[1].name);
+        }
+    }
+    #[test]
+    fn test_compare_applied_versions() {
+        let status = compare(&[1, 2, 3, 99]);
+        assert_eq!(status.pending.len(), MIGRATIONS.len() - 3);
+        assert_eq!(status.pending[0].version, 4);
+        assert_eq!(status.unknown, vec![99]);
+    }
+}
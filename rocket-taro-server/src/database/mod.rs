@@ -1,26 +1,66 @@
-use tokio_postgres::{Client, NoTls, Error};
+use anyhow::Context as _;
+use tokio_postgres::{Client, NoTls, Error, Socket, types::ToSql};
+use tokio_postgres::config::SslMode as PgSslMode;
+use tokio_postgres::error::SqlState;
+use tokio_postgres::tls::MakeTlsConnect;
+use rocket::futures::{future, Stream, TryStreamExt};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::Mutex;
-use tracing::error;
+use tokio::task::JoinHandle;
+use tracing::{info, warn, error};
+
+use crate::config::{DatabaseConfig, SslMode};
+use crate::metrics::MetricsRegistry;
+use crate::utils::pagination::PageRequest;
+use row::{FromRow, impl_from_row};
+use uuid::Uuid;
 
 pub mod auth;
 pub mod wx_auth;
+pub mod remote_config;
+pub mod account;
+pub mod audit;
+pub mod notification;
+pub mod data_export;
+pub mod admin_stats;
+pub mod migrations;
+pub mod payment;
+pub mod order;
+pub mod webhook;
+pub mod side_effect;
+pub mod tls;
+pub mod health;
+pub mod query_monitor;
+pub mod profile;
+pub mod route_execution;
+pub mod announcement;
+pub mod settings;
+pub mod email_change;
+pub mod upload;
+pub mod history;
+pub mod version;
+pub mod row;
+pub mod login_log;
+pub mod log_archive;
+pub mod user_data_reply;
+pub mod api_quota;
+pub mod registration_review;
+pub mod consent;
+pub mod identity;
+pub mod moderation;
+pub mod avatar;
+pub mod storage;
+pub mod experiment;
 
-pub type DbPool = Arc<Mutex<Client>>;
+pub use health::DbHealth;
+pub use query_monitor::{DbClient, QueryMonitor};
 
-pub async fn create_connection() -> Result<DbPool, Error> {
-    // 从环境变量或默认配置获取数据库连接字符串
-    let database_url = std::env::var("DATABASE_URL")
-        .unwrap_or_else(|_| "host=192.168.5.222 port=5432 user=user_ck password=ck320621 dbname=postgres".to_string());
-    
-    let (client, connection) = tokio_postgres::connect(&database_url, NoTls).await?;
+pub type DbPool = Arc<Mutex<DbClient>>;
 
-    // 在后台运行连接
-    tokio::spawn(async move {
-        if let Err(e) = connection.await {
-            error!("Database connection error: {}", e);
-        }
-    });
+pub async fn create_connection(config: &DatabaseConfig, health: DbHealth, metrics: MetricsRegistry) -> anyhow::Result<DbPool> {
+    let (mut client, connection) = open_client(config).await?;
+    info!("Connected to database (sslmode={})", config.sslmode.as_str());
 
     // 创建表（如果不存在）
     client.execute(
@@ -35,10 +75,138 @@ pub async fn create_connection() -> Result<DbPool, Error> {
         &[],
     ).await?;
 
+    client.execute(
+        "CREATE INDEX IF NOT EXISTS idx_user_data_created_at ON user_data(created_at DESC, id DESC)",
+        &[],
+    ).await?;
+
     // 创建认证相关的表
     init_auth_tables(&client).await?;
 
-    Ok(Arc::new(Mutex::new(client)))
+    // 执行增量迁移脚本
+    let applied = migrations::run_migrations(&mut client).await?;
+    if !applied.is_empty() {
+        info!("Applied {} database migration(s): {:?}", applied.len(), applied);
+    }
+
+    // 建表和迁移不受 statement_timeout 限制，完成后再设置
+    apply_statement_timeout(&client, config).await?;
+
+    let monitor = QueryMonitor::new(Duration::from_millis(config.slow_query_threshold_ms), metrics);
+    let pool = Arc::new(Mutex::new(DbClient::new(client, monitor)));
+    tokio::spawn(supervise(pool.clone(), connection, config.clone(), health));
+    Ok(pool)
+}
+
+/// 单独建立一个连接检查迁移是否已全部执行，不建表、不执行迁移，用于启动自检
+pub async fn check_migrations(config: &DatabaseConfig) -> anyhow::Result<migrations::MigrationStatus> {
+    let (client, connection) = open_client(config).await?;
+    let status = migrations::migration_status(&client).await;
+    drop(client);
+    connection.abort();
+    Ok(status?)
+}
+
+// 按配置的 sslmode 建立连接，连接任务在后台运行，任务结束即表示连接已断开
+async fn open_client(config: &DatabaseConfig) -> anyhow::Result<(Client, JoinHandle<Result<(), Error>>)> {
+    let mut pg_config: tokio_postgres::Config = config.database_url.parse()
+        .context("Invalid database_url")?;
+
+    match config.sslmode {
+        SslMode::Disable => {
+            pg_config.ssl_mode(PgSslMode::Disable);
+            connect(&pg_config, NoTls).await.map_err(|e| {
+                // 服务器拒绝明文连接时给出明确的配置提示
+                if requires_tls(&e) {
+                    anyhow::anyhow!(
+                        "Database server requires TLS, set sslmode = \"require\" (or verify-ca / verify-full) in [databases]: {}",
+                        e
+                    )
+                } else {
+                    anyhow::Error::new(e).context("Failed to connect to database")
+                }
+            })
+        }
+        sslmode => {
+            // verify-ca / verify-full 的证书验证由 rustls 完成，对 tokio-postgres 而言等同于 require
+            pg_config.ssl_mode(if sslmode == SslMode::Prefer { PgSslMode::Prefer } else { PgSslMode::Require });
+            let tls_config = tls::client_config(sslmode, config.ca_cert_path.as_deref())?;
+            connect(&pg_config, tls::MakeRustlsConnect::new(tls_config)).await
+                .with_context(|| format!("Failed to connect to database with sslmode={}", sslmode.as_str()))
+        }
+    }
+}
+
+// 监视连接任务：连接断开后打开熔断器，按指数退避重连，成功后替换连接池中的客户端并关闭熔断器
+async fn supervise(pool: DbPool, mut connection: JoinHandle<Result<(), Error>>, config: DatabaseConfig, health: DbHealth) {
+    let min_backoff = Duration::from_millis(config.reconnect_min_backoff_ms);
+    let max_backoff = Duration::from_secs(config.reconnect_max_backoff_secs).max(min_backoff);
+
+    loop {
+        let error = match connection.await {
+            Ok(Ok(())) => None,
+            Ok(Err(e)) => Some(e.to_string()),
+            Err(e) => Some(e.to_string()),
+        };
+        warn!("Database connection lost, reconnecting");
+        health.record_disconnect(error);
+
+        let mut backoff = min_backoff;
+        connection = loop {
+            health.record_attempt();
+            let reconnected = match open_client(&config).await {
+                Ok((client, handle)) => apply_statement_timeout(&client, &config).await
+                    .map(|_| (client, handle))
+                    .map_err(anyhow::Error::new),
+                Err(e) => Err(e),
+            };
+            match reconnected {
+                Ok((client, handle)) => {
+                    pool.lock().await.replace(client);
+                    health.record_success();
+                    info!("Database connection re-established");
+                    break handle;
+                }
+                Err(e) => {
+                    warn!("Database reconnect failed, retrying in {:?}: {:#}", backoff, e);
+                    health.record_failure(format!("{:#}", e));
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(max_backoff);
+                }
+            }
+        };
+    }
+}
+
+// 设置会话级 statement_timeout，超时的查询由服务器取消，避免请求无限等待
+async fn apply_statement_timeout(client: &Client, config: &DatabaseConfig) -> Result<(), Error> {
+    if config.statement_timeout_ms > 0 {
+        client.batch_execute(&format!("SET statement_timeout = {}", config.statement_timeout_ms)).await?;
+    }
+    Ok(())
+}
+
+// 建立连接并在后台运行连接任务，返回连接任务句柄
+async fn connect<T>(pg_config: &tokio_postgres::Config, tls: T) -> Result<(Client, JoinHandle<Result<(), Error>>), Error>
+where
+    T: MakeTlsConnect<Socket>,
+    T::Stream: Send + 'static,
+{
+    let (client, connection) = pg_config.connect(tls).await?;
+
+    let handle = tokio::spawn(async move {
+        connection.await.inspect_err(|e| error!("Database connection error: {}", e))
+    });
+
+    Ok((client, handle))
+}
+
+// pg_hba.conf 只允许 hostssl 时，明文连接会被拒绝（PG14+ 提示 "no encryption"，更早版本为 "SSL off"）
+fn requires_tls(e: &Error) -> bool {
+    e.as_db_error().is_some_and(|db| {
+        *db.code() == SqlState::INVALID_AUTHORIZATION_SPECIFICATION
+            && (db.message().contains("no encryption") || db.message().contains("SSL off"))
+    })
 }
 
 async fn init_auth_tables(client: &Client) -> Result<(), Error> {
@@ -83,6 +251,17 @@ async fn init_auth_tables(client: &Client) -> Result<(), Error> {
         &[],
     ).await;
 
+    // 添加账户注销相关字段（如果不存在）
+    let _ = client.execute(
+        "ALTER TABLE users ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ",
+        &[],
+    ).await;
+
+    let _ = client.execute(
+        "ALTER TABLE users ADD COLUMN IF NOT EXISTS anonymized_at TIMESTAMPTZ",
+        &[],
+    ).await;
+
     // 为wx_openid添加唯一索引（如果不存在）
     let _ = client.execute(
         "CREATE UNIQUE INDEX IF NOT EXISTS idx_users_wx_openid ON users(wx_openid) WHERE wx_openid IS NOT NULL",
@@ -94,7 +273,7 @@ async fn init_auth_tables(client: &Client) -> Result<(), Error> {
         "CREATE TABLE IF NOT EXISTS user_sessions (
             id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
             user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
-            session_token VARCHAR(255) NOT NULL UNIQUE,
+            token_hash VARCHAR(64) NOT NULL UNIQUE,
             user_agent TEXT,
             ip_address INET,
             expires_at TIMESTAMPTZ NOT NULL,
@@ -110,86 +289,41 @@ async fn init_auth_tables(client: &Client) -> Result<(), Error> {
             id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
             user_id UUID REFERENCES users(id) ON DELETE SET NULL,
             username VARCHAR(50) NOT NULL,
-            is_success BOOLEAN NOT NULL,
+            success BOOLEAN NOT NULL,
             ip_address INET,
             user_agent TEXT,
-            error_message TEXT,
+            failure_reason TEXT,
             created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
         )",
         &[],
     ).await?;
 
-    // 插入默认用户（如果不存在）
-    let existing_users: i64 = client.query_one(
-        "SELECT COUNT(*) FROM users WHERE username IN ('admin', 'test')",
-        &[],
-    ).await?.get(0);
-
-    if existing_users == 0 {
-        // 生成新的密码哈希
-        use bcrypt::{hash, DEFAULT_COST};
-        let admin_hash = hash("password", DEFAULT_COST).unwrap();
-        let test_hash = hash("password", DEFAULT_COST).unwrap();
-        
-        // 创建admin用户 (密码: admin123)
-        client.execute(
-            "INSERT INTO users (username, email, password_hash, full_name, is_admin, is_active) 
-             VALUES ($1, $2, $3, $4, $5, $6)",
-            &[
-                &"admin",
-                &"admin@rocket-taro.com", 
-                &admin_hash,
-                &"系统管理员",
-                &true,
-                &true,
-            ],
-        ).await?;
-
-        // 创建test用户 (密码: test123) 
-        client.execute(
-            "INSERT INTO users (username, email, password_hash, full_name, is_admin, is_active)
-             VALUES ($1, $2, $3, $4, $5, $6)",
-            &[
-                &"test",
-                &"test@rocket-taro.com",
-                &test_hash,
-                &"测试用户", 
-                &false,
-                &true,
-            ],
-        ).await?;
-        
-        // 默认用户创建完成
-    } else {
-        // 更新现有用户密码哈希
-        // 为password生成稳定的哈希
-        let password_hash = "$2b$10$92IXUNpkjO0rOQ5byMi.Ye4oKoEa3Ro9llC/.og/at2.uheWG/igi"; // "password"的bcrypt哈希
-        
-        client.execute(
-            "UPDATE users SET password_hash = $1 WHERE username = $2",
-            &[&password_hash, &"admin"],
-        ).await?;
-        
-        client.execute(
-            "UPDATE users SET password_hash = $1 WHERE username = $2", 
-            &[&password_hash, &"test"], // 两个用户都用相同密码"password"
-        ).await?;
-        
-        // 用户密码哈希更新完成
-    }
-
     Ok(())
 }
 
+impl_from_row!(crate::models::user_data::UserData {
+    id, name, email, phone, message, created_at,
+    status: &str => crate::models::user_data::UserDataStatus::parse,
+    version,
+    #[sql = upload::USER_DATA_ATTACHMENTS_SQL]
+    attachments: serde_json::Value => upload::parse_attachments,
+});
+
+// 用户数据查询列，附件列表为计算列
+fn user_data_columns() -> String {
+    crate::models::user_data::UserData::select_columns()
+}
+
 pub async fn insert_user_data(
     pool: &DbPool,
     data: &crate::models::user_data::UserData,
 ) -> Result<(), Error> {
-    let client = pool.lock().await;
+    let mut client = pool.lock().await;
+    let transaction = client.transaction().await?;
     
-    client.execute(
-        "INSERT INTO user_data (id, name, email, phone, message, created_at) 
-         VALUES ($1, $2, $3, $4, $5, $6)",
+    transaction.execute(
+        "INSERT INTO user_data (id, name, email, phone, message, created_at, status) 
+         VALUES ($1, $2, $3, $4, $5, $6, $7)",
         &[
             &data.id,
             &data.name,
@@ -197,8 +331,18 @@ pub async fn insert_user_data(
             &data.phone,
             &data.message,
             &data.created_at,
+            &data.status.as_str(),
         ],
     ).await?;
+    upload::attach_uploads(&transaction, data.id, &data.attachments).await?;
+
+    // 提交事件写入 Webhook 发件箱，与数据同时提交
+    webhook::enqueue_webhook_event(
+        &transaction,
+        crate::models::webhook::WebhookEventType::UserDataCreated,
+        &serde_json::to_value(data).unwrap_or_default(),
+    ).await?;
+    transaction.commit().await?;
 
     Ok(())
 }
@@ -209,21 +353,163 @@ pub async fn get_all_user_data(
     let client = pool.lock().await;
     
     let rows = client.query(
-        "SELECT id, name, email, phone, message, created_at FROM user_data ORDER BY created_at DESC",
+        &format!("SELECT {} FROM user_data ORDER BY created_at DESC", user_data_columns()),
         &[],
     ).await?;
 
-    let mut data = Vec::new();
-    for row in rows {
-        data.push(crate::models::user_data::UserData {
-            id: row.get(0),
-            name: row.get(1),
-            email: row.get(2),
-            phone: row.get(3),
-            message: row.get(4),
-            created_at: row.get(5),
-        });
-    }
+    rows.iter().map(crate::models::user_data::UserData::from_row).collect()
+}
+// 按游标分页读取用户数据（按 created_at, id 倒序）
+pub async fn list_user_data_page(
+    pool: &DbPool,
+    page: &PageRequest,
+) -> Result<Vec<crate::models::user_data::UserData>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!(
+            "SELECT {} FROM user_data
+             WHERE ($1::timestamptz IS NULL OR (created_at, id) < ($1, $2))
+             ORDER BY created_at DESC, id DESC
+             LIMIT $3",
+            user_data_columns(),
+        ),
+        &[&page.cursor_created_at(), &page.cursor_id(), &page.fetch_limit()],
+    ).await?;
+
+    rows.iter().map(crate::models::user_data::UserData::from_row).collect()
+}
+
+// 按处理状态筛选并游标分页读取用户数据（按 created_at, id 倒序），status 为 None 时不筛选
+pub async fn list_user_data_by_status_page(
+    pool: &DbPool,
+    status: Option<crate::models::user_data::UserDataStatus>,
+    page: &PageRequest,
+) -> Result<Vec<crate::models::user_data::UserData>, Error> {
+    let client = pool.lock().await;
+
+    let status = status.map(|status| status.as_str());
+    let rows = client.query(
+        &format!(
+            "SELECT {} FROM user_data
+             WHERE ($1::text IS NULL OR status = $1)
+               AND ($2::timestamptz IS NULL OR (created_at, id) < ($2, $3))
+             ORDER BY created_at DESC, id DESC
+             LIMIT $4",
+            user_data_columns(),
+        ),
+        &[&status, &page.cursor_created_at(), &page.cursor_id(), &page.fetch_limit()],
+    ).await?;
+
+    rows.iter().map(crate::models::user_data::UserData::from_row).collect()
+}
+
+// 按邮箱（不区分大小写）批量读取用户数据，最新的在前
+pub async fn list_user_data_by_emails(
+    pool: &DbPool,
+    emails: &[String],
+) -> Result<Vec<crate::models::user_data::UserData>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!(
+            "SELECT {} FROM user_data WHERE lower(email) = ANY($1) ORDER BY created_at DESC, id DESC",
+            user_data_columns(),
+        ),
+        &[&emails],
+    ).await?;
+
+    rows.iter().map(crate::models::user_data::UserData::from_row).collect()
+}
+
+// 用户数据总数
+pub async fn count_user_data(pool: &DbPool) -> Result<i64, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_one("SELECT COUNT(*) FROM user_data", &[]).await?;
+    Ok(row.get(0))
+}
 
-    Ok(data)
-}
\ No newline at end of file
+// 全文搜索用户数据（按相关度排序，并生成留言高亮片段）
+pub async fn search_user_data(
+    pool: &DbPool,
+    query: &str,
+    limit: i64,
+) -> Result<Vec<crate::models::user_data::UserDataSearchHit>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!(
+            "SELECT {},
+                    ts_rank(search_vector, q) AS rank,
+                    CASE WHEN message IS NULL THEN NULL
+                         ELSE ts_headline('simple', message, q, 'StartSel=<mark>, StopSel=</mark>, MaxFragments=2')
+                    END AS highlight
+             FROM user_data, websearch_to_tsquery('simple', $1) q
+             WHERE search_vector @@ q
+             ORDER BY rank DESC, created_at DESC
+             LIMIT $2",
+            user_data_columns(),
+        ),
+        &[&query, &limit],
+    ).await?;
+
+    rows.iter().map(|row| Ok(crate::models::user_data::UserDataSearchHit {
+        data: crate::models::user_data::UserData::from_row(row)?,
+        rank: row.try_get("rank")?,
+        highlight: row.try_get("highlight")?,
+    })).collect()
+}
+
+// 按ID查询用户数据
+pub async fn find_user_data(
+    pool: &DbPool,
+    id: Uuid,
+) -> Result<Option<crate::models::user_data::UserData>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        &format!("SELECT {} FROM user_data WHERE id = $1", user_data_columns()),
+        &[&id],
+    ).await?;
+    row.as_ref().map(crate::models::user_data::UserData::from_row).transpose()
+}
+
+// 查询某个邮箱提交的用户数据（不区分大小写，按提交时间倒序）
+pub async fn list_user_data_by_email(
+    pool: &DbPool,
+    email: &str,
+    limit: i64,
+) -> Result<Vec<crate::models::user_data::UserData>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!(
+            "SELECT {} FROM user_data WHERE lower(email) = lower($1) ORDER BY created_at DESC LIMIT $2",
+            user_data_columns(),
+        ),
+        &[&email, &limit],
+    ).await?;
+    rows.iter().map(crate::models::user_data::UserData::from_row).collect()
+}
+
+// 以流的方式逐行读取用户数据（可按邮箱过滤），避免一次性加载全部行
+// 流在发出查询后即释放连接锁，后续行由连接按顺序返回
+pub async fn stream_user_data(
+    pool: &DbPool,
+    email: Option<&str>,
+) -> Result<impl Stream<Item = Result<crate::models::user_data::UserData, Error>>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query_raw(
+        &format!(
+            "SELECT {} FROM user_data
+             WHERE ($1::text IS NULL OR email = $1)
+             ORDER BY created_at DESC",
+            user_data_columns(),
+        ),
+        [&email as &(dyn ToSql + Sync)],
+    ).await?;
+
+    Ok(rows.and_then(|row| future::ready(crate::models::user_data::UserData::from_row(&row))))
+}
@@ -0,0 +1,127 @@
+use tokio_postgres::Error;
+use uuid::Uuid;
+
+use crate::models::moderation::ModerationCheck;
+use super::DbPool;
+use super::row::{FromRow, impl_from_row};
+
+impl_from_row!(ModerationCheck {
+    id,
+    subject_type,
+    subject_id,
+    user_id,
+    #[sql = "(SELECT u.wx_openid FROM users u WHERE u.id = moderation_checks.user_id)"]
+    openid,
+    content,
+    reason,
+    status,
+    label,
+    attempts,
+    last_error,
+    next_check_at,
+    created_at,
+    checked_at,
+});
+
+// 加入复核队列，delay_secs 秒后首次复核
+pub async fn enqueue_moderation_check(
+    pool: &DbPool,
+    subject_type: &str,
+    subject_id: Uuid,
+    user_id: Option<Uuid>,
+    content: &str,
+    reason: &str,
+    delay_secs: i64,
+) -> Result<(), Error> {
+    let client = pool.lock().await;
+
+    client.execute(
+        "INSERT INTO moderation_checks (subject_type, subject_id, user_id, content, reason, next_check_at)
+         VALUES ($1, $2, $3, $4, $5, CURRENT_TIMESTAMP + make_interval(secs => $6::BIGINT))",
+        &[&subject_type, &subject_id, &user_id, &content, &reason, &delay_secs],
+    ).await?;
+    Ok(())
+}
+
+// 领取到期的复核记录，同时把下次复核时间推后 retry_delay_secs 秒，多实例不会重复领取
+pub async fn claim_due_moderation_checks(pool: &DbPool, limit: i64, retry_delay_secs: i64) -> Result<Vec<ModerationCheck>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!(
+            "UPDATE moderation_checks SET
+                 attempts = attempts + 1,
+                 next_check_at = CURRENT_TIMESTAMP + make_interval(secs => $2::BIGINT)
+             WHERE id IN (
+                 SELECT id FROM moderation_checks
+                 WHERE status = 'pending' AND next_check_at <= CURRENT_TIMESTAMP
+                 ORDER BY next_check_at
+                 LIMIT $1
+                 FOR UPDATE SKIP LOCKED
+             )
+             RETURNING {}",
+            ModerationCheck::select_columns(),
+        ),
+        &[&limit, &retry_delay_secs],
+    ).await?;
+    rows.iter().map(ModerationCheck::from_row).collect()
+}
+
+// 记录复核结果；status 仍为 pending 时只更新错误信息，等待下次复核
+pub async fn complete_moderation_check(
+    pool: &DbPool,
+    id: Uuid,
+    status: &str,
+    label: Option<&str>,
+    last_error: Option<&str>,
+) -> Result<(), Error> {
+    let client = pool.lock().await;
+
+    client.execute(
+        "UPDATE moderation_checks SET
+             status = $2,
+             label = COALESCE($3, label),
+             last_error = $4,
+             checked_at = CASE WHEN $2::VARCHAR = 'pending' THEN checked_at ELSE CURRENT_TIMESTAMP END
+         WHERE id = $1",
+        &[&id, &status, &label, &last_error],
+    ).await?;
+    Ok(())
+}
+
+// 按状态列出复核记录，最新的在前
+pub async fn list_moderation_checks(pool: &DbPool, status: &str, limit: i64) -> Result<Vec<ModerationCheck>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!(
+            "SELECT {} FROM moderation_checks WHERE status = $1 ORDER BY created_at DESC LIMIT $2",
+            ModerationCheck::select_columns(),
+        ),
+        &[&status, &limit],
+    ).await?;
+    rows.iter().map(ModerationCheck::from_row).collect()
+}
+
+// 清除违规留言，留言已被修改时不处理；返回是否清除
+pub async fn clear_user_data_message(pool: &DbPool, id: Uuid, message: &str) -> Result<bool, Error> {
+    let client = pool.lock().await;
+
+    let updated = client.execute(
+        "UPDATE user_data SET message = NULL, version = version + 1 WHERE id = $1 AND message = $2",
+        &[&id, &message],
+    ).await?;
+    Ok(updated > 0)
+}
+
+// 清除违规昵称，昵称已被修改时不处理；返回是否清除
+pub async fn clear_profile_name(pool: &DbPool, user_id: Uuid, full_name: &str) -> Result<bool, Error> {
+    let client = pool.lock().await;
+
+    let updated = client.execute(
+        "UPDATE users SET full_name = NULL, updated_at = CURRENT_TIMESTAMP, version = version + 1
+         WHERE id = $1 AND full_name = $2",
+        &[&user_id, &full_name],
+    ).await?;
+    Ok(updated > 0)
+}
@@ -0,0 +1,80 @@
+use tokio_postgres::{Error, Row};
+use uuid::Uuid;
+
+use crate::database::DbPool;
+use crate::models::notification::{Notification, NewNotification};
+use crate::utils::pagination::PageRequest;
+
+fn row_to_notification(row: &Row) -> Notification {
+    Notification {
+        id: row.get(0),
+        user_id: row.get(1),
+        category: row.get(2),
+        title: row.get(3),
+        content: row.get(4),
+        data: row.get(5),
+        is_read: row.get(6),
+        created_at: row.get(7),
+    }
+}
+
+// 创建通知
+pub async fn create_notification(pool: &DbPool, notification: &NewNotification) -> Result<Notification, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_one(
+        "INSERT INTO notifications (user_id, category, title, content, data)
+         VALUES ($1, $2, $3, $4, $5)
+         RETURNING id, user_id, category, title, content, data, is_read, created_at",
+        &[&notification.user_id, &notification.category, &notification.title, &notification.content, &notification.data],
+    ).await?;
+
+    Ok(row_to_notification(&row))
+}
+
+// 用户是否有未读通知
+pub async fn has_unread_notifications(pool: &DbPool, user_id: Uuid) -> Result<bool, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_one(
+        "SELECT EXISTS(SELECT 1 FROM notifications WHERE user_id = $1 AND is_read = false)",
+        &[&user_id],
+    ).await?;
+    Ok(row.get(0))
+}
+
+// 按游标分页查询用户的通知（最新的在前）
+pub async fn list_notifications(pool: &DbPool, user_id: Uuid, page: &PageRequest) -> Result<Vec<Notification>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        "SELECT id, user_id, category, title, content, data, is_read, created_at
+         FROM notifications
+         WHERE user_id = $1 AND ($2::timestamptz IS NULL OR (created_at, id) < ($2, $3))
+         ORDER BY created_at DESC, id DESC
+         LIMIT $4",
+        &[&user_id, &page.cursor_created_at(), &page.cursor_id(), &page.fetch_limit()],
+    ).await?;
+
+    Ok(rows.iter().map(row_to_notification).collect())
+}
+
+// 用户的通知总数
+pub async fn count_notifications(pool: &DbPool, user_id: Uuid) -> Result<i64, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_one("SELECT COUNT(*) FROM notifications WHERE user_id = $1", &[&user_id]).await?;
+    Ok(row.get(0))
+}
+
+// 标记通知为已读
+pub async fn mark_notification_read(pool: &DbPool, user_id: Uuid, notification_id: Uuid) -> Result<bool, Error> {
+    let client = pool.lock().await;
+
+    let updated = client.execute(
+        "UPDATE notifications SET is_read = true WHERE id = $1 AND user_id = $2",
+        &[&notification_id, &user_id],
+    ).await?;
+
+    Ok(updated > 0)
+}
@@ -0,0 +1,169 @@
+use tokio_postgres::{Error, Row};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::database::DbPool;
+use crate::models::order::{Order, OrderItem, OrderStatus};
+use crate::utils::pagination::PageRequest;
+
+const ORDER_COLUMNS: &str =
+    "id, order_no, user_id, title, items, total_amount, status, out_trade_no, remark, expires_at, paid_at, cancelled_at, created_at, updated_at";
+
+fn row_to_order(row: &Row) -> Order {
+    let items: serde_json::Value = row.get(4);
+    Order {
+        id: row.get(0),
+        order_no: row.get(1),
+        user_id: row.get(2),
+        title: row.get(3),
+        items: serde_json::from_value::<Vec<OrderItem>>(items).unwrap_or_default(),
+        total_amount: row.get(5),
+        status: OrderStatus::parse(row.get(6)).unwrap_or(OrderStatus::Cancelled),
+        out_trade_no: row.get(7),
+        remark: row.get(8),
+        expires_at: row.get(9),
+        paid_at: row.get(10),
+        cancelled_at: row.get(11),
+        created_at: row.get(12),
+        updated_at: row.get(13),
+    }
+}
+
+// 创建订单
+#[allow(clippy::too_many_arguments)]
+pub async fn create_order(
+    pool: &DbPool,
+    user_id: Uuid,
+    order_no: &str,
+    title: &str,
+    items: &[OrderItem],
+    total_amount: i64,
+    remark: Option<&str>,
+    expires_at: DateTime<Utc>,
+) -> Result<Order, Error> {
+    let client = pool.lock().await;
+    let items = serde_json::to_value(items).unwrap_or_else(|_| serde_json::json!([]));
+
+    let row = client.query_one(
+        &format!(
+            "INSERT INTO orders (order_no, user_id, title, items, total_amount, remark, expires_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING {}",
+            ORDER_COLUMNS
+        ),
+        &[&order_no, &user_id, &title, &items, &total_amount, &remark, &expires_at],
+    ).await?;
+
+    Ok(row_to_order(&row))
+}
+
+// 按订单号查询用户的订单
+pub async fn find_user_order(pool: &DbPool, user_id: Uuid, order_no: &str) -> Result<Option<Order>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        &format!("SELECT {} FROM orders WHERE order_no = $1 AND user_id = $2", ORDER_COLUMNS),
+        &[&order_no, &user_id],
+    ).await?;
+
+    Ok(row.as_ref().map(row_to_order))
+}
+
+// 按状态筛选用户的订单（游标分页），statuses 为空时不筛选
+pub async fn list_user_orders(
+    pool: &DbPool,
+    user_id: Uuid,
+    statuses: &[OrderStatus],
+    page: &PageRequest,
+) -> Result<Vec<Order>, Error> {
+    let client = pool.lock().await;
+    let statuses: Vec<&str> = statuses.iter().map(|s| s.as_str()).collect();
+
+    let rows = client.query(
+        &format!(
+            "SELECT {} FROM orders
+             WHERE user_id = $1
+               AND (cardinality($2::varchar[]) = 0 OR status = ANY($2))
+               AND ($3::timestamptz IS NULL OR (created_at, id) < ($3, $4))
+             ORDER BY created_at DESC, id DESC
+             LIMIT $5",
+            ORDER_COLUMNS
+        ),
+        &[&user_id, &statuses, &page.cursor_created_at(), &page.cursor_id(), &page.fetch_limit()],
+    ).await?;
+
+    Ok(rows.iter().map(row_to_order).collect())
+}
+
+// 关联支付单，仅待支付且未过期的订单可以发起支付
+pub async fn attach_order_payment(pool: &DbPool, order_id: Uuid, out_trade_no: &str) -> Result<Option<Order>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        &format!(
+            "UPDATE orders SET out_trade_no = $2, updated_at = CURRENT_TIMESTAMP
+             WHERE id = $1 AND status = 'pending' AND expires_at > CURRENT_TIMESTAMP
+             RETURNING {}",
+            ORDER_COLUMNS
+        ),
+        &[&order_id, &out_trade_no],
+    ).await?;
+
+    Ok(row.as_ref().map(row_to_order))
+}
+
+// 用户取消待支付订单
+pub async fn cancel_order(pool: &DbPool, order_id: Uuid) -> Result<Option<Order>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        &format!(
+            "UPDATE orders SET status = 'cancelled', cancelled_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP
+             WHERE id = $1 AND status = 'pending'
+             RETURNING {}",
+            ORDER_COLUMNS
+        ),
+        &[&order_id],
+    ).await?;
+
+    Ok(row.as_ref().map(row_to_order))
+}
+
+// 支付成功后将关联的订单标记为已支付
+pub async fn mark_order_paid(pool: &DbPool, out_trade_no: &str, paid_at: DateTime<Utc>) -> Result<Option<Order>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        &format!(
+            "UPDATE orders SET status = 'paid', paid_at = $2, updated_at = CURRENT_TIMESTAMP
+             WHERE out_trade_no = $1 AND status = 'pending'
+             RETURNING {}",
+            ORDER_COLUMNS
+        ),
+        &[&out_trade_no, &paid_at],
+    ).await?;
+
+    Ok(row.as_ref().map(row_to_order))
+}
+
+// 关闭超时未支付的订单，返回被关闭的订单
+pub async fn expire_overdue_orders(pool: &DbPool, limit: i64) -> Result<Vec<Order>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!(
+            "UPDATE orders SET status = 'expired', updated_at = CURRENT_TIMESTAMP
+             WHERE id IN (
+                 SELECT id FROM orders
+                 WHERE status = 'pending' AND expires_at <= CURRENT_TIMESTAMP
+                 ORDER BY expires_at
+                 LIMIT $1
+                 FOR UPDATE SKIP LOCKED
+             )
+             RETURNING {}",
+            ORDER_COLUMNS
+        ),
+        &[&limit],
+    ).await?;
+
+    Ok(rows.iter().map(row_to_order).collect())
+}
@@ -0,0 +1,124 @@
+use tokio_postgres::{Error, Row};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::database::DbPool;
+use crate::models::payment::{Payment, PaymentStatus};
+
+const PAYMENT_COLUMNS: &str =
+    "id, user_id, out_trade_no, description, amount, currency, status, prepay_id, transaction_id, paid_at, created_at, updated_at";
+
+fn row_to_payment(row: &Row) -> Payment {
+    Payment {
+        id: row.get(0),
+        user_id: row.get(1),
+        out_trade_no: row.get(2),
+        description: row.get(3),
+        amount: row.get(4),
+        currency: row.get(5),
+        status: PaymentStatus::parse(row.get(6)).unwrap_or(PaymentStatus::Failed),
+        prepay_id: row.get(7),
+        transaction_id: row.get(8),
+        paid_at: row.get(9),
+        created_at: row.get(10),
+        updated_at: row.get(11),
+    }
+}
+
+// 创建支付单
+pub async fn create_payment(
+    pool: &DbPool,
+    user_id: Uuid,
+    out_trade_no: &str,
+    description: &str,
+    amount: i64,
+) -> Result<Payment, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_one(
+        &format!(
+            "INSERT INTO payments (user_id, out_trade_no, description, amount)
+             VALUES ($1, $2, $3, $4) RETURNING {}",
+            PAYMENT_COLUMNS
+        ),
+        &[&user_id, &out_trade_no, &description, &amount],
+    ).await?;
+
+    Ok(row_to_payment(&row))
+}
+
+// 按商户订单号查询支付单
+pub async fn find_payment_by_out_trade_no(pool: &DbPool, out_trade_no: &str) -> Result<Option<Payment>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        &format!("SELECT {} FROM payments WHERE out_trade_no = $1", PAYMENT_COLUMNS),
+        &[&out_trade_no],
+    ).await?;
+
+    Ok(row.as_ref().map(row_to_payment))
+}
+
+// 保存 prepay_id 并转为待支付状态
+pub async fn mark_payment_prepaid(pool: &DbPool, payment_id: Uuid, prepay_id: &str) -> Result<Option<Payment>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        &format!(
+            "UPDATE payments SET status = 'prepaid', prepay_id = $2, updated_at = CURRENT_TIMESTAMP
+             WHERE id = $1 AND status = 'created'
+             RETURNING {}",
+            PAYMENT_COLUMNS
+        ),
+        &[&payment_id, &prepay_id],
+    ).await?;
+
+    Ok(row.as_ref().map(row_to_payment))
+}
+
+// 按状态机转换支付单状态，仅当当前状态允许转换时更新
+pub async fn transition_payment_status(
+    pool: &DbPool,
+    out_trade_no: &str,
+    target: PaymentStatus,
+    transaction_id: Option<&str>,
+    paid_at: Option<DateTime<Utc>>,
+) -> Result<Option<Payment>, Error> {
+    let client = pool.lock().await;
+    let sources: Vec<&str> = PaymentStatus::allowed_sources(target).iter().map(|s| s.as_str()).collect();
+
+    let row = client.query_opt(
+        &format!(
+            "UPDATE payments SET status = $2,
+                transaction_id = COALESCE($3, transaction_id),
+                paid_at = COALESCE($4, paid_at),
+                updated_at = CURRENT_TIMESTAMP
+             WHERE out_trade_no = $1 AND status = ANY($5)
+             RETURNING {}",
+            PAYMENT_COLUMNS
+        ),
+        &[&out_trade_no, &target.as_str(), &transaction_id, &paid_at, &sources],
+    ).await?;
+
+    Ok(row.as_ref().map(row_to_payment))
+}
+
+// 记录支付回调，重复的通知返回 false
+pub async fn record_payment_notification(
+    pool: &DbPool,
+    notification_id: &str,
+    out_trade_no: &str,
+    event_type: &str,
+    payload: &serde_json::Value,
+) -> Result<bool, Error> {
+    let client = pool.lock().await;
+
+    let inserted = client.execute(
+        "INSERT INTO payment_notifications (notification_id, out_trade_no, event_type, payload)
+         VALUES ($1, $2, $3, $4)
+         ON CONFLICT (notification_id) DO NOTHING",
+        &[&notification_id, &out_trade_no, &event_type, &payload],
+    ).await?;
+
+    Ok(inserted > 0)
+}
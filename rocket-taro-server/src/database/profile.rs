@@ -0,0 +1,159 @@
+use tokio_postgres::Error;
+use uuid::Uuid;
+use tracing::info;
+
+use crate::models::auth::User;
+use crate::models::profile::ProfileField;
+use super::DbPool;
+use super::row::FromRow;
+use super::version::{VersionedTable, VersionedUpdate, resolve_versioned_update};
+use crate::utils::pagination::PageRequest;
+
+// 版本号一致时更新单个资料字段，返回更新后的用户
+pub async fn update_profile_field(
+    pool: &DbPool,
+    user_id: Uuid,
+    expected_version: i32,
+    field: ProfileField,
+    value: &str,
+) -> Result<VersionedUpdate<User>, Error> {
+    let client = pool.lock().await;
+
+    // 列名来自枚举，不拼接用户输入
+    let row = client.query_opt(
+        &format!(
+            "UPDATE users SET {} = $1, updated_at = CURRENT_TIMESTAMP, version = version + 1
+             WHERE id = $2 AND deleted_at IS NULL AND version = $3
+             RETURNING {}",
+            field.as_str(),
+            User::select_columns(),
+        ),
+        &[&value, &user_id, &expected_version],
+    ).await?;
+
+    if row.is_some() {
+        info!(user_id = %user_id, field = %field.as_str(), "Updated user profile field");
+    }
+    resolve_versioned_update(&**client, VersionedTable::Users, user_id, row.as_ref().map(User::from_row).transpose()?).await
+}
+
+// 版本号一致时同时更新多个资料字段，None 表示保持原值
+pub async fn update_profile(
+    pool: &DbPool,
+    user_id: Uuid,
+    expected_version: i32,
+    full_name: Option<&str>,
+    avatar_url: Option<&str>,
+) -> Result<VersionedUpdate<User>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        &format!(
+            "UPDATE users SET
+                full_name = COALESCE($1, full_name),
+                avatar_url = COALESCE($2, avatar_url),
+                updated_at = CURRENT_TIMESTAMP,
+                version = version + 1
+             WHERE id = $3 AND deleted_at IS NULL AND version = $4
+             RETURNING {}",
+            User::select_columns(),
+        ),
+        &[&full_name, &avatar_url, &user_id, &expected_version],
+    ).await?;
+
+    if row.is_some() {
+        info!(user_id = %user_id, "Updated user profile");
+    }
+    resolve_versioned_update(&**client, VersionedTable::Users, user_id, row.as_ref().map(User::from_row).transpose()?).await
+}
+
+// 查询有效（未停用、未注销）的用户
+pub async fn find_active_user(pool: &DbPool, user_id: Uuid) -> Result<Option<User>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        &format!("SELECT {} FROM users WHERE id = $1 AND is_active = true AND deleted_at IS NULL", User::select_columns()),
+        &[&user_id],
+    ).await?;
+    row.as_ref().map(User::from_row).transpose()
+}
+
+// 按邮箱查询有效用户（不区分大小写）
+pub async fn find_active_user_by_email(pool: &DbPool, email: &str) -> Result<Option<User>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        &format!("SELECT {} FROM users WHERE lower(email) = lower($1) AND is_active = true AND deleted_at IS NULL", User::select_columns()),
+        &[&email],
+    ).await?;
+    row.as_ref().map(User::from_row).transpose()
+}
+
+// 按用户名查询未注销的用户
+pub async fn find_user_by_username(pool: &DbPool, username: &str) -> Result<Option<User>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        &format!("SELECT {} FROM users WHERE username = $1 AND deleted_at IS NULL", User::select_columns()),
+        &[&username],
+    ).await?;
+    row.as_ref().map(User::from_row).transpose()
+}
+
+// 管理后台按游标分页查询未注销的用户（最新注册的在前），search 按用户名、邮箱、姓名模糊匹配
+pub async fn list_users_page(pool: &DbPool, search: Option<&str>, page: &PageRequest) -> Result<Vec<User>, Error> {
+    let client = pool.lock().await;
+
+    let pattern = search.map(|search| format!("%{}%", escape_like(search)));
+    let rows = client.query(
+        &format!(
+            "SELECT {} FROM users
+             WHERE deleted_at IS NULL
+               AND ($1::text IS NULL OR username ILIKE $1 OR email ILIKE $1 OR full_name ILIKE $1)
+               AND ($2::timestamptz IS NULL OR (created_at, id) < ($2, $3))
+             ORDER BY created_at DESC, id DESC
+             LIMIT $4",
+            User::select_columns(),
+        ),
+        &[&pattern, &page.cursor_created_at(), &page.cursor_id(), &page.fetch_limit()],
+    ).await?;
+    rows.iter().map(User::from_row).collect()
+}
+
+// 按ID批量查询未注销的用户
+pub async fn find_users_by_ids(pool: &DbPool, user_ids: &[Uuid]) -> Result<Vec<User>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!("SELECT {} FROM users WHERE id = ANY($1) AND deleted_at IS NULL", User::select_columns()),
+        &[&user_ids],
+    ).await?;
+    rows.iter().map(User::from_row).collect()
+}
+
+// 转义 LIKE 模式中的通配符
+fn escape_like(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+// 查询用户的密码哈希，用于修改敏感资料前重新验证
+pub async fn find_password_hash(pool: &DbPool, user_id: Uuid) -> Result<Option<String>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        "SELECT password_hash FROM users WHERE id = $1 AND is_active = true AND deleted_at IS NULL",
+        &[&user_id],
+    ).await?;
+    Ok(row.map(|row| row.get(0)))
+}
+
+// 邮箱是否已被其他用户使用（不区分大小写）
+pub async fn email_in_use(pool: &DbPool, email: &str, exclude_user_id: Uuid) -> Result<bool, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_one(
+        "SELECT EXISTS(SELECT 1 FROM users WHERE lower(email) = lower($1) AND id <> $2)",
+        &[&email, &exclude_user_id],
+    ).await?;
+    Ok(row.get(0))
+}
@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::ops::{Deref, DerefMut};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio_postgres::error::SqlState;
+use tokio_postgres::types::{BorrowToSql, ToSql};
+use tokio_postgres::{Client, Error, Row, RowStream, Statement};
+use tracing::{Instrument, debug, debug_span, field::Empty, warn};
+
+use crate::metrics::MetricsRegistry;
+
+/// 慢查询次数
+pub const DB_SLOW_QUERIES: &str = "db.slow_queries";
+/// 被 statement_timeout 取消的查询次数
+pub const DB_QUERY_TIMEOUTS: &str = "db.query_timeouts";
+/// 使用预编译语句执行的查询中的慢查询次数（同时计入 db.slow_queries），用于对比预编译前后的效果
+pub const DB_SLOW_PREPARED_QUERIES: &str = "db.slow_queries.prepared";
+/// 预编译语句缓存命中次数
+pub const DB_STATEMENT_CACHE_HITS: &str = "db.statement_cache.hits";
+/// 预编译语句缓存未命中（向服务器发送 PREPARE）的次数
+pub const DB_STATEMENT_CACHE_MISSES: &str = "db.statement_cache.misses";
+
+/// 查询耗时监控：超过阈值的查询记录日志并计数，日志只包含 SQL 和参数个数，不包含参数值
+#[derive(Clone)]
+pub struct QueryMonitor {
+    slow_threshold: Option<Duration>,
+    metrics: MetricsRegistry,
+}
+
+impl QueryMonitor {
+    /// slow_threshold 为 0 时不记录慢查询
+    pub fn new(slow_threshold: Duration, metrics: MetricsRegistry) -> Self {
+        Self {
+            slow_threshold: (!slow_threshold.is_zero()).then_some(slow_threshold),
+            metrics,
+        }
+    }
+
+    fn observe<T>(&self, sql: &str, param_count: usize, prepared: bool, started: Instant, result: &Result<T, Error>) {
+        let elapsed = started.elapsed();
+
+        if let Err(e) = result {
+            if e.code() == Some(&SqlState::QUERY_CANCELED) {
+                self.metrics.increment(DB_QUERY_TIMEOUTS);
+                warn!(
+                    elapsed_ms = elapsed.as_millis() as u64,
+                    sql = %compact_sql(sql),
+                    params = %redacted_params(param_count),
+                    "Query canceled by statement_timeout"
+                );
+                return;
+            }
+        }
+
+        if self.slow_threshold.is_some_and(|threshold| elapsed >= threshold) {
+            self.metrics.increment(DB_SLOW_QUERIES);
+            if prepared {
+                self.metrics.increment(DB_SLOW_PREPARED_QUERIES);
+            }
+            warn!(
+                elapsed_ms = elapsed.as_millis() as u64,
+                sql = %compact_sql(sql),
+                params = %redacted_params(param_count),
+                prepared,
+                "Slow query"
+            );
+        }
+    }
+}
+
+/// 带耗时监控和追踪 span 的数据库客户端，查询方法与 tokio_postgres::Client 一致，事务等其他方法直接使用底层客户端。
+/// `*_prepared` 方法使用按 SQL 缓存的预编译语句，适用于登录、会话校验等高频查询
+pub struct DbClient {
+    client: Client,
+    monitor: QueryMonitor,
+    // 预编译语句属于具体连接，重连后清空
+    statements: Mutex<HashMap<String, Statement>>,
+}
+
+impl DbClient {
+    pub fn new(client: Client, monitor: QueryMonitor) -> Self {
+        Self { client, monitor, statements: Mutex::new(HashMap::new()) }
+    }
+
+    /// 替换底层连接（重连成功后调用）
+    pub fn replace(&mut self, client: Client) {
+        self.client = client;
+        self.statements.get_mut().unwrap().clear();
+    }
+
+    // 取出缓存的预编译语句，未缓存时向服务器预编译并缓存
+    async fn statement(&self, sql: &str) -> Result<Statement, Error> {
+        if let Some(statement) = self.statements.lock().unwrap().get(sql) {
+            self.monitor.metrics.increment(DB_STATEMENT_CACHE_HITS);
+            return Ok(statement.clone());
+        }
+        self.monitor.metrics.increment(DB_STATEMENT_CACHE_MISSES);
+        let statement = self.monitored(sql, 0, false, self.client.prepare(sql)).await?;
+        debug!(sql = %compact_sql(sql), "Prepared statement cached");
+        self.statements.lock().unwrap().insert(sql.to_string(), statement.clone());
+        Ok(statement)
+    }
+
+    // 表结构变化后缓存的语句会失效（服务器返回 cached plan must not change result type），移出缓存以便下次重新预编译
+    fn evict_if_stale<T>(&self, sql: &str, result: &Result<T, Error>) {
+        if result.as_ref().err().and_then(Error::code).is_some_and(is_stale_statement) {
+            warn!(sql = %compact_sql(sql), "Prepared statement is stale, evicting from cache");
+            self.statements.lock().unwrap().remove(sql);
+        }
+    }
+
+    // 在 db.query span 中执行查询并记录耗时
+    async fn monitored<T>(&self, sql: &str, param_count: usize, prepared: bool, query: impl Future<Output = Result<T, Error>>) -> Result<T, Error> {
+        let span = debug_span!(
+            "db.query",
+            otel.name = "postgresql",
+            otel.kind = "client",
+            otel.status_code = Empty,
+            db.system = "postgresql",
+            db.statement = %compact_sql(sql),
+        );
+        let started = Instant::now();
+        let result = query.instrument(span.clone()).await;
+        if result.is_err() {
+            crate::telemetry::record_error(&span);
+        }
+        self.monitor.observe(sql, param_count, prepared, started, &result);
+        result
+    }
+
+    pub async fn execute(&self, query: &str, params: &[&(dyn ToSql + Sync)]) -> Result<u64, Error> {
+        self.monitored(query, params.len(), false, self.client.execute(query, params)).await
+    }
+
+    pub async fn query(&self, query: &str, params: &[&(dyn ToSql + Sync)]) -> Result<Vec<Row>, Error> {
+        self.monitored(query, params.len(), false, self.client.query(query, params)).await
+    }
+
+    pub async fn query_one(&self, query: &str, params: &[&(dyn ToSql + Sync)]) -> Result<Row, Error> {
+        self.monitored(query, params.len(), false, self.client.query_one(query, params)).await
+    }
+
+    pub async fn query_opt(&self, query: &str, params: &[&(dyn ToSql + Sync)]) -> Result<Option<Row>, Error> {
+        self.monitored(query, params.len(), false, self.client.query_opt(query, params)).await
+    }
+
+    /// 流式查询，耗时统计到服务器开始返回结果为止
+    pub async fn query_raw<P, I>(&self, query: &str, params: I) -> Result<RowStream, Error>
+    where
+        P: BorrowToSql,
+        I: IntoIterator<Item = P>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let params = params.into_iter();
+        let param_count = params.len();
+        self.monitored(query, param_count, false, self.client.query_raw(query, params)).await
+    }
+
+    /// 使用预编译语句执行，`query` 应为固定的 SQL 文本，否则缓存会不断增长
+    pub async fn execute_prepared(&self, query: &str, params: &[&(dyn ToSql + Sync)]) -> Result<u64, Error> {
+        let statement = self.statement(query).await?;
+        let result = self.monitored(query, params.len(), true, self.client.execute(&statement, params)).await;
+        self.evict_if_stale(query, &result);
+        result
+    }
+
+    /// 使用预编译语句查询至多一行，`query` 应为固定的 SQL 文本
+    pub async fn query_opt_prepared(&self, query: &str, params: &[&(dyn ToSql + Sync)]) -> Result<Option<Row>, Error> {
+        let statement = self.statement(query).await?;
+        let result = self.monitored(query, params.len(), true, self.client.query_opt(&statement, params)).await;
+        self.evict_if_stale(query, &result);
+        result
+    }
+}
+
+impl Deref for DbClient {
+    type Target = Client;
+
+    fn deref(&self) -> &Client {
+        &self.client
+    }
+}
+
+impl DerefMut for DbClient {
+    fn deref_mut(&mut self) -> &mut Client {
+        &mut self.client
+    }
+}
+
+// 缓存的预编译语句已无法使用：结果列类型变化（0A000）或服务器端语句不存在（26000）
+fn is_stale_statement(code: &SqlState) -> bool {
+    *code == SqlState::FEATURE_NOT_SUPPORTED || *code == SqlState::INVALID_SQL_STATEMENT_NAME
+}
+
+// 压缩 SQL 中的换行和缩进，便于单行日志检索
+fn compact_sql(sql: &str) -> String {
+    sql.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// 参数值可能包含密码摘要、令牌等敏感数据，日志中只保留个数
+fn redacted_params(count: usize) -> String {
+    format!("[{} redacted]", count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_formatting() {
+        assert_eq!(
+            compact_sql("SELECT id\n            FROM users\n            WHERE username = $1"),
+            "SELECT id FROM users WHERE username = $1"
+        );
+        assert_eq!(redacted_params(2), "[2 redacted]");
+    }
+
+    #[test]
+    fn test_slow_queries_are_counted() {
+        let metrics = MetricsRegistry::new();
+        let monitor = QueryMonitor::new(Duration::from_millis(50), metrics.clone());
+
+        let ok: Result<(), Error> = Ok(());
+        monitor.observe("SELECT 1", 0, false, Instant::now(), &ok);
+        assert_eq!(metrics.get(DB_SLOW_QUERIES), 0);
+
+        monitor.observe("SELECT pg_sleep(1)", 0, false, Instant::now() - Duration::from_millis(100), &ok);
+        assert_eq!(metrics.get(DB_SLOW_QUERIES), 1);
+        assert_eq!(metrics.get(DB_SLOW_PREPARED_QUERIES), 0);
+
+        // 预编译语句的慢查询同时单独计数
+        monitor.observe("SELECT pg_sleep(1)", 0, true, Instant::now() - Duration::from_millis(100), &ok);
+        assert_eq!(metrics.get(DB_SLOW_QUERIES), 2);
+        assert_eq!(metrics.get(DB_SLOW_PREPARED_QUERIES), 1);
+
+        // 阈值为 0 时关闭慢查询记录
+        let disabled = QueryMonitor::new(Duration::ZERO, metrics.clone());
+        disabled.observe("SELECT pg_sleep(1)", 0, false, Instant::now() - Duration::from_millis(100), &ok);
+        assert_eq!(metrics.get(DB_SLOW_QUERIES), 2);
+    }
+
+    #[test]
+    fn test_stale_statement_codes() {
+        assert!(is_stale_statement(&SqlState::FEATURE_NOT_SUPPORTED));
+        assert!(is_stale_statement(&SqlState::INVALID_SQL_STATEMENT_NAME));
+        assert!(!is_stale_statement(&SqlState::QUERY_CANCELED));
+    }
+}
@@ -0,0 +1,93 @@
+use std::net::IpAddr;
+use tokio_postgres::Error;
+use uuid::Uuid;
+
+use crate::models::registration_review::RegistrationReview;
+use super::DbPool;
+use super::row::{FromRow, impl_from_row};
+
+impl_from_row!(RegistrationReview {
+    id,
+    user_id,
+    #[sql = "(SELECT u.username FROM users u WHERE u.id = registration_reviews.user_id)"]
+    username,
+    reasons,
+    ip_address: Option<IpAddr> => |ip: Option<IpAddr>| ip.map(|ip| ip.to_string()),
+    device_id,
+    status,
+    created_at,
+    reviewed_by,
+    reviewed_at,
+    note,
+});
+
+// 新账号加入审核队列，同一用户只保留一条
+pub async fn create_registration_review(
+    pool: &DbPool,
+    user_id: Uuid,
+    reasons: &[String],
+    ip_address: Option<IpAddr>,
+    device_id: Option<&str>,
+) -> Result<Option<RegistrationReview>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        &format!(
+            "INSERT INTO registration_reviews (user_id, reasons, ip_address, device_id)
+             VALUES ($1, $2, $3, $4)
+             ON CONFLICT (user_id) DO NOTHING
+             RETURNING {}",
+            RegistrationReview::select_columns(),
+        ),
+        &[&user_id, &reasons, &ip_address, &device_id],
+    ).await?;
+    row.as_ref().map(RegistrationReview::from_row).transpose()
+}
+
+// 按状态列出审核队列，最新的在前
+pub async fn list_registration_reviews(pool: &DbPool, status: &str, limit: i64) -> Result<Vec<RegistrationReview>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!(
+            "SELECT {} FROM registration_reviews WHERE status = $1 ORDER BY created_at DESC LIMIT $2",
+            RegistrationReview::select_columns(),
+        ),
+        &[&status, &limit],
+    ).await?;
+    rows.iter().map(RegistrationReview::from_row).collect()
+}
+
+// 处理待审核的记录，已处理或不存在时返回 None
+pub async fn decide_registration_review(
+    pool: &DbPool,
+    id: Uuid,
+    status: &str,
+    reviewed_by: Uuid,
+    note: Option<&str>,
+) -> Result<Option<RegistrationReview>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        &format!(
+            "UPDATE registration_reviews
+             SET status = $2, reviewed_by = $3, reviewed_at = CURRENT_TIMESTAMP, note = $4
+             WHERE id = $1 AND status = 'pending'
+             RETURNING {}",
+            RegistrationReview::select_columns(),
+        ),
+        &[&id, &status, &reviewed_by, &note],
+    ).await?;
+    row.as_ref().map(RegistrationReview::from_row).transpose()
+}
+
+// 停用账号（审核拒绝），不标记注销，账号保留供排查
+pub async fn deactivate_user(pool: &DbPool, user_id: Uuid) -> Result<bool, Error> {
+    let client = pool.lock().await;
+
+    let updated = client.execute(
+        "UPDATE users SET is_active = false, updated_at = CURRENT_TIMESTAMP WHERE id = $1 AND is_active = true",
+        &[&user_id],
+    ).await?;
+    Ok(updated > 0)
+}
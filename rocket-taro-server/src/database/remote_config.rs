@@ -0,0 +1,109 @@
+use tokio_postgres::{Error, Row};
+use uuid::Uuid;
+use tracing::info;
+
+use crate::config::Platform;
+use crate::database::DbPool;
+use crate::models::remote_config::{RemoteConfigEntry, ALL_PLATFORMS};
+
+fn platform_column(platform: Option<Platform>) -> &'static str {
+    platform.map(|p| p.as_str()).unwrap_or(ALL_PLATFORMS)
+}
+
+fn row_to_entry(row: &Row) -> RemoteConfigEntry {
+    let platform: String = row.get(1);
+    RemoteConfigEntry {
+        config_key: row.get(0),
+        platform: Platform::from_str(&platform),
+        value: row.get(2),
+        version: row.get(3),
+        is_deleted: row.get(4),
+        description: row.get(5),
+        updated_by: row.get(6),
+        updated_at: row.get(7),
+    }
+}
+
+// 获取某个平台可见的全部配置项（基础值 + 该平台覆盖，包括已删除的项）
+pub async fn list_remote_configs_for_platform(
+    pool: &DbPool,
+    platform: Platform,
+) -> Result<Vec<RemoteConfigEntry>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        "SELECT config_key, platform, value, version, is_deleted, description, updated_by, updated_at
+         FROM remote_configs WHERE platform IN ($1, $2) ORDER BY config_key",
+        &[&ALL_PLATFORMS, &platform.as_str()],
+    ).await?;
+
+    Ok(rows.iter().map(row_to_entry).collect())
+}
+
+// 获取全部配置项（管理端使用）
+pub async fn list_all_remote_configs(pool: &DbPool) -> Result<Vec<RemoteConfigEntry>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        "SELECT config_key, platform, value, version, is_deleted, description, updated_by, updated_at
+         FROM remote_configs ORDER BY config_key, platform",
+        &[],
+    ).await?;
+
+    Ok(rows.iter().map(row_to_entry).collect())
+}
+
+// 写入配置项，每次写入都会分配新的版本号
+pub async fn upsert_remote_config(
+    pool: &DbPool,
+    config_key: &str,
+    platform: Option<Platform>,
+    value: &serde_json::Value,
+    description: Option<&str>,
+    updated_by: Uuid,
+) -> Result<RemoteConfigEntry, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_one(
+        "INSERT INTO remote_configs (config_key, platform, value, description, updated_by)
+         VALUES ($1, $2, $3, $4, $5)
+         ON CONFLICT (config_key, platform) DO UPDATE SET
+            value = EXCLUDED.value,
+            description = COALESCE(EXCLUDED.description, remote_configs.description),
+            updated_by = EXCLUDED.updated_by,
+            version = nextval('remote_config_version_seq'),
+            is_deleted = false,
+            updated_at = CURRENT_TIMESTAMP
+         RETURNING config_key, platform, value, version, is_deleted, description, updated_by, updated_at",
+        &[&config_key, &platform_column(platform), value, &description, &updated_by],
+    ).await?;
+
+    info!("Remote config updated: {} ({})", config_key, platform_column(platform));
+    Ok(row_to_entry(&row))
+}
+
+// 删除配置项（软删除，保留删除版本号以便客户端增量同步）
+pub async fn delete_remote_config(
+    pool: &DbPool,
+    config_key: &str,
+    platform: Option<Platform>,
+    updated_by: Uuid,
+) -> Result<Option<RemoteConfigEntry>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        "UPDATE remote_configs SET
+            is_deleted = true,
+            version = nextval('remote_config_version_seq'),
+            updated_by = $3,
+            updated_at = CURRENT_TIMESTAMP
+         WHERE config_key = $1 AND platform = $2 AND is_deleted = false
+         RETURNING config_key, platform, value, version, is_deleted, description, updated_by, updated_at",
+        &[&config_key, &platform_column(platform), &updated_by],
+    ).await?;
+
+    if row.is_some() {
+        info!("Remote config deleted: {} ({})", config_key, platform_column(platform));
+    }
+    Ok(row.as_ref().map(row_to_entry))
+}
@@ -0,0 +1,120 @@
+use tokio_postgres::Error;
+use uuid::Uuid;
+
+use crate::database::DbPool;
+use crate::models::admin_stats::StatsRange;
+use crate::models::route_decision::{RouteDecision, RouteDecisionRecord};
+use crate::models::route_execution::{ExecutionStatus, FlowCompletionStats};
+use super::row::{FromRow, impl_from_row};
+
+impl_from_row!(RouteDecisionRecord {
+    execution_id,
+    seq,
+    rule,
+    inputs,
+    created_at,
+});
+
+// 记录下发的路由指令
+pub async fn insert_execution(
+    pool: &DbPool,
+    execution_id: Uuid,
+    flow: &str,
+    command_type: &str,
+    platform: &str,
+    user_id: Option<Uuid>,
+) -> Result<(), Error> {
+    let client = pool.lock().await;
+
+    client.execute(
+        "INSERT INTO route_command_executions (execution_id, flow, command_type, platform, user_id)
+         VALUES ($1, $2, $3, $4, $5)",
+        &[&execution_id, &flow, &command_type, &platform, &user_id],
+    ).await?;
+
+    Ok(())
+}
+
+// 记录执行确认，只接受第一次确认，返回是否更新
+pub async fn acknowledge_execution(
+    pool: &DbPool,
+    execution_id: Uuid,
+    status: ExecutionStatus,
+    duration_ms: Option<i64>,
+    error: Option<&str>,
+) -> Result<bool, Error> {
+    let client = pool.lock().await;
+
+    let updated = client.execute(
+        "UPDATE route_command_executions
+         SET acked_at = CURRENT_TIMESTAMP, status = $2, duration_ms = $3, error = $4
+         WHERE execution_id = $1 AND acked_at IS NULL",
+        &[&execution_id, &status.as_str(), &duration_ms, &error],
+    ).await?;
+
+    Ok(updated > 0)
+}
+
+// 按业务流程汇总范围内下发指令的确认情况
+pub async fn completion_by_flow(pool: &DbPool, range: &StatsRange) -> Result<Vec<FlowCompletionStats>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        "SELECT flow,
+                COUNT(*),
+                COUNT(*) FILTER (WHERE status = 'success'),
+                COUNT(*) FILTER (WHERE status = 'error'),
+                AVG(duration_ms) FILTER (WHERE status = 'success')::float8
+         FROM route_command_executions
+         WHERE issued_at >= $1 AND issued_at < $2
+         GROUP BY flow
+         ORDER BY flow",
+        &[&range.start(), &range.end()],
+    ).await?;
+
+    Ok(rows.iter()
+        .map(|row| FlowCompletionStats::new(row.get(0), row.get(1), row.get(2), row.get(3), row.get(4)))
+        .collect())
+}
+
+// 记录一次指令生成命中的路由决策，seq 为命中顺序
+pub async fn insert_route_decisions(pool: &DbPool, execution_id: Uuid, decisions: &[RouteDecision]) -> Result<(), Error> {
+    let client = pool.lock().await;
+
+    let seqs: Vec<i32> = (0..decisions.len() as i32).collect();
+    let rules: Vec<&str> = decisions.iter().map(|decision| decision.rule.as_str()).collect();
+    let inputs: Vec<&serde_json::Value> = decisions.iter().map(|decision| &decision.inputs).collect();
+    client.execute(
+        "INSERT INTO route_command_decisions (execution_id, seq, rule, inputs)
+         SELECT $1, d.seq, d.rule, d.inputs
+         FROM UNNEST($2::INTEGER[], $3::VARCHAR[], $4::JSONB[]) AS d(seq, rule, inputs)",
+        &[&execution_id, &seqs, &rules, &inputs],
+    ).await?;
+
+    Ok(())
+}
+
+// 按执行ID查询路由决策，按命中顺序排列
+pub async fn list_route_decisions(pool: &DbPool, execution_id: Uuid) -> Result<Vec<RouteDecisionRecord>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!(
+            "SELECT {} FROM route_command_decisions WHERE execution_id = $1 ORDER BY seq",
+            RouteDecisionRecord::select_columns(),
+        ),
+        &[&execution_id],
+    ).await?;
+    rows.iter().map(RouteDecisionRecord::from_row).collect()
+}
+
+// 删除超过保留天数的路由决策，返回删除数量
+pub async fn delete_expired_route_decisions(pool: &DbPool, retention_days: i64) -> Result<u64, Error> {
+    let client = pool.lock().await;
+
+    client.execute(
+        "DELETE FROM route_command_decisions
+         WHERE created_at < CURRENT_TIMESTAMP - make_interval(days => $1::INTEGER)",
+        &[&(retention_days as i32)],
+    ).await
+}
@@ -0,0 +1,150 @@
+use std::borrow::Cow;
+
+use tokio_postgres::{Error, Row};
+
+/// 查询结果中映射到结构体字段的列
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Column {
+    /// 结果列名，与结构体字段同名
+    pub name: &'static str,
+    /// 计算列的 SQL 表达式，None 表示直接读取同名列
+    pub expr: Option<&'static str>,
+}
+
+impl Column {
+    // 生成 SELECT 列表中的一项，计算列和带前缀的列使用 AS 别名
+    fn select_item(&self, table_alias: Option<&str>, prefix: &str) -> String {
+        let source = match (self.expr, table_alias) {
+            (Some(expr), _) => Cow::Borrowed(expr),
+            (None, Some(alias)) => Cow::Owned(format!("{}.{}", alias, self.name)),
+            (None, None) => Cow::Borrowed(self.name),
+        };
+        if self.expr.is_none() && prefix.is_empty() {
+            source.into_owned()
+        } else {
+            format!("{} AS {}{}", source, prefix, self.name)
+        }
+    }
+}
+
+/// 按列名从查询结果构造模型，列清单和字段映射由 `impl_from_row!` 从同一份字段列表生成，
+/// SELECT/RETURNING 不再手写列顺序
+pub trait FromRow: Sized {
+    /// 映射的列，顺序与生成的 SELECT 列表一致
+    const COLUMNS: &'static [Column];
+
+    /// 读取带前缀的结果列，用于连接查询中区分同名列
+    fn from_row_prefixed(row: &Row, prefix: &str) -> Result<Self, Error>;
+
+    fn from_row(row: &Row) -> Result<Self, Error> {
+        Self::from_row_prefixed(row, "")
+    }
+
+    /// 单表查询的 SELECT/RETURNING 列表
+    fn select_columns() -> String {
+        select_list(Self::COLUMNS, None, "")
+    }
+
+    /// 连接查询的列表，如 `u.id AS user_id`，配合 `from_row_prefixed(row, "user_")` 读取
+    fn aliased_columns(table_alias: &str, prefix: &str) -> String {
+        select_list(Self::COLUMNS, Some(table_alias), prefix)
+    }
+}
+
+fn select_list(columns: &[Column], table_alias: Option<&str>, prefix: &str) -> String {
+    columns.iter()
+        .map(|column| column.select_item(table_alias, prefix))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// 带前缀的结果列名
+pub fn column_name(prefix: &str, name: &'static str) -> Cow<'static, str> {
+    if prefix.is_empty() {
+        Cow::Borrowed(name)
+    } else {
+        Cow::Owned(format!("{}{}", prefix, name))
+    }
+}
+
+/// 为模型实现 `FromRow`，字段必须列全，漏列或多列在编译时报错。
+///
+/// - `field` 直接读取同名列
+/// - `field: RawType => convert` 按 RawType 读取后转换
+/// - `#[sql = EXPR]` 标记计算列，查询时生成 `EXPR AS field`
+macro_rules! impl_from_row {
+    ($ty:ty { $($(#[sql = $sql:expr])? $field:ident $(: $raw:ty => $convert:expr)?),* $(,)? }) => {
+        impl $crate::database::row::FromRow for $ty {
+            const COLUMNS: &'static [$crate::database::row::Column] = &[
+                $($crate::database::row::Column {
+                    name: stringify!($field),
+                    expr: $crate::database::row::impl_from_row!(@expr $($sql)?),
+                }),*
+            ];
+
+            fn from_row_prefixed(row: &tokio_postgres::Row, prefix: &str) -> Result<Self, tokio_postgres::Error> {
+                Ok(Self {
+                    $($field: $crate::database::row::impl_from_row!(@get row, prefix, $field $(, $raw, $convert)?)),*
+                })
+            }
+        }
+    };
+    (@expr) => { None };
+    (@expr $sql:expr) => { Some($sql) };
+    (@get $row:ident, $prefix:ident, $field:ident) => {
+        $row.try_get(&*$crate::database::row::column_name($prefix, stringify!($field)))?
+    };
+    (@get $row:ident, $prefix:ident, $field:ident, $raw:ty, $convert:expr) => {
+        ($convert)($row.try_get::<_, $raw>(&*$crate::database::row::column_name($prefix, stringify!($field)))?)
+    };
+}
+
+pub(crate) use impl_from_row;
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::models::auth::User;
+    use crate::models::user_data::UserData;
+    use crate::models::wx_auth::WxUser;
+
+    const USER_COLUMNS: &str = "id, username, email, full_name, avatar_url, is_active, is_admin, is_guest, wx_openid, wx_unionid, wx_session_key, last_login_at, created_at, updated_at, version";
+
+    fn assert_unique(columns: &[Column]) {
+        let names: HashSet<_> = columns.iter().map(|column| column.name).collect();
+        assert_eq!(names.len(), columns.len(), "duplicate column in {:?}", columns);
+    }
+
+    #[test]
+    fn test_user_columns() {
+        assert_unique(User::COLUMNS);
+        assert_eq!(User::select_columns(), USER_COLUMNS);
+        assert_eq!(WxUser::select_columns(), USER_COLUMNS);
+        // 凭据列不在映射中，需要时由查询单独读取
+        assert!(!User::COLUMNS.iter().any(|column| column.name == "password_hash"));
+    }
+
+    #[test]
+    fn test_aliased_columns() {
+        let aliased = User::aliased_columns("u", "user_");
+        assert!(aliased.starts_with("u.id AS user_id, u.username AS user_username"));
+        assert!(aliased.ends_with("u.version AS user_version"));
+        assert_eq!(column_name("user_", "id"), "user_id");
+        assert_eq!(column_name("", "id"), "id");
+    }
+
+    #[test]
+    fn test_user_data_columns() {
+        assert_unique(UserData::COLUMNS);
+        let select = UserData::select_columns();
+        assert!(select.starts_with("id, name, email, phone, message, created_at, status, version, "));
+        assert!(select.ends_with(" AS attachments"));
+        let computed: Vec<_> = UserData::COLUMNS.iter()
+            .filter(|column| column.expr.is_some())
+            .map(|column| column.name)
+            .collect();
+        assert_eq!(computed, ["attachments"]);
+    }
+}
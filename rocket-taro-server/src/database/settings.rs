@@ -0,0 +1,29 @@
+use tokio_postgres::Error;
+use uuid::Uuid;
+
+use crate::database::DbPool;
+
+// 获取用户设置，未保存过时返回 None
+pub async fn get_user_settings(pool: &DbPool, user_id: Uuid) -> Result<Option<serde_json::Value>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        "SELECT settings FROM user_settings WHERE user_id = $1",
+        &[&user_id],
+    ).await?;
+
+    Ok(row.map(|row| row.get(0)))
+}
+
+// 保存用户设置
+pub async fn upsert_user_settings(pool: &DbPool, user_id: Uuid, settings: &serde_json::Value) -> Result<(), Error> {
+    let client = pool.lock().await;
+
+    client.execute(
+        "INSERT INTO user_settings (user_id, settings) VALUES ($1, $2)
+         ON CONFLICT (user_id) DO UPDATE SET settings = EXCLUDED.settings, updated_at = CURRENT_TIMESTAMP",
+        &[&user_id, settings],
+    ).await?;
+
+    Ok(())
+}
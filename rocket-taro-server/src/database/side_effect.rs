@@ -0,0 +1,88 @@
+use tokio_postgres::{Error, GenericClient};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::database::DbPool;
+use crate::models::side_effect::{PendingSideEffect, SideEffect, SideEffectStatus};
+
+// 写入副作用，需与业务数据在同一事务中调用
+pub async fn enqueue_side_effects(
+    client: &(impl GenericClient + Sync),
+    effects: &[SideEffect],
+) -> Result<(), Error> {
+    for effect in effects {
+        let payload = serde_json::to_value(effect).expect("side effect serializes to JSON");
+        client.execute(
+            "INSERT INTO side_effect_outbox (effect_type, payload) VALUES ($1, $2)",
+            &[&effect.effect_type(), &payload],
+        ).await?;
+    }
+
+    Ok(())
+}
+
+// 领取到期的副作用；locked_at 早于 stale_before 的记录视为执行进程中断，重新领取
+pub async fn claim_due_side_effects(
+    pool: &DbPool,
+    stale_before: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<PendingSideEffect>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        "UPDATE side_effect_outbox SET
+             attempts = attempts + 1,
+             locked_at = CURRENT_TIMESTAMP
+         WHERE id IN (
+             SELECT id FROM side_effect_outbox
+             WHERE status = 'pending'
+               AND next_attempt_at <= CURRENT_TIMESTAMP
+               AND (locked_at IS NULL OR locked_at < $1)
+             ORDER BY next_attempt_at
+             LIMIT $2
+             FOR UPDATE SKIP LOCKED
+         )
+         RETURNING id, attempts, payload",
+        &[&stale_before, &limit],
+    ).await?;
+
+    Ok(rows.iter().map(|row| PendingSideEffect {
+        id: row.get(0),
+        attempts: row.get(1),
+        payload: row.get(2),
+    }).collect())
+}
+
+// 记录执行结果，next_attempt_at 仅在等待重试时使用
+pub async fn complete_side_effect(
+    pool: &DbPool,
+    id: Uuid,
+    status: SideEffectStatus,
+    error: Option<&str>,
+    next_attempt_at: Option<DateTime<Utc>>,
+) -> Result<(), Error> {
+    let client = pool.lock().await;
+
+    client.execute(
+        "UPDATE side_effect_outbox SET
+             status = $2,
+             last_error = $3,
+             next_attempt_at = COALESCE($4, next_attempt_at),
+             locked_at = NULL,
+             completed_at = CASE WHEN $2 = 'done' THEN CURRENT_TIMESTAMP END
+         WHERE id = $1",
+        &[&id, &status.as_str(), &error, &next_attempt_at],
+    ).await?;
+
+    Ok(())
+}
+
+// 清理已执行的历史记录，放弃的记录保留供排查
+pub async fn purge_side_effects(pool: &DbPool, before: DateTime<Utc>) -> Result<u64, Error> {
+    let client = pool.lock().await;
+
+    client.execute(
+        "DELETE FROM side_effect_outbox WHERE status = 'done' AND completed_at < $1",
+        &[&before],
+    ).await
+}
@@ -0,0 +1,108 @@
+use chrono::{DateTime, Utc};
+use tokio_postgres::Error;
+use uuid::Uuid;
+
+use crate::models::storage::{StorageObject, StorageUsage};
+use super::DbPool;
+use super::row::{FromRow, impl_from_row};
+
+impl_from_row!(StorageObject {
+    key,
+    user_id,
+    kind,
+    size,
+    content_type,
+    created_at,
+    expires_at,
+});
+
+impl_from_row!(StorageUsage {
+    user_id,
+    #[sql = "COUNT(*)"]
+    object_count,
+    #[sql = "COALESCE(SUM(size), 0)::BIGINT"]
+    total_bytes,
+});
+
+// 记录保存的文件，同一 key 重复保存时覆盖
+pub async fn record_storage_object(
+    pool: &DbPool,
+    key: &str,
+    user_id: Option<Uuid>,
+    kind: &str,
+    size: i64,
+    content_type: &str,
+    expires_at: Option<DateTime<Utc>>,
+) -> Result<(), Error> {
+    let client = pool.lock().await;
+
+    client.execute(
+        "INSERT INTO storage_objects (key, user_id, kind, size, content_type, expires_at)
+         VALUES ($1, $2, $3, $4, $5, $6)
+         ON CONFLICT (key) DO UPDATE SET
+             user_id = EXCLUDED.user_id,
+             kind = EXCLUDED.kind,
+             size = EXCLUDED.size,
+             content_type = EXCLUDED.content_type,
+             created_at = CURRENT_TIMESTAMP,
+             expires_at = EXCLUDED.expires_at",
+        &[&key, &user_id, &kind, &size, &content_type, &expires_at],
+    ).await?;
+    Ok(())
+}
+
+// 领取已过期的文件，同时把过期时间推后 retry_delay_secs 秒，删除失败时稍后重试，多实例不会重复领取
+pub async fn claim_expired_storage_objects(pool: &DbPool, limit: i64, retry_delay_secs: i64) -> Result<Vec<StorageObject>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!(
+            "UPDATE storage_objects SET expires_at = CURRENT_TIMESTAMP + make_interval(secs => $2::BIGINT)
+             WHERE key IN (
+                 SELECT key FROM storage_objects
+                 WHERE expires_at <= CURRENT_TIMESTAMP
+                 ORDER BY expires_at
+                 LIMIT $1
+                 FOR UPDATE SKIP LOCKED
+             )
+             RETURNING {}",
+            StorageObject::select_columns(),
+        ),
+        &[&limit, &retry_delay_secs],
+    ).await?;
+    rows.iter().map(StorageObject::from_row).collect()
+}
+
+// 删除文件记录
+pub async fn delete_storage_object(pool: &DbPool, key: &str) -> Result<(), Error> {
+    let client = pool.lock().await;
+
+    client.execute("DELETE FROM storage_objects WHERE key = $1", &[&key]).await?;
+    Ok(())
+}
+
+// 用户占用的存储空间
+pub async fn get_storage_usage(pool: &DbPool, user_id: Uuid) -> Result<StorageUsage, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_one(
+        "SELECT $1::UUID AS user_id, COUNT(*) AS object_count, COALESCE(SUM(size), 0)::BIGINT AS total_bytes
+         FROM storage_objects WHERE user_id = $1",
+        &[&user_id],
+    ).await?;
+    StorageUsage::from_row(&row)
+}
+
+// 按占用空间从大到小列出用户，用户已删除的文件合并为 user_id 为空的一项
+pub async fn list_top_storage_usage(pool: &DbPool, limit: i64) -> Result<Vec<StorageUsage>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!(
+            "SELECT {} FROM storage_objects GROUP BY user_id ORDER BY total_bytes DESC LIMIT $1",
+            StorageUsage::select_columns(),
+        ),
+        &[&limit],
+    ).await?;
+    rows.iter().map(StorageUsage::from_row).collect()
+}
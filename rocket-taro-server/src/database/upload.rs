@@ -0,0 +1,134 @@
+use tokio_postgres::{Error, Row, Transaction};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::database::DbPool;
+use crate::models::upload::{Attachment, Upload, download_url};
+
+const UPLOAD_COLUMNS: &str = "id, user_id, file_name, content_type, file_size, file_path, created_at";
+
+/// 查询用户数据时附带附件列表的子查询（JSON 数组，按上传顺序），需在 FROM user_data 的查询中使用
+pub const USER_DATA_ATTACHMENTS_SQL: &str =
+    "COALESCE((SELECT json_agg(json_build_object(
+                   'id', u.id, 'file_name', u.file_name, 'content_type', u.content_type, 'file_size', u.file_size
+               ) ORDER BY a.position)
+               FROM user_data_attachments a JOIN uploads u ON u.id = a.upload_id
+               WHERE a.user_data_id = user_data.id), '[]'::json)";
+
+fn row_to_upload(row: &Row) -> Upload {
+    Upload {
+        id: row.get(0),
+        user_id: row.get(1),
+        file_name: row.get(2),
+        content_type: row.get(3),
+        file_size: row.get(4),
+        file_path: row.get(5),
+        created_at: row.get(6),
+    }
+}
+
+#[derive(Deserialize)]
+struct AttachmentRow {
+    id: Uuid,
+    file_name: String,
+    content_type: String,
+    file_size: i64,
+}
+
+/// 解析 USER_DATA_ATTACHMENTS_SQL 查询出的附件列表
+pub fn parse_attachments(value: serde_json::Value) -> Vec<Attachment> {
+    serde_json::from_value::<Vec<AttachmentRow>>(value)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|row| Attachment {
+            url: download_url(row.id),
+            id: row.id,
+            file_name: row.file_name,
+            content_type: row.content_type,
+            file_size: row.file_size,
+        })
+        .collect()
+}
+
+// 记录上传的文件
+pub async fn insert_upload(pool: &DbPool, upload: &Upload) -> Result<(), Error> {
+    let client = pool.lock().await;
+
+    client.execute(
+        "INSERT INTO uploads (id, user_id, file_name, content_type, file_size, file_path, created_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7)",
+        &[
+            &upload.id,
+            &upload.user_id,
+            &upload.file_name,
+            &upload.content_type,
+            &upload.file_size,
+            &upload.file_path,
+            &upload.created_at,
+        ],
+    ).await?;
+
+    Ok(())
+}
+
+// 按ID查询上传的文件
+pub async fn find_upload(pool: &DbPool, upload_id: Uuid) -> Result<Option<Upload>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        &format!("SELECT {} FROM uploads WHERE id = $1", UPLOAD_COLUMNS),
+        &[&upload_id],
+    ).await?;
+
+    Ok(row.as_ref().map(row_to_upload))
+}
+
+// 查询尚未作为附件的文件（已关联其他用户数据的文件不返回）
+pub async fn find_unattached_uploads(pool: &DbPool, upload_ids: &[Uuid]) -> Result<Vec<Upload>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!(
+            "SELECT {} FROM uploads u
+             WHERE id = ANY($1)
+               AND NOT EXISTS (SELECT 1 FROM user_data_attachments a WHERE a.upload_id = u.id)",
+            UPLOAD_COLUMNS,
+        ),
+        &[&upload_ids],
+    ).await?;
+
+    Ok(rows.iter().map(row_to_upload).collect())
+}
+
+// 在保存用户数据的事务中关联附件，按列表顺序排列
+pub async fn attach_uploads(transaction: &Transaction<'_>, user_data_id: Uuid, attachments: &[Attachment]) -> Result<(), Error> {
+    for (position, attachment) in attachments.iter().enumerate() {
+        transaction.execute(
+            "INSERT INTO user_data_attachments (upload_id, user_data_id, position) VALUES ($1, $2, $3)",
+            &[&attachment.id, &user_data_id, &(position as i16)],
+        ).await?;
+    }
+    Ok(())
+}
+
+// 删除指定时间之前上传且未作为附件的文件记录，返回需要删除的文件路径
+pub async fn delete_orphaned_uploads(pool: &DbPool, uploaded_before: DateTime<Utc>, limit: i64) -> Result<Vec<String>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        "DELETE FROM uploads
+         WHERE id IN (
+             SELECT id FROM uploads u
+             WHERE created_at < $1
+               AND NOT EXISTS (SELECT 1 FROM user_data_attachments a WHERE a.upload_id = u.id)
+             ORDER BY created_at
+             LIMIT $2
+         )
+         RETURNING file_path",
+        &[&uploaded_before, &limit],
+    ).await?;
+
+    Ok(rows.iter().map(|row| row.get(0)).collect())
+}
+
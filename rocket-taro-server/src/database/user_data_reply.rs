@@ -0,0 +1,84 @@
+use tokio_postgres::{Error, Row};
+use uuid::Uuid;
+
+use crate::database::{DbPool, history};
+use crate::database::version::{VersionedTable, VersionedUpdate, resolve_versioned_update};
+use crate::models::user_data::{UserDataReply, UserDataStatus};
+
+const REPLY_COLUMNS: &str = "id, user_data_id, admin_id, content, created_at";
+
+fn row_to_reply(row: &Row) -> UserDataReply {
+    UserDataReply {
+        id: row.get(0),
+        user_data_id: row.get(1),
+        admin_id: row.get(2),
+        content: row.get(3),
+        created_at: row.get(4),
+    }
+}
+
+// 查询用户数据的回复（按时间顺序）
+pub async fn list_replies(pool: &DbPool, user_data_id: Uuid) -> Result<Vec<UserDataReply>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!("SELECT {} FROM user_data_replies WHERE user_data_id = $1 ORDER BY created_at, id", REPLY_COLUMNS),
+        &[&user_data_id],
+    ).await?;
+    Ok(rows.iter().map(row_to_reply).collect())
+}
+
+// 保存回复并更新处理状态，用户数据不存在时返回 None
+pub async fn insert_reply(
+    pool: &DbPool,
+    reply: &UserDataReply,
+    status: UserDataStatus,
+) -> Result<Option<UserDataReply>, Error> {
+    let mut client = pool.lock().await;
+    let transaction = client.transaction().await?;
+    if let Some(admin_id) = reply.admin_id {
+        history::set_actor(&transaction, admin_id).await?;
+    }
+
+    let updated = transaction.execute(
+        "UPDATE user_data SET status = $1, version = version + 1 WHERE id = $2",
+        &[&status.as_str(), &reply.user_data_id],
+    ).await?;
+    if updated == 0 {
+        return Ok(None);
+    }
+
+    let row = transaction.query_one(
+        &format!(
+            "INSERT INTO user_data_replies (id, user_data_id, admin_id, content, created_at)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING {}",
+            REPLY_COLUMNS,
+        ),
+        &[&reply.id, &reply.user_data_id, &reply.admin_id, &reply.content, &reply.created_at],
+    ).await?;
+    transaction.commit().await?;
+
+    Ok(Some(row_to_reply(&row)))
+}
+
+// 版本号一致时更新处理状态，返回新的版本号
+pub async fn update_status(
+    pool: &DbPool,
+    admin_id: Uuid,
+    user_data_id: Uuid,
+    expected_version: i32,
+    status: UserDataStatus,
+) -> Result<VersionedUpdate<i32>, Error> {
+    let mut client = pool.lock().await;
+    let transaction = client.transaction().await?;
+    history::set_actor(&transaction, admin_id).await?;
+
+    let row = transaction.query_opt(
+        "UPDATE user_data SET status = $1, version = version + 1 WHERE id = $2 AND version = $3 RETURNING version",
+        &[&status.as_str(), &user_data_id, &expected_version],
+    ).await?;
+    let result = resolve_versioned_update(&transaction, VersionedTable::UserData, user_data_id, row.map(|row| row.get(0))).await?;
+    transaction.commit().await?;
+    Ok(result)
+}
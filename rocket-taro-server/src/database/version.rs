@@ -0,0 +1,56 @@
+use tokio_postgres::{Error, GenericClient};
+use uuid::Uuid;
+
+/// 使用乐观锁的表
+#[derive(Debug, Clone, Copy)]
+pub enum VersionedTable {
+    Users,
+    UserData,
+}
+
+impl VersionedTable {
+    // 查询当前版本号，已注销的用户视为不存在
+    fn current_version_sql(&self) -> &'static str {
+        match self {
+            VersionedTable::Users => "SELECT version FROM users WHERE id = $1 AND deleted_at IS NULL",
+            VersionedTable::UserData => "SELECT version FROM user_data WHERE id = $1",
+        }
+    }
+}
+
+/// 带版本号条件的更新结果
+#[derive(Debug)]
+pub enum VersionedUpdate<T> {
+    Updated(T),
+    /// 记录已被其他请求修改
+    Conflict { current_version: i32 },
+    NotFound,
+}
+
+impl<T> VersionedUpdate<T> {
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> VersionedUpdate<U> {
+        match self {
+            VersionedUpdate::Updated(value) => VersionedUpdate::Updated(f(value)),
+            VersionedUpdate::Conflict { current_version } => VersionedUpdate::Conflict { current_version },
+            VersionedUpdate::NotFound => VersionedUpdate::NotFound,
+        }
+    }
+}
+
+// 处理 `WHERE id = $ AND version = $` 条件更新的结果：未更新时查询当前版本号，区分版本冲突和记录不存在
+pub async fn resolve_versioned_update<C: GenericClient + Sync, T>(
+    client: &C,
+    table: VersionedTable,
+    id: Uuid,
+    updated: Option<T>,
+) -> Result<VersionedUpdate<T>, Error> {
+    if let Some(value) = updated {
+        return Ok(VersionedUpdate::Updated(value));
+    }
+
+    let row = client.query_opt(table.current_version_sql(), &[&id]).await?;
+    Ok(match row {
+        Some(row) => VersionedUpdate::Conflict { current_version: row.get(0) },
+        None => VersionedUpdate::NotFound,
+    })
+}
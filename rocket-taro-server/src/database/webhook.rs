@@ -0,0 +1,381 @@
+use tokio_postgres::{Error, GenericClient, Row};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::database::DbPool;
+use crate::models::webhook::{
+    WebhookDelivery, WebhookDeliveryAttempt, WebhookDeliveryDetail, WebhookDeliveryStatus,
+    WebhookDeliveryTask, WebhookEndpoint, WebhookEvent, WebhookEventType,
+};
+use crate::utils::pagination::PageRequest;
+
+const ENDPOINT_COLUMNS: &str =
+    "id, url, description, event_types, secret, is_active, created_by, created_at, updated_at";
+const DELIVERY_COLUMNS: &str =
+    "id, endpoint_id, event_id, event_type, status, attempts, next_attempt_at, last_status_code, last_error, delivered_at, created_at, updated_at";
+
+fn row_to_endpoint(row: &Row) -> WebhookEndpoint {
+    WebhookEndpoint {
+        id: row.get(0),
+        url: row.get(1),
+        description: row.get(2),
+        event_types: row.get(3),
+        secret: row.get(4),
+        is_active: row.get(5),
+        created_by: row.get(6),
+        created_at: row.get(7),
+        updated_at: row.get(8),
+    }
+}
+
+fn row_to_delivery(row: &Row) -> WebhookDelivery {
+    WebhookDelivery {
+        id: row.get(0),
+        endpoint_id: row.get(1),
+        event_id: row.get(2),
+        event_type: row.get(3),
+        status: WebhookDeliveryStatus::parse(row.get(4)).unwrap_or(WebhookDeliveryStatus::Dead),
+        attempts: row.get(5),
+        next_attempt_at: row.get(6),
+        last_status_code: row.get(7),
+        last_error: row.get(8),
+        delivered_at: row.get(9),
+        created_at: row.get(10),
+        updated_at: row.get(11),
+    }
+}
+
+// 写入发件箱事件，传入事务以保证与业务数据同时提交
+pub async fn enqueue_webhook_event(
+    client: &(impl GenericClient + Sync),
+    event_type: WebhookEventType,
+    payload: &serde_json::Value,
+) -> Result<Uuid, Error> {
+    let row = client.query_one(
+        "INSERT INTO webhook_events (event_type, payload) VALUES ($1, $2) RETURNING id",
+        &[&event_type.as_str(), &payload],
+    ).await?;
+
+    Ok(row.get(0))
+}
+
+// 获取全部接收端
+pub async fn list_webhook_endpoints(pool: &DbPool) -> Result<Vec<WebhookEndpoint>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        &format!("SELECT {} FROM webhook_endpoints ORDER BY created_at DESC", ENDPOINT_COLUMNS),
+        &[],
+    ).await?;
+
+    Ok(rows.iter().map(row_to_endpoint).collect())
+}
+
+// 按ID查询接收端
+pub async fn find_webhook_endpoint(pool: &DbPool, endpoint_id: Uuid) -> Result<Option<WebhookEndpoint>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        &format!("SELECT {} FROM webhook_endpoints WHERE id = $1", ENDPOINT_COLUMNS),
+        &[&endpoint_id],
+    ).await?;
+
+    Ok(row.as_ref().map(row_to_endpoint))
+}
+
+// 创建接收端
+pub async fn create_webhook_endpoint(
+    pool: &DbPool,
+    url: &str,
+    description: Option<&str>,
+    event_types: &[String],
+    secret: &str,
+    created_by: Uuid,
+) -> Result<WebhookEndpoint, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_one(
+        &format!(
+            "INSERT INTO webhook_endpoints (url, description, event_types, secret, created_by)
+             VALUES ($1, $2, $3, $4, $5) RETURNING {}",
+            ENDPOINT_COLUMNS
+        ),
+        &[&url, &description, &event_types, &secret, &created_by],
+    ).await?;
+
+    Ok(row_to_endpoint(&row))
+}
+
+// 更新接收端，参数为 None 的字段保持不变
+pub async fn update_webhook_endpoint(
+    pool: &DbPool,
+    endpoint_id: Uuid,
+    url: Option<&str>,
+    description: Option<&str>,
+    event_types: Option<&[String]>,
+    is_active: Option<bool>,
+) -> Result<Option<WebhookEndpoint>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        &format!(
+            "UPDATE webhook_endpoints SET
+                url = COALESCE($2, url),
+                description = COALESCE($3, description),
+                event_types = COALESCE($4, event_types),
+                is_active = COALESCE($5, is_active),
+                updated_at = CURRENT_TIMESTAMP
+             WHERE id = $1
+             RETURNING {}",
+            ENDPOINT_COLUMNS
+        ),
+        &[&endpoint_id, &url, &description, &event_types, &is_active],
+    ).await?;
+
+    Ok(row.as_ref().map(row_to_endpoint))
+}
+
+// 更换接收端签名密钥
+pub async fn rotate_webhook_secret(pool: &DbPool, endpoint_id: Uuid, secret: &str) -> Result<Option<WebhookEndpoint>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        &format!(
+            "UPDATE webhook_endpoints SET secret = $2, updated_at = CURRENT_TIMESTAMP
+             WHERE id = $1
+             RETURNING {}",
+            ENDPOINT_COLUMNS
+        ),
+        &[&endpoint_id, &secret],
+    ).await?;
+
+    Ok(row.as_ref().map(row_to_endpoint))
+}
+
+// 删除接收端及其投递记录
+pub async fn delete_webhook_endpoint(pool: &DbPool, endpoint_id: Uuid) -> Result<bool, Error> {
+    let client = pool.lock().await;
+
+    let deleted = client.execute("DELETE FROM webhook_endpoints WHERE id = $1", &[&endpoint_id]).await?;
+
+    Ok(deleted > 0)
+}
+
+// 将未分发的事件展开为各订阅接收端的投递记录，返回处理的事件数
+pub async fn dispatch_webhook_events(pool: &DbPool, limit: i64) -> Result<u64, Error> {
+    let client = pool.lock().await;
+
+    let dispatched = client.execute(
+        "WITH batch AS (
+             SELECT id, event_type FROM webhook_events
+             WHERE dispatched_at IS NULL
+             ORDER BY created_at
+             LIMIT $1
+             FOR UPDATE SKIP LOCKED
+         ),
+         fanout AS (
+             INSERT INTO webhook_deliveries (endpoint_id, event_id, event_type)
+             SELECT e.id, b.id, b.event_type
+             FROM batch b
+             JOIN webhook_endpoints e
+               ON e.is_active AND (b.event_type = ANY(e.event_types) OR '*' = ANY(e.event_types))
+             ON CONFLICT (endpoint_id, event_id) DO NOTHING
+         )
+         UPDATE webhook_events SET dispatched_at = CURRENT_TIMESTAMP
+         WHERE id IN (SELECT id FROM batch)",
+        &[&limit],
+    ).await?;
+
+    Ok(dispatched)
+}
+
+// 领取到期的投递记录（包括超时未完成的投递），并增加投递次数
+pub async fn claim_due_webhook_deliveries(
+    pool: &DbPool,
+    stale_before: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<WebhookDeliveryTask>, Error> {
+    let client = pool.lock().await;
+
+    let rows = client.query(
+        "WITH claimed AS (
+             UPDATE webhook_deliveries SET
+                 status = 'delivering',
+                 attempts = attempts + 1,
+                 locked_at = CURRENT_TIMESTAMP,
+                 updated_at = CURRENT_TIMESTAMP
+             WHERE id IN (
+                 SELECT d.id FROM webhook_deliveries d
+                 JOIN webhook_endpoints e ON e.id = d.endpoint_id AND e.is_active
+                 WHERE (d.status = 'pending' AND d.next_attempt_at <= CURRENT_TIMESTAMP)
+                    OR (d.status = 'delivering' AND d.locked_at < $1)
+                 ORDER BY d.next_attempt_at
+                 LIMIT $2
+                 FOR UPDATE OF d SKIP LOCKED
+             )
+             RETURNING *
+         )
+         SELECT c.id, c.endpoint_id, c.event_id, c.event_type, c.status, c.attempts, c.next_attempt_at,
+                c.last_status_code, c.last_error, c.delivered_at, c.created_at, c.updated_at,
+                e.url, e.secret, ev.payload, ev.created_at
+         FROM claimed c
+         JOIN webhook_endpoints e ON e.id = c.endpoint_id
+         JOIN webhook_events ev ON ev.id = c.event_id",
+        &[&stale_before, &limit],
+    ).await?;
+
+    Ok(rows.iter().map(|row| {
+        let delivery = row_to_delivery(row);
+        let event = WebhookEvent {
+            id: delivery.event_id,
+            event_type: delivery.event_type.clone(),
+            payload: row.get(14),
+            created_at: row.get(15),
+        };
+        WebhookDeliveryTask {
+            delivery,
+            url: row.get(12),
+            secret: row.get(13),
+            event,
+        }
+    }).collect())
+}
+
+// 记录一次投递尝试并更新投递状态，next_attempt_at 仅在等待重试时使用
+#[allow(clippy::too_many_arguments)]
+pub async fn record_webhook_delivery_attempt(
+    pool: &DbPool,
+    delivery_id: Uuid,
+    attempt: i32,
+    status_code: Option<i32>,
+    error: Option<&str>,
+    duration_ms: i64,
+    status: WebhookDeliveryStatus,
+    next_attempt_at: Option<DateTime<Utc>>,
+) -> Result<(), Error> {
+    let mut client = pool.lock().await;
+    let transaction = client.transaction().await?;
+
+    transaction.execute(
+        "INSERT INTO webhook_delivery_attempts (delivery_id, attempt, status_code, error, duration_ms)
+         VALUES ($1, $2, $3, $4, $5)",
+        &[&delivery_id, &attempt, &status_code, &error, &duration_ms],
+    ).await?;
+
+    transaction.execute(
+        "UPDATE webhook_deliveries SET
+             status = $2,
+             last_status_code = $3,
+             last_error = $4,
+             next_attempt_at = COALESCE($5, next_attempt_at),
+             delivered_at = CASE WHEN $2 = 'succeeded' THEN CURRENT_TIMESTAMP ELSE delivered_at END,
+             locked_at = NULL,
+             updated_at = CURRENT_TIMESTAMP
+         WHERE id = $1",
+        &[&delivery_id, &status.as_str(), &status_code, &error, &next_attempt_at],
+    ).await?;
+
+    transaction.commit().await
+}
+
+// 按状态筛选接收端的投递记录（游标分页），statuses 为空时不筛选
+pub async fn list_webhook_deliveries(
+    pool: &DbPool,
+    endpoint_id: Uuid,
+    statuses: &[WebhookDeliveryStatus],
+    page: &PageRequest,
+) -> Result<Vec<WebhookDelivery>, Error> {
+    let client = pool.lock().await;
+    let statuses: Vec<&str> = statuses.iter().map(|s| s.as_str()).collect();
+
+    let rows = client.query(
+        &format!(
+            "SELECT {} FROM webhook_deliveries
+             WHERE endpoint_id = $1
+               AND (cardinality($2::varchar[]) = 0 OR status = ANY($2))
+               AND ($3::timestamptz IS NULL OR (created_at, id) < ($3, $4))
+             ORDER BY created_at DESC, id DESC
+             LIMIT $5",
+            DELIVERY_COLUMNS
+        ),
+        &[&endpoint_id, &statuses, &page.cursor_created_at(), &page.cursor_id(), &page.fetch_limit()],
+    ).await?;
+
+    Ok(rows.iter().map(row_to_delivery).collect())
+}
+
+// 查询投递详情，包括事件内容和每次尝试的日志
+pub async fn get_webhook_delivery_detail(pool: &DbPool, delivery_id: Uuid) -> Result<Option<WebhookDeliveryDetail>, Error> {
+    let client = pool.lock().await;
+
+    let Some(row) = client.query_opt(
+        &format!("SELECT {} FROM webhook_deliveries WHERE id = $1", DELIVERY_COLUMNS),
+        &[&delivery_id],
+    ).await? else {
+        return Ok(None);
+    };
+    let delivery = row_to_delivery(&row);
+
+    let event_row = client.query_one(
+        "SELECT id, event_type, payload, created_at FROM webhook_events WHERE id = $1",
+        &[&delivery.event_id],
+    ).await?;
+    let event = WebhookEvent {
+        id: event_row.get(0),
+        event_type: event_row.get(1),
+        payload: event_row.get(2),
+        created_at: event_row.get(3),
+    };
+
+    let attempts_log = client.query(
+        "SELECT id, delivery_id, attempt, status_code, error, duration_ms, created_at
+         FROM webhook_delivery_attempts
+         WHERE delivery_id = $1
+         ORDER BY attempt",
+        &[&delivery_id],
+    ).await?.iter().map(|row| WebhookDeliveryAttempt {
+        id: row.get(0),
+        delivery_id: row.get(1),
+        attempt: row.get(2),
+        status_code: row.get(3),
+        error: row.get(4),
+        duration_ms: row.get(5),
+        created_at: row.get(6),
+    }).collect();
+
+    Ok(Some(WebhookDeliveryDetail { delivery, event, attempts_log }))
+}
+
+// 将已放弃的投递重新放入队列，尝试次数从零开始
+pub async fn retry_webhook_delivery(pool: &DbPool, delivery_id: Uuid) -> Result<Option<WebhookDelivery>, Error> {
+    let client = pool.lock().await;
+
+    let row = client.query_opt(
+        &format!(
+            "UPDATE webhook_deliveries SET status = 'pending', attempts = 0,
+                next_attempt_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP
+             WHERE id = $1 AND status = 'dead'
+             RETURNING {}",
+            DELIVERY_COLUMNS
+        ),
+        &[&delivery_id],
+    ).await?;
+
+    Ok(row.as_ref().map(row_to_delivery))
+}
+
+// 清理过期的事件（投递记录和尝试日志级联删除），仍在投递中的事件保留
+pub async fn purge_webhook_events(pool: &DbPool, before: DateTime<Utc>) -> Result<u64, Error> {
+    let client = pool.lock().await;
+
+    client.execute(
+        "DELETE FROM webhook_events ev
+         WHERE ev.created_at < $1
+           AND ev.dispatched_at IS NOT NULL
+           AND NOT EXISTS (
+               SELECT 1 FROM webhook_deliveries d
+               WHERE d.event_id = ev.id AND d.status IN ('pending', 'delivering')
+           )",
+        &[&before],
+    ).await
+}
@@ -0,0 +1,58 @@
+use rocket::{Request, State, request::{self, FromRequest}, http::Status};
+
+use crate::models::auth::User;
+use super::MockStore;
+
+/// 模拟模式下指定当前用户的请求头
+pub const MOCK_USER_HEADER: &str = "X-Mock-User";
+
+/// 模拟模式的当前用户：依次取 X-Mock-User 请求头、模拟登录返回的会话令牌、配置的默认用户
+pub struct MockUser {
+    pub user: User,
+    /// 通过会话令牌识别时的令牌，用于登出
+    pub session_token: Option<String>,
+}
+
+// 与正式认证守卫相同，从Cookie或Authorization头获取会话令牌
+fn session_token(req: &Request<'_>) -> Option<String> {
+    req.cookies()
+        .get_private("session_token")
+        .map(|cookie| cookie.value().to_string())
+        .or_else(|| {
+            req.headers()
+                .get_one("Authorization")
+                .and_then(|auth| auth.strip_prefix("Bearer "))
+                .map(|token| token.to_string())
+        })
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for MockUser {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let store = match req.guard::<&State<MockStore>>().await.succeeded() {
+            Some(store) => store,
+            None => return request::Outcome::Error((Status::InternalServerError, ())),
+        };
+
+        let header_user = req.headers()
+            .get_one(MOCK_USER_HEADER)
+            .map(str::trim)
+            .filter(|username| !username.is_empty());
+        if let Some(username) = header_user {
+            return request::Outcome::Success(MockUser { user: store.user(username), session_token: None });
+        }
+
+        if let Some(token) = session_token(req) {
+            if let Some(user) = store.user_by_token(&token) {
+                return request::Outcome::Success(MockUser { user, session_token: Some(token) });
+            }
+        }
+
+        match store.default_user() {
+            Some(user) => request::Outcome::Success(MockUser { user, session_token: None }),
+            None => request::Outcome::Error((Status::Unauthorized, ())),
+        }
+    }
+}
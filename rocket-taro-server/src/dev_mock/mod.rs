@@ -0,0 +1,39 @@
+use rocket::{Build, Rocket};
+use rocket::fs::{FileServer, relative};
+use tracing::warn;
+
+use crate::config::{BatchConfig, CookieConfig, DevMockConfig, ResponseProfileConfig, RouteConfig};
+use crate::use_cases::batch_use_case::BatchUseCase;
+use crate::{fairings, routes};
+
+pub mod store;
+pub mod guards;
+
+pub use store::MockStore;
+pub use guards::MockUser;
+
+/// 构建前端开发模拟模式的 Rocket 实例：不连接 PostgreSQL、Redis 和微信，
+/// 只挂载内存实现的认证和用户数据接口
+pub fn build(config: DevMockConfig, route_config: RouteConfig) -> Rocket<Build> {
+    warn!("Dev mock mode enabled: PostgreSQL, Redis and WeChat are not used, data is kept in memory");
+
+    rocket::build()
+        .manage(MockStore::new(config))
+        .manage(route_config)
+        .manage(CookieConfig::from_figment(&rocket::Config::figment()))
+        .manage(BatchUseCase::from_config(BatchConfig::from_figment(&rocket::Config::figment()), None))
+        .mount("/", routes![
+            routes::mock_auth::login,
+            routes::mock_auth::logout,
+            routes::mock_auth::get_current_user,
+            routes::mock_auth::auth_status,
+            routes::mock_user_data::create_user_data,
+            routes::mock_user_data::get_user_data,
+            routes::mock_user_data::get_user_data_page,
+            routes::batch::batch,
+        ])
+        .mount("/", routes::cors::cors_routes())
+        .mount("/", FileServer::from(relative!("frontend/dist")))
+        .attach(fairings::cors::CORS)
+        .attach(fairings::response_profile::ResponseShaping::new(ResponseProfileConfig::from_figment(&rocket::Config::figment())))
+}
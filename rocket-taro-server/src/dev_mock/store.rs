@@ -0,0 +1,178 @@
+use chrono::{Duration, Utc};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use uuid::Uuid;
+
+use crate::config::DevMockConfig;
+use crate::models::auth::{User, UserSession, generate_session_token};
+use crate::models::user_data::UserData;
+use crate::utils::pagination::PageRequest;
+
+/// 模拟会话有效期（天）
+const MOCK_SESSION_DAYS: i64 = 30;
+
+/// 模拟模式的内存数据：用户按用户名首次使用时创建，重启后清空
+pub struct MockStore {
+    config: DevMockConfig,
+    users: RwLock<HashMap<String, User>>,
+    sessions: RwLock<HashMap<String, UserSession>>,
+    user_data: RwLock<Vec<UserData>>,
+}
+
+impl MockStore {
+    pub fn new(config: DevMockConfig) -> Self {
+        Self {
+            config,
+            users: RwLock::new(HashMap::new()),
+            sessions: RwLock::new(HashMap::new()),
+            user_data: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// 按用户名获取模拟用户，不存在时创建
+    pub fn user(&self, username: &str) -> User {
+        if let Some(user) = self.users.read().unwrap().get(username) {
+            return user.clone();
+        }
+
+        let now = Utc::now();
+        self.users.write().unwrap()
+            .entry(username.to_string())
+            .or_insert_with(|| User {
+                id: Uuid::new_v4(),
+                username: username.to_string(),
+                email: format!("{}@mock.local", username),
+                full_name: Some(username.to_string()),
+                avatar_url: None,
+                is_active: true,
+                is_admin: self.config.admin_users.iter().any(|admin| admin == username),
+                is_guest: false,
+                wx_openid: None,
+                wx_unionid: None,
+                wx_session_key: None,
+                last_login_at: None,
+                created_at: now,
+                updated_at: now,
+                version: 1,
+            })
+            .clone()
+    }
+
+    /// 配置的默认用户，未配置时为 None
+    pub fn default_user(&self) -> Option<User> {
+        self.config.default_user.as_deref().map(|username| self.user(username))
+    }
+
+    /// 为用户创建模拟会话
+    pub fn create_session(&self, user: &User, user_agent: Option<String>) -> UserSession {
+        let now = Utc::now();
+        let session = UserSession {
+            id: Uuid::new_v4(),
+            user_id: user.id,
+            session_token: generate_session_token(),
+            user_agent,
+            ip_address: None,
+            expires_at: now + Duration::days(MOCK_SESSION_DAYS),
+            created_at: now,
+            last_accessed_at: now,
+            impersonator_id: None,
+        };
+        self.sessions.write().unwrap().insert(session.session_token.clone(), session.clone());
+        session
+    }
+
+    /// 按会话令牌查找用户
+    pub fn user_by_token(&self, token: &str) -> Option<User> {
+        let session = self.sessions.read().unwrap().get(token).cloned()?;
+        if session.expires_at < Utc::now() {
+            return None;
+        }
+        self.users.read().unwrap().values().find(|user| user.id == session.user_id).cloned()
+    }
+
+    /// 删除会话，返回会话是否存在
+    pub fn remove_session(&self, token: &str) -> bool {
+        self.sessions.write().unwrap().remove(token).is_some()
+    }
+
+    pub fn insert_user_data(&self, data: UserData) {
+        self.user_data.write().unwrap().push(data);
+    }
+
+    /// 全部用户数据，按创建时间倒序（与数据库一致精确到微秒）
+    pub fn all_user_data(&self) -> Vec<UserData> {
+        let mut data = self.user_data.read().unwrap().clone();
+        data.sort_by_key(|item| std::cmp::Reverse((item.created_at.timestamp_micros(), item.id)));
+        data
+    }
+
+    /// 与数据库分页查询一致：按 (created_at, id) 倒序，取游标之后的 fetch_limit 条
+    pub fn user_data_page(&self, page: &PageRequest) -> Vec<UserData> {
+        self.all_user_data()
+            .into_iter()
+            .filter(|item| match page.cursor {
+                Some(cursor) => (item.created_at.timestamp_micros(), item.id) < (cursor.created_at.timestamp_micros(), cursor.id),
+                None => true,
+            })
+            .take(page.fetch_limit() as usize)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::user_data::NewUserData;
+    use crate::utils::pagination::Cursor;
+
+    fn new_data(name: &str) -> UserData {
+        UserData::new(NewUserData {
+            name: name.to_string(),
+            email: format!("{}@example.com", name),
+            phone: None,
+            message: None,
+            attachment_ids: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn test_mock_users_are_stable() {
+        let store = MockStore::new(DevMockConfig::default());
+
+        let alice = store.user("alice");
+        assert_eq!(store.user("alice").id, alice.id);
+        assert!(!alice.is_admin);
+        assert!(store.user("admin").is_admin);
+        assert!(store.default_user().is_none());
+    }
+
+    #[test]
+    fn test_mock_sessions() {
+        let store = MockStore::new(DevMockConfig::default());
+        let alice = store.user("alice");
+
+        let session = store.create_session(&alice, None);
+        assert_eq!(store.user_by_token(&session.session_token).unwrap().id, alice.id);
+        assert!(store.remove_session(&session.session_token));
+        assert!(store.user_by_token(&session.session_token).is_none());
+    }
+
+    #[test]
+    fn test_mock_user_data_pages() {
+        let store = MockStore::new(DevMockConfig::default());
+        for name in ["a", "b", "c"] {
+            store.insert_user_data(new_data(name));
+        }
+
+        let first = PageRequest::parse(None, Some(2), 20, 100).unwrap();
+        let rows = store.user_data_page(&first);
+        assert_eq!(rows.len(), 3);
+
+        let last = &rows[1];
+        let token = Cursor::new(last.created_at, last.id).encode();
+        let second = PageRequest::parse(Some(&token), Some(2), 20, 100).unwrap();
+        let rest = store.user_data_page(&second);
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest[0].id, rows[2].id);
+    }
+}
@@ -0,0 +1,234 @@
+use rocket::{async_trait, Rocket, Build, fairing::{Fairing, Info, Kind}};
+use rocket::futures::future::join_all;
+use std::net::IpAddr;
+use std::sync::Arc;
+use uuid::Uuid;
+use tracing::{info, error, debug};
+
+use crate::cache::RedisPool;
+use crate::config::{RouteConfig, SessionLimitsConfig, SideEffectConfig, WebhookConfig};
+use crate::database::DbPool;
+use crate::push::PushHub;
+use crate::utils::http_client::HttpClient;
+use crate::models::{
+    auth::{User, UserSession},
+    user_data::UserData,
+};
+
+pub mod subscribers;
+
+use subscribers::{AuditLogSubscriber, CacheWarmingSubscriber, NotificationSubscriber, SessionLimitSubscriber, SideEffectDispatchSubscriber, WebhookDispatchSubscriber};
+
+/// 登录 / 注册方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthMethod {
+    Password,
+    Guest,
+    Wechat,
+    QrCode,
+}
+
+impl AuthMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuthMethod::Password => "password",
+            AuthMethod::Guest => "guest",
+            AuthMethod::Wechat => "wechat",
+            AuthMethod::QrCode => "qr_code",
+        }
+    }
+}
+
+/// 业务用例发布的领域事件
+#[allow(clippy::enum_variant_names)]
+#[derive(Debug, Clone)]
+pub enum DomainEvent {
+    UserRegistered {
+        user: User,
+        method: AuthMethod,
+        ip_address: Option<IpAddr>,
+    },
+    UserLoggedIn {
+        user: User,
+        session: UserSession,
+        method: AuthMethod,
+        ip_address: Option<IpAddr>,
+        user_agent: Option<String>,
+    },
+    UserLoginFailed {
+        username: String,
+        reason: String,
+        ip_address: Option<IpAddr>,
+        user_agent: Option<String>,
+    },
+    UserLoggedOut {
+        user_id: Uuid,
+        session_token: String,
+    },
+    UserDataCreated {
+        data: UserData,
+    },
+}
+
+impl DomainEvent {
+    /// 事件名称，用于日志
+    pub fn name(&self) -> &'static str {
+        match self {
+            DomainEvent::UserRegistered { .. } => "user_registered",
+            DomainEvent::UserLoggedIn { .. } => "user_logged_in",
+            DomainEvent::UserLoginFailed { .. } => "user_login_failed",
+            DomainEvent::UserLoggedOut { .. } => "user_logged_out",
+            DomainEvent::UserDataCreated { .. } => "user_data_created",
+        }
+    }
+}
+
+/// 事件订阅者
+#[async_trait]
+pub trait EventSubscriber: Send + Sync {
+    /// 订阅者名称，用于日志
+    fn name(&self) -> &'static str;
+
+    /// 处理事件，返回的错误只记录日志，不影响发布者和其他订阅者
+    async fn handle(&self, event: &DomainEvent) -> anyhow::Result<()>;
+}
+
+/// 进程内事件总线
+///
+/// 发布时并发调用全部订阅者并等待完成，保证响应返回前缓存等副作用已生效；
+/// 耗时的工作（如 Webhook 投递）由订阅者自行放到后台执行
+#[derive(Clone, Default)]
+pub struct EventBus {
+    subscribers: Vec<Arc<dyn EventSubscriber>>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册订阅者
+    pub fn subscribe(mut self, subscriber: impl EventSubscriber + 'static) -> Self {
+        self.subscribers.push(Arc::new(subscriber));
+        self
+    }
+
+    /// 发布事件
+    pub async fn publish(&self, event: DomainEvent) {
+        debug!(event = %event.name(), subscribers = %self.subscribers.len(), "Publishing domain event");
+
+        join_all(self.subscribers.iter().map(|subscriber| {
+            let event = &event;
+            async move {
+                if let Err(e) = subscriber.handle(event).await {
+                    error!(event = %event.name(), subscriber = %subscriber.name(), error = %e, "Event subscriber failed");
+                }
+            }
+        })).await;
+    }
+}
+
+/// 在 Redis 连接建立后组装事件总线，需在 CacheFairing 之后挂载
+pub struct EventBusFairing {
+    webhook_config: WebhookConfig,
+    side_effect_config: SideEffectConfig,
+    session_limits_config: SessionLimitsConfig,
+}
+
+impl EventBusFairing {
+    pub fn new(webhook_config: WebhookConfig, side_effect_config: SideEffectConfig, session_limits_config: SessionLimitsConfig) -> Self {
+        Self { webhook_config, side_effect_config, session_limits_config }
+    }
+}
+
+#[async_trait]
+impl Fairing for EventBusFairing {
+    fn info(&self) -> Info {
+        Info {
+            name: "Domain Event Bus",
+            kind: Kind::Ignite,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> rocket::fairing::Result {
+        let (Some(db_pool), Some(redis)) = (rocket.state::<DbPool>(), rocket.state::<RedisPool>()) else {
+            error!("Event bus requires database and Redis connections");
+            return Err(rocket);
+        };
+        let (Some(push), Some(route_config)) = (rocket.state::<PushHub>(), rocket.state::<RouteConfig>()) else {
+            error!("Event bus requires push hub and route configuration");
+            return Err(rocket);
+        };
+        let Some(http) = rocket.state::<HttpClient>() else {
+            error!("Event bus requires the shared HTTP client");
+            return Err(rocket);
+        };
+
+        let bus = EventBus::new()
+            .subscribe(CacheWarmingSubscriber::new(redis.clone()))
+            .subscribe(AuditLogSubscriber::new(db_pool.clone()))
+            .subscribe(NotificationSubscriber::new(db_pool.clone()))
+            .subscribe(SideEffectDispatchSubscriber::new(db_pool.clone(), redis.clone(), self.side_effect_config.clone()))
+            .subscribe(WebhookDispatchSubscriber::new(db_pool.clone(), self.webhook_config.clone(), http.clone()))
+            .subscribe(SessionLimitSubscriber::new(
+                db_pool.clone(),
+                redis.clone(),
+                push.clone(),
+                self.session_limits_config.clone(),
+                route_config.clone(),
+            ));
+        info!(subscribers = %bus.subscribers.len(), "Domain event bus initialized");
+
+        Ok(rocket.manage(bus))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct CountingSubscriber {
+        handled: Arc<AtomicUsize>,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl EventSubscriber for CountingSubscriber {
+        fn name(&self) -> &'static str {
+            "counting"
+        }
+
+        async fn handle(&self, _event: &DomainEvent) -> anyhow::Result<()> {
+            self.handled.fetch_add(1, Ordering::SeqCst);
+            if self.fail {
+                anyhow::bail!("subscriber failure");
+            }
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_publish_reaches_all_subscribers() {
+        let handled = Arc::new(AtomicUsize::new(0));
+        let bus = EventBus::new()
+            .subscribe(CountingSubscriber { handled: handled.clone(), fail: true })
+            .subscribe(CountingSubscriber { handled: handled.clone(), fail: false });
+
+        let data = UserData {
+            id: uuid::Uuid::new_v4(),
+            name: "test".to_string(),
+            email: "test@example.com".to_string(),
+            phone: None,
+            message: None,
+            created_at: chrono::Utc::now(),
+            attachments: Vec::new(),
+            status: Default::default(),
+            version: 1,
+        };
+        bus.publish(DomainEvent::UserDataCreated { data }).await;
+
+        // 一个订阅者失败不影响其他订阅者
+        assert_eq!(handled.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,254 @@
+use rocket::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+use tracing::{debug, error};
+
+use crate::cache::{RedisPool, availability::AvailabilityCache, data::DataCache, session::SessionCache, user::UserCache};
+use crate::config::{RouteConfig, SessionLimitsConfig, SideEffectConfig, WebhookConfig};
+use crate::database::DbPool;
+use crate::models::{audit::AuditEvent, auth::AvailabilityField, login_log::NewLoginLog, notification::NewNotification};
+use crate::push::PushHub;
+use crate::use_cases::{session_limit_use_case::SessionLimitUseCase, side_effect_use_case::SideEffectUseCase, webhook_use_case::WebhookUseCase};
+use crate::utils::http_client::HttpClient;
+use crate::webhooks::WebhookClient;
+use super::{DomainEvent, EventSubscriber};
+
+/// 缓存预热与失效：注册后缓存用户并清除可用性负缓存，登出后清理会话缓存，数据变更后清理列表缓存；
+/// 登录后的缓存写入经由副作用发件箱执行，见 SideEffectDispatchSubscriber
+pub struct CacheWarmingSubscriber {
+    redis: RedisPool,
+}
+
+impl CacheWarmingSubscriber {
+    pub fn new(redis: RedisPool) -> Self {
+        Self { redis }
+    }
+}
+
+#[async_trait]
+impl EventSubscriber for CacheWarmingSubscriber {
+    fn name(&self) -> &'static str {
+        "cache_warming"
+    }
+
+    async fn handle(&self, event: &DomainEvent) -> anyhow::Result<()> {
+        match event {
+            DomainEvent::UserRegistered { user, .. } => {
+                let user_cache = UserCache::new(self.redis.clone());
+                user_cache.cache_user(user).await?;
+                user_cache.cache_username_mapping(&user.username, user.id).await?;
+                // 注册后用户名和邮箱不再可用
+                let availability_cache = AvailabilityCache::new(self.redis.clone());
+                availability_cache.invalidate(AvailabilityField::Username, &user.username).await?;
+                availability_cache.invalidate(AvailabilityField::Email, &user.email).await?;
+            }
+            DomainEvent::UserLoggedIn { .. } => {}
+            DomainEvent::UserLoginFailed { username, .. } => {
+                UserCache::new(self.redis.clone()).record_login_failure(username).await?;
+            }
+            DomainEvent::UserLoggedOut { user_id, session_token } => {
+                // 当前会话的缓存无论是否仍有记录都要删除，再清理该用户其余的会话缓存，
+                // 其他设备的会话在下次请求时从数据库重新加载
+                let session_cache = SessionCache::new(self.redis.clone());
+                session_cache.invalidate_session(session_token).await?;
+                session_cache.invalidate_user_sessions(*user_id).await?;
+            }
+            DomainEvent::UserDataCreated { data } => {
+                let data_cache = DataCache::new(self.redis.clone());
+                data_cache.cache_user_data(data).await?;
+                data_cache.invalidate_all_user_data().await?;
+                data_cache.invalidate_search_results().await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 登录失败日志和审计日志，登录成功日志经由副作用发件箱写入
+pub struct AuditLogSubscriber {
+    db_pool: DbPool,
+}
+
+impl AuditLogSubscriber {
+    pub fn new(db_pool: DbPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl EventSubscriber for AuditLogSubscriber {
+    fn name(&self) -> &'static str {
+        "audit_log"
+    }
+
+    async fn handle(&self, event: &DomainEvent) -> anyhow::Result<()> {
+        use crate::database::login_log::insert_login_log;
+        use crate::database::audit::record_audit_event;
+
+        match event {
+            DomainEvent::UserRegistered { user, method, ip_address } => {
+                let audit = AuditEvent::new("user.registered", "user")
+                    .actor(user.id)
+                    .target(user.id)
+                    .ip(*ip_address)
+                    .details(json!({ "method": method.as_str() }));
+                record_audit_event(&self.db_pool, &audit).await?;
+            }
+            DomainEvent::UserLoginFailed { username, reason, ip_address, user_agent } => {
+                let log = NewLoginLog::failure(None, username, reason).client(*ip_address, user_agent.clone());
+                insert_login_log(&self.db_pool, &log).await?;
+            }
+            DomainEvent::UserLoggedIn { .. } | DomainEvent::UserLoggedOut { .. } | DomainEvent::UserDataCreated { .. } => {}
+        }
+        Ok(())
+    }
+}
+
+/// 站内通知：新用户注册后发送欢迎通知
+pub struct NotificationSubscriber {
+    db_pool: DbPool,
+}
+
+impl NotificationSubscriber {
+    pub fn new(db_pool: DbPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl EventSubscriber for NotificationSubscriber {
+    fn name(&self) -> &'static str {
+        "notification"
+    }
+
+    async fn handle(&self, event: &DomainEvent) -> anyhow::Result<()> {
+        use crate::database::notification::create_notification;
+
+        if let DomainEvent::UserRegistered { user, .. } = event {
+            let notification = NewNotification::new(
+                user.id,
+                "account",
+                "欢迎加入",
+                &format!("{}，欢迎使用！您可以在个人中心完善资料", user.full_name.as_deref().unwrap_or(&user.username)),
+            );
+            create_notification(&self.db_pool, &notification).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Webhook 即时投递：事件已随业务事务写入发件箱，这里在后台立即触发分发，
+/// 不必等待下一轮定时任务；失败的投递仍由定时任务重试
+pub struct WebhookDispatchSubscriber {
+    db_pool: DbPool,
+    config: WebhookConfig,
+    client: Arc<WebhookClient>,
+}
+
+impl WebhookDispatchSubscriber {
+    pub fn new(db_pool: DbPool, config: WebhookConfig, http: HttpClient) -> Self {
+        let client = Arc::new(WebhookClient::from_config(&config, http));
+        Self { db_pool, config, client }
+    }
+}
+
+#[async_trait]
+impl EventSubscriber for WebhookDispatchSubscriber {
+    fn name(&self) -> &'static str {
+        "webhook_dispatch"
+    }
+
+    async fn handle(&self, event: &DomainEvent) -> anyhow::Result<()> {
+        if !matches!(event, DomainEvent::UserRegistered { .. } | DomainEvent::UserDataCreated { .. }) {
+            return Ok(());
+        }
+
+        let use_case = WebhookUseCase::new(self.db_pool.clone(), self.config.clone());
+        let client = self.client.clone();
+        tokio::spawn(async move {
+            let result = match use_case.dispatch_pending_events().await {
+                Ok(0) => return,
+                Ok(_) => use_case.deliver_due(&client).await.map(|_| ()),
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                error!(error = %e, "Immediate webhook dispatch failed");
+            }
+        });
+        debug!(event = %event.name(), "Scheduled immediate webhook dispatch");
+        Ok(())
+    }
+}
+
+/// 登录记账即时执行：副作用已随会话在同一事务中写入发件箱，这里在后台立即执行，
+/// 缩短缓存预热的延迟；失败的记录由定时任务重试
+pub struct SideEffectDispatchSubscriber {
+    db_pool: DbPool,
+    redis: RedisPool,
+    config: SideEffectConfig,
+}
+
+impl SideEffectDispatchSubscriber {
+    pub fn new(db_pool: DbPool, redis: RedisPool, config: SideEffectConfig) -> Self {
+        Self { db_pool, redis, config }
+    }
+}
+
+#[async_trait]
+impl EventSubscriber for SideEffectDispatchSubscriber {
+    fn name(&self) -> &'static str {
+        "side_effect_dispatch"
+    }
+
+    async fn handle(&self, event: &DomainEvent) -> anyhow::Result<()> {
+        if !matches!(event, DomainEvent::UserLoggedIn { .. }) {
+            return Ok(());
+        }
+
+        let use_case = SideEffectUseCase::new(self.db_pool.clone(), self.redis.clone(), self.config.clone());
+        tokio::spawn(async move {
+            if let Err(e) = use_case.dispatch_due().await {
+                error!(error = %e, "Immediate side effect dispatch failed");
+            }
+        });
+        Ok(())
+    }
+}
+
+/// 同时在线会话数限制：登录后踢出超出限制的最早会话，并推送退出登录指令给被踢出的客户端
+pub struct SessionLimitSubscriber {
+    db_pool: DbPool,
+    redis: RedisPool,
+    push: PushHub,
+    config: SessionLimitsConfig,
+    route_config: RouteConfig,
+}
+
+impl SessionLimitSubscriber {
+    pub fn new(db_pool: DbPool, redis: RedisPool, push: PushHub, config: SessionLimitsConfig, route_config: RouteConfig) -> Self {
+        Self { db_pool, redis, push, config, route_config }
+    }
+}
+
+#[async_trait]
+impl EventSubscriber for SessionLimitSubscriber {
+    fn name(&self) -> &'static str {
+        "session_limit"
+    }
+
+    async fn handle(&self, event: &DomainEvent) -> anyhow::Result<()> {
+        let DomainEvent::UserLoggedIn { user, session, .. } = event else {
+            return Ok(());
+        };
+
+        let use_case = SessionLimitUseCase::new(
+            self.db_pool.clone(),
+            self.redis.clone(),
+            self.push.clone(),
+            self.config.clone(),
+            self.route_config.clone(),
+        );
+        use_case.enforce(user.id, session.id).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+use chrono::Utc;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::{Header, Method, uri::Origin};
+use rocket::{Data, Request, Response};
+use tracing::warn;
+
+use crate::auth::{RequestInfo, SessionUser};
+use crate::cache::RedisPool;
+use crate::config::ApiQuotasConfig;
+use crate::database::DbPool;
+use crate::metrics::MetricsRegistry;
+use crate::models::api_quota::{QuotaCaller, QuotaRole, QuotaStatus};
+use crate::use_cases::api_quota_use_case::ApiQuotaUseCase;
+
+/// 超出每日配额被拒绝的请求计数器
+pub const QUOTA_REJECTED: &str = "http.quota_rejected";
+
+/// 本次请求计入配额后的状态，响应时写入 X-Quota-* 头
+struct RequestQuota(Option<QuotaStatus>);
+
+/// 接口每日配额：按分组统计每个用户（未登录时按 IP）每天的请求次数，UTC 零点重置；
+/// 响应带 X-Quota-Limit / X-Quota-Remaining / X-Quota-Reset，超出时改写到 429 路由
+pub struct ApiQuotaLimiter {
+    config: ApiQuotasConfig,
+}
+
+impl ApiQuotaLimiter {
+    pub fn new(config: ApiQuotasConfig) -> Self {
+        Self { config }
+    }
+}
+
+// 识别调用方：有效会话按用户计数，否则按来源 IP
+async fn resolve_caller(request: &Request<'_>) -> Option<QuotaCaller> {
+    if let Some(SessionUser(auth_user)) = request.guard::<SessionUser>().await.succeeded() {
+        let role = if auth_user.user.is_admin {
+            QuotaRole::Admin
+        } else if auth_user.user.is_guest {
+            QuotaRole::Guest
+        } else {
+            QuotaRole::User
+        };
+        return Some(QuotaCaller::User { user_id: auth_user.user.id, role });
+    }
+    request.guard::<RequestInfo>().await.succeeded()
+        .and_then(|info| info.ip_address)
+        .map(QuotaCaller::Anonymous)
+}
+
+#[rocket::async_trait]
+impl Fairing for ApiQuotaLimiter {
+    fn info(&self) -> Info {
+        Info {
+            name: "API quotas",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, _data: &mut Data<'_>) {
+        if !self.config.enabled || request.method() == Method::Options {
+            return;
+        }
+        let path = request.uri().path().to_string();
+        let Some(group) = self.config.group_for(&path) else {
+            return;
+        };
+        let (Some(db_pool), Some(redis)) = (request.rocket().state::<DbPool>(), request.rocket().state::<RedisPool>()) else {
+            return;
+        };
+        let Some(caller) = resolve_caller(request).await else {
+            return;
+        };
+
+        let use_case = ApiQuotaUseCase::new(db_pool.clone(), redis.clone(), self.config.clone());
+        let Some(status) = use_case.consume(group, &caller, Utc::now()).await else {
+            return;
+        };
+        let exceeded = status.exceeded;
+        let (limit, reset_secs) = (status.limit, status.reset_secs);
+        request.local_cache(|| RequestQuota(Some(status)));
+        if !exceeded {
+            return;
+        }
+
+        warn!(path = %path, group = %group.name, caller = %caller.key_id(), limit, "Request rejected by daily quota");
+        if let Some(metrics) = request.rocket().state::<MetricsRegistry>() {
+            metrics.increment(QUOTA_REJECTED);
+        }
+
+        // 改写到 429 路由（routes::limits::quota_exceeded）
+        let uri = format!("/api/limits/quota-exceeded?group={}&limit={}&retry_after={}", group.name, limit, reset_secs);
+        match Origin::parse_owned(uri) {
+            Ok(uri) => {
+                request.set_method(Method::Get);
+                request.set_uri(uri);
+            }
+            Err(e) => warn!("Failed to rewrite quota-limited request: {}", e),
+        }
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let RequestQuota(Some(status)) = request.local_cache(|| RequestQuota(None)) else {
+            return;
+        };
+        response.set_header(Header::new("X-Quota-Limit", status.limit.to_string()));
+        response.set_header(Header::new("X-Quota-Remaining", status.remaining.to_string()));
+        response.set_header(Header::new("X-Quota-Reset", status.reset_secs.to_string()));
+    }
+}
@@ -0,0 +1,65 @@
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::{Method, uri::Origin};
+use rocket::{Data, Request};
+use tracing::warn;
+
+use crate::config::{BodyKind, BodyLimitsConfig};
+use crate::metrics::MetricsRegistry;
+
+/// 请求体超限计数器
+pub const PAYLOAD_TOO_LARGE: &str = "http.payload_too_large";
+
+/// 请求体大小限制：按 Content-Length 和路由分组的上限拦截过大的 JSON / multipart 请求，改写到 413 路由
+///
+/// 未携带 Content-Length 的请求由 Rocket 的 `[default.limits]` 在读取时截断，超限时由 413 catcher 返回同样的响应。
+pub struct BodyLimits {
+    config: BodyLimitsConfig,
+}
+
+impl BodyLimits {
+    pub fn new(config: BodyLimitsConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for BodyLimits {
+    fn info(&self) -> Info {
+        Info {
+            name: "Body size limits",
+            kind: Kind::Request,
+        }
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, _data: &mut Data<'_>) {
+        let kind = match request.content_type() {
+            Some(content_type) if content_type.is_json() => BodyKind::Json,
+            Some(content_type) if content_type.is_form_data() => BodyKind::Multipart,
+            _ => return,
+        };
+        let Some(length) = request.headers().get_one("Content-Length").and_then(|value| value.parse::<u64>().ok()) else {
+            return;
+        };
+
+        let path = request.uri().path().to_string();
+        let (group, limit) = self.config.limit_for(&path, kind);
+        if length <= limit.as_u64() {
+            return;
+        }
+
+        warn!(path = %path, group = group.unwrap_or("default"), kind = kind.as_str(), length, limit = %limit, "Request body too large");
+        if let Some(metrics) = request.rocket().state::<MetricsRegistry>() {
+            metrics.increment(PAYLOAD_TOO_LARGE);
+        }
+
+        // 改写到 413 路由（routes::limits::payload_too_large）
+        let uri = format!("/api/limits/payload-too-large?limit={}", limit.as_u64());
+        match Origin::parse_owned(uri) {
+            Ok(uri) => {
+                request.set_method(Method::Get);
+                request.set_uri(uri);
+            }
+            Err(e) => warn!("Failed to rewrite oversized request: {}", e),
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::{Build, Rocket};
+use tracing::{info, warn};
+
+use crate::cache::RedisPool;
+use crate::config::CacheWarmupConfig;
+use crate::database::DbPool;
+use crate::use_cases::cache_warmup_use_case::CacheWarmupUseCase;
+
+/// 启动缓存预热：需在 CacheFairing 之后挂载，预热失败不阻止服务启动
+pub struct CacheWarmup {
+    config: CacheWarmupConfig,
+}
+
+impl CacheWarmup {
+    pub fn new(config: CacheWarmupConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for CacheWarmup {
+    fn info(&self) -> Info {
+        Info {
+            name: "Cache warm-up",
+            kind: Kind::Ignite,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> rocket::fairing::Result {
+        if !self.config.enabled || self.config.datasets.is_empty() {
+            info!("Cache warm-up disabled");
+            return Ok(rocket);
+        }
+        let (Some(db_pool), Some(redis)) = (rocket.state::<DbPool>(), rocket.state::<RedisPool>()) else {
+            warn!("Cache warm-up skipped: database or Redis connection is missing");
+            return Ok(rocket);
+        };
+
+        CacheWarmupUseCase::new(db_pool.clone(), redis.clone()).run(&self.config.datasets).await;
+        Ok(rocket)
+    }
+}
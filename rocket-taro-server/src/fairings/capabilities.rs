@@ -0,0 +1,111 @@
+use std::io::Cursor;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::ContentType;
+use rocket::{Request, Response};
+use tracing::{debug, warn};
+
+use crate::models::client_capabilities::ClientCapabilities;
+use crate::models::route_command::{ROUTE_COMMAND_VERSION_HEADER, VersionedRouteCommand};
+
+/// 按客户端声明的能力和指令版本改写响应中的 route_command：降级不支持的指令，
+/// 声明的版本低于当前版本时只返回指令本身（旧格式）；支持全部指令和当前版本的客户端（默认）不读取响应体
+pub struct RouteCommandCapabilities;
+
+#[rocket::async_trait]
+impl Fairing for RouteCommandCapabilities {
+    fn info(&self) -> Info {
+        Info {
+            name: "Adapt route commands to client capabilities and version",
+            kind: Kind::Response,
+        }
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        if response.content_type() != Some(ContentType::JSON) {
+            return;
+        }
+        let client_version = request.headers().get_one(ROUTE_COMMAND_VERSION_HEADER).and_then(|version| version.trim().parse().ok());
+        let versioned = VersionedRouteCommand::client_supports_versioned(client_version);
+        let capabilities = request.guard::<ClientCapabilities>().await.succeeded()
+            .filter(|capabilities| !capabilities.is_full());
+        if versioned && capabilities.is_none() {
+            return;
+        }
+
+        let body = match response.body_mut().to_bytes().await {
+            Ok(body) => body,
+            Err(e) => {
+                warn!("Failed to read response body for route command adaptation: {}", e);
+                return;
+            }
+        };
+
+        let adapted = adapt_body(&body, capabilities.as_ref(), versioned).unwrap_or(body);
+        response.set_sized_body(adapted.len(), Cursor::new(adapted));
+    }
+}
+
+// 改写响应体中的 route_command，没有指令或无法解析时返回 None（保留原响应体）
+fn adapt_body(body: &[u8], capabilities: Option<&ClientCapabilities>, versioned: bool) -> Option<Vec<u8>> {
+    let mut value: serde_json::Value = serde_json::from_slice(body).ok()?;
+    let command = value.get_mut("route_command").filter(|command| !command.is_null())?;
+
+    let mut route_command: VersionedRouteCommand = serde_json::from_value(command.take()).ok()?;
+    if let Some(capabilities) = capabilities {
+        debug!(command_type = %route_command.command.type_name(), "Adapting route command to client capabilities");
+        route_command = route_command.adapt_to(capabilities);
+    }
+    // 旧版本客户端不认识版本化字段，回退指令也无法使用
+    *command = if versioned {
+        serde_json::to_value(route_command).ok()?
+    } else {
+        serde_json::to_value(route_command.command).ok()?
+    };
+
+    serde_json::to_vec(&value).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::route_command::RouteCommand;
+    use serde_json::json;
+
+    #[test]
+    fn test_adapt_body() {
+        let capabilities = ClientCapabilities::from_header("");
+        let body = json!({
+            "code": 200,
+            "message": "success",
+            "data": { "id": 1 },
+            "route_command": VersionedRouteCommand::generated(RouteCommand::delay(300, RouteCommand::navigate_to("/home"))),
+        });
+
+        let adapted: serde_json::Value = serde_json::from_slice(
+            &adapt_body(&serde_json::to_vec(&body).unwrap(), Some(&capabilities), true).unwrap()
+        ).unwrap();
+        assert_eq!(adapted["data"]["id"], 1);
+        assert_eq!(adapted["route_command"]["type"], "NavigateTo");
+        assert!(adapted["route_command"]["metadata"]["id"].is_string());
+
+        // 没有路由指令时不改写
+        let body = json!({ "code": 200, "message": "ok", "data": null, "route_command": null });
+        assert!(adapt_body(&serde_json::to_vec(&body).unwrap(), Some(&capabilities), true).is_none());
+    }
+
+    #[test]
+    fn test_legacy_clients_receive_bare_command() {
+        assert!(VersionedRouteCommand::client_supports_versioned(None));
+        assert!(!VersionedRouteCommand::client_supports_versioned(Some(1)));
+
+        let command = VersionedRouteCommand::generated(RouteCommand::navigate_to("/home")).set_fallback(RouteCommand::toast("失败"));
+        let body = json!({ "code": 200, "message": "success", "data": null, "route_command": command });
+        let adapted: serde_json::Value = serde_json::from_slice(
+            &adapt_body(&serde_json::to_vec(&body).unwrap(), None, false).unwrap()
+        ).unwrap();
+        assert_eq!(adapted["route_command"], json!({
+            "type": "NavigateTo",
+            "payload": { "path": "/home", "params": null, "replace": null, "fallback_path": null },
+        }));
+    }
+}
@@ -0,0 +1,125 @@
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::{Method, uri::Origin};
+use rocket::{Data, Request, Response};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tracing::{debug, warn};
+
+use crate::config::ConcurrencyLimitsConfig;
+use crate::metrics::MetricsRegistry;
+
+/// 排队超时被拒绝的请求计数器
+pub const CONCURRENCY_REJECTED: &str = "http.concurrency_rejected";
+
+/// 请求占用的并发名额，响应生成后释放；请求提前结束时随请求一起释放
+struct HeldPermit(Mutex<Option<OwnedSemaphorePermit>>);
+
+/// 并发限制：按路由分组限制同时处理的请求数，达到上限的请求排队等待，
+/// 超过 `queue_timeout_ms` 仍未取得名额时改写到 503 路由
+pub struct ConcurrencyLimiter {
+    config: ConcurrencyLimitsConfig,
+    semaphores: Vec<Arc<Semaphore>>,
+}
+
+impl ConcurrencyLimiter {
+    pub fn new(config: ConcurrencyLimitsConfig) -> Self {
+        let semaphores = config.groups.iter()
+            .map(|group| Arc::new(Semaphore::new(group.max_concurrent)))
+            .collect();
+        Self { config, semaphores }
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for ConcurrencyLimiter {
+    fn info(&self) -> Info {
+        Info {
+            name: "Concurrency limits",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, _data: &mut Data<'_>) {
+        if !self.config.enabled || request.method() == Method::Options {
+            return;
+        }
+        let path = request.uri().path().to_string();
+        let Some(index) = self.config.group_for(&path) else {
+            return;
+        };
+        let group = &self.config.groups[index];
+
+        // 信号量不会被关闭，获取失败只可能是排队超时
+        let acquire = self.semaphores[index].clone().acquire_owned();
+        if let Ok(Ok(permit)) = tokio::time::timeout(self.config.queue_timeout(), acquire).await {
+            debug!(group = %group.name, available = self.semaphores[index].available_permits(), "Concurrency permit acquired");
+            request.local_cache(|| HeldPermit(Mutex::new(Some(permit))));
+            return;
+        }
+
+        warn!(path = %path, group = %group.name, max_concurrent = group.max_concurrent, "Request rejected by concurrency limit");
+        if let Some(metrics) = request.rocket().state::<MetricsRegistry>() {
+            metrics.increment(CONCURRENCY_REJECTED);
+        }
+
+        // 改写到 503 路由（routes::limits::overloaded）
+        let uri = format!("/api/limits/overloaded?group={}&retry_after={}", group.name, self.config.retry_after_secs);
+        match Origin::parse_owned(uri) {
+            Ok(uri) => {
+                request.set_method(Method::Get);
+                request.set_uri(uri);
+            }
+            Err(e) => warn!("Failed to rewrite rejected request: {}", e),
+        }
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, _response: &mut Response<'r>) {
+        let held = request.local_cache(|| HeldPermit(Mutex::new(None)));
+        held.0.lock().unwrap().take();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::concurrency_limits::ConcurrencyGroup;
+    use rocket::http::Status;
+    use rocket::local::asynchronous::Client;
+
+    #[rocket::post("/api/auth/login")]
+    async fn slow_login() -> &'static str {
+        tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+        "ok"
+    }
+
+    #[tokio::test]
+    async fn test_rejects_when_queue_times_out() {
+        let config = ConcurrencyLimitsConfig {
+            enabled: true,
+            queue_timeout_ms: 50,
+            retry_after_secs: 3,
+            groups: vec![ConcurrencyGroup { name: "password".to_string(), path_prefixes: vec!["/api/auth/login".to_string()], max_concurrent: 1 }],
+        };
+        let rocket = rocket::build()
+            .manage(MetricsRegistry::new())
+            .mount("/", rocket::routes![slow_login, crate::routes::limits::overloaded])
+            .attach(ConcurrencyLimiter::new(config));
+        let client = Client::untracked(rocket).await.unwrap();
+
+        let (first, second) = tokio::join!(
+            client.post("/api/auth/login").dispatch(),
+            async {
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+                client.post("/api/auth/login").dispatch().await
+            },
+        );
+        assert_eq!(first.status(), Status::Ok);
+        assert_eq!(second.status(), Status::ServiceUnavailable);
+        assert_eq!(second.headers().get_one("Retry-After"), Some("3"));
+        let metrics = client.rocket().state::<MetricsRegistry>().unwrap();
+        assert_eq!(metrics.get(CONCURRENCY_REJECTED), 1);
+
+        // 前一个请求完成后名额已释放
+        assert_eq!(client.post("/api/auth/login").dispatch().await.status(), Status::Ok);
+    }
+}
@@ -2,6 +2,7 @@ use rocket::fairing::{Fairing, Info, Kind};
 use rocket::http::Header;
 use rocket::{Request, Response};
 
+#[allow(clippy::upper_case_acronyms)]
 pub struct CORS;
 
 #[rocket::async_trait]
@@ -17,11 +18,12 @@ impl Fairing for CORS {
         response.set_header(Header::new("Access-Control-Allow-Origin", "*"));
         response.set_header(Header::new(
             "Access-Control-Allow-Methods",
-            "GET, POST, PUT, DELETE, OPTIONS",
+            "GET, POST, PUT, PATCH, DELETE, OPTIONS",
         ));
         response.set_header(Header::new(
             "Access-Control-Allow-Headers",
-            "Content-Type, Authorization",
+            "Content-Type, Authorization, Idempotency-Key, X-Mock-User, X-Client-Capabilities, X-Route-Command-Version, X-Request-Id, Accept-Profile",
         ));
+        response.set_header(Header::new("Access-Control-Expose-Headers", "X-Request-Id, Content-Profile"));
     }
 }
\ No newline at end of file
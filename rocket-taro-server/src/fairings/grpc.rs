@@ -0,0 +1,72 @@
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::{Orbit, Rocket};
+use std::net::{IpAddr, SocketAddr};
+use tracing::{error, info, warn};
+
+use crate::cache::RedisPool;
+use crate::config::{GrpcConfig, SessionExpiryConfig};
+use crate::database::{DbHealth, DbPool};
+use crate::use_cases::session_lookup_use_case::SessionLookupUseCase;
+
+/// Rocket 启动后在独立端口启动内部 gRPC 服务，Rocket 关闭时一起停止；
+/// 启动失败只记录错误，不影响 HTTP 服务
+pub struct GrpcServer {
+    config: GrpcConfig,
+}
+
+impl GrpcServer {
+    pub fn new(config: GrpcConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for GrpcServer {
+    fn info(&self) -> Info {
+        Info {
+            name: "Internal gRPC server",
+            kind: Kind::Liftoff,
+        }
+    }
+
+    async fn on_liftoff(&self, rocket: &Rocket<Orbit>) {
+        if !self.config.enabled {
+            info!("Internal gRPC server disabled");
+            return;
+        }
+        if self.config.auth_token.is_empty() {
+            error!("Internal gRPC server not started: grpc.auth_token (GRPC_AUTH_TOKEN) is empty");
+            return;
+        }
+        let address = match self.config.address.parse::<IpAddr>() {
+            Ok(address) => SocketAddr::new(address, self.config.port),
+            Err(e) => {
+                error!("Internal gRPC server not started: invalid address {}: {}", self.config.address, e);
+                return;
+            }
+        };
+        let Some(db_pool) = rocket.state::<DbPool>() else {
+            error!("Internal gRPC server not started: database connection is missing");
+            return;
+        };
+        let redis = rocket.state::<RedisPool>().cloned();
+        if redis.is_none() {
+            warn!("Internal gRPC server running without Redis, lookups go to the database");
+        }
+
+        let lookup = SessionLookupUseCase::new(
+            db_pool.clone(),
+            redis,
+            rocket.state::<DbHealth>().cloned(),
+            rocket.state::<SessionExpiryConfig>().cloned().unwrap_or_default(),
+        );
+        let auth_token = self.config.auth_token.clone();
+        let shutdown = rocket.shutdown();
+        info!("Internal gRPC server listening on {}", address);
+        tokio::spawn(async move {
+            if let Err(e) = crate::grpc::serve(address, &auth_token, lookup, shutdown).await {
+                error!("Internal gRPC server stopped: {}", e);
+            }
+        });
+    }
+}
@@ -0,0 +1,71 @@
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::Method;
+use rocket::{Request, Response};
+use serde_json::json;
+use std::sync::OnceLock;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::auth::request_ip;
+use crate::database::{DbPool, audit::record_audit_event};
+use crate::models::audit::AuditEvent;
+
+/// 本次请求使用的模拟登录会话，由认证守卫写入
+#[derive(Default)]
+pub struct ImpersonatedRequest(OnceLock<Impersonation>);
+
+#[derive(Debug, Clone, Copy)]
+struct Impersonation {
+    admin_id: Uuid,
+    user_id: Uuid,
+    session_id: Uuid,
+}
+
+/// 记录本次请求通过模拟登录会话认证
+pub fn record_impersonation(request: &Request<'_>, admin_id: Uuid, user_id: Uuid, session_id: Uuid) {
+    let _ = request.local_cache(ImpersonatedRequest::default).0.set(Impersonation { admin_id, user_id, session_id });
+}
+
+/// 本次请求是否通过模拟登录会话认证
+pub fn is_impersonated(request: &Request<'_>) -> bool {
+    request.local_cache(ImpersonatedRequest::default).0.get().is_some()
+}
+
+/// 模拟登录审计：模拟登录会话发出的写请求（非 GET / HEAD / OPTIONS）以原管理员身份记录审计事件
+pub struct ImpersonationAudit;
+
+#[rocket::async_trait]
+impl Fairing for ImpersonationAudit {
+    fn info(&self) -> Info {
+        Info {
+            name: "Impersonation audit",
+            kind: Kind::Response,
+        }
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        if matches!(request.method(), Method::Get | Method::Head | Method::Options) {
+            return;
+        }
+        let Some(impersonation) = request.local_cache(ImpersonatedRequest::default).0.get() else {
+            return;
+        };
+        let Some(db_pool) = request.rocket().state::<DbPool>() else {
+            return;
+        };
+
+        let event = AuditEvent::new("impersonation.request", "user")
+            .actor(impersonation.admin_id)
+            .target(impersonation.user_id)
+            .ip(request_ip(request))
+            .details(json!({
+                "session_id": impersonation.session_id,
+                "method": request.method().as_str(),
+                "path": request.uri().path().as_str(),
+                "status": response.status().code,
+            }));
+        if let Err(e) = record_audit_event(db_pool, &event).await {
+            warn!(admin_id = %impersonation.admin_id, "Failed to record impersonation audit event: {}", e);
+        }
+    }
+}
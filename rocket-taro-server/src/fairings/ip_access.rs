@@ -0,0 +1,93 @@
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::Method;
+use rocket::{Data, Request};
+use serde_json::json;
+use std::sync::{Arc, RwLock};
+use tracing::warn;
+
+use crate::auth::RequestInfo;
+use crate::config::{IpAccessConfig, IpAccessRules};
+use crate::database::{DbPool, audit::record_audit_event};
+use crate::models::audit::AuditEvent;
+
+/// 当前生效的访问规则，fairing 与重新加载任务共享
+#[derive(Clone, Default)]
+pub struct IpAccessList {
+    rules: Arc<RwLock<IpAccessRules>>,
+}
+
+impl IpAccessList {
+    pub fn new(config: &IpAccessConfig) -> Self {
+        Self { rules: Arc::new(RwLock::new(IpAccessRules::from_config(config))) }
+    }
+
+    pub fn rules(&self) -> IpAccessRules {
+        self.rules.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// 替换访问规则，返回规则是否有变化
+    pub fn reload(&self, config: &IpAccessConfig) -> bool {
+        let rules = IpAccessRules::from_config(config);
+        let mut current = self.rules.write().unwrap_or_else(|e| e.into_inner());
+        if *current == rules {
+            return false;
+        }
+        *current = rules;
+        true
+    }
+}
+
+/// 管理接口来源 IP 限制：受限路径的请求来源不满足允许/禁止列表时改写到拦截路由，并记录审计日志
+pub struct IpAccessControl {
+    list: IpAccessList,
+}
+
+impl IpAccessControl {
+    pub fn new(list: IpAccessList) -> Self {
+        Self { list }
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for IpAccessControl {
+    fn info(&self) -> Info {
+        Info {
+            name: "IP access control",
+            kind: Kind::Request,
+        }
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, _data: &mut Data<'_>) {
+        if request.method() == Method::Options {
+            return;
+        }
+        let path = request.uri().path().to_string();
+        let rules = self.list.rules();
+        if !rules.is_protected(&path) {
+            return;
+        }
+
+        // 与业务接口一致，直连来源是可信代理时才使用代理传递的 X-Real-IP / X-Forwarded-For
+        let ip_address = request.guard::<RequestInfo>().await
+            .succeeded()
+            .and_then(|request_info| request_info.ip_address);
+        let Err(reason) = rules.check(ip_address) else {
+            return;
+        };
+
+        warn!(method = %request.method(), path = %path, ip = ?ip_address, reason = reason.as_str(), "Request blocked by IP access control");
+        if let Some(pool) = request.rocket().state::<DbPool>() {
+            let event = AuditEvent::new("ip_access.blocked", "route")
+                .target(&path)
+                .ip(ip_address)
+                .details(json!({ "method": request.method().as_str(), "reason": reason.as_str() }));
+            if let Err(e) = record_audit_event(pool, &event).await {
+                warn!(error = %e, "Failed to record IP access audit event");
+            }
+        }
+
+        request.set_method(Method::Get);
+        // 改写到拦截路由（routes::access::ip_access_blocked）
+        request.set_uri(rocket::uri!("/api/access/blocked"));
+    }
+}
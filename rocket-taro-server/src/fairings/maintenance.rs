@@ -0,0 +1,65 @@
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::Method;
+use rocket::{Data, Request};
+use tracing::{info, warn};
+
+use crate::auth::guards::AdminUser;
+use crate::cache::{RedisPool, maintenance::MaintenanceCache};
+use crate::config::MaintenanceConfig;
+
+/// 维护期间仍可访问的维护状态接口
+const MAINTENANCE_PATH_PREFIX: &str = "/api/maintenance";
+
+/// 维护模式：开启后非管理员的 API 请求（白名单除外）改写到维护提示路由，不进入业务处理
+pub struct MaintenanceMode {
+    config: MaintenanceConfig,
+}
+
+impl MaintenanceMode {
+    pub fn new(config: MaintenanceConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for MaintenanceMode {
+    fn info(&self) -> Info {
+        Info {
+            name: "Maintenance mode",
+            kind: Kind::Request,
+        }
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, _data: &mut Data<'_>) {
+        let path = request.uri().path().as_str();
+        if request.method() == Method::Options
+            || !path.starts_with("/api/")
+            || path.starts_with(MAINTENANCE_PATH_PREFIX)
+            || self.config.is_allowed(path)
+        {
+            return;
+        }
+
+        let Some(redis) = request.rocket().state::<RedisPool>() else {
+            return;
+        };
+        // Redis 不可用时不拦截请求
+        match MaintenanceCache::new(redis.clone()).get().await {
+            Ok(Some(_)) => {}
+            Ok(None) => return,
+            Err(e) => {
+                warn!("Failed to check maintenance mode: {}", e);
+                return;
+            }
+        }
+
+        if request.guard::<AdminUser>().await.succeeded().is_some() {
+            return;
+        }
+
+        info!(method = %request.method(), path = %path, "Request blocked by maintenance mode");
+        request.set_method(Method::Get);
+        // 改写到维护提示路由（routes::maintenance::maintenance_blocked）
+        request.set_uri(rocket::uri!("/api/maintenance/blocked"));
+    }
+}
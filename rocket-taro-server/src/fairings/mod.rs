@@ -1 +1,16 @@
-pub mod cors;
\ No newline at end of file
+pub mod cors;
+pub mod capabilities;
+pub mod route_command_metadata;
+pub mod maintenance;
+pub mod ip_access;
+pub mod body_limits;
+pub mod concurrency;
+pub mod api_quota;
+pub mod request_log;
+pub mod impersonation_audit;
+pub mod cache_warmup;
+pub mod response_profile;pub mod grpc;
+pub mod telemetry;
+pub mod slo;
+pub mod runtime_stats;
+pub mod startup_check;
@@ -0,0 +1,97 @@
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::Header;
+use rocket::{Data, Request, Response};
+use std::sync::OnceLock;
+use std::time::Instant;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::config::RequestLogConfig;
+
+/// 客户端可传入的请求 ID 最大长度
+const MAX_REQUEST_ID_LENGTH: usize = 64;
+
+/// 本次请求认证出的用户，由 AuthenticatedUser 守卫写入
+#[derive(Default)]
+pub struct ResolvedUser(OnceLock<Uuid>);
+
+/// 记录本次请求认证出的用户，供请求日志归属
+pub fn record_user(request: &Request<'_>, user_id: Uuid) {
+    let _ = request.local_cache(ResolvedUser::default).0.set(user_id);
+}
+
+/// 需要记录的请求在进入时保存的信息（fairing 改写前的方法和路径）
+struct RequestLogContext {
+    request_id: String,
+    method: String,
+    path: String,
+    started_at: Instant,
+}
+
+/// API 请求日志：记录方法、路径、状态码、耗时、用户和请求 ID，并在响应头返回 X-Request-Id
+pub struct RequestLogger {
+    config: RequestLogConfig,
+}
+
+impl RequestLogger {
+    pub fn new(config: RequestLogConfig) -> Self {
+        Self { config }
+    }
+}
+
+/// 沿用客户端传入的 X-Request-Id（仅限字母、数字和 `-` `_`），否则生成新的
+fn request_id(request: &Request<'_>) -> String {
+    request.headers().get_one("X-Request-Id")
+        .filter(|id| !id.is_empty() && id.len() <= MAX_REQUEST_ID_LENGTH)
+        .filter(|id| id.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_')))
+        .map(str::to_string)
+        .unwrap_or_else(|| Uuid::new_v4().to_string())
+}
+
+#[rocket::async_trait]
+impl Fairing for RequestLogger {
+    fn info(&self) -> Info {
+        Info {
+            name: "API request log",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, _data: &mut Data<'_>) {
+        if !self.config.is_logged_path(request.uri().path().as_str()) {
+            return;
+        }
+        let context = RequestLogContext {
+            request_id: request_id(request),
+            method: request.method().to_string(),
+            path: request.uri().path().to_string(),
+            started_at: Instant::now(),
+        };
+        request.local_cache(|| Some(context));
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let Some(context) = request.local_cache(|| None::<RequestLogContext>) else {
+            return;
+        };
+        response.set_header(Header::new("X-Request-Id", context.request_id.clone()));
+
+        let status = response.status().code;
+        let latency_ms = context.started_at.elapsed().as_millis() as u64;
+        if !self.config.should_log(status, latency_ms, rand::random::<f64>()) {
+            return;
+        }
+
+        let user_id = request.local_cache(ResolvedUser::default).0.get().map(|id| id.to_string());
+        info!(
+            target: "api_request",
+            request_id = %context.request_id,
+            method = %context.method,
+            path = %context.path,
+            status,
+            latency_ms,
+            user_id = user_id.as_deref().unwrap_or("-"),
+            "API request"
+        );
+    }
+}
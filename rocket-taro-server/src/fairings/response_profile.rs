@@ -0,0 +1,73 @@
+use std::io::Cursor;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::{ContentType, Header};
+use rocket::{Request, Response};
+use tracing::warn;
+
+use crate::config::{Platform, ResponseProfileConfig};
+use crate::models::response_profile::{ACCEPT_PROFILE_HEADER, CONTENT_PROFILE_HEADER, ResponseProfile};
+
+/// 按 Accept-Profile 请求头或客户端平台裁剪 ApiResponse 信封，处理函数不需要区分平台
+pub struct ResponseShaping {
+    config: ResponseProfileConfig,
+}
+
+impl ResponseShaping {
+    pub fn new(config: ResponseProfileConfig) -> Self {
+        Self { config }
+    }
+
+    // 请求头指定的形式优先，否则按 User-Agent 检测的平台
+    fn profile_for(&self, request: &Request<'_>) -> ResponseProfile {
+        if let Some(profile) = request.headers().get_one(ACCEPT_PROFILE_HEADER).and_then(ResponseProfile::from_name) {
+            return profile;
+        }
+        let platform = Platform::from_user_agent(request.headers().get_one("User-Agent").unwrap_or("unknown"));
+        self.config.for_platform(platform)
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for ResponseShaping {
+    fn info(&self) -> Info {
+        Info {
+            name: "Shape API response envelopes per platform",
+            kind: Kind::Response,
+        }
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        if !self.config.enabled || response.content_type() != Some(ContentType::JSON) {
+            return;
+        }
+        let profile = self.profile_for(request);
+        if profile == ResponseProfile::Combined {
+            return;
+        }
+
+        let body = match response.body_mut().to_bytes().await {
+            Ok(body) => body,
+            Err(e) => {
+                warn!("Failed to read response body for envelope shaping: {}", e);
+                return;
+            }
+        };
+
+        match shape_body(&body, profile) {
+            Some(shaped) => {
+                response.set_sized_body(shaped.len(), Cursor::new(shaped));
+                response.set_header(Header::new(CONTENT_PROFILE_HEADER, profile.as_str()));
+            }
+            None => response.set_sized_body(body.len(), Cursor::new(body)),
+        }
+    }
+}
+
+// 裁剪响应体，不是 ApiResponse 或无法解析时返回 None（保留原响应体）
+fn shape_body(body: &[u8], profile: ResponseProfile) -> Option<Vec<u8>> {
+    let mut value: serde_json::Value = serde_json::from_slice(body).ok()?;
+    if !profile.shape(&mut value) {
+        return None;
+    }
+    serde_json::to_vec(&value).ok()
+}
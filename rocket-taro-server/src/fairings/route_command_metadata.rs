@@ -0,0 +1,77 @@
+use std::io::Cursor;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::ContentType;
+use rocket::{Request, Response};
+use tracing::warn;
+
+use crate::config::RouteCommandMetadataConfig;
+use crate::models::route_command::VersionedRouteCommand;
+
+/// 按 `[default.route_command_metadata]` 为响应中的 route_command 填写超时和是否可重试，
+/// 配置作为 Rocket 状态管理；需在 RouteCommandCapabilities 之前挂载，旧版本客户端的指令格式由后者转换
+pub struct RouteCommandMetadataStamp;
+
+#[rocket::async_trait]
+impl Fairing for RouteCommandMetadataStamp {
+    fn info(&self) -> Info {
+        Info {
+            name: "Fill in route command metadata",
+            kind: Kind::Response,
+        }
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        if response.content_type() != Some(ContentType::JSON) {
+            return;
+        }
+        let Some(config) = request.rocket().state::<RouteCommandMetadataConfig>() else {
+            return;
+        };
+
+        let body = match response.body_mut().to_bytes().await {
+            Ok(body) => body,
+            Err(e) => {
+                warn!("Failed to read response body for route command metadata: {}", e);
+                return;
+            }
+        };
+
+        let stamped = stamp_body(&body, config).unwrap_or(body);
+        response.set_sized_body(stamped.len(), Cursor::new(stamped));
+    }
+}
+
+// 填写响应体中 route_command 的元数据，没有指令或无法解析时返回 None（保留原响应体）
+fn stamp_body(body: &[u8], config: &RouteCommandMetadataConfig) -> Option<Vec<u8>> {
+    let mut value: serde_json::Value = serde_json::from_slice(body).ok()?;
+    let command = value.get_mut("route_command").filter(|command| !command.is_null())?;
+
+    let mut route_command: VersionedRouteCommand = serde_json::from_value(command.take()).ok()?;
+    config.apply_to(&mut route_command);
+    *command = serde_json::to_value(route_command).ok()?;
+
+    serde_json::to_vec(&value).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{response::ApiResponse, route_command::RouteCommand};
+
+    #[test]
+    fn test_stamp_body() {
+        let config = RouteCommandMetadataConfig::default();
+        let response = ApiResponse::success_with_command(serde_json::json!({ "ok": true }), RouteCommand::navigate_to("/pages/home/home"));
+        let body = serde_json::to_vec(&response).unwrap();
+
+        let stamped: serde_json::Value = serde_json::from_slice(&stamp_body(&body, &config).unwrap()).unwrap();
+        let metadata = &stamped["route_command"]["metadata"];
+        assert_eq!(metadata["id"], serde_json::to_value(&response.route_command.unwrap().metadata.id).unwrap());
+        assert_eq!(metadata["timeout_ms"], 5000);
+        assert_eq!(metadata["retryable"], true);
+        assert_eq!(stamped["data"]["ok"], true);
+
+        assert!(stamp_body(br#"{"code":200,"route_command":null}"#, &config).is_none());
+        assert!(stamp_body(b"not json", &config).is_none());
+    }
+}
@@ -0,0 +1,29 @@
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::{Request, Response};
+
+use crate::metrics::runtime_stats::RuntimeStats;
+
+/// 统计已处理的请求数和 5xx 数，供健康检查和管理后台展示
+pub struct RequestCounter {
+    stats: RuntimeStats,
+}
+
+impl RequestCounter {
+    pub fn new(stats: RuntimeStats) -> Self {
+        Self { stats }
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for RequestCounter {
+    fn info(&self) -> Info {
+        Info {
+            name: "Request counter",
+            kind: Kind::Response,
+        }
+    }
+
+    async fn on_response<'r>(&self, _request: &'r Request<'_>, response: &mut Response<'r>) {
+        self.stats.record_request(response.status().code);
+    }
+}
@@ -0,0 +1,47 @@
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::{Data, Request, Response};
+use std::time::Instant;
+
+use crate::metrics::slo::SloTracker;
+
+/// 进入时匹配到的路由组和开始时间（fairing 改写前的方法和路径）
+struct SloContext {
+    objective: usize,
+    started_at: Instant,
+}
+
+/// 按路由组记录每个请求的状态码和耗时，供 SLO 统计
+pub struct SloRecorder {
+    tracker: SloTracker,
+}
+
+impl SloRecorder {
+    pub fn new(tracker: SloTracker) -> Self {
+        Self { tracker }
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for SloRecorder {
+    fn info(&self) -> Info {
+        Info {
+            name: "SLO recorder",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, _data: &mut Data<'_>) {
+        let Some(objective) = self.tracker.objective_for(request.method().as_str(), request.uri().path().as_str()) else {
+            return;
+        };
+        request.local_cache(|| Some(SloContext { objective, started_at: Instant::now() }));
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let Some(context) = request.local_cache(|| None::<SloContext>) else {
+            return;
+        };
+        let latency_ms = context.started_at.elapsed().as_millis() as u64;
+        self.tracker.record(context.objective, response.status().code, latency_ms, chrono::Utc::now());
+    }
+}
@@ -0,0 +1,48 @@
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::{Build, Rocket};
+use tracing::{error, info, warn};
+
+use crate::config::StartupCheckConfig;
+use crate::self_check::{CheckStatus, SelfCheck};
+
+/// 严格模式下启动前执行自检，有失败项时拒绝启动
+pub struct StartupCheck {
+    config: StartupCheckConfig,
+}
+
+impl StartupCheck {
+    pub fn new(config: StartupCheckConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for StartupCheck {
+    fn info(&self) -> Info {
+        Info {
+            name: "Startup self-check",
+            kind: Kind::Ignite,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> rocket::fairing::Result {
+        if !self.config.strict {
+            return Ok(rocket);
+        }
+
+        let report = SelfCheck::from_figment(rocket.figment()).run().await;
+        for check in &report.checks {
+            match check.status {
+                CheckStatus::Fail => error!(check = check.name, "Startup check failed: {}", check.detail),
+                CheckStatus::Warn => warn!(check = check.name, "Startup check warning: {}", check.detail),
+                CheckStatus::Pass | CheckStatus::Skip => info!(check = check.name, "Startup check {:?}: {}", check.status, check.detail),
+            }
+        }
+        if report.passed() {
+            Ok(rocket)
+        } else {
+            error!("Startup check failed in strict mode, refusing to start");
+            Err(rocket)
+        }
+    }
+}
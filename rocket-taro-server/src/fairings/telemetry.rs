@@ -0,0 +1,35 @@
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::{Orbit, Rocket};
+use tracing::warn;
+
+/// 关闭时发送尚未导出的 span
+pub struct TelemetryShutdown {
+    provider: SdkTracerProvider,
+}
+
+impl TelemetryShutdown {
+    pub fn new(provider: SdkTracerProvider) -> Self {
+        Self { provider }
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for TelemetryShutdown {
+    fn info(&self) -> Info {
+        Info {
+            name: "Tracing export shutdown",
+            kind: Kind::Shutdown,
+        }
+    }
+
+    async fn on_shutdown(&self, _rocket: &Rocket<Orbit>) {
+        // shutdown 会阻塞等待最后一次导出完成
+        let provider = self.provider.clone();
+        match tokio::task::spawn_blocking(move || provider.shutdown()).await {
+            Ok(Err(e)) => warn!("Failed to flush trace spans on shutdown: {}", e),
+            Err(e) => warn!("Failed to flush trace spans on shutdown: {}", e),
+            Ok(Ok(())) => {}
+        }
+    }
+}
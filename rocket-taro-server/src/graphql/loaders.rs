@@ -0,0 +1,84 @@
+use async_graphql::dataloader::Loader;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::database::DbPool;
+use crate::models::{auth::{ActiveSession, User}, login_log::LoginLog, user_data::UserData};
+
+/// 嵌套查询中每个用户最多返回的登录记录数
+pub const MAX_LOGIN_LOGS_PER_USER: i64 = 20;
+
+/// 按用户ID批量加载用户
+pub struct UserLoader(pub DbPool);
+
+impl Loader<Uuid> for UserLoader {
+    type Value = User;
+    type Error = String;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, User>, String> {
+        use crate::database::profile::find_users_by_ids;
+
+        let users = find_users_by_ids(&self.0, keys).await.map_err(|e| e.to_string())?;
+        Ok(users.into_iter().map(|user| (user.id, user)).collect())
+    }
+}
+
+/// 按用户ID批量加载有效会话
+pub struct SessionLoader(pub DbPool);
+
+impl Loader<Uuid> for SessionLoader {
+    type Value = Vec<ActiveSession>;
+    type Error = String;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Vec<ActiveSession>>, String> {
+        use crate::database::auth::list_active_sessions_for_users;
+
+        let rows = list_active_sessions_for_users(&self.0, keys).await.map_err(|e| e.to_string())?;
+        let mut sessions: HashMap<Uuid, Vec<ActiveSession>> = HashMap::new();
+        for (user_id, session) in rows {
+            sessions.entry(user_id).or_default().push(session);
+        }
+        Ok(sessions)
+    }
+}
+
+/// 按用户ID批量加载最近的登录记录
+pub struct LoginLogLoader(pub DbPool);
+
+impl Loader<Uuid> for LoginLogLoader {
+    type Value = Vec<LoginLog>;
+    type Error = String;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Vec<LoginLog>>, String> {
+        use crate::database::login_log::list_recent_login_logs_for_users;
+
+        let rows = list_recent_login_logs_for_users(&self.0, keys, MAX_LOGIN_LOGS_PER_USER).await
+            .map_err(|e| e.to_string())?;
+        let mut logs: HashMap<Uuid, Vec<LoginLog>> = HashMap::new();
+        for log in rows {
+            if let Some(user_id) = log.user_id {
+                logs.entry(user_id).or_default().push(log);
+            }
+        }
+        Ok(logs)
+    }
+}
+
+/// 按邮箱（小写）批量加载用户数据
+pub struct UserDataByEmailLoader(pub DbPool);
+
+impl Loader<String> for UserDataByEmailLoader {
+    type Value = Vec<UserData>;
+    type Error = String;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, Vec<UserData>>, String> {
+        use crate::database::list_user_data_by_emails;
+
+        let rows = list_user_data_by_emails(&self.0, keys).await.map_err(|e| e.to_string())?;
+        let mut data: HashMap<String, Vec<UserData>> = HashMap::new();
+        for row in rows {
+            data.entry(row.email.to_lowercase()).or_default().push(row);
+        }
+        Ok(data)
+    }
+}
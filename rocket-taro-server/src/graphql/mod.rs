@@ -0,0 +1,87 @@
+//! 管理后台 GraphQL 接口：查询用户、会话、登录记录和用户数据，
+//! 嵌套字段通过 DataLoader 合并为批量查询
+
+use async_graphql::{
+    Context, EmptyMutation, EmptySubscription, Guard, Request, Response, Result, Schema,
+    dataloader::DataLoader,
+    extensions::apollo_persisted_queries::{ApolloPersistedQueries, LruCacheStorage},
+};
+
+use crate::auth::AuthenticatedUser;
+use crate::config::GraphqlConfig;
+use crate::database::DbPool;
+
+pub mod loaders;
+pub mod query;
+pub mod types;
+
+use loaders::{LoginLogLoader, SessionLoader, UserDataByEmailLoader, UserLoader};
+use query::QueryRoot;
+
+pub type AdminSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// 按配置构建 Schema：深度和复杂度限制、内省开关、持久化查询缓存
+pub fn build_schema(config: &GraphqlConfig) -> AdminSchema {
+    let mut builder = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(config.clone())
+        .limit_depth(config.max_depth)
+        .limit_complexity(config.max_complexity)
+        .extension(ApolloPersistedQueries::new(LruCacheStorage::new(config.persisted_query_cache_size.max(1))));
+    if !config.introspection {
+        builder = builder.disable_introspection();
+    }
+    builder.finish()
+}
+
+/// 以当前管理员身份执行查询，DataLoader 按请求创建，不跨请求缓存
+pub async fn execute(schema: &AdminSchema, db_pool: DbPool, admin: AuthenticatedUser, request: Request) -> Response {
+    let request = request
+        .data(DataLoader::new(UserLoader(db_pool.clone()), tokio::spawn))
+        .data(DataLoader::new(SessionLoader(db_pool.clone()), tokio::spawn))
+        .data(DataLoader::new(LoginLogLoader(db_pool.clone()), tokio::spawn))
+        .data(DataLoader::new(UserDataByEmailLoader(db_pool.clone()), tokio::spawn))
+        .data(db_pool)
+        .data(admin);
+    schema.execute(request).await
+}
+
+/// 字段级权限：与 AdminUser 请求守卫相同，要求当前用户是管理员
+pub struct AdminGuard;
+
+impl Guard for AdminGuard {
+    async fn check(&self, ctx: &Context<'_>) -> Result<()> {
+        match ctx.data_opt::<AuthenticatedUser>() {
+            Some(auth_user) if auth_user.user.is_admin => Ok(()),
+            _ => Err("需要管理员权限".into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_fields_require_admin() {
+        let schema = build_schema(&GraphqlConfig::default());
+        let response = schema.execute("{ users { items { id } } }").await;
+        assert_eq!(response.errors[0].message, "需要管理员权限");
+    }
+
+    #[tokio::test]
+    async fn test_query_limits() {
+        let schema = build_schema(&GraphqlConfig { max_depth: 3, introspection: false, ..GraphqlConfig::default() });
+        let response = schema.execute("{ loginLogs { items { user { sessions { id } } } } }").await;
+        assert!(response.errors[0].message.contains("nested too deep"));
+
+        let response = schema.execute("{ __schema { queryType { name } } }").await;
+        assert!(!response.data.to_string().contains("QueryRoot"));
+
+        // 超大的 limit 按单页上限计算复杂度，不会溢出
+        let schema = build_schema(&GraphqlConfig { max_complexity: 150, ..GraphqlConfig::default() });
+        let response = schema.execute("{ users(limit: 20) { items { id email } } }").await;
+        assert_eq!(response.errors[0].message, "需要管理员权限");
+        let response = schema.execute("{ users(limit: 9223372036854775807) { items { id email } } }").await;
+        assert!(response.errors[0].message.contains("too complex"));
+    }
+}
@@ -0,0 +1,96 @@
+use async_graphql::{Context, Object, Result, dataloader::DataLoader};
+use uuid::Uuid;
+
+use crate::database::DbPool;
+use crate::models::user_data::UserDataStatus;
+use crate::utils::pagination::{Cursor, Page, PageRequest};
+use super::AdminGuard;
+use super::loaders::UserLoader;
+use super::types::{LoginLogNode, PageNode, UserDataNode, UserNode};
+
+/// 列表查询单页数量上限。复杂度表达式只能使用字段参数，拿不到 Schema 中的数据，因此不做成配置项
+pub const MAX_PAGE_SIZE: i64 = 100;
+
+// 列表字段的复杂度：实际返回的条数（与 page_request 相同地限制在单页上限内）乘以子字段复杂度
+fn page_complexity(limit: Option<i64>, child_complexity: usize) -> usize {
+    let limit = limit.unwrap_or(20).clamp(1, MAX_PAGE_SIZE);
+    (limit as usize).saturating_mul(child_complexity)
+}
+
+/// 管理后台查询入口，所有字段都需要管理员身份
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// 按ID查询用户（不含已注销用户）
+    #[graphql(guard = "AdminGuard")]
+    async fn user(&self, ctx: &Context<'_>, id: Uuid) -> Result<Option<UserNode>> {
+        let user = ctx.data_unchecked::<DataLoader<UserLoader>>().load_one(id).await?;
+        Ok(user.map(UserNode))
+    }
+
+    /// 游标分页查询用户（最新注册的在前），search 按用户名、邮箱、姓名模糊匹配
+    #[graphql(guard = "AdminGuard", complexity = "page_complexity(limit, child_complexity)")]
+    async fn users(
+        &self,
+        ctx: &Context<'_>,
+        search: Option<String>,
+        cursor: Option<String>,
+        limit: Option<i64>,
+    ) -> Result<PageNode<UserNode>> {
+        use crate::database::profile::list_users_page;
+
+        let page = page_request(cursor, limit)?;
+        let search = search.as_deref().map(str::trim).filter(|search| !search.is_empty());
+        let rows = list_users_page(db(ctx), search, &page).await?;
+        let page = Page::from_rows(rows, &page, |user| Cursor::new(user.created_at, user.id));
+        Ok(PageNode::from_page(page, UserNode))
+    }
+
+    /// 游标分页查询登录记录（最新的在前），success 筛选成功或失败的记录
+    #[graphql(guard = "AdminGuard", complexity = "page_complexity(limit, child_complexity)")]
+    async fn login_logs(
+        &self,
+        ctx: &Context<'_>,
+        success: Option<bool>,
+        cursor: Option<String>,
+        limit: Option<i64>,
+    ) -> Result<PageNode<LoginLogNode>> {
+        use crate::database::login_log::list_login_logs_page;
+
+        let page = page_request(cursor, limit)?;
+        let rows = list_login_logs_page(db(ctx), success, &page).await?;
+        let page = Page::from_rows(rows, &page, |log| Cursor::new(log.created_at, log.id));
+        Ok(PageNode::from_page(page, LoginLogNode))
+    }
+
+    /// 游标分页查询用户数据（最新的在前），status 为 new / in_progress / resolved
+    #[graphql(guard = "AdminGuard", complexity = "page_complexity(limit, child_complexity)")]
+    async fn user_data(
+        &self,
+        ctx: &Context<'_>,
+        status: Option<String>,
+        cursor: Option<String>,
+        limit: Option<i64>,
+    ) -> Result<PageNode<UserDataNode>> {
+        use crate::database::list_user_data_by_status_page;
+
+        let status = match status.as_deref() {
+            None => None,
+            Some(value @ ("new" | "in_progress" | "resolved")) => Some(UserDataStatus::parse(value)),
+            Some(value) => return Err(format!("无效的处理状态: {}", value).into()),
+        };
+        let page = page_request(cursor, limit)?;
+        let rows = list_user_data_by_status_page(db(ctx), status, &page).await?;
+        let page = Page::from_rows(rows, &page, |data| Cursor::new(data.created_at, data.id));
+        Ok(PageNode::from_page(page, UserDataNode))
+    }
+}
+
+fn db<'a>(ctx: &Context<'a>) -> &'a DbPool {
+    ctx.data_unchecked::<DbPool>()
+}
+
+fn page_request(cursor: Option<String>, limit: Option<i64>) -> Result<PageRequest> {
+    Ok(PageRequest::parse(cursor.as_deref(), limit, 20, MAX_PAGE_SIZE)?)
+}
@@ -0,0 +1,244 @@
+use async_graphql::{Context, Object, OutputType, Result, SimpleObject, dataloader::DataLoader};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::config::Platform;
+use crate::models::{
+    auth::{ActiveSession, User},
+    login_log::LoginLog,
+    upload::Attachment,
+    user_data::UserData,
+};
+use crate::utils::pagination::Page;
+use super::loaders::{LoginLogLoader, MAX_LOGIN_LOGS_PER_USER, SessionLoader, UserDataByEmailLoader, UserLoader};
+
+/// 游标分页结果，与 REST 接口的 next_cursor / has_more 含义一致
+#[derive(SimpleObject)]
+#[graphql(concrete(name = "UserPage", params(UserNode)))]
+#[graphql(concrete(name = "LoginLogPage", params(LoginLogNode)))]
+#[graphql(concrete(name = "UserDataPage", params(UserDataNode)))]
+pub struct PageNode<T: OutputType> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+    pub has_more: bool,
+}
+
+impl<T: OutputType> PageNode<T> {
+    pub fn from_page<M>(page: Page<M>, node: impl Fn(M) -> T) -> Self {
+        Self {
+            items: page.items.into_iter().map(node).collect(),
+            next_cursor: page.next_cursor,
+            has_more: page.has_more,
+        }
+    }
+}
+
+/// 用户；不暴露微信 session_key 等凭证
+pub struct UserNode(pub User);
+
+#[Object(name = "User")]
+impl UserNode {
+    async fn id(&self) -> Uuid {
+        self.0.id
+    }
+
+    async fn username(&self) -> &str {
+        &self.0.username
+    }
+
+    async fn email(&self) -> &str {
+        &self.0.email
+    }
+
+    async fn full_name(&self) -> Option<&str> {
+        self.0.full_name.as_deref()
+    }
+
+    async fn avatar_url(&self) -> Option<&str> {
+        self.0.avatar_url.as_deref()
+    }
+
+    async fn is_active(&self) -> bool {
+        self.0.is_active
+    }
+
+    async fn is_admin(&self) -> bool {
+        self.0.is_admin
+    }
+
+    async fn is_guest(&self) -> bool {
+        self.0.is_guest
+    }
+
+    /// 是否绑定了微信
+    async fn has_wechat(&self) -> bool {
+        self.0.wx_openid.is_some()
+    }
+
+    async fn last_login_at(&self) -> Option<DateTime<Utc>> {
+        self.0.last_login_at
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.0.updated_at
+    }
+
+    /// 未过期的会话（不含管理员模拟登录会话），按创建时间排序
+    async fn sessions(&self, ctx: &Context<'_>) -> Result<Vec<SessionNode>> {
+        let sessions = ctx.data_unchecked::<DataLoader<SessionLoader>>().load_one(self.0.id).await?;
+        Ok(sessions.unwrap_or_default().into_iter().map(SessionNode).collect())
+    }
+
+    /// 最近的登录记录（最新的在前）
+    async fn login_logs(&self, ctx: &Context<'_>, #[graphql(default = 10)] limit: i64) -> Result<Vec<LoginLogNode>> {
+        let logs = ctx.data_unchecked::<DataLoader<LoginLogLoader>>().load_one(self.0.id).await?;
+        let limit = limit.clamp(0, MAX_LOGIN_LOGS_PER_USER) as usize;
+        Ok(logs.unwrap_or_default().into_iter().take(limit).map(LoginLogNode).collect())
+    }
+
+    /// 以该用户邮箱提交的用户数据（最新的在前）
+    async fn user_data(&self, ctx: &Context<'_>) -> Result<Vec<UserDataNode>> {
+        let data = ctx.data_unchecked::<DataLoader<UserDataByEmailLoader>>().load_one(self.0.email.to_lowercase()).await?;
+        Ok(data.unwrap_or_default().into_iter().map(UserDataNode).collect())
+    }
+}
+
+/// 用户的有效会话；不暴露会话令牌
+pub struct SessionNode(pub ActiveSession);
+
+#[Object(name = "Session")]
+impl SessionNode {
+    async fn id(&self) -> Uuid {
+        self.0.id
+    }
+
+    async fn user_agent(&self) -> Option<&str> {
+        self.0.user_agent.as_deref()
+    }
+
+    /// 按 User-Agent 识别的平台：miniprogram / h5 / admin
+    async fn platform(&self) -> &'static str {
+        Platform::from_user_agent(self.0.user_agent.as_deref().unwrap_or("unknown")).as_str()
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+}
+
+/// 一次登录尝试
+pub struct LoginLogNode(pub LoginLog);
+
+#[Object(name = "LoginLog")]
+impl LoginLogNode {
+    async fn id(&self) -> Uuid {
+        self.0.id
+    }
+
+    async fn username(&self) -> &str {
+        &self.0.username
+    }
+
+    async fn success(&self) -> bool {
+        self.0.success
+    }
+
+    async fn ip_address(&self) -> Option<String> {
+        self.0.ip_address.map(|ip| ip.to_string())
+    }
+
+    async fn user_agent(&self) -> Option<&str> {
+        self.0.user_agent.as_deref()
+    }
+
+    /// 失败原因；成功登录时为备注（如扫码登录）
+    async fn failure_reason(&self) -> Option<&str> {
+        self.0.failure_reason.as_deref()
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    /// 登录的用户；用户名不存在的失败记录为 null
+    async fn user(&self, ctx: &Context<'_>) -> Result<Option<UserNode>> {
+        let Some(user_id) = self.0.user_id else {
+            return Ok(None);
+        };
+        let user = ctx.data_unchecked::<DataLoader<UserLoader>>().load_one(user_id).await?;
+        Ok(user.map(UserNode))
+    }
+}
+
+/// 用户提交的数据
+pub struct UserDataNode(pub UserData);
+
+#[Object(name = "UserData")]
+impl UserDataNode {
+    async fn id(&self) -> Uuid {
+        self.0.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn email(&self) -> &str {
+        &self.0.email
+    }
+
+    async fn phone(&self) -> Option<&str> {
+        self.0.phone.as_deref()
+    }
+
+    async fn message(&self) -> Option<&str> {
+        self.0.message.as_deref()
+    }
+
+    /// 处理状态：new / in_progress / resolved
+    async fn status(&self) -> &'static str {
+        self.0.status.as_str()
+    }
+
+    async fn version(&self) -> i32 {
+        self.0.version
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn attachments(&self) -> Vec<AttachmentNode> {
+        self.0.attachments.iter().cloned().map(AttachmentNode).collect()
+    }
+}
+
+/// 用户数据的附件
+pub struct AttachmentNode(pub Attachment);
+
+#[Object(name = "Attachment")]
+impl AttachmentNode {
+    async fn id(&self) -> Uuid {
+        self.0.id
+    }
+
+    async fn file_name(&self) -> &str {
+        &self.0.file_name
+    }
+
+    async fn content_type(&self) -> &str {
+        &self.0.content_type
+    }
+
+    async fn file_size(&self) -> i64 {
+        self.0.file_size
+    }
+
+    async fn url(&self) -> &str {
+        &self.0.url
+    }
+}
@@ -0,0 +1,82 @@
+//! 内部 gRPC 接口：在独立端口上为其他内部服务提供会话校验和用户查询，
+//! 与 Rocket 共用缓存和数据库连接，随 Rocket 一起关闭
+
+use sha2::{Digest, Sha256};
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tonic::{Request, Status, service::Interceptor, transport::Server};
+
+use crate::use_cases::session_lookup_use_case::SessionLookupUseCase;
+
+pub mod service;
+
+/// protoc 生成的消息和服务定义（proto/internal/v1/internal.proto）
+pub mod proto {
+    tonic::include_proto!("rocket_taro.internal.v1");
+}
+
+use proto::{session_validation_server::SessionValidationServer, user_lookup_server::UserLookupServer};
+use service::{SessionValidationService, UserLookupService};
+
+/// 校验调用方携带的 `authorization: Bearer <token>`，比较摘要以避免时序差异
+#[derive(Clone)]
+pub struct BearerAuth {
+    token_digest: [u8; 32],
+}
+
+impl BearerAuth {
+    pub fn new(token: &str) -> Self {
+        Self { token_digest: Sha256::digest(token.as_bytes()).into() }
+    }
+}
+
+impl Interceptor for BearerAuth {
+    fn call(&mut self, request: Request<()>) -> Result<Request<()>, Status> {
+        let token = request.metadata().get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or_else(|| Status::unauthenticated("缺少调用令牌"))?;
+        let digest: [u8; 32] = Sha256::digest(token.as_bytes()).into();
+        if !openssl::memcmp::eq(&digest, &self.token_digest) {
+            return Err(Status::unauthenticated("调用令牌无效"));
+        }
+        Ok(request)
+    }
+}
+
+/// 启动 gRPC 服务，直到 shutdown 完成
+pub async fn serve(
+    addr: SocketAddr,
+    auth_token: &str,
+    lookup: SessionLookupUseCase,
+    shutdown: impl Future<Output = ()>,
+) -> Result<(), tonic::transport::Error> {
+    let lookup = Arc::new(lookup);
+    let auth = BearerAuth::new(auth_token);
+    Server::builder()
+        .add_service(SessionValidationServer::with_interceptor(SessionValidationService::new(lookup.clone()), auth.clone()))
+        .add_service(UserLookupServer::with_interceptor(UserLookupService::new(lookup), auth))
+        .serve_with_shutdown(addr, shutdown)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bearer_auth() {
+        let mut auth = BearerAuth::new("secret");
+
+        let mut request = Request::new(());
+        request.metadata_mut().insert("authorization", "Bearer secret".parse().unwrap());
+        assert!(auth.call(request).is_ok());
+
+        let mut request = Request::new(());
+        request.metadata_mut().insert("authorization", "Bearer other".parse().unwrap());
+        assert_eq!(auth.call(request).unwrap_err().code(), tonic::Code::Unauthenticated);
+
+        assert_eq!(auth.call(Request::new(())).unwrap_err().code(), tonic::Code::Unauthenticated);
+    }
+}
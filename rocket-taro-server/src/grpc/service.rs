@@ -0,0 +1,116 @@
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+use uuid::Uuid;
+
+use crate::cache::user::CachedUser;
+use crate::models::auth::UserSession;
+use crate::use_cases::{UseCaseError, session_lookup_use_case::{SessionLookup, SessionLookupUseCase}};
+use super::proto::{
+    self, GetUserRequest, GetUsersRequest, GetUsersResponse, ValidateSessionRequest, ValidateSessionResponse,
+    get_user_request::Key, session_validation_server::SessionValidation, user_lookup_server::UserLookup,
+};
+
+/// SessionValidation 服务
+pub struct SessionValidationService {
+    lookup: Arc<SessionLookupUseCase>,
+}
+
+impl SessionValidationService {
+    pub fn new(lookup: Arc<SessionLookupUseCase>) -> Self {
+        Self { lookup }
+    }
+}
+
+#[tonic::async_trait]
+impl SessionValidation for SessionValidationService {
+    async fn validate_session(&self, request: Request<ValidateSessionRequest>) -> Result<Response<ValidateSessionResponse>, Status> {
+        let token = request.into_inner().session_token;
+        if token.is_empty() {
+            return Err(Status::invalid_argument("session_token 不能为空"));
+        }
+
+        let response = match self.lookup.validate_session(&token).await.map_err(status)? {
+            SessionLookup::Valid { user, session } => ValidateSessionResponse {
+                valid: true,
+                reason: String::new(),
+                user: Some(user_message(*user)),
+                session: Some(session_message(&session)),
+            },
+            SessionLookup::Invalid => rejected("invalid"),
+            SessionLookup::Idle => rejected("idle"),
+        };
+        Ok(Response::new(response))
+    }
+}
+
+/// UserLookup 服务
+pub struct UserLookupService {
+    lookup: Arc<SessionLookupUseCase>,
+}
+
+impl UserLookupService {
+    pub fn new(lookup: Arc<SessionLookupUseCase>) -> Self {
+        Self { lookup }
+    }
+}
+
+#[tonic::async_trait]
+impl UserLookup for UserLookupService {
+    async fn get_user(&self, request: Request<GetUserRequest>) -> Result<Response<proto::User>, Status> {
+        let user = match request.into_inner().key {
+            Some(Key::Id(id)) => self.lookup.get_user(parse_id(&id)?).await,
+            Some(Key::Username(username)) => self.lookup.get_user_by_username(&username).await,
+            None => return Err(Status::invalid_argument("需要提供 id 或 username")),
+        };
+        match user.map_err(status)? {
+            Some(user) => Ok(Response::new(user_message(user))),
+            None => Err(Status::not_found("用户不存在")),
+        }
+    }
+
+    async fn get_users(&self, request: Request<GetUsersRequest>) -> Result<Response<GetUsersResponse>, Status> {
+        let ids = request.into_inner().ids.iter().map(|id| parse_id(id)).collect::<Result<Vec<_>, _>>()?;
+        let users = self.lookup.get_users(&ids).await.map_err(status)?;
+        Ok(Response::new(GetUsersResponse { users: users.into_iter().map(user_message).collect() }))
+    }
+}
+
+fn rejected(reason: &str) -> ValidateSessionResponse {
+    ValidateSessionResponse { valid: false, reason: reason.to_string(), user: None, session: None }
+}
+
+fn parse_id(id: &str) -> Result<Uuid, Status> {
+    Uuid::parse_str(id).map_err(|_| Status::invalid_argument(format!("无效的用户ID: {}", id)))
+}
+
+// 数据库不可用时返回 UNAVAILABLE，调用方可以重试
+fn status(error: UseCaseError) -> Status {
+    match error {
+        UseCaseError::ValidationError(msg) => Status::invalid_argument(msg),
+        UseCaseError::DatabaseError(msg) => Status::unavailable(msg),
+        error => Status::internal(error.to_string()),
+    }
+}
+
+fn user_message(user: CachedUser) -> proto::User {
+    proto::User {
+        id: user.id.to_string(),
+        username: user.username,
+        email: user.email,
+        full_name: user.full_name,
+        avatar_url: user.avatar_url,
+        is_active: user.is_active,
+        is_admin: user.is_admin,
+        is_guest: user.is_guest,
+        has_wechat: user.wx_openid.is_some(),
+    }
+}
+
+fn session_message(session: &UserSession) -> proto::Session {
+    proto::Session {
+        id: session.id.to_string(),
+        created_at: session.created_at.timestamp(),
+        expires_at: session.expires_at.timestamp(),
+        impersonator_id: session.impersonator_id.map(|id| id.to_string()),
+    }
+}
@@ -0,0 +1,98 @@
+use chrono::NaiveDate;
+use rocket::async_trait;
+use serde::Deserialize;
+use serde_json::json;
+use std::time::Duration;
+use tracing::{info, error};
+
+use crate::config::IdentityVerificationConfig;
+use crate::utils::http_client::HttpClient;
+use super::{IdentityVerifier, VerificationOutcome};
+
+/// 认证服务的响应
+#[derive(Debug, Deserialize)]
+struct VerifyResponse {
+    /// verified、rejected 或 pending
+    status: String,
+    #[serde(default)]
+    reference: Option<String>,
+    #[serde(default)]
+    reason: Option<String>,
+    #[serde(default)]
+    birth_date: Option<NaiveDate>,
+}
+
+impl VerifyResponse {
+    fn into_outcome(self) -> Result<VerificationOutcome, String> {
+        match self.status.as_str() {
+            "verified" => Ok(VerificationOutcome::Verified { birth_date: self.birth_date, reference: self.reference }),
+            "rejected" => Ok(VerificationOutcome::Rejected {
+                reason: self.reason.unwrap_or_else(|| "姓名与证件号不一致".to_string()),
+            }),
+            "pending" => self.reference
+                .map(|reference| VerificationOutcome::Pending { reference })
+                .ok_or_else(|| "Pending response without reference".to_string()),
+            other => Err(format!("Unknown verification status: {}", other)),
+        }
+    }
+}
+
+/// 通过 HTTP 调用第三方实名认证服务
+pub struct HttpIdentityVerifier {
+    config: IdentityVerificationConfig,
+    http: HttpClient,
+}
+
+impl HttpIdentityVerifier {
+    pub fn new(config: IdentityVerificationConfig, http: HttpClient) -> Self {
+        Self { config, http }
+    }
+
+    async fn parse(response: reqwest::Response) -> Result<VerificationOutcome, String> {
+        if !response.status().is_success() {
+            error!("Identity verification service returned non-success status: {}", response.status());
+            return Err(format!("Identity verification service returned error: {}", response.status()));
+        }
+        let body: VerifyResponse = response.json().await
+            .map_err(|e| format!("Invalid identity verification response: {}", e))?;
+        body.into_outcome()
+    }
+}
+
+#[async_trait]
+impl IdentityVerifier for HttpIdentityVerifier {
+    fn name(&self) -> &'static str {
+        "http"
+    }
+
+    async fn verify(&self, real_name: &str, id_number: &str) -> Result<VerificationOutcome, String> {
+        info!("Submitting identity verification");
+
+        let request = self.http
+            .post(&self.config.api_url)
+            .timeout(Duration::from_secs(self.config.request_timeout_secs))
+            .bearer_auth(&self.config.api_key)
+            .json(&json!({ "real_name": real_name, "id_number": id_number }));
+        let response = self.http.send(request)
+            .await
+            .map_err(|e| {
+                error!("HTTP request to identity verification service failed: {}", e);
+                format!("HTTP request failed: {}", e)
+            })?;
+        Self::parse(response).await
+    }
+
+    async fn query(&self, reference: &str) -> Result<VerificationOutcome, String> {
+        let request = self.http
+            .get(format!("{}/{}", self.config.api_url.trim_end_matches('/'), reference))
+            .timeout(Duration::from_secs(self.config.request_timeout_secs))
+            .bearer_auth(&self.config.api_key);
+        let response = self.http.send(request)
+            .await
+            .map_err(|e| {
+                error!("HTTP request to identity verification service failed: {}", e);
+                format!("HTTP request failed: {}", e)
+            })?;
+        Self::parse(response).await
+    }
+}
@@ -0,0 +1,25 @@
+use rocket::async_trait;
+
+use crate::models::identity::parse_resident_id;
+use super::{IdentityVerifier, VerificationOutcome};
+
+/// 只校验身份证号格式和校验位，不核对姓名，用于本地开发和测试
+pub struct MockIdentityVerifier;
+
+#[async_trait]
+impl IdentityVerifier for MockIdentityVerifier {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
+    async fn verify(&self, _real_name: &str, id_number: &str) -> Result<VerificationOutcome, String> {
+        Ok(match parse_resident_id(id_number) {
+            Some(birth_date) => VerificationOutcome::Verified { birth_date: Some(birth_date), reference: None },
+            None => VerificationOutcome::Rejected { reason: "身份证号无效".to_string() },
+        })
+    }
+
+    async fn query(&self, _reference: &str) -> Result<VerificationOutcome, String> {
+        Err("Mock identity verifier has no pending verifications".to_string())
+    }
+}
@@ -0,0 +1,48 @@
+use chrono::NaiveDate;
+use rocket::async_trait;
+use std::sync::Arc;
+use tracing::warn;
+
+use crate::config::{IdentityVerificationConfig, IdentityClientKind};
+use crate::utils::http_client::HttpClient;
+
+pub mod http_verifier;
+pub mod mock_verifier;
+
+pub use http_verifier::HttpIdentityVerifier;
+pub use mock_verifier::MockIdentityVerifier;
+
+/// 实名认证服务返回的结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerificationOutcome {
+    /// 姓名与证件号一致；服务未返回出生日期时由证件号推算
+    Verified { birth_date: Option<NaiveDate>, reference: Option<String> },
+    /// 姓名与证件号不一致或证件无效
+    Rejected { reason: String },
+    /// 服务异步核验，稍后用 reference 查询结果
+    Pending { reference: String },
+}
+
+/// 第三方实名认证服务接口
+#[async_trait]
+pub trait IdentityVerifier: Send + Sync {
+    /// 服务名称，记录在认证记录中
+    fn name(&self) -> &'static str;
+
+    /// 核验姓名与证件号
+    async fn verify(&self, real_name: &str, id_number: &str) -> Result<VerificationOutcome, String>;
+
+    /// 查询异步核验的结果
+    async fn query(&self, reference: &str) -> Result<VerificationOutcome, String>;
+}
+
+/// 按配置选择认证服务实现，作为 Rocket 托管状态共享
+pub fn verifier_from_config(config: &IdentityVerificationConfig, http: &HttpClient) -> Arc<dyn IdentityVerifier> {
+    match config.client {
+        IdentityClientKind::Http => Arc::new(HttpIdentityVerifier::new(config.clone(), http.clone())),
+        IdentityClientKind::Mock => {
+            warn!("Mock identity verifier enabled, only the ID number checksum is verified");
+            Arc::new(MockIdentityVerifier)
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use rocket::async_trait;
+use serde_json::json;
+use std::time::Duration;
+use tracing::{info, error};
+
+use crate::config::MailConfig;
+use crate::utils::http_client::HttpClient;
+use super::{MailMessage, Mailer};
+
+/// 通过 HTTP 邮件网关发送邮件
+pub struct HttpMailer {
+    config: MailConfig,
+    http: HttpClient,
+}
+
+impl HttpMailer {
+    pub fn new(config: MailConfig, http: HttpClient) -> Self {
+        Self { config, http }
+    }
+}
+
+#[async_trait]
+impl Mailer for HttpMailer {
+    async fn send(&self, message: &MailMessage) -> Result<(), String> {
+        info!(subject = %message.subject, "Sending email via mail gateway");
+
+        let request = self.http
+            .post(&self.config.api_url)
+            .timeout(Duration::from_secs(self.config.request_timeout_secs))
+            .bearer_auth(&self.config.api_key)
+            .json(&json!({
+                "from": self.config.from,
+                "to": message.to,
+                "subject": message.subject,
+                "text": message.text,
+            }));
+        // 邮件网关的发送接口不是幂等的，只在连接失败时重试
+        let response = self.http.send(request)
+            .await
+            .map_err(|e| {
+                error!("HTTP request to mail gateway failed: {}", e);
+                format!("HTTP request failed: {}", e)
+            })?;
+
+        if !response.status().is_success() {
+            error!("Mail gateway returned non-success status: {}", response.status());
+            return Err(format!("Mail gateway returned error: {}", response.status()));
+        }
+        Ok(())
+    }
+}
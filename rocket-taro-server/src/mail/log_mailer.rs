@@ -0,0 +1,15 @@
+use rocket::async_trait;
+use tracing::info;
+
+use super::{MailMessage, Mailer};
+
+/// 只把邮件内容写入日志，用于本地开发和测试
+pub struct LogMailer;
+
+#[async_trait]
+impl Mailer for LogMailer {
+    async fn send(&self, message: &MailMessage) -> Result<(), String> {
+        info!(to = %message.to, subject = %message.subject, text = %message.text, "Email (log mailer)");
+        Ok(())
+    }
+}
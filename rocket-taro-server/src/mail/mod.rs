@@ -0,0 +1,37 @@
+use rocket::async_trait;
+use std::sync::Arc;
+use tracing::warn;
+
+use crate::config::{MailConfig, MailClientKind};
+use crate::utils::http_client::HttpClient;
+
+pub mod http_mailer;
+pub mod log_mailer;
+
+pub use http_mailer::HttpMailer;
+pub use log_mailer::LogMailer;
+
+/// 待发送的纯文本邮件
+#[derive(Debug, Clone)]
+pub struct MailMessage {
+    pub to: String,
+    pub subject: String,
+    pub text: String,
+}
+
+/// 邮件发送接口
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send(&self, message: &MailMessage) -> Result<(), String>;
+}
+
+/// 按配置选择发送客户端实现，作为 Rocket 托管状态共享
+pub fn mailer_from_config(config: MailConfig, http: &HttpClient) -> Arc<dyn Mailer> {
+    match config.client {
+        MailClientKind::Http => Arc::new(HttpMailer::new(config, http.clone())),
+        MailClientKind::Log => {
+            warn!("Log mailer enabled, emails are written to the log instead of being sent");
+            Arc::new(LogMailer)
+        }
+    }
+}
@@ -11,19 +11,39 @@ mod cache;
 mod use_cases;
 mod config;
 mod utils;
+mod scheduler;
+mod payments;
+mod webhooks;
+mod events;
+mod wechat;
+mod dev_mock;
+mod metrics;
+mod push;
+mod mail;
+mod identity;
+mod moderation;
+mod storage;
+mod graphql;
+mod grpc;
+mod telemetry;
+mod self_check;
 
 use rocket::fs::{FileServer, relative};
-use tracing_subscriber;
-use config::RouteConfig;
+use config::{RouteConfig, AccountConfig, DataExportConfig, WechatPayConfig, OrderConfig, WebhookConfig, SideEffectConfig, PasswordConfig, WechatConfig, DevMockConfig, DatabaseConfig, AccountFlagsConfig, MaintenanceConfig, SessionExpiryConfig, IpAccessConfig, BodyLimitsConfig, RequestLogConfig, MailConfig, EmailChangeConfig, QrLoginConfig, SessionLimitsConfig, RecentAuthConfig, UserDataConfig, UploadConfig, LogArchiveConfig, SessionBindingConfig, ImpersonationConfig, CacheWarmupConfig, ResponseProfileConfig, BatchConfig, GraphqlConfig, GrpcConfig, TelemetryConfig, SloConfig, StartupCheckConfig, CookieConfig, HttpClientConfig, LoginTimeoutConfig, ConcurrencyLimitsConfig, PresenceConfig, ApiQuotasConfig, RegistrationGuardConfig, IdentityVerificationConfig, ModerationConfig, AvatarConfig, StorageConfig, RouteTraceConfig, RouteCommandMetadataConfig, ExperimentsConfig, ProxyConfig, TrustedProxies};
+use use_cases::{account_flags::AccountFlagPipeline, batch_use_case::BatchUseCase};
+use scheduler::{Scheduler, account_anonymization::AccountAnonymizationJob, data_export::DataExportJob, order_expiry::OrderExpiryJob, webhook_delivery::WebhookDeliveryJob, side_effect_dispatch::SideEffectDispatchJob, session_expiry_warning::SessionExpiryWarningJob, ip_access_reload::IpAccessReloadJob, upload_cleanup::UploadCleanupJob, log_archive::LogArchiveJob, slo_evaluation::SloEvaluationJob, moderation_recheck::ModerationRecheckJob, avatar_processing::AvatarProcessingJob, storage_cleanup::StorageCleanupJob, route_decision_cleanup::RouteDecisionCleanupJob};
 
 #[launch]
 async fn rocket() -> _ {
-    // 初始化日志系统
-    tracing_subscriber::fmt::init();
-    
-    // 初始化数据库连接
-    let db_pool = database::create_connection().await
-        .expect("Failed to connect to database");
+    // 初始化日志系统和链路追踪导出
+    let tracer_provider = telemetry::init(&TelemetryConfig::from_figment(&rocket::Config::figment()));
+
+    // 启动自检：命令行 --check 检查配置、数据库、Redis 和微信凭据后退出，有失败项时退出码为 1
+    if std::env::args().any(|arg| arg == "--check") {
+        let report = self_check::SelfCheck::from_figment(&rocket::Config::figment()).run().await;
+        println!("{}", report);
+        std::process::exit(if report.passed() { 0 } else { 1 });
+    }
     
     // 初始化路由配置
     let route_config = RouteConfig::from_file("routes.toml")
@@ -33,35 +53,317 @@ async fn rocket() -> _ {
     route_config.validate()
         .expect("Route configuration validation failed");
 
+    // 前端开发模拟模式：不连接数据库、缓存和微信
+    let dev_mock_config = DevMockConfig::from_figment(&rocket::Config::figment());
+    let rocket = if dev_mock_config.enabled {
+        dev_mock::build(dev_mock_config, route_config)
+    } else {
+        build_rocket(route_config).await
+    };
+    match tracer_provider {
+        Some(provider) => rocket.attach(fairings::telemetry::TelemetryShutdown::new(provider)),
+        None => rocket,
+    }
+}
+
+async fn build_rocket(route_config: RouteConfig) -> rocket::Rocket<rocket::Build> {
+    // 运行时长从这里开始计算，包含连接数据库和缓存的耗时
+    let runtime_stats = metrics::runtime_stats::RuntimeStats::new();
+
+    // 初始化数据库连接
+    let database_config = DatabaseConfig::from_figment(&rocket::Config::figment());
+    let db_health = database::DbHealth::default();
+    let metrics = metrics::MetricsRegistry::new();
+    let db_pool = database::create_connection(&database_config, db_health.clone(), metrics.clone()).await
+        .expect("Failed to connect to database");
+
+    let account_config = AccountConfig::from_figment(&rocket::Config::figment());
+    let data_export_config = DataExportConfig::from_figment(&rocket::Config::figment());
+    let order_config = OrderConfig::from_figment(&rocket::Config::figment());
+    let webhook_config = WebhookConfig::from_figment(&rocket::Config::figment());
+    let side_effect_config = SideEffectConfig::from_figment(&rocket::Config::figment());
+    let password_hasher = auth::PasswordHasher::new(PasswordConfig::from_figment(&rocket::Config::figment()));
+    let flag_pipeline = AccountFlagPipeline::from_config(&AccountFlagsConfig::from_figment(&rocket::Config::figment()), db_pool.clone());
+
+    // 初始管理员：命令行 --create-initial-admin 创建后退出，环境变量 CREATE_INITIAL_ADMIN 创建后继续启动
+    let create_admin_and_exit = std::env::args().any(|arg| arg == "--create-initial-admin");
+    if create_admin_and_exit || env_flag("CREATE_INITIAL_ADMIN") {
+        let created = create_initial_admin(&db_pool, &password_hasher).await;
+        if create_admin_and_exit {
+            std::process::exit(if created { 0 } else { 1 });
+        }
+    }
+    // 微信接口、微信支付、Webhook 和邮件共用的对外 HTTP 客户端
+    let http_client = utils::http_client::HttpClient::from_config(HttpClientConfig::from_figment(&rocket::Config::figment()));
+    let wechat_config = WechatConfig::from_figment(&rocket::Config::figment());
+    let watermark_config = wechat_config.watermark.clone();
+    let moderation_config = ModerationConfig::from_figment(&rocket::Config::figment());
+    let moderator = moderation::moderator_from_config(&moderation_config, wechat_config.clone(), &http_client);
+    let wx_api = wechat::client_from_config(wechat_config, &http_client);
+    let identity_config = IdentityVerificationConfig::from_figment(&rocket::Config::figment());
+    let wechat_pay = payments::WechatPayClient::from_config(WechatPayConfig::from_figment(&rocket::Config::figment()), http_client.clone());
+    let ip_access_config = IpAccessConfig::from_figment(&rocket::Config::figment());
+    let ip_access = fairings::ip_access::IpAccessList::new(&ip_access_config);
+    let upload_config = UploadConfig::from_figment(&rocket::Config::figment());
+    let avatar_config = AvatarConfig::from_figment(&rocket::Config::figment());
+    let storage_config = StorageConfig::from_figment(&rocket::Config::figment());
+    let storage = storage::storage_from_config(&storage_config, &http_client);
+    let route_trace_config = RouteTraceConfig::from_figment(&rocket::Config::figment());
+    let session_expiry_config = SessionExpiryConfig::from_figment(&rocket::Config::figment());
+    let log_archive_config = LogArchiveConfig::from_figment(&rocket::Config::figment());
+    let graphql_config = GraphqlConfig::from_figment(&rocket::Config::figment());
+    let batch = BatchUseCase::from_config(BatchConfig::from_figment(&rocket::Config::figment()), Some(metrics.clone()));
+    let slo_config = SloConfig::from_figment(&rocket::Config::figment());
+    let slo = metrics::slo::SloTracker::new(slo_config.clone(), metrics.clone());
+    let api_quotas_config = ApiQuotasConfig::from_figment(&rocket::Config::figment());
+    let login_timeouts = use_cases::login_timeouts::LoginTimeouts::new(LoginTimeoutConfig::from_figment(&rocket::Config::figment()), metrics.clone());
+
     rocket::build()
         .manage(db_pool)
+        .manage(db_health)
+        .manage(metrics)
+        .manage(slo.clone())
+        .manage(login_timeouts)
+        .manage(runtime_stats.clone())
+        .manage(CookieConfig::from_figment(&rocket::Config::figment()))
+        .manage(TrustedProxies::from_config(&ProxyConfig::from_figment(&rocket::Config::figment())))
         .manage(route_config)
-        .mount("/api", routes![
+        .manage(route_trace_config.clone())
+        .manage(RouteCommandMetadataConfig::from_figment(&rocket::Config::figment()))
+        .manage(ExperimentsConfig::from_figment(&rocket::Config::figment()))
+        .manage(account_config.clone())
+        .manage(data_export_config.clone())
+        .manage(storage_config.clone())
+        .manage(storage.clone())
+        .manage(order_config.clone())
+        .manage(webhook_config.clone())
+        .manage(password_hasher)
+        .manage(flag_pipeline)
+        .manage(http_client.clone())
+        .manage(wx_api)
+        .manage(watermark_config)
+        .manage(wechat_pay)
+        .manage(mail::mailer_from_config(MailConfig::from_figment(&rocket::Config::figment()), &http_client))
+        .manage(EmailChangeConfig::from_figment(&rocket::Config::figment()))
+        .manage(QrLoginConfig::from_figment(&rocket::Config::figment()))
+        .manage(RecentAuthConfig::from_figment(&rocket::Config::figment()))
+        .manage(PresenceConfig::from_figment(&rocket::Config::figment()))
+        .manage(api_quotas_config.clone())
+        .manage(RegistrationGuardConfig::from_figment(&rocket::Config::figment()))
+        .manage(identity::verifier_from_config(&identity_config, &http_client))
+        .manage(identity_config)
+        .manage(moderator.clone())
+        .manage(moderation_config.clone())
+        .manage(UserDataConfig::from_figment(&rocket::Config::figment()))
+        .manage(upload_config.clone())
+        .manage(avatar_config.clone())
+        .manage(session_expiry_config.clone())
+        .manage(SessionBindingConfig::from_figment(&rocket::Config::figment()))
+        .manage(log_archive_config.clone())
+        .manage(ImpersonationConfig::from_figment(&rocket::Config::figment()))
+        .manage(push::PushHub::new())
+        .manage(batch)
+        .manage(graphql::build_schema(&graphql_config))
+        .manage(graphql_config)
+        .mount("/api", telemetry::traced(routes![
             routes::api::health_check,
             routes::api::get_user,
             routes::api::get_data,
             routes::api::get_public_config,
-        ])
-        .mount("/", routes![
+        ]))
+        .mount("/", telemetry::traced(routes![
+            routes::api::readiness,
+            routes::batch::batch,
+            routes::graphql::graphql_query,
             routes::user_data::create_user_data,
             routes::user_data::get_user_data,
+            routes::user_data::get_user_data_page,
+            routes::user_data::search_user_data_route,
+            routes::user_data_reply::list_own_user_data,
+            routes::user_data_reply::get_own_user_data_thread,
+            routes::user_data_reply::get_user_data_thread,
+            routes::user_data_reply::reply_user_data,
+            routes::user_data_reply::update_user_data_status,
+            routes::upload::upload_file,
+            routes::upload::download_upload,
+            routes::avatar::upload_avatar,
+            routes::avatar::get_avatar_status,
+            routes::avatar::get_avatar,
+            routes::storage::download_stored_file,
             routes::auth::login,
             routes::auth::register,
             routes::auth::logout,
+            routes::auth::extend_session,
+            routes::auth::delete_account,
+            routes::auth::request_data_export,
+            routes::auth::download_data_export,
             routes::auth::get_current_user,
+            routes::auth::list_sessions,
             routes::auth::auth_status,
             routes::auth::guest_login,
             routes::auth::wx_login,
             routes::auth::update_user_profile,
+            routes::auth::patch_user_profile,
+            routes::auth::check_availability,
+            routes::email_change::request_email_change,
+            routes::email_change::get_email_change,
+            routes::email_change::cancel_email_change,
+            routes::email_change::confirm_email_change,
+            routes::account_recovery::reset_password,
+            routes::qr_login::create_qr_login,
+            routes::qr_login::poll_qr_login,
+            routes::qr_login::qr_login_stream,
+            routes::qr_login::scan_qr_login,
+            routes::qr_login::confirm_qr_login,
+            routes::reauth::reauthenticate,
+            routes::profile::get_profile_completion,
+            routes::profile::update_profile_step,
             routes::cache::cache_health_check,
             routes::cache::invalidate_cache,
+            routes::cache::invalidate_user_cache,
+            routes::cache::invalidate_username_cache,
+            routes::cache::invalidate_session_cache,
+            routes::cache::invalidate_user_data_cache,
+            routes::cache::invalidate_cache_category,
             routes::cache::cleanup_expired_sessions,
             routes::metrics::receive_route_command_error_metric,
+            routes::metrics::acknowledge_route_command,
+            routes::capabilities::declare_capabilities,
+            routes::capabilities::get_capabilities,
             routes::metrics::receive_performance_metric,
-            routes::metrics::get_system_health
-        ])
+            routes::metrics::get_system_health,
+            routes::metrics::get_metrics,
+            routes::metrics::get_slo_report,
+            routes::remote_config::list_remote_configs,
+            routes::remote_config::set_remote_config,
+            routes::remote_config::delete_remote_config,
+            routes::notification::get_notifications,
+            routes::notification::read_notification,
+            routes::admin::get_admin_stats,
+            routes::admin::get_index_usage,
+            routes::admin::get_presence,
+            routes::admin::list_quota_overrides,
+            routes::admin::set_quota_override,
+            routes::admin::delete_quota_override,
+            routes::admin::list_registration_reviews,
+            routes::admin::decide_registration_review,
+            routes::admin::list_moderation_checks,
+            routes::admin::list_storage_usage,
+            routes::admin::get_user_storage_usage,
+            routes::admin::export_user_data,
+            routes::history::get_row_history,
+            routes::admin::preview_route_command,
+            routes::admin::get_route_decisions,
+            routes::admin::get_experiments,
+            routes::admin::get_route_command_completion,
+            routes::admin::run_log_archive,
+            routes::impersonation::impersonate_user,
+            routes::maintenance::get_maintenance_status,
+            routes::maintenance::maintenance_blocked,
+            routes::access::ip_access_blocked,
+            routes::limits::payload_too_large,
+            routes::limits::overloaded,
+            routes::limits::quota_exceeded,
+            routes::maintenance::get_admin_maintenance,
+            routes::maintenance::update_maintenance,
+            routes::announcement::list_announcements,
+            routes::announcement::create_announcement,
+            routes::announcement::update_announcement,
+            routes::announcement::delete_announcement,
+            routes::announcement::dismiss_announcement,
+            routes::consent::get_active_policy,
+            routes::consent::get_pending_consents,
+            routes::consent::list_consents,
+            routes::consent::accept_consents,
+            routes::consent::list_policies,
+            routes::consent::create_policy,
+            routes::consent::activate_policy,
+            routes::identity::get_identity_verification,
+            routes::identity::submit_identity_verification,
+            routes::push::push_stream,
+            routes::settings::get_settings,
+            routes::settings::update_settings,
+            routes::drafts::save_draft,
+            routes::drafts::get_draft,
+            routes::drafts::delete_draft,
+            routes::payment::create_payment,
+            routes::payment::get_payment,
+            routes::payment::wechat_pay_notify,
+            routes::order::create_order,
+            routes::order::list_orders,
+            routes::order::get_order,
+            routes::order::cancel_order,
+            routes::order::pay_order,
+            routes::webhook::list_webhook_endpoints,
+            routes::webhook::create_webhook_endpoint,
+            routes::webhook::update_webhook_endpoint,
+            routes::webhook::delete_webhook_endpoint,
+            routes::webhook::rotate_webhook_secret,
+            routes::webhook::list_webhook_deliveries,
+            routes::webhook::get_webhook_delivery,
+            routes::webhook::retry_webhook_delivery,
+        ]))
         .mount("/", routes::cors::cors_routes())
+        .register("/", catchers![routes::limits::payload_too_large_catcher, routes::reauth::reauth_required_catcher, routes::impersonation::forbidden_catcher, routes::consent::consent_required_catcher])
         .mount("/", FileServer::from(relative!("frontend/dist")))
+        .attach(fairings::startup_check::StartupCheck::new(StartupCheckConfig::from_figment(&rocket::Config::figment())))
         .attach(fairings::cors::CORS)
+        .attach(fairings::route_command_metadata::RouteCommandMetadataStamp)
+        .attach(fairings::capabilities::RouteCommandCapabilities)
+        .attach(fairings::response_profile::ResponseShaping::new(ResponseProfileConfig::from_figment(&rocket::Config::figment())))
+        .attach(fairings::request_log::RequestLogger::new(RequestLogConfig::from_figment(&rocket::Config::figment())))
+        .attach(fairings::slo::SloRecorder::new(slo.clone()))
+        .attach(fairings::runtime_stats::RequestCounter::new(runtime_stats))
+        .attach(fairings::impersonation_audit::ImpersonationAudit)
+        .attach(fairings::maintenance::MaintenanceMode::new(MaintenanceConfig::from_figment(&rocket::Config::figment())))
+        .attach(fairings::ip_access::IpAccessControl::new(ip_access.clone()))
+        .attach(fairings::body_limits::BodyLimits::new(BodyLimitsConfig::from_figment(&rocket::Config::figment())))
+        .attach(fairings::concurrency::ConcurrencyLimiter::new(ConcurrencyLimitsConfig::from_figment(&rocket::Config::figment())))
+        .attach(fairings::api_quota::ApiQuotaLimiter::new(api_quotas_config))
         .attach(cache::CacheFairing)
+        .attach(fairings::cache_warmup::CacheWarmup::new(CacheWarmupConfig::from_figment(&rocket::Config::figment())))
+        .attach(events::EventBusFairing::new(webhook_config.clone(), side_effect_config.clone(), SessionLimitsConfig::from_figment(&rocket::Config::figment())))
+        .attach(Scheduler::new()
+            .register(AccountAnonymizationJob::new(account_config))
+            .register(DataExportJob::new(data_export_config, storage.clone(), storage_config.clone()))
+            .register(OrderExpiryJob::new(order_config))
+            .register(WebhookDeliveryJob::new(webhook_config, http_client))
+            .register(SideEffectDispatchJob::new(side_effect_config))
+            .register(SessionExpiryWarningJob::new(session_expiry_config))
+            .register(IpAccessReloadJob::new(ip_access, &ip_access_config))
+            .register(UploadCleanupJob::new(upload_config))
+            .register(LogArchiveJob::new(log_archive_config))
+            .register(SloEvaluationJob::new(slo, &slo_config))
+            .register(ModerationRecheckJob::new(moderator.clone(), moderation_config.clone()))
+            .register(AvatarProcessingJob::new(avatar_config, moderator, moderation_config))
+            .register(StorageCleanupJob::new(storage, storage_config))
+            .register(RouteDecisionCleanupJob::new(route_trace_config)))
+        .attach(fairings::grpc::GrpcServer::new(GrpcConfig::from_figment(&rocket::Config::figment())))
+}
+
+fn env_flag(name: &str) -> bool {
+    std::env::var(name).is_ok_and(|value| matches!(value.trim(), "1" | "true"))
+}
+
+/// 创建初始管理员，随机密码只在此处输出一次
+async fn create_initial_admin(db_pool: &database::DbPool, password_hasher: &auth::PasswordHasher) -> bool {
+    use use_cases::bootstrap_use_case::BootstrapUseCase;
+
+    let username = std::env::var("INITIAL_ADMIN_USERNAME").unwrap_or_else(|_| "admin".to_string());
+    let email = std::env::var("INITIAL_ADMIN_EMAIL").unwrap_or_else(|_| "admin@rocket-taro.com".to_string());
+
+    match BootstrapUseCase::new(db_pool.clone(), password_hasher.clone()).create_initial_admin(&username, &email).await {
+        Ok(admin) => {
+            println!("========================================");
+            println!("初始管理员账户已创建，密码仅显示这一次，请妥善保存");
+            println!("用户名: {}", admin.user.username);
+            println!("密码: {}", admin.password);
+            println!("========================================");
+            true
+        }
+        Err(e) => {
+            tracing::warn!("Initial admin not created: {}", e);
+            false
+        }
+    }
 }
\ No newline at end of file
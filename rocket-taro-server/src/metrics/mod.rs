@@ -0,0 +1,55 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+pub mod slo;
+pub mod runtime_stats;
+
+/// 进程内计数器注册表，各模块按名称累加，管理接口读取快照
+#[derive(Clone, Default)]
+pub struct MetricsRegistry {
+    counters: Arc<Mutex<BTreeMap<&'static str, u64>>>,
+}
+
+impl MetricsRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 计数器加一
+    pub fn increment(&self, name: &'static str) {
+        *self.counters.lock().unwrap().entry(name).or_insert(0) += 1;
+    }
+
+    /// 读取计数器当前值，未记录过时为 0
+    pub fn get(&self, name: &str) -> u64 {
+        self.counters.lock().unwrap().get(name).copied().unwrap_or(0)
+    }
+
+    /// 全部计数器的快照，按名称排序
+    pub fn snapshot(&self) -> BTreeMap<String, u64> {
+        self.counters.lock().unwrap()
+            .iter()
+            .map(|(name, value)| (name.to_string(), *value))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counters() {
+        let metrics = MetricsRegistry::new();
+        assert_eq!(metrics.get("db.slow_queries"), 0);
+
+        metrics.increment("db.slow_queries");
+        metrics.clone().increment("db.slow_queries");
+        metrics.increment("db.query_timeouts");
+
+        assert_eq!(metrics.get("db.slow_queries"), 2);
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.len(), 2);
+        assert_eq!(snapshot["db.query_timeouts"], 1);
+    }
+}
@@ -0,0 +1,158 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// 构建时的 git 提交，由 build.rs 写入；无法获取时为 None
+pub const GIT_COMMIT_HASH: Option<&str> = option_env!("GIT_COMMIT_HASH");
+
+/// 在线会话数估算的缓存时长，避免每次健康检查都查询数据库
+pub const ACTIVE_SESSIONS_TTL: Duration = Duration::from_secs(60);
+
+/// 进程运行状态：启动时间、请求计数和在线会话数估算
+#[derive(Clone)]
+pub struct RuntimeStats {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    started_at: DateTime<Utc>,
+    started_instant: Instant,
+    requests_total: AtomicU64,
+    server_errors: AtomicU64,
+    active_sessions: Mutex<Option<(Instant, i64)>>,
+}
+
+/// 对外展示的运行状态快照
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuntimeSnapshot {
+    pub started_at: DateTime<Utc>,
+    pub uptime_secs: u64,
+    /// 便于阅读的运行时长，如 "2天3小时15分钟"
+    pub uptime: String,
+    pub version: String,
+    pub git_hash: Option<String>,
+    /// 启动以来处理的请求数
+    pub requests_total: u64,
+    /// 启动以来返回 5xx 的请求数
+    pub server_errors: u64,
+    /// 未过期的会话数，最多缓存一分钟；查询失败时为 None
+    pub active_sessions: Option<i64>,
+    /// 常驻内存（字节），非 Linux 平台为 None
+    pub memory_rss_bytes: Option<u64>,
+}
+
+impl Default for RuntimeStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RuntimeStats {
+    /// 以当前时间作为进程启动时间
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                started_at: Utc::now(),
+                started_instant: Instant::now(),
+                requests_total: AtomicU64::new(0),
+                server_errors: AtomicU64::new(0),
+                active_sessions: Mutex::new(None),
+            }),
+        }
+    }
+
+    /// 记录一个已完成的请求
+    pub fn record_request(&self, status: u16) {
+        self.inner.requests_total.fetch_add(1, Ordering::Relaxed);
+        if status >= 500 {
+            self.inner.server_errors.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub fn uptime(&self) -> Duration {
+        self.inner.started_instant.elapsed()
+    }
+
+    /// 未过期的缓存会话数
+    pub fn cached_active_sessions(&self, now: Instant) -> Option<i64> {
+        self.inner.active_sessions.lock().unwrap()
+            .filter(|(updated_at, _)| now.duration_since(*updated_at) < ACTIVE_SESSIONS_TTL)
+            .map(|(_, count)| count)
+    }
+
+    pub fn set_active_sessions(&self, count: i64, now: Instant) {
+        *self.inner.active_sessions.lock().unwrap() = Some((now, count));
+    }
+
+    /// 生成快照，在线会话数由调用方查询后传入
+    pub fn snapshot(&self, active_sessions: Option<i64>) -> RuntimeSnapshot {
+        let uptime_secs = self.uptime().as_secs();
+        RuntimeSnapshot {
+            started_at: self.inner.started_at,
+            uptime_secs,
+            uptime: format_uptime(uptime_secs),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            git_hash: GIT_COMMIT_HASH.map(str::to_string),
+            requests_total: self.inner.requests_total.load(Ordering::Relaxed),
+            server_errors: self.inner.server_errors.load(Ordering::Relaxed),
+            active_sessions,
+            memory_rss_bytes: memory_rss_bytes(),
+        }
+    }
+}
+
+/// 运行时长格式化为 "X天X小时X分钟"，不足一分钟时显示秒
+pub fn format_uptime(secs: u64) -> String {
+    let (days, hours, minutes) = (secs / 86400, secs % 86400 / 3600, secs % 3600 / 60);
+    match (days, hours, minutes) {
+        (0, 0, 0) => format!("{}秒", secs),
+        (0, 0, _) => format!("{}分钟", minutes),
+        (0, _, _) => format!("{}小时{}分钟", hours, minutes),
+        _ => format!("{}天{}小时{}分钟", days, hours, minutes),
+    }
+}
+
+/// 从 /proc/self/status 读取 VmRSS
+fn memory_rss_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    parse_vm_rss(&status)
+}
+
+fn parse_vm_rss(status: &str) -> Option<u64> {
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let kb: u64 = line.trim_start_matches("VmRSS:").trim().trim_end_matches("kB").trim().parse().ok()?;
+    Some(kb * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_uptime() {
+        assert_eq!(format_uptime(42), "42秒");
+        assert_eq!(format_uptime(125), "2分钟");
+        assert_eq!(format_uptime(3 * 3600 + 60), "3小时1分钟");
+        assert_eq!(format_uptime(2 * 86400 + 3 * 3600 + 15 * 60), "2天3小时15分钟");
+    }
+
+    #[test]
+    fn test_counters_and_session_cache() {
+        let stats = RuntimeStats::new();
+        stats.record_request(200);
+        stats.clone().record_request(503);
+        let snapshot = stats.snapshot(None);
+        assert_eq!(snapshot.requests_total, 2);
+        assert_eq!(snapshot.server_errors, 1);
+
+        let now = Instant::now();
+        assert_eq!(stats.cached_active_sessions(now), None);
+        stats.set_active_sessions(12, now);
+        assert_eq!(stats.cached_active_sessions(now), Some(12));
+        assert_eq!(stats.cached_active_sessions(now + ACTIVE_SESSIONS_TTL), None);
+
+        assert_eq!(parse_vm_rss("Name:\tserver\nVmRSS:\t   2048 kB\n"), Some(2048 * 1024));
+    }
+}
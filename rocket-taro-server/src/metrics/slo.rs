@@ -0,0 +1,344 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use tracing::{info, warn};
+
+use crate::config::{SloConfig, SloObjective};
+use super::MetricsRegistry;
+
+/// 燃烧率告警触发次数
+pub const SLO_BURN_RATE_ALERTS: &str = "slo.burn_rate_alerts";
+
+/// 一个统计桶内的请求数
+#[derive(Debug, Clone, Copy, Default)]
+struct Bucket {
+    start: i64,
+    total: u64,
+    errors: u64,
+    slow: u64,
+}
+
+/// 窗口内的请求数合计
+#[derive(Debug, Clone, Copy, Default)]
+struct Totals {
+    total: u64,
+    errors: u64,
+    slow: u64,
+}
+
+impl Totals {
+    fn error_ratio(&self) -> f64 {
+        ratio(self.errors, self.total)
+    }
+
+    fn slow_ratio(&self) -> f64 {
+        ratio(self.slow, self.total)
+    }
+}
+
+fn ratio(part: u64, total: u64) -> f64 {
+    if total == 0 { 0.0 } else { part as f64 / total as f64 }
+}
+
+/// 燃烧率：坏请求比例与错误预算（1 - 目标）之比，1 表示窗口结束时刚好用完预算
+fn burn_rate(bad_ratio: f64, target: f64) -> f64 {
+    bad_ratio / (1.0 - target).max(1e-6)
+}
+
+struct ObjectiveState {
+    objective: SloObjective,
+    buckets: VecDeque<Bucket>,
+    series: VecDeque<BurnRatePoint>,
+    firing: Vec<String>,
+}
+
+impl ObjectiveState {
+    fn totals(&self, now: i64, window_secs: u64) -> Totals {
+        let since = now - window_secs as i64;
+        let mut totals = Totals::default();
+        for bucket in self.buckets.iter().rev().take_while(|bucket| bucket.start > since) {
+            totals.total += bucket.total;
+            totals.errors += bucket.errors;
+            totals.slow += bucket.slow;
+        }
+        totals
+    }
+
+    fn burn_rate(&self, now: i64, window_secs: u64) -> BurnRate {
+        let totals = self.totals(now, window_secs);
+        BurnRate {
+            window_secs,
+            availability: burn_rate(totals.error_ratio(), self.objective.availability_target),
+            latency: burn_rate(totals.slow_ratio(), self.objective.latency_target),
+        }
+    }
+}
+
+/// 某个窗口内的可用性和延迟燃烧率
+#[derive(Debug, Clone, Serialize)]
+pub struct BurnRate {
+    pub window_secs: u64,
+    pub availability: f64,
+    pub latency: f64,
+}
+
+/// 燃烧率序列中的一个点（窗口为 series_window_secs）
+#[derive(Debug, Clone, Serialize)]
+pub struct BurnRatePoint {
+    pub at: DateTime<Utc>,
+    pub availability: f64,
+    pub latency: f64,
+}
+
+/// 一个路由组的达标情况
+#[derive(Debug, Serialize)]
+pub struct ObjectiveReport {
+    pub name: String,
+    pub path_prefixes: Vec<String>,
+    pub methods: Vec<String>,
+    pub window_secs: u64,
+    pub availability_target: f64,
+    pub latency_threshold_ms: u64,
+    pub latency_target: f64,
+    pub total_requests: u64,
+    pub error_requests: u64,
+    pub slow_requests: u64,
+    /// 窗口内没有请求时为 null
+    pub availability: Option<f64>,
+    pub latency_compliance: Option<f64>,
+    /// 窗口内剩余的错误预算比例，负数表示已超支
+    pub availability_budget_remaining: f64,
+    pub latency_budget_remaining: f64,
+    pub compliant: bool,
+    pub burn_rates: Vec<BurnRate>,
+    pub firing_alerts: Vec<String>,
+    pub series: Vec<BurnRatePoint>,
+}
+
+/// SLO 汇总
+#[derive(Debug, Serialize)]
+pub struct SloReport {
+    pub enabled: bool,
+    pub generated_at: DateTime<Utc>,
+    pub objectives: Vec<ObjectiveReport>,
+}
+
+/// 按路由组统计请求的可用性和延迟，计算滚动达标率和燃烧率；数据只保存在进程内，重启后重新统计
+#[derive(Clone)]
+pub struct SloTracker {
+    config: Arc<SloConfig>,
+    state: Arc<Mutex<Vec<ObjectiveState>>>,
+    metrics: MetricsRegistry,
+}
+
+impl SloTracker {
+    pub fn new(config: SloConfig, metrics: MetricsRegistry) -> Self {
+        let objectives = if config.enabled { config.objectives.clone() } else { Vec::new() };
+        let state = objectives.into_iter()
+            .map(|objective| ObjectiveState { objective, buckets: VecDeque::new(), series: VecDeque::new(), firing: Vec::new() })
+            .collect();
+        Self { config: Arc::new(config), state: Arc::new(Mutex::new(state)), metrics }
+    }
+
+    /// 请求所属的路由组（第一个匹配的）
+    pub fn objective_for(&self, method: &str, path: &str) -> Option<usize> {
+        self.state.lock().unwrap().iter().position(|state| state.objective.matches(method, path))
+    }
+
+    /// 记录一次请求：5xx 计为不可用，耗时超过阈值计为慢请求
+    pub fn record(&self, objective: usize, status: u16, latency_ms: u64, now: DateTime<Utc>) {
+        let bucket_secs = self.config.bucket_secs.max(1) as i64;
+        let retention = self.retention_secs() as i64;
+        let now = now.timestamp();
+        let start = now - now.rem_euclid(bucket_secs);
+
+        let mut state = self.state.lock().unwrap();
+        let Some(state) = state.get_mut(objective) else {
+            return;
+        };
+        if state.buckets.back().is_none_or(|bucket| bucket.start < start) {
+            state.buckets.push_back(Bucket { start, ..Bucket::default() });
+        }
+        // 并发请求完成顺序与开始时间可能略有出入，记入最新的桶
+        let bucket = state.buckets.back_mut().expect("bucket was just pushed");
+        bucket.total += 1;
+        if status >= 500 {
+            bucket.errors += 1;
+        }
+        if latency_ms > state.objective.latency_threshold_ms {
+            bucket.slow += 1;
+        }
+        while state.buckets.front().is_some_and(|bucket| bucket.start <= now - retention) {
+            state.buckets.pop_front();
+        }
+    }
+
+    // 需要保留的最长统计时长
+    fn retention_secs(&self) -> u64 {
+        let longest_alert = self.config.alerts.iter().map(|alert| alert.long_window_secs).max().unwrap_or(0);
+        let longest_objective = self.config.objectives.iter().map(|objective| objective.window_secs).max().unwrap_or(0);
+        longest_alert.max(longest_objective).max(self.config.series_window_secs)
+    }
+
+    // 报告中列出燃烧率的窗口：序列窗口和各告警规则的窗口
+    fn burn_rate_windows(&self) -> Vec<u64> {
+        let mut windows: Vec<u64> = self.config.alerts.iter()
+            .flat_map(|alert| [alert.short_window_secs, alert.long_window_secs])
+            .chain([self.config.series_window_secs])
+            .collect();
+        windows.sort_unstable();
+        windows.dedup();
+        windows
+    }
+
+    /// 记录燃烧率序列并检查告警，返回本次新触发的告警数
+    pub fn evaluate(&self, now: DateTime<Utc>) -> usize {
+        let timestamp = now.timestamp();
+        let mut fired = 0;
+        let mut state = self.state.lock().unwrap();
+        for state in state.iter_mut() {
+            let point = state.burn_rate(timestamp, self.config.series_window_secs);
+            state.series.push_back(BurnRatePoint { at: now, availability: point.availability, latency: point.latency });
+            while state.series.len() > self.config.series_length {
+                state.series.pop_front();
+            }
+
+            for alert in &self.config.alerts {
+                let long = state.burn_rate(timestamp, alert.long_window_secs);
+                let short = state.burn_rate(timestamp, alert.short_window_secs);
+                let firing = (long.availability >= alert.burn_rate && short.availability >= alert.burn_rate)
+                    || (long.latency >= alert.burn_rate && short.latency >= alert.burn_rate);
+                let was_firing = state.firing.contains(&alert.name);
+                if firing && !was_firing {
+                    warn!(
+                        objective = %state.objective.name,
+                        alert = %alert.name,
+                        availability_burn_rate = long.availability,
+                        latency_burn_rate = long.latency,
+                        threshold = alert.burn_rate,
+                        "SLO burn rate alert firing"
+                    );
+                    self.metrics.increment(SLO_BURN_RATE_ALERTS);
+                    state.firing.push(alert.name.clone());
+                    fired += 1;
+                } else if !firing && was_firing {
+                    info!(objective = %state.objective.name, alert = %alert.name, "SLO burn rate alert resolved");
+                    state.firing.retain(|name| name != &alert.name);
+                }
+            }
+        }
+        fired
+    }
+
+    /// 各路由组在统计窗口内的达标情况
+    pub fn report(&self, now: DateTime<Utc>) -> SloReport {
+        let timestamp = now.timestamp();
+        let windows = self.burn_rate_windows();
+        let state = self.state.lock().unwrap();
+        let objectives = state.iter()
+            .map(|state| {
+                let objective = &state.objective;
+                let totals = state.totals(timestamp, objective.window_secs);
+                let availability = (totals.total > 0).then(|| 1.0 - totals.error_ratio());
+                let latency_compliance = (totals.total > 0).then(|| 1.0 - totals.slow_ratio());
+                ObjectiveReport {
+                    name: objective.name.clone(),
+                    path_prefixes: objective.path_prefixes.clone(),
+                    methods: objective.methods.clone(),
+                    window_secs: objective.window_secs,
+                    availability_target: objective.availability_target,
+                    latency_threshold_ms: objective.latency_threshold_ms,
+                    latency_target: objective.latency_target,
+                    total_requests: totals.total,
+                    error_requests: totals.errors,
+                    slow_requests: totals.slow,
+                    availability,
+                    latency_compliance,
+                    availability_budget_remaining: 1.0 - burn_rate(totals.error_ratio(), objective.availability_target),
+                    latency_budget_remaining: 1.0 - burn_rate(totals.slow_ratio(), objective.latency_target),
+                    compliant: availability.is_none_or(|value| value >= objective.availability_target)
+                        && latency_compliance.is_none_or(|value| value >= objective.latency_target),
+                    burn_rates: windows.iter().map(|window| state.burn_rate(timestamp, *window)).collect(),
+                    firing_alerts: state.firing.clone(),
+                    series: state.series.iter().cloned().collect(),
+                }
+            })
+            .collect();
+        SloReport { enabled: self.config.enabled, generated_at: now, objectives }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use crate::config::slo::BurnRateAlert;
+
+    fn tracker() -> SloTracker {
+        let config = SloConfig {
+            alerts: vec![BurnRateAlert { name: "fast".to_string(), long_window_secs: 3600, short_window_secs: 300, burn_rate: 10.0 }],
+            objectives: vec![SloObjective {
+                name: "auth".to_string(),
+                path_prefixes: vec!["/api/auth/".to_string()],
+                availability_target: 0.99,
+                latency_threshold_ms: 500,
+                latency_target: 0.9,
+                ..SloObjective::default()
+            }],
+            ..SloConfig::default()
+        };
+        SloTracker::new(config, MetricsRegistry::new())
+    }
+
+    #[test]
+    fn test_report_ratios() {
+        let tracker = tracker();
+        let now = Utc::now();
+        let auth = tracker.objective_for("POST", "/api/auth/login").unwrap();
+        assert!(tracker.objective_for("GET", "/api/orders").is_none());
+
+        // 超出统计窗口的请求不计入
+        tracker.record(auth, 500, 100, now - Duration::days(2));
+        for i in 0..100 {
+            let status = if i < 1 { 500 } else { 200 };
+            let latency = if i < 5 { 800 } else { 100 };
+            tracker.record(auth, status, latency, now);
+        }
+
+        let report = tracker.report(now);
+        let auth = &report.objectives[0];
+        assert_eq!(auth.total_requests, 100);
+        assert_eq!(auth.error_requests, 1);
+        assert_eq!(auth.slow_requests, 5);
+        assert!((auth.availability.unwrap() - 0.99).abs() < 1e-9);
+        assert!((auth.latency_budget_remaining - 0.5).abs() < 1e-9);
+        assert!(auth.compliant);
+    }
+
+    #[test]
+    fn test_burn_rate_alert() {
+        let tracker = tracker();
+        let metrics = tracker.metrics.clone();
+        let now = Utc::now();
+        let auth = tracker.objective_for("GET", "/api/auth/status").unwrap();
+
+        for i in 0..100 {
+            tracker.record(auth, if i < 20 { 503 } else { 200 }, 100, now);
+        }
+        assert_eq!(tracker.evaluate(now), 1);
+        assert_eq!(tracker.evaluate(now), 0);
+        assert_eq!(metrics.get(SLO_BURN_RATE_ALERTS), 1);
+
+        let report = tracker.report(now);
+        assert_eq!(report.objectives[0].firing_alerts, vec!["fast"]);
+        assert_eq!(report.objectives[0].series.len(), 2);
+        assert!((report.objectives[0].series[0].availability - 20.0).abs() < 1e-6);
+
+        // 短窗口内恢复后告警解除
+        let later = now + Duration::minutes(10);
+        tracker.record(auth, 200, 100, later);
+        assert_eq!(tracker.evaluate(later), 0);
+        assert!(tracker.report(later).objectives[0].firing_alerts.is_empty());
+    }
+}
@@ -0,0 +1,42 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// 微信验证身份后签发的重置密码凭据，缓存中按令牌摘要保存
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasswordRecoveryTicket {
+    pub user_id: Uuid,
+    pub issued_at: DateTime<Utc>,
+}
+
+/// 使用找回凭据重置密码
+#[derive(Debug, Deserialize)]
+pub struct PasswordResetRequest {
+    pub recovery_token: String,
+    pub new_password: String,
+}
+
+// 找回令牌生成（十六进制，随导航参数下发）
+pub fn generate_recovery_token() -> String {
+    hex::encode(rand::random::<[u8; 32]>())
+}
+
+// 找回令牌摘要，缓存键中只使用该值（SHA-256 十六进制）
+pub fn hash_recovery_token(token: &str) -> String {
+    use sha2::{Digest, Sha256};
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recovery_token_hash() {
+        let token = generate_recovery_token();
+        assert_eq!(token.len(), 64);
+        assert_ne!(generate_recovery_token(), token);
+        assert_ne!(hash_recovery_token(&token), token);
+        assert_eq!(hash_recovery_token(&token), hash_recovery_token(&token));
+    }
+}
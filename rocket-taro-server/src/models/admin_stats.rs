@@ -0,0 +1,176 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+use crate::metrics::runtime_stats::RuntimeSnapshot;
+
+/// 统计查询允许的最大天数
+pub const MAX_STATS_RANGE_DAYS: i64 = 90;
+/// 未指定时间范围时的默认天数
+pub const DEFAULT_STATS_RANGE_DAYS: i64 = 7;
+
+/// 统计的日期范围（按 UTC 日期，包含首尾）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatsRange {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+}
+
+impl StatsRange {
+    /// 解析查询参数（YYYY-MM-DD），缺省时取截至今天的最近7天
+    pub fn parse(from: Option<&str>, to: Option<&str>, today: NaiveDate) -> Result<Self, String> {
+        let parse_date = |value: &str| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| format!("日期格式错误: {}", value))
+        };
+
+        let to = match to {
+            Some(value) => parse_date(value)?,
+            None => today,
+        };
+        let from = match from {
+            Some(value) => parse_date(value)?,
+            None => to - Duration::days(DEFAULT_STATS_RANGE_DAYS - 1),
+        };
+
+        if from > to {
+            return Err("开始日期不能晚于结束日期".to_string());
+        }
+        if (to - from).num_days() + 1 > MAX_STATS_RANGE_DAYS {
+            return Err(format!("统计范围不能超过{}天", MAX_STATS_RANGE_DAYS));
+        }
+
+        Ok(Self { from, to })
+    }
+
+    /// 范围起点（包含）
+    pub fn start(&self) -> DateTime<Utc> {
+        self.from.and_hms_opt(0, 0, 0).unwrap().and_utc()
+    }
+
+    /// 范围终点（不包含）
+    pub fn end(&self) -> DateTime<Utc> {
+        (self.to + Duration::days(1)).and_hms_opt(0, 0, 0).unwrap().and_utc()
+    }
+
+    /// 范围内的所有日期
+    pub fn days(&self) -> Vec<NaiveDate> {
+        self.from.iter_days().take_while(|day| *day <= self.to).collect()
+    }
+
+    /// 用于缓存键的标识
+    pub fn cache_id(&self) -> String {
+        format!("{}:{}", self.from, self.to)
+    }
+}
+
+/// 按登录方式划分的用户数量
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuthMethodBreakdown {
+    pub guest: i64,
+    pub wechat: i64,
+    pub password: i64,
+}
+
+/// 单日统计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyStats {
+    pub date: NaiveDate,
+    pub active_users: i64,
+    pub login_success: i64,
+    pub login_failure: i64,
+    pub new_registrations: i64,
+    pub user_data_submissions: i64,
+}
+
+impl DailyStats {
+    pub fn empty(date: NaiveDate) -> Self {
+        Self {
+            date,
+            active_users: 0,
+            login_success: 0,
+            login_failure: 0,
+            new_registrations: 0,
+            user_data_submissions: 0,
+        }
+    }
+}
+
+/// 时间范围内的汇总统计
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StatsSummary {
+    /// 范围内至少成功登录一次的用户数（去重）
+    pub active_users: i64,
+    pub login_success: i64,
+    pub login_failure: i64,
+    pub new_registrations: i64,
+    pub user_data_submissions: i64,
+    /// 当前有效用户总数
+    pub total_users: i64,
+    /// 提交数据总量
+    pub total_user_data: i64,
+}
+
+/// 管理后台统计数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdminStats {
+    pub range: StatsRange,
+    pub generated_at: DateTime<Utc>,
+    pub summary: StatsSummary,
+    pub daily: Vec<DailyStats>,
+    /// 范围内新注册用户的登录方式分布
+    pub registrations_by_method: AuthMethodBreakdown,
+    /// 范围内活跃用户的登录方式分布
+    pub active_users_by_method: AuthMethodBreakdown,
+    /// 最近15分钟内有访问的用户数，每次请求实时统计
+    #[serde(default)]
+    pub online_users: i64,
+    /// 服务进程的运行状态，每次请求实时生成，不缓存
+    #[serde(default)]
+    pub runtime: Option<RuntimeSnapshot>,
+}
+
+/// 单个索引的使用情况（来自 pg_stat_user_indexes，自统计信息上次重置以来累计）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexUsage {
+    pub table_name: String,
+    pub index_name: String,
+    /// 索引扫描次数，为 0 表示索引未被使用
+    pub scans: i64,
+    /// 扫描返回的索引项数
+    pub tuples_read: i64,
+    /// 通过索引扫描取回的表行数
+    pub tuples_fetched: i64,
+    pub size_bytes: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_default_range() {
+        let range = StatsRange::parse(None, None, date("2024-03-10")).unwrap();
+        assert_eq!(range.from, date("2024-03-04"));
+        assert_eq!(range.to, date("2024-03-10"));
+        assert_eq!(range.days().len(), 7);
+    }
+
+    #[test]
+    fn test_explicit_range_bounds() {
+        let range = StatsRange::parse(Some("2024-02-28"), Some("2024-03-01"), date("2024-03-10")).unwrap();
+        assert_eq!(range.days().len(), 3);
+        assert_eq!(range.start().to_rfc3339(), "2024-02-28T00:00:00+00:00");
+        assert_eq!(range.end().to_rfc3339(), "2024-03-02T00:00:00+00:00");
+    }
+
+    #[test]
+    fn test_invalid_ranges() {
+        let today = date("2024-03-10");
+        assert!(StatsRange::parse(Some("2024-03-05"), Some("2024-03-01"), today).is_err());
+        assert!(StatsRange::parse(Some("2023-01-01"), Some("2024-03-01"), today).is_err());
+        assert!(StatsRange::parse(Some("03/01/2024"), None, today).is_err());
+    }
+}
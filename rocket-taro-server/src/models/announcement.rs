@@ -0,0 +1,226 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::config::Platform;
+use crate::models::{auth::User, business_results::AccountFlags};
+
+/// 通过 ProcessData 下发公告时使用的数据类型
+pub const ANNOUNCEMENTS_DATA_TYPE: &str = "announcements";
+
+/// 公告级别，前端据此选择横幅样式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum AnnouncementLevel {
+    #[default]
+    Info,
+    Warning,
+    Critical,
+}
+
+impl AnnouncementLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AnnouncementLevel::Info => "info",
+            AnnouncementLevel::Warning => "warning",
+            AnnouncementLevel::Critical => "critical",
+        }
+    }
+
+    pub fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "info" => Some(AnnouncementLevel::Info),
+            "warning" => Some(AnnouncementLevel::Warning),
+            "critical" => Some(AnnouncementLevel::Critical),
+            _ => None,
+        }
+    }
+}
+
+/// 受众标记，对应账户标记和用户类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AudienceFlag {
+    Vip,
+    NewUser,
+    Guest,
+    NeedsProfileCompletion,
+}
+
+impl AudienceFlag {
+    fn matches(&self, user: &User, flags: &AccountFlags) -> bool {
+        match self {
+            AudienceFlag::Vip => flags.is_vip,
+            AudienceFlag::NewUser => flags.is_new_user,
+            AudienceFlag::Guest => user.is_guest,
+            AudienceFlag::NeedsProfileCompletion => flags.needs_profile_completion,
+        }
+    }
+}
+
+/// 公告受众：平台为空表示所有平台；标记为空表示所有用户，多个标记需同时满足
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AnnouncementAudience {
+    #[serde(default)]
+    pub platforms: Vec<Platform>,
+    #[serde(default)]
+    pub flags: Vec<AudienceFlag>,
+}
+
+impl AnnouncementAudience {
+    pub fn matches(&self, platform: Platform, user: &User, flags: &AccountFlags) -> bool {
+        (self.platforms.is_empty() || self.platforms.contains(&platform))
+            && self.flags.iter().all(|flag| flag.matches(user, flags))
+    }
+}
+
+/// 公告（对应 announcements 表中的一行）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Announcement {
+    pub id: Uuid,
+    pub title: String,
+    pub content: String,
+    pub level: AnnouncementLevel,
+    pub dismissible: bool,
+    pub audience: AnnouncementAudience,
+    pub starts_at: DateTime<Utc>,
+    /// 结束时间，None 表示一直有效
+    pub ends_at: Option<DateTime<Utc>>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Announcement {
+    /// 是否在有效期内
+    pub fn is_active_at(&self, now: DateTime<Utc>) -> bool {
+        self.starts_at <= now && self.ends_at.is_none_or(|ends_at| now < ends_at)
+    }
+
+    /// 是否应展示给该用户
+    pub fn is_visible_to(&self, platform: Platform, user: &User, flags: &AccountFlags, now: DateTime<Utc>) -> bool {
+        self.is_active_at(now) && self.audience.matches(platform, user, flags)
+    }
+}
+
+/// 管理端创建/更新公告请求
+#[derive(Debug, Deserialize)]
+pub struct AnnouncementRequest {
+    pub title: String,
+    pub content: String,
+    #[serde(default)]
+    pub level: AnnouncementLevel,
+    #[serde(default = "default_dismissible")]
+    pub dismissible: bool,
+    #[serde(default)]
+    pub audience: AnnouncementAudience,
+    /// 开始时间，默认立即生效
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+fn default_dismissible() -> bool {
+    true
+}
+
+/// 下发给客户端的公告内容
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnnouncementView {
+    pub id: Uuid,
+    pub title: String,
+    pub content: String,
+    pub level: AnnouncementLevel,
+    pub dismissible: bool,
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+impl From<Announcement> for AnnouncementView {
+    fn from(announcement: Announcement) -> Self {
+        Self {
+            id: announcement.id,
+            title: announcement.title,
+            content: announcement.content,
+            level: announcement.level,
+            dismissible: announcement.dismissible,
+            ends_at: announcement.ends_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn user(is_guest: bool) -> User {
+        let now = Utc::now();
+        User {
+            id: Uuid::new_v4(),
+            username: "alice".to_string(),
+            email: "alice@example.com".to_string(),
+            full_name: None,
+            avatar_url: None,
+            is_active: true,
+            is_admin: false,
+            is_guest,
+            wx_openid: None,
+            wx_unionid: None,
+            wx_session_key: None,
+            last_login_at: None,
+            created_at: now,
+            updated_at: now,
+            version: 1,
+        }
+    }
+
+    fn announcement(audience: AnnouncementAudience, ends_at: Option<DateTime<Utc>>) -> Announcement {
+        let now = Utc::now();
+        Announcement {
+            id: Uuid::new_v4(),
+            title: "系统升级".to_string(),
+            content: "今晚22点进行系统升级".to_string(),
+            level: AnnouncementLevel::Warning,
+            dismissible: true,
+            audience,
+            starts_at: now - Duration::hours(1),
+            ends_at,
+            created_by: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_audience_targeting() {
+        let now = Utc::now();
+        let vip = AccountFlags { is_vip: true, ..AccountFlags::default() };
+        let audience = AnnouncementAudience {
+            platforms: vec![Platform::Miniprogram],
+            flags: vec![AudienceFlag::Vip],
+        };
+        let targeted = announcement(audience, None);
+
+        assert!(targeted.is_visible_to(Platform::Miniprogram, &user(false), &vip, now));
+        assert!(!targeted.is_visible_to(Platform::H5, &user(false), &vip, now));
+        assert!(!targeted.is_visible_to(Platform::Miniprogram, &user(false), &AccountFlags::default(), now));
+
+        // 多个标记需同时满足
+        let guests_vip = announcement(AnnouncementAudience { platforms: vec![], flags: vec![AudienceFlag::Vip, AudienceFlag::Guest] }, None);
+        assert!(!guests_vip.is_visible_to(Platform::H5, &user(false), &vip, now));
+        assert!(guests_vip.is_visible_to(Platform::H5, &user(true), &vip, now));
+    }
+
+    #[test]
+    fn test_active_window() {
+        let now = Utc::now();
+        let flags = AccountFlags::default();
+        assert!(announcement(AnnouncementAudience::default(), None).is_visible_to(Platform::H5, &user(false), &flags, now));
+
+        let expired = announcement(AnnouncementAudience::default(), Some(now - Duration::minutes(1)));
+        assert!(!expired.is_visible_to(Platform::H5, &user(false), &flags, now));
+
+        let mut upcoming = announcement(AnnouncementAudience::default(), None);
+        upcoming.starts_at = now + Duration::hours(1);
+        assert!(!upcoming.is_active_at(now));
+    }
+}
@@ -0,0 +1,158 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use uuid::Uuid;
+
+/// 配额按角色区分的调用方类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuotaRole {
+    /// 未登录，按 IP 计数
+    Anonymous,
+    Guest,
+    User,
+    Admin,
+}
+
+impl QuotaRole {
+    pub const ALL: [QuotaRole; 4] = [QuotaRole::Anonymous, QuotaRole::Guest, QuotaRole::User, QuotaRole::Admin];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuotaRole::Anonymous => "anonymous",
+            QuotaRole::Guest => "guest",
+            QuotaRole::User => "user",
+            QuotaRole::Admin => "admin",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|role| role.as_str() == value)
+    }
+}
+
+/// 计数的调用方：登录用户按用户ID，未登录请求按 IP
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuotaCaller {
+    User { user_id: Uuid, role: QuotaRole },
+    Anonymous(IpAddr),
+}
+
+impl QuotaCaller {
+    pub fn role(&self) -> QuotaRole {
+        match self {
+            QuotaCaller::User { role, .. } => *role,
+            QuotaCaller::Anonymous(_) => QuotaRole::Anonymous,
+        }
+    }
+
+    /// 计数键中的调用方标识
+    pub fn key_id(&self) -> String {
+        match self {
+            QuotaCaller::User { user_id, .. } => format!("user:{}", user_id),
+            QuotaCaller::Anonymous(ip) => format!("ip:{}", ip),
+        }
+    }
+}
+
+/// 管理端覆盖的对象：`user:<用户ID>` 或 `role:<角色>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotaSubject {
+    User(Uuid),
+    Role(QuotaRole),
+}
+
+impl QuotaSubject {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.split_once(':')? {
+            ("user", id) => Uuid::parse_str(id).ok().map(QuotaSubject::User),
+            ("role", role) => QuotaRole::parse(role).map(QuotaSubject::Role),
+            _ => None,
+        }
+    }
+
+    pub fn as_string(&self) -> String {
+        match self {
+            QuotaSubject::User(user_id) => format!("user:{}", user_id),
+            QuotaSubject::Role(role) => format!("role:{}", role.as_str()),
+        }
+    }
+}
+
+/// 管理端设置的配额覆盖（对应 api_quota_overrides 表中的一行）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuotaOverride {
+    pub group_name: String,
+    pub subject: String,
+    /// 每日上限，0 表示不限制
+    pub daily_limit: i64,
+    pub updated_by: Option<Uuid>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// 管理端写入配额覆盖请求
+#[derive(Debug, Deserialize)]
+pub struct SetQuotaOverrideRequest {
+    pub group_name: String,
+    /// `user:<用户ID>` 或 `role:<anonymous|guest|user|admin>`
+    pub subject: String,
+    pub daily_limit: i64,
+}
+
+/// 本次请求计入配额后的状态，写入 X-Quota-* 响应头
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuotaStatus {
+    pub group: String,
+    pub limit: u64,
+    pub remaining: u64,
+    /// 距离配额重置（下一个 UTC 零点）的秒数
+    pub reset_secs: u64,
+    pub exceeded: bool,
+}
+
+impl QuotaStatus {
+    /// 按本周期已用次数（含本次）计算状态
+    pub fn new(group: &str, limit: u64, used: u64, reset_secs: u64) -> Self {
+        Self {
+            group: group.to_string(),
+            limit,
+            remaining: limit.saturating_sub(used),
+            reset_secs,
+            exceeded: used > limit,
+        }
+    }
+}
+
+/// 配额周期（UTC 日期）及距离下一个 UTC 零点的秒数
+pub fn quota_window(now: DateTime<Utc>) -> (NaiveDate, u64) {
+    let today = now.date_naive();
+    let next_reset = (today + Duration::days(1)).and_hms_opt(0, 0, 0).unwrap().and_utc();
+    (today, (next_reset - now).num_seconds().max(1) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subject_round_trip() {
+        let user_id = Uuid::new_v4();
+        assert_eq!(QuotaSubject::parse(&format!("user:{}", user_id)), Some(QuotaSubject::User(user_id)));
+        assert_eq!(QuotaSubject::parse("role:guest"), Some(QuotaSubject::Role(QuotaRole::Guest)));
+        assert_eq!(QuotaSubject::Role(QuotaRole::Admin).as_string(), "role:admin");
+        assert_eq!(QuotaSubject::parse("role:owner"), None);
+        assert_eq!(QuotaSubject::parse("admin"), None);
+    }
+
+    #[test]
+    fn test_status_and_window() {
+        let status = QuotaStatus::new("export", 2, 2, 60);
+        assert_eq!((status.remaining, status.exceeded), (0, false));
+        assert!(QuotaStatus::new("export", 2, 3, 60).exceeded);
+
+        let now = DateTime::parse_from_rfc3339("2026-03-01T23:59:00Z").unwrap().with_timezone(&Utc);
+        let (day, reset_secs) = quota_window(now);
+        assert_eq!(day.to_string(), "2026-03-01");
+        assert_eq!(reset_secs, 60);
+    }
+}
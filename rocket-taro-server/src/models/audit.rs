@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use std::net::IpAddr;
+use uuid::Uuid;
+
+/// 待写入的审计事件
+#[derive(Debug, Clone)]
+pub struct AuditEvent {
+    /// 执行操作的用户（系统任务为 None）
+    pub actor_id: Option<Uuid>,
+    /// 操作名称，例如 `account.delete_requested`
+    pub action: String,
+    /// 操作对象类型，例如 `user`
+    pub target_type: String,
+    pub target_id: Option<String>,
+    pub ip_address: Option<IpAddr>,
+    pub details: serde_json::Value,
+}
+
+/// 已记录的审计日志
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditLog {
+    pub id: Uuid,
+    pub actor_id: Option<Uuid>,
+    pub action: String,
+    pub target_type: String,
+    pub target_id: Option<String>,
+    pub ip_address: Option<String>,
+    pub details: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AuditEvent {
+    pub fn new(action: &str, target_type: &str) -> Self {
+        Self {
+            actor_id: None,
+            action: action.to_string(),
+            target_type: target_type.to_string(),
+            target_id: None,
+            ip_address: None,
+            details: serde_json::Value::Null,
+        }
+    }
+
+    /// 设置操作者
+    pub fn actor(mut self, actor_id: Uuid) -> Self {
+        self.actor_id = Some(actor_id);
+        self
+    }
+
+    /// 设置操作对象ID
+    pub fn target(mut self, target_id: impl ToString) -> Self {
+        self.target_id = Some(target_id.to_string());
+        self
+    }
+
+    /// 设置来源IP
+    pub fn ip(mut self, ip_address: Option<IpAddr>) -> Self {
+        self.ip_address = ip_address;
+        self
+    }
+
+    /// 设置附加信息
+    pub fn details(mut self, details: serde_json::Value) -> Self {
+        self.details = details;
+        self
+    }
+}
@@ -19,6 +19,9 @@ pub struct User {
     pub last_login_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// 乐观锁版本号，修改资料时递增
+    #[serde(default)]
+    pub version: i32,
 }
 
 #[derive(Deserialize, Debug)]
@@ -27,18 +30,26 @@ pub struct LoginRequest {
     pub password: String,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct RegisterRequest {
     pub username: String,
     pub password: String,
     pub confirm_password: String,
     pub email: String,
     pub phone: String,
+    /// 蜜罐字段：前端渲染但对用户隐藏，真实用户提交时为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub website: Option<String>,
+    /// 客户端记录的表单填写耗时（毫秒），过短的提交进入人工审核
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub form_elapsed_ms: Option<u64>,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 pub struct LoginResponse {
     pub user: UserInfo,
+    /// 幂等记录中不保存，重放时重新签发
+    #[serde(default)]
     pub session_token: String,
     pub expires_at: DateTime<Utc>,
 }
@@ -55,6 +66,13 @@ pub struct UserInfo {
     pub wx_openid: Option<String>,
     pub has_wx_session: bool,  // 标识是否有有效的微信会话
     pub display_name: String,  // 优先显示full_name，其次username
+    pub version: i32,  // 修改资料时通过 If-Match 提交
+}
+
+/// POST /api/auth/extend-session 响应
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SessionExtension {
+    pub expires_at: DateTime<Utc>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -66,6 +84,20 @@ pub struct UserSession {
     pub ip_address: Option<String>,
     pub expires_at: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
+    /// 最近一次访问时间，用于空闲过期判断
+    #[serde(default = "Utc::now")]
+    pub last_accessed_at: DateTime<Utc>,
+    /// 管理员模拟登录创建的会话记录管理员ID
+    #[serde(default)]
+    pub impersonator_id: Option<Uuid>,
+}
+
+/// 用户的有效会话摘要，用于会话数量限制和会话列表
+#[derive(Debug, Clone, Serialize)]
+pub struct ActiveSession {
+    pub id: Uuid,
+    pub user_agent: Option<String>,
+    pub created_at: DateTime<Utc>,
 }
 
 impl From<User> for UserInfo {
@@ -83,26 +115,46 @@ impl From<User> for UserInfo {
             wx_openid: user.wx_openid.clone(),  // 返回wx_openid用于识别微信用户
             has_wx_session: user.wx_session_key.is_some(),  // 标识是否有有效的微信会话
             display_name,  // 优先显示full_name，其次username
+            version: user.version,
         }
     }
 }
 
 
-// 密码验证结构
-#[derive(Debug)]
-pub struct PasswordHash {
-    pub hash: String,
+/// 可用性检查的字段
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AvailabilityField {
+    Username,
+    Email,
 }
 
-impl PasswordHash {
-    pub fn new(password: &str) -> Result<Self, bcrypt::BcryptError> {
-        let hash = bcrypt::hash(password, bcrypt::DEFAULT_COST)?;
-        Ok(PasswordHash { hash })
+impl AvailabilityField {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AvailabilityField::Username => "username",
+            AvailabilityField::Email => "email",
+        }
     }
+}
 
-    pub fn verify(&self, password: &str) -> bool {
-        bcrypt::verify(password, &self.hash).unwrap_or(false)
-    }
+/// 单个字段的可用性
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FieldAvailability {
+    pub field: AvailabilityField,
+    pub value: String,
+    pub available: bool,
+    /// 不可用的原因（格式不正确或已被占用）
+    pub reason: Option<String>,
+    /// 用户名被占用时推荐的可用用户名
+    pub suggestions: Vec<String>,
+}
+
+/// GET /api/auth/check-availability 响应，只包含请求中提供的字段
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct AvailabilityResult {
+    pub username: Option<FieldAvailability>,
+    pub email: Option<FieldAvailability>,
 }
 
 // 会话令牌生成
@@ -111,4 +163,25 @@ pub fn generate_session_token() -> String {
     let mut rng = rand::thread_rng();
     let bytes: [u8; 32] = rng.gen();
     BASE64.encode(bytes)
-}
\ No newline at end of file
+}
+
+// 会话令牌摘要，数据库中只保存该值（SHA-256 十六进制）
+pub fn hash_session_token(session_token: &str) -> String {
+    use sha2::{Digest, Sha256};
+    hex::encode(Sha256::digest(session_token.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_session_token() {
+        assert_eq!(
+            hash_session_token("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        let token = generate_session_token();
+        assert_ne!(hash_session_token(&token), token);
+    }
+}
@@ -0,0 +1,93 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// 头像处理状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AvatarStatus {
+    /// 等待处理，处理完成前不对外提供
+    Pending,
+    /// 已处理，可以访问
+    Ready,
+    /// 未通过内容审核
+    Rejected,
+    /// 无法识别的图片，或达到最大处理次数
+    Failed,
+}
+
+impl AvatarStatus {
+    pub const ALL: [AvatarStatus; 4] = [
+        AvatarStatus::Pending,
+        AvatarStatus::Ready,
+        AvatarStatus::Rejected,
+        AvatarStatus::Failed,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AvatarStatus::Pending => "pending",
+            AvatarStatus::Ready => "ready",
+            AvatarStatus::Rejected => "rejected",
+            AvatarStatus::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|status| status.as_str() == value)
+    }
+}
+
+/// 上传的头像（对应 avatars 表）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Avatar {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub status: String,
+    /// 原图路径，处理结束后删除
+    #[serde(skip)]
+    pub source_path: String,
+    pub source_content_type: String,
+    /// 处理后的 WebP 文件路径
+    #[serde(skip)]
+    pub file_path: Option<String>,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub file_size: Option<i64>,
+    pub failure_reason: Option<String>,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub processed_at: Option<DateTime<Utc>>,
+}
+
+impl Avatar {
+    pub fn status(&self) -> AvatarStatus {
+        AvatarStatus::parse(&self.status).unwrap_or(AvatarStatus::Failed)
+    }
+
+    /// 返回给客户端的处理状态
+    pub fn view(&self) -> AvatarView {
+        let status = self.status();
+        AvatarView {
+            id: self.id,
+            status,
+            url: (status == AvatarStatus::Ready).then(|| avatar_url(self.id)),
+            failure_reason: self.failure_reason.clone(),
+        }
+    }
+}
+
+/// 头像处理状态，处理完成后 url 为头像地址
+#[derive(Debug, Clone, Serialize)]
+pub struct AvatarView {
+    pub id: Uuid,
+    pub status: AvatarStatus,
+    pub url: Option<String>,
+    pub failure_reason: Option<String>,
+}
+
+/// 头像访问地址，同时写入 users.avatar_url
+pub fn avatar_url(avatar_id: Uuid) -> String {
+    format!("/api/avatars/{}", avatar_id)
+}
@@ -0,0 +1,86 @@
+use rocket::http::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// 批量请求自身的路径，子请求不能再调用它
+pub const BATCH_PATH: &str = "/api/batch";
+
+/// POST /api/batch 中的单个子请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchSubRequest {
+    pub method: String,
+    /// 含查询参数的路径，如 `/api/notifications?limit=5`
+    pub path: String,
+    #[serde(default)]
+    pub body: Option<Value>,
+}
+
+impl BatchSubRequest {
+    /// 校验方法和路径，路径必须以 path_prefixes 之一开头
+    pub fn validate(&self, path_prefixes: &[String]) -> Result<Method, String> {
+        let method = match self.method.to_ascii_uppercase().as_str() {
+            "GET" => Method::Get,
+            "POST" => Method::Post,
+            "PUT" => Method::Put,
+            "PATCH" => Method::Patch,
+            "DELETE" => Method::Delete,
+            _ => return Err(format!("不支持的请求方法: {}", self.method)),
+        };
+        if method == Method::Get && self.body.is_some() {
+            return Err("GET 请求不能带请求体".to_string());
+        }
+
+        let path = self.path.split(['?', '#']).next().unwrap_or_default();
+        if !path.starts_with('/') || path.contains("..") || path.contains("//") {
+            return Err(format!("无效的路径: {}", self.path));
+        }
+        if path.trim_end_matches('/') == BATCH_PATH {
+            return Err("批量请求不能嵌套".to_string());
+        }
+        if !path_prefixes.iter().any(|prefix| path.starts_with(prefix.as_str())) {
+            return Err(format!("不允许批量调用的路径: {}", self.path));
+        }
+        Ok(method)
+    }
+}
+
+/// 单个子请求的结果，顺序与请求数组一致
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchItemResult {
+    /// 子请求的 HTTP 状态码；校验不通过为 400，转发失败或超时为 502 / 504
+    pub status: u16,
+    /// 子请求的 JSON 响应，非 JSON 响应为 None
+    pub body: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl BatchItemResult {
+    pub fn failed(status: u16, error: impl Into<String>) -> Self {
+        Self { status, body: None, error: Some(error.into()) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sub_request(method: &str, path: &str, body: Option<Value>) -> BatchSubRequest {
+        BatchSubRequest { method: method.to_string(), path: path.to_string(), body }
+    }
+
+    #[test]
+    fn test_validate() {
+        let prefixes = vec!["/api/".to_string()];
+        assert_eq!(sub_request("get", "/api/auth/status", None).validate(&prefixes), Ok(Method::Get));
+        assert_eq!(sub_request("POST", "/api/drafts/feedback?x=1", Some(json!({}))).validate(&prefixes), Ok(Method::Post));
+
+        assert!(sub_request("HEAD", "/api/auth/status", None).validate(&prefixes).is_err());
+        assert!(sub_request("GET", "/api/settings", Some(json!({}))).validate(&prefixes).is_err());
+        assert!(sub_request("GET", "/health", None).validate(&prefixes).is_err());
+        assert!(sub_request("GET", "http://example.com/api/", None).validate(&prefixes).is_err());
+        assert!(sub_request("GET", "/api/../health", None).validate(&prefixes).is_err());
+        assert!(sub_request("POST", "/api/batch/", Some(json!([]))).validate(&prefixes).is_err());
+    }
+}
@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 use super::auth::{User, UserSession};
+use super::profile::ProfileField;
 
 /// 认证相关业务结果
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,6 +36,28 @@ pub struct LogoutResult {
     pub has_unsaved_data: bool,
 }
 
+/// 注册结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisterResult {
+    /// 新建的用户
+    pub user: User,
+    /// 自动登录的会话，创建失败时为空
+    pub session: Option<UserSession>,
+}
+
+/// 账户注销结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountDeletionResult {
+    /// 用户ID
+    pub user_id: Uuid,
+    /// 撤销的会话数量
+    pub sessions_revoked: u64,
+    /// 注销时间
+    pub deleted_at: DateTime<Utc>,
+    /// 个人信息匿名化时间（保留期结束）
+    pub anonymize_after: DateTime<Utc>,
+}
+
 /// 账户状态标记
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccountFlags {
@@ -46,8 +69,17 @@ pub struct AccountFlags {
     pub has_unread_notifications: bool,
     /// 是否需要完善个人信息
     pub needs_profile_completion: bool,
+    /// 未填写的资料字段，用于生成资料完善步骤
+    #[serde(default)]
+    pub missing_profile_fields: Vec<ProfileField>,
     /// 账户安全等级（1-5）
     pub security_level: u8,
+    /// 是否有未同意的生效协议版本（服务协议、隐私政策）
+    #[serde(default)]
+    pub needs_policy_consent: bool,
+    /// 未同意的协议类型
+    #[serde(default)]
+    pub pending_policies: Vec<String>,
 }
 
 impl Default for AccountFlags {
@@ -57,7 +89,10 @@ impl Default for AccountFlags {
             is_new_user: false,
             has_unread_notifications: false,
             needs_profile_completion: false,
+            missing_profile_fields: Vec::new(),
             security_level: 1,
+            needs_policy_consent: false,
+            pending_policies: Vec::new(),
         }
     }
 }
@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::cache::CacheCategory;
+
+/// 可按类别清除的缓存：只包含可以从数据库重建的数据，
+/// 登录失败计数、幂等记录、找回密码凭证等状态类缓存不在其中
+pub const INVALIDATABLE_CATEGORIES: &[CacheCategory] = &[
+    CacheCategory::AdminStats,
+    CacheCategory::AllUserData,
+    CacheCategory::ClientCapabilities,
+    CacheCategory::RemoteConfig,
+    CacheCategory::Session,
+    CacheCategory::SessionToken,
+    CacheCategory::User,
+    CacheCategory::UserData,
+    CacheCategory::UserDataSearch,
+    CacheCategory::UserSession,
+    CacheCategory::UserSettings,
+    CacheCategory::Username,
+];
+
+/// 精确清除缓存的对象
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CacheTarget {
+    /// 用户信息、用户名映射、设置和该用户的所有会话缓存
+    User(Uuid),
+    /// 用户名到用户ID的映射
+    Username(String),
+    /// 单个会话的缓存
+    Session(Uuid),
+    /// 单条用户数据及包含它的列表、搜索结果
+    UserData(Uuid),
+    /// 某个类别下的所有缓存
+    Category(CacheCategory),
+}
+
+impl CacheTarget {
+    /// 校验类别名，只接受 INVALIDATABLE_CATEGORIES 中的类别
+    pub fn category(name: &str) -> Result<Self, String> {
+        match CacheCategory::from_name(name) {
+            Some(category) if INVALIDATABLE_CATEGORIES.contains(&category) => Ok(CacheTarget::Category(category)),
+            _ => {
+                let names: Vec<&str> = INVALIDATABLE_CATEGORIES.iter().map(|category| category.as_str()).collect();
+                Err(format!("不支持清除的缓存类别: {}，可选: {}", name, names.join(", ")))
+            }
+        }
+    }
+
+    /// 用于响应和审计日志的描述，如 `user:<id>`
+    pub fn describe(&self) -> String {
+        match self {
+            CacheTarget::User(user_id) => format!("user:{}", user_id),
+            CacheTarget::Username(username) => format!("username:{}", username),
+            CacheTarget::Session(session_id) => format!("session:{}", session_id),
+            CacheTarget::UserData(data_id) => format!("user_data:{}", data_id),
+            CacheTarget::Category(category) => format!("category:{}", category),
+        }
+    }
+}
+
+/// 精确清除缓存的结果
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CacheInvalidation {
+    pub target: String,
+    /// 实际删除的键数量
+    pub keys_removed: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_category_target() {
+        assert_eq!(CacheTarget::category("user_settings"), Ok(CacheTarget::Category(CacheCategory::UserSettings)));
+        assert!(CacheTarget::category("login_failures").is_err());
+        assert!(CacheTarget::category("*").is_err());
+        assert_eq!(CacheTarget::category("user").unwrap().describe(), "category:user");
+    }
+}
@@ -0,0 +1,83 @@
+use std::collections::BTreeSet;
+use serde::{Deserialize, Serialize};
+
+/// 所有客户端都必须支持的基础指令，降级转换的结果只使用这些指令
+pub const BASELINE_COMMANDS: &[&str] = &["NavigateTo", "ShowDialog", "ProcessData", "Sequence"];
+
+/// 服务端可能下发的全部指令类型（与 RouteCommand::type_name 一致）
+pub const ALL_COMMANDS: &[&str] = &[
+    "NavigateTo",
+    "ShowDialog",
+    "ProcessData",
+    "Sequence",
+    "Conditional",
+    "Delay",
+    "Parallel",
+    "Retry",
+    "RequestPayment",
+];
+
+/// 客户端声明支持的路由指令类型
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientCapabilities {
+    pub commands: BTreeSet<String>,
+}
+
+impl Default for ClientCapabilities {
+    /// 未声明能力的客户端视为支持全部指令，保持原有行为
+    fn default() -> Self {
+        Self::from_commands(ALL_COMMANDS.iter().copied())
+    }
+}
+
+impl ClientCapabilities {
+    /// 由指令类型列表创建，基础指令总是包含在内，未知类型忽略（兼容更新的客户端）
+    pub fn from_commands<'a>(commands: impl IntoIterator<Item = &'a str>) -> Self {
+        let commands = commands.into_iter()
+            .map(str::trim)
+            .filter(|command| ALL_COMMANDS.contains(command))
+            .chain(BASELINE_COMMANDS.iter().copied())
+            .map(str::to_string)
+            .collect();
+        Self { commands }
+    }
+
+    /// 解析 X-Client-Capabilities 请求头（逗号分隔的指令类型）
+    pub fn from_header(value: &str) -> Self {
+        Self::from_commands(value.split(','))
+    }
+
+    pub fn supports(&self, command_type: &str) -> bool {
+        self.commands.contains(command_type)
+    }
+
+    /// 是否支持全部指令，无需降级
+    pub fn is_full(&self) -> bool {
+        ALL_COMMANDS.iter().all(|command| self.supports(command))
+    }
+}
+
+/// PUT /api/route-commands/capabilities 请求
+#[derive(Debug, Clone, Deserialize)]
+pub struct ClientCapabilitiesRequest {
+    pub commands: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_parsing_keeps_baseline() {
+        let capabilities = ClientCapabilities::from_header("Conditional, Delay ,FutureCommand");
+        assert!(capabilities.supports("Conditional"));
+        assert!(capabilities.supports("Delay"));
+        assert!(!capabilities.supports("FutureCommand"));
+        assert!(!capabilities.supports("Parallel"));
+        for command in BASELINE_COMMANDS {
+            assert!(capabilities.supports(command));
+        }
+        assert!(!capabilities.is_full());
+        assert!(ClientCapabilities::default().is_full());
+    }
+}
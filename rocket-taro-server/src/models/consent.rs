@@ -0,0 +1,101 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// 需要用户同意的协议类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PolicyType {
+    /// 用户服务协议
+    Terms,
+    /// 隐私政策
+    Privacy,
+}
+
+impl PolicyType {
+    pub const ALL: [PolicyType; 2] = [PolicyType::Terms, PolicyType::Privacy];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PolicyType::Terms => "terms",
+            PolicyType::Privacy => "privacy",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|policy_type| policy_type.as_str() == value)
+    }
+}
+
+/// 协议文档的一个版本，同一类型同时只有一个生效版本
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyDocument {
+    pub id: Uuid,
+    pub policy_type: String,
+    pub version: String,
+    pub title: String,
+    pub content: String,
+    pub is_active: bool,
+    pub published_at: Option<DateTime<Utc>>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// 用户同意某个协议版本的记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserConsent {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub document_id: Uuid,
+    pub policy_type: String,
+    pub version: String,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub accepted_at: DateTime<Utc>,
+}
+
+/// 同意协议请求：列出本次同意的协议版本ID，只接受当前生效的版本
+#[derive(Debug, Clone, Deserialize)]
+pub struct AcceptConsentRequest {
+    pub document_ids: Vec<Uuid>,
+}
+
+/// 同意协议结果
+#[derive(Debug, Clone, Serialize)]
+pub struct ConsentResult {
+    /// 本次新增的同意记录（之前已同意的版本不重复记录）
+    pub accepted: Vec<UserConsent>,
+    /// 仍未同意的生效版本
+    pub pending: Vec<PolicyDocument>,
+}
+
+/// 管理员发布协议新版本
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreatePolicyDocumentRequest {
+    pub policy_type: PolicyType,
+    pub version: String,
+    pub title: String,
+    pub content: String,
+    /// 是否立即生效，生效后所有用户需要重新同意
+    #[serde(default)]
+    pub activate: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_type_round_trip() {
+        for policy_type in PolicyType::ALL {
+            assert_eq!(PolicyType::parse(policy_type.as_str()), Some(policy_type));
+        }
+        assert_eq!(PolicyType::parse("cookies"), None);
+
+        let request: CreatePolicyDocumentRequest = serde_json::from_str(
+            r#"{"policy_type":"privacy","version":"2026-10","title":"隐私政策","content":"..."}"#,
+        ).unwrap();
+        assert_eq!(request.policy_type, PolicyType::Privacy);
+        assert!(!request.activate);
+    }
+}
@@ -0,0 +1,169 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::login_log::LoginLog;
+
+/// 数据导出任务状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DataExportStatus {
+    Pending,
+    Processing,
+    Ready,
+    Failed,
+    Expired,
+}
+
+impl DataExportStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DataExportStatus::Pending => "pending",
+            DataExportStatus::Processing => "processing",
+            DataExportStatus::Ready => "ready",
+            DataExportStatus::Failed => "failed",
+            DataExportStatus::Expired => "expired",
+        }
+    }
+
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "pending" => DataExportStatus::Pending,
+            "processing" => DataExportStatus::Processing,
+            "ready" => DataExportStatus::Ready,
+            "expired" => DataExportStatus::Expired,
+            _ => DataExportStatus::Failed,
+        }
+    }
+
+    /// 任务是否仍在进行中
+    pub fn is_in_progress(&self) -> bool {
+        matches!(self, DataExportStatus::Pending | DataExportStatus::Processing)
+    }
+}
+
+/// 用户数据导出任务
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataExport {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub status: DataExportStatus,
+    /// 文件在存储中的 key，不对外暴露
+    #[serde(skip)]
+    pub file_path: Option<String>,
+    pub file_size: Option<i64>,
+    pub error_message: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl DataExport {
+    /// 导出文件是否可以下载
+    pub fn is_downloadable(&self, now: DateTime<Utc>) -> bool {
+        self.status == DataExportStatus::Ready
+            && self.file_path.is_some()
+            && self.expires_at.is_none_or(|expires_at| expires_at > now)
+    }
+
+    /// 下载地址，需要登录，跳转到存储的限时下载地址
+    pub fn download_url(&self) -> String {
+        format!("/api/auth/my-data/{}/download", self.id)
+    }
+
+    /// 下载时使用的文件名
+    pub fn file_name(&self) -> String {
+        format!("my-data-{}.json", self.created_at.format("%Y%m%d%H%M%S"))
+    }
+}
+
+/// 导出数据中的用户资料（不包含会话密钥等敏感字段）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedProfile {
+    pub id: Uuid,
+    pub username: String,
+    pub email: String,
+    pub full_name: Option<String>,
+    pub avatar_url: Option<String>,
+    pub is_admin: bool,
+    pub is_guest: bool,
+    pub wx_openid: Option<String>,
+    pub wx_unionid: Option<String>,
+    pub last_login_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// 导出数据中的会话记录（不包含会话令牌）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedSession {
+    pub id: Uuid,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// 用户数据导出文件内容（提交数据量可能很大，写入文件时以 `submissions` 数组流式追加）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserDataArchive {
+    pub generated_at: DateTime<Utc>,
+    pub profile: ArchivedProfile,
+    pub login_history: Vec<LoginLog>,
+    pub sessions: Vec<ArchivedSession>,
+}
+
+/// 数据导出请求的处理结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataExportInfo {
+    #[serde(flatten)]
+    pub export: DataExport,
+    pub download_url: Option<String>,
+    /// 本次请求是否新建了导出任务
+    pub newly_requested: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_export(status: DataExportStatus) -> DataExport {
+        DataExport {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            status,
+            file_path: Some("exports/sample.json".to_string()),
+            file_size: Some(128),
+            error_message: None,
+            created_at: Utc::now(),
+            completed_at: Some(Utc::now()),
+            expires_at: Some(Utc::now() + chrono::Duration::hours(1)),
+        }
+    }
+
+    #[test]
+    fn test_status_round_trip() {
+        for status in [
+            DataExportStatus::Pending,
+            DataExportStatus::Processing,
+            DataExportStatus::Ready,
+            DataExportStatus::Failed,
+            DataExportStatus::Expired,
+        ] {
+            assert_eq!(DataExportStatus::parse(status.as_str()), status);
+        }
+        assert_eq!(DataExportStatus::parse("unknown"), DataExportStatus::Failed);
+    }
+
+    #[test]
+    fn test_is_downloadable() {
+        let now = Utc::now();
+        assert!(sample_export(DataExportStatus::Ready).is_downloadable(now));
+        assert!(!sample_export(DataExportStatus::Processing).is_downloadable(now));
+
+        let mut expired = sample_export(DataExportStatus::Ready);
+        expired.expires_at = Some(now - chrono::Duration::minutes(1));
+        assert!(!expired.is_downloadable(now));
+    }
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+
+/// 表单草稿（保存在 Redis 中，按用户和表单键区分）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FormDraft {
+    pub form_key: String,
+    /// 表单内容，由前端决定格式
+    pub data: serde_json::Value,
+    pub updated_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
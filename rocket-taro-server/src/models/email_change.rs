@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// 确认令牌类型：分别发送到当前邮箱和新邮箱
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmailChangeTokenType {
+    Current,
+    New,
+}
+
+/// POST /api/auth/email-change 请求
+#[derive(Debug, Clone, Deserialize)]
+pub struct EmailChangeRequest {
+    pub new_email: String,
+    /// 需要重新验证当前密码
+    pub current_password: String,
+}
+
+/// POST /api/auth/email-change/confirm 请求，令牌来自确认邮件中的链接
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConfirmEmailChangeRequest {
+    pub token: String,
+}
+
+/// 待确认的邮箱修改（每个用户最多一条，重新申请时覆盖）
+#[derive(Debug, Clone)]
+pub struct PendingEmailChange {
+    pub user_id: Uuid,
+    pub pending_email: String,
+    /// 发起修改的会话，修改生效时保留，其余会话失效
+    pub session_id: Option<Uuid>,
+    /// 当前邮箱为微信占位邮箱时不需要当前邮箱确认
+    pub requires_current: bool,
+    pub current_confirmed_at: Option<DateTime<Utc>>,
+    pub new_confirmed_at: Option<DateTime<Utc>>,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PendingEmailChange {
+    /// 尚未确认的令牌类型
+    pub fn awaiting(&self) -> Vec<EmailChangeTokenType> {
+        let mut awaiting = Vec::new();
+        if self.requires_current && self.current_confirmed_at.is_none() {
+            awaiting.push(EmailChangeTokenType::Current);
+        }
+        if self.new_confirmed_at.is_none() {
+            awaiting.push(EmailChangeTokenType::New);
+        }
+        awaiting
+    }
+}
+
+/// 确认一个令牌后的结果
+#[derive(Debug, Clone)]
+pub enum EmailChangeConfirmation {
+    /// 还需要另一个地址确认
+    Pending(PendingEmailChange),
+    /// 新旧邮箱都已确认，邮箱已修改
+    Applied {
+        user_id: Uuid,
+        email: String,
+        revoked_sessions: u64,
+    },
+}
+
+/// 返回给客户端的修改状态
+#[derive(Debug, Clone, Serialize)]
+pub struct EmailChangeStatus {
+    pub pending_email: String,
+    /// 完成修改还需要确认的地址
+    pub awaiting: Vec<EmailChangeTokenType>,
+    /// 为 true 时邮箱已修改
+    pub completed: bool,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl From<PendingEmailChange> for EmailChangeStatus {
+    fn from(pending: PendingEmailChange) -> Self {
+        Self {
+            awaiting: pending.awaiting(),
+            pending_email: pending.pending_email,
+            completed: false,
+            expires_at: Some(pending.expires_at),
+        }
+    }
+}
+
+// 确认令牌生成（十六进制，可直接放入链接）
+pub fn generate_email_change_token() -> String {
+    hex::encode(rand::random::<[u8; 32]>())
+}
+
+// 确认令牌摘要，数据库中只保存该值（SHA-256 十六进制）
+pub fn hash_email_change_token(token: &str) -> String {
+    use sha2::{Digest, Sha256};
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending(requires_current: bool, current_confirmed: bool, new_confirmed: bool) -> PendingEmailChange {
+        let now = Utc::now();
+        PendingEmailChange {
+            user_id: Uuid::new_v4(),
+            pending_email: "new@example.com".to_string(),
+            session_id: None,
+            requires_current,
+            current_confirmed_at: current_confirmed.then_some(now),
+            new_confirmed_at: new_confirmed.then_some(now),
+            expires_at: now,
+            created_at: now,
+        }
+    }
+
+    #[test]
+    fn test_awaiting_confirmations() {
+        assert_eq!(pending(true, false, false).awaiting(), vec![EmailChangeTokenType::Current, EmailChangeTokenType::New]);
+        assert_eq!(pending(true, true, false).awaiting(), vec![EmailChangeTokenType::New]);
+        assert_eq!(pending(true, false, true).awaiting(), vec![EmailChangeTokenType::Current]);
+        assert!(pending(false, false, true).awaiting().is_empty());
+    }
+
+    #[test]
+    fn test_token_hash() {
+        let token = generate_email_change_token();
+        assert_eq!(token.len(), 64);
+        assert_ne!(hash_email_change_token(&token), token);
+        assert_eq!(hash_email_change_token(&token), hash_email_change_token(&token));
+    }
+}
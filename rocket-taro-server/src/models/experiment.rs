@@ -0,0 +1,74 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::route_decision::RouteDecision;
+
+/// 路由决策器按实验分组选择流程时记录的决策规则名
+pub const EXPERIMENT_ASSIGNED_RULE: &str = "experiment.assigned";
+
+/// 用户在一个实验中的分组
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExperimentAssignment {
+    pub experiment: String,
+    pub variant: String,
+}
+
+impl ExperimentAssignment {
+    /// 从生成指令时的路由决策中取出实验分组，用于记录曝光
+    pub fn from_decisions(decisions: &[RouteDecision]) -> Vec<Self> {
+        decisions.iter()
+            .filter(|decision| decision.rule == EXPERIMENT_ASSIGNED_RULE)
+            .filter_map(|decision| serde_json::from_value(decision.inputs.clone()).ok())
+            .collect()
+    }
+}
+
+/// 一个分组的曝光统计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VariantExposure {
+    pub variant: String,
+    /// 配置的权重，已从配置中移除的分组为 0
+    pub weight: u32,
+    /// 分到该分组并收到过对应指令的用户数
+    pub users: i64,
+    /// 下发对应指令的总次数
+    pub exposures: i64,
+}
+
+/// 实验的分组统计（管理端）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExperimentReport {
+    pub name: String,
+    /// 当前是否在实验时间内
+    pub running: bool,
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: Option<DateTime<Utc>>,
+    pub variants: Vec<VariantExposure>,
+}
+
+/// 按实验和分组汇总的曝光数（对应 experiment_exposures 表的聚合结果）
+#[derive(Debug, Clone)]
+pub struct ExposureCount {
+    pub experiment: String,
+    pub variant: String,
+    pub users: i64,
+    pub exposures: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_assignments_from_decisions() {
+        let decisions = vec![
+            RouteDecision::new(EXPERIMENT_ASSIGNED_RULE, json!({ "experiment": "login_onboarding", "variant": "dialog" })),
+            RouteDecision::new("login.new_user", json!({ "route": "/pages/home/home", "onboarding": "dialog" })),
+        ];
+        assert_eq!(
+            ExperimentAssignment::from_decisions(&decisions),
+            vec![ExperimentAssignment { experiment: "login_onboarding".to_string(), variant: "dialog".to_string() }]
+        );
+    }
+}
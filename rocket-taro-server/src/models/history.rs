@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// 记录变更历史的实体
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryEntity {
+    Users,
+    UserData,
+}
+
+impl HistoryEntity {
+    /// 解析接口路径中的实体名（users、user-data）
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "users" => Some(HistoryEntity::Users),
+            "user-data" | "user_data" => Some(HistoryEntity::UserData),
+            _ => None,
+        }
+    }
+
+    /// 对应的历史表名（由迁移 005 的触发器写入）
+    pub fn history_table(&self) -> &'static str {
+        match self {
+            HistoryEntity::Users => "users_history",
+            HistoryEntity::UserData => "user_data_history",
+        }
+    }
+}
+
+/// 一次行变更：修改前后的完整数据（不含凭据等排除的列），删除时 new_data 为 None
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RowHistory {
+    pub id: Uuid,
+    pub entity: HistoryEntity,
+    pub row_id: Uuid,
+    /// update 或 delete
+    pub operation: String,
+    /// 发生变化的字段，删除时为空
+    pub changed_fields: Vec<String>,
+    pub old_data: serde_json::Value,
+    pub new_data: Option<serde_json::Value>,
+    /// 执行变更的用户，应用未设置时为 None
+    pub changed_by: Option<Uuid>,
+    pub changed_at: DateTime<Utc>,
+}
+
+/// 比较修改前后的数据，返回值不同的字段名（按字母顺序）
+pub fn changed_fields(old_data: &serde_json::Value, new_data: &serde_json::Value) -> Vec<String> {
+    let (Some(old), Some(new)) = (old_data.as_object(), new_data.as_object()) else {
+        return Vec::new();
+    };
+    let mut fields: Vec<String> = old.keys()
+        .chain(new.keys().filter(|key| !old.contains_key(*key)))
+        .filter(|key| old.get(*key) != new.get(*key))
+        .cloned()
+        .collect();
+    fields.sort();
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_entity() {
+        assert_eq!(HistoryEntity::parse("users"), Some(HistoryEntity::Users));
+        assert_eq!(HistoryEntity::parse("user-data"), Some(HistoryEntity::UserData));
+        assert_eq!(HistoryEntity::parse("orders"), None);
+    }
+
+    #[test]
+    fn test_changed_fields() {
+        let old = json!({ "name": "张三", "email": "a@example.com", "phone": null, "status": "new" });
+        let new = json!({ "name": "张三", "email": "b@example.com", "phone": "+8613812345678", "status": "new", "extra": 1 });
+        assert_eq!(changed_fields(&old, &new), vec!["email", "extra", "phone"]);
+        assert!(changed_fields(&old, &old).is_empty());
+        assert!(changed_fields(&old, &serde_json::Value::Null).is_empty());
+    }
+}
@@ -0,0 +1,247 @@
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// 实名认证状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IdentityStatus {
+    /// 未提交
+    Unverified,
+    /// 已提交，等待认证服务返回结果
+    Pending,
+    Verified,
+    Rejected,
+}
+
+impl IdentityStatus {
+    pub const ALL: [IdentityStatus; 4] = [
+        IdentityStatus::Unverified,
+        IdentityStatus::Pending,
+        IdentityStatus::Verified,
+        IdentityStatus::Rejected,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IdentityStatus::Unverified => "unverified",
+            IdentityStatus::Pending => "pending",
+            IdentityStatus::Verified => "verified",
+            IdentityStatus::Rejected => "rejected",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|status| status.as_str() == value)
+    }
+}
+
+/// 用户的实名认证记录（对应 identity_verifications 表），不保存完整的姓名和证件号
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdentityVerification {
+    pub user_id: Uuid,
+    pub status: String,
+    pub masked_name: Option<String>,
+    pub masked_id_number: Option<String>,
+    pub birth_date: Option<NaiveDate>,
+    pub provider: Option<String>,
+    pub provider_reference: Option<String>,
+    pub failure_reason: Option<String>,
+    pub attempts: i32,
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    pub verified_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl IdentityVerification {
+    pub fn status(&self) -> IdentityStatus {
+        IdentityStatus::parse(&self.status).unwrap_or(IdentityStatus::Unverified)
+    }
+}
+
+/// 返回给客户端的实名认证状态
+#[derive(Debug, Clone, Serialize)]
+pub struct IdentityVerificationStatus {
+    pub status: IdentityStatus,
+    pub masked_name: Option<String>,
+    pub masked_id_number: Option<String>,
+    /// 是否达到年龄要求，未认证时为 None
+    pub meets_age_requirement: Option<bool>,
+    pub failure_reason: Option<String>,
+    pub verified_at: Option<DateTime<Utc>>,
+}
+
+impl IdentityVerificationStatus {
+    pub fn unverified() -> Self {
+        Self {
+            status: IdentityStatus::Unverified,
+            masked_name: None,
+            masked_id_number: None,
+            meets_age_requirement: None,
+            failure_reason: None,
+            verified_at: None,
+        }
+    }
+
+    pub fn from_record(record: &IdentityVerification, min_age: u32, today: NaiveDate) -> Self {
+        let status = record.status();
+        Self {
+            status,
+            masked_name: record.masked_name.clone(),
+            masked_id_number: record.masked_id_number.clone(),
+            meets_age_requirement: (status == IdentityStatus::Verified)
+                .then(|| record.birth_date.is_some_and(|birth_date| age_on(birth_date, today) >= min_age)),
+            failure_reason: record.failure_reason.clone(),
+            verified_at: record.verified_at,
+        }
+    }
+}
+
+/// 提交实名认证，姓名和证件号只转交认证服务，不写入日志和数据库
+#[derive(Clone, Deserialize)]
+pub struct SubmitIdentityRequest {
+    pub real_name: String,
+    pub id_number: String,
+    /// 认证通过后返回的页面，未提供时返回首页
+    #[serde(default)]
+    pub redirect: Option<String>,
+}
+
+/// 功能对实名认证的要求
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdentityRequirement {
+    /// 完成实名认证
+    RealName,
+    /// 完成实名认证且达到年龄限制
+    Adult,
+}
+
+/// 需要实名认证才能使用的功能的拦截原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerificationBlock {
+    /// 未完成实名认证
+    Unverified,
+    /// 已认证但未达到年龄要求
+    Underage { min_age: u32 },
+}
+
+impl VerificationBlock {
+    /// 按认证记录判断是否满足要求，满足时返回 None
+    pub fn check(
+        record: Option<&IdentityVerification>,
+        requirement: IdentityRequirement,
+        min_age: u32,
+        today: NaiveDate,
+    ) -> Option<Self> {
+        let Some(record) = record.filter(|record| record.status() == IdentityStatus::Verified) else {
+            return Some(VerificationBlock::Unverified);
+        };
+        match requirement {
+            IdentityRequirement::RealName => None,
+            IdentityRequirement::Adult => {
+                // 认证服务未返回出生日期且无法从证件号推算时按未成年处理
+                let adult = record.birth_date.is_some_and(|birth_date| age_on(birth_date, today) >= min_age);
+                (!adult).then_some(VerificationBlock::Underage { min_age })
+            }
+        }
+    }
+}
+
+/// 校验 18 位居民身份证号（含校验位），返回出生日期
+pub fn parse_resident_id(id_number: &str) -> Option<NaiveDate> {
+    const WEIGHTS: [u32; 17] = [7, 9, 10, 5, 8, 4, 2, 1, 6, 3, 7, 9, 10, 5, 8, 4, 2];
+    const CHECK_CODES: [char; 11] = ['1', '0', 'X', '9', '8', '7', '6', '5', '4', '3', '2'];
+
+    let chars: Vec<char> = id_number.chars().map(|c| c.to_ascii_uppercase()).collect();
+    if chars.len() != 18 || !chars[..17].iter().all(char::is_ascii_digit) {
+        return None;
+    }
+    let sum: u32 = chars[..17].iter().zip(WEIGHTS).map(|(c, weight)| c.to_digit(10).unwrap() * weight).sum();
+    if CHECK_CODES[(sum % 11) as usize] != chars[17] {
+        return None;
+    }
+    let birth: String = chars[6..14].iter().collect();
+    NaiveDate::parse_from_str(&birth, "%Y%m%d").ok()
+}
+
+/// 周岁年龄
+pub fn age_on(birth_date: NaiveDate, today: NaiveDate) -> u32 {
+    let mut age = today.year() - birth_date.year();
+    if (today.month(), today.day()) < (birth_date.month(), birth_date.day()) {
+        age -= 1;
+    }
+    age.max(0) as u32
+}
+
+/// 姓名只保留第一个字，如 `张*`
+pub fn mask_name(name: &str) -> String {
+    let mut chars = name.trim().chars();
+    match chars.next() {
+        Some(first) => format!("{}{}", first, "*".repeat(chars.count().max(1))),
+        None => String::new(),
+    }
+}
+
+/// 证件号只保留首尾各一位
+pub fn mask_id_number(id_number: &str) -> String {
+    let chars: Vec<char> = id_number.trim().chars().collect();
+    match chars.as_slice() {
+        [first, middle @ .., last] => format!("{}{}{}", first, "*".repeat(middle.len()), last),
+        _ => "*".repeat(chars.len()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_resident_id() {
+        assert_eq!(parse_resident_id("11010519491231002X"), NaiveDate::from_ymd_opt(1949, 12, 31));
+        assert_eq!(parse_resident_id("11010519491231002x"), NaiveDate::from_ymd_opt(1949, 12, 31));
+        // 校验位错误
+        assert_eq!(parse_resident_id("110105194912310021"), None);
+        assert_eq!(parse_resident_id("1101051949123100"), None);
+        assert_eq!(parse_resident_id(""), None);
+    }
+
+    #[test]
+    fn test_age_and_masking() {
+        let birth = NaiveDate::from_ymd_opt(2008, 10, 17).unwrap();
+        assert_eq!(age_on(birth, NaiveDate::from_ymd_opt(2026, 10, 16).unwrap()), 17);
+        assert_eq!(age_on(birth, NaiveDate::from_ymd_opt(2026, 10, 17).unwrap()), 18);
+
+        assert_eq!(mask_name("张三丰"), "张**");
+        assert_eq!(mask_name("李"), "李*");
+        assert_eq!(mask_id_number("11010519491231002X"), "1****************X");
+    }
+
+    #[test]
+    fn test_verification_block() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        let mut record = IdentityVerification {
+            user_id: Uuid::new_v4(),
+            status: "pending".to_string(),
+            masked_name: None,
+            masked_id_number: None,
+            birth_date: NaiveDate::from_ymd_opt(2010, 1, 1),
+            provider: None,
+            provider_reference: None,
+            failure_reason: None,
+            attempts: 1,
+            last_attempt_at: None,
+            verified_at: None,
+            updated_at: Utc::now(),
+        };
+        assert_eq!(VerificationBlock::check(None, IdentityRequirement::RealName, 18, today), Some(VerificationBlock::Unverified));
+        assert_eq!(VerificationBlock::check(Some(&record), IdentityRequirement::RealName, 18, today), Some(VerificationBlock::Unverified));
+
+        record.status = "verified".to_string();
+        assert_eq!(VerificationBlock::check(Some(&record), IdentityRequirement::RealName, 18, today), None);
+        assert_eq!(
+            VerificationBlock::check(Some(&record), IdentityRequirement::Adult, 18, today),
+            Some(VerificationBlock::Underage { min_age: 18 })
+        );
+        assert_eq!(VerificationBlock::check(Some(&record), IdentityRequirement::Adult, 16, today), None);
+    }
+}
@@ -0,0 +1,35 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::auth::UserInfo;
+
+/// 模拟登录提示条，通过 ProcessData 指令（`impersonation`）下发给客户端
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ImpersonationBanner {
+    pub impersonator_id: Uuid,
+    pub user_id: Uuid,
+    pub username: String,
+    pub expires_at: DateTime<Utc>,
+    pub message: String,
+}
+
+impl ImpersonationBanner {
+    pub fn new(impersonator_id: Uuid, user_id: Uuid, username: &str, expires_at: DateTime<Utc>) -> Self {
+        Self {
+            impersonator_id,
+            user_id,
+            username: username.to_string(),
+            expires_at,
+            message: format!("正在以用户 {} 的身份登录，部分敏感操作不可用", username),
+        }
+    }
+}
+
+/// POST /api/admin/impersonate/<user_id> 响应；令牌只在响应中返回，不写入管理员的 Cookie
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ImpersonationSession {
+    pub user: UserInfo,
+    pub session_token: String,
+    pub expires_at: DateTime<Utc>,
+}
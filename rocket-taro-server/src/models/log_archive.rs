@@ -0,0 +1,98 @@
+use chrono::{DateTime, Datelike, Months, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 按月归档的日志表
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchiveTable {
+    LoginLogs,
+    AuditLogs,
+}
+
+impl ArchiveTable {
+    pub const ALL: [ArchiveTable; 2] = [ArchiveTable::LoginLogs, ArchiveTable::AuditLogs];
+
+    /// 在线表
+    pub fn table_name(&self) -> &'static str {
+        match self {
+            ArchiveTable::LoginLogs => "login_logs",
+            ArchiveTable::AuditLogs => "audit_logs",
+        }
+    }
+
+    /// 按月分区的归档表
+    pub fn archive_table_name(&self) -> &'static str {
+        match self {
+            ArchiveTable::LoginLogs => "login_logs_archive",
+            ArchiveTable::AuditLogs => "audit_logs_archive",
+        }
+    }
+
+    /// 某月的分区名，如 `login_logs_archive_2026_10`
+    pub fn partition_name(&self, month: NaiveDate) -> String {
+        format!("{}_{:04}_{:02}", self.archive_table_name(), month.year(), month.month())
+    }
+
+    /// 从分区名解析月份，不是本表按月命名的分区时返回 None
+    pub fn partition_month(&self, partition: &str) -> Option<NaiveDate> {
+        let suffix = partition.strip_prefix(self.archive_table_name())?.strip_prefix('_')?;
+        let (year, month) = suffix.split_once('_')?;
+        if year.len() != 4 || month.len() != 2 {
+            return None;
+        }
+        NaiveDate::from_ymd_opt(year.parse().ok()?, month.parse().ok()?, 1)
+    }
+}
+
+/// 时间所在月份的第一天（UTC）
+pub fn month_start(at: DateTime<Utc>) -> NaiveDate {
+    at.date_naive().with_day(1).expect("every month has a first day")
+}
+
+/// 下个月的第一天
+pub fn next_month(month: NaiveDate) -> NaiveDate {
+    month + Months::new(1)
+}
+
+/// 单个表的归档结果
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TableArchiveReport {
+    pub table: ArchiveTable,
+    /// 移入归档表的行数
+    pub archived: u64,
+    /// 在线表中超过保留期直接删除的行数
+    pub purged: u64,
+    /// 超过保留期删除的归档分区
+    pub dropped_partitions: Vec<String>,
+}
+
+/// 一次归档任务的结果
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ArchiveReport {
+    pub tables: Vec<TableArchiveReport>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partition_name() {
+        let month = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        let name = ArchiveTable::LoginLogs.partition_name(month);
+        assert_eq!(name, "login_logs_archive_2026_03");
+        assert_eq!(ArchiveTable::LoginLogs.partition_month(&name), Some(month));
+
+        assert_eq!(ArchiveTable::AuditLogs.partition_month(&name), None);
+        assert_eq!(ArchiveTable::LoginLogs.partition_month("login_logs_archive_default"), None);
+        assert_eq!(ArchiveTable::LoginLogs.partition_month("login_logs_archive_2026_13"), None);
+    }
+
+    #[test]
+    fn test_month_bounds() {
+        let at = "2026-12-31T23:59:59Z".parse::<DateTime<Utc>>().unwrap();
+        let month = month_start(at);
+        assert_eq!(month, NaiveDate::from_ymd_opt(2026, 12, 1).unwrap());
+        assert_eq!(next_month(month), NaiveDate::from_ymd_opt(2027, 1, 1).unwrap());
+    }
+}
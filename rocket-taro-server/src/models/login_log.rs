@@ -0,0 +1,85 @@
+use std::net::IpAddr;
+
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// login_logs.username 列长度
+pub const MAX_LOGIN_LOG_USERNAME_CHARS: usize = 50;
+
+/// 一次登录尝试的记录
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LoginLog {
+    pub id: Uuid,
+    /// 登录失败且用户名不存在时为 None
+    pub user_id: Option<Uuid>,
+    pub username: String,
+    pub success: bool,
+    pub ip_address: Option<IpAddr>,
+    pub user_agent: Option<String>,
+    /// 失败原因；成功登录时为备注（如扫码登录）
+    pub failure_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// 待写入的登录记录
+#[derive(Debug, Clone)]
+pub struct NewLoginLog {
+    pub user_id: Option<Uuid>,
+    pub username: String,
+    pub success: bool,
+    pub ip_address: Option<IpAddr>,
+    pub user_agent: Option<String>,
+    pub failure_reason: Option<String>,
+}
+
+impl NewLoginLog {
+    /// 登录成功，`note` 记录登录方式等备注
+    pub fn success(user_id: Uuid, username: &str, note: Option<String>) -> Self {
+        Self::new(Some(user_id), username, true, note)
+    }
+
+    /// 登录失败
+    pub fn failure(user_id: Option<Uuid>, username: &str, reason: &str) -> Self {
+        Self::new(user_id, username, false, Some(reason.to_string()))
+    }
+
+    // 尝试登录时输入的用户名不受注册规则限制，超出列长度的部分截断
+    fn new(user_id: Option<Uuid>, username: &str, success: bool, failure_reason: Option<String>) -> Self {
+        Self {
+            user_id,
+            username: username.chars().take(MAX_LOGIN_LOG_USERNAME_CHARS).collect(),
+            success,
+            ip_address: None,
+            user_agent: None,
+            failure_reason,
+        }
+    }
+
+    pub fn client(mut self, ip_address: Option<IpAddr>, user_agent: Option<String>) -> Self {
+        self.ip_address = ip_address;
+        self.user_agent = user_agent;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_login_log() {
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let log = NewLoginLog::failure(None, &"用".repeat(80), "密码错误").client(Some(ip), None);
+        assert!(!log.success);
+        assert_eq!(log.username.chars().count(), MAX_LOGIN_LOG_USERNAME_CHARS);
+        assert_eq!(log.failure_reason.as_deref(), Some("密码错误"));
+        assert_eq!(log.ip_address, Some(ip));
+
+        let user_id = Uuid::new_v4();
+        let log = NewLoginLog::success(user_id, "alice", None);
+        assert!(log.success);
+        assert_eq!(log.user_id, Some(user_id));
+        assert_eq!(log.username, "alice");
+    }
+}
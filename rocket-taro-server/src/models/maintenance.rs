@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// 维护模式状态（保存在 Redis 中，所有实例共享）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MaintenanceState {
+    pub enabled: bool,
+    /// 展示给用户的维护说明
+    pub message: Option<String>,
+    /// 预计结束时间，到期后自动退出维护模式
+    pub ends_at: Option<DateTime<Utc>>,
+    pub updated_by: Option<Uuid>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// PUT /api/admin/maintenance 请求
+#[derive(Debug, Clone, Deserialize)]
+pub struct MaintenanceRequest {
+    pub enabled: bool,
+    pub message: Option<String>,
+    pub ends_at: Option<DateTime<Utc>>,
+}
@@ -3,4 +3,45 @@ pub mod user_data;
 pub mod auth;
 pub mod wx_auth;
 pub mod business_results;  // 新增：业务结果模型
-pub mod route_command;
\ No newline at end of file
+pub mod route_command;
+pub mod route_condition;
+pub mod route_preview;
+pub mod route_execution;
+pub mod client_capabilities;
+pub mod maintenance;
+pub mod announcement;
+pub mod settings;
+pub mod draft;
+pub mod email_change;
+pub mod remote_config;
+pub mod audit;
+pub mod notification;
+pub mod data_export;
+pub mod admin_stats;
+pub mod payment;
+pub mod order;
+pub mod webhook;
+pub mod side_effect;
+pub mod profile;
+pub mod account_recovery;
+pub mod qr_login;
+pub mod reauth;
+pub mod upload;
+pub mod history;
+pub mod login_log;
+pub mod log_archive;
+pub mod impersonation;
+pub mod cache_invalidation;
+pub mod session_binding;
+pub mod response_profile;
+pub mod batch;
+pub mod presence;
+pub mod api_quota;
+pub mod registration_review;
+pub mod consent;
+pub mod identity;
+pub mod moderation;
+pub mod avatar;
+pub mod storage;
+pub mod route_decision;
+pub mod experiment;
@@ -0,0 +1,184 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// 审核服务给出的处理建议
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModerationSuggest {
+    Pass,
+    /// 疑似违规，需要复核
+    Review,
+    /// 违规
+    Risky,
+}
+
+/// 一次审核的结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModerationVerdict {
+    pub suggest: ModerationSuggest,
+    /// 违规类型的说明，如 `广告`、`辱骂`
+    pub label: Option<String>,
+}
+
+impl ModerationVerdict {
+    pub fn pass() -> Self {
+        Self { suggest: ModerationSuggest::Pass, label: None }
+    }
+
+    pub fn new(suggest: ModerationSuggest, label: impl Into<String>) -> Self {
+        Self { suggest, label: Some(label.into()) }
+    }
+}
+
+/// 需要审核的用户提交内容
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModerationSubject {
+    /// 用户数据中的留言
+    UserDataMessage,
+    /// 资料中的昵称（full_name）
+    ProfileName,
+    /// 上传的图片
+    Upload,
+}
+
+impl ModerationSubject {
+    pub const ALL: [ModerationSubject; 3] = [
+        ModerationSubject::UserDataMessage,
+        ModerationSubject::ProfileName,
+        ModerationSubject::Upload,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModerationSubject::UserDataMessage => "user_data_message",
+            ModerationSubject::ProfileName => "profile_name",
+            ModerationSubject::Upload => "upload",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|subject| subject.as_str() == value)
+    }
+
+    fn display_name(&self) -> &'static str {
+        match self {
+            ModerationSubject::UserDataMessage => "留言",
+            ModerationSubject::ProfileName => "昵称",
+            ModerationSubject::Upload => "图片",
+        }
+    }
+}
+
+/// 提交内容未通过审核
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentRejection {
+    pub subject: ModerationSubject,
+    pub label: Option<String>,
+}
+
+impl ContentRejection {
+    /// 展示给用户的原因
+    pub fn message(&self) -> String {
+        let subject = self.subject.display_name();
+        let action = if self.subject == ModerationSubject::Upload { "更换后重新上传" } else { "修改后重新提交" };
+        match &self.label {
+            Some(label) => format!("{}可能包含{}相关的违规内容，请{}", subject, label, action),
+            None => format!("{}包含违规内容，请{}", subject, action),
+        }
+    }
+}
+
+/// 复核队列中记录的状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModerationCheckStatus {
+    /// 等待复核
+    Pending,
+    Passed,
+    /// 复核违规，内容已清除
+    Removed,
+    /// 达到最大复核次数仍无结论，需要人工处理
+    Failed,
+}
+
+impl ModerationCheckStatus {
+    pub const ALL: [ModerationCheckStatus; 4] = [
+        ModerationCheckStatus::Pending,
+        ModerationCheckStatus::Passed,
+        ModerationCheckStatus::Removed,
+        ModerationCheckStatus::Failed,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModerationCheckStatus::Pending => "pending",
+            ModerationCheckStatus::Passed => "passed",
+            ModerationCheckStatus::Removed => "removed",
+            ModerationCheckStatus::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|status| status.as_str() == value)
+    }
+}
+
+/// 加入复核队列的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecheckReason {
+    /// 审核服务建议复核
+    Review,
+    /// 审核服务不可用，提交时放行
+    Unavailable,
+}
+
+impl RecheckReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RecheckReason::Review => "review",
+            RecheckReason::Unavailable => "unavailable",
+        }
+    }
+}
+
+/// 复核队列中的一条记录（对应 moderation_checks 表），content 为提交时的内容
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModerationCheck {
+    pub id: Uuid,
+    pub subject_type: String,
+    pub subject_id: Uuid,
+    pub user_id: Option<Uuid>,
+    /// 提交者的微信 openid，微信审核接口据此判断用户风险
+    #[serde(skip)]
+    pub openid: Option<String>,
+    pub content: String,
+    pub reason: String,
+    pub status: String,
+    pub label: Option<String>,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub next_check_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub checked_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rejection_message() {
+        let rejection = ContentRejection { subject: ModerationSubject::ProfileName, label: Some("广告".to_string()) };
+        assert_eq!(rejection.message(), "昵称可能包含广告相关的违规内容，请修改后重新提交");
+
+        let rejection = ContentRejection { subject: ModerationSubject::Upload, label: None };
+        assert_eq!(rejection.message(), "图片包含违规内容，请更换后重新上传");
+
+        for subject in ModerationSubject::ALL {
+            assert_eq!(ModerationSubject::parse(subject.as_str()), Some(subject));
+        }
+        assert_eq!(ModerationCheckStatus::parse("removed"), Some(ModerationCheckStatus::Removed));
+    }
+}
@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// 站内通知
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Notification {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    /// 通知类别，例如 `data_export`
+    pub category: String,
+    pub title: String,
+    pub content: String,
+    /// 附加数据（例如下载地址）
+    pub data: serde_json::Value,
+    pub is_read: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// 待创建的通知
+#[derive(Debug, Clone)]
+pub struct NewNotification {
+    pub user_id: Uuid,
+    pub category: String,
+    pub title: String,
+    pub content: String,
+    pub data: serde_json::Value,
+}
+
+impl NewNotification {
+    pub fn new(user_id: Uuid, category: &str, title: &str, content: &str) -> Self {
+        Self {
+            user_id,
+            category: category.to_string(),
+            title: title.to_string(),
+            content: content.to_string(),
+            data: serde_json::json!({}),
+        }
+    }
+
+    /// 设置附加数据
+    pub fn data(mut self, data: serde_json::Value) -> Self {
+        self.data = data;
+        self
+    }
+}
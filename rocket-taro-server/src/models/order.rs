@@ -0,0 +1,199 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// 订单状态
+///
+/// 状态流转：pending → paid → completed，
+/// pending 可被用户取消（cancelled）或超时未支付自动关闭（expired）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OrderStatus {
+    /// 待支付
+    Pending,
+    Paid,
+    Completed,
+    Cancelled,
+    Expired,
+}
+
+impl OrderStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrderStatus::Pending => "pending",
+            OrderStatus::Paid => "paid",
+            OrderStatus::Completed => "completed",
+            OrderStatus::Cancelled => "cancelled",
+            OrderStatus::Expired => "expired",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(OrderStatus::Pending),
+            "paid" => Some(OrderStatus::Paid),
+            "completed" => Some(OrderStatus::Completed),
+            "cancelled" => Some(OrderStatus::Cancelled),
+            "expired" => Some(OrderStatus::Expired),
+            _ => None,
+        }
+    }
+
+    /// 可以转换到目标状态的前置状态
+    pub fn allowed_sources(target: OrderStatus) -> &'static [OrderStatus] {
+        match target {
+            OrderStatus::Pending => &[],
+            OrderStatus::Paid | OrderStatus::Cancelled | OrderStatus::Expired => &[OrderStatus::Pending],
+            OrderStatus::Completed => &[OrderStatus::Paid],
+        }
+    }
+
+    /// 是否允许从当前状态转换到目标状态
+    pub fn can_transition_to(&self, target: OrderStatus) -> bool {
+        Self::allowed_sources(target).contains(self)
+    }
+
+    /// 解析列表查询的状态筛选，支持逗号分隔多个状态，空值表示不筛选
+    pub fn parse_filter(value: Option<&str>) -> Result<Vec<OrderStatus>, String> {
+        let Some(value) = value else {
+            return Ok(Vec::new());
+        };
+
+        let mut statuses = Vec::new();
+        for part in value.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+            let status = Self::parse(part).ok_or_else(|| format!("无效的订单状态: {}", part))?;
+            if !statuses.contains(&status) {
+                statuses.push(status);
+            }
+        }
+        Ok(statuses)
+    }
+}
+
+/// 订单商品
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderItem {
+    pub name: String,
+    pub quantity: i32,
+    /// 单价（分）
+    pub unit_price: i64,
+}
+
+impl OrderItem {
+    /// 小计（分），溢出时返回 None
+    pub fn subtotal(&self) -> Option<i64> {
+        self.unit_price.checked_mul(self.quantity as i64)
+    }
+}
+
+/// 订单
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Order {
+    pub id: Uuid,
+    /// 订单号，对外展示和查询使用
+    pub order_no: String,
+    pub user_id: Uuid,
+    pub title: String,
+    pub items: Vec<OrderItem>,
+    /// 订单总金额（分）
+    pub total_amount: i64,
+    pub status: OrderStatus,
+    /// 关联的支付单商户订单号
+    pub out_trade_no: Option<String>,
+    pub remark: Option<String>,
+    /// 未支付订单的自动关闭时间
+    pub expires_at: DateTime<Utc>,
+    pub paid_at: Option<DateTime<Utc>>,
+    pub cancelled_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// 创建订单请求，总金额由服务端根据商品计算
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateOrderRequest {
+    pub title: String,
+    pub items: Vec<OrderItem>,
+    pub remark: Option<String>,
+}
+
+impl CreateOrderRequest {
+    /// 校验订单内容并计算总金额（分）
+    pub fn validate(&self, max_items: usize, max_amount: i64) -> Result<i64, String> {
+        let title = self.title.trim();
+        if title.is_empty() || title.chars().count() > 100 {
+            return Err("订单标题不能为空且不能超过100个字符".to_string());
+        }
+        if self.items.is_empty() || self.items.len() > max_items {
+            return Err(format!("订单商品数量须在1到{}之间", max_items));
+        }
+        if self.remark.as_ref().is_some_and(|remark| remark.chars().count() > 500) {
+            return Err("订单备注不能超过500个字符".to_string());
+        }
+
+        let mut total: i64 = 0;
+        for item in &self.items {
+            if item.name.trim().is_empty() {
+                return Err("商品名称不能为空".to_string());
+            }
+            if item.quantity <= 0 || item.unit_price <= 0 {
+                return Err(format!("商品 {} 的数量或单价无效", item.name.trim()));
+            }
+            total = item.subtotal()
+                .and_then(|subtotal| total.checked_add(subtotal))
+                .ok_or_else(|| "订单金额超出上限".to_string())?;
+        }
+
+        if total > max_amount {
+            return Err("订单金额超出上限".to_string());
+        }
+        Ok(total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(name: &str, quantity: i32, unit_price: i64) -> OrderItem {
+        OrderItem { name: name.to_string(), quantity, unit_price }
+    }
+
+    #[test]
+    fn test_status_transitions() {
+        assert!(OrderStatus::Pending.can_transition_to(OrderStatus::Paid));
+        assert!(OrderStatus::Pending.can_transition_to(OrderStatus::Expired));
+        assert!(OrderStatus::Paid.can_transition_to(OrderStatus::Completed));
+        assert!(!OrderStatus::Paid.can_transition_to(OrderStatus::Cancelled));
+        assert!(!OrderStatus::Expired.can_transition_to(OrderStatus::Paid));
+    }
+
+    #[test]
+    fn test_parse_filter() {
+        assert!(OrderStatus::parse_filter(None).unwrap().is_empty());
+        assert_eq!(
+            OrderStatus::parse_filter(Some("pending, paid,pending")).unwrap(),
+            vec![OrderStatus::Pending, OrderStatus::Paid]
+        );
+        assert!(OrderStatus::parse_filter(Some("pending,unknown")).is_err());
+    }
+
+    #[test]
+    fn test_validate_total() {
+        let request = CreateOrderRequest {
+            title: "测试订单".to_string(),
+            items: vec![item("商品A", 2, 150), item("商品B", 1, 99)],
+            remark: None,
+        };
+        assert_eq!(request.validate(20, 10_000), Ok(399));
+        assert!(request.validate(1, 10_000).is_err());
+        assert!(request.validate(20, 300).is_err());
+
+        let overflow = CreateOrderRequest {
+            title: "溢出".to_string(),
+            items: vec![item("商品", i32::MAX, i64::MAX / 2)],
+            remark: None,
+        };
+        assert!(overflow.validate(20, i64::MAX).is_err());
+    }
+}
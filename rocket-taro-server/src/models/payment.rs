@@ -0,0 +1,218 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// 支付单状态
+///
+/// 状态流转：created → prepaid → paid → refunded，
+/// created / prepaid 可转为 closed 或 failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PaymentStatus {
+    /// 已创建，尚未向微信下单
+    Created,
+    /// 已获取 prepay_id，等待用户支付
+    Prepaid,
+    Paid,
+    Closed,
+    Failed,
+    Refunded,
+}
+
+impl PaymentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PaymentStatus::Created => "created",
+            PaymentStatus::Prepaid => "prepaid",
+            PaymentStatus::Paid => "paid",
+            PaymentStatus::Closed => "closed",
+            PaymentStatus::Failed => "failed",
+            PaymentStatus::Refunded => "refunded",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "created" => Some(PaymentStatus::Created),
+            "prepaid" => Some(PaymentStatus::Prepaid),
+            "paid" => Some(PaymentStatus::Paid),
+            "closed" => Some(PaymentStatus::Closed),
+            "failed" => Some(PaymentStatus::Failed),
+            "refunded" => Some(PaymentStatus::Refunded),
+            _ => None,
+        }
+    }
+
+    /// 可以转换到目标状态的前置状态
+    pub fn allowed_sources(target: PaymentStatus) -> &'static [PaymentStatus] {
+        match target {
+            PaymentStatus::Created => &[],
+            PaymentStatus::Prepaid => &[PaymentStatus::Created],
+            PaymentStatus::Paid => &[PaymentStatus::Created, PaymentStatus::Prepaid],
+            PaymentStatus::Closed | PaymentStatus::Failed => &[PaymentStatus::Created, PaymentStatus::Prepaid],
+            PaymentStatus::Refunded => &[PaymentStatus::Paid],
+        }
+    }
+
+    /// 是否允许从当前状态转换到目标状态
+    pub fn can_transition_to(&self, target: PaymentStatus) -> bool {
+        Self::allowed_sources(target).contains(self)
+    }
+
+    /// 是否为终态（不会再因支付回调变化）
+    pub fn is_final(&self) -> bool {
+        matches!(self, PaymentStatus::Paid | PaymentStatus::Closed | PaymentStatus::Failed | PaymentStatus::Refunded)
+    }
+
+    /// 将微信支付 trade_state 映射为支付单状态
+    pub fn from_trade_state(trade_state: &str) -> Option<Self> {
+        match trade_state {
+            "SUCCESS" => Some(PaymentStatus::Paid),
+            "CLOSED" | "REVOKED" => Some(PaymentStatus::Closed),
+            "PAYERROR" => Some(PaymentStatus::Failed),
+            "REFUND" => Some(PaymentStatus::Refunded),
+            _ => None,
+        }
+    }
+}
+
+/// 支付单
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Payment {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    /// 商户订单号
+    pub out_trade_no: String,
+    pub description: String,
+    /// 金额（分）
+    pub amount: i64,
+    pub currency: String,
+    pub status: PaymentStatus,
+    #[serde(skip_serializing)]
+    pub prepay_id: Option<String>,
+    /// 微信支付订单号
+    pub transaction_id: Option<String>,
+    pub paid_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// 创建支付单请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatePaymentRequest {
+    pub description: String,
+    /// 金额（分）
+    pub amount: i64,
+}
+
+/// 小程序 wx.requestPayment 所需参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestPaymentParams {
+    pub time_stamp: String,
+    pub nonce_str: String,
+    pub package: String,
+    pub sign_type: String,
+    pub pay_sign: String,
+}
+
+/// 下单结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentOrderResult {
+    pub payment: Payment,
+    pub pay_params: RequestPaymentParams,
+}
+
+/// 微信支付回调通知
+#[derive(Debug, Clone, Deserialize)]
+pub struct WechatPayNotification {
+    pub id: String,
+    pub event_type: String,
+    pub resource: WechatPayNotificationResource,
+}
+
+/// 回调通知中的加密资源
+#[derive(Debug, Clone, Deserialize)]
+pub struct WechatPayNotificationResource {
+    pub algorithm: String,
+    pub ciphertext: String,
+    pub nonce: String,
+    pub associated_data: Option<String>,
+}
+
+/// 解密后的支付结果
+#[derive(Debug, Clone, Deserialize)]
+pub struct WechatPayTransaction {
+    pub appid: String,
+    pub mchid: String,
+    pub out_trade_no: String,
+    pub transaction_id: Option<String>,
+    pub trade_state: String,
+    pub success_time: Option<DateTime<Utc>>,
+    pub amount: Option<WechatPayTransactionAmount>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WechatPayTransactionAmount {
+    pub total: i64,
+}
+
+impl WechatPayTransaction {
+    /// 校验支付结果属于本商户和小程序，且金额与支付单一致；支付成功的通知必须携带金额
+    pub fn check(&self, app_id: &str, mch_id: &str, expected_amount: i64) -> Result<(), String> {
+        if self.mchid != mch_id || self.appid != app_id {
+            return Err("支付结果的商户号或 AppID 不匹配".to_string());
+        }
+        match &self.amount {
+            Some(amount) if amount.total != expected_amount => Err("支付金额不一致".to_string()),
+            None if PaymentStatus::from_trade_state(&self.trade_state) == Some(PaymentStatus::Paid) => {
+                Err("支付结果缺少金额".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_transitions() {
+        assert!(PaymentStatus::Created.can_transition_to(PaymentStatus::Prepaid));
+        assert!(PaymentStatus::Prepaid.can_transition_to(PaymentStatus::Paid));
+        assert!(PaymentStatus::Paid.can_transition_to(PaymentStatus::Refunded));
+        assert!(!PaymentStatus::Paid.can_transition_to(PaymentStatus::Closed));
+        assert!(!PaymentStatus::Closed.can_transition_to(PaymentStatus::Paid));
+        assert!(!PaymentStatus::Refunded.can_transition_to(PaymentStatus::Paid));
+    }
+
+    #[test]
+    fn test_trade_state_mapping() {
+        assert_eq!(PaymentStatus::from_trade_state("SUCCESS"), Some(PaymentStatus::Paid));
+        assert_eq!(PaymentStatus::from_trade_state("PAYERROR"), Some(PaymentStatus::Failed));
+        assert_eq!(PaymentStatus::from_trade_state("USERPAYING"), None);
+    }
+
+    fn transaction(trade_state: &str, amount: Option<i64>) -> WechatPayTransaction {
+        WechatPayTransaction {
+            appid: "wx-app".to_string(),
+            mchid: "1900000001".to_string(),
+            out_trade_no: "ORDER1".to_string(),
+            transaction_id: None,
+            trade_state: trade_state.to_string(),
+            success_time: None,
+            amount: amount.map(|total| WechatPayTransactionAmount { total }),
+        }
+    }
+
+    #[test]
+    fn test_transaction_check() {
+        assert!(transaction("SUCCESS", Some(100)).check("wx-app", "1900000001", 100).is_ok());
+        assert!(transaction("SUCCESS", Some(99)).check("wx-app", "1900000001", 100).is_err());
+        assert!(transaction("SUCCESS", None).check("wx-app", "1900000001", 100).is_err());
+        assert!(transaction("CLOSED", None).check("wx-app", "1900000001", 100).is_ok());
+        assert!(transaction("SUCCESS", Some(100)).check("wx-other", "1900000001", 100).is_err());
+        assert!(transaction("SUCCESS", Some(100)).check("wx-app", "1900000002", 100).is_err());
+    }
+}
@@ -0,0 +1,22 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 按平台划分的在线用户数
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PlatformPresence {
+    pub miniprogram: u64,
+    pub h5: u64,
+    pub admin: u64,
+}
+
+/// 在线用户统计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresenceStats {
+    /// 窗口内有请求的用户数，多端同时在线的用户只计一次
+    pub online_users: u64,
+    /// 各平台在线用户数，同一用户可能同时计入多个平台
+    pub by_platform: PlatformPresence,
+    /// 在线判定窗口（秒）
+    pub window_secs: i64,
+    pub generated_at: DateTime<Utc>,
+}
@@ -0,0 +1,155 @@
+use serde::{Deserialize, Serialize};
+use super::auth::User;
+
+/// 微信登录自动生成的占位邮箱后缀，视为未填写邮箱
+pub const PLACEHOLDER_EMAIL_SUFFIX: &str = "@wx.temp";
+
+/// 资料完善流程的字段，每个字段对应步骤条中的一步，按展示顺序排列
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProfileField {
+    FullName,
+    Email,
+    AvatarUrl,
+}
+
+impl ProfileField {
+    pub const ALL: [ProfileField; 3] = [ProfileField::FullName, ProfileField::Email, ProfileField::AvatarUrl];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProfileField::FullName => "full_name",
+            ProfileField::Email => "email",
+            ProfileField::AvatarUrl => "avatar_url",
+        }
+    }
+
+    /// 必填字段缺失时登录后引导完善资料，选填字段只在流程中一并展示
+    pub fn is_required(&self) -> bool {
+        !matches!(self, ProfileField::AvatarUrl)
+    }
+
+    /// 用户是否缺少该字段
+    pub fn is_missing(&self, user: &User) -> bool {
+        match self {
+            ProfileField::FullName => user.full_name.as_deref().is_none_or(|name| name.trim().is_empty()),
+            ProfileField::Email => user.email.is_empty() || user.email.ends_with(PLACEHOLDER_EMAIL_SUFFIX),
+            ProfileField::AvatarUrl => user.avatar_url.as_deref().is_none_or(str::is_empty),
+        }
+    }
+}
+
+/// 资料完善状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileCompletion {
+    /// 尚未填写的字段（步骤条的剩余步骤）
+    pub missing_fields: Vec<ProfileField>,
+    /// 必填字段是否已全部填写
+    pub completed: bool,
+    /// 用户资料的版本号，提交下一步时通过 If-Match 或 version 字段带回
+    #[serde(default)]
+    pub version: i32,
+}
+
+impl ProfileCompletion {
+    /// 计算用户的资料完善状态，游客不需要完善资料
+    pub fn for_user(user: &User) -> Self {
+        let missing_fields: Vec<ProfileField> = if user.is_guest {
+            Vec::new()
+        } else {
+            ProfileField::ALL.into_iter().filter(|field| field.is_missing(user)).collect()
+        };
+        let completed = !missing_fields.iter().any(ProfileField::is_required);
+        Self { missing_fields, completed, version: user.version }
+    }
+}
+
+/// PATCH /api/profile 请求：提交步骤条中的一步
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProfileStepRequest {
+    pub field: ProfileField,
+    pub value: String,
+    /// 读取资料时的版本号，也可通过 If-Match 请求头提交
+    #[serde(default)]
+    pub version: Option<i32>,
+}
+
+/// 提交一步后的结果
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileStepResult {
+    /// 更新后的用户
+    pub user: User,
+    /// 更新后的完善状态
+    pub completion: ProfileCompletion,
+}
+
+/// PATCH /api/auth/profile 请求：非微信用户直接修改资料，未提供的字段保持不变
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProfileUpdateRequest {
+    pub full_name: Option<String>,
+    pub avatar_url: Option<String>,
+    /// 只能与当前邮箱相同，修改邮箱使用 POST /api/auth/email-change
+    pub email: Option<String>,
+    /// 读取资料时的版本号，也可通过 If-Match 请求头提交
+    #[serde(default)]
+    pub version: Option<i32>,
+}
+
+/// 资料修改结果
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileUpdateResult {
+    /// 更新后的用户
+    pub user: User,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn user(email: &str, full_name: Option<&str>, avatar_url: Option<&str>) -> User {
+        User {
+            id: Uuid::new_v4(),
+            username: "alice".to_string(),
+            email: email.to_string(),
+            full_name: full_name.map(str::to_string),
+            avatar_url: avatar_url.map(str::to_string),
+            is_active: true,
+            is_admin: false,
+            is_guest: false,
+            wx_openid: None,
+            wx_unionid: None,
+            wx_session_key: None,
+            last_login_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            version: 1,
+        }
+    }
+
+    #[test]
+    fn test_missing_fields() {
+        let wx_user = user("o1234567890@wx.temp", None, None);
+        let completion = ProfileCompletion::for_user(&wx_user);
+        assert_eq!(completion.missing_fields, ProfileField::ALL.to_vec());
+        assert!(!completion.completed);
+
+        // 只缺选填字段时视为已完善
+        let completion = ProfileCompletion::for_user(&user("alice@example.com", Some("Alice"), None));
+        assert_eq!(completion.missing_fields, vec![ProfileField::AvatarUrl]);
+        assert!(completion.completed);
+
+        let completion = ProfileCompletion::for_user(&user("alice@example.com", Some("  "), Some("https://img/a.png")));
+        assert_eq!(completion.missing_fields, vec![ProfileField::FullName]);
+    }
+
+    #[test]
+    fn test_guests_skip_profile_completion() {
+        let mut guest = user("", None, None);
+        guest.is_guest = true;
+        let completion = ProfileCompletion::for_user(&guest);
+        assert!(completion.missing_fields.is_empty());
+        assert!(completion.completed);
+    }
+}
@@ -0,0 +1,106 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use uuid::Uuid;
+
+/// 扫码登录推送事件名，网页端通过 SSE 订阅
+pub const QR_LOGIN_EVENT: &str = "qr_login";
+
+/// 扫码登录状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QrLoginStatus {
+    /// 等待扫码
+    Pending,
+    /// 已扫码，等待在小程序中确认
+    Scanned,
+    /// 已确认，等待网页端领取会话
+    Confirmed,
+    /// 已在小程序中拒绝
+    Rejected,
+}
+
+/// 缓存中的扫码登录记录，只保存网页端轮询令牌的摘要
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QrLoginTicket {
+    pub login_id: Uuid,
+    pub poll_token_hash: String,
+    pub status: QrLoginStatus,
+    /// 扫码的管理员
+    pub user_id: Option<Uuid>,
+    /// 网页端信息，用于创建会话
+    pub user_agent: Option<String>,
+    pub ip_address: Option<IpAddr>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl QrLoginTicket {
+    /// 剩余有效期（秒），更新记录时沿用原过期时间
+    pub fn remaining_secs(&self, now: DateTime<Utc>) -> usize {
+        (self.expires_at - now).num_seconds().max(1) as usize
+    }
+}
+
+/// 网页端获取的二维码：qr_content 用于生成二维码，poll_token 只保存在网页端
+#[derive(Debug, Serialize)]
+pub struct QrLoginChallenge {
+    pub login_id: Uuid,
+    pub qr_content: String,
+    pub poll_token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// 网页端查询的扫码状态
+#[derive(Debug, Serialize)]
+pub struct QrLoginState {
+    pub status: QrLoginStatus,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// 网页端轮询请求
+#[derive(Debug, Deserialize)]
+pub struct QrLoginPollRequest {
+    pub poll_token: String,
+}
+
+/// 小程序确认或拒绝登录
+#[derive(Debug, Deserialize)]
+pub struct ConfirmQrLoginRequest {
+    pub approve: bool,
+}
+
+// 轮询令牌生成（十六进制）
+pub fn generate_poll_token() -> String {
+    hex::encode(rand::random::<[u8; 32]>())
+}
+
+// 轮询令牌摘要（SHA-256 十六进制）
+pub fn hash_poll_token(token: &str) -> String {
+    use sha2::{Digest, Sha256};
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_remaining_secs() {
+        let now = Utc::now();
+        let ticket = QrLoginTicket {
+            login_id: Uuid::new_v4(),
+            poll_token_hash: hash_poll_token(&generate_poll_token()),
+            status: QrLoginStatus::Pending,
+            user_id: None,
+            user_agent: None,
+            ip_address: None,
+            created_at: now,
+            expires_at: now + Duration::seconds(90),
+        };
+        assert_eq!(ticket.remaining_secs(now), 90);
+        // 已过期的记录仍保留至少1秒，由缓存自然过期
+        assert_eq!(ticket.remaining_secs(now + Duration::seconds(120)), 1);
+    }
+}
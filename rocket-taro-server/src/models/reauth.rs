@@ -0,0 +1,15 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 敏感操作前重新验证密码
+#[derive(Debug, Deserialize)]
+pub struct ReauthRequest {
+    pub password: String,
+}
+
+/// 二次验证结果，valid_until 之前可以执行敏感操作
+#[derive(Debug, Serialize)]
+pub struct ReauthStatus {
+    pub confirmed_at: DateTime<Utc>,
+    pub valid_until: DateTime<Utc>,
+}
@@ -0,0 +1,103 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// 新账号进入人工审核队列的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewReason {
+    /// 同一 IP 短时间内注册多个账号
+    IpVelocity,
+    /// 同一设备短时间内注册多个账号
+    DeviceVelocity,
+    /// 表单填写耗时过短
+    FastSubmission,
+}
+
+impl ReviewReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReviewReason::IpVelocity => "ip_velocity",
+            ReviewReason::DeviceVelocity => "device_velocity",
+            ReviewReason::FastSubmission => "fast_submission",
+        }
+    }
+}
+
+/// 审核状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReviewStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl ReviewStatus {
+    pub const ALL: [ReviewStatus; 3] = [ReviewStatus::Pending, ReviewStatus::Approved, ReviewStatus::Rejected];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReviewStatus::Pending => "pending",
+            ReviewStatus::Approved => "approved",
+            ReviewStatus::Rejected => "rejected",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|status| status.as_str() == value)
+    }
+}
+
+/// 注册时的风险评估结果，原因为空表示无需审核
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RegistrationAssessment {
+    pub reasons: Vec<ReviewReason>,
+}
+
+impl RegistrationAssessment {
+    pub fn is_suspicious(&self) -> bool {
+        !self.reasons.is_empty()
+    }
+}
+
+/// 审核队列中的账号
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistrationReview {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub username: Option<String>,
+    pub reasons: Vec<String>,
+    pub ip_address: Option<String>,
+    pub device_id: Option<String>,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub note: Option<String>,
+}
+
+/// 管理员处理审核：通过保留账号，拒绝停用账号并撤销其全部会话
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReviewDecisionRequest {
+    pub status: ReviewStatus,
+    pub note: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_review_status_round_trip() {
+        for status in ReviewStatus::ALL {
+            assert_eq!(ReviewStatus::parse(status.as_str()), Some(status));
+        }
+        assert_eq!(ReviewStatus::parse("unknown"), None);
+
+        let request: ReviewDecisionRequest = serde_json::from_str(r#"{"status":"rejected"}"#).unwrap();
+        assert_eq!(request.status, ReviewStatus::Rejected);
+        assert!(request.note.is_none());
+        assert_eq!(serde_json::to_string(&ReviewReason::IpVelocity).unwrap(), "\"ip_velocity\"");
+    }
+}
@@ -0,0 +1,198 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use crate::config::Platform;
+
+/// 数据库中表示"所有平台"的平台取值
+pub const ALL_PLATFORMS: &str = "*";
+
+/// 远程配置项（对应 remote_configs 表中的一行）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteConfigEntry {
+    pub config_key: String,
+    /// 平台覆盖，None 表示对所有平台生效的基础值
+    pub platform: Option<Platform>,
+    pub value: serde_json::Value,
+    /// 全局单调递增的版本号，每次写入或删除都会更新
+    pub version: i64,
+    pub is_deleted: bool,
+    pub description: Option<String>,
+    pub updated_by: Option<Uuid>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// 管理端写入配置请求
+#[derive(Debug, Deserialize)]
+pub struct SetRemoteConfigRequest {
+    pub value: serde_json::Value,
+    pub platform: Option<Platform>,
+    pub description: Option<String>,
+}
+
+/// 解析后的单个配置值
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ResolvedConfigValue {
+    pub value: serde_json::Value,
+    /// 该键最近一次变更的版本号
+    pub version: i64,
+}
+
+/// 某个平台解析后的完整配置快照（会被缓存到Redis）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteConfigSnapshot {
+    pub platform: Platform,
+    /// 快照版本号，即所有相关配置项中最大的版本号
+    pub version: i64,
+    pub values: BTreeMap<String, ResolvedConfigValue>,
+    /// 已被删除的键及其删除时的版本号，用于增量响应
+    pub tombstones: BTreeMap<String, i64>,
+}
+
+/// 增量配置结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemoteConfigDelta {
+    pub configs: BTreeMap<String, serde_json::Value>,
+    pub removed_keys: Vec<String>,
+}
+
+impl RemoteConfigSnapshot {
+    /// 根据基础配置与平台覆盖解析出平台快照
+    ///
+    /// 平台覆盖优先；平台覆盖被删除时回退到基础值；两者都不可用时记为删除。
+    pub fn resolve(platform: Platform, entries: &[RemoteConfigEntry]) -> Self {
+        let mut base: BTreeMap<&str, &RemoteConfigEntry> = BTreeMap::new();
+        let mut overrides: BTreeMap<&str, &RemoteConfigEntry> = BTreeMap::new();
+
+        for entry in entries {
+            match entry.platform {
+                None => { base.insert(&entry.config_key, entry); }
+                Some(p) if p == platform => { overrides.insert(&entry.config_key, entry); }
+                Some(_) => {}
+            }
+        }
+
+        let mut values = BTreeMap::new();
+        let mut tombstones = BTreeMap::new();
+        let mut version = 0;
+
+        let keys: std::collections::BTreeSet<&str> = base.keys().chain(overrides.keys()).copied().collect();
+        for key in keys {
+            let base_entry = base.get(key);
+            let override_entry = overrides.get(key);
+
+            let changed_at = base_entry.map(|e| e.version).unwrap_or(0)
+                .max(override_entry.map(|e| e.version).unwrap_or(0));
+            version = version.max(changed_at);
+
+            let effective = override_entry.filter(|e| !e.is_deleted)
+                .or_else(|| base_entry.filter(|e| !e.is_deleted));
+
+            match effective {
+                Some(entry) => {
+                    values.insert(key.to_string(), ResolvedConfigValue {
+                        value: entry.value.clone(),
+                        version: changed_at,
+                    });
+                }
+                None => {
+                    tombstones.insert(key.to_string(), changed_at);
+                }
+            }
+        }
+
+        Self { platform, version, values, tombstones }
+    }
+
+    /// 计算自某个版本以来的增量
+    pub fn delta_since(&self, since_version: i64) -> RemoteConfigDelta {
+        let configs = self.values.iter()
+            .filter(|(_, v)| v.version > since_version)
+            .map(|(k, v)| (k.clone(), v.value.clone()))
+            .collect();
+        let removed_keys = self.tombstones.iter()
+            .filter(|(_, version)| **version > since_version)
+            .map(|(k, _)| k.clone())
+            .collect();
+
+        RemoteConfigDelta { configs, removed_keys }
+    }
+
+    /// 返回完整配置（不含删除标记）
+    pub fn full(&self) -> RemoteConfigDelta {
+        RemoteConfigDelta {
+            configs: self.values.iter().map(|(k, v)| (k.clone(), v.value.clone())).collect(),
+            removed_keys: Vec::new(),
+        }
+    }
+
+    /// 用于 ETag / If-None-Match 的实体标签
+    pub fn etag(&self) -> String {
+        format!("\"{}-{}\"", self.platform.as_str(), self.version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entry(key: &str, platform: Option<Platform>, value: serde_json::Value, version: i64, is_deleted: bool) -> RemoteConfigEntry {
+        RemoteConfigEntry {
+            config_key: key.to_string(),
+            platform,
+            value,
+            version,
+            is_deleted,
+            description: None,
+            updated_by: None,
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_platform_override_wins() {
+        let entries = vec![
+            entry("theme", None, json!("light"), 1, false),
+            entry("theme", Some(Platform::H5), json!("dark"), 2, false),
+            entry("theme", Some(Platform::Admin), json!("blue"), 3, false),
+        ];
+
+        let h5 = RemoteConfigSnapshot::resolve(Platform::H5, &entries);
+        assert_eq!(h5.values["theme"].value, json!("dark"));
+        assert_eq!(h5.version, 2);
+
+        let mp = RemoteConfigSnapshot::resolve(Platform::Miniprogram, &entries);
+        assert_eq!(mp.values["theme"].value, json!("light"));
+        assert_eq!(mp.version, 1);
+    }
+
+    #[test]
+    fn test_deleted_override_falls_back_to_base() {
+        let entries = vec![
+            entry("banner", None, json!("base"), 1, false),
+            entry("banner", Some(Platform::H5), json!("h5"), 4, true),
+        ];
+
+        let snapshot = RemoteConfigSnapshot::resolve(Platform::H5, &entries);
+        assert_eq!(snapshot.values["banner"], ResolvedConfigValue { value: json!("base"), version: 4 });
+        assert!(snapshot.tombstones.is_empty());
+    }
+
+    #[test]
+    fn test_delta_since_version() {
+        let entries = vec![
+            entry("a", None, json!(1), 1, false),
+            entry("b", None, json!(2), 5, false),
+            entry("c", None, json!(3), 6, true),
+        ];
+
+        let snapshot = RemoteConfigSnapshot::resolve(Platform::Miniprogram, &entries);
+        let delta = snapshot.delta_since(4);
+        assert_eq!(delta.configs.len(), 1);
+        assert_eq!(delta.configs["b"], json!(2));
+        assert_eq!(delta.removed_keys, vec!["c".to_string()]);
+        assert_eq!(snapshot.etag(), "\"miniprogram-6\"");
+    }
+}
@@ -1,12 +1,84 @@
 use serde::{Deserialize, Serialize};
-use super::route_command::RouteCommand;
+use uuid::Uuid;
+use super::route_command::{RouteCommand, VersionedRouteCommand};
+use crate::utils::pagination::{Page, PageRequest};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ApiResponse<T> {
     pub code: i32,
     pub message: String,
     pub data: Option<T>,
-    pub route_command: Option<RouteCommand>,
+    /// 路由指令，metadata 中带有自动分配的指令ID、超时和是否可重试
+    pub route_command: Option<VersionedRouteCommand>,
+    /// 路由指令的执行ID，前端执行完成后通过 /api/route-commands/ack 确认
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution_id: Option<Uuid>,
+    /// 字段级校验错误，前端按 field 标注到对应输入项
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub errors: Option<Vec<FieldError>>,
+}
+
+/// 单个字段的校验错误
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: &str, message: impl Into<String>) -> Self {
+        Self { field: field.to_string(), message: message.into() }
+    }
+}
+
+/// 列表响应：数据和分页信息
+///
+/// 一次返回全部数据的列表 page 为 1、page_size 为条数；游标分页的列表 page 为 None，
+/// 用 next_cursor 翻页，total 只在统计了总数时返回
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PagedResponse<T> {
+    pub items: Vec<T>,
+    /// 总条数，未统计时为 None
+    pub total: Option<i64>,
+    /// 当前页码（从 1 开始），游标分页时为 None
+    pub page: Option<i64>,
+    pub page_size: i64,
+    /// 下一页的游标，没有更多数据时为 None
+    pub next_cursor: Option<String>,
+    pub has_more: bool,
+}
+
+impl<T> PagedResponse<T> {
+    /// 不分页的完整列表
+    pub fn all(items: Vec<T>) -> Self {
+        let total = items.len() as i64;
+        Self {
+            items,
+            total: Some(total),
+            page: Some(1),
+            page_size: total,
+            next_cursor: None,
+            has_more: false,
+        }
+    }
+
+    /// 游标分页的一页数据
+    pub fn from_page(page: Page<T>, request: &PageRequest) -> Self {
+        Self {
+            items: page.items,
+            total: None,
+            page: None,
+            page_size: request.limit,
+            next_cursor: page.next_cursor,
+            has_more: page.has_more,
+        }
+    }
+
+    /// 设置总条数
+    pub fn with_total(mut self, total: i64) -> Self {
+        self.total = Some(total);
+        self
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -23,6 +95,8 @@ impl<T> ApiResponse<T> {
             message: "success".to_string(),
             data: Some(data),
             route_command: None,
+            execution_id: None,
+            errors: None,
         }
     }
 
@@ -32,6 +106,8 @@ impl<T> ApiResponse<T> {
             message: message.to_string(),
             data: None,
             route_command: None,
+            execution_id: None,
+            errors: None,
         }
     }
 
@@ -41,39 +117,66 @@ impl<T> ApiResponse<T> {
             message: "ok".to_string(),
             data: None,
             route_command: None,
+            execution_id: None,
+            errors: None,
         }
     }
     
     /// 创建带路由指令的成功响应
-    pub fn success_with_command(data: T, command: RouteCommand) -> Self {
+    pub fn success_with_command(data: T, command: impl Into<VersionedRouteCommand>) -> Self {
         Self {
             code: 200,
             message: "success".to_string(),
             data: Some(data),
-            route_command: Some(command),
+            route_command: Some(command.into()),
+            execution_id: None,
+            errors: None,
         }
     }
     
     /// 创建仅包含路由指令的响应
-    pub fn command_only(command: RouteCommand) -> Self {
+    pub fn command_only(command: impl Into<VersionedRouteCommand>) -> Self {
         Self {
             code: 200,
             message: "success".to_string(),
             data: None,
-            route_command: Some(command),
+            route_command: Some(command.into()),
+            execution_id: None,
+            errors: None,
         }
     }
     
     /// 创建带路由指令的错误响应
-    pub fn error_with_command(message: &str, command: RouteCommand) -> Self {
+    pub fn error_with_command(message: &str, command: impl Into<VersionedRouteCommand>) -> Self {
         Self {
             code: 500,
             message: message.to_string(),
             data: None,
-            route_command: Some(command),
+            route_command: Some(command.into()),
+            execution_id: None,
+            errors: None,
         }
     }
     
+    /// 创建字段校验失败的响应（code 422），提示第一个错误
+    pub fn validation_error(errors: Vec<FieldError>) -> Self {
+        let message = errors.first().map(|e| e.message.clone()).unwrap_or_else(|| "提交内容不正确".to_string());
+        Self {
+            code: 422,
+            route_command: Some(VersionedRouteCommand::generated(RouteCommand::toast(&message))),
+            message,
+            data: None,
+            execution_id: None,
+            errors: Some(errors),
+        }
+    }
+
+    /// 设置路由指令的执行ID
+    pub fn with_execution_id(mut self, execution_id: Uuid) -> Self {
+        self.execution_id = Some(execution_id);
+        self
+    }
+
     /// 创建带导航的成功响应
     pub fn with_navigation(data: T, path: &str) -> Self {
         Self::success_with_command(
@@ -89,4 +192,54 @@ impl<T> ApiResponse<T> {
             RouteCommand::toast(message),
         )
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::pagination::Cursor;
+    use chrono::DateTime;
+
+    #[test]
+    fn test_route_command_carries_metadata() {
+        let response = ApiResponse::success_with_command((), RouteCommand::navigate_to("/pages/home/home"));
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["route_command"]["type"], "NavigateTo");
+        assert_eq!(json["route_command"]["version"], 2);
+        assert!(Uuid::parse_str(json["route_command"]["metadata"]["id"].as_str().unwrap()).is_ok());
+
+        // 旧格式（不带版本和元数据）的指令仍可解析
+        let legacy: ApiResponse<()> = serde_json::from_value(serde_json::json!({
+            "code": 200,
+            "message": "success",
+            "data": null,
+            "route_command": { "type": "NavigateTo", "payload": { "path": "/pages/home/home" } },
+        })).unwrap();
+        assert!(legacy.route_command.unwrap().metadata.id.is_none());
+    }
+
+    #[test]
+    fn test_paged_response_all() {
+        let paged = PagedResponse::all(vec![1, 2, 3]);
+        assert_eq!(paged.total, Some(3));
+        assert_eq!(paged.page, Some(1));
+        assert_eq!(paged.page_size, 3);
+        assert!(paged.next_cursor.is_none());
+        assert!(!paged.has_more);
+    }
+
+    #[test]
+    fn test_paged_response_from_page() {
+        let request = PageRequest::parse(None, Some(2), 20, 100).unwrap();
+        let rows: Vec<Cursor> = (0..3)
+            .map(|i| Cursor::new(DateTime::from_timestamp(1_700_000_000 - i, 0).unwrap(), Uuid::new_v4()))
+            .collect();
+
+        let paged = PagedResponse::from_page(Page::from_rows(rows, &request, |row| *row), &request).with_total(3);
+        assert_eq!(paged.items.len(), 2);
+        assert_eq!(paged.total, Some(3));
+        assert_eq!(paged.page, None);
+        assert_eq!(paged.page_size, 2);
+        assert!(paged.has_more);
+        assert!(paged.next_cursor.is_some());
+    }
+}
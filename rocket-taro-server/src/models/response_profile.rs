@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// 客户端可通过该请求头指定响应信封形式，优先于按平台的默认形式
+pub const ACCEPT_PROFILE_HEADER: &str = "Accept-Profile";
+/// 响应中实际使用的信封形式
+pub const CONTENT_PROFILE_HEADER: &str = "Content-Profile";
+
+/// ApiResponse 信封形式：code / message / errors 总是保留
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseProfile {
+    /// 数据和路由指令都返回（小程序默认）
+    Combined,
+    /// 只返回数据，去掉 route_command 和 execution_id（管理后台默认）
+    Data,
+    /// 只返回路由指令，去掉 data
+    Command,
+}
+
+impl ResponseProfile {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "combined" => Some(ResponseProfile::Combined),
+            "data" | "data-only" => Some(ResponseProfile::Data),
+            "command" | "command-only" => Some(ResponseProfile::Command),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResponseProfile::Combined => "combined",
+            ResponseProfile::Data => "data",
+            ResponseProfile::Command => "command",
+        }
+    }
+
+    /// 按信封形式裁剪 ApiResponse 的 JSON，不是 ApiResponse 结构时返回 false 且不修改
+    pub fn shape(&self, body: &mut Value) -> bool {
+        let Some(envelope) = body.as_object_mut() else {
+            return false;
+        };
+        if !(envelope.contains_key("code") && envelope.contains_key("message") && envelope.contains_key("route_command")) {
+            return false;
+        }
+        match self {
+            ResponseProfile::Combined => {}
+            ResponseProfile::Data => {
+                envelope.remove("route_command");
+                envelope.remove("execution_id");
+            }
+            ResponseProfile::Command => {
+                envelope.remove("data");
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_shape() {
+        let body = json!({
+            "code": 200,
+            "message": "success",
+            "data": { "id": 1 },
+            "route_command": { "type": "NavigateTo" },
+            "execution_id": "00000000-0000-0000-0000-000000000000",
+        });
+
+        let mut data = body.clone();
+        assert!(ResponseProfile::Data.shape(&mut data));
+        assert_eq!(data, json!({ "code": 200, "message": "success", "data": { "id": 1 } }));
+
+        let mut command = body.clone();
+        assert!(ResponseProfile::Command.shape(&mut command));
+        assert!(command.get("data").is_none());
+        assert_eq!(command["route_command"]["type"], "NavigateTo");
+
+        let mut combined = body.clone();
+        assert!(ResponseProfile::Combined.shape(&mut combined));
+        assert_eq!(combined, body);
+
+        // 其他 JSON（如支付回调应答）不修改
+        let mut other = json!({ "code": "SUCCESS", "message": "成功" });
+        assert!(!ResponseProfile::Data.shape(&mut other));
+        assert_eq!(other, json!({ "code": "SUCCESS", "message": "成功" }));
+    }
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(ResponseProfile::from_name("Data"), Some(ResponseProfile::Data));
+        assert_eq!(ResponseProfile::from_name("command-only"), Some(ResponseProfile::Command));
+        assert_eq!(ResponseProfile::from_name("full"), None);
+    }
+}
@@ -1,8 +1,15 @@
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::client_capabilities::ClientCapabilities;
+use super::route_condition::{ConditionContext, ConditionExpr};
 
 /// 路由指令版本控制常量
 pub const ROUTE_COMMAND_VERSION: u32 = 2;
 
+/// 客户端声明支持的路由指令版本的请求头；低于 ROUTE_COMMAND_VERSION 的客户端收到旧格式的指令
+pub const ROUTE_COMMAND_VERSION_HEADER: &str = "X-Route-Command-Version";
+
 /// 默认版本号函数
 fn default_version() -> u32 {
     ROUTE_COMMAND_VERSION
@@ -100,6 +107,16 @@ pub enum RouteCommand {
         max_attempts: u32,
         delay_ms: u64,
     },
+
+    /// 调起微信支付（小程序 wx.requestPayment）
+    RequestPayment {
+        /// wx.requestPayment 参数（timeStamp、nonceStr、package、signType、paySign）
+        params: serde_json::Value,
+        /// 支付成功后执行的指令
+        on_success: Option<Box<RouteCommand>>,
+        /// 支付失败或用户取消后执行的指令
+        on_fail: Option<Box<RouteCommand>>,
+    },
 }
 
 /// 对话框类型
@@ -141,6 +158,17 @@ impl VersionedRouteCommand {
         }
     }
     
+    /// 包装下发给前端的指令，分配新的 UUID 作为指令ID；
+    /// 超时和是否可重试由响应 fairing 按 `[default.route_command_metadata]` 填写
+    pub fn generated(command: RouteCommand) -> Self {
+        let metadata = RouteCommandMetadata {
+            id: Some(Uuid::new_v4().to_string()),
+            priority: 5,
+            ..RouteCommandMetadata::default()
+        };
+        Self::with_metadata(command, metadata)
+    }
+    
     /// 创建带有回退指令的版本化路由指令
     pub fn with_fallback(command: RouteCommand, fallback: RouteCommand) -> Self {
         Self {
@@ -173,6 +201,20 @@ impl VersionedRouteCommand {
         self
     }
     
+    /// 客户端是否能解析版本化指令（version、fallback、metadata）；未声明版本的客户端按当前版本处理
+    pub fn client_supports_versioned(client_version: Option<u32>) -> bool {
+        client_version.is_none_or(|version| version >= ROUTE_COMMAND_VERSION)
+    }
+
+    /// 按客户端能力降级指令和回退指令，元数据保持不变
+    pub fn adapt_to(self, capabilities: &ClientCapabilities) -> Self {
+        Self {
+            command: self.command.adapt_to(capabilities),
+            fallback: self.fallback.map(|fallback| Box::new(fallback.adapt_to(capabilities))),
+            ..self
+        }
+    }
+    
     /// 检查版本兼容性
     pub fn is_compatible(&self, client_version: u32) -> bool {
         // 简单的版本兼容性检查：主版本号必须匹配
@@ -180,6 +222,13 @@ impl VersionedRouteCommand {
     }
 }
 
+/// 响应中的指令都带有自动生成的元数据
+impl From<RouteCommand> for VersionedRouteCommand {
+    fn from(command: RouteCommand) -> Self {
+        Self::generated(command)
+    }
+}
+
 impl RouteCommand {
     /// 创建简单的页面导航指令
     pub fn navigate_to(path: &str) -> Self {
@@ -332,6 +381,148 @@ impl RouteCommand {
         }
     }
     
+    /// 创建调起支付指令
+    pub fn request_payment(params: serde_json::Value, on_success: RouteCommand, on_fail: RouteCommand) -> Self {
+        Self::RequestPayment {
+            params,
+            on_success: Some(Box::new(on_success)),
+            on_fail: Some(Box::new(on_fail)),
+        }
+    }
+    
+    /// 创建条件指令，条件由前端按当前状态求值
+    pub fn conditional(condition: &ConditionExpr, if_true: RouteCommand, if_false: Option<RouteCommand>) -> Self {
+        Self::Conditional {
+            condition: condition.to_string(),
+            if_true: Box::new(if_true),
+            if_false: if_false.map(Box::new),
+        }
+    }
+
+    /// 指令类型名称（与序列化的 type 字段一致）
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            RouteCommand::NavigateTo { .. } => "NavigateTo",
+            RouteCommand::ShowDialog { .. } => "ShowDialog",
+            RouteCommand::ProcessData { .. } => "ProcessData",
+            RouteCommand::Sequence { .. } => "Sequence",
+            RouteCommand::Conditional { .. } => "Conditional",
+            RouteCommand::Delay { .. } => "Delay",
+            RouteCommand::Parallel { .. } => "Parallel",
+            RouteCommand::Retry { .. } => "Retry",
+            RouteCommand::RequestPayment { .. } => "RequestPayment",
+        }
+    }
+
+    /// 用服务端已知的上下文展开条件指令：能确定结果的条件替换为对应分支，
+    /// 取决于前端状态或无法解析的条件原样保留
+    pub fn resolve_conditions(self, context: &ConditionContext) -> Self {
+        let resolve = |command: Box<RouteCommand>| Box::new(command.resolve_conditions(context));
+        match self {
+            Self::Conditional { condition, if_true, if_false } => {
+                match ConditionExpr::parse(&condition).ok().and_then(|expr| expr.evaluate(context)) {
+                    Some(true) => if_true.resolve_conditions(context),
+                    Some(false) => if_false
+                        .map(|command| command.resolve_conditions(context))
+                        .unwrap_or_else(|| Self::sequence(vec![])),
+                    None => Self::Conditional {
+                        condition,
+                        if_true: resolve(if_true),
+                        if_false: if_false.map(resolve),
+                    },
+                }
+            }
+            Self::Sequence { commands, stop_on_error } => Self::Sequence {
+                commands: commands.into_iter().map(|command| command.resolve_conditions(context)).collect(),
+                stop_on_error,
+            },
+            Self::Parallel { commands, wait_for_all } => Self::Parallel {
+                commands: commands.into_iter().map(|command| command.resolve_conditions(context)).collect(),
+                wait_for_all,
+            },
+            Self::Delay { duration_ms, command } => Self::Delay { duration_ms, command: resolve(command) },
+            Self::Retry { command, max_attempts, delay_ms } => Self::Retry { command: resolve(command), max_attempts, delay_ms },
+            Self::ShowDialog { dialog_type, title, content, actions } => Self::ShowDialog {
+                dialog_type,
+                title,
+                content,
+                actions: actions.into_iter().map(|action| DialogAction {
+                    text: action.text,
+                    action: action.action.map(|command| command.resolve_conditions(context)),
+                }).collect(),
+            },
+            Self::RequestPayment { params, on_success, on_fail } => Self::RequestPayment {
+                params,
+                on_success: on_success.map(resolve),
+                on_fail: on_fail.map(resolve),
+            },
+            command => command,
+        }
+    }
+
+    /// 按客户端能力降级指令，不支持的类型改写为等价或最接近的基础指令：
+    /// Parallel 改为顺序执行，Delay 立即执行，Retry 只执行一次，
+    /// Conditional 取 if_true 分支（条件分支中需要用户确认的操作放在 if_true），
+    /// RequestPayment 执行 on_fail 或提示不支持支付
+    pub fn adapt_to(self, capabilities: &ClientCapabilities) -> Self {
+        let adapt = |command: Box<RouteCommand>| Box::new(command.adapt_to(capabilities));
+        let supported = capabilities.supports(self.type_name());
+        match self {
+            Self::Sequence { commands, stop_on_error } => Self::Sequence {
+                commands: commands.into_iter().map(|command| command.adapt_to(capabilities)).collect(),
+                stop_on_error,
+            },
+            Self::Parallel { commands, wait_for_all } => {
+                let commands = commands.into_iter().map(|command| command.adapt_to(capabilities)).collect();
+                if supported {
+                    Self::Parallel { commands, wait_for_all }
+                } else {
+                    Self::sequence_continue_on_error(commands)
+                }
+            }
+            Self::Conditional { condition, if_true, if_false } => {
+                if supported {
+                    Self::Conditional { condition, if_true: adapt(if_true), if_false: if_false.map(adapt) }
+                } else {
+                    if_true.adapt_to(capabilities)
+                }
+            }
+            Self::Delay { duration_ms, command } => {
+                if supported {
+                    Self::Delay { duration_ms, command: adapt(command) }
+                } else {
+                    command.adapt_to(capabilities)
+                }
+            }
+            Self::Retry { command, max_attempts, delay_ms } => {
+                if supported {
+                    Self::Retry { command: adapt(command), max_attempts, delay_ms }
+                } else {
+                    command.adapt_to(capabilities)
+                }
+            }
+            Self::RequestPayment { params, on_success, on_fail } => {
+                if supported {
+                    Self::RequestPayment { params, on_success: on_success.map(adapt), on_fail: on_fail.map(adapt) }
+                } else {
+                    on_fail
+                        .map(|command| command.adapt_to(capabilities))
+                        .unwrap_or_else(|| Self::toast("当前客户端不支持支付，请在微信小程序中完成支付"))
+                }
+            }
+            Self::ShowDialog { dialog_type, title, content, actions } => Self::ShowDialog {
+                dialog_type,
+                title,
+                content,
+                actions: actions.into_iter().map(|action| DialogAction {
+                    text: action.text,
+                    action: action.action.map(|command| command.adapt_to(capabilities)),
+                }).collect(),
+            },
+            command => command,
+        }
+    }
+    
     /// 包装为版本化指令
     pub fn versioned(self) -> VersionedRouteCommand {
         VersionedRouteCommand::new(self)
@@ -368,7 +559,7 @@ impl RouteCommandMetadata {
     
     /// 设置优先级
     pub fn with_priority(mut self, priority: u8) -> Self {
-        self.priority = priority.min(10).max(1);
+        self.priority = priority.clamp(1, 10);
         self
     }
     
@@ -503,7 +694,7 @@ mod tests {
         match parallel_cmd {
             RouteCommand::Parallel { commands, wait_for_all } => {
                 assert_eq!(commands.len(), 2);
-                assert_eq!(wait_for_all, true);
+                assert!(wait_for_all);
             },
             _ => panic!("Expected Parallel command"),
         }
@@ -518,6 +709,51 @@ mod tests {
         assert_eq!(metadata.id, Some("test_command".to_string()));
         assert_eq!(metadata.description, Some("Test command description".to_string()));
         assert_eq!(metadata.priority, 8);
-        assert_eq!(metadata.retryable, false);
+        assert!(!metadata.retryable);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_resolve_conditions() {
+        use crate::models::route_condition::{ConditionContext, ConditionExpr};
+
+        let command = RouteCommand::sequence(vec![
+            RouteCommand::conditional(
+                &ConditionExpr::eq("user.is_admin", true),
+                RouteCommand::navigate_to("/admin"),
+                Some(RouteCommand::navigate_to("/home")),
+            ),
+            RouteCommand::conditional(&ConditionExpr::truthy("store.hasUnsavedData"), RouteCommand::toast("unsaved"), None),
+        ]);
+
+        let context = ConditionContext::new().with("user", json!({ "is_admin": false }));
+        let resolved = serde_json::to_value(command.resolve_conditions(&context)).unwrap();
+        assert_eq!(resolved["payload"]["commands"][0]["type"], "NavigateTo");
+        assert_eq!(resolved["payload"]["commands"][0]["payload"]["path"], "/home");
+        // 依赖前端状态的条件保留
+        assert_eq!(resolved["payload"]["commands"][1]["type"], "Conditional");
+        assert_eq!(resolved["payload"]["commands"][1]["payload"]["condition"], "store.hasUnsavedData");
+    }
+
+    #[test]
+    fn test_adapt_to_capabilities() {
+        use crate::models::client_capabilities::ClientCapabilities;
+
+        let command = RouteCommand::parallel(vec![
+            RouteCommand::delay(500, RouteCommand::navigate_to("/home")),
+            RouteCommand::process_data("user", json!(null)),
+        ]);
+
+        // 支持全部指令时保持不变
+        let full = serde_json::to_value(command.clone().adapt_to(&ClientCapabilities::default())).unwrap();
+        assert_eq!(full["type"], "Parallel");
+
+        let adapted = serde_json::to_value(command.adapt_to(&ClientCapabilities::from_header(""))).unwrap();
+        assert_eq!(adapted["type"], "Sequence");
+        assert_eq!(adapted["payload"]["stop_on_error"], false);
+        assert_eq!(adapted["payload"]["commands"][0]["type"], "NavigateTo");
+
+        let payment = RouteCommand::RequestPayment { params: json!({}), on_success: None, on_fail: None };
+        let adapted = serde_json::to_value(payment.adapt_to(&ClientCapabilities::from_header("Conditional"))).unwrap();
+        assert_eq!(adapted["type"], "ShowDialog");
+    }
+}
@@ -0,0 +1,680 @@
+use serde_json::{Map, Value};
+use std::cmp::Ordering;
+use std::fmt;
+
+/// 条件表达式可引用的根对象，与前端求值上下文一致
+pub const CONDITION_ROOTS: [&str; 2] = ["user", "store"];
+
+// 括号和取反的最大嵌套层数
+const MAX_DEPTH: usize = 32;
+
+/// 字面量
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    Null,
+    Undefined,
+    Bool(bool),
+    Number(f64),
+    String(String),
+}
+
+impl From<bool> for Literal {
+    fn from(value: bool) -> Self {
+        Literal::Bool(value)
+    }
+}
+
+impl From<i64> for Literal {
+    fn from(value: i64) -> Self {
+        Literal::Number(value as f64)
+    }
+}
+
+impl From<f64> for Literal {
+    fn from(value: f64) -> Self {
+        Literal::Number(value)
+    }
+}
+
+impl From<&str> for Literal {
+    fn from(value: &str) -> Self {
+        Literal::String(value.to_string())
+    }
+}
+
+/// 比较运算的操作数
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operand {
+    /// 属性路径，第一段为根对象
+    Path(Vec<String>),
+    Literal(Literal),
+}
+
+/// 比较运算符
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl CompareOp {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CompareOp::Eq => "===",
+            CompareOp::Ne => "!==",
+            CompareOp::Lt => "<",
+            CompareOp::Le => "<=",
+            CompareOp::Gt => ">",
+            CompareOp::Ge => ">=",
+        }
+    }
+}
+
+/// Conditional 路由指令的条件表达式
+///
+/// 条件字符串由前端 `evaluateCondition` 作为 JavaScript 表达式求值，这里定义其中的一个子集，
+/// 服务端按相同语义解析和求值，已知上下文时可以提前展开条件，只把依赖前端状态的条件下发：
+///
+/// ```text
+/// expr       := or
+/// or         := and ( "||" and )*
+/// and        := unary ( "&&" unary )*
+/// unary      := "!" unary | "(" expr ")" | comparison
+/// comparison := operand ( op operand )?
+/// op         := "===" | "!==" | "<" | "<=" | ">" | ">="
+/// operand    := path | literal
+/// path       := ("user" | "store") ( "." ident )*
+/// literal    := number | string | true | false | null | undefined
+/// ```
+///
+/// - `user` 为 ProcessData("user") 写入的当前用户，`store` 为前端状态仓库
+/// - 只支持严格相等（`===` / `!==`），不做类型转换；大小比较只对两个数字或两个字符串成立
+/// - 单独的操作数按 JavaScript 真值判断（`null`、`undefined`、`false`、`0`、`""` 为假）
+/// - 数组和字符串支持 `.length`
+/// - 访问 `null` / `undefined` 的属性时整个条件为假（前端求值异常时同样返回假）
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConditionExpr {
+    /// 按真值判断单个操作数
+    Truthy(Operand),
+    Compare(Operand, CompareOp, Operand),
+    Not(Box<ConditionExpr>),
+    And(Box<ConditionExpr>, Box<ConditionExpr>),
+    Or(Box<ConditionExpr>, Box<ConditionExpr>),
+}
+
+/// 条件表达式解析错误
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConditionError {
+    /// 出错位置（字符偏移）
+    pub position: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ConditionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid condition at {}: {}", self.position, self.message)
+    }
+}
+
+impl std::error::Error for ConditionError {}
+
+/// 服务端已知的求值上下文，未提供的根对象视为前端状态
+#[derive(Debug, Clone, Default)]
+pub struct ConditionContext {
+    roots: Map<String, Value>,
+}
+
+impl ConditionContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 提供根对象的值（如当前用户）
+    pub fn with(mut self, root: &str, value: Value) -> Self {
+        debug_assert!(CONDITION_ROOTS.contains(&root), "unknown condition root: {}", root);
+        self.roots.insert(root.to_string(), value);
+        self
+    }
+}
+
+impl ConditionExpr {
+    /// 解析条件字符串
+    pub fn parse(input: &str) -> Result<Self, ConditionError> {
+        let tokens = tokenize(input)?;
+        let mut parser = Parser { tokens, pos: 0, end: input.chars().count(), depth: 0 };
+        let expr = parser.parse_or()?;
+        match parser.peek() {
+            None => Ok(expr),
+            Some((position, _)) => Err(ConditionError { position, message: "unexpected token".to_string() }),
+        }
+    }
+
+    /// 按真值判断路径，如 `store.hasUnsavedData`
+    pub fn truthy(path: &str) -> Self {
+        ConditionExpr::Truthy(Operand::path(path))
+    }
+
+    /// 路径严格等于字面量
+    pub fn eq(path: &str, value: impl Into<Literal>) -> Self {
+        ConditionExpr::Compare(Operand::path(path), CompareOp::Eq, Operand::Literal(value.into()))
+    }
+
+    /// 路径与字面量比较
+    pub fn compare(path: &str, op: CompareOp, value: impl Into<Literal>) -> Self {
+        ConditionExpr::Compare(Operand::path(path), op, Operand::Literal(value.into()))
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Self {
+        ConditionExpr::Not(Box::new(self))
+    }
+
+    pub fn and(self, other: ConditionExpr) -> Self {
+        ConditionExpr::And(Box::new(self), Box::new(other))
+    }
+
+    pub fn or(self, other: ConditionExpr) -> Self {
+        ConditionExpr::Or(Box::new(self), Box::new(other))
+    }
+
+    /// 在已知上下文中求值：`Some` 为确定结果，`None` 表示取决于前端状态
+    pub fn evaluate(&self, context: &ConditionContext) -> Option<bool> {
+        match self.eval(context) {
+            Ok(Truth::True) => Some(true),
+            Ok(Truth::False) | Err(NullAccess) => Some(false),
+            Ok(Truth::Unknown) => None,
+        }
+    }
+
+    fn eval(&self, context: &ConditionContext) -> Result<Truth, NullAccess> {
+        match self {
+            ConditionExpr::Truthy(operand) => Ok(operand.resolve(context)?.truthiness()),
+            ConditionExpr::Compare(left, op, right) => {
+                let left = left.resolve(context)?;
+                let right = right.resolve(context)?;
+                Ok(compare(&left, *op, &right))
+            }
+            ConditionExpr::Not(inner) => Ok(match inner.eval(context)? {
+                Truth::True => Truth::False,
+                Truth::False => Truth::True,
+                Truth::Unknown => Truth::Unknown,
+            }),
+            ConditionExpr::And(left, right) => match left.eval(context)? {
+                Truth::False => Ok(Truth::False),
+                Truth::True => right.eval(context),
+                // 左侧未知时，只有右侧为假才能确定结果为假
+                Truth::Unknown => Ok(match right.eval(context) {
+                    Ok(Truth::False) => Truth::False,
+                    _ => Truth::Unknown,
+                }),
+            },
+            ConditionExpr::Or(left, right) => match left.eval(context)? {
+                Truth::True => Ok(Truth::True),
+                Truth::False => right.eval(context),
+                Truth::Unknown => Ok(match right.eval(context) {
+                    Ok(Truth::True) => Truth::True,
+                    _ => Truth::Unknown,
+                }),
+            },
+        }
+    }
+}
+
+impl Operand {
+    fn path(path: &str) -> Self {
+        let segments: Vec<String> = path.split('.').map(str::to_string).collect();
+        debug_assert!(CONDITION_ROOTS.contains(&segments[0].as_str()), "unknown condition root: {}", path);
+        Operand::Path(segments)
+    }
+
+    fn resolve(&self, context: &ConditionContext) -> Result<Resolved, NullAccess> {
+        let segments = match self {
+            Operand::Literal(literal) => return Ok(Resolved::from(literal)),
+            Operand::Path(segments) => segments,
+        };
+        let mut current = match context.roots.get(&segments[0]) {
+            Some(value) => Resolved::Value(value.clone()),
+            None => return Ok(Resolved::Unknown),
+        };
+        for segment in &segments[1..] {
+            current = match current {
+                Resolved::Undefined | Resolved::Value(Value::Null) => return Err(NullAccess),
+                Resolved::Value(Value::Object(mut map)) => map.remove(segment).map_or(Resolved::Undefined, Resolved::Value),
+                Resolved::Value(Value::Array(items)) if segment == "length" => Resolved::Value(Value::from(items.len())),
+                Resolved::Value(Value::String(s)) if segment == "length" => Resolved::Value(Value::from(s.encode_utf16().count())),
+                _ => Resolved::Undefined,
+            };
+        }
+        Ok(current)
+    }
+}
+
+// 访问 null / undefined 的属性（前端抛出 TypeError）
+struct NullAccess;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Truth {
+    True,
+    False,
+    Unknown,
+}
+
+enum Resolved {
+    /// 依赖前端状态
+    Unknown,
+    Undefined,
+    Value(Value),
+}
+
+impl From<&Literal> for Resolved {
+    fn from(literal: &Literal) -> Self {
+        match literal {
+            Literal::Undefined => Resolved::Undefined,
+            Literal::Null => Resolved::Value(Value::Null),
+            Literal::Bool(b) => Resolved::Value(Value::Bool(*b)),
+            Literal::Number(n) => Resolved::Value(serde_json::Number::from_f64(*n).map_or(Value::Null, Value::Number)),
+            Literal::String(s) => Resolved::Value(Value::String(s.clone())),
+        }
+    }
+}
+
+impl Resolved {
+    fn truthiness(&self) -> Truth {
+        let truthy = match self {
+            Resolved::Unknown => return Truth::Unknown,
+            Resolved::Undefined | Resolved::Value(Value::Null) => false,
+            Resolved::Value(Value::Bool(b)) => *b,
+            Resolved::Value(Value::Number(n)) => n.as_f64().is_some_and(|n| n != 0.0),
+            Resolved::Value(Value::String(s)) => !s.is_empty(),
+            Resolved::Value(Value::Array(_)) | Resolved::Value(Value::Object(_)) => true,
+        };
+        if truthy { Truth::True } else { Truth::False }
+    }
+}
+
+fn compare(left: &Resolved, op: CompareOp, right: &Resolved) -> Truth {
+    let result = match (left, right) {
+        (Resolved::Unknown, _) | (_, Resolved::Unknown) => return Truth::Unknown,
+        _ => match op {
+            CompareOp::Eq => strict_equals(left, right),
+            CompareOp::Ne => !strict_equals(left, right),
+            CompareOp::Lt => ordering(left, right) == Some(Ordering::Less),
+            CompareOp::Le => matches!(ordering(left, right), Some(Ordering::Less | Ordering::Equal)),
+            CompareOp::Gt => ordering(left, right) == Some(Ordering::Greater),
+            CompareOp::Ge => matches!(ordering(left, right), Some(Ordering::Greater | Ordering::Equal)),
+        },
+    };
+    if result { Truth::True } else { Truth::False }
+}
+
+// JavaScript `===`：对象和数组按引用比较，这里恒为不相等
+fn strict_equals(left: &Resolved, right: &Resolved) -> bool {
+    match (left, right) {
+        (Resolved::Undefined, Resolved::Undefined) => true,
+        (Resolved::Value(Value::Number(a)), Resolved::Value(Value::Number(b))) => a.as_f64() == b.as_f64(),
+        (Resolved::Value(Value::Null), Resolved::Value(Value::Null)) => true,
+        (Resolved::Value(Value::Bool(a)), Resolved::Value(Value::Bool(b))) => a == b,
+        (Resolved::Value(Value::String(a)), Resolved::Value(Value::String(b))) => a == b,
+        _ => false,
+    }
+}
+
+fn ordering(left: &Resolved, right: &Resolved) -> Option<Ordering> {
+    match (left, right) {
+        (Resolved::Value(Value::Number(a)), Resolved::Value(Value::Number(b))) => a.as_f64()?.partial_cmp(&b.as_f64()?),
+        // JavaScript 按 UTF-16 码元比较字符串
+        (Resolved::Value(Value::String(a)), Resolved::Value(Value::String(b))) => Some(a.encode_utf16().cmp(b.encode_utf16())),
+        _ => None,
+    }
+}
+
+impl fmt::Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Literal::Null => f.write_str("null"),
+            Literal::Undefined => f.write_str("undefined"),
+            Literal::Bool(b) => write!(f, "{}", b),
+            Literal::Number(n) if n.fract() == 0.0 && n.abs() < 1e15 => write!(f, "{}", *n as i64),
+            Literal::Number(n) => write!(f, "{}", n),
+            // JSON 字符串同时是合法的 JavaScript 字符串字面量
+            Literal::String(s) => write!(f, "{}", Value::String(s.clone())),
+        }
+    }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operand::Path(segments) => f.write_str(&segments.join(".")),
+            Operand::Literal(literal) => write!(f, "{}", literal),
+        }
+    }
+}
+
+/// 输出前端可直接求值的条件字符串
+impl fmt::Display for ConditionExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConditionExpr::Truthy(operand) => write!(f, "{}", operand),
+            ConditionExpr::Compare(left, op, right) => write!(f, "{} {} {}", left, op.as_str(), right),
+            ConditionExpr::Not(inner) => match inner.as_ref() {
+                ConditionExpr::Truthy(_) | ConditionExpr::Not(_) => write!(f, "!{}", inner),
+                _ => write!(f, "!({})", inner),
+            },
+            ConditionExpr::And(left, right) => {
+                write_and_operand(f, left)?;
+                f.write_str(" && ")?;
+                write_and_operand(f, right)
+            }
+            ConditionExpr::Or(left, right) => write!(f, "{} || {}", left, right),
+        }
+    }
+}
+
+// && 的优先级高于 ||，|| 作为 && 的操作数时需要括号
+fn write_and_operand(f: &mut fmt::Formatter<'_>, expr: &ConditionExpr) -> fmt::Result {
+    match expr {
+        ConditionExpr::Or(..) => write!(f, "({})", expr),
+        _ => write!(f, "{}", expr),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Dot,
+    Literal(Literal),
+    Op(CompareOp),
+    And,
+    Or,
+    Not,
+    LParen,
+    RParen,
+}
+
+fn tokenize(input: &str) -> Result<Vec<(usize, Token)>, ConditionError> {
+    let chars: Vec<char> = input.chars().collect();
+    let error = |position: usize, message: &str| ConditionError { position, message: message.to_string() };
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let start = i;
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let token = match c {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '.' => { i += 1; Token::Dot }
+            '(' => { i += 1; Token::LParen }
+            ')' => { i += 1; Token::RParen }
+            '&' if next == Some('&') => { i += 2; Token::And }
+            '|' if next == Some('|') => { i += 2; Token::Or }
+            '=' | '!' if next == Some('=') => {
+                if chars.get(i + 2) != Some(&'=') {
+                    return Err(error(start, "loose equality is not supported, use === or !=="));
+                }
+                i += 3;
+                Token::Op(if c == '=' { CompareOp::Eq } else { CompareOp::Ne })
+            }
+            '!' => { i += 1; Token::Not }
+            '<' | '>' => {
+                let or_equal = next == Some('=');
+                i += if or_equal { 2 } else { 1 };
+                Token::Op(match (c, or_equal) {
+                    ('<', false) => CompareOp::Lt,
+                    ('<', true) => CompareOp::Le,
+                    ('>', false) => CompareOp::Gt,
+                    _ => CompareOp::Ge,
+                })
+            }
+            '\'' | '"' => {
+                let mut value = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => return Err(error(start, "unterminated string")),
+                        Some(&ch) if ch == c => { i += 1; break; }
+                        Some('\\') => {
+                            let escaped = match chars.get(i + 1) {
+                                Some('n') => '\n',
+                                Some('t') => '\t',
+                                Some(&ch @ ('\\' | '\'' | '"')) => ch,
+                                _ => return Err(error(i, "unsupported escape sequence")),
+                            };
+                            value.push(escaped);
+                            i += 2;
+                        }
+                        Some(&ch) => { value.push(ch); i += 1; }
+                    }
+                }
+                Token::Literal(Literal::String(value))
+            }
+            c if c.is_ascii_digit() || (c == '-' && next.is_some_and(|n| n.is_ascii_digit())) => {
+                i += 1;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                let number = text.parse::<f64>().map_err(|_| error(start, "invalid number"))?;
+                Token::Literal(Literal::Number(number))
+            }
+            c if c.is_ascii_alphabetic() || c == '_' || c == '$' => {
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_' || chars[i] == '$') {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                match word.as_str() {
+                    "true" => Token::Literal(Literal::Bool(true)),
+                    "false" => Token::Literal(Literal::Bool(false)),
+                    "null" => Token::Literal(Literal::Null),
+                    "undefined" => Token::Literal(Literal::Undefined),
+                    _ => Token::Ident(word),
+                }
+            }
+            _ => return Err(error(start, &format!("unexpected character '{}'", c))),
+        };
+        tokens.push((start, token));
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+    /// 输入长度，用于报告末尾的错误位置
+    end: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<(usize, &Token)> {
+        self.tokens.get(self.pos).map(|(position, token)| (*position, token))
+    }
+
+    fn next(&mut self) -> Option<(usize, Token)> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn error(&self, message: &str) -> ConditionError {
+        let position = self.peek().map_or(self.end, |(position, _)| position);
+        ConditionError { position, message: message.to_string() }
+    }
+
+    fn parse_or(&mut self) -> Result<ConditionExpr, ConditionError> {
+        let mut expr = self.parse_and()?;
+        while matches!(self.peek(), Some((_, Token::Or))) {
+            self.pos += 1;
+            expr = expr.or(self.parse_and()?);
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<ConditionExpr, ConditionError> {
+        let mut expr = self.parse_unary()?;
+        while matches!(self.peek(), Some((_, Token::And))) {
+            self.pos += 1;
+            expr = expr.and(self.parse_unary()?);
+        }
+        Ok(expr)
+    }
+
+    fn parse_unary(&mut self) -> Result<ConditionExpr, ConditionError> {
+        if self.depth >= MAX_DEPTH {
+            return Err(self.error("condition is nested too deeply"));
+        }
+        match self.peek() {
+            Some((_, Token::Not)) => {
+                self.pos += 1;
+                self.depth += 1;
+                let inner = self.parse_unary()?;
+                self.depth -= 1;
+                Ok(inner.not())
+            }
+            Some((_, Token::LParen)) => {
+                self.pos += 1;
+                self.depth += 1;
+                let inner = self.parse_or()?;
+                self.depth -= 1;
+                match self.next() {
+                    Some((_, Token::RParen)) => Ok(inner),
+                    _ => {
+                        self.pos -= 1;
+                        Err(self.error("expected ')'"))
+                    }
+                }
+            }
+            _ => self.parse_comparison(),
+        }
+    }
+
+    fn parse_comparison(&mut self) -> Result<ConditionExpr, ConditionError> {
+        let left = self.parse_operand()?;
+        if let Some((_, Token::Op(op))) = self.peek() {
+            let op = *op;
+            self.pos += 1;
+            let right = self.parse_operand()?;
+            return Ok(ConditionExpr::Compare(left, op, right));
+        }
+        Ok(ConditionExpr::Truthy(left))
+    }
+
+    fn parse_operand(&mut self) -> Result<Operand, ConditionError> {
+        match self.peek() {
+            Some((_, Token::Literal(literal))) => {
+                let literal = literal.clone();
+                self.pos += 1;
+                Ok(Operand::Literal(literal))
+            }
+            Some((_, Token::Ident(root))) => {
+                if !CONDITION_ROOTS.contains(&root.as_str()) {
+                    return Err(self.error(&format!("unknown root '{}', expected one of: user, store", root)));
+                }
+                let mut segments = vec![root.clone()];
+                self.pos += 1;
+                while matches!(self.peek(), Some((_, Token::Dot))) {
+                    self.pos += 1;
+                    match self.next() {
+                        Some((_, Token::Ident(segment))) => segments.push(segment),
+                        _ => {
+                            self.pos -= 1;
+                            return Err(self.error("expected property name after '.'"));
+                        }
+                    }
+                }
+                Ok(Operand::Path(segments))
+            }
+            _ => Err(self.error("expected a path or literal")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn eval(condition: &str, context: &ConditionContext) -> Option<bool> {
+        ConditionExpr::parse(condition).unwrap().evaluate(context)
+    }
+
+    fn user_context() -> ConditionContext {
+        ConditionContext::new().with("user", json!({
+            "is_admin": false,
+            "is_guest": false,
+            "full_name": null,
+            "username": "alice",
+            "tags": ["a", "b"],
+        }))
+    }
+
+    #[test]
+    fn test_parse_and_display_round_trip() {
+        for condition in [
+            "user.is_admin",
+            "!user.is_guest",
+            "user.is_admin === true && store.cart.count > 0",
+            "(user.is_admin || user.is_guest) && !(store.step >= 2)",
+            "user.username !== \"bob\"",
+            "store.total <= -1.5",
+        ] {
+            let expr = ConditionExpr::parse(condition).unwrap();
+            assert_eq!(expr.to_string(), condition);
+            assert_eq!(ConditionExpr::parse(&expr.to_string()).unwrap(), expr);
+        }
+        // 单引号字符串输出为双引号
+        assert_eq!(ConditionExpr::parse("user.username === 'it\\'s'").unwrap().to_string(), "user.username === \"it's\"");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(ConditionExpr::parse("user.is_admin == true").unwrap_err().message.contains("==="));
+        assert!(ConditionExpr::parse("window.alert").is_err());
+        assert!(ConditionExpr::parse("user.").is_err());
+        assert!(ConditionExpr::parse("(user.is_admin").is_err());
+        assert!(ConditionExpr::parse("user.is_admin user").is_err());
+        assert!(ConditionExpr::parse("isAdmin()").is_err());
+        assert!(ConditionExpr::parse("'unterminated").is_err());
+        assert!(ConditionExpr::parse(&"!".repeat(100)).is_err());
+        assert_eq!(ConditionExpr::parse("user.a === ").unwrap_err().position, 11);
+    }
+
+    #[test]
+    fn test_evaluate_known_context() {
+        let context = user_context();
+        assert_eq!(eval("user.is_admin", &context), Some(false));
+        assert_eq!(eval("!user.is_admin", &context), Some(true));
+        assert_eq!(eval("user.username === 'alice'", &context), Some(true));
+        assert_eq!(eval("user.tags.length > 1", &context), Some(true));
+        assert_eq!(eval("user.missing === undefined", &context), Some(true));
+        assert_eq!(eval("user.missing === null", &context), Some(false));
+        assert_eq!(eval("user.full_name === null", &context), Some(true));
+        // 不做类型转换
+        assert_eq!(eval("user.tags.length === '2'", &context), Some(false));
+        assert_eq!(eval("user.username > 1", &context), Some(false));
+        // 访问 null 的属性时整个条件为假，与前端一致
+        assert_eq!(eval("!user.full_name.length", &context), Some(false));
+    }
+
+    #[test]
+    fn test_evaluate_client_state_is_unknown() {
+        let context = user_context();
+        assert_eq!(eval("store.hasUnsavedData", &context), None);
+        assert_eq!(eval("!store.hasUnsavedData", &context), None);
+        // 已知部分可以确定结果时提前展开
+        assert_eq!(eval("user.is_admin && store.hasUnsavedData", &context), Some(false));
+        assert_eq!(eval("store.hasUnsavedData && user.is_admin", &context), Some(false));
+        assert_eq!(eval("user.username === 'alice' || store.x", &context), Some(true));
+        assert_eq!(eval("store.x || user.is_guest", &context), None);
+        assert_eq!(eval("!(store.x && user.full_name.length)", &context), None);
+        assert_eq!(eval("user.is_admin", &ConditionContext::new()), None);
+    }
+}
@@ -0,0 +1,77 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+/// 路由决策器生成指令时命中的一条规则及其输入
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RouteDecision {
+    /// 规则名，如 `login.first_login`、`consent.required`
+    pub rule: String,
+    /// 规则使用的输入值，如跳转路径、缺失字段
+    pub inputs: Value,
+}
+
+impl RouteDecision {
+    pub fn new(rule: &str, inputs: Value) -> Self {
+        Self { rule: rule.to_string(), inputs }
+    }
+}
+
+/// 路由决策器生成指令时命中的决策，由调用方创建并传给生成函数，按命中顺序记录
+#[derive(Debug, Default)]
+pub struct RouteTrace {
+    decisions: Vec<RouteDecision>,
+}
+
+impl RouteTrace {
+    pub fn record(&mut self, rule: &str, inputs: Value) {
+        self.decisions.push(RouteDecision::new(rule, inputs));
+    }
+
+    pub fn into_decisions(self) -> Vec<RouteDecision> {
+        self.decisions
+    }
+}
+
+/// 按命中顺序拼接决策，用于指令元数据的 description，如 `login.default {"route":"/pages/home/index"}`
+pub fn describe_decisions(decisions: &[RouteDecision]) -> String {
+    decisions.iter()
+        .map(|decision| match &decision.inputs {
+            Value::Null => decision.rule.clone(),
+            inputs => format!("{} {}", decision.rule, inputs),
+        })
+        .collect::<Vec<_>>()
+        .join(" → ")
+}
+
+/// 记录的路由决策（对应 route_command_decisions 表）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteDecisionRecord {
+    pub execution_id: Uuid,
+    /// 在本次指令生成中的命中顺序，从 0 开始
+    pub seq: i32,
+    pub rule: String,
+    pub inputs: Value,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_describe_decisions() {
+        let decisions = vec![
+            RouteDecision::new("login.policy_consent", json!({ "pending_policies": ["terms"] })),
+            RouteDecision::new("consent.required", json!({ "route": "/pages/auth/consent" })),
+            RouteDecision::new("announcements.none", Value::Null),
+        ];
+        assert_eq!(
+            describe_decisions(&decisions),
+            r#"login.policy_consent {"pending_policies":["terms"]} → consent.required {"route":"/pages/auth/consent"} → announcements.none"#
+        );
+        assert_eq!(describe_decisions(&[]), "");
+    }
+}
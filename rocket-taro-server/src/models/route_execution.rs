@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::admin_stats::StatsRange;
+
+/// 前端上报的路由指令执行结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExecutionStatus {
+    Success,
+    Error,
+}
+
+impl ExecutionStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExecutionStatus::Success => "success",
+            ExecutionStatus::Error => "error",
+        }
+    }
+}
+
+/// POST /api/route-commands/ack 请求（与指标上报接口一致使用 camelCase）
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RouteCommandAckRequest {
+    /// 服务端随路由指令下发的执行ID
+    pub execution_id: Uuid,
+    pub status: ExecutionStatus,
+    /// 前端执行耗时（毫秒）
+    pub duration_ms: Option<i64>,
+    /// 执行失败时的错误信息
+    pub error: Option<String>,
+}
+
+/// 执行确认结果
+#[derive(Debug, Clone, Serialize)]
+pub struct RouteCommandAck {
+    pub execution_id: Uuid,
+    /// 是否记录了本次确认；执行ID未知或已确认过时为 false
+    pub recorded: bool,
+}
+
+/// 单个业务流程的指令完成情况
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlowCompletionStats {
+    /// 生成指令的业务流程，如 login、logout、register
+    pub flow: String,
+    pub issued: i64,
+    pub succeeded: i64,
+    pub failed: i64,
+    /// 已下发但尚未确认
+    pub pending: i64,
+    /// 成功确认数 / 下发数，未下发时为 0
+    pub completion_rate: f64,
+    /// 成功执行的平均耗时（毫秒）
+    pub avg_duration_ms: Option<f64>,
+}
+
+impl FlowCompletionStats {
+    pub fn new(flow: String, issued: i64, succeeded: i64, failed: i64, avg_duration_ms: Option<f64>) -> Self {
+        let completion_rate = if issued > 0 { succeeded as f64 / issued as f64 } else { 0.0 };
+        Self {
+            flow,
+            issued,
+            succeeded,
+            failed,
+            pending: (issued - succeeded - failed).max(0),
+            completion_rate,
+            avg_duration_ms,
+        }
+    }
+}
+
+/// 路由指令完成率统计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteCommandCompletion {
+    pub range: StatsRange,
+    pub flows: Vec<FlowCompletionStats>,
+    pub generated_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flow_completion_stats() {
+        let stats = FlowCompletionStats::new("login".to_string(), 10, 7, 1, Some(120.0));
+        assert_eq!(stats.pending, 2);
+        assert!((stats.completion_rate - 0.7).abs() < f64::EPSILON);
+
+        let empty = FlowCompletionStats::new("logout".to_string(), 0, 0, 0, None);
+        assert_eq!(empty.completion_rate, 0.0);
+        assert_eq!(empty.pending, 0);
+    }
+
+    #[test]
+    fn test_ack_request_uses_camel_case() {
+        let request: RouteCommandAckRequest = serde_json::from_value(serde_json::json!({
+            "executionId": "5f0c6a0e-8a3c-4f5e-9f00-3f6c5a1b2c3d",
+            "status": "error",
+            "durationMs": 42,
+            "error": "页面跳转失败",
+        })).unwrap();
+        assert_eq!(request.status, ExecutionStatus::Error);
+        assert_eq!(request.duration_ms, Some(42));
+    }
+}
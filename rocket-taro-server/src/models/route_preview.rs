@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+use crate::config::Platform;
+use super::route_command::VersionedRouteCommand;
+
+/// 预览的业务结果类型，决定 result 按哪种结果解析、由哪个生成函数处理
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoutePreviewKind {
+    /// result 为 LoginResult
+    Login,
+    /// result 为 LoginResult（游客）
+    GuestLogin,
+    /// result 为 LogoutResult
+    Logout,
+}
+
+impl RoutePreviewKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RoutePreviewKind::Login => "login",
+            RoutePreviewKind::GuestLogin => "guest_login",
+            RoutePreviewKind::Logout => "logout",
+        }
+    }
+}
+
+/// POST /api/admin/route-commands/preview 请求
+#[derive(Debug, Clone, Deserialize)]
+pub struct RoutePreviewRequest {
+    pub kind: RoutePreviewKind,
+    /// 构造的业务结果 JSON
+    pub result: serde_json::Value,
+    /// 目标平台，默认小程序
+    #[serde(default)]
+    pub platform: Platform,
+}
+
+/// 路由指令预览结果
+#[derive(Debug, Clone, Serialize)]
+pub struct RoutePreview {
+    pub kind: RoutePreviewKind,
+    pub platform: Platform,
+    /// 生成的版本化路由指令树
+    pub command: VersionedRouteCommand,
+}
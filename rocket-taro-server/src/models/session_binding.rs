@@ -0,0 +1,169 @@
+use std::net::IpAddr;
+
+use serde::{Deserialize, Serialize};
+
+/// 请求与会话创建时的客户端不一致时的处理方式
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum BindingMode {
+    /// 不检查
+    Off,
+    /// 只记录审计日志
+    #[default]
+    Log,
+    /// 要求重新验证密码，验证后会话绑定到新的客户端
+    Challenge,
+    /// 拒绝请求
+    Reject,
+}
+
+impl BindingMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BindingMode::Off => "off",
+            BindingMode::Log => "log",
+            BindingMode::Challenge => "challenge",
+            BindingMode::Reject => "reject",
+        }
+    }
+}
+
+/// 不一致的项
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BindingMismatch {
+    IpAddress,
+    UserAgent,
+}
+
+/// 用于比较的客户端特征：User-Agent 类别和 IP 网段
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct ClientFingerprint {
+    pub user_agent_family: Option<String>,
+    pub ip_prefix: Option<String>,
+}
+
+impl ClientFingerprint {
+    /// 与另一个特征比较，任一方缺少的项不比较
+    pub fn mismatches(&self, other: &ClientFingerprint) -> Vec<BindingMismatch> {
+        let mut mismatches = Vec::new();
+        if differs(&self.ip_prefix, &other.ip_prefix) {
+            mismatches.push(BindingMismatch::IpAddress);
+        }
+        if differs(&self.user_agent_family, &other.user_agent_family) {
+            mismatches.push(BindingMismatch::UserAgent);
+        }
+        mismatches
+    }
+}
+
+fn differs(a: &Option<String>, b: &Option<String>) -> bool {
+    matches!((a, b), (Some(a), Some(b)) if a != b)
+}
+
+/// User-Agent 类别：客户端类型和操作系统，浏览器版本升级不影响结果
+pub fn user_agent_family(user_agent: &str) -> String {
+    let ua = user_agent.to_lowercase();
+    // 微信登录未携带 User-Agent 时会话记录为 "WeChat Mini Program"
+    let client = if ua.contains("miniprogram") || ua.contains("mini program") {
+        "wechat_miniprogram"
+    } else if ua.contains("micromessenger") {
+        "wechat"
+    } else if ua.contains("edg/") {
+        "edge"
+    } else if ua.contains("firefox/") || ua.contains("fxios/") {
+        "firefox"
+    } else if ua.contains("chrome/") || ua.contains("crios/") {
+        "chrome"
+    } else if ua.contains("safari/") {
+        "safari"
+    } else {
+        "other"
+    };
+    if client == "wechat_miniprogram" {
+        return client.to_string();
+    }
+
+    let os = if ua.contains("android") {
+        "android"
+    } else if ua.contains("iphone") || ua.contains("ipad") {
+        "ios"
+    } else if ua.contains("windows") {
+        "windows"
+    } else if ua.contains("mac os") {
+        "macos"
+    } else if ua.contains("linux") {
+        "linux"
+    } else {
+        "other"
+    };
+    format!("{}/{}", client, os)
+}
+
+/// IP 所在网段，如 `203.0.113.0/24`；IPv4 映射的 IPv6 地址按 IPv4 处理
+pub fn ip_prefix(ip: IpAddr, ipv4_prefix_len: u8, ipv6_prefix_len: u8) -> String {
+    match ip.to_canonical() {
+        IpAddr::V4(v4) => {
+            let len = ipv4_prefix_len.min(32);
+            let mask = u32::MAX.checked_shl(32 - len as u32).unwrap_or(0);
+            format!("{}/{}", std::net::Ipv4Addr::from(u32::from(v4) & mask), len)
+        }
+        IpAddr::V6(v6) => {
+            let len = ipv6_prefix_len.min(128);
+            let mask = u128::MAX.checked_shl(128 - len as u32).unwrap_or(0);
+            format!("{}/{}", std::net::Ipv6Addr::from(u128::from(v6) & mask), len)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_user_agent_family() {
+        let chrome_mac = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+        let chrome_mac_upgraded = chrome_mac.replace("120.0.0.0", "121.0.6167.85");
+        let safari_iphone = "Mozilla/5.0 (iPhone; CPU iPhone OS 17_2 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.2 Mobile/15E148 Safari/604.1";
+        let edge_windows = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36 Edg/120.0.0.0";
+        let mini_program = "Mozilla/5.0 (iPhone; CPU iPhone OS 17_2 like Mac OS X) AppleWebKit/605.1.15 MicroMessenger/8.0.44 miniProgram/wx2078fa60851884ca";
+
+        assert_eq!(user_agent_family(chrome_mac), "chrome/macos");
+        assert_eq!(user_agent_family(chrome_mac), user_agent_family(&chrome_mac_upgraded));
+        assert_eq!(user_agent_family(safari_iphone), "safari/ios");
+        assert_eq!(user_agent_family(edge_windows), "edge/windows");
+        assert_eq!(user_agent_family(mini_program), "wechat_miniprogram");
+        assert_eq!(user_agent_family("WeChat Mini Program"), "wechat_miniprogram");
+        assert_eq!(user_agent_family("curl/8.4.0"), "other/other");
+    }
+
+    #[test]
+    fn test_ip_prefix() {
+        assert_eq!(ip_prefix("203.0.113.77".parse().unwrap(), 24, 48), "203.0.113.0/24");
+        assert_eq!(ip_prefix("::ffff:203.0.113.77".parse().unwrap(), 24, 48), "203.0.113.0/24");
+        assert_eq!(ip_prefix("2001:db8:1234:5678::1".parse().unwrap(), 24, 48), "2001:db8:1234::/48");
+        assert_eq!(ip_prefix("203.0.113.77".parse().unwrap(), 0, 48), "0.0.0.0/0");
+        assert_eq!(ip_prefix("203.0.113.77".parse().unwrap(), 40, 48), "203.0.113.77/32");
+    }
+
+    #[test]
+    fn test_mismatches() {
+        let bound = ClientFingerprint {
+            user_agent_family: Some("chrome/macos".to_string()),
+            ip_prefix: Some("203.0.113.0/24".to_string()),
+        };
+        assert!(bound.mismatches(&bound.clone()).is_empty());
+
+        let moved = ClientFingerprint { ip_prefix: Some("198.51.100.0/24".to_string()), ..bound.clone() };
+        assert_eq!(bound.mismatches(&moved), [BindingMismatch::IpAddress]);
+
+        let unknown = ClientFingerprint::default();
+        assert!(bound.mismatches(&unknown).is_empty());
+
+        let other = ClientFingerprint {
+            user_agent_family: Some("firefox/linux".to_string()),
+            ip_prefix: Some("198.51.100.0/24".to_string()),
+        };
+        assert_eq!(bound.mismatches(&other), [BindingMismatch::IpAddress, BindingMismatch::UserAgent]);
+    }
+}
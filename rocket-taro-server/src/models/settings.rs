@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// 支持的界面语言
+pub const SUPPORTED_LOCALES: &[&str] = &["zh-CN", "en-US"];
+
+/// 界面主题
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    #[default]
+    Light,
+    Dark,
+    /// 跟随系统
+    System,
+}
+
+/// 通知偏好
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationSettings {
+    /// 是否接收站内通知
+    pub enabled: bool,
+    /// 订单状态变更通知
+    pub order_updates: bool,
+    /// 营销活动通知
+    pub marketing: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self { enabled: true, order_updates: true, marketing: false }
+    }
+}
+
+/// 用户偏好设置（保存在 user_settings 表的 JSONB 列中）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UserSettings {
+    pub locale: String,
+    pub theme: Theme,
+    pub notifications: NotificationSettings,
+}
+
+impl Default for UserSettings {
+    fn default() -> Self {
+        Self {
+            locale: "zh-CN".to_string(),
+            theme: Theme::default(),
+            notifications: NotificationSettings::default(),
+        }
+    }
+}
+
+impl UserSettings {
+    /// 按 PATCH 请求更新设置，只接受已知的键，任一键无效时不做任何修改
+    pub fn apply_patch(&mut self, patch: &Value) -> Result<(), String> {
+        let patch = patch.as_object().ok_or("设置必须是JSON对象")?;
+        let mut updated = self.clone();
+
+        for (key, value) in patch {
+            match key.as_str() {
+                "locale" => {
+                    let locale = value.as_str()
+                        .filter(|locale| SUPPORTED_LOCALES.contains(locale))
+                        .ok_or_else(|| format!("不支持的语言: {}，可选值: {}", value, SUPPORTED_LOCALES.join(", ")))?;
+                    updated.locale = locale.to_string();
+                }
+                "theme" => {
+                    updated.theme = serde_json::from_value(value.clone())
+                        .map_err(|_| format!("无效的主题: {}，可选值: light, dark, system", value))?;
+                }
+                "notifications" => {
+                    let notifications = value.as_object().ok_or("notifications 必须是JSON对象")?;
+                    updated.notifications.apply_patch(notifications)?;
+                }
+                _ => return Err(format!("未知的设置项: {}", key)),
+            }
+        }
+
+        *self = updated;
+        Ok(())
+    }
+}
+
+impl NotificationSettings {
+    fn apply_patch(&mut self, patch: &Map<String, Value>) -> Result<(), String> {
+        for (key, value) in patch {
+            let field = match key.as_str() {
+                "enabled" => &mut self.enabled,
+                "order_updates" => &mut self.order_updates,
+                "marketing" => &mut self.marketing,
+                _ => return Err(format!("未知的通知设置项: notifications.{}", key)),
+            };
+            *field = value.as_bool().ok_or_else(|| format!("notifications.{} 必须是布尔值", key))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_apply_patch() {
+        let mut settings = UserSettings::default();
+        settings.apply_patch(&json!({ "theme": "dark", "notifications": { "marketing": true } })).unwrap();
+        assert_eq!(settings.theme, Theme::Dark);
+        assert!(settings.notifications.marketing);
+        assert!(settings.notifications.order_updates);
+        assert_eq!(settings.locale, "zh-CN");
+    }
+
+    #[test]
+    fn test_invalid_patch_is_rejected_atomically() {
+        let mut settings = UserSettings::default();
+        assert!(settings.apply_patch(&json!({ "theme": "dark", "locale": "fr-FR" })).is_err());
+        assert!(settings.apply_patch(&json!({ "font_size": 14 })).is_err());
+        assert!(settings.apply_patch(&json!({ "notifications": { "enabled": "yes" } })).is_err());
+        assert!(settings.apply_patch(&json!(["theme"])).is_err());
+        assert_eq!(settings, UserSettings::default());
+    }
+}
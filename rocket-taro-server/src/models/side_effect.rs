@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use uuid::Uuid;
+
+/// 需要可靠执行的副作用，与业务数据在同一事务中写入发件箱，由后台任务执行并重试
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SideEffect {
+    /// 写入登录日志
+    LoginLog {
+        user_id: Option<Uuid>,
+        username: String,
+        success: bool,
+        ip_address: Option<IpAddr>,
+        user_agent: Option<String>,
+        note: Option<String>,
+    },
+    /// 按会话ID从数据库读取最新的用户和会话并写入缓存；发件箱不保存明文会话令牌，
+    /// 以令牌为键的会话缓存在首次请求时写入
+    CacheUserSession {
+        user_id: Uuid,
+        session_id: Uuid,
+    },
+    /// 清除登录失败计数
+    ClearLoginFailures {
+        username: String,
+    },
+}
+
+impl SideEffect {
+    /// 副作用类型，存入 effect_type 列，便于排查
+    pub fn effect_type(&self) -> &'static str {
+        match self {
+            SideEffect::LoginLog { .. } => "login_log",
+            SideEffect::CacheUserSession { .. } => "cache_user_session",
+            SideEffect::ClearLoginFailures { .. } => "clear_login_failures",
+        }
+    }
+}
+
+/// 发件箱记录状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SideEffectStatus {
+    /// 等待执行或等待重试
+    Pending,
+    /// 已执行
+    Done,
+    /// 超过最大尝试次数，放弃
+    Dead,
+}
+
+impl SideEffectStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SideEffectStatus::Pending => "pending",
+            SideEffectStatus::Done => "done",
+            SideEffectStatus::Dead => "dead",
+        }
+    }
+}
+
+/// 已被领取、等待执行的副作用
+#[derive(Debug, Clone)]
+pub struct PendingSideEffect {
+    pub id: Uuid,
+    /// 包含本次在内的尝试次数
+    pub attempts: i32,
+    pub payload: serde_json::Value,
+}
+
+impl PendingSideEffect {
+    /// 解析副作用内容，旧版本写入的未知类型会解析失败
+    pub fn effect(&self) -> Result<SideEffect, serde_json::Error> {
+        serde_json::from_value(self.payload.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_side_effect_payload_roundtrip() {
+        let effect = SideEffect::LoginLog {
+            user_id: Some(Uuid::nil()),
+            username: "alice".to_string(),
+            success: true,
+            ip_address: Some("127.0.0.1".parse().unwrap()),
+            user_agent: None,
+            note: Some("游客登录".to_string()),
+        };
+
+        let payload = serde_json::to_value(&effect).unwrap();
+        assert_eq!(payload["type"], effect.effect_type());
+        assert_eq!(serde_json::from_value::<SideEffect>(payload).unwrap(), effect);
+    }
+}
@@ -0,0 +1,29 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// 数据导出文件的存储类别
+pub const DATA_EXPORT_KIND: &str = "data_export";
+
+/// 存储中的一个生成文件（对应 storage_objects 表）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageObject {
+    pub key: String,
+    /// 文件所属用户，用户删除后为空，文件仍按过期时间清理
+    pub user_id: Option<Uuid>,
+    /// 文件类别，如 `data_export`
+    pub kind: String,
+    pub size: i64,
+    pub content_type: String,
+    pub created_at: DateTime<Utc>,
+    /// 过期时间，为空表示长期保留
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// 用户占用的存储空间
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageUsage {
+    pub user_id: Option<Uuid>,
+    pub object_count: i64,
+    pub total_bytes: i64,
+}
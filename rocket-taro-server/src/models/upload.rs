@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// 文件名最大字符数
+const MAX_FILE_NAME_CHARS: usize = 100;
+
+/// 上传的文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Upload {
+    pub id: Uuid,
+    /// 上传者，未登录上传时为 None
+    pub user_id: Option<Uuid>,
+    pub file_name: String,
+    pub content_type: String,
+    pub file_size: i64,
+    /// 服务器上的文件路径，不对外暴露
+    #[serde(skip)]
+    pub file_path: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Upload {
+    /// 对外展示的附件信息
+    pub fn attachment(&self) -> Attachment {
+        Attachment {
+            id: self.id,
+            file_name: self.file_name.clone(),
+            content_type: self.content_type.clone(),
+            file_size: self.file_size,
+            url: download_url(self.id),
+        }
+    }
+
+    /// 图片在浏览器中直接显示，其他文件作为附件下载
+    pub fn is_inline(&self) -> bool {
+        self.content_type.starts_with("image/")
+    }
+}
+
+/// 用户数据附件
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Attachment {
+    pub id: Uuid,
+    pub file_name: String,
+    pub content_type: String,
+    pub file_size: i64,
+    pub url: String,
+}
+
+/// 文件下载地址
+pub fn download_url(upload_id: Uuid) -> String {
+    format!("/api/uploads/{}", upload_id)
+}
+
+/// 规范化客户端提交的文件名：只保留最后一段路径，去除控制字符和引号，过长时截断
+pub fn sanitize_file_name(raw: &str) -> String {
+    let name = raw.rsplit(['/', '\\']).next().unwrap_or_default();
+    let name: String = name.chars()
+        .filter(|c| !c.is_control() && *c != '"')
+        .take(MAX_FILE_NAME_CHARS)
+        .collect();
+    let name = name.trim();
+    if name.is_empty() || name.chars().all(|c| c == '.') {
+        "file".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_file_name() {
+        assert_eq!(sanitize_file_name("照片.jpg"), "照片.jpg");
+        assert_eq!(sanitize_file_name("C:\\Users\\a\\report.pdf"), "report.pdf");
+        assert_eq!(sanitize_file_name("../../etc/passwd"), "passwd");
+        assert_eq!(sanitize_file_name("a\"b\r\n.txt"), "ab.txt");
+        assert_eq!(sanitize_file_name(".."), "file");
+        assert_eq!(sanitize_file_name(""), "file");
+        assert_eq!(sanitize_file_name(&"长".repeat(200)).chars().count(), MAX_FILE_NAME_CHARS);
+    }
+}
@@ -1,6 +1,42 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
+use super::response::FieldError;
+use super::upload::Attachment;
+use crate::utils::validation::{normalize_phone, parse_email, strip_html};
+
+/// 姓名最大字符数
+pub const MAX_NAME_CHARS: usize = 50;
+/// 留言最大字符数
+pub const MAX_MESSAGE_CHARS: usize = 1000;
+
+/// 用户数据的处理状态，管理员跟进时更新
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum UserDataStatus {
+    #[default]
+    New,
+    InProgress,
+    Resolved,
+}
+
+impl UserDataStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserDataStatus::New => "new",
+            UserDataStatus::InProgress => "in_progress",
+            UserDataStatus::Resolved => "resolved",
+        }
+    }
+
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "in_progress" => UserDataStatus::InProgress,
+            "resolved" => UserDataStatus::Resolved,
+            _ => UserDataStatus::New,
+        }
+    }
+}
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct UserData {
@@ -10,14 +46,73 @@ pub struct UserData {
     pub phone: Option<String>,
     pub message: Option<String>,
     pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
+    #[serde(default)]
+    pub status: UserDataStatus,
+    /// 乐观锁版本号，修改处理状态时递增
+    #[serde(default)]
+    pub version: i32,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 pub struct NewUserData {
     pub name: String,
     pub email: String,
     pub phone: Option<String>,
     pub message: Option<String>,
+    /// 通过 POST /api/uploads 上传的文件ID，按顺序作为附件
+    #[serde(default)]
+    pub attachment_ids: Vec<Uuid>,
+}
+
+impl NewUserData {
+    /// 校验并规范化提交内容：去除 HTML、邮箱域名转小写、手机号转为 E.164 格式，空的选填项视为未填写
+    pub fn normalize(self) -> Result<NewUserData, Vec<FieldError>> {
+        let mut errors = Vec::new();
+
+        let name = strip_html(&self.name);
+        if name.is_empty() {
+            errors.push(FieldError::new("name", "请输入姓名"));
+        } else if name.chars().count() > MAX_NAME_CHARS {
+            errors.push(FieldError::new("name", format!("姓名不能超过{}个字符", MAX_NAME_CHARS)));
+        } else if name.chars().any(char::is_control) {
+            errors.push(FieldError::new("name", "姓名包含非法字符"));
+        }
+
+        let email = parse_email(&self.email);
+        if email.is_none() {
+            let message = if self.email.trim().is_empty() { "请输入邮箱" } else { "邮箱格式不正确" };
+            errors.push(FieldError::new("email", message));
+        }
+
+        let phone = match self.phone.as_deref().map(str::trim).filter(|phone| !phone.is_empty()) {
+            Some(phone) => {
+                let normalized = normalize_phone(phone);
+                if normalized.is_none() {
+                    errors.push(FieldError::new("phone", "手机号格式不正确"));
+                }
+                normalized
+            }
+            None => None,
+        };
+
+        let message = self.message.as_deref().map(strip_html).filter(|message| !message.is_empty());
+        if message.as_ref().is_some_and(|message| message.chars().count() > MAX_MESSAGE_CHARS) {
+            errors.push(FieldError::new("message", format!("留言不能超过{}个字符", MAX_MESSAGE_CHARS)));
+        }
+
+        let attachment_ids = self.attachment_ids;
+        let unique: std::collections::HashSet<&Uuid> = attachment_ids.iter().collect();
+        if unique.len() != attachment_ids.len() {
+            errors.push(FieldError::new("attachment_ids", "附件重复"));
+        }
+
+        match email {
+            Some(email) if errors.is_empty() => Ok(NewUserData { name, email, phone, message, attachment_ids }),
+            _ => Err(errors),
+        }
+    }
 }
 
 impl UserData {
@@ -29,6 +124,127 @@ impl UserData {
             phone: data.phone,
             message: data.message,
             created_at: Utc::now(),
+            attachments: Vec::new(),
+            status: UserDataStatus::New,
+            version: 1,
+        }
+    }
+}
+/// 管理员对用户数据的回复
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UserDataReply {
+    pub id: Uuid,
+    pub user_data_id: Uuid,
+    /// 回复的管理员，管理员账户删除后为 None
+    pub admin_id: Option<Uuid>,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// 用户数据及其回复（按时间顺序）
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UserDataThread {
+    #[serde(flatten)]
+    pub data: UserData,
+    pub replies: Vec<UserDataReply>,
+}
+
+/// 管理员回复请求，可同时更新处理状态；未指定状态时新提交转为处理中
+#[derive(Deserialize, Debug)]
+pub struct UserDataReplyRequest {
+    pub content: String,
+    #[serde(default)]
+    pub status: Option<UserDataStatus>,
+}
+
+/// 更新处理状态请求，版本号也可通过 If-Match 请求头提交
+#[derive(Deserialize, Debug)]
+pub struct UserDataStatusRequest {
+    pub status: UserDataStatus,
+    #[serde(default)]
+    pub version: Option<i32>,
+}
+
+/// 全文搜索命中的用户数据
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UserDataSearchHit {
+    #[serde(flatten)]
+    pub data: UserData,
+    /// 相关度得分
+    pub rank: f32,
+    /// 高亮后的留言片段，匹配词以 `<mark>` 标记
+    pub highlight: Option<String>,
+}
+
+/// 全文搜索结果
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UserDataSearchResult {
+    pub query: String,
+    pub hits: Vec<UserDataSearchHit>,
+}
+
+/// 规范化搜索词：去除首尾空白、合并连续空白并转为小写，用作缓存键
+pub fn normalize_search_query(query: &str) -> String {
+    query.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_search_query() {
+        assert_eq!(normalize_search_query("  Hello   World\t"), "hello world");
+        assert_eq!(normalize_search_query("张三  留言"), "张三 留言");
+        assert_eq!(normalize_search_query("   "), "");
+    }
+
+    fn new_data(name: &str, email: &str, phone: Option<&str>, message: Option<&str>) -> NewUserData {
+        NewUserData {
+            name: name.to_string(),
+            email: email.to_string(),
+            phone: phone.map(str::to_string),
+            message: message.map(str::to_string),
+            attachment_ids: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_normalize_new_user_data() {
+        let data = new_data(" <b>张三</b> ", "Zhang.San@Example.COM", Some("138 1234 5678"), Some("<p>你好</p>")).normalize().unwrap();
+        assert_eq!(data.name, "张三");
+        assert_eq!(data.email, "Zhang.San@example.com");
+        assert_eq!(data.phone.as_deref(), Some("+8613812345678"));
+        assert_eq!(data.message.as_deref(), Some("你好"));
+
+        let data = new_data("李四", "lisi@example.com", Some("  "), Some("<br>")).normalize().unwrap();
+        assert_eq!(data.phone, None);
+        assert_eq!(data.message, None);
+    }
+
+    #[test]
+    fn test_normalize_new_user_data_errors() {
+        let long_message = "留".repeat(MAX_MESSAGE_CHARS + 1);
+        let errors = new_data("<i></i>", "not-an-email", Some("12345"), Some(&long_message)).normalize().unwrap_err();
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, ["name", "email", "phone", "message"]);
+
+        let errors = new_data("王五", " ", None, None).normalize().unwrap_err();
+        assert_eq!(errors, vec![FieldError::new("email", "请输入邮箱")]);
+
+        let id = Uuid::new_v4();
+        let mut data = new_data("王五", "wangwu@example.com", None, None);
+        data.attachment_ids = vec![id, id];
+        assert_eq!(data.normalize().unwrap_err(), vec![FieldError::new("attachment_ids", "附件重复")]);
+    }
+
+    #[test]
+    fn test_user_data_status() {
+        for status in [UserDataStatus::New, UserDataStatus::InProgress, UserDataStatus::Resolved] {
+            assert_eq!(UserDataStatus::parse(status.as_str()), status);
+            assert_eq!(serde_json::to_value(status).unwrap(), status.as_str());
         }
+        assert_eq!(UserDataStatus::parse("unknown"), UserDataStatus::New);
+        assert!(serde_json::from_str::<UserDataStatus>("\"closed\"").is_err());
     }
-}
\ No newline at end of file
+}
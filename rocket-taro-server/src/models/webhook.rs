@@ -0,0 +1,251 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+/// 对外推送的事件类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WebhookEventType {
+    #[serde(rename = "user.registered")]
+    UserRegistered,
+    #[serde(rename = "user_data.created")]
+    UserDataCreated,
+}
+
+impl WebhookEventType {
+    pub const ALL: &'static [WebhookEventType] = &[WebhookEventType::UserRegistered, WebhookEventType::UserDataCreated];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookEventType::UserRegistered => "user.registered",
+            WebhookEventType::UserDataCreated => "user_data.created",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|event_type| event_type.as_str() == value)
+    }
+}
+
+/// 订阅全部事件的通配符
+pub const WILDCARD_EVENT: &str = "*";
+
+/// 管理员配置的 Webhook 接收端
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookEndpoint {
+    pub id: Uuid,
+    pub url: String,
+    pub description: Option<String>,
+    /// 订阅的事件类型，`*` 表示全部
+    pub event_types: Vec<String>,
+    /// 签名密钥，仅在创建和轮换时返回
+    #[serde(skip_serializing)]
+    pub secret: String,
+    pub is_active: bool,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WebhookEndpoint {
+    /// 是否订阅了指定事件
+    pub fn subscribes_to(&self, event_type: &str) -> bool {
+        self.event_types.iter().any(|t| t == WILDCARD_EVENT || t == event_type)
+    }
+}
+
+/// 创建接收端或轮换密钥后的返回结果，密钥只在此时可见
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookEndpointWithSecret {
+    #[serde(flatten)]
+    pub endpoint: WebhookEndpoint,
+    pub secret: String,
+}
+
+/// 创建接收端请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateWebhookEndpointRequest {
+    pub url: String,
+    pub description: Option<String>,
+    pub event_types: Vec<String>,
+}
+
+/// 更新接收端请求，未提供的字段保持不变
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateWebhookEndpointRequest {
+    pub url: Option<String>,
+    pub description: Option<String>,
+    pub event_types: Option<Vec<String>>,
+    pub is_active: Option<bool>,
+}
+
+/// 校验接收端地址，只允许 http/https
+pub fn validate_endpoint_url(url: &str) -> Result<String, String> {
+    let parsed = reqwest::Url::parse(url.trim()).map_err(|_| "无效的 Webhook 地址".to_string())?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        return Err("Webhook 地址必须是 http 或 https URL".to_string());
+    }
+    if url.len() > 2048 {
+        return Err("Webhook 地址过长".to_string());
+    }
+    Ok(parsed.to_string())
+}
+
+/// 校验并去重订阅的事件类型
+pub fn validate_event_types(event_types: &[String]) -> Result<Vec<String>, String> {
+    let mut result: Vec<String> = Vec::new();
+    for event_type in event_types.iter().map(|t| t.trim()) {
+        if event_type != WILDCARD_EVENT && WebhookEventType::parse(event_type).is_none() {
+            return Err(format!("不支持的事件类型: {}", event_type));
+        }
+        if !result.iter().any(|t| t == event_type) {
+            result.push(event_type.to_string());
+        }
+    }
+    if result.is_empty() {
+        return Err("至少需要订阅一个事件类型".to_string());
+    }
+    Ok(result)
+}
+
+/// 发件箱中的事件，与业务数据在同一事务中写入
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookEvent {
+    pub id: Uuid,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+impl WebhookEvent {
+    /// 推送给接收端的请求体
+    pub fn envelope(&self) -> serde_json::Value {
+        serde_json::json!({
+            "id": self.id,
+            "type": self.event_type,
+            "created_at": self.created_at,
+            "data": self.payload,
+        })
+    }
+}
+
+/// 投递状态
+///
+/// pending → delivering → succeeded，
+/// 失败后回到 pending 等待重试，超过最大次数后转为 dead
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookDeliveryStatus {
+    Pending,
+    Delivering,
+    Succeeded,
+    Dead,
+}
+
+impl WebhookDeliveryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookDeliveryStatus::Pending => "pending",
+            WebhookDeliveryStatus::Delivering => "delivering",
+            WebhookDeliveryStatus::Succeeded => "succeeded",
+            WebhookDeliveryStatus::Dead => "dead",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(WebhookDeliveryStatus::Pending),
+            "delivering" => Some(WebhookDeliveryStatus::Delivering),
+            "succeeded" => Some(WebhookDeliveryStatus::Succeeded),
+            "dead" => Some(WebhookDeliveryStatus::Dead),
+            _ => None,
+        }
+    }
+}
+
+/// 单个接收端对单个事件的投递记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub endpoint_id: Uuid,
+    pub event_id: Uuid,
+    pub event_type: String,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_status_code: Option<i32>,
+    pub last_error: Option<String>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// 单次投递尝试的日志
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookDeliveryAttempt {
+    pub id: Uuid,
+    pub delivery_id: Uuid,
+    pub attempt: i32,
+    pub status_code: Option<i32>,
+    pub error: Option<String>,
+    pub duration_ms: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// 投递详情：记录及全部尝试日志
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookDeliveryDetail {
+    #[serde(flatten)]
+    pub delivery: WebhookDelivery,
+    pub event: WebhookEvent,
+    pub attempts_log: Vec<WebhookDeliveryAttempt>,
+}
+
+/// 投递任务：已领取的投递记录及发送所需的数据
+#[derive(Debug, Clone)]
+pub struct WebhookDeliveryTask {
+    pub delivery: WebhookDelivery,
+    pub url: String,
+    pub secret: String,
+    pub event: WebhookEvent,
+}
+
+/// 第 attempt 次失败后的重试等待时间：指数退避，上限为 max
+pub fn retry_backoff(attempt: i32, base: Duration, max: Duration) -> Duration {
+    let exponent = attempt.saturating_sub(1).clamp(0, 20) as u32;
+    let delay = base.checked_mul(2_i32.pow(exponent)).unwrap_or(max);
+    delay.min(max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_backoff() {
+        let base = Duration::seconds(30);
+        let max = Duration::hours(1);
+        assert_eq!(retry_backoff(1, base, max), Duration::seconds(30));
+        assert_eq!(retry_backoff(2, base, max), Duration::seconds(60));
+        assert_eq!(retry_backoff(4, base, max), Duration::seconds(240));
+        assert_eq!(retry_backoff(10, base, max), max);
+        assert_eq!(retry_backoff(1000, base, max), max);
+    }
+
+    #[test]
+    fn test_validate_event_types() {
+        assert_eq!(
+            validate_event_types(&["user.registered".to_string(), "user.registered".to_string()]).unwrap(),
+            vec!["user.registered".to_string()]
+        );
+        assert!(validate_event_types(&["*".to_string()]).is_ok());
+        assert!(validate_event_types(&[]).is_err());
+        assert!(validate_event_types(&["order.paid".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_validate_endpoint_url() {
+        assert!(validate_endpoint_url("https://example.com/hooks").is_ok());
+        assert!(validate_endpoint_url("ftp://example.com/hooks").is_err());
+        assert!(validate_endpoint_url("not a url").is_err());
+    }
+}
@@ -2,25 +2,33 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 pub struct WxLoginRequest {
     pub code: String,
     pub encrypted_data: Option<String>,
     pub iv: Option<String>,
     pub signature: Option<String>,
     pub raw_data: Option<String>,
+    /// 找回密码模式：只登录已绑定该微信的账户，账户设置过密码时提示重置
+    #[serde(default)]
+    pub recover_password: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct WxLoginResponse {
     pub user: crate::models::auth::UserInfo,
+    /// 防重放记录中不保存，重放时重新签发
+    #[serde(default)]
     pub session_token: String,
     pub expires_at: DateTime<Utc>,
 }
 
+/// 出错时微信只返回 errcode 和 errmsg
 #[derive(Deserialize, Debug)]
 pub struct Code2SessionResponse {
+    #[serde(default)]
     pub openid: String,
+    #[serde(default)]
     pub session_key: String,
     pub unionid: Option<String>,
     pub errcode: Option<i32>,
@@ -43,6 +51,17 @@ pub struct WxUser {
     pub last_login_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub version: i32,
+}
+
+impl From<&crate::models::business_results::LoginResult> for WxLoginResponse {
+    fn from(login: &crate::models::business_results::LoginResult) -> Self {
+        WxLoginResponse {
+            user: crate::models::auth::UserInfo::from(login.user.clone()),
+            session_token: login.session.session_token.clone(),
+            expires_at: login.session.expires_at,
+        }
+    }
 }
 
 impl From<WxUser> for crate::models::auth::User {
@@ -62,6 +81,7 @@ impl From<WxUser> for crate::models::auth::User {
             last_login_at: wx_user.last_login_at,
             created_at: wx_user.created_at,
             updated_at: wx_user.updated_at,
+            version: wx_user.version,
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,58 @@
+use rocket::async_trait;
+
+use crate::models::moderation::{ModerationSuggest, ModerationVerdict};
+use super::{ContentModerator, TextSubmission};
+
+/// 按本地关键词列表审核文本，不区分大小写；图片一律放行
+pub struct KeywordModerator {
+    blocked: Vec<String>,
+    review: Vec<String>,
+}
+
+impl KeywordModerator {
+    pub fn new(blocked: &[String], review: &[String]) -> Self {
+        let normalize = |keywords: &[String]| keywords.iter()
+            .map(|keyword| keyword.trim().to_lowercase())
+            .filter(|keyword| !keyword.is_empty())
+            .collect();
+        Self { blocked: normalize(blocked), review: normalize(review) }
+    }
+}
+
+#[async_trait]
+impl ContentModerator for KeywordModerator {
+    fn name(&self) -> &'static str {
+        "keyword"
+    }
+
+    async fn check_text(&self, text: &TextSubmission<'_>) -> Result<ModerationVerdict, String> {
+        let content = text.content.to_lowercase();
+        if self.blocked.iter().any(|keyword| content.contains(keyword.as_str())) {
+            return Ok(ModerationVerdict::new(ModerationSuggest::Risky, "违禁词"));
+        }
+        if self.review.iter().any(|keyword| content.contains(keyword.as_str())) {
+            return Ok(ModerationVerdict::new(ModerationSuggest::Review, "敏感词"));
+        }
+        Ok(ModerationVerdict::pass())
+    }
+
+    async fn check_image(&self, _image: &[u8], _content_type: &str) -> Result<ModerationVerdict, String> {
+        Ok(ModerationVerdict::pass())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::moderation::ModerationSubject;
+
+    #[tokio::test]
+    async fn test_keyword_moderation() {
+        let moderator = KeywordModerator::new(&["Casino".to_string(), " ".to_string()], &["加微信".to_string()]);
+        let text = |content| TextSubmission { content, subject: ModerationSubject::UserDataMessage, openid: None };
+
+        assert_eq!(moderator.check_text(&text("你好")).await.unwrap(), ModerationVerdict::pass());
+        assert_eq!(moderator.check_text(&text("online CASINO")).await.unwrap().suggest, ModerationSuggest::Risky);
+        assert_eq!(moderator.check_text(&text("详情加微信")).await.unwrap().suggest, ModerationSuggest::Review);
+    }
+}
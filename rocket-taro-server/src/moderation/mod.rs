@@ -0,0 +1,48 @@
+use rocket::async_trait;
+use std::sync::Arc;
+use tracing::warn;
+
+use crate::config::{ModerationConfig, ModerationProviderKind, WechatConfig};
+use crate::models::moderation::{ModerationSubject, ModerationVerdict};
+use crate::utils::http_client::HttpClient;
+
+pub mod keyword_moderator;
+pub mod wechat_moderator;
+
+pub use keyword_moderator::KeywordModerator;
+pub use wechat_moderator::WechatModerator;
+
+/// 待审核的文本
+#[derive(Debug, Clone, Copy)]
+pub struct TextSubmission<'a> {
+    pub content: &'a str,
+    pub subject: ModerationSubject,
+    /// 提交者的微信 openid，未登录或非微信用户为 None
+    pub openid: Option<&'a str>,
+}
+
+/// 内容审核服务接口
+#[async_trait]
+pub trait ContentModerator: Send + Sync {
+    /// 服务名称，用于日志
+    fn name(&self) -> &'static str;
+
+    /// 审核文本
+    async fn check_text(&self, text: &TextSubmission<'_>) -> Result<ModerationVerdict, String>;
+
+    /// 审核图片
+    async fn check_image(&self, image: &[u8], content_type: &str) -> Result<ModerationVerdict, String>;
+}
+
+/// 按配置选择审核服务实现，作为 Rocket 托管状态共享
+pub fn moderator_from_config(config: &ModerationConfig, wechat: WechatConfig, http: &HttpClient) -> Arc<dyn ContentModerator> {
+    match config.provider {
+        ModerationProviderKind::Wechat => Arc::new(WechatModerator::new(wechat, http.clone())),
+        ModerationProviderKind::Keyword => {
+            if config.enabled {
+                warn!("Keyword moderator enabled, images are not moderated");
+            }
+            Arc::new(KeywordModerator::new(&config.blocked_keywords, &config.review_keywords))
+        }
+    }
+}
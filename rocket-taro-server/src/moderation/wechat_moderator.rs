@@ -0,0 +1,236 @@
+use rocket::async_trait;
+use serde::Deserialize;
+use serde_json::json;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tracing::{debug, error, info};
+use uuid::Uuid;
+
+use crate::config::WechatConfig;
+use crate::models::moderation::{ModerationSubject, ModerationSuggest, ModerationVerdict};
+use crate::utils::http_client::HttpClient;
+use super::{ContentModerator, TextSubmission};
+
+/// 获取稳定版 access_token 的接口路径；普通版接口会使正在使用的 access_token 失效
+const STABLE_TOKEN_PATH: &str = "/cgi-bin/stable_token";
+const MSG_SEC_CHECK_PATH: &str = "/wxa/msg_sec_check";
+const IMG_SEC_CHECK_PATH: &str = "/wxa/img_sec_check";
+
+/// 内容含有违法违规内容（1.0 版接口）
+const RISKY_CONTENT_ERRCODE: i32 = 87014;
+/// access_token 无效 / 已过期 / 不合法
+const INVALID_TOKEN_ERRCODES: [i32; 3] = [40001, 42001, 40014];
+/// access_token 提前刷新的时间
+const TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    #[serde(default)]
+    access_token: Option<String>,
+    #[serde(default)]
+    expires_in: Option<u64>,
+    #[serde(default)]
+    errcode: Option<i32>,
+    #[serde(default)]
+    errmsg: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SecCheckResult {
+    suggest: String,
+    label: i32,
+}
+
+#[derive(Debug, Deserialize)]
+struct SecCheckResponse {
+    #[serde(default)]
+    errcode: i32,
+    #[serde(default)]
+    errmsg: Option<String>,
+    /// 只有 2.0 版接口返回
+    #[serde(default)]
+    result: Option<SecCheckResult>,
+}
+
+/// 微信内容安全接口的违规类型说明
+fn label_name(label: i32) -> &'static str {
+    match label {
+        10001 => "广告",
+        20001 => "时政",
+        20002 => "色情",
+        20003 => "辱骂",
+        20006 => "违法犯罪",
+        20008 => "欺诈",
+        20012 => "低俗",
+        20013 => "版权",
+        _ => "其他",
+    }
+}
+
+/// 2.0 版接口的场景值：1 资料，2 评论
+fn scene(subject: ModerationSubject) -> u8 {
+    match subject {
+        ModerationSubject::ProfileName => 1,
+        _ => 2,
+    }
+}
+
+/// 调用微信内容安全接口：文本有 openid 时使用 msgSecCheck 2.0，否则使用 1.0；图片使用 imgSecCheck
+pub struct WechatModerator {
+    config: WechatConfig,
+    http: HttpClient,
+    /// 缓存的 access_token 及其过期时间
+    token: Mutex<Option<(String, Instant)>>,
+}
+
+impl WechatModerator {
+    pub fn new(config: WechatConfig, http: HttpClient) -> Self {
+        Self { config, http, token: Mutex::new(None) }
+    }
+
+    async fn access_token(&self) -> Result<String, String> {
+        let mut token = self.token.lock().await;
+        if let Some((value, expires_at)) = token.as_ref() {
+            if Instant::now() < *expires_at {
+                return Ok(value.clone());
+            }
+        }
+
+        let request = self.http
+            .post(format!("{}{}", self.config.api_base, STABLE_TOKEN_PATH))
+            .timeout(Duration::from_secs(self.config.request_timeout_secs))
+            .json(&json!({
+                "grant_type": "client_credential",
+                "appid": self.config.app_id,
+                "secret": self.config.app_secret,
+            }));
+        let response: TokenResponse = self.http.send(request).await
+            .map_err(|e| format!("HTTP request failed: {}", e.without_url()))?
+            .json().await
+            .map_err(|e| format!("Invalid stable_token response: {}", e.without_url()))?;
+
+        let Some(access_token) = response.access_token else {
+            error!(errcode = ?response.errcode, "Failed to get WeChat access token: {}", response.errmsg.as_deref().unwrap_or_default());
+            return Err(format!("stable_token returned error {:?}", response.errcode));
+        };
+        let ttl = Duration::from_secs(response.expires_in.unwrap_or(7200)).saturating_sub(TOKEN_REFRESH_MARGIN);
+        *token = Some((access_token.clone(), Instant::now() + ttl));
+        debug!("WeChat access token refreshed");
+        Ok(access_token)
+    }
+
+    async fn call(&self, path: &str, request: reqwest::RequestBuilder) -> Result<SecCheckResponse, String> {
+        let response = self.http.send(request).await
+            .map_err(|e| {
+                let e = e.without_url();
+                error!("HTTP request to WeChat {} failed: {}", path, e);
+                format!("HTTP request failed: {}", e)
+            })?;
+        if !response.status().is_success() {
+            return Err(format!("WeChat {} returned HTTP {}", path, response.status()));
+        }
+        let response: SecCheckResponse = response.json().await
+            .map_err(|e| format!("Invalid WeChat {} response: {}", path, e.without_url()))?;
+        if INVALID_TOKEN_ERRCODES.contains(&response.errcode) {
+            // 令牌在其他地方被刷新或提前失效，下次调用重新获取
+            *self.token.lock().await = None;
+        }
+        Ok(response)
+    }
+
+    fn verdict(path: &str, response: SecCheckResponse) -> Result<ModerationVerdict, String> {
+        match (response.errcode, response.result) {
+            (0, Some(result)) => {
+                let suggest = match result.suggest.as_str() {
+                    "pass" => return Ok(ModerationVerdict::pass()),
+                    "review" => ModerationSuggest::Review,
+                    "risky" => ModerationSuggest::Risky,
+                    other => return Err(format!("Unknown suggest from WeChat {}: {}", path, other)),
+                };
+                Ok(ModerationVerdict::new(suggest, label_name(result.label)))
+            }
+            (0, None) => Ok(ModerationVerdict::pass()),
+            (RISKY_CONTENT_ERRCODE, _) => Ok(ModerationVerdict { suggest: ModerationSuggest::Risky, label: None }),
+            (errcode, _) => {
+                let errmsg = response.errmsg.unwrap_or_default();
+                error!("WeChat {} returned error code {}: {}", path, errcode, errmsg);
+                Err(format!("WeChat {} returned error {}: {}", path, errcode, errmsg))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl ContentModerator for WechatModerator {
+    fn name(&self) -> &'static str {
+        "wechat"
+    }
+
+    async fn check_text(&self, text: &TextSubmission<'_>) -> Result<ModerationVerdict, String> {
+        info!(subject = text.subject.as_str(), "Calling WeChat API: msgSecCheck");
+
+        let access_token = self.access_token().await?;
+        let body = match text.openid {
+            Some(openid) => json!({
+                "content": text.content,
+                "version": 2,
+                "scene": scene(text.subject),
+                "openid": openid,
+            }),
+            None => json!({ "content": text.content }),
+        };
+        let request = self.http
+            .post(format!("{}{}", self.config.api_base, MSG_SEC_CHECK_PATH))
+            .timeout(Duration::from_secs(self.config.request_timeout_secs))
+            .query(&[("access_token", access_token.as_str())])
+            .json(&body);
+        let response = self.call(MSG_SEC_CHECK_PATH, request).await?;
+        Self::verdict(MSG_SEC_CHECK_PATH, response)
+    }
+
+    async fn check_image(&self, image: &[u8], content_type: &str) -> Result<ModerationVerdict, String> {
+        info!(size = image.len(), "Calling WeChat API: imgSecCheck");
+
+        let access_token = self.access_token().await?;
+        // reqwest 未启用 multipart 特性，按 RFC 7578 手工拼装只有一个文件字段的表单
+        let boundary = format!("----RocketTaroModeration{}", Uuid::new_v4().simple());
+        let mut body = format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"media\"; filename=\"media\"\r\nContent-Type: {}\r\n\r\n",
+            boundary, content_type,
+        ).into_bytes();
+        body.extend_from_slice(image);
+        body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+
+        let request = self.http
+            .post(format!("{}{}", self.config.api_base, IMG_SEC_CHECK_PATH))
+            .timeout(Duration::from_secs(self.config.request_timeout_secs))
+            .query(&[("access_token", access_token.as_str())])
+            .header(reqwest::header::CONTENT_TYPE, format!("multipart/form-data; boundary={}", boundary))
+            .body(body);
+        let response = self.call(IMG_SEC_CHECK_PATH, request).await?;
+        Self::verdict(IMG_SEC_CHECK_PATH, response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(json: &str) -> SecCheckResponse {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_sec_check_verdict() {
+        let verdict = WechatModerator::verdict("", response(r#"{"errcode":0,"result":{"suggest":"pass","label":100}}"#));
+        assert_eq!(verdict.unwrap(), ModerationVerdict::pass());
+
+        let verdict = WechatModerator::verdict("", response(r#"{"errcode":0,"result":{"suggest":"risky","label":20003}}"#));
+        assert_eq!(verdict.unwrap(), ModerationVerdict::new(ModerationSuggest::Risky, "辱骂"));
+
+        // 1.0 版接口只返回错误码
+        let verdict = WechatModerator::verdict("", response(r#"{"errcode":87014,"errmsg":"risky content"}"#));
+        assert_eq!(verdict.unwrap().suggest, ModerationSuggest::Risky);
+        assert!(WechatModerator::verdict("", response(r#"{"errcode":40001,"errmsg":"invalid credential"}"#)).is_err());
+    }
+}
@@ -0,0 +1,3 @@
+pub mod wechat_pay;
+
+pub use wechat_pay::{WechatPayClient, WechatPayNotifyHeaders};
\ No newline at end of file
@@ -0,0 +1,315 @@
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use chrono::Utc;
+use openssl::hash::MessageDigest;
+use openssl::pkey::{PKey, Private, Public};
+use openssl::sign::{Signer, Verifier};
+use openssl::symm::{Cipher, decrypt_aead};
+use openssl::x509::X509;
+use rand::{Rng, distributions::Alphanumeric};
+use serde::Deserialize;
+use serde_json::json;
+use tracing::{Span, field::Empty, info, error, instrument, warn};
+
+use crate::config::WechatPayConfig;
+use crate::models::payment::{Payment, RequestPaymentParams, WechatPayNotificationResource};
+use crate::utils::http_client::HttpClient;
+
+/// JSAPI 下单接口路径
+const JSAPI_ORDER_PATH: &str = "/v3/pay/transactions/jsapi";
+/// AES-GCM 认证标签长度
+const GCM_TAG_LENGTH: usize = 16;
+
+/// 回调请求中用于验签的请求头
+#[derive(Debug, Clone)]
+pub struct WechatPayNotifyHeaders {
+    pub timestamp: String,
+    pub nonce: String,
+    pub signature: String,
+    pub serial: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsapiOrderResponse {
+    prepay_id: String,
+}
+
+/// 微信支付 v3 客户端：请求签名、下单、回调验签与解密
+pub struct WechatPayClient {
+    config: WechatPayConfig,
+    merchant_key: Option<PKey<Private>>,
+    platform_key: Option<PKey<Public>>,
+    platform_serial: Option<String>,
+    http: HttpClient,
+}
+
+impl WechatPayClient {
+    /// 根据配置加载商户私钥和平台证书，加载失败时支付功能不可用
+    pub fn from_config(config: WechatPayConfig, http: HttpClient) -> Self {
+        let mut client = Self {
+            config,
+            merchant_key: None,
+            platform_key: None,
+            platform_serial: None,
+            http,
+        };
+
+        if !client.config.enabled {
+            return client;
+        }
+
+        match std::fs::read(&client.config.private_key_path)
+            .map_err(|e| e.to_string())
+            .and_then(|pem| PKey::private_key_from_pem(&pem).map_err(|e| e.to_string()))
+        {
+            Ok(key) => client.merchant_key = Some(key),
+            Err(e) => error!("微信支付商户私钥加载失败 ({}): {}", client.config.private_key_path, e),
+        }
+
+        match std::fs::read(&client.config.platform_certificate_path)
+            .map_err(|e| e.to_string())
+            .and_then(|pem| X509::from_pem(&pem).map_err(|e| e.to_string()))
+        {
+            Ok(cert) => {
+                client.platform_serial = cert.serial_number().to_bn().ok()
+                    .and_then(|serial| serial.to_hex_str().ok())
+                    .map(|serial| serial.to_string());
+                match cert.public_key() {
+                    Ok(key) => client.platform_key = Some(key),
+                    Err(e) => error!("微信支付平台证书公钥读取失败: {}", e),
+                }
+            }
+            Err(e) => error!("微信支付平台证书加载失败 ({}): {}", client.config.platform_certificate_path, e),
+        }
+
+        if client.is_enabled() {
+            info!("微信支付已启用, 商户号: {}", client.config.mch_id);
+        }
+        client
+    }
+
+    /// 配置启用且密钥加载成功
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled && self.merchant_key.is_some() && self.platform_key.is_some()
+    }
+
+    pub fn config(&self) -> &WechatPayConfig {
+        &self.config
+    }
+
+    /// JSAPI 下单，返回 prepay_id
+    #[instrument(
+        level = "debug",
+        skip_all,
+        name = "wechat_pay.jsapi_order",
+        fields(otel.kind = "client", otel.status_code = Empty, http.response.status_code = Empty),
+    )]
+    pub async fn create_jsapi_order(&self, payment: &Payment, openid: &str) -> Result<String, String> {
+        let body = json!({
+            "appid": self.config.app_id,
+            "mchid": self.config.mch_id,
+            "description": payment.description,
+            "out_trade_no": payment.out_trade_no,
+            "notify_url": self.config.notify_url,
+            "amount": { "total": payment.amount, "currency": payment.currency },
+            "payer": { "openid": openid },
+        }).to_string();
+
+        let authorization = self.authorization("POST", JSAPI_ORDER_PATH, &body)?;
+        let request = self.http
+            .post(format!("{}{}", self.config.api_base, JSAPI_ORDER_PATH))
+            .header("Authorization", authorization)
+            .header("Accept", "application/json")
+            .header("Content-Type", "application/json")
+            .body(body);
+        let response = self.http.send(request)
+            .await
+            .map_err(|e| format!("微信支付下单请求失败: {}", e))?;
+
+        let status = response.status();
+        Span::current().record("http.response.status_code", status.as_u16());
+        let text = response.text().await.map_err(|e| format!("读取下单响应失败: {}", e))?;
+        if !status.is_success() {
+            crate::telemetry::record_error(&Span::current());
+            warn!("微信支付下单失败, out_trade_no: {}, status: {}, body: {}", payment.out_trade_no, status, text);
+            return Err(format!("微信支付下单失败: {}", status));
+        }
+
+        let order: JsapiOrderResponse = serde_json::from_str(&text)
+            .map_err(|e| format!("解析下单响应失败: {}", e))?;
+        Ok(order.prepay_id)
+    }
+
+    /// 生成小程序调起支付所需参数
+    pub fn build_request_payment_params(&self, prepay_id: &str) -> Result<RequestPaymentParams, String> {
+        let time_stamp = Utc::now().timestamp().to_string();
+        let nonce_str = generate_nonce();
+        let package = format!("prepay_id={}", prepay_id);
+        let pay_sign = self.sign(&pay_sign_message(&self.config.app_id, &time_stamp, &nonce_str, &package))?;
+
+        Ok(RequestPaymentParams {
+            time_stamp,
+            nonce_str,
+            package,
+            sign_type: "RSA".to_string(),
+            pay_sign,
+        })
+    }
+
+    /// 验证回调签名（平台证书序列号、时间戳、签名）
+    pub fn verify_notification(&self, headers: &WechatPayNotifyHeaders, body: &str) -> Result<(), String> {
+        let platform_key = self.platform_key.as_ref().ok_or("微信支付平台证书未加载")?;
+
+        if let Some(serial) = &self.platform_serial {
+            if !serial.eq_ignore_ascii_case(&headers.serial) {
+                return Err(format!("未知的平台证书序列号: {}", headers.serial));
+            }
+        }
+
+        let timestamp: i64 = headers.timestamp.parse().map_err(|_| "回调时间戳格式错误".to_string())?;
+        if (Utc::now().timestamp() - timestamp).abs() > self.config.notify_tolerance_secs {
+            return Err("回调时间戳已过期".to_string());
+        }
+
+        let message = notify_message(&headers.timestamp, &headers.nonce, body);
+        if verify_signature(platform_key, &message, &headers.signature)? {
+            Ok(())
+        } else {
+            Err("回调签名验证失败".to_string())
+        }
+    }
+
+    /// 解密回调通知中的资源（AEAD_AES_256_GCM）
+    pub fn decrypt_resource(&self, resource: &WechatPayNotificationResource) -> Result<String, String> {
+        if resource.algorithm != "AEAD_AES_256_GCM" {
+            return Err(format!("不支持的加密算法: {}", resource.algorithm));
+        }
+        decrypt_resource(
+            &self.config.api_v3_key,
+            &resource.ciphertext,
+            &resource.nonce,
+            resource.associated_data.as_deref().unwrap_or(""),
+        )
+    }
+
+    /// 生成请求签名 Authorization 头
+    fn authorization(&self, method: &str, url_path: &str, body: &str) -> Result<String, String> {
+        let timestamp = Utc::now().timestamp().to_string();
+        let nonce = generate_nonce();
+        let signature = self.sign(&authorization_message(method, url_path, &timestamp, &nonce, body))?;
+
+        Ok(format!(
+            "WECHATPAY2-SHA256-RSA2048 mchid=\"{}\",nonce_str=\"{}\",signature=\"{}\",timestamp=\"{}\",serial_no=\"{}\"",
+            self.config.mch_id, nonce, signature, timestamp, self.config.merchant_serial_no
+        ))
+    }
+
+    fn sign(&self, message: &str) -> Result<String, String> {
+        let key = self.merchant_key.as_ref().ok_or("微信支付商户私钥未加载")?;
+        sign_message(key, message)
+    }
+}
+
+/// 请求签名串：HTTP方法\nURL\n时间戳\n随机串\n请求体\n
+pub fn authorization_message(method: &str, url_path: &str, timestamp: &str, nonce: &str, body: &str) -> String {
+    format!("{}\n{}\n{}\n{}\n{}\n", method, url_path, timestamp, nonce, body)
+}
+
+/// 调起支付签名串：appId\n时间戳\n随机串\npackage\n
+pub fn pay_sign_message(app_id: &str, time_stamp: &str, nonce_str: &str, package: &str) -> String {
+    format!("{}\n{}\n{}\n{}\n", app_id, time_stamp, nonce_str, package)
+}
+
+/// 回调验签串：时间戳\n随机串\n请求体\n
+pub fn notify_message(timestamp: &str, nonce: &str, body: &str) -> String {
+    format!("{}\n{}\n{}\n", timestamp, nonce, body)
+}
+
+/// SHA256-RSA 签名，返回 Base64
+pub fn sign_message(key: &PKey<Private>, message: &str) -> Result<String, String> {
+    let mut signer = Signer::new(MessageDigest::sha256(), key).map_err(|e| e.to_string())?;
+    signer.update(message.as_bytes()).map_err(|e| e.to_string())?;
+    let signature = signer.sign_to_vec().map_err(|e| e.to_string())?;
+    Ok(BASE64.encode(signature))
+}
+
+/// 验证 SHA256-RSA 签名
+pub fn verify_signature(key: &PKey<Public>, message: &str, signature: &str) -> Result<bool, String> {
+    let signature = BASE64.decode(signature).map_err(|_| "签名格式错误".to_string())?;
+    let mut verifier = Verifier::new(MessageDigest::sha256(), key).map_err(|e| e.to_string())?;
+    verifier.update(message.as_bytes()).map_err(|e| e.to_string())?;
+    verifier.verify(&signature).map_err(|e| e.to_string())
+}
+
+/// AES-256-GCM 解密，密文末尾16字节为认证标签
+pub fn decrypt_resource(api_v3_key: &str, ciphertext: &str, nonce: &str, associated_data: &str) -> Result<String, String> {
+    let data = BASE64.decode(ciphertext).map_err(|_| "密文格式错误".to_string())?;
+    if data.len() < GCM_TAG_LENGTH {
+        return Err("密文长度不足".to_string());
+    }
+    let (encrypted, tag) = data.split_at(data.len() - GCM_TAG_LENGTH);
+
+    let plaintext = decrypt_aead(
+        Cipher::aes_256_gcm(),
+        api_v3_key.as_bytes(),
+        Some(nonce.as_bytes()),
+        associated_data.as_bytes(),
+        encrypted,
+        tag,
+    ).map_err(|e| format!("回调内容解密失败: {}", e))?;
+
+    String::from_utf8(plaintext).map_err(|_| "回调内容不是有效的UTF-8".to_string())
+}
+
+/// 生成32位随机串
+pub fn generate_nonce() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(32)
+        .map(char::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openssl::rsa::Rsa;
+    use openssl::symm::encrypt_aead;
+
+    #[test]
+    fn test_sign_and_verify() {
+        let rsa = Rsa::generate(2048).unwrap();
+        let private_key = PKey::from_rsa(rsa.clone()).unwrap();
+        let public_key = PKey::public_key_from_pem(&rsa.public_key_to_pem().unwrap()).unwrap();
+
+        let message = notify_message("1700000000", "nonce", "{\"id\":\"1\"}");
+        let signature = sign_message(&private_key, &message).unwrap();
+
+        assert!(verify_signature(&public_key, &message, &signature).unwrap());
+        assert!(!verify_signature(&public_key, &notify_message("1700000000", "nonce", "{}"), &signature).unwrap());
+    }
+
+    #[test]
+    fn test_decrypt_resource() {
+        let key = "0123456789abcdef0123456789abcdef";
+        let nonce = "abcdefghijkl";
+        let mut tag = [0u8; GCM_TAG_LENGTH];
+        let mut encrypted = encrypt_aead(
+            Cipher::aes_256_gcm(), key.as_bytes(), Some(nonce.as_bytes()), b"transaction", b"{\"trade_state\":\"SUCCESS\"}", &mut tag,
+        ).unwrap();
+        encrypted.extend_from_slice(&tag);
+
+        let plaintext = decrypt_resource(key, &BASE64.encode(&encrypted), nonce, "transaction").unwrap();
+        assert_eq!(plaintext, "{\"trade_state\":\"SUCCESS\"}");
+        assert!(decrypt_resource(key, &BASE64.encode(&encrypted), nonce, "other").is_err());
+    }
+
+    #[test]
+    fn test_message_formats() {
+        assert_eq!(
+            authorization_message("POST", "/v3/pay/transactions/jsapi", "1", "n", "{}"),
+            "POST\n/v3/pay/transactions/jsapi\n1\nn\n{}\n"
+        );
+        assert_eq!(pay_sign_message("wx1", "1", "n", "prepay_id=p"), "wx1\n1\nn\nprepay_id=p\n");
+        assert_eq!(generate_nonce().len(), 32);
+    }
+}
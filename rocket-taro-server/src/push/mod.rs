@@ -0,0 +1,123 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+use tracing::debug;
+
+use crate::models::route_command::RouteCommand;
+
+/// 每个用户推送通道的缓冲消息数，客户端处理不过来时丢弃最早的消息
+const CHANNEL_CAPACITY: usize = 16;
+
+/// 推送给客户端的消息（SSE 事件名为 event，数据为整个消息）
+#[derive(Debug, Clone, Serialize)]
+pub struct PushMessage {
+    pub event: String,
+    pub route_command: RouteCommand,
+    /// 只推送给该会话的连接，为空时推送给用户的所有连接
+    #[serde(skip)]
+    pub session_id: Option<Uuid>,
+}
+
+impl PushMessage {
+    pub fn new(event: &str, route_command: RouteCommand) -> Self {
+        Self { event: event.to_string(), route_command, session_id: None }
+    }
+
+    /// 只推送给指定会话的消息
+    pub fn for_session(event: &str, session_id: Uuid, route_command: RouteCommand) -> Self {
+        Self { session_id: Some(session_id), ..Self::new(event, route_command) }
+    }
+
+    /// 该会话的连接是否应收到此消息
+    pub fn is_for_session(&self, session_id: Uuid) -> bool {
+        self.session_id.is_none_or(|target| target == session_id)
+    }
+}
+
+/// 按用户分发推送消息，同一用户的多个连接都会收到
+///
+/// 只在当前进程内分发，多实例部署时用户只会收到所连接实例发出的推送。
+/// 扫码登录时网页端尚未登录，以扫码登录记录 ID 作为通道。
+#[derive(Clone, Default)]
+pub struct PushHub {
+    channels: Arc<Mutex<HashMap<Uuid, broadcast::Sender<PushMessage>>>>,
+}
+
+impl PushHub {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 订阅用户的推送消息
+    pub fn subscribe(&self, user_id: Uuid) -> broadcast::Receiver<PushMessage> {
+        let mut channels = self.channels.lock().unwrap_or_else(|e| e.into_inner());
+        channels.entry(user_id)
+            .or_insert_with(|| broadcast::channel(CHANNEL_CAPACITY).0)
+            .subscribe()
+    }
+
+    /// 向用户推送消息，返回收到消息的连接数；用户没有连接时返回 0
+    pub fn send(&self, user_id: Uuid, message: PushMessage) -> usize {
+        let mut channels = self.channels.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(sender) = channels.get(&user_id) else {
+            return 0;
+        };
+
+        match sender.send(message) {
+            Ok(receivers) => receivers,
+            Err(_) => {
+                // 所有连接都已断开，清理通道
+                channels.remove(&user_id);
+                debug!(user_id = %user_id, "Removed push channel without subscribers");
+                0
+            }
+        }
+    }
+
+    /// 是否有连接在订阅该通道
+    pub fn is_connected(&self, user_id: Uuid) -> bool {
+        let channels = self.channels.lock().unwrap_or_else(|e| e.into_inner());
+        channels.get(&user_id).is_some_and(|sender| sender.receiver_count() > 0)
+    }
+
+    /// 当前有连接的用户数
+    pub fn connected_users(&self) -> usize {
+        let channels = self.channels.lock().unwrap_or_else(|e| e.into_inner());
+        channels.values().filter(|sender| sender.receiver_count() > 0).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_send_to_subscribers() {
+        let hub = PushHub::new();
+        let user_id = Uuid::new_v4();
+        assert_eq!(hub.send(user_id, PushMessage::new("test", RouteCommand::toast("hi"))), 0);
+
+        let mut receiver = hub.subscribe(user_id);
+        assert_eq!(hub.send(user_id, PushMessage::new("test", RouteCommand::toast("hi"))), 1);
+        assert_eq!(receiver.recv().await.unwrap().event, "test");
+        assert_eq!(hub.connected_users(), 1);
+        assert!(hub.is_connected(user_id));
+
+        // 连接断开后清理通道
+        drop(receiver);
+        assert_eq!(hub.send(user_id, PushMessage::new("test", RouteCommand::toast("hi"))), 0);
+        assert_eq!(hub.connected_users(), 0);
+        assert!(!hub.is_connected(user_id));
+    }
+
+    #[test]
+    fn test_session_targeted_message() {
+        let session_id = Uuid::new_v4();
+        assert!(PushMessage::new("test", RouteCommand::toast("hi")).is_for_session(session_id));
+        let message = PushMessage::for_session("test", session_id, RouteCommand::toast("hi"));
+        assert!(message.is_for_session(session_id));
+        assert!(!message.is_for_session(Uuid::new_v4()));
+    }
+}
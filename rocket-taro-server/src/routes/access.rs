@@ -0,0 +1,10 @@
+use rocket::{serde::json::Json, get};
+use rocket::http::Status;
+
+use crate::models::response::ApiResponse;
+
+/// 来源 IP 不满足限制的管理接口请求由 IpAccessControl fairing 改写到这里，返回 403
+#[get("/api/access/blocked")]
+pub async fn ip_access_blocked() -> (Status, Json<ApiResponse<()>>) {
+    (Status::Forbidden, Json(ApiResponse::error("当前网络无权访问该接口")))
+}
@@ -0,0 +1,44 @@
+use rocket::{State, serde::json::Json, post};
+use tracing::error;
+
+use crate::models::{
+    account_recovery::PasswordResetRequest,
+    response::ApiResponse,
+    route_command::RouteCommand,
+};
+use crate::database::DbPool;
+use crate::cache::RedisPool;
+use crate::auth::{RequestInfo, PasswordHasher};
+use crate::config::{RouteConfig, Platform};
+use crate::use_cases::{
+    UseCaseError,
+    account_recovery_use_case::AccountRecoveryUseCase,
+    route_command_generator::RouteCommandGenerator,
+};
+
+/// 使用微信找回凭据重置密码（无需登录，凭据即身份），成功后所有会话失效
+#[post("/api/auth/recovery/reset", data = "<request>")]
+pub async fn reset_password(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    password_hasher: &State<PasswordHasher>,
+    route_config: &State<RouteConfig>,
+    request_info: RequestInfo,
+    request: Json<PasswordResetRequest>,
+) -> Json<ApiResponse<()>> {
+    let platform = request_info.user_agent.as_deref()
+        .map(Platform::from_user_agent)
+        .unwrap_or_default();
+    let use_case = AccountRecoveryUseCase::new(pool.inner().clone(), redis.inner().clone(), password_hasher.inner().clone());
+
+    match use_case.reset_password(request.into_inner(), request_info.ip_address).await {
+        Ok(()) => Json(ApiResponse::success_with_command((), RouteCommandGenerator::generate_password_reset_route_command(route_config, platform))),
+        Err(UseCaseError::ValidationError(msg)) | Err(UseCaseError::BusinessLogicError(msg)) => {
+            Json(ApiResponse::error_with_command(&msg, RouteCommand::toast(&msg)))
+        }
+        Err(e) => {
+            error!("重置密码失败: {}", e);
+            Json(ApiResponse::error_with_command("重置密码失败", RouteCommand::toast("重置密码失败")))
+        }
+    }
+}
@@ -0,0 +1,403 @@
+use rocket::{State, serde::json::Json, get, post, put, delete};
+use rocket::http::{ContentType, Status};
+use rocket::response::stream::ByteStream;
+use rocket::futures::StreamExt;
+use std::sync::Arc;
+use tracing::{info, error};
+
+use crate::models::{
+    response::ApiResponse,
+    admin_stats::{AdminStats, IndexUsage, StatsRange},
+    route_command::RouteCommand,
+    route_preview::{RoutePreview, RoutePreviewRequest},
+    route_execution::RouteCommandCompletion,
+    route_decision::RouteDecisionRecord,
+    experiment::ExperimentReport,
+    log_archive::ArchiveReport,
+    presence::PresenceStats,
+    api_quota::{QuotaOverride, SetQuotaOverrideRequest},
+    registration_review::{RegistrationReview, ReviewDecisionRequest},
+    moderation::ModerationCheck,
+    storage::StorageUsage,
+};
+use crate::database::{DbPool, stream_user_data};
+use crate::cache::RedisPool;
+use crate::auth::{RequestInfo, guards::AdminUser};
+use crate::config::{RouteConfig, ExperimentsConfig, LogArchiveConfig, PresenceConfig, ApiQuotasConfig, RegistrationGuardConfig, ModerationConfig, StorageConfig};
+use crate::moderation::ContentModerator;
+use crate::storage::ObjectStorage;
+use crate::metrics::runtime_stats::RuntimeStats;
+use crate::use_cases::{UseCaseError, admin_stats_use_case::AdminStatsUseCase, route_preview_use_case::RoutePreviewUseCase, route_execution_use_case::RouteExecutionUseCase, log_archive_use_case::LogArchiveUseCase, runtime_stats_use_case::RuntimeStatsUseCase, presence_use_case::PresenceUseCase, api_quota_use_case::ApiQuotaUseCase, registration_guard_use_case::RegistrationGuardUseCase, moderation_use_case::ModerationUseCase, storage_use_case::StorageUseCase, experiment_use_case::ExperimentUseCase};
+
+// 管理后台统计数据（日期格式 YYYY-MM-DD，默认最近7天）
+#[get("/api/admin/stats?<from>&<to>")]
+pub async fn get_admin_stats(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    runtime: &State<RuntimeStats>,
+    _admin: AdminUser,
+    from: Option<&str>,
+    to: Option<&str>,
+) -> Json<ApiResponse<AdminStats>> {
+    let range = match StatsRange::parse(from, to, chrono::Utc::now().date_naive()) {
+        Ok(range) => range,
+        Err(msg) => return Json(ApiResponse::error(&msg)),
+    };
+
+    let use_case = AdminStatsUseCase::new(pool.inner().clone(), redis.inner().clone());
+    match use_case.get_stats(range).await {
+        Ok(mut stats) => {
+            stats.runtime = Some(RuntimeStatsUseCase::new(pool.inner().clone(), runtime.inner().clone()).snapshot().await);
+            Json(ApiResponse::success(stats))
+        }
+        Err(e) => {
+            error!("Failed to compute admin stats: {}", e);
+            Json(ApiResponse::error("获取统计数据失败"))
+        }
+    }
+}
+
+// 在线用户数及各平台分布（最近 online_window_secs 秒内有请求的用户），实时统计不缓存
+#[get("/api/admin/presence")]
+pub async fn get_presence(
+    redis: &State<RedisPool>,
+    config: &State<PresenceConfig>,
+    _admin: AdminUser,
+) -> Json<ApiResponse<PresenceStats>> {
+    let use_case = PresenceUseCase::new(redis.inner().clone(), config.inner().clone());
+    match use_case.snapshot(chrono::Utc::now()).await {
+        Ok(stats) => Json(ApiResponse::success(stats)),
+        Err(e) => {
+            error!("Failed to load presence: {}", e);
+            Json(ApiResponse::error("获取在线用户失败"))
+        }
+    }
+}
+
+// 数据库索引使用情况（pg_stat_user_indexes），scans 为 0 的索引未被查询使用
+#[get("/api/admin/db/index-usage")]
+pub async fn get_index_usage(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    _admin: AdminUser,
+) -> Json<ApiResponse<Vec<IndexUsage>>> {
+    let use_case = AdminStatsUseCase::new(pool.inner().clone(), redis.inner().clone());
+    match use_case.index_usage().await {
+        Ok(usage) => Json(ApiResponse::success(usage)),
+        Err(e) => {
+            error!("Failed to load index usage: {}", e);
+            Json(ApiResponse::error("获取索引使用情况失败"))
+        }
+    }
+}
+
+// 按业务流程统计路由指令完成率（日期格式 YYYY-MM-DD，默认最近7天）
+#[get("/api/admin/route-commands/completion?<from>&<to>")]
+pub async fn get_route_command_completion(
+    pool: &State<DbPool>,
+    _admin: AdminUser,
+    from: Option<&str>,
+    to: Option<&str>,
+) -> Json<ApiResponse<RouteCommandCompletion>> {
+    let range = match StatsRange::parse(from, to, chrono::Utc::now().date_naive()) {
+        Ok(range) => range,
+        Err(msg) => return Json(ApiResponse::error(&msg)),
+    };
+
+    let use_case = RouteExecutionUseCase::new(pool.inner().clone());
+    match use_case.completion(range).await {
+        Ok(completion) => Json(ApiResponse::success(completion)),
+        Err(e) => {
+            error!("Failed to compute route command completion: {}", e);
+            Json(ApiResponse::error("获取指令完成率失败"))
+        }
+    }
+}
+
+// 路由指令预览：用构造的 LoginResult/LogoutResult 运行路由决策器，返回生成的指令树
+#[post("/api/admin/route-commands/preview", data = "<request>")]
+pub async fn preview_route_command(
+    route_config: &State<RouteConfig>,
+    experiments: &State<ExperimentsConfig>,
+    admin: AdminUser,
+    request: Json<RoutePreviewRequest>,
+) -> Json<ApiResponse<RoutePreview>> {
+    info!(admin_id = %admin.0.user.id, "Route command preview requested");
+
+    let use_case = RoutePreviewUseCase::new(route_config.inner().clone()).with_experiments(experiments.inner().clone());
+    match use_case.execute_preview(request.into_inner()) {
+        Ok(preview) => Json(ApiResponse::success(preview)),
+        Err(UseCaseError::ValidationError(msg)) => {
+            Json(ApiResponse::error_with_command(&msg, RouteCommand::toast(&msg)))
+        }
+        Err(e) => {
+            error!("Failed to preview route command: {}", e);
+            Json(ApiResponse::error("路由指令预览失败"))
+        }
+    }
+}
+
+// 查询一次路由指令下发时命中的路由决策（需开启 route_trace），执行ID取自响应的 execution_id
+#[get("/api/admin/route-commands/<execution_id>/decisions")]
+pub async fn get_route_decisions(
+    pool: &State<DbPool>,
+    _admin: AdminUser,
+    execution_id: &str,
+) -> Json<ApiResponse<Vec<RouteDecisionRecord>>> {
+    let Ok(execution_id) = uuid::Uuid::parse_str(execution_id) else {
+        return Json(ApiResponse::error("执行ID格式错误"));
+    };
+    let use_case = RouteExecutionUseCase::new(pool.inner().clone());
+    match use_case.decisions(execution_id).await {
+        Ok(decisions) => Json(ApiResponse::success(decisions)),
+        Err(e) => {
+            error!("Failed to get route decisions: {}", e);
+            Json(ApiResponse::error("获取路由决策失败"))
+        }
+    }
+}
+
+// 配置中各 A/B 实验的分组统计：每个分组曝光的用户数和次数
+#[get("/api/admin/experiments")]
+pub async fn get_experiments(
+    pool: &State<DbPool>,
+    experiments: &State<ExperimentsConfig>,
+    _admin: AdminUser,
+) -> Json<ApiResponse<Vec<ExperimentReport>>> {
+    match ExperimentUseCase::new(pool.inner().clone()).with_config(experiments.inner().clone()).report().await {
+        Ok(report) => Json(ApiResponse::success(report)),
+        Err(e) => {
+            error!("Failed to get experiment report: {}", e);
+            Json(ApiResponse::error("获取实验统计失败"))
+        }
+    }
+}
+
+// 以 NDJSON 流导出全部用户提交数据（可按邮箱过滤），每行一个 JSON 对象
+#[get("/api/admin/user-data/export?<email>")]
+pub async fn export_user_data(
+    pool: &State<DbPool>,
+    admin: AdminUser,
+    email: Option<String>,
+) -> Result<(ContentType, ByteStream![Vec<u8>]), Status> {
+    let rows = stream_user_data(pool, email.as_deref()).await.map_err(|e| {
+        error!("Failed to start user data export: {}", e);
+        Status::InternalServerError
+    })?;
+
+    info!(admin_id = %admin.0.user.id, email = ?email, "Streaming user data export");
+
+    let stream = ByteStream! {
+        let mut rows = std::pin::pin!(rows);
+        while let Some(row) = rows.next().await {
+            match row {
+                Ok(data) => yield to_ndjson_line(&data),
+                Err(e) => {
+                    // 中途出错时输出错误行并结束，便于客户端识别数据不完整
+                    error!("User data export interrupted: {}", e);
+                    yield to_ndjson_line(&serde_json::json!({ "error": "导出中断" }));
+                    break;
+                }
+            }
+        }
+    };
+
+    Ok((ContentType::new("application", "x-ndjson"), stream))
+}
+
+fn to_ndjson_line<T: serde::Serialize>(value: &T) -> Vec<u8> {
+    let mut line = serde_json::to_vec(value).unwrap_or_default();
+    line.push(b'\n');
+    line
+}
+
+// 立即执行日志归档（与后台任务相同的策略），返回各表归档结果
+#[post("/api/admin/log-archive/run")]
+pub async fn run_log_archive(
+    pool: &State<DbPool>,
+    config: &State<LogArchiveConfig>,
+    admin: AdminUser,
+) -> Json<ApiResponse<ArchiveReport>> {
+    info!(admin_id = %admin.0.user.id, "Log archive triggered");
+
+    let use_case = LogArchiveUseCase::new(pool.inner().clone(), config.inner().clone());
+    match use_case.run_by_admin(admin.0.user.id).await {
+        Ok(report) => Json(ApiResponse::success(report)),
+        Err(e) => {
+            error!("Failed to archive logs: {}", e);
+            Json(ApiResponse::error("日志归档失败"))
+        }
+    }
+}
+
+// 列出接口配额的用户、角色覆盖
+#[get("/api/admin/quotas/overrides")]
+pub async fn list_quota_overrides(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    config: &State<ApiQuotasConfig>,
+    _admin: AdminUser,
+) -> Json<ApiResponse<Vec<QuotaOverride>>> {
+    let use_case = ApiQuotaUseCase::new(pool.inner().clone(), redis.inner().clone(), config.inner().clone());
+    match use_case.list_overrides().await {
+        Ok(overrides) => Json(ApiResponse::success(overrides)),
+        Err(e) => {
+            error!("Failed to list quota overrides: {}", e);
+            Json(ApiResponse::error("获取配额覆盖失败"))
+        }
+    }
+}
+
+// 设置某个用户或角色在配额分组中的每日上限（0 表示不限制），当天已用次数不清零
+#[put("/api/admin/quotas/overrides", data = "<request>")]
+pub async fn set_quota_override(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    config: &State<ApiQuotasConfig>,
+    admin: AdminUser,
+    request_info: RequestInfo,
+    request: Json<SetQuotaOverrideRequest>,
+) -> Json<ApiResponse<QuotaOverride>> {
+    let use_case = ApiQuotaUseCase::new(pool.inner().clone(), redis.inner().clone(), config.inner().clone());
+    let request = request.into_inner();
+    match use_case.set_override(&request.group_name, &request.subject, request.daily_limit, admin.0.user.id, request_info.ip_address).await {
+        Ok(entry) => Json(ApiResponse::success(entry)),
+        Err(UseCaseError::ValidationError(msg)) => Json(ApiResponse::error(&msg)),
+        Err(e) => {
+            error!("Failed to set quota override: {}", e);
+            Json(ApiResponse::error("配额覆盖保存失败"))
+        }
+    }
+}
+
+// 删除配额覆盖，恢复配置中的上限
+#[delete("/api/admin/quotas/overrides?<group>&<subject>")]
+pub async fn delete_quota_override(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    config: &State<ApiQuotasConfig>,
+    admin: AdminUser,
+    request_info: RequestInfo,
+    group: &str,
+    subject: &str,
+) -> Json<ApiResponse<QuotaOverride>> {
+    let use_case = ApiQuotaUseCase::new(pool.inner().clone(), redis.inner().clone(), config.inner().clone());
+    match use_case.delete_override(group, subject, admin.0.user.id, request_info.ip_address).await {
+        Ok(Some(entry)) => Json(ApiResponse::success(entry)),
+        Ok(None) => Json(ApiResponse::error("配额覆盖不存在")),
+        Err(UseCaseError::ValidationError(msg)) => Json(ApiResponse::error(&msg)),
+        Err(e) => {
+            error!("Failed to delete quota override: {}", e);
+            Json(ApiResponse::error("配额覆盖删除失败"))
+        }
+    }
+}
+
+// 注册审核队列（status 默认 pending，可选 approved、rejected），最新的在前
+#[get("/api/admin/registration-reviews?<status>&<limit>")]
+pub async fn list_registration_reviews(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    config: &State<RegistrationGuardConfig>,
+    _admin: AdminUser,
+    status: Option<&str>,
+    limit: Option<i64>,
+) -> Json<ApiResponse<Vec<RegistrationReview>>> {
+    let use_case = RegistrationGuardUseCase::new(pool.inner().clone(), redis.inner().clone(), config.inner().clone());
+    match use_case.list_reviews(status, limit).await {
+        Ok(reviews) => Json(ApiResponse::success(reviews)),
+        Err(UseCaseError::ValidationError(msg)) => Json(ApiResponse::error(&msg)),
+        Err(e) => {
+            error!("Failed to list registration reviews: {}", e);
+            Json(ApiResponse::error("获取注册审核队列失败"))
+        }
+    }
+}
+
+// 处理注册审核：approved 保留账号，rejected 停用账号并撤销其全部会话
+#[post("/api/admin/registration-reviews/<review_id>/decision", data = "<request>")]
+pub async fn decide_registration_review(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    config: &State<RegistrationGuardConfig>,
+    admin: AdminUser,
+    request_info: RequestInfo,
+    review_id: &str,
+    request: Json<ReviewDecisionRequest>,
+) -> Json<ApiResponse<RegistrationReview>> {
+    let Ok(review_id) = uuid::Uuid::parse_str(review_id) else {
+        return Json(ApiResponse::error("无效的审核记录ID"));
+    };
+    let use_case = RegistrationGuardUseCase::new(pool.inner().clone(), redis.inner().clone(), config.inner().clone());
+    match use_case.decide(review_id, request.into_inner(), admin.0.user.id, request_info.ip_address).await {
+        Ok(review) => Json(ApiResponse::success(review)),
+        Err(UseCaseError::ValidationError(msg)) | Err(UseCaseError::BusinessLogicError(msg)) => Json(ApiResponse::error(&msg)),
+        Err(e) => {
+            error!("Failed to decide registration review: {}", e);
+            Json(ApiResponse::error("处理注册审核失败"))
+        }
+    }
+}
+
+// 内容审核复核队列（status 默认 failed，即需要人工处理的记录；可选 pending、passed、removed），最新的在前
+#[get("/api/admin/moderation-checks?<status>&<limit>")]
+pub async fn list_moderation_checks(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    moderator: &State<Arc<dyn ContentModerator>>,
+    config: &State<ModerationConfig>,
+    _admin: AdminUser,
+    status: Option<&str>,
+    limit: Option<i64>,
+) -> Json<ApiResponse<Vec<ModerationCheck>>> {
+    let use_case = ModerationUseCase::new(pool.inner().clone(), redis.inner().clone(), moderator.inner().clone(), config.inner().clone());
+    match use_case.list_checks(status, limit).await {
+        Ok(checks) => Json(ApiResponse::success(checks)),
+        Err(UseCaseError::ValidationError(msg)) => Json(ApiResponse::error(&msg)),
+        Err(e) => {
+            error!("Failed to list moderation checks: {}", e);
+            Json(ApiResponse::error("获取内容复核队列失败"))
+        }
+    }
+}
+
+// 生成文件占用空间最多的用户，用户已删除的文件合并为 user_id 为空的一项
+#[get("/api/admin/storage/usage?<limit>")]
+pub async fn list_storage_usage(
+    pool: &State<DbPool>,
+    storage: &State<Arc<dyn ObjectStorage>>,
+    config: &State<StorageConfig>,
+    _admin: AdminUser,
+    limit: Option<i64>,
+) -> Json<ApiResponse<Vec<StorageUsage>>> {
+    let use_case = StorageUseCase::new(pool.inner().clone(), storage.inner().clone(), config.inner().clone());
+    match use_case.top_usage(limit).await {
+        Ok(usage) => Json(ApiResponse::success(usage)),
+        Err(e) => {
+            error!("Failed to list storage usage: {}", e);
+            Json(ApiResponse::error("获取存储占用失败"))
+        }
+    }
+}
+
+// 单个用户的生成文件占用空间
+#[get("/api/admin/storage/usage/<user_id>")]
+pub async fn get_user_storage_usage(
+    pool: &State<DbPool>,
+    storage: &State<Arc<dyn ObjectStorage>>,
+    config: &State<StorageConfig>,
+    _admin: AdminUser,
+    user_id: &str,
+) -> Json<ApiResponse<StorageUsage>> {
+    let Ok(user_id) = uuid::Uuid::parse_str(user_id) else {
+        return Json(ApiResponse::error("用户ID格式错误"));
+    };
+    let use_case = StorageUseCase::new(pool.inner().clone(), storage.inner().clone(), config.inner().clone());
+    match use_case.usage(user_id).await {
+        Ok(usage) => Json(ApiResponse::success(usage)),
+        Err(e) => {
+            error!("Failed to get storage usage: {}", e);
+            Json(ApiResponse::error("获取存储占用失败"))
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use rocket::{State, serde::json::Json, get, post, put, delete};
+use tracing::error;
+use uuid::Uuid;
+
+use crate::models::{
+    announcement::{Announcement, AnnouncementRequest},
+    response::{ApiResponse, PagedResponse},
+};
+use crate::database::DbPool;
+use crate::auth::{AuthenticatedUser, guards::AdminUser};
+use crate::use_cases::{UseCaseError, UseCaseResult, announcement_use_case::AnnouncementUseCase};
+
+fn use_case(pool: &State<DbPool>) -> AnnouncementUseCase {
+    AnnouncementUseCase::new(pool.inner().clone())
+}
+
+fn to_response<T>(result: UseCaseResult<T>, failure: &str) -> Json<ApiResponse<T>> {
+    match result {
+        Ok(value) => Json(ApiResponse::success(value)),
+        Err(UseCaseError::ValidationError(msg)) | Err(UseCaseError::BusinessLogicError(msg)) => {
+            Json(ApiResponse::error(&msg))
+        }
+        Err(e) => {
+            error!("{}: {}", failure, e);
+            Json(ApiResponse::error(failure))
+        }
+    }
+}
+
+fn parse_id(id: &str) -> Option<Uuid> {
+    Uuid::parse_str(id).ok()
+}
+
+// 获取全部公告
+#[get("/api/admin/announcements")]
+pub async fn list_announcements(
+    pool: &State<DbPool>,
+    _admin: AdminUser,
+) -> Json<ApiResponse<PagedResponse<Announcement>>> {
+    to_response(use_case(pool).list().await.map(PagedResponse::all), "获取公告列表失败")
+}
+
+// 创建公告
+#[post("/api/admin/announcements", data = "<request>")]
+pub async fn create_announcement(
+    pool: &State<DbPool>,
+    admin: AdminUser,
+    request: Json<AnnouncementRequest>,
+) -> Json<ApiResponse<Announcement>> {
+    to_response(use_case(pool).create(&admin.0.user, request.into_inner()).await, "创建公告失败")
+}
+
+// 更新公告
+#[put("/api/admin/announcements/<announcement_id>", data = "<request>")]
+pub async fn update_announcement(
+    pool: &State<DbPool>,
+    admin: AdminUser,
+    announcement_id: &str,
+    request: Json<AnnouncementRequest>,
+) -> Json<ApiResponse<Announcement>> {
+    let Some(announcement_id) = parse_id(announcement_id) else {
+        return Json(ApiResponse::error("无效的公告ID"));
+    };
+    to_response(
+        use_case(pool).update(&admin.0.user, announcement_id, request.into_inner()).await,
+        "更新公告失败",
+    )
+}
+
+// 删除公告
+#[delete("/api/admin/announcements/<announcement_id>")]
+pub async fn delete_announcement(
+    pool: &State<DbPool>,
+    admin: AdminUser,
+    announcement_id: &str,
+) -> Json<ApiResponse<()>> {
+    let Some(announcement_id) = parse_id(announcement_id) else {
+        return Json(ApiResponse::error("无效的公告ID"));
+    };
+    match use_case(pool).delete(&admin.0.user, announcement_id).await {
+        Ok(()) => Json(ApiResponse::ok()),
+        Err(e) => to_response(Err(e), "删除公告失败"),
+    }
+}
+
+/// 关闭公告，之后登录和认证状态响应中不再下发
+#[post("/api/announcements/<announcement_id>/dismiss")]
+pub async fn dismiss_announcement(
+    pool: &State<DbPool>,
+    auth_user: AuthenticatedUser,
+    announcement_id: &str,
+) -> Json<ApiResponse<()>> {
+    let Some(announcement_id) = parse_id(announcement_id) else {
+        return Json(ApiResponse::error("无效的公告ID"));
+    };
+    match use_case(pool).dismiss(&auth_user.user, announcement_id).await {
+        Ok(()) => Json(ApiResponse::ok()),
+        Err(e) => to_response(Err(e), "关闭公告失败"),
+    }
+}
@@ -1,11 +1,19 @@
 use rocket::serde::json::Json;
-use rocket::State;
+use rocket::{Request, State, request::{self, FromRequest}, http::{Header, Status}};
 use crate::models::response::{ApiResponse, User};
-use crate::database::DbPool;
-use crate::cache::RedisPool;
+use crate::models::remote_config::RemoteConfigSnapshot;
+use crate::database::{DbPool, DbHealth, health::DbHealthSnapshot};
+use crate::cache::{RedisPool, health::CacheHealthSnapshot};
+use crate::auth::RequestInfo;
+use crate::config::Platform;
+use crate::use_cases::remote_config_use_case::RemoteConfigUseCase;
+use crate::use_cases::runtime_stats_use_case::RuntimeStatsUseCase;
+use crate::metrics::runtime_stats::{RuntimeSnapshot, RuntimeStats};
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
 use std::time::Instant;
+use tracing::warn;
 
 #[derive(Serialize, Deserialize)]
 pub struct SystemHealth {
@@ -23,6 +31,7 @@ pub struct ServerStatus {
     pub uptime: String,
     pub host: String,
     pub port: u16,
+    pub runtime: RuntimeSnapshot,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -50,6 +59,7 @@ pub struct CacheStatus {
 pub async fn health_check(
     database: &State<DbPool>,
     redis: &State<RedisPool>,
+    runtime_stats: &State<RuntimeStats>,
 ) -> Json<ApiResponse<SystemHealth>> {
     let now = Utc::now();
     
@@ -110,11 +120,13 @@ pub async fn health_check(
     };
     
     // 服务器状态
+    let runtime = RuntimeStatsUseCase::new(database.inner().clone(), runtime_stats.inner().clone()).snapshot().await;
     let server_status = ServerStatus {
         status: "running".to_string(),
-        uptime: "运行中".to_string(), // 实际项目中可以计算真实运行时间
+        uptime: runtime.uptime.clone(),
         host: "0.0.0.0".to_string(),
         port: 8000,
+        runtime,
     };
     
     // 整体状态判断
@@ -138,6 +150,40 @@ pub async fn health_check(
     Json(ApiResponse::success(health))
 }
 
+#[derive(Serialize)]
+pub struct Readiness {
+    pub ready: bool,
+    /// ok / degraded（Redis 熔断，请求回退到数据库）/ unavailable
+    pub status: String,
+    pub database: DbHealthSnapshot,
+    pub cache: CacheHealthSnapshot,
+}
+
+/// 就绪检查：数据库熔断时返回 503，供负载均衡摘除实例；Redis 熔断时仍可服务，标记为 degraded
+#[get("/readyz")]
+pub fn readiness(db_health: &State<DbHealth>, redis: &State<RedisPool>) -> (Status, Json<ApiResponse<Readiness>>) {
+    let ready = db_health.is_available();
+    let cache_available = redis.is_available();
+    let status = match (ready, cache_available) {
+        (false, _) => "unavailable",
+        (true, false) => "degraded",
+        (true, true) => "ok",
+    };
+    let mut response = ApiResponse::success(Readiness {
+        ready,
+        status: status.to_string(),
+        database: db_health.snapshot(),
+        cache: redis.health().snapshot(),
+    });
+    if !ready {
+        response.code = 503;
+        response.message = "Database unavailable".to_string();
+    } else if !cache_available {
+        response.message = "Cache unavailable, serving from database".to_string();
+    }
+    (if ready { Status::Ok } else { Status::ServiceUnavailable }, Json(response))
+}
+
 #[get("/user", format = "json")]
 pub fn get_user() -> Json<ApiResponse<User>> {
     let user = User {
@@ -172,16 +218,94 @@ pub struct SystemConfig {
     pub platform: String,
     pub environment: String,
     pub timezone: String,
+    /// 客户端平台（决定使用哪组平台覆盖配置）
+    pub client_platform: Platform,
+    /// 当前配置版本，客户端下次请求时通过 since_version 或 If-None-Match 回传
+    pub config_version: i64,
+    /// 是否为增量响应
+    pub is_delta: bool,
+    pub configs: BTreeMap<String, serde_json::Value>,
+    /// 增量响应中自 since_version 以来被删除的键
+    pub removed_keys: Vec<String>,
+}
+
+/// If-None-Match 请求头守卫
+pub struct IfNoneMatch(pub Option<String>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for IfNoneMatch {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let value = req.headers().get_one("If-None-Match").map(|s| s.to_string());
+        request::Outcome::Success(IfNoneMatch(value))
+    }
+}
+
+impl IfNoneMatch {
+    /// 检查请求头中的实体标签是否与当前标签匹配（支持弱校验和多个标签）
+    pub fn matches(&self, etag: &str) -> bool {
+        match &self.0 {
+            Some(header) => header.split(',')
+                .map(|tag| tag.trim().trim_start_matches("W/"))
+                .any(|tag| tag == "*" || tag == etag),
+            None => false,
+        }
+    }
+}
+
+#[derive(Responder)]
+pub enum PublicConfigResponse {
+    #[response(status = 304)]
+    NotModified((), Header<'static>),
+    Config(Box<Json<ApiResponse<SystemConfig>>>, Header<'static>),
 }
 
-#[get("/public/config")]
-pub fn get_public_config() -> Json<ApiResponse<SystemConfig>> {
+#[get("/public/config?<platform>&<since_version>")]
+pub async fn get_public_config(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    request_info: RequestInfo,
+    if_none_match: IfNoneMatch,
+    platform: Option<&str>,
+    since_version: Option<i64>,
+) -> PublicConfigResponse {
+    // 显式指定的平台优先，否则根据 User-Agent 检测
+    let client_platform = platform.and_then(Platform::from_str).unwrap_or_else(|| {
+        Platform::from_user_agent(request_info.user_agent.as_deref().unwrap_or("unknown"))
+    });
+
+    let use_case = RemoteConfigUseCase::new(pool.inner().clone(), redis.inner().clone());
+    let snapshot = match use_case.get_snapshot(client_platform).await {
+        Ok(snapshot) => snapshot,
+        Err(e) => {
+            // 配置服务不可用时降级为仅返回系统信息
+            warn!("Remote config unavailable, serving static config only: {}", e);
+            RemoteConfigSnapshot::resolve(client_platform, &[])
+        }
+    };
+
+    let etag = snapshot.etag();
+    if if_none_match.matches(&etag) || since_version == Some(snapshot.version) {
+        return PublicConfigResponse::NotModified((), Header::new("ETag", etag));
+    }
+
+    let (is_delta, delta) = match since_version {
+        Some(since) if since < snapshot.version => (true, snapshot.delta_since(since)),
+        _ => (false, snapshot.full()),
+    };
+
     let config = SystemConfig {
         server_time: Utc::now(),
         version: env!("CARGO_PKG_VERSION").to_string(),
         platform: "rocket-server".to_string(),
         environment: "development".to_string(),
         timezone: "UTC".to_string(),
+        client_platform,
+        config_version: snapshot.version,
+        is_delta,
+        configs: delta.configs,
+        removed_keys: delta.removed_keys,
     };
-    Json(ApiResponse::success(config))
-}
\ No newline at end of file
+    PublicConfigResponse::Config(Box::new(Json(ApiResponse::success(config))), Header::new("ETag", etag))
+}
@@ -1,259 +1,475 @@
-use rocket::{State, serde::json::Json, post, get, http::{Cookie, CookieJar, SameSite}};
-use rocket::time::{OffsetDateTime, Duration};
+use rocket::{State, serde::json::Json, post, get, patch, delete};
+use rocket::response::Redirect;
+use rocket::http::Status;
+use std::sync::Arc;
 use tracing::{info, warn, error};
+use sha2::{Digest, Sha256};
 
 use crate::models::{
-    response::ApiResponse,
-    auth::{LoginRequest, RegisterRequest, LoginResponse, UserInfo},
+    response::{ApiResponse, PagedResponse},
+    auth::{User, UserSession, LoginRequest, RegisterRequest, LoginResponse, UserInfo, AvailabilityResult, SessionExtension, ActiveSession},
     wx_auth::{WxLoginRequest, WxLoginResponse},
+    data_export::DataExportInfo,
+    profile::ProfileUpdateRequest,
     route_command::RouteCommand,
+    route_decision::RouteTrace,
+    announcement::AnnouncementView,
+    business_results::AccountFlags,
 };
-use crate::database::{
-    DbPool,
-    auth::{authenticate_user, create_user_session, log_login_attempt},
+use crate::database::DbPool;
+use crate::auth::{AuthenticatedUser, OwnerSession, SessionUser, OptionalUser, RequestInfo, IdempotencyKey, DeviceId, IfMatch, PasswordHasher, SessionCookies, ConsentedUser};
+use crate::cache::{RedisPool, user::UserCache, session::SessionCache, idempotency::{IdempotencyStore, IdempotencyOutcome}};
+use crate::events::EventBus;
+use crate::metrics::MetricsRegistry;
+use crate::wechat::{WxApiClient, WxError};
+use crate::moderation::ContentModerator;
+use crate::storage::ObjectStorage;
+use crate::use_cases::{
+    UseCaseError,
+    auth_use_case::AuthUseCase,
+    wx_auth_use_case::{WxAuthUseCase, WxLoginOutcome},
+    session_issuer::{RequestContext, SessionIssuer},
+    login_timeouts::{Dependency, LoginTimeouts},
+    account_use_case::AccountUseCase,
+    data_export_use_case::DataExportUseCase,
+    storage_use_case::StorageUseCase,
+    profile_use_case::ProfileUseCase,
+    moderation_use_case::ModerationUseCase,
+    availability_use_case::AvailabilityUseCase,
+    impersonation_use_case::ImpersonationUseCase,
+    account_flags::AccountFlagPipeline,
+    route_command_generator::RouteCommandGenerator,
+    route_execution_use_case::RouteExecutionUseCase,
+    announcement_use_case::AnnouncementUseCase,
+    session_expiry_use_case::SessionExpiryUseCase,
+    settings_use_case::SettingsUseCase,
+    account_recovery_use_case::AccountRecoveryUseCase,
+    registration_guard_use_case::RegistrationGuardUseCase,
 };
-use crate::auth::{AuthenticatedUser, OptionalUser, RequestInfo};
-use crate::cache::{RedisPool, user::UserCache, session::SessionCache};
-use crate::use_cases::{auth_use_case::AuthUseCase, wx_auth_use_case::WxAuthUseCase};
-use crate::config::{RouteConfig, Platform};
+use crate::config::{RouteConfig, Platform, route_keys, AccountConfig, DataExportConfig, WatermarkConfig, RegistrationGuardConfig, ModerationConfig, StorageConfig, RouteTraceConfig, ExperimentsConfig};
+
+// 获取用户当前应展示的公告，查询失败不影响登录和认证状态响应
+async fn active_announcements(pool: &State<DbPool>, user: &User, flags: &AccountFlags, platform: Platform) -> Vec<AnnouncementView> {
+    match AnnouncementUseCase::new(pool.inner().clone()).active_for(user, flags, platform).await {
+        Ok(announcements) => announcements,
+        Err(e) => {
+            warn!(user_id = %user.id, error = %e, "Failed to load announcements");
+            Vec::new()
+        }
+    }
+}
+
+// 幂等重放保存的响应不含会话令牌：为首次请求签发的会话更换令牌并下发 Cookie，清除旧令牌的会话缓存；
+// 不创建新会话，会话已失效（或记录中没有会话）时返回 None
+async fn resume_replayed_session(
+    issuer: SessionIssuer,
+    redis: &RedisPool,
+    session_id: Option<uuid::Uuid>,
+    user_id: uuid::Uuid,
+    cookies: &SessionCookies<'_>,
+) -> Option<UserSession> {
+    let session_id = session_id?;
+    match issuer.resume(session_id, user_id).await {
+        Ok(Some(session)) => {
+            if let Err(e) = SessionCache::new(redis.clone()).purge_session(session_id).await {
+                warn!(session_id = %session_id, error = %e, "Failed to purge cache for resumed session");
+            }
+            cookies.set(&session.session_token);
+            Some(session)
+        }
+        Ok(None) => None,
+        Err(e) => {
+            error!(user_id = %user_id, "重放登录结果时重新下发会话失败: {}", e);
+            None
+        }
+    }
+}
+
+// 在登录指令前附加用户设置，读取失败时不附加
+async fn with_user_settings(pool: &State<DbPool>, redis: &State<RedisPool>, route_command: RouteCommand, user_id: uuid::Uuid) -> RouteCommand {
+    match SettingsUseCase::new(pool.inner().clone(), redis.inner().clone()).get(user_id).await {
+        Ok(settings) => RouteCommandGenerator::with_settings(route_command, &settings),
+        Err(e) => {
+            warn!(user_id = %user_id, error = %e, "Failed to load user settings for login");
+            route_command
+        }
+    }
+}
+
+// 微信找回密码：账户设置过密码时签发找回凭据并追加重置确认，签发失败时只完成登录
+async fn with_password_recovery(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    password_hasher: &State<PasswordHasher>,
+    route_config: &RouteConfig,
+    platform: Platform,
+    route_command: RouteCommand,
+    user_id: uuid::Uuid,
+) -> RouteCommand {
+    let use_case = AccountRecoveryUseCase::new(pool.inner().clone(), redis.inner().clone(), password_hasher.inner().clone());
+    match use_case.issue(user_id).await {
+        Ok(token) => RouteCommandGenerator::with_password_recovery(route_command, token.as_deref(), route_config, platform),
+        Err(e) => {
+            warn!(user_id = %user_id, error = %e, "Failed to issue password recovery ticket");
+            route_command
+        }
+    }
+}
 
 #[post("/api/auth/login", data = "<login_req>")]
+#[allow(clippy::too_many_arguments)]
 pub async fn login(
     pool: &State<DbPool>,
     redis: &State<RedisPool>,
     route_config: &State<RouteConfig>,
-    cookies: &CookieJar<'_>,
+    experiments: &State<ExperimentsConfig>,
+    events: &State<EventBus>,
+    password_hasher: &State<PasswordHasher>,
+    flag_pipeline: &State<AccountFlagPipeline>,
+    timeouts: &State<LoginTimeouts>,
+    route_trace: &State<RouteTraceConfig>,
+    cookies: SessionCookies<'_>,
     login_req: Json<LoginRequest>,
     request_info: RequestInfo,
 ) -> Json<ApiResponse<LoginResponse>> {
-    let ip_address = request_info.ip_address.unwrap_or_else(|| "0.0.0.0".parse().unwrap());
     let user_agent = request_info.user_agent.unwrap_or_else(|| "unknown".to_string());
     
     let user_cache = UserCache::new(redis.inner().clone());
-    let session_cache = SessionCache::new(redis.inner().clone());
     
-    // 检查账户是否被锁定
-    if let Ok(is_locked) = user_cache.is_account_locked(&login_req.username, 5).await {
-        if is_locked {
-            warn!("Account locked due to too many failed attempts: {}", login_req.username);
-            return Json(ApiResponse::error_with_command(
-                "账户已被锁定，请稍后再试",
-                RouteCommand::alert("账户锁定", "由于多次登录失败，您的账户已被临时锁定，请稍后再试")
-            ));
-        }
+    // 检查账户是否被锁定，Redis 出错或超时时不阻止登录
+    if let Ok(Ok(true)) = timeouts.run(Dependency::Redis, user_cache.is_account_locked(&login_req.username, 5)).await {
+        warn!("Account locked due to too many failed attempts: {}", login_req.username);
+        return Json(ApiResponse::error_with_command(
+            "账户已被锁定，请稍后再试",
+            RouteCommand::alert("账户锁定", "由于多次登录失败，您的账户已被临时锁定，请稍后再试")
+        ));
     }
 
-    // 保存登录请求的副本用于后续处理
-    let login_req_copy = LoginRequest {
-        username: login_req.username.clone(),
-        password: login_req.password.clone(),
-    };
-
     // 从 User-Agent 检测平台
     let platform = Platform::from_user_agent(&user_agent);
     
-    // 使用用例层处理登录逻辑
-    let auth_use_case = AuthUseCase::new(pool.inner().clone(), route_config.inner().clone());
-    let route_command = match auth_use_case.handle_login(login_req.into_inner(), platform).await {
-        Ok(command) => command,
-        Err(e) => {
-            error!("Login use case failed: {}", e);
-            // 记录登录失败
-            let _ = user_cache.record_login_failure(&login_req_copy.username).await;
-            let _ = log_login_attempt(
-                pool,
-                None,
-                &login_req_copy.username,
-                false,
-                Some(ip_address),
-                Some(user_agent.clone()),
-                Some("用例处理失败".to_string()),
-            ).await;
-            
-            RouteCommand::alert("登录失败", "登录过程中发生错误，请稍后重试")
+    // 使用用例层处理登录逻辑，缓存和登录日志由事件订阅者处理
+    let auth_use_case = AuthUseCase::new(pool.inner().clone(), route_config.inner().clone(), events.inner().clone(), password_hasher.inner().clone())
+        .with_flag_pipeline(flag_pipeline.inner().clone())
+        .with_experiments(experiments.inner().clone())
+        .with_timeouts(timeouts.inner().clone());
+    let tracking = RouteExecutionUseCase::new(pool.inner().clone()).with_trace(route_trace.inner().clone());
+    match auth_use_case.execute_login_with_route(login_req.into_inner(), RequestContext::new(request_info.ip_address, Some(user_agent)), platform).await {
+        Ok((login_result, route_command, decisions)) => {
+            cookies.set(&login_result.session.session_token);
+            let user_id = login_result.user.id;
+            let announcements = active_announcements(pool, &login_result.user, &login_result.account_flags, platform).await;
+            let route_command = RouteCommandGenerator::with_announcements(route_command, &announcements);
+            let route_command = with_user_settings(pool, redis, route_command, user_id).await;
+            let response = LoginResponse {
+                user: UserInfo::from(login_result.user),
+                session_token: login_result.session.session_token,
+                expires_at: login_result.session.expires_at,
+            };
+            Json(tracking.track(ApiResponse::success_with_command(response, route_command), "login", platform, Some(user_id), &decisions).await)
         }
-    };
-
-    // 如果是成功的登录，需要处理会话和缓存逻辑
-    // 这里保持向后兼容性，仍然创建会话token和设置cookie
-    if let RouteCommand::Sequence { commands, .. } = &route_command {
-        // 检查是否包含用户数据处理命令，说明登录成功
-        if commands.iter().any(|cmd| matches!(cmd, RouteCommand::ProcessData { data_type, .. } if data_type == "user")) {
-            // 重新验证用户以获取完整用户信息（用于向后兼容）
-            if let Ok(Some(user)) = authenticate_user(pool, &login_req_copy).await {
-                // 创建会话
-                if let Ok(session) = create_user_session(pool, user.id, Some(user_agent.clone()), Some(ip_address)).await {
-                    // 设置会话Cookie
-                    let mut cookie = Cookie::new("session_token", session.session_token.clone());
-                    cookie.set_same_site(SameSite::Lax);
-                    cookie.set_http_only(true);
-                    cookie.set_expires(OffsetDateTime::now_utc() + Duration::hours(8));
-                    cookie.set_path("/");
-                    cookies.add_private(cookie);
-
-                    // 缓存用户信息和会话
-                    let _ = user_cache.cache_user(&user).await;
-                    let _ = user_cache.cache_username_mapping(&user.username, user.id).await;
-                    let _ = session_cache.cache_user_session(&user, &session).await;
-                    let _ = user_cache.clear_login_failures(&login_req_copy.username).await;
-
-                    // 记录成功登录日志
-                    let _ = log_login_attempt(
-                        pool,
-                        Some(user.id),
-                        &login_req_copy.username,
-                        true,
-                        Some(ip_address),
-                        Some(user_agent),
-                        None,
-                    ).await;
-
-                    // 返回传统的登录响应数据以保持兼容性
-                    let response = LoginResponse {
-                        user: UserInfo::from(user),
-                        session_token: session.session_token,
-                        expires_at: session.expires_at,
-                    };
-
-                    return Json(ApiResponse::success_with_command(response, route_command));
-                }
+        Err(e) => {
+            if !matches!(e, UseCaseError::AuthenticationError(_)) {
+                error!("Login use case failed: {}", e);
             }
+            let (route_command, decisions) = RouteCommandGenerator::traced(|trace| {
+                RouteCommandGenerator::generate_login_failed_route_command(&e, route_config, platform, trace)
+            });
+            Json(tracking.track(ApiResponse::command_only(route_command), "login_failed", platform, None, &decisions).await)
         }
     }
-
-    // 如果不是成功登录，记录失败尝试
-    if !matches!(route_command, RouteCommand::Sequence { .. }) {
-        let _ = user_cache.record_login_failure(&login_req_copy.username).await;
-        let _ = log_login_attempt(
-            pool,
-            None,
-            &login_req_copy.username,
-            false,
-            Some(ip_address),
-            Some(user_agent.clone()),
-            Some("认证失败".to_string()),
-        ).await;
-    }
-
-    // 如果不是成功登录，或者处理过程中出错，只返回路由指令
-    Json(ApiResponse::command_only(route_command))
 }
 
 #[post("/api/auth/logout")]
+#[allow(clippy::too_many_arguments)]
 pub async fn logout(
     pool: &State<DbPool>,
     redis: &State<RedisPool>,
     route_config: &State<RouteConfig>,
-    cookies: &CookieJar<'_>,
-    auth_user: AuthenticatedUser,
+    events: &State<EventBus>,
+    password_hasher: &State<PasswordHasher>,
+    route_trace: &State<RouteTraceConfig>,
+    cookies: SessionCookies<'_>,
+    session_user: SessionUser,
     request_info: RequestInfo,
 ) -> Json<ApiResponse<()>> {
+    let SessionUser(auth_user) = session_user;
     info!("User logout: {}", auth_user.user.username);
     
     let user_agent = request_info.user_agent.unwrap_or_else(|| "unknown".to_string());
     let platform = Platform::from_user_agent(&user_agent);
     
-    let auth_use_case = AuthUseCase::new(pool.inner().clone(), route_config.inner().clone());
-    let route_command = match auth_use_case.handle_logout(&auth_user.session.session_token, platform).await {
-        Ok(command) => command,
+    let auth_use_case = AuthUseCase::new(pool.inner().clone(), route_config.inner().clone(), events.inner().clone(), password_hasher.inner().clone())
+        .with_redis(redis.inner().clone());
+    let (route_command, decisions) = match auth_use_case.handle_logout(&auth_user.session.session_token, auth_user.user.id, platform).await {
+        Ok(result) => result,
         Err(e) => {
             warn!("Logout use case failed: {}", e);
             // 即使后端处理失败，也要清理前端状态
-            let login_route = route_config.get_route("auth.login", platform)
+            let login_route = route_config.get_route(route_keys::AUTH_LOGIN, platform)
                 .unwrap_or_else(|| "/pages/login/login".to_string());
-            RouteCommand::sequence(vec![
+            let command = RouteCommand::sequence(vec![
                 RouteCommand::process_data("user", serde_json::json!(null)),
                 RouteCommand::redirect_to(&login_route),
-            ])
+            ]);
+            (command, Vec::new())
         }
     };
     
-    // 清理session缓存和cookie
-    let session_cache = SessionCache::new(redis.inner().clone());
-    let _ = session_cache.invalidate_session(&auth_user.session.session_token).await;
-    cookies.remove_private(Cookie::build(("session_token", "")));
+    // 会话缓存由事件订阅者清理，这里只移除cookie
+    cookies.clear();
     
-    Json(ApiResponse::command_only(route_command))
+    let tracking = RouteExecutionUseCase::new(pool.inner().clone()).with_trace(route_trace.inner().clone());
+    Json(tracking.track(ApiResponse::command_only(route_command), "logout", platform, Some(auth_user.user.id), &decisions).await)
+}
+
+/// 延长当前会话（响应会话过期提醒）
+#[post("/api/auth/extend-session")]
+pub async fn extend_session(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    owner_session: OwnerSession,
+) -> Json<ApiResponse<SessionExtension>> {
+    let OwnerSession(auth_user) = owner_session;
+    let use_case = SessionExpiryUseCase::new(pool.inner().clone(), redis.inner().clone());
+    match use_case.extend(&auth_user.user, &auth_user.session).await {
+        Ok(expires_at) => Json(ApiResponse::success_with_command(
+            SessionExtension { expires_at },
+            RouteCommand::toast("登录已延长"),
+        )),
+        Err(UseCaseError::AuthenticationError(msg)) => Json(ApiResponse::error(&msg)),
+        Err(e) => {
+            error!("Failed to extend session: {}", e);
+            Json(ApiResponse::error("延长登录失败，请稍后重试"))
+        }
+    }
+}
+
+#[delete("/api/auth/account")]
+#[allow(clippy::too_many_arguments)]
+pub async fn delete_account(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    route_config: &State<RouteConfig>,
+    account_config: &State<AccountConfig>,
+    route_trace: &State<RouteTraceConfig>,
+    cookies: SessionCookies<'_>,
+    owner_session: OwnerSession,
+    request_info: RequestInfo,
+) -> Json<ApiResponse<()>> {
+    let OwnerSession(auth_user) = owner_session;
+    info!("Account deletion request: {}", auth_user.user.username);
+
+    let user_agent = request_info.user_agent.unwrap_or_else(|| "unknown".to_string());
+    let platform = Platform::from_user_agent(&user_agent);
+    let grace_period = chrono::Duration::days(account_config.deletion_grace_days);
+
+    let account_use_case = AccountUseCase::new(pool.inner().clone(), redis.inner().clone());
+    match account_use_case.execute_delete_account(&auth_user.user, grace_period, request_info.ip_address).await {
+        Ok(result) => {
+            cookies.clear();
+            let (route_command, decisions) = RouteCommandGenerator::traced(|trace| {
+                RouteCommandGenerator::generate_account_deleted_route_command(&result, route_config, platform, trace)
+            });
+            let tracking = RouteExecutionUseCase::new(pool.inner().clone()).with_trace(route_trace.inner().clone());
+            Json(tracking.track(ApiResponse::command_only(route_command), "account_deleted", platform, Some(auth_user.user.id), &decisions).await)
+        }
+        Err(UseCaseError::BusinessLogicError(msg)) => {
+            Json(ApiResponse::error_with_command(&msg, RouteCommand::alert("注销失败", &msg)))
+        }
+        Err(e) => {
+            error!("Account deletion failed: {}", e);
+            Json(ApiResponse::error_with_command(
+                "账户注销失败",
+                RouteCommand::alert("注销失败", "账户注销过程中发生错误，请稍后重试"),
+            ))
+        }
+    }
+}
+
+/// 请求导出个人数据，或查询最近一次导出的状态
+#[get("/api/auth/my-data")]
+pub async fn request_data_export(
+    pool: &State<DbPool>,
+    export_config: &State<DataExportConfig>,
+    storage: &State<Arc<dyn ObjectStorage>>,
+    storage_config: &State<StorageConfig>,
+    owner_session: OwnerSession,
+    request_info: RequestInfo,
+) -> Json<ApiResponse<DataExportInfo>> {
+    let OwnerSession(auth_user) = owner_session;
+    let storage = StorageUseCase::new(pool.inner().clone(), storage.inner().clone(), storage_config.inner().clone());
+    let use_case = DataExportUseCase::new(pool.inner().clone(), export_config.inner().clone(), storage);
+    match use_case.execute_request_export(&auth_user.user, request_info.ip_address).await {
+        Ok(info) => {
+            let route_command = RouteCommandGenerator::generate_data_export_route_command(&info);
+            Json(ApiResponse::success_with_command(info, route_command))
+        }
+        Err(UseCaseError::BusinessLogicError(msg)) => {
+            Json(ApiResponse::error_with_command(&msg, RouteCommand::alert("无法导出", &msg)))
+        }
+        Err(e) => {
+            error!("Data export request failed: {}", e);
+            Json(ApiResponse::error("数据导出请求失败"))
+        }
+    }
+}
+
+/// 下载已生成的个人数据导出文件：跳转到存储的限时下载地址
+#[get("/api/auth/my-data/<export_id>/download")]
+pub async fn download_data_export(
+    pool: &State<DbPool>,
+    export_config: &State<DataExportConfig>,
+    storage: &State<Arc<dyn ObjectStorage>>,
+    storage_config: &State<StorageConfig>,
+    owner_session: OwnerSession,
+    export_id: &str,
+) -> Result<Redirect, Status> {
+    let OwnerSession(auth_user) = owner_session;
+    let export_id = uuid::Uuid::parse_str(export_id).map_err(|_| Status::NotFound)?;
+
+    let storage = StorageUseCase::new(pool.inner().clone(), storage.inner().clone(), storage_config.inner().clone());
+    let use_case = DataExportUseCase::new(pool.inner().clone(), export_config.inner().clone(), storage);
+    match use_case.execute_open_export(auth_user.user.id, export_id).await {
+        Ok(url) => Ok(Redirect::to(url)),
+        Err(UseCaseError::ValidationError(_)) | Err(UseCaseError::BusinessLogicError(_)) => Err(Status::NotFound),
+        Err(e) => {
+            error!("Data export download failed: {}", e);
+            Err(Status::InternalServerError)
+        }
+    }
 }
 
 #[post("/api/auth/register", data = "<register_req>")]
+#[allow(clippy::too_many_arguments)]
 pub async fn register(
     pool: &State<DbPool>,
     redis: &State<RedisPool>,
     route_config: &State<RouteConfig>,
-    cookies: &CookieJar<'_>,
+    events: &State<EventBus>,
+    password_hasher: &State<PasswordHasher>,
+    registration_guard_config: &State<RegistrationGuardConfig>,
+    metrics: &State<MetricsRegistry>,
+    route_trace: &State<RouteTraceConfig>,
+    cookies: SessionCookies<'_>,
     register_req: Json<RegisterRequest>,
     request_info: RequestInfo,
+    device_id: DeviceId,
+    idempotency_key: IdempotencyKey,
 ) -> Json<ApiResponse<LoginResponse>> {
-    let ip_address = request_info.ip_address.unwrap_or_else(|| "0.0.0.0".parse().unwrap());
+    let register_data = register_req.into_inner();
     let user_agent = request_info.user_agent.unwrap_or_else(|| "unknown".to_string());
+    let context = RequestContext::new(request_info.ip_address, Some(user_agent)).with_device_id(device_id.0);
+
+    // 带 Idempotency-Key 的重复提交返回首次注册的结果，并重新下发首次注册时签发的会话
+    let idempotency = idempotency_key.0.map(|key| {
+        IdempotencyStore::new(redis.inner().clone(), "auth.register", &key, &register_data)
+    });
+    if let Some(store) = &idempotency {
+        match store.begin::<LoginResponse>().await {
+            IdempotencyOutcome::Proceed => {}
+            IdempotencyOutcome::Replay { mut response, session_id } => {
+                if let Some(login) = response.data.as_mut() {
+                    let issuer = SessionIssuer::new(pool.inner().clone(), events.inner().clone());
+                    let Some(session) = resume_replayed_session(issuer, redis, session_id, login.user.id, &cookies).await else {
+                        return Json(ApiResponse::error("登录状态已失效，请重新登录"));
+                    };
+                    login.session_token = session.session_token;
+                    login.expires_at = session.expires_at;
+                }
+                return Json(*response);
+            }
+            outcome => return Json(outcome.into_rejection()),
+        }
+    }
+
+    let registration_guard = RegistrationGuardUseCase::new(pool.inner().clone(), redis.inner().clone(), registration_guard_config.inner().clone())
+        .with_metrics(metrics.inner().clone());
+    let tracking = RouteExecutionUseCase::new(pool.inner().clone()).with_trace(route_trace.inner().clone());
+    let (response, session_id) = process_register(pool, route_config, events, password_hasher, registration_guard, tracking, &cookies, register_data, context).await;
+    if let Some(store) = &idempotency {
+        store.finish_with_session(&response, session_id).await;
+    }
+    Json(response)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn process_register(
+    pool: &State<DbPool>,
+    route_config: &State<RouteConfig>,
+    events: &State<EventBus>,
+    password_hasher: &State<PasswordHasher>,
+    registration_guard: RegistrationGuardUseCase,
+    tracking: RouteExecutionUseCase,
+    cookies: &SessionCookies<'_>,
+    register_data: RegisterRequest,
+    context: RequestContext,
+) -> (ApiResponse<LoginResponse>, Option<uuid::Uuid>) {
+    info!("User registration request: {}", register_data.username);
     
-    info!("User registration request: {}", register_req.username);
-    
-    let platform = Platform::from_user_agent(&user_agent);
-    let register_data = register_req.into_inner();
-    let auth_use_case = AuthUseCase::new(pool.inner().clone(), route_config.inner().clone());
-    let route_command = match auth_use_case.handle_register(register_data.clone(), platform).await {
-        Ok(command) => command,
+    let platform = Platform::from_user_agent(context.user_agent.as_deref().unwrap_or("unknown"));
+    let auth_use_case = AuthUseCase::new(pool.inner().clone(), route_config.inner().clone(), events.inner().clone(), password_hasher.inner().clone())
+        .with_registration_guard(registration_guard);
+    let result = match auth_use_case.execute_register(register_data, context).await {
+        Ok(result) => result,
+        Err(UseCaseError::ValidationError(msg)) => {
+            return (ApiResponse::command_only(RouteCommand::alert("注册失败", &msg)), None);
+        }
         Err(e) => {
             error!("Registration use case failed: {}", e);
-            RouteCommand::alert("注册失败", "注册过程中发生错误，请稍后重试")
+            return (ApiResponse::command_only(RouteCommand::alert("注册失败", "注册过程中发生错误，请稍后重试")), None);
         }
     };
 
-    // 如果注册成功并包含用户数据处理指令，说明是自动登录成功
-    if let RouteCommand::Sequence { commands, .. } = &route_command {
-        if let Some(RouteCommand::ProcessData { data, .. }) = commands.first() {
-            if let Ok(user_info) = serde_json::from_value::<UserInfo>(data.clone()) {
-                
-                // 通过用户名重新获取完整用户信息  
-                let login_for_session = LoginRequest {
-                    username: user_info.username.clone(),
-                    password: register_data.password.clone(),
-                };
-                if let Ok(Some(user)) = authenticate_user(pool, &login_for_session).await {
-                    // 创建会话
-                    if let Ok(session) = create_user_session(pool, user.id, Some(user_agent.clone()), Some(ip_address)).await {
-                        // 设置会话Cookie
-                        let mut cookie = Cookie::new("session_token", session.session_token.clone());
-                        cookie.set_same_site(SameSite::Lax);
-                        cookie.set_http_only(true);
-                        cookie.set_expires(OffsetDateTime::now_utc() + Duration::hours(8));
-                        cookie.set_path("/");
-                        cookies.add_private(cookie);
-
-                        // 缓存用户信息
-                        let user_cache = UserCache::new(redis.inner().clone());
-                        let session_cache = SessionCache::new(redis.inner().clone());
-                        let _ = user_cache.cache_user(&user).await;
-                        let _ = user_cache.cache_username_mapping(&user.username, user.id).await;
-                        let _ = session_cache.cache_user_session(&user, &session).await;
-
-                        // 返回完整的注册响应
-                        let response = LoginResponse {
-                            user: UserInfo::from(user),
-                            session_token: session.session_token,
-                            expires_at: session.expires_at,
-                        };
-
-                        return Json(ApiResponse::success_with_command(response, route_command));
-                    }
-                }
-            }
+    let (route_command, decisions) = RouteCommandGenerator::traced(|trace| {
+        RouteCommandGenerator::generate_register_route_command(&result, route_config, platform, trace)
+    });
+    let user_id = result.user.id;
+    let session_id = result.session.as_ref().map(|session| session.id);
+    let response = match result.session {
+        // 自动登录成功，设置会话Cookie并返回完整的注册响应
+        Some(session) => {
+            cookies.set(&session.session_token);
+            let response = LoginResponse {
+                user: UserInfo::from(result.user),
+                session_token: session.session_token,
+                expires_at: session.expires_at,
+            };
+            ApiResponse::success_with_command(response, route_command)
         }
-    }
+        None => ApiResponse::command_only(route_command),
+    };
+    (tracking.track(response, "register", platform, Some(user_id), &decisions).await, session_id)
+}
 
-    // 如果不是成功注册，只返回路由指令
-    Json(ApiResponse::command_only(route_command))
+/// 注册表单输入时检查用户名/邮箱是否可用，前端应做防抖；结果仅供提示，提交注册时仍会校验
+#[get("/api/auth/check-availability?<username>&<email>")]
+pub async fn check_availability(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    username: Option<&str>,
+    email: Option<&str>,
+) -> Json<ApiResponse<AvailabilityResult>> {
+    let use_case = AvailabilityUseCase::new(pool.inner().clone(), redis.inner().clone());
+    match use_case.execute_check(username, email).await {
+        Ok(result) => Json(ApiResponse::success(result)),
+        Err(UseCaseError::ValidationError(msg)) => Json(ApiResponse::error(&msg)),
+        Err(e) => {
+            error!("Availability check failed: {}", e);
+            Json(ApiResponse::error("检查失败，请稍后重试"))
+        }
+    }
 }
 
 #[get("/api/auth/current")]
 pub async fn get_current_user(
     pool: &State<DbPool>,
     route_config: &State<RouteConfig>,
+    events: &State<EventBus>,
+    password_hasher: &State<PasswordHasher>,
     auth_user: AuthenticatedUser
 ) -> Json<ApiResponse<UserInfo>> {
-    let auth_use_case = AuthUseCase::new(pool.inner().clone(), route_config.inner().clone());
+    let auth_use_case = AuthUseCase::new(pool.inner().clone(), route_config.inner().clone(), events.inner().clone(), password_hasher.inner().clone());
     let route_command = match auth_use_case.get_current_user(auth_user.user).await {
         Ok(command) => command,
         Err(e) => {
@@ -275,95 +491,107 @@ pub async fn get_current_user(
     }
 }
 
+/// 当前用户的有效会话（不含管理员模拟登录会话），按创建时间排序
+#[get("/api/auth/sessions")]
+pub async fn list_sessions(
+    pool: &State<DbPool>,
+    auth_user: AuthenticatedUser,
+) -> Json<ApiResponse<PagedResponse<ActiveSession>>> {
+    use crate::database::auth::list_active_sessions;
+
+    match list_active_sessions(pool, auth_user.user.id).await {
+        Ok(sessions) => Json(ApiResponse::success(PagedResponse::all(sessions))),
+        Err(e) => {
+            error!("Failed to list sessions: {}", e);
+            Json(ApiResponse::error("获取会话列表失败"))
+        }
+    }
+}
+
 #[post("/api/auth/guest-login")]
+#[allow(clippy::too_many_arguments)]
 pub async fn guest_login(
     pool: &State<DbPool>,
     redis: &State<RedisPool>,
     route_config: &State<RouteConfig>,
-    cookies: &CookieJar<'_>,
+    events: &State<EventBus>,
+    password_hasher: &State<PasswordHasher>,
+    flag_pipeline: &State<AccountFlagPipeline>,
+    route_trace: &State<RouteTraceConfig>,
+    cookies: SessionCookies<'_>,
     request_info: RequestInfo,
 ) -> Json<ApiResponse<LoginResponse>> {
-    let ip_address = request_info.ip_address.unwrap_or_else(|| "0.0.0.0".parse().unwrap());
     let user_agent = request_info.user_agent.unwrap_or_else(|| "unknown".to_string());
     
-    info!("Guest login request from IP: {}", ip_address);
+    info!("Guest login request from IP: {:?}", request_info.ip_address);
     
     let platform = Platform::from_user_agent(&user_agent);
-    let auth_use_case = AuthUseCase::new(pool.inner().clone(), route_config.inner().clone());
+    let auth_use_case = AuthUseCase::new(pool.inner().clone(), route_config.inner().clone(), events.inner().clone(), password_hasher.inner().clone())
+        .with_flag_pipeline(flag_pipeline.inner().clone());
     
-    let route_command = match auth_use_case.handle_guest_login(platform).await {
-        Ok(command) => command,
+    match auth_use_case.execute_guest_login(RequestContext::new(request_info.ip_address, Some(user_agent))).await {
+        Ok(login_result) => {
+            cookies.set(&login_result.session.session_token);
+            let (route_command, decisions) = RouteCommandGenerator::traced(|trace| {
+                RouteCommandGenerator::generate_guest_login_route_command(&login_result, route_config, platform, trace)
+            });
+            let user_id = login_result.user.id;
+            let route_command = with_user_settings(pool, redis, route_command, user_id).await;
+            let response = LoginResponse {
+                user: UserInfo::from(login_result.user),
+                session_token: login_result.session.session_token,
+                expires_at: login_result.session.expires_at,
+            };
+            let tracking = RouteExecutionUseCase::new(pool.inner().clone()).with_trace(route_trace.inner().clone());
+            Json(tracking.track(ApiResponse::success_with_command(response, route_command), "guest_login", platform, Some(user_id), &decisions).await)
+        }
         Err(e) => {
             error!("Guest login use case failed: {}", e);
-            RouteCommand::alert("游客登录失败", "游客登录过程中发生错误，请稍后重试")
-        }
-    };
-
-    // 如果是成功的游客登录，创建会话token和设置cookie
-    if let RouteCommand::Sequence { commands, .. } = &route_command {
-        if let Some(RouteCommand::ProcessData { data, .. }) = commands.first() {
-            if let Ok(user_info) = serde_json::from_value::<UserInfo>(data.clone()) {
-                // 由于游客用户无密码，我们直接通过用户名查找用户
-                if let Ok(Some(user)) = crate::database::auth::authenticate_guest_user(pool, &user_info.username).await {
-                    if let Ok(session) = create_user_session(pool, user.id, Some(user_agent.clone()), Some(ip_address)).await {
-                        // 设置会话Cookie
-                        let mut cookie = Cookie::new("session_token", session.session_token.clone());
-                        cookie.set_same_site(SameSite::Lax);
-                        cookie.set_http_only(true);
-                        cookie.set_expires(OffsetDateTime::now_utc() + Duration::hours(8));
-                        cookie.set_path("/");
-                        cookies.add_private(cookie);
-
-                        // 缓存游客用户信息
-                        let user_cache = UserCache::new(redis.inner().clone());
-                        let session_cache = SessionCache::new(redis.inner().clone());
-                        let _ = user_cache.cache_user(&user).await;
-                        let _ = user_cache.cache_username_mapping(&user.username, user.id).await;
-                        let _ = session_cache.cache_user_session(&user, &session).await;
-
-                        // 记录游客登录日志
-                        let _ = log_login_attempt(
-                            pool,
-                            Some(user.id),
-                            &user.username,
-                            true,
-                            Some(ip_address),
-                            Some(user_agent),
-                            Some("游客登录".to_string()),
-                        ).await;
-
-                        let response = LoginResponse {
-                            user: UserInfo::from(user),
-                            session_token: session.session_token,
-                            expires_at: session.expires_at,
-                        };
-
-                        return Json(ApiResponse::success_with_command(response, route_command));
-                    }
-                }
-            }
+            Json(ApiResponse::command_only(RouteCommand::alert("游客登录失败", "游客登录过程中发生错误，请稍后重试")))
         }
     }
-
-    Json(ApiResponse::command_only(route_command))
 }
 
 #[get("/api/auth/status")]
 pub async fn auth_status(
+    pool: &State<DbPool>,
     route_config: &State<RouteConfig>,
+    flag_pipeline: &State<AccountFlagPipeline>,
     optional_user: OptionalUser,
     request_info: RequestInfo
 ) -> Json<ApiResponse<Option<UserInfo>>> {
     match optional_user.0 {
         Some(auth_user) => {
+            // 已登录用户附带当前有效的公告
+            let platform = request_info.user_agent.as_deref().map(Platform::from_user_agent).unwrap_or_default();
+            let flags = flag_pipeline.build(&auth_user.user).await;
+            let announcements = active_announcements(pool, &auth_user.user, &flags, platform).await;
+            // 模拟登录会话每次检查状态时重新下发提示条
+            let banner = ImpersonationUseCase::banner_for(&auth_user);
+            // 有未同意的生效协议时跳转协议同意页，模拟登录的管理员不能代替用户同意
+            let consent = (flags.needs_policy_consent && auth_user.session.impersonator_id.is_none()).then(|| {
+                RouteCommandGenerator::generate_consent_required_route_command(&flags.pending_policies, None, route_config, platform, &mut RouteTrace::default())
+            });
             let user_info = UserInfo::from(auth_user.user);
-            Json(ApiResponse::success(Some(user_info)))
+            let mut commands: Vec<RouteCommand> = [banner, RouteCommandGenerator::generate_announcements_route_command(&announcements), consent]
+                .into_iter()
+                .flatten()
+                .collect();
+            let route_command = match commands.len() {
+                0 => None,
+                1 => commands.pop(),
+                _ => Some(RouteCommand::sequence(commands)),
+            };
+            match route_command {
+                Some(route_command) => Json(ApiResponse::success_with_command(Some(user_info), route_command)),
+                None => Json(ApiResponse::success(Some(user_info))),
+            }
         }
         None => {
             // 未登录用户，返回跳转登录页的路由指令
             let user_agent = request_info.user_agent.unwrap_or_else(|| "unknown".to_string());
             let platform = Platform::from_user_agent(&user_agent);
-            let login_route = route_config.get_route("auth.login", platform)
+            let login_route = route_config.get_route(route_keys::AUTH_LOGIN, platform)
                 .unwrap_or_else(|| "/pages/login/login".to_string());
             let route_command = RouteCommand::navigate_to(&login_route);
             Json(ApiResponse::error_with_command("未登录", route_command))
@@ -372,78 +600,115 @@ pub async fn auth_status(
 }
 
 #[post("/api/auth/wx-login", data = "<wx_login_req>")]
+#[allow(clippy::too_many_arguments)]
 pub async fn wx_login(
     pool: &State<DbPool>,
     redis: &State<RedisPool>,
     route_config: &State<RouteConfig>,
-    cookies: &CookieJar<'_>,
+    experiments: &State<ExperimentsConfig>,
+    events: &State<EventBus>,
+    wx_api: &State<Arc<dyn WxApiClient>>,
+    password_hasher: &State<PasswordHasher>,
+    watermark: &State<WatermarkConfig>,
+    metrics: &State<MetricsRegistry>,
+    timeouts: &State<LoginTimeouts>,
+    route_trace: &State<RouteTraceConfig>,
+    cookies: SessionCookies<'_>,
     wx_login_req: Json<WxLoginRequest>,
     request_info: RequestInfo,
 ) -> Json<ApiResponse<WxLoginResponse>> {
-    let user_agent = request_info.user_agent.unwrap_or_else(|| "WeChat Mini Program".to_string());
-    let ip_address = request_info.ip_address.unwrap_or_else(|| "0.0.0.0".parse().unwrap());
-    
     info!("收到微信登录请求");
+
+    // 同一个 code 只能换取一次会话，客户端重试时返回首次登录的结果并重新下发同一个会话；失败的登录不保留记录
+    let code_hash = hex::encode(Sha256::digest(wx_login_req.code.as_bytes()));
+    let replay_guard = IdempotencyStore::new(redis.inner().clone(), "auth.wx_login", &code_hash, &*wx_login_req)
+        .with_ttl(redis.ttl().wx_login_code);
+    // Redis 超时时与不可用相同，不做检查
+    let begin = timeouts.run(Dependency::Redis, replay_guard.begin::<WxLoginResponse>()).await;
+    match begin.unwrap_or(IdempotencyOutcome::Proceed) {
+        IdempotencyOutcome::Proceed => {}
+        IdempotencyOutcome::Replay { mut response, session_id } => {
+            metrics.increment("wx_login.code_replays");
+            info!("微信登录 code 重复提交，返回首次登录结果");
+            if let Some(login) = response.data.as_mut() {
+                let issuer = SessionIssuer::new(pool.inner().clone(), events.inner().clone()).with_timeouts(timeouts.inner().clone());
+                let Some(session) = resume_replayed_session(issuer, redis, session_id, login.user.id, &cookies).await else {
+                    return Json(ApiResponse::error("登录状态已失效，请重新登录"));
+                };
+                login.session_token = session.session_token;
+                login.expires_at = session.expires_at;
+            }
+            return Json(*response);
+        }
+        outcome => {
+            metrics.increment("wx_login.code_conflicts");
+            warn!("微信登录 code 重复提交，首次请求仍在处理或请求内容不同");
+            return Json(outcome.into_rejection());
+        }
+    }
+
+    let tracking = RouteExecutionUseCase::new(pool.inner().clone()).with_trace(route_trace.inner().clone());
+    let (response, session_id) = process_wx_login(pool, redis, route_config, experiments, events, wx_api, password_hasher, watermark, timeouts, tracking, &cookies, wx_login_req.into_inner(), request_info).await;
+    if timeouts.run(Dependency::Redis, replay_guard.finish_with_session(&response, session_id)).await.is_err() {
+        warn!("微信登录结果未能在时间预算内保存，重复提交的 code 将无法重放");
+    }
+    Json(response)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn process_wx_login(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    route_config: &State<RouteConfig>,
+    experiments: &State<ExperimentsConfig>,
+    events: &State<EventBus>,
+    wx_api: &State<Arc<dyn WxApiClient>>,
+    password_hasher: &State<PasswordHasher>,
+    watermark: &State<WatermarkConfig>,
+    timeouts: &State<LoginTimeouts>,
+    tracking: RouteExecutionUseCase,
+    cookies: &SessionCookies<'_>,
+    wx_login_req: WxLoginRequest,
+    request_info: RequestInfo,
+) -> (ApiResponse<WxLoginResponse>, Option<uuid::Uuid>) {
+    let user_agent = request_info.user_agent.unwrap_or_else(|| "WeChat Mini Program".to_string());
     
     // 从User-Agent检测平台
     let platform = Platform::from_user_agent(&user_agent);
     
+    let recover_password = wx_login_req.recover_password;
+
     // 使用微信登录用例处理业务逻辑
-    let wx_auth_use_case = WxAuthUseCase::new(pool.inner().clone(), std::sync::Arc::new(route_config.inner().clone()));
-    let route_command = match wx_auth_use_case.handle_wx_login(wx_login_req.into_inner(), platform).await {
-        Ok(command) => command,
+    let wx_auth_use_case = WxAuthUseCase::new(pool.inner().clone(), Arc::new(route_config.inner().clone()), events.inner().clone(), wx_api.inner().clone())
+        .with_watermark(watermark.inner().clone())
+        .with_experiments(experiments.inner().clone())
+        .with_timeouts(timeouts.inner().clone());
+    let outcome = match wx_auth_use_case.handle_wx_login(wx_login_req, platform, RequestContext::new(request_info.ip_address, Some(user_agent))).await {
+        Ok(outcome) => outcome,
         Err(e) => {
-            error!("微信登录用例处理失败: {}", e);
-            RouteCommand::alert("登录失败", "微信登录过程中发生错误，请稍后重试")
+            error!(kind = e.kind(), "微信登录用例处理失败: {}", e);
+            WxLoginOutcome::failed(RouteCommand::alert("登录失败", "微信登录过程中发生错误，请稍后重试"))
         }
     };
 
-    // 如果是成功的登录，需要设置Cookie（向后兼容）
-    if let RouteCommand::Sequence { commands, .. } = &route_command {
-        if let Some(RouteCommand::ProcessData { data_type, data, .. }) = commands.first() {
-            if data_type == "user" {
-                if let Ok(wx_response) = serde_json::from_value::<WxLoginResponse>(data.clone()) {
-                    // 设置会话Cookie
-                    let mut cookie = Cookie::new("session_token", wx_response.session_token.clone());
-                    cookie.set_same_site(SameSite::Lax);
-                    cookie.set_http_only(true);
-                    cookie.set_expires(OffsetDateTime::now_utc() + Duration::hours(8));
-                    cookie.set_path("/");
-                    cookies.add_private(cookie);
-
-                    // 缓存用户信息
-                    let user_cache = UserCache::new(redis.inner().clone());
-                    let session_cache = SessionCache::new(redis.inner().clone());
-                    
-                    // 注意：这里需要构建完整的User对象用于缓存
-                    // 由于我们已经有了UserInfo，但缓存需要完整的User，这里先跳过缓存
-                    // 在生产环境中，应该重新查询完整的用户信息进行缓存
-                    
-                    info!("微信用户登录成功，已设置会话");
-                }
-            }
-        }
-    }
-
-    // 构建响应（注意：实际数据会通过RouteCommand传递）
-    let default_response = WxLoginResponse {
-        user: UserInfo {
-            id: uuid::Uuid::new_v4(),
-            username: "wx_user".to_string(),
-            email: "temp@wx.temp".to_string(),
-            full_name: None,
-            avatar_url: None,
-            is_admin: false,
-            is_guest: true,
-            wx_openid: None,
-            has_wx_session: false,
-            display_name: "wx_user".to_string(),
-        },
-        session_token: "".to_string(),
-        expires_at: chrono::Utc::now(),
+    // 登录成功时设置会话Cookie（向后兼容），用户和会话缓存由 SessionIssuer 写入的副作用发件箱处理
+    let Some(login) = outcome.login else {
+        return (tracking.track(ApiResponse::command_only(outcome.route_command), "wx_login_failed", platform, None, &outcome.decisions).await, None);
+    };
+    cookies.set(&login.session.session_token);
+    info!("微信用户登录成功，已设置会话");
+
+    let user_id = login.user.id;
+    let route_command = if recover_password {
+        let route_command = with_user_settings(pool, redis, outcome.route_command, user_id).await;
+        with_password_recovery(pool, redis, password_hasher, route_config, platform, route_command, user_id).await
+    } else {
+        with_user_settings(pool, redis, outcome.route_command, user_id).await
     };
 
-    Json(ApiResponse::success_with_command(default_response, route_command))
+    // 记录指令下发，新用户、首次登录按实验分组的欢迎提示同时记录曝光
+    let response = tracking.track(ApiResponse::success_with_command(WxLoginResponse::from(&login), route_command), "wx_login", platform, Some(user_id), &outcome.decisions).await;
+    (response, Some(login.session.id))
 }
 
 #[derive(serde::Deserialize, Debug)]
@@ -460,16 +725,13 @@ pub async fn update_user_profile(
     redis: &State<RedisPool>,
     profile_req: Json<UpdateProfileRequest>,
     auth_user: AuthenticatedUser,
-) -> Json<ApiResponse<UserInfo>> {
+) -> (Status, Json<ApiResponse<UserInfo>>) {
     info!("收到用户信息更新请求: {}", auth_user.user.username);
     
-    // 检查是否为微信用户（需要有有效的wx_session_key）
-    if auth_user.user.wx_session_key.is_none() {
-        return Json(ApiResponse::error("当前用户不是微信用户或会话已过期，请使用微信重新登录"));
-    }
-    
-    // wx_session_key只在服务端使用，不能返回给客户端
-    let session_key = auth_user.user.wx_session_key.as_ref().unwrap();
+    // 检查是否为微信用户（需要有有效的wx_session_key），wx_session_key只在服务端使用，不能返回给客户端
+    let Some(session_key) = auth_user.user.wx_session_key.as_ref() else {
+        return (Status::Unauthorized, Json(ApiResponse::error("当前用户不是微信用户或会话已过期，请使用微信重新登录")));
+    };
     
     // 处理用户资料更新
     match process_user_profile_update(pool, &auth_user.user, &profile_req, session_key).await {
@@ -482,11 +744,55 @@ pub async fn update_user_profile(
             let _ = user_cache.invalidate_user(auth_user.user.id).await;
             let _ = session_cache.invalidate_user_sessions(auth_user.user.id).await;
             
-            Json(ApiResponse::success(updated_user_info))
+            (Status::Ok, Json(ApiResponse::success(updated_user_info)))
         },
         Err(e) => {
-            error!("用户信息更新失败: {}", e);
-            Json(ApiResponse::error("用户信息更新失败"))
+            error!(kind = e.kind(), "用户信息更新失败: {}", e);
+            let message = match e {
+                WxError::SessionExpired => "微信会话已过期，请使用微信重新登录",
+                _ => "用户信息更新失败",
+            };
+            (e.status(), Json(ApiResponse::error(message)))
+        }
+    }
+}
+
+/// 非微信用户（密码登录、H5、管理员）直接修改资料，修改邮箱使用 POST /api/auth/email-change；
+/// 需提交读取资料时的版本号（If-Match 或 version），不一致时返回 409
+#[patch("/api/auth/profile", data = "<profile_req>")]
+pub async fn patch_user_profile(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    moderator: &State<Arc<dyn ContentModerator>>,
+    moderation_config: &State<ModerationConfig>,
+    profile_req: Json<ProfileUpdateRequest>,
+    consented: ConsentedUser,
+    if_match: IfMatch,
+) -> (Status, Json<ApiResponse<UserInfo>>) {
+    let ConsentedUser(auth_user) = consented;
+    let moderation = ModerationUseCase::new(pool.inner().clone(), redis.inner().clone(), moderator.inner().clone(), moderation_config.inner().clone());
+    let use_case = ProfileUseCase::new(pool.inner().clone(), redis.inner().clone()).with_moderation(moderation);
+    match use_case.execute_update_profile(&auth_user.user, profile_req.into_inner(), if_match.0).await {
+        Ok(result) => {
+            let route_command = RouteCommandGenerator::generate_profile_update_route_command(&result);
+            (Status::Ok, Json(ApiResponse::success_with_command(UserInfo::from(result.user), route_command)))
+        }
+        Err(UseCaseError::ConflictError(msg)) => {
+            let command = RouteCommandGenerator::generate_version_conflict_route_command("profile", None);
+            (Status::Conflict, Json(ApiResponse::error_with_command(&msg, command)))
+        }
+        Err(UseCaseError::ContentRejected(rejection)) => {
+            let command = RouteCommandGenerator::generate_content_rejected_route_command(&rejection);
+            (Status::Ok, Json(ApiResponse::error_with_command(&rejection.message(), command)))
+        }
+        Err(UseCaseError::ValidationError(msg))
+        | Err(UseCaseError::BusinessLogicError(msg))
+        | Err(UseCaseError::AuthenticationError(msg)) => {
+            (Status::Ok, Json(ApiResponse::error_with_command(&msg, RouteCommand::toast(&msg))))
+        }
+        Err(e) => {
+            error!("Profile update failed: {}", e);
+            (Status::Ok, Json(ApiResponse::error_with_command("资料修改失败", RouteCommand::toast("资料修改失败，请稍后重试"))))
         }
     }
 }
@@ -497,31 +803,30 @@ async fn process_user_profile_update(
     user: &crate::models::auth::User,
     profile_req: &UpdateProfileRequest,
     session_key: &str,
-) -> Result<UserInfo, String> {
+) -> Result<UserInfo, WxError> {
     use crate::utils::wx_crypto::WxCrypto;
     use crate::database::wx_auth::update_wx_user_profile;
     
     // 验证必要的数据
-    let encrypted_data = profile_req.encrypted_data.as_ref().ok_or("缺少加密数据")?;
-    let iv = profile_req.iv.as_ref().ok_or("缺少初始向量")?;
-    let signature = profile_req.signature.as_ref().ok_or("缺少签名")?;
-    let raw_data = profile_req.raw_data.as_ref().ok_or("缺少原始数据")?;
+    let encrypted_data = profile_req.encrypted_data.as_ref().ok_or(WxError::MissingField("encrypted_data"))?;
+    let iv = profile_req.iv.as_ref().ok_or(WxError::MissingField("iv"))?;
+    let signature = profile_req.signature.as_ref().ok_or(WxError::MissingField("signature"))?;
+    let raw_data = profile_req.raw_data.as_ref().ok_or(WxError::MissingField("raw_data"))?;
     
     // 1. 验证数据签名
-    if !WxCrypto::verify_signature(raw_data, session_key, signature)? {
-        return Err("数据签名验证失败".to_string());
-    }
+    WxCrypto::verify_signature(raw_data, session_key, signature)?;
     
     // 2. 解密用户Profile数据（使用专门的方法处理wx.getUserProfile数据）
     let profile_info = WxCrypto::decrypt_user_profile(encrypted_data, session_key, iv)?;
     
     // 3. 更新用户信息到数据库（只更新昵称和头像）
-    update_wx_user_profile(
+    let version = update_wx_user_profile(
         pool,
         user.id,
         &profile_info.nick_name,
         &profile_info.avatar_url,
-    ).await.map_err(|e| format!("更新数据库失败: {}", e))?;
+    ).await?
+        .ok_or(WxError::UserNotFound)?;
     
     // 4. 返回更新后的用户信息
     let display_name = profile_info.nick_name.clone();
@@ -536,6 +841,7 @@ async fn process_user_profile_update(
         wx_openid: user.wx_openid.clone(),
         has_wx_session: user.wx_session_key.is_some(),
         display_name,
+        version,
     })
 }
 
@@ -0,0 +1,115 @@
+use rocket::{State, serde::json::Json, get, post, FromForm, Responder};
+use rocket::form::{Form, Errors};
+use rocket::fs::TempFile;
+use rocket::http::{ContentType, Header, Status};
+use tracing::{warn, error};
+
+use crate::models::{
+    response::{ApiResponse, FieldError},
+    avatar::AvatarView,
+    route_command::RouteCommand,
+};
+use crate::database::DbPool;
+use crate::cache::RedisPool;
+use crate::auth::{AuthenticatedUser, ConsentedUser};
+use crate::config::AvatarConfig;
+use crate::use_cases::{UseCaseError, avatar_use_case::AvatarUseCase};
+
+/// multipart 头像上传表单，文件字段名为 file
+#[derive(FromForm)]
+pub struct AvatarForm<'r> {
+    file: TempFile<'r>,
+}
+
+/// 上传头像，处理完成后自动设为用户头像（通过推送事件 avatar 或 GET /api/uploads/avatar/<id> 获取结果）
+#[post("/api/uploads/avatar", data = "<form>")]
+pub async fn upload_avatar(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    config: &State<AvatarConfig>,
+    consented: ConsentedUser,
+    form: Result<Form<AvatarForm<'_>>, Errors<'_>>,
+) -> Json<ApiResponse<AvatarView>> {
+    let ConsentedUser(auth_user) = consented;
+    let mut form = match form {
+        Ok(form) => form,
+        Err(errors) => {
+            warn!("Invalid avatar form: {}", errors);
+            return Json(ApiResponse::validation_error(vec![FieldError::new("file", "请选择头像图片，且图片不能过大")]));
+        }
+    };
+
+    let use_case = AvatarUseCase::new(pool.inner().clone(), redis.inner().clone(), config.inner().clone());
+    match use_case.upload(&auth_user.user, &mut form.file).await {
+        Ok(avatar) => Json(ApiResponse::success_with_command(avatar, RouteCommand::toast("头像处理中，完成后自动更新"))),
+        Err(UseCaseError::ValidationError(msg)) => Json(ApiResponse::validation_error(vec![FieldError::new("file", msg)])),
+        Err(e) => {
+            error!("Avatar upload failed: {}", e);
+            Json(ApiResponse::error("头像上传失败"))
+        }
+    }
+}
+
+/// 查看本人上传的头像的处理状态
+#[get("/api/uploads/avatar/<avatar_id>")]
+pub async fn get_avatar_status(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    config: &State<AvatarConfig>,
+    auth_user: AuthenticatedUser,
+    avatar_id: &str,
+) -> Json<ApiResponse<AvatarView>> {
+    let Ok(avatar_id) = uuid::Uuid::parse_str(avatar_id) else {
+        return Json(ApiResponse::error("头像不存在"));
+    };
+    let use_case = AvatarUseCase::new(pool.inner().clone(), redis.inner().clone(), config.inner().clone());
+    match use_case.status(auth_user.user.id, avatar_id).await {
+        Ok(avatar) => Json(ApiResponse::success(avatar)),
+        Err(UseCaseError::ValidationError(msg)) => Json(ApiResponse::error(&msg)),
+        Err(e) => {
+            error!("Failed to get avatar status: {}", e);
+            Json(ApiResponse::error("获取头像状态失败"))
+        }
+    }
+}
+
+/// 处理后的头像响应，内容不变，允许长期缓存
+#[derive(Responder)]
+pub struct AvatarFile {
+    file: tokio::fs::File,
+    content_type: ContentType,
+    cache_control: Header<'static>,
+    nosniff: Header<'static>,
+}
+
+/// 访问头像，只提供处理完成的头像
+#[get("/api/avatars/<avatar_id>")]
+pub async fn get_avatar(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    config: &State<AvatarConfig>,
+    avatar_id: &str,
+) -> Result<AvatarFile, Status> {
+    let avatar_id = uuid::Uuid::parse_str(avatar_id).map_err(|_| Status::NotFound)?;
+
+    let use_case = AvatarUseCase::new(pool.inner().clone(), redis.inner().clone(), config.inner().clone());
+    let file_path = match use_case.open(avatar_id).await {
+        Ok(file_path) => file_path,
+        Err(UseCaseError::ValidationError(_)) => return Err(Status::NotFound),
+        Err(e) => {
+            error!("Avatar download failed: {}", e);
+            return Err(Status::InternalServerError);
+        }
+    };
+
+    let file = tokio::fs::File::open(&file_path).await.map_err(|e| {
+        error!("Avatar file missing: {}: {}", file_path, e);
+        Status::NotFound
+    })?;
+    Ok(AvatarFile {
+        file,
+        content_type: ContentType::new("image", "webp"),
+        cache_control: Header::new("Cache-Control", "public, max-age=31536000, immutable"),
+        nosniff: Header::new("X-Content-Type-Options", "nosniff"),
+    })
+}
@@ -0,0 +1,107 @@
+use rocket::{Config, State, serde::json::Json, post};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use tracing::error;
+
+use crate::auth::ForwardedHeaders;
+use crate::models::{batch::{BatchItemResult, BatchSubRequest}, response::ApiResponse};
+use crate::use_cases::{UseCaseError, batch_use_case::BatchUseCase};
+
+/// 并发执行多个子请求，用于小程序启动时合并初始化请求
+#[post("/api/batch", data = "<requests>")]
+pub async fn batch(
+    batch: &State<BatchUseCase>,
+    config: &Config,
+    forwarded: ForwardedHeaders,
+    requests: Json<Vec<BatchSubRequest>>,
+) -> Json<ApiResponse<Vec<BatchItemResult>>> {
+    match batch.execute(&loopback_url(config), &forwarded.0, requests.into_inner()).await {
+        Ok(results) => Json(ApiResponse::success(results)),
+        Err(UseCaseError::ValidationError(msg)) | Err(UseCaseError::BusinessLogicError(msg)) => {
+            Json(ApiResponse::error(&msg))
+        }
+        Err(e) => {
+            error!("Batch request failed: {}", e);
+            Json(ApiResponse::error("批量请求失败"))
+        }
+    }
+}
+
+// 本服务的回环地址：监听所有地址时使用 localhost
+fn loopback_url(config: &Config) -> String {
+    let address = match config.address {
+        IpAddr::V4(address) if address.is_unspecified() => IpAddr::V4(Ipv4Addr::LOCALHOST),
+        IpAddr::V6(address) if address.is_unspecified() => IpAddr::V6(Ipv6Addr::LOCALHOST),
+        address => address,
+    };
+    format!("http://{}", SocketAddr::new(address, config.port))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::BatchConfig;
+    use rocket::figment::Figment;
+    use rocket::http::{ContentType, Header};
+    use rocket::local::asynchronous::Client;
+    use serde_json::json;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// 代替本服务接收子请求的 HTTP 服务，返回端口和收到的请求头名（小写）
+    async fn capture() -> (u16, tokio::task::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let handle = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buffer = [0u8; 8192];
+            let read = stream.read(&mut buffer).await.unwrap();
+            let response = "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: 2\r\nconnection: close\r\n\r\n{}";
+            stream.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8_lossy(&buffer[..read]).lines()
+                .skip(1)
+                .filter_map(|line| line.split_once(':').map(|(name, _)| name.to_ascii_lowercase()))
+                .collect()
+        });
+        (port, handle)
+    }
+
+    async fn post(client: &Client, body: serde_json::Value) -> ApiResponse<Vec<BatchItemResult>> {
+        client.post("/api/batch")
+            .header(ContentType::JSON)
+            .header(Header::new("Cookie", "session_token=abc"))
+            .header(Header::new("Accept-Language", "zh-CN"))
+            .header(Header::new("X-Internal-Token", "secret"))
+            .body(body.to_string())
+            .dispatch()
+            .await
+            .into_json()
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_batch_security_rules() {
+        let (port, server) = capture().await;
+        let figment = Figment::from(Config::debug_default()).merge(("address", "127.0.0.1")).merge(("port", port));
+        let config = BatchConfig { max_requests: 2, ..BatchConfig::default() };
+        let rocket = rocket::custom(figment)
+            .manage(BatchUseCase::from_config(config, None))
+            .mount("/", rocket::routes![batch]);
+        let client = Client::untracked(rocket).await.unwrap();
+
+        // 超过 max_requests 时整个批量请求被拒绝，不转发任何子请求
+        let status = json!({ "method": "GET", "path": "/api/auth/status" });
+        let response = post(&client, json!([status, status, status])).await;
+        assert!(response.data.is_none());
+
+        // 子请求不能调用 /api/batch 自身；转发的子请求只带白名单中的请求头
+        let results = post(&client, json!([{ "method": "POST", "path": "/api/batch/", "body": [] }, status])).await.data.unwrap();
+        assert_eq!(results[0].status, 400);
+        assert_eq!(results[1].status, 200);
+
+        let headers = server.await.unwrap();
+        assert!(headers.contains(&"cookie".to_string()));
+        assert!(headers.contains(&"accept-language".to_string()));
+        assert!(!headers.contains(&"x-internal-token".to_string()));
+    }
+}
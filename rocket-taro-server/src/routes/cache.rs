@@ -1,13 +1,20 @@
 use rocket::{State, serde::json::Json, get, post};
 use serde::{Serialize, Deserialize};
-use tracing::info;
+use tracing::{info, error};
+use uuid::Uuid;
 
-use crate::models::response::ApiResponse;
+use crate::models::{
+    response::ApiResponse,
+    route_command::RouteCommand,
+    cache_invalidation::{CacheInvalidation, CacheTarget},
+};
 use crate::cache::{
     RedisPool,
     session::SessionCache,
 };
-use crate::auth::guards::AdminUser;
+use crate::database::DbPool;
+use crate::auth::{RecentAuth, guards::AdminUser};
+use crate::use_cases::cache_invalidation_use_case::CacheInvalidationUseCase;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CacheHealthCheck {
@@ -35,11 +42,12 @@ pub async fn cache_health_check(
     Json(ApiResponse::success(health))
 }
 
-// 清除所有缓存（简化版）
+// 清除所有缓存（简化版），需近期验证过密码
 #[post("/api/cache/invalidate")]
 pub async fn invalidate_cache(
     redis: &State<RedisPool>,
     _admin: AdminUser,
+    _recent_auth: RecentAuth,
 ) -> Json<ApiResponse<String>> {
     // 清除所有应用缓存
     let pattern = "rocket_taro:*";
@@ -52,11 +60,12 @@ pub async fn invalidate_cache(
     }
 }
 
-// 清理过期会话缓存
+// 清理过期会话缓存，需近期验证过密码
 #[post("/api/cache/cleanup")]
 pub async fn cleanup_expired_sessions(
     redis: &State<RedisPool>,
     _admin: AdminUser,
+    _recent_auth: RecentAuth,
 ) -> Json<ApiResponse<String>> {
     let session_cache = SessionCache::new(redis.inner().clone());
     
@@ -67,4 +76,96 @@ pub async fn cleanup_expired_sessions(
         }
         Err(e) => Json(ApiResponse::error(&format!("清理过期会话失败: {}", e))),
     }
-}
\ No newline at end of file
+}
+
+// 清除指定对象的缓存，返回删除的键数量；调用的接口都需近期验证过密码
+async fn invalidate_target(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    admin: &AdminUser,
+    target: CacheTarget,
+) -> Json<ApiResponse<CacheInvalidation>> {
+    let use_case = CacheInvalidationUseCase::new(pool.inner().clone(), redis.inner().clone());
+    match use_case.invalidate(admin.0.user.id, target).await {
+        Ok(result) => {
+            let message = format!("已清除 {} 个缓存键", result.keys_removed);
+            Json(ApiResponse::success_with_command(result, RouteCommand::toast(&message)))
+        }
+        Err(e) => {
+            error!("Failed to invalidate cache: {}", e);
+            Json(ApiResponse::error("缓存清除失败"))
+        }
+    }
+}
+
+// 清除用户信息、用户名映射、设置和该用户的所有会话缓存
+#[post("/api/cache/invalidate/users/<user_id>")]
+pub async fn invalidate_user_cache(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    admin: AdminUser,
+    _recent_auth: RecentAuth,
+    user_id: &str,
+) -> Json<ApiResponse<CacheInvalidation>> {
+    let Ok(user_id) = Uuid::parse_str(user_id) else {
+        return Json(ApiResponse::error("无效的用户ID"));
+    };
+    invalidate_target(pool, redis, &admin, CacheTarget::User(user_id)).await
+}
+
+// 清除用户名到用户ID的映射
+#[post("/api/cache/invalidate/usernames/<username>")]
+pub async fn invalidate_username_cache(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    admin: AdminUser,
+    _recent_auth: RecentAuth,
+    username: &str,
+) -> Json<ApiResponse<CacheInvalidation>> {
+    invalidate_target(pool, redis, &admin, CacheTarget::Username(username.to_string())).await
+}
+
+// 按会话ID清除会话缓存（会话仍然有效，下次请求从数据库重新验证）
+#[post("/api/cache/invalidate/sessions/<session_id>")]
+pub async fn invalidate_session_cache(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    admin: AdminUser,
+    _recent_auth: RecentAuth,
+    session_id: &str,
+) -> Json<ApiResponse<CacheInvalidation>> {
+    let Ok(session_id) = Uuid::parse_str(session_id) else {
+        return Json(ApiResponse::error("无效的会话ID"));
+    };
+    invalidate_target(pool, redis, &admin, CacheTarget::Session(session_id)).await
+}
+
+// 清除单条用户数据及数据列表、搜索结果缓存
+#[post("/api/cache/invalidate/user-data/<data_id>")]
+pub async fn invalidate_user_data_cache(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    admin: AdminUser,
+    _recent_auth: RecentAuth,
+    data_id: &str,
+) -> Json<ApiResponse<CacheInvalidation>> {
+    let Ok(data_id) = Uuid::parse_str(data_id) else {
+        return Json(ApiResponse::error("无效的数据ID"));
+    };
+    invalidate_target(pool, redis, &admin, CacheTarget::UserData(data_id)).await
+}
+
+// 清除某个类别下的所有缓存（只支持可从数据库重建的类别）
+#[post("/api/cache/invalidate/categories/<category>")]
+pub async fn invalidate_cache_category(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    admin: AdminUser,
+    _recent_auth: RecentAuth,
+    category: &str,
+) -> Json<ApiResponse<CacheInvalidation>> {
+    match CacheTarget::category(category) {
+        Ok(target) => invalidate_target(pool, redis, &admin, target).await,
+        Err(msg) => Json(ApiResponse::error_with_command(&msg, RouteCommand::toast(&msg))),
+    }
+}
@@ -0,0 +1,35 @@
+use rocket::{State, serde::json::Json, get, put};
+use tracing::{info, error};
+
+use crate::auth::AuthenticatedUser;
+use crate::cache::{RedisPool, capabilities::ClientCapabilitiesCache};
+use crate::models::{
+    response::ApiResponse,
+    client_capabilities::{ClientCapabilities, ClientCapabilitiesRequest},
+};
+
+/// 客户端启动时声明支持的路由指令类型，本会话后续响应中的指令按此降级
+#[put("/api/route-commands/capabilities", data = "<req>")]
+pub async fn declare_capabilities(
+    redis: &State<RedisPool>,
+    auth_user: AuthenticatedUser,
+    req: Json<ClientCapabilitiesRequest>,
+) -> Json<ApiResponse<ClientCapabilities>> {
+    let capabilities = ClientCapabilities::from_commands(req.commands.iter().map(String::as_str));
+    info!(user_id = %auth_user.user.id, commands = ?capabilities.commands, "Client declared route command capabilities");
+
+    let cache = ClientCapabilitiesCache::new(redis.inner().clone());
+    match cache.set(&auth_user.session.session_token, &capabilities).await {
+        Ok(()) => Json(ApiResponse::success(capabilities)),
+        Err(e) => {
+            error!("Failed to store client capabilities: {}", e);
+            Json(ApiResponse::error("保存客户端能力失败"))
+        }
+    }
+}
+
+/// 查询当前请求生效的路由指令能力（请求头优先于会话声明）
+#[get("/api/route-commands/capabilities")]
+pub async fn get_capabilities(capabilities: ClientCapabilities) -> Json<ApiResponse<ClientCapabilities>> {
+    Json(ApiResponse::success(capabilities))
+}
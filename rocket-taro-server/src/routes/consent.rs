@@ -0,0 +1,136 @@
+use rocket::{Request, State, catch, serde::json::Json, get, post};
+use tracing::error;
+use uuid::Uuid;
+
+use crate::models::{
+    consent::{AcceptConsentRequest, ConsentResult, CreatePolicyDocumentRequest, PolicyDocument, UserConsent},
+    response::ApiResponse,
+    route_command::RouteCommand,
+    route_decision::RouteTrace,
+};
+use crate::database::DbPool;
+use crate::auth::{AuthenticatedUser, OwnerSession, RequestInfo, guards::{AdminUser, PendingPolicies}};
+use crate::config::{RouteConfig, Platform};
+use crate::use_cases::{
+    UseCaseError,
+    UseCaseResult,
+    consent_use_case::ConsentUseCase,
+    route_command_generator::RouteCommandGenerator,
+    session_issuer::RequestContext,
+};
+
+fn use_case(pool: &State<DbPool>) -> ConsentUseCase {
+    ConsentUseCase::new(pool.inner().clone())
+}
+
+fn to_response<T>(result: UseCaseResult<T>, failure: &str) -> Json<ApiResponse<T>> {
+    match result {
+        Ok(value) => Json(ApiResponse::success(value)),
+        Err(UseCaseError::ValidationError(msg))
+        | Err(UseCaseError::BusinessLogicError(msg))
+        | Err(UseCaseError::ConflictError(msg)) => Json(ApiResponse::error(&msg)),
+        Err(e) => {
+            error!("{}: {}", failure, e);
+            Json(ApiResponse::error(failure))
+        }
+    }
+}
+
+// 某类协议（terms、privacy）当前生效的版本，不需要登录
+#[get("/api/policies/<policy_type>")]
+pub async fn get_active_policy(pool: &State<DbPool>, policy_type: &str) -> Json<ApiResponse<PolicyDocument>> {
+    match use_case(pool).active(policy_type).await {
+        Ok(Some(document)) => Json(ApiResponse::success(document)),
+        Ok(None) => Json(ApiResponse::error("该协议尚未发布")),
+        Err(e) => to_response(Err(e), "获取协议失败"),
+    }
+}
+
+// 当前用户尚未同意的生效协议版本，协议同意页据此展示内容
+#[get("/api/consents/pending")]
+pub async fn get_pending_consents(pool: &State<DbPool>, auth_user: AuthenticatedUser) -> Json<ApiResponse<Vec<PolicyDocument>>> {
+    to_response(use_case(pool).pending(auth_user.user.id).await, "获取待同意协议失败")
+}
+
+// 当前用户的同意记录
+#[get("/api/consents")]
+pub async fn list_consents(pool: &State<DbPool>, auth_user: AuthenticatedUser) -> Json<ApiResponse<Vec<UserConsent>>> {
+    to_response(use_case(pool).history(auth_user.user.id).await, "获取同意记录失败")
+}
+
+/// 同意协议，记录 IP、User-Agent 和时间；模拟登录会话不能代替用户同意
+#[post("/api/consents", data = "<request>")]
+pub async fn accept_consents(
+    pool: &State<DbPool>,
+    route_config: &State<RouteConfig>,
+    owner_session: OwnerSession,
+    request_info: RequestInfo,
+    request: Json<AcceptConsentRequest>,
+) -> Json<ApiResponse<ConsentResult>> {
+    let OwnerSession(auth_user) = owner_session;
+    let platform = request_info.user_agent.as_deref().map(Platform::from_user_agent).unwrap_or_default();
+    let context = RequestContext::new(request_info.ip_address, request_info.user_agent);
+
+    match use_case(pool).accept(auth_user.user.id, request.into_inner(), &context).await {
+        Ok(result) => {
+            let route_command = RouteCommandGenerator::generate_consent_accepted_route_command(&result, route_config, platform);
+            Json(ApiResponse::success_with_command(result, route_command))
+        }
+        Err(e) => to_response(Err(e), "同意协议失败"),
+    }
+}
+
+/// ConsentedUser 守卫拒绝时返回 451 和跳转协议同意页的路由指令
+#[catch(451)]
+pub fn consent_required_catcher(request: &Request<'_>) -> Json<ApiResponse<()>> {
+    let platform = request.headers().get_one("User-Agent")
+        .map(Platform::from_user_agent)
+        .unwrap_or_default();
+    let message = "请先阅读并同意最新的服务协议和隐私政策";
+    let pending = request.local_cache(PendingPolicies::default);
+    let route_command = match request.rocket().state::<RouteConfig>() {
+        Some(route_config) => RouteCommandGenerator::generate_consent_required_route_command(
+            &pending.0,
+            Some(request.uri().path().as_str()),
+            route_config,
+            platform,
+            &mut RouteTrace::default(),
+        ),
+        None => RouteCommand::toast(message),
+    };
+    Json(ApiResponse::error_with_command(message, route_command))
+}
+
+// 全部协议版本
+#[get("/api/admin/policies")]
+pub async fn list_policies(pool: &State<DbPool>, _admin: AdminUser) -> Json<ApiResponse<Vec<PolicyDocument>>> {
+    to_response(use_case(pool).list_documents().await, "获取协议列表失败")
+}
+
+// 发布协议新版本，activate 为 true 时立即生效，所有用户需要重新同意
+#[post("/api/admin/policies", data = "<request>")]
+pub async fn create_policy(
+    pool: &State<DbPool>,
+    admin: AdminUser,
+    request_info: RequestInfo,
+    request: Json<CreatePolicyDocumentRequest>,
+) -> Json<ApiResponse<PolicyDocument>> {
+    to_response(
+        use_case(pool).create_document(request.into_inner(), admin.0.user.id, request_info.ip_address).await,
+        "发布协议失败",
+    )
+}
+
+// 让某个协议版本生效，同类型的旧版本失效
+#[post("/api/admin/policies/<document_id>/activate")]
+pub async fn activate_policy(
+    pool: &State<DbPool>,
+    admin: AdminUser,
+    request_info: RequestInfo,
+    document_id: &str,
+) -> Json<ApiResponse<PolicyDocument>> {
+    let Ok(document_id) = Uuid::parse_str(document_id) else {
+        return Json(ApiResponse::error("无效的协议ID"));
+    };
+    to_response(use_case(pool).activate(document_id, admin.0.user.id, request_info.ip_address).await, "协议生效失败")
+}
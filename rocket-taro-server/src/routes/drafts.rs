@@ -0,0 +1,57 @@
+use rocket::{State, serde::json::Json, get, put, delete};
+use tracing::error;
+
+use crate::models::{draft::FormDraft, response::ApiResponse, route_command::RouteCommand};
+use crate::cache::RedisPool;
+use crate::auth::AuthenticatedUser;
+use crate::use_cases::{UseCaseError, UseCaseResult, draft_use_case::DraftUseCase};
+
+fn to_response<T>(result: UseCaseResult<T>, failure: &str) -> Json<ApiResponse<T>> {
+    match result {
+        Ok(value) => Json(ApiResponse::success(value)),
+        Err(UseCaseError::ValidationError(msg)) => {
+            Json(ApiResponse::error_with_command(&msg, RouteCommand::toast(&msg)))
+        }
+        Err(e) => {
+            error!("{}: {}", failure, e);
+            Json(ApiResponse::error(failure))
+        }
+    }
+}
+
+/// 保存表单草稿，请求体即表单内容
+#[put("/api/drafts/<form_key>", data = "<data>")]
+pub async fn save_draft(
+    redis: &State<RedisPool>,
+    auth_user: AuthenticatedUser,
+    form_key: &str,
+    data: Json<serde_json::Value>,
+) -> Json<ApiResponse<FormDraft>> {
+    let use_case = DraftUseCase::new(redis.inner().clone());
+    to_response(use_case.save(auth_user.user.id, form_key, data.into_inner()).await, "保存草稿失败")
+}
+
+/// 获取表单草稿，不存在时 data 为 null
+#[get("/api/drafts/<form_key>")]
+pub async fn get_draft(
+    redis: &State<RedisPool>,
+    auth_user: AuthenticatedUser,
+    form_key: &str,
+) -> Json<ApiResponse<Option<FormDraft>>> {
+    let use_case = DraftUseCase::new(redis.inner().clone());
+    to_response(use_case.get(auth_user.user.id, form_key).await, "获取草稿失败")
+}
+
+/// 删除表单草稿（提交或放弃表单后调用）
+#[delete("/api/drafts/<form_key>")]
+pub async fn delete_draft(
+    redis: &State<RedisPool>,
+    auth_user: AuthenticatedUser,
+    form_key: &str,
+) -> Json<ApiResponse<()>> {
+    let use_case = DraftUseCase::new(redis.inner().clone());
+    match use_case.delete(auth_user.user.id, form_key).await {
+        Ok(()) => Json(ApiResponse::ok()),
+        Err(e) => to_response(Err(e), "删除草稿失败"),
+    }
+}
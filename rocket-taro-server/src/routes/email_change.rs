@@ -0,0 +1,118 @@
+use rocket::{State, serde::json::Json, get, post, delete};
+use std::sync::Arc;
+use tracing::error;
+
+use crate::models::{
+    email_change::{ConfirmEmailChangeRequest, EmailChangeRequest, EmailChangeStatus},
+    response::ApiResponse,
+    route_command::RouteCommand,
+};
+use crate::database::DbPool;
+use crate::cache::RedisPool;
+use crate::auth::{AuthenticatedUser, OwnerSession, RequestInfo, PasswordHasher};
+use crate::config::EmailChangeConfig;
+use crate::mail::Mailer;
+use crate::use_cases::{UseCaseError, UseCaseResult, email_change_use_case::EmailChangeUseCase};
+
+fn use_case(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    password_hasher: &State<PasswordHasher>,
+    mailer: &State<Arc<dyn Mailer>>,
+    config: &State<EmailChangeConfig>,
+) -> EmailChangeUseCase {
+    EmailChangeUseCase::new(
+        pool.inner().clone(),
+        redis.inner().clone(),
+        password_hasher.inner().clone(),
+        mailer.inner().clone(),
+        config.inner().clone(),
+    )
+}
+
+fn to_response<T>(result: UseCaseResult<T>, failure: &str, success_toast: impl FnOnce(&T) -> &'static str) -> Json<ApiResponse<T>> {
+    match result {
+        Ok(value) => {
+            let toast = success_toast(&value);
+            Json(ApiResponse::with_toast(value, toast))
+        }
+        Err(UseCaseError::ValidationError(msg))
+        | Err(UseCaseError::BusinessLogicError(msg))
+        | Err(UseCaseError::AuthenticationError(msg)) => {
+            Json(ApiResponse::error_with_command(&msg, RouteCommand::toast(&msg)))
+        }
+        Err(e) => {
+            error!("{}: {}", failure, e);
+            Json(ApiResponse::error_with_command(failure, RouteCommand::toast(failure)))
+        }
+    }
+}
+
+/// 申请修改邮箱，向新旧邮箱发送确认链接
+#[post("/api/auth/email-change", data = "<request>")]
+#[allow(clippy::too_many_arguments)]
+pub async fn request_email_change(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    password_hasher: &State<PasswordHasher>,
+    mailer: &State<Arc<dyn Mailer>>,
+    config: &State<EmailChangeConfig>,
+    owner_session: OwnerSession,
+    request_info: RequestInfo,
+    request: Json<EmailChangeRequest>,
+) -> Json<ApiResponse<EmailChangeStatus>> {
+    let OwnerSession(auth_user) = owner_session;
+    let use_case = use_case(pool, redis, password_hasher, mailer, config);
+    let result = use_case.request(&auth_user.user, auth_user.session.id, request.into_inner(), request_info.ip_address).await;
+    to_response(result, "申请修改邮箱失败", |_| "确认邮件已发送，请查收")
+}
+
+/// 查询待确认的邮箱修改，没有时 data 为 null
+#[get("/api/auth/email-change")]
+pub async fn get_email_change(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    password_hasher: &State<PasswordHasher>,
+    mailer: &State<Arc<dyn Mailer>>,
+    config: &State<EmailChangeConfig>,
+    auth_user: AuthenticatedUser,
+) -> Json<ApiResponse<Option<EmailChangeStatus>>> {
+    match use_case(pool, redis, password_hasher, mailer, config).status(&auth_user.user).await {
+        Ok(status) => Json(ApiResponse::success(status)),
+        Err(e) => {
+            error!("Failed to load email change status: {}", e);
+            Json(ApiResponse::error("获取邮箱修改状态失败"))
+        }
+    }
+}
+
+/// 取消邮箱修改
+#[delete("/api/auth/email-change")]
+pub async fn cancel_email_change(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    password_hasher: &State<PasswordHasher>,
+    mailer: &State<Arc<dyn Mailer>>,
+    config: &State<EmailChangeConfig>,
+    owner_session: OwnerSession,
+) -> Json<ApiResponse<()>> {
+    let OwnerSession(auth_user) = owner_session;
+    let result = use_case(pool, redis, password_hasher, mailer, config).cancel(&auth_user.user).await;
+    to_response(result, "取消邮箱修改失败", |_| "已取消邮箱修改")
+}
+
+/// 确认邮件中的链接（无需登录，令牌即凭证）
+#[post("/api/auth/email-change/confirm", data = "<request>")]
+pub async fn confirm_email_change(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    password_hasher: &State<PasswordHasher>,
+    mailer: &State<Arc<dyn Mailer>>,
+    config: &State<EmailChangeConfig>,
+    request: Json<ConfirmEmailChangeRequest>,
+) -> Json<ApiResponse<EmailChangeStatus>> {
+    let result = use_case(pool, redis, password_hasher, mailer, config).confirm(&request.token).await;
+    to_response(result, "确认邮箱修改失败", |status| {
+        if status.completed { "邮箱已修改，其他设备需要重新登录" } else { "已确认，请继续确认另一封邮件" }
+    })
+}
@@ -0,0 +1,22 @@
+use async_graphql::{Request, Response, ServerError};
+use rocket::{State, serde::json::Json, post};
+
+use crate::auth::guards::AdminUser;
+use crate::config::GraphqlConfig;
+use crate::database::DbPool;
+use crate::graphql::{self, AdminSchema};
+
+/// 管理后台 GraphQL 查询；请求体为标准 GraphQL JSON，支持 Apollo 持久化查询扩展
+#[post("/api/graphql", data = "<request>")]
+pub async fn graphql_query(
+    schema: &State<AdminSchema>,
+    config: &State<GraphqlConfig>,
+    pool: &State<DbPool>,
+    admin: AdminUser,
+    request: Json<Request>,
+) -> Json<Response> {
+    if !config.enabled {
+        return Json(Response::from_errors(vec![ServerError::new("GraphQL 接口未启用", None)]));
+    }
+    Json(graphql::execute(schema, pool.inner().clone(), admin.0, request.into_inner()).await)
+}
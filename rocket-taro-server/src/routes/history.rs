@@ -0,0 +1,27 @@
+use rocket::{State, serde::json::Json, get};
+use tracing::error;
+
+use crate::models::{history::RowHistory, response::{ApiResponse, PagedResponse}};
+use crate::database::DbPool;
+use crate::auth::guards::AdminUser;
+use crate::use_cases::{UseCaseError, history_use_case::HistoryUseCase};
+
+// 管理员查看用户（users）或用户数据（user-data）的变更历史
+#[get("/api/admin/history/<entity>/<id>?<cursor>&<limit>")]
+pub async fn get_row_history(
+    pool: &State<DbPool>,
+    _admin: AdminUser,
+    entity: &str,
+    id: &str,
+    cursor: Option<&str>,
+    limit: Option<i64>,
+) -> Json<ApiResponse<PagedResponse<RowHistory>>> {
+    match HistoryUseCase::new(pool.inner().clone()).timeline(entity, id, cursor, limit).await {
+        Ok(page) => Json(ApiResponse::success(page)),
+        Err(UseCaseError::ValidationError(msg)) => Json(ApiResponse::error(&msg)),
+        Err(e) => {
+            error!("Failed to load row history: {}", e);
+            Json(ApiResponse::error("获取变更历史失败"))
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use rocket::{State, serde::json::Json, get, post};
+use std::sync::Arc;
+use tracing::error;
+
+use crate::models::{
+    identity::{IdentityVerificationStatus, SubmitIdentityRequest},
+    response::ApiResponse,
+    route_command::RouteCommand,
+};
+use crate::database::DbPool;
+use crate::auth::{AuthenticatedUser, OwnerSession, RequestInfo};
+use crate::config::{IdentityVerificationConfig, RouteConfig, Platform};
+use crate::identity::IdentityVerifier;
+use crate::use_cases::{
+    UseCaseError,
+    identity_use_case::IdentityUseCase,
+    route_command_generator::RouteCommandGenerator,
+    session_issuer::RequestContext,
+};
+
+fn use_case(
+    pool: &State<DbPool>,
+    verifier: &State<Arc<dyn IdentityVerifier>>,
+    config: &State<IdentityVerificationConfig>,
+) -> IdentityUseCase {
+    IdentityUseCase::new(pool.inner().clone(), verifier.inner().clone(), config.inner().clone())
+}
+
+// 当前用户的实名认证状态（脱敏）
+#[get("/api/identity/verification")]
+pub async fn get_identity_verification(
+    pool: &State<DbPool>,
+    verifier: &State<Arc<dyn IdentityVerifier>>,
+    config: &State<IdentityVerificationConfig>,
+    auth_user: AuthenticatedUser,
+) -> Json<ApiResponse<IdentityVerificationStatus>> {
+    match use_case(pool, verifier, config).status(auth_user.user.id).await {
+        Ok(status) => Json(ApiResponse::success(status)),
+        Err(e) => {
+            error!("Failed to get identity verification: {}", e);
+            Json(ApiResponse::error("获取实名认证状态失败"))
+        }
+    }
+}
+
+/// 提交实名认证，模拟登录会话不能代替用户认证
+#[post("/api/identity/verification", data = "<request>")]
+pub async fn submit_identity_verification(
+    pool: &State<DbPool>,
+    verifier: &State<Arc<dyn IdentityVerifier>>,
+    config: &State<IdentityVerificationConfig>,
+    route_config: &State<RouteConfig>,
+    owner_session: OwnerSession,
+    request_info: RequestInfo,
+    request: Json<SubmitIdentityRequest>,
+) -> Json<ApiResponse<IdentityVerificationStatus>> {
+    let OwnerSession(auth_user) = owner_session;
+    let platform = request_info.user_agent.as_deref().map(Platform::from_user_agent).unwrap_or_default();
+    let context = RequestContext::new(request_info.ip_address, request_info.user_agent);
+    let request = request.into_inner();
+
+    match use_case(pool, verifier, config).submit(auth_user.user.id, &request, &context).await {
+        Ok(status) => {
+            let route_command = RouteCommandGenerator::generate_identity_submitted_route_command(
+                &status,
+                request.redirect.as_deref(),
+                route_config,
+                platform,
+            );
+            Json(ApiResponse::success_with_command(status, route_command))
+        }
+        Err(UseCaseError::ValidationError(msg))
+        | Err(UseCaseError::BusinessLogicError(msg))
+        | Err(UseCaseError::InternalError(msg)) => {
+            Json(ApiResponse::error_with_command(&msg, RouteCommand::toast(&msg)))
+        }
+        Err(e) => {
+            error!("Failed to submit identity verification: {}", e);
+            Json(ApiResponse::error_with_command("实名认证失败", RouteCommand::toast("实名认证失败，请稍后重试")))
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use rocket::{Request, State, catch, serde::json::Json, post};
+use tracing::{info, error};
+use uuid::Uuid;
+
+use crate::models::{
+    identity::VerificationBlock,
+    impersonation::ImpersonationSession,
+    response::ApiResponse,
+    route_command::RouteCommand,
+};
+use crate::database::DbPool;
+use crate::cache::RedisPool;
+use crate::auth::{RecentAuth, RequestInfo, guards::{AdminUser, IdentityBlock}};
+use crate::config::{ImpersonationConfig, Platform, RouteConfig};
+use crate::fairings::impersonation_audit::is_impersonated;
+use crate::use_cases::{UseCaseError, impersonation_use_case::ImpersonationUseCase, route_command_generator::RouteCommandGenerator};
+
+/// 管理员以指定用户身份登录，返回模拟登录会话令牌、用户信息和提示条指令；
+/// 要求管理员最近验证过密码，否则由 428 catcher 下发重新验证指令
+#[post("/api/admin/impersonate/<user_id>")]
+pub async fn impersonate_user(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    config: &State<ImpersonationConfig>,
+    admin: AdminUser,
+    _recent_auth: RecentAuth,
+    request_info: RequestInfo,
+    user_id: &str,
+) -> Json<ApiResponse<ImpersonationSession>> {
+    let Ok(user_id) = Uuid::parse_str(user_id) else {
+        return Json(ApiResponse::error("无效的用户ID"));
+    };
+    info!(admin_id = %admin.0.user.id, user_id = %user_id, "Impersonation requested");
+
+    let use_case = ImpersonationUseCase::new(pool.inner().clone(), redis.inner().clone(), config.inner().clone());
+    match use_case.start(&admin.0, user_id, request_info.ip_address, request_info.user_agent).await {
+        Ok((session, route_command)) => Json(ApiResponse::success_with_command(session, route_command)),
+        Err(UseCaseError::ValidationError(msg)) | Err(UseCaseError::BusinessLogicError(msg)) => {
+            Json(ApiResponse::error_with_command(&msg, RouteCommand::toast(&msg)))
+        }
+        Err(e) => {
+            error!("Failed to start impersonation: {}", e);
+            Json(ApiResponse::error("模拟登录失败"))
+        }
+    }
+}
+
+/// 403 响应：实名认证守卫拦截时说明原因并引导认证，模拟登录会话执行敏感操作时提示原因
+#[catch(403)]
+pub fn forbidden_catcher(request: &Request<'_>) -> Json<ApiResponse<()>> {
+    if let (IdentityBlock(Some(block)), Some(route_config)) = (request.local_cache(IdentityBlock::default), request.rocket().state::<RouteConfig>()) {
+        let platform = request.headers().get_one("User-Agent")
+            .map(Platform::from_user_agent)
+            .unwrap_or_default();
+        let message = match block {
+            VerificationBlock::Unverified => "请先完成实名认证",
+            VerificationBlock::Underage { .. } => "未达到该功能的年龄要求",
+        };
+        let route_command = RouteCommandGenerator::generate_verification_required_route_command(
+            *block,
+            Some(request.uri().path().as_str()),
+            route_config,
+            platform,
+        );
+        return Json(ApiResponse::error_with_command(message, route_command));
+    }
+
+    let message = if is_impersonated(request) {
+        "模拟登录期间不能执行该操作"
+    } else {
+        "没有权限执行该操作"
+    };
+    Json(ApiResponse::error_with_command(message, RouteCommand::toast(message)))
+}
@@ -0,0 +1,81 @@
+use rocket::{Request, Responder, catch, get, serde::json::Json};
+use rocket::data::ByteUnit;
+use rocket::http::{Header, Status};
+
+use crate::fairings::body_limits::PAYLOAD_TOO_LARGE;
+use crate::metrics::MetricsRegistry;
+use crate::models::{response::ApiResponse, route_command::RouteCommand};
+
+/// 并发数超限的响应，Retry-After 为建议的重试间隔（秒）
+#[derive(Responder)]
+#[response(status = 503)]
+pub struct Overloaded {
+    body: Json<ApiResponse<()>>,
+    retry_after: Header<'static>,
+}
+
+/// 超出每日配额的响应，Retry-After 为距离配额重置的秒数
+#[derive(Responder)]
+#[response(status = 429)]
+pub struct QuotaExceeded {
+    body: Json<ApiResponse<()>>,
+    retry_after: Header<'static>,
+}
+
+/// 请求体超过路由分组上限时由 BodyLimits fairing 改写到这里，返回 413
+#[get("/api/limits/payload-too-large?<limit>")]
+pub async fn payload_too_large(limit: Option<u64>) -> (Status, Json<ApiResponse<()>>) {
+    let message = match limit {
+        Some(limit) => format!("请求内容不能超过{}", ByteUnit::from(limit)),
+        None => "请求内容过大".to_string(),
+    };
+    (Status::PayloadTooLarge, Json(ApiResponse::error(&message)))
+}
+
+/// 路由分组的并发数已满且排队超时时由 ConcurrencyLimiter fairing 改写到这里，返回 503，客户端可稍后重试
+#[get("/api/limits/overloaded?<group>&<retry_after>")]
+pub async fn overloaded(group: Option<&str>, retry_after: Option<u64>) -> Overloaded {
+    let retry_after = retry_after.unwrap_or(1);
+    let message = match group {
+        Some("wechat") => "当前微信登录人数较多，请稍后重试",
+        Some("password") => "当前登录人数较多，请稍后重试",
+        _ => "服务繁忙，请稍后重试",
+    };
+    let route_command = RouteCommand::alert("服务繁忙", &format!("{}（约 {} 秒后）", message, retry_after));
+    let mut response = ApiResponse::error_with_command(message, route_command);
+    response.code = Status::ServiceUnavailable.code as i32;
+    Overloaded {
+        body: Json(response),
+        retry_after: Header::new("Retry-After", retry_after.to_string()),
+    }
+}
+
+/// 调用方当天的配额已用完时由 ApiQuotaLimiter fairing 改写到这里，返回 429，UTC 零点后恢复
+#[get("/api/limits/quota-exceeded?<group>&<limit>&<retry_after>")]
+pub async fn quota_exceeded(group: Option<&str>, limit: Option<u64>, retry_after: Option<u64>) -> QuotaExceeded {
+    let action = match group {
+        Some("export") => "数据导出",
+        Some("search") => "搜索",
+        Some("batch") => "批量请求",
+        _ => "该操作",
+    };
+    let message = match limit {
+        Some(limit) => format!("今日{}次数已达上限（{}次），请明天再试", action, limit),
+        None => format!("今日{}次数已达上限，请明天再试", action),
+    };
+    let mut response = ApiResponse::error_with_command(&message, RouteCommand::alert("次数已达上限", &message));
+    response.code = Status::TooManyRequests.code as i32;
+    QuotaExceeded {
+        body: Json(response),
+        retry_after: Header::new("Retry-After", retry_after.unwrap_or(60).to_string()),
+    }
+}
+
+/// 未携带 Content-Length 的请求在读取时超过 Rocket 的 `[default.limits]`
+#[catch(413)]
+pub fn payload_too_large_catcher(request: &Request<'_>) -> Json<ApiResponse<()>> {
+    if let Some(metrics) = request.rocket().state::<MetricsRegistry>() {
+        metrics.increment(PAYLOAD_TOO_LARGE);
+    }
+    Json(ApiResponse::error("请求内容过大"))
+}
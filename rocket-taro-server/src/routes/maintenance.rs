@@ -0,0 +1,88 @@
+use rocket::{State, serde::json::Json, get, put};
+use rocket::http::Status;
+use tracing::{error, warn};
+
+use crate::auth::RequestInfo;
+use crate::auth::guards::AdminUser;
+use crate::cache::RedisPool;
+use crate::config::{RouteConfig, Platform};
+use crate::database::DbPool;
+use crate::models::{
+    response::ApiResponse,
+    route_command::RouteCommand,
+    maintenance::{MaintenanceRequest, MaintenanceState},
+};
+use crate::use_cases::{UseCaseError, maintenance_use_case::MaintenanceUseCase, route_command_generator::RouteCommandGenerator};
+
+/// 查询维护状态（维护期间始终可访问，供客户端轮询）
+#[get("/api/maintenance")]
+pub async fn get_maintenance_status(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+) -> Json<ApiResponse<MaintenanceState>> {
+    let use_case = MaintenanceUseCase::new(pool.inner().clone(), redis.inner().clone());
+    match use_case.status().await {
+        Ok(state) => Json(ApiResponse::success(state)),
+        Err(e) => {
+            error!("Failed to load maintenance state: {}", e);
+            Json(ApiResponse::error("获取维护状态失败"))
+        }
+    }
+}
+
+/// 维护期间被拦截的请求由 MaintenanceMode fairing 改写到这里，返回 503 和跳转维护页的路由指令
+#[get("/api/maintenance/blocked")]
+pub async fn maintenance_blocked(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    route_config: &State<RouteConfig>,
+    request_info: RequestInfo,
+) -> (Status, Json<ApiResponse<MaintenanceState>>) {
+    let platform = request_info.user_agent.as_deref()
+        .map(Platform::from_user_agent)
+        .unwrap_or_default();
+
+    let use_case = MaintenanceUseCase::new(pool.inner().clone(), redis.inner().clone());
+    let state = use_case.status().await.unwrap_or_else(|e| {
+        warn!("Failed to load maintenance state for blocked request: {}", e);
+        MaintenanceState::default()
+    });
+    let message = state.message.clone().unwrap_or_else(|| "系统维护中，请稍后再试".to_string());
+    let route_command = RouteCommandGenerator::generate_maintenance_route_command(&state, route_config, platform);
+    (Status::ServiceUnavailable, Json(ApiResponse::error_with_command(&message, route_command)))
+}
+
+/// 管理端查询维护状态
+#[get("/api/admin/maintenance")]
+pub async fn get_admin_maintenance(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    _admin: AdminUser,
+) -> Json<ApiResponse<MaintenanceState>> {
+    get_maintenance_status(pool, redis).await
+}
+
+/// 开启或关闭维护模式（管理员请求不受维护模式影响）
+#[put("/api/admin/maintenance", data = "<req>")]
+pub async fn update_maintenance(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    admin: AdminUser,
+    request_info: RequestInfo,
+    req: Json<MaintenanceRequest>,
+) -> Json<ApiResponse<MaintenanceState>> {
+    let use_case = MaintenanceUseCase::new(pool.inner().clone(), redis.inner().clone());
+    match use_case.execute_update(&admin.0.user, req.into_inner(), request_info.ip_address).await {
+        Ok(state) => {
+            let message = if state.enabled { "维护模式已开启" } else { "维护模式已关闭" };
+            Json(ApiResponse::with_toast(state, message))
+        }
+        Err(UseCaseError::ValidationError(msg)) => {
+            Json(ApiResponse::error_with_command(&msg, RouteCommand::toast(&msg)))
+        }
+        Err(e) => {
+            error!("Failed to update maintenance mode: {}", e);
+            Json(ApiResponse::error("更新维护模式失败"))
+        }
+    }
+}
@@ -1,9 +1,16 @@
-use rocket::{post, serde::json::Json};
+use rocket::{get, post, State, serde::json::Json};
+use std::collections::BTreeMap;
 use serde::{Deserialize, Serialize};
 use tracing::{info, warn, error, instrument};
 use chrono::{DateTime, Utc};
 
+use crate::auth::guards::AdminUser;
+use crate::database::DbPool;
+use crate::metrics::{MetricsRegistry, slo::{SloReport, SloTracker}};
 use crate::models::response::ApiResponse;
+use crate::models::route_command::RouteCommand;
+use crate::models::route_execution::{RouteCommandAck, RouteCommandAckRequest};
+use crate::use_cases::{UseCaseError, route_execution_use_case::RouteExecutionUseCase};
 
 /// 前端路由指令执行错误指标
 #[derive(Debug, Deserialize)]
@@ -62,6 +69,26 @@ pub async fn receive_route_command_error_metric(
     Json(ApiResponse::with_toast((), "指标已记录"))
 }
 
+/// 确认路由指令执行结果（执行ID随响应的 execution_id 字段下发）
+#[post("/api/route-commands/ack", data = "<ack>")]
+#[instrument(skip_all, name = "acknowledge_route_command")]
+pub async fn acknowledge_route_command(
+    pool: &State<DbPool>,
+    ack: Json<RouteCommandAckRequest>,
+) -> Json<ApiResponse<RouteCommandAck>> {
+    let use_case = RouteExecutionUseCase::new(pool.inner().clone());
+    match use_case.acknowledge(ack.into_inner()).await {
+        Ok(result) => Json(ApiResponse::success(result)),
+        Err(UseCaseError::ValidationError(msg)) => {
+            Json(ApiResponse::error_with_command(&msg, RouteCommand::toast(&msg)))
+        }
+        Err(e) => {
+            error!("Failed to acknowledge route command: {}", e);
+            Json(ApiResponse::error("执行确认记录失败"))
+        }
+    }
+}
+
 /// 前端性能指标
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -124,6 +151,24 @@ pub async fn receive_performance_metric(
     Json(ApiResponse::with_toast((), "性能指标已记录"))
 }
 
+/// 获取服务端计数器（慢查询、查询超时等），仅管理员可访问
+#[get("/api/metrics")]
+pub async fn get_metrics(
+    _admin: AdminUser,
+    metrics: &State<MetricsRegistry>,
+) -> Json<ApiResponse<BTreeMap<String, u64>>> {
+    Json(ApiResponse::success(metrics.snapshot()))
+}
+
+/// 各路由组的 SLO 达标情况、燃烧率和正在触发的告警，仅管理员可访问
+#[get("/api/admin/slo")]
+pub async fn get_slo_report(
+    _admin: AdminUser,
+    slo: &State<SloTracker>,
+) -> Json<ApiResponse<SloReport>> {
+    Json(ApiResponse::success(slo.report(Utc::now())))
+}
+
 /// 获取系统健康状态
 #[post("/api/metrics/health")]
 #[instrument(name = "get_system_health")]
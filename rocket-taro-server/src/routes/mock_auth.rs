@@ -0,0 +1,93 @@
+use rocket::{State, serde::json::Json, post, get};
+use tracing::info;
+
+use crate::models::{
+    auth::{LoginRequest, LoginResponse, UserInfo},
+    business_results::LogoutResult,
+    response::ApiResponse,
+    route_command::RouteCommand,
+    route_decision::RouteTrace,
+};
+use crate::auth::{RequestInfo, SessionCookies};
+use crate::config::{RouteConfig, Platform, route_keys};
+use crate::dev_mock::{MockStore, MockUser};
+use crate::use_cases::route_command_generator::RouteCommandGenerator;
+
+/// 模拟登录：任意密码均可登录，用户不存在时自动创建
+#[post("/api/auth/login", data = "<login_req>")]
+pub async fn login(
+    store: &State<MockStore>,
+    route_config: &State<RouteConfig>,
+    cookies: SessionCookies<'_>,
+    login_req: Json<LoginRequest>,
+    request_info: RequestInfo,
+) -> Json<ApiResponse<LoginResponse>> {
+    let username = login_req.username.trim();
+    if username.is_empty() {
+        return Json(ApiResponse::error("用户名不能为空"));
+    }
+
+    let user = store.user(username);
+    let session = store.create_session(&user, request_info.user_agent.clone());
+    cookies.set(&session.session_token);
+    info!("Mock login: {}", user.username);
+
+    let platform = Platform::from_user_agent(request_info.user_agent.as_deref().unwrap_or("unknown"));
+    let home_route = route_config.get_route(route_keys::HOME_MAIN, platform)
+        .unwrap_or_else(|| "/pages/home/home".to_string());
+    let user_info = UserInfo::from(user);
+    let route_command = RouteCommand::sequence(vec![
+        RouteCommand::process_data("user", serde_json::to_value(&user_info).unwrap()),
+        RouteCommand::redirect_to(&home_route),
+    ]);
+
+    Json(ApiResponse::success_with_command(LoginResponse {
+        user: user_info,
+        session_token: session.session_token,
+        expires_at: session.expires_at,
+    }, route_command))
+}
+
+#[post("/api/auth/logout")]
+pub async fn logout(
+    store: &State<MockStore>,
+    route_config: &State<RouteConfig>,
+    cookies: SessionCookies<'_>,
+    mock_user: MockUser,
+    request_info: RequestInfo,
+) -> Json<ApiResponse<()>> {
+    let session_destroyed = mock_user.session_token.as_deref()
+        .map(|token| store.remove_session(token))
+        .unwrap_or(false);
+    cookies.clear();
+
+    let platform = Platform::from_user_agent(request_info.user_agent.as_deref().unwrap_or("unknown"));
+    let result = LogoutResult {
+        user_id: mock_user.user.id,
+        session_destroyed,
+        has_unsaved_data: false,
+    };
+    Json(ApiResponse::command_only(RouteCommandGenerator::generate_logout_route_command(&result, route_config, platform, &mut RouteTrace::default())))
+}
+
+#[get("/api/auth/current")]
+pub async fn get_current_user(mock_user: MockUser) -> Json<ApiResponse<UserInfo>> {
+    Json(ApiResponse::success(UserInfo::from(mock_user.user)))
+}
+
+#[get("/api/auth/status")]
+pub async fn auth_status(
+    route_config: &State<RouteConfig>,
+    mock_user: Option<MockUser>,
+    request_info: RequestInfo,
+) -> Json<ApiResponse<Option<UserInfo>>> {
+    match mock_user {
+        Some(mock_user) => Json(ApiResponse::success(Some(UserInfo::from(mock_user.user)))),
+        None => {
+            let platform = Platform::from_user_agent(request_info.user_agent.as_deref().unwrap_or("unknown"));
+            let login_route = route_config.get_route(route_keys::AUTH_LOGIN, platform)
+                .unwrap_or_else(|| "/pages/login/login".to_string());
+            Json(ApiResponse::error_with_command("未登录", RouteCommand::navigate_to(&login_route)))
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use rocket::{State, serde::json::Json, get, post};
+use tracing::info;
+
+use crate::models::{response::{ApiResponse, PagedResponse}, user_data::{UserData, NewUserData}};
+use crate::dev_mock::MockStore;
+use crate::utils::pagination::{Cursor, Page, PageRequest};
+
+/// 单页用户数据数量上限，与正式接口一致
+const MAX_USER_DATA_PAGE_SIZE: i64 = 100;
+
+#[post("/api/user-data", data = "<new_data>")]
+pub async fn create_user_data(
+    store: &State<MockStore>,
+    new_data: Json<NewUserData>,
+) -> Json<ApiResponse<UserData>> {
+    let new_data = match new_data.into_inner().normalize() {
+        Ok(new_data) => new_data,
+        Err(errors) => return Json(ApiResponse::validation_error(errors)),
+    };
+    let user_data = UserData::new(new_data);
+    store.insert_user_data(user_data.clone());
+    info!("Mock user data created: {}", user_data.id);
+    Json(ApiResponse::success(user_data))
+}
+
+#[get("/api/user-data")]
+pub async fn get_user_data(store: &State<MockStore>) -> Json<ApiResponse<PagedResponse<UserData>>> {
+    Json(ApiResponse::success(PagedResponse::all(store.all_user_data())))
+}
+
+#[get("/api/user-data/page?<cursor>&<limit>")]
+pub async fn get_user_data_page(
+    store: &State<MockStore>,
+    cursor: Option<&str>,
+    limit: Option<i64>,
+) -> Json<ApiResponse<PagedResponse<UserData>>> {
+    let page = match PageRequest::parse(cursor, limit, 20, MAX_USER_DATA_PAGE_SIZE) {
+        Ok(page) => page,
+        Err(msg) => return Json(ApiResponse::error(&msg)),
+    };
+
+    let total = store.all_user_data().len() as i64;
+    let rows = Page::from_rows(store.user_data_page(&page), &page, |data| Cursor::new(data.created_at, data.id));
+    Json(ApiResponse::success(PagedResponse::from_page(rows, &page).with_total(total)))
+}
@@ -3,4 +3,35 @@ pub mod user_data;
 pub mod auth;
 pub mod cache;
 pub mod cors;
-pub mod metrics;
\ No newline at end of file
+pub mod metrics;
+pub mod remote_config;
+pub mod notification;
+pub mod admin;
+pub mod payment;
+pub mod order;
+pub mod webhook;
+pub mod profile;
+pub mod capabilities;
+pub mod maintenance;
+pub mod announcement;
+pub mod push;
+pub mod settings;
+pub mod drafts;
+pub mod access;
+pub mod limits;
+pub mod email_change;
+pub mod account_recovery;
+pub mod qr_login;
+pub mod reauth;
+pub mod upload;
+pub mod user_data_reply;
+pub mod history;
+pub mod impersonation;
+pub mod batch;
+pub mod graphql;
+pub mod mock_auth;
+pub mod mock_user_data;
+pub mod consent;
+pub mod identity;
+pub mod avatar;
+pub mod storage;
@@ -0,0 +1,64 @@
+use rocket::{State, serde::json::Json, get, post};
+use tracing::error;
+use uuid::Uuid;
+
+use crate::auth::AuthenticatedUser;
+use crate::database::{
+    DbPool,
+    notification::{count_notifications, list_notifications, mark_notification_read},
+};
+use crate::models::{notification::Notification, response::{ApiResponse, PagedResponse}};
+use crate::utils::pagination::{Cursor, Page, PageRequest};
+
+/// 单次查询的通知数量上限
+const MAX_NOTIFICATIONS: i64 = 100;
+
+/// 获取当前用户的通知（游标分页）
+#[get("/api/notifications?<cursor>&<limit>")]
+pub async fn get_notifications(
+    pool: &State<DbPool>,
+    auth_user: AuthenticatedUser,
+    cursor: Option<&str>,
+    limit: Option<i64>,
+) -> Json<ApiResponse<PagedResponse<Notification>>> {
+    let page = match PageRequest::parse(cursor, limit, 20, MAX_NOTIFICATIONS) {
+        Ok(page) => page,
+        Err(msg) => return Json(ApiResponse::error(&msg)),
+    };
+
+    let result = match list_notifications(pool, auth_user.user.id, &page).await {
+        Ok(rows) => count_notifications(pool, auth_user.user.id).await.map(|total| (rows, total)),
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok((rows, total)) => {
+            let rows = Page::from_rows(rows, &page, |n| Cursor::new(n.created_at, n.id));
+            Json(ApiResponse::success(PagedResponse::from_page(rows, &page).with_total(total)))
+        }
+        Err(e) => {
+            error!("Failed to list notifications: {}", e);
+            Json(ApiResponse::error("获取通知失败"))
+        }
+    }
+}
+
+/// 将通知标记为已读
+#[post("/api/notifications/<notification_id>/read")]
+pub async fn read_notification(
+    pool: &State<DbPool>,
+    auth_user: AuthenticatedUser,
+    notification_id: &str,
+) -> Json<ApiResponse<()>> {
+    let Ok(notification_id) = Uuid::parse_str(notification_id) else {
+        return Json(ApiResponse::error("无效的通知ID"));
+    };
+
+    match mark_notification_read(pool, auth_user.user.id, notification_id).await {
+        Ok(true) => Json(ApiResponse::ok()),
+        Ok(false) => Json(ApiResponse::error("通知不存在")),
+        Err(e) => {
+            error!("Failed to mark notification as read: {}", e);
+            Json(ApiResponse::error("操作失败"))
+        }
+    }
+}
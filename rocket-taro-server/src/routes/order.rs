@@ -0,0 +1,194 @@
+use rocket::{State, serde::json::Json, get, post};
+use tracing::error;
+
+use crate::models::{
+    response::ApiResponse,
+    order::{CreateOrderRequest, Order, OrderStatus},
+    payment::PaymentOrderResult,
+    route_command::RouteCommand,
+};
+use crate::database::DbPool;
+use crate::cache::{RedisPool, idempotency::{IdempotencyStore, IdempotencyOutcome}};
+use crate::auth::{AuthenticatedUser, RealNameVerified, AdultVerified, RequestInfo, IdempotencyKey};
+use crate::config::{RouteConfig, Platform, OrderConfig, RouteTraceConfig};
+use crate::payments::WechatPayClient;
+use crate::utils::pagination::{Page, PageRequest};
+use crate::use_cases::{
+    UseCaseError,
+    order_use_case::OrderUseCase,
+    route_command_generator::RouteCommandGenerator,
+    route_execution_use_case::RouteExecutionUseCase,
+};
+
+/// 单次查询的订单数量上限
+const MAX_ORDERS: i64 = 100;
+
+fn detect_platform(request_info: &RequestInfo) -> Platform {
+    request_info.user_agent.as_deref()
+        .map(Platform::from_user_agent)
+        .unwrap_or_default()
+}
+
+/// 创建订单，返回跳转订单详情的路由指令；需要完成实名认证
+#[post("/api/orders", data = "<order_req>")]
+#[allow(clippy::too_many_arguments)]
+pub async fn create_order(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    route_config: &State<RouteConfig>,
+    order_config: &State<OrderConfig>,
+    route_trace: &State<RouteTraceConfig>,
+    verified: RealNameVerified,
+    order_req: Json<CreateOrderRequest>,
+    request_info: RequestInfo,
+    idempotency_key: IdempotencyKey,
+) -> Json<ApiResponse<Order>> {
+    let RealNameVerified(auth_user) = verified;
+    let order_req = order_req.into_inner();
+
+    // 同一个 Idempotency-Key 的重试返回同一个订单
+    let idempotency = idempotency_key.0.map(|key| {
+        IdempotencyStore::new(redis.inner().clone(), &format!("order.create:{}", auth_user.user.id), &key, &order_req)
+    });
+    if let Some(store) = &idempotency {
+        match store.begin::<Order>().await {
+            IdempotencyOutcome::Proceed => {}
+            outcome => return Json(outcome.into_rejection()),
+        }
+    }
+
+    let platform = detect_platform(&request_info);
+    let use_case = OrderUseCase::new(pool.inner().clone(), order_config.inner().clone());
+    let response = match use_case.execute_create_order(&auth_user.user, &order_req, request_info.ip_address).await {
+        Ok(order) => {
+            let (route_command, decisions) = RouteCommandGenerator::traced(|trace| {
+                RouteCommandGenerator::generate_order_created_route_command(&order, route_config, platform, trace)
+            });
+            RouteExecutionUseCase::new(pool.inner().clone())
+                .with_trace(route_trace.inner().clone())
+                .track(ApiResponse::success_with_command(order, route_command), "order_created", platform, Some(auth_user.user.id), &decisions)
+                .await
+        }
+        Err(UseCaseError::ValidationError(msg)) => {
+            ApiResponse::error_with_command(&msg, RouteCommand::alert("无法下单", &msg))
+        }
+        Err(e) => {
+            error!("Order creation failed: {}", e);
+            ApiResponse::error_with_command("下单失败", RouteCommand::alert("下单失败", "创建订单失败，请稍后重试"))
+        }
+    };
+
+    if let Some(store) = &idempotency {
+        store.finish(&response).await;
+    }
+    Json(response)
+}
+
+/// 获取当前用户的订单（游标分页），status 支持逗号分隔多个状态
+#[get("/api/orders?<status>&<cursor>&<limit>")]
+pub async fn list_orders(
+    pool: &State<DbPool>,
+    order_config: &State<OrderConfig>,
+    auth_user: AuthenticatedUser,
+    status: Option<&str>,
+    cursor: Option<&str>,
+    limit: Option<i64>,
+) -> Json<ApiResponse<Page<Order>>> {
+    let statuses = match OrderStatus::parse_filter(status) {
+        Ok(statuses) => statuses,
+        Err(msg) => return Json(ApiResponse::error(&msg)),
+    };
+    let page = match PageRequest::parse(cursor, limit, 20, MAX_ORDERS) {
+        Ok(page) => page,
+        Err(msg) => return Json(ApiResponse::error(&msg)),
+    };
+
+    let use_case = OrderUseCase::new(pool.inner().clone(), order_config.inner().clone());
+    match use_case.list_orders(&auth_user.user, &statuses, &page).await {
+        Ok(orders) => Json(ApiResponse::success(orders)),
+        Err(e) => {
+            error!("Failed to list orders: {}", e);
+            Json(ApiResponse::error("获取订单失败"))
+        }
+    }
+}
+
+/// 查询当前用户的订单详情
+#[get("/api/orders/<order_no>")]
+pub async fn get_order(
+    pool: &State<DbPool>,
+    order_config: &State<OrderConfig>,
+    auth_user: AuthenticatedUser,
+    order_no: &str,
+) -> Json<ApiResponse<Order>> {
+    let use_case = OrderUseCase::new(pool.inner().clone(), order_config.inner().clone());
+    match use_case.get_order(&auth_user.user, order_no).await {
+        Ok(order) => Json(ApiResponse::success(order)),
+        Err(UseCaseError::ValidationError(msg)) => Json(ApiResponse::error(&msg)),
+        Err(e) => {
+            error!("Failed to load order {}: {}", order_no, e);
+            Json(ApiResponse::error("查询订单失败"))
+        }
+    }
+}
+
+/// 取消待支付订单
+#[post("/api/orders/<order_no>/cancel")]
+pub async fn cancel_order(
+    pool: &State<DbPool>,
+    route_config: &State<RouteConfig>,
+    order_config: &State<OrderConfig>,
+    auth_user: AuthenticatedUser,
+    request_info: RequestInfo,
+    order_no: &str,
+) -> Json<ApiResponse<Order>> {
+    let platform = detect_platform(&request_info);
+    let use_case = OrderUseCase::new(pool.inner().clone(), order_config.inner().clone());
+    match use_case.execute_cancel_order(&auth_user.user, order_no, request_info.ip_address).await {
+        Ok(order) => {
+            let route_command = RouteCommandGenerator::generate_order_cancelled_route_command(&order, route_config, platform);
+            Json(ApiResponse::success_with_command(order, route_command))
+        }
+        Err(UseCaseError::ValidationError(msg)) | Err(UseCaseError::BusinessLogicError(msg)) => {
+            Json(ApiResponse::error_with_command(&msg, RouteCommand::toast(&msg)))
+        }
+        Err(e) => {
+            error!("Failed to cancel order {}: {}", order_no, e);
+            Json(ApiResponse::error_with_command("取消订单失败", RouteCommand::toast("取消订单失败，请稍后重试")))
+        }
+    }
+}
+
+/// 为待支付订单发起微信支付；需要完成实名认证且达到年龄限制
+#[post("/api/orders/<order_no>/pay")]
+#[allow(clippy::too_many_arguments)]
+pub async fn pay_order(
+    pool: &State<DbPool>,
+    route_config: &State<RouteConfig>,
+    order_config: &State<OrderConfig>,
+    wechat_pay: &State<WechatPayClient>,
+    route_trace: &State<RouteTraceConfig>,
+    verified: AdultVerified,
+    request_info: RequestInfo,
+    order_no: &str,
+) -> Json<ApiResponse<PaymentOrderResult>> {
+    let AdultVerified(auth_user) = verified;
+    let platform = detect_platform(&request_info);
+    let use_case = OrderUseCase::new(pool.inner().clone(), order_config.inner().clone());
+    match use_case.execute_pay_order(&auth_user.user, order_no, wechat_pay.inner(), request_info.ip_address).await {
+        Ok(result) => {
+            let (route_command, decisions) = RouteCommandGenerator::traced(|trace| {
+                RouteCommandGenerator::generate_order_payment_route_command(order_no, &result, route_config, platform, trace)
+            });
+            let tracking = RouteExecutionUseCase::new(pool.inner().clone()).with_trace(route_trace.inner().clone());
+            Json(tracking.track(ApiResponse::success_with_command(result, route_command), "order_payment", platform, Some(auth_user.user.id), &decisions).await)
+        }
+        Err(UseCaseError::ValidationError(msg)) | Err(UseCaseError::BusinessLogicError(msg)) => {
+            Json(ApiResponse::error_with_command(&msg, RouteCommand::alert("无法支付", &msg)))
+        }
+        Err(e) => {
+            error!("Failed to pay order {}: {}", order_no, e);
+            Json(ApiResponse::error_with_command("下单失败", RouteCommand::alert("下单失败", "支付下单失败，请稍后重试")))
+        }
+    }
+}
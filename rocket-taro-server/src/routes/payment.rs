@@ -0,0 +1,146 @@
+use rocket::{State, serde::json::Json, get, post, Request};
+use rocket::http::Status;
+use rocket::request::{self, FromRequest};
+use serde_json::{json, Value};
+use tracing::{warn, error};
+
+use crate::models::{
+    response::ApiResponse,
+    payment::{CreatePaymentRequest, Payment, PaymentOrderResult},
+    route_command::RouteCommand,
+};
+use crate::database::DbPool;
+use crate::cache::{RedisPool, idempotency::{IdempotencyStore, IdempotencyOutcome}};
+use crate::auth::{AuthenticatedUser, AdultVerified, RequestInfo, IdempotencyKey};
+use crate::config::{RouteConfig, Platform, RouteTraceConfig};
+use crate::payments::{WechatPayClient, WechatPayNotifyHeaders};
+use crate::use_cases::{
+    UseCaseError,
+    payment_use_case::PaymentUseCase,
+    route_command_generator::RouteCommandGenerator,
+    route_execution_use_case::RouteExecutionUseCase,
+};
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for WechatPayNotifyHeaders {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let header = |name: &str| req.headers().get_one(name).map(|value| value.to_string());
+
+        match (
+            header("Wechatpay-Timestamp"),
+            header("Wechatpay-Nonce"),
+            header("Wechatpay-Signature"),
+            header("Wechatpay-Serial"),
+        ) {
+            (Some(timestamp), Some(nonce), Some(signature), Some(serial)) => {
+                request::Outcome::Success(WechatPayNotifyHeaders { timestamp, nonce, signature, serial })
+            }
+            _ => {
+                warn!("WeChat Pay notification is missing signature headers");
+                request::Outcome::Error((Status::BadRequest, ()))
+            }
+        }
+    }
+}
+
+/// 创建支付单，返回调起微信支付的路由指令；需要完成实名认证且达到年龄限制
+#[post("/api/payments", data = "<payment_req>")]
+#[allow(clippy::too_many_arguments)]
+pub async fn create_payment(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    route_config: &State<RouteConfig>,
+    wechat_pay: &State<WechatPayClient>,
+    route_trace: &State<RouteTraceConfig>,
+    verified: AdultVerified,
+    payment_req: Json<CreatePaymentRequest>,
+    request_info: RequestInfo,
+    idempotency_key: IdempotencyKey,
+) -> Json<ApiResponse<PaymentOrderResult>> {
+    let AdultVerified(auth_user) = verified;
+    let payment_req = payment_req.into_inner();
+
+    // 同一个 Idempotency-Key 的重试返回同一笔支付单
+    let idempotency = idempotency_key.0.map(|key| {
+        IdempotencyStore::new(redis.inner().clone(), &format!("payment.create:{}", auth_user.user.id), &key, &payment_req)
+    });
+    if let Some(store) = &idempotency {
+        match store.begin::<PaymentOrderResult>().await {
+            IdempotencyOutcome::Proceed => {}
+            outcome => return Json(outcome.into_rejection()),
+        }
+    }
+
+    let platform = request_info.user_agent.as_deref()
+        .map(Platform::from_user_agent)
+        .unwrap_or_default();
+
+    let use_case = PaymentUseCase::new(pool.inner().clone(), wechat_pay.inner());
+    let response = match use_case.execute_create_payment(&auth_user.user, &payment_req, request_info.ip_address).await {
+        Ok(result) => {
+            let (route_command, decisions) = RouteCommandGenerator::traced(|trace| {
+                RouteCommandGenerator::generate_payment_route_command(&result, route_config, platform, trace)
+            });
+            RouteExecutionUseCase::new(pool.inner().clone())
+                .with_trace(route_trace.inner().clone())
+                .track(ApiResponse::success_with_command(result, route_command), "payment", platform, Some(auth_user.user.id), &decisions)
+                .await
+        }
+        Err(UseCaseError::ValidationError(msg)) | Err(UseCaseError::BusinessLogicError(msg)) => {
+            ApiResponse::error_with_command(&msg, RouteCommand::alert("无法支付", &msg))
+        }
+        Err(e) => {
+            error!("Payment creation failed: {}", e);
+            ApiResponse::error_with_command("下单失败", RouteCommand::alert("下单失败", "支付下单失败，请稍后重试"))
+        }
+    };
+
+    if let Some(store) = &idempotency {
+        store.finish(&response).await;
+    }
+    Json(response)
+}
+
+/// 查询当前用户的支付单状态
+#[get("/api/payments/<out_trade_no>")]
+pub async fn get_payment(
+    pool: &State<DbPool>,
+    wechat_pay: &State<WechatPayClient>,
+    auth_user: AuthenticatedUser,
+    out_trade_no: &str,
+) -> Json<ApiResponse<Payment>> {
+    let use_case = PaymentUseCase::new(pool.inner().clone(), wechat_pay.inner());
+    match use_case.get_payment(&auth_user.user, out_trade_no).await {
+        Ok(payment) => Json(ApiResponse::success(payment)),
+        Err(UseCaseError::ValidationError(msg)) => Json(ApiResponse::error(&msg)),
+        Err(e) => {
+            error!("Failed to load payment {}: {}", out_trade_no, e);
+            Json(ApiResponse::error("查询支付单失败"))
+        }
+    }
+}
+
+/// 微信支付结果回调，应答格式遵循微信支付 v3 约定
+#[post("/api/payments/wechat/notify", data = "<body>")]
+pub async fn wechat_pay_notify(
+    pool: &State<DbPool>,
+    wechat_pay: &State<WechatPayClient>,
+    headers: WechatPayNotifyHeaders,
+    body: String,
+) -> (Status, Json<Value>) {
+    let use_case = PaymentUseCase::new(pool.inner().clone(), wechat_pay.inner());
+    match use_case.execute_payment_notification(&headers, &body).await {
+        Ok(()) => (Status::Ok, Json(json!({ "code": "SUCCESS", "message": "成功" }))),
+        Err(UseCaseError::AuthenticationError(msg)) => {
+            warn!("Rejected WeChat Pay notification: {}", msg);
+            (Status::Unauthorized, Json(json!({ "code": "FAIL", "message": msg })))
+        }
+        Err(e) => {
+            // 返回失败后微信支付会按策略重试通知
+            error!("WeChat Pay notification handling failed: {}", e);
+            (Status::InternalServerError, Json(json!({ "code": "FAIL", "message": "处理失败" })))
+        }
+    }
+}
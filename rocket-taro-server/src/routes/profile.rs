@@ -0,0 +1,81 @@
+use rocket::{State, serde::json::Json, get, patch};
+use rocket::http::Status;
+use std::sync::Arc;
+use tracing::error;
+
+use crate::models::{
+    response::ApiResponse,
+    profile::{ProfileCompletion, ProfileStepRequest},
+    route_command::RouteCommand,
+};
+use crate::database::DbPool;
+use crate::cache::RedisPool;
+use crate::auth::{AuthenticatedUser, ConsentedUser, IfMatch, RequestInfo};
+use crate::config::{RouteConfig, Platform, ModerationConfig, RouteTraceConfig};
+use crate::moderation::ContentModerator;
+use crate::use_cases::{
+    UseCaseError,
+    moderation_use_case::ModerationUseCase,
+    profile_use_case::ProfileUseCase,
+    route_command_generator::RouteCommandGenerator,
+    route_execution_use_case::RouteExecutionUseCase,
+};
+
+/// 获取当前用户的资料完善状态（步骤条初始化时调用）
+#[get("/api/profile/completion")]
+pub async fn get_profile_completion(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    auth_user: AuthenticatedUser,
+) -> Json<ApiResponse<ProfileCompletion>> {
+    let use_case = ProfileUseCase::new(pool.inner().clone(), redis.inner().clone());
+    Json(ApiResponse::success(use_case.completion(&auth_user.user)))
+}
+
+/// 提交资料完善步骤条中的一步，返回下一步或完成后的路由指令；版本号不一致时返回 409
+#[patch("/api/profile", data = "<step_req>")]
+#[allow(clippy::too_many_arguments)]
+pub async fn update_profile_step(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    route_config: &State<RouteConfig>,
+    moderator: &State<Arc<dyn ContentModerator>>,
+    moderation_config: &State<ModerationConfig>,
+    route_trace: &State<RouteTraceConfig>,
+    consented: ConsentedUser,
+    request_info: RequestInfo,
+    if_match: IfMatch,
+    step_req: Json<ProfileStepRequest>,
+) -> (Status, Json<ApiResponse<ProfileCompletion>>) {
+    let ConsentedUser(auth_user) = consented;
+    let platform = request_info.user_agent.as_deref()
+        .map(Platform::from_user_agent)
+        .unwrap_or_default();
+
+    let moderation = ModerationUseCase::new(pool.inner().clone(), redis.inner().clone(), moderator.inner().clone(), moderation_config.inner().clone());
+    let use_case = ProfileUseCase::new(pool.inner().clone(), redis.inner().clone()).with_moderation(moderation);
+    match use_case.execute_update_step(&auth_user.user, step_req.into_inner(), if_match.0).await {
+        Ok(result) => {
+            let (route_command, decisions) = RouteCommandGenerator::traced(|trace| {
+                RouteCommandGenerator::generate_profile_step_route_command(&result, route_config, platform, trace)
+            });
+            let tracking = RouteExecutionUseCase::new(pool.inner().clone()).with_trace(route_trace.inner().clone());
+            (Status::Ok, Json(tracking.track(ApiResponse::success_with_command(result.completion, route_command), "profile_step", platform, Some(auth_user.user.id), &decisions).await))
+        }
+        Err(UseCaseError::ConflictError(msg)) => {
+            let command = RouteCommandGenerator::generate_version_conflict_route_command("profile", None);
+            (Status::Conflict, Json(ApiResponse::error_with_command(&msg, command)))
+        }
+        Err(UseCaseError::ContentRejected(rejection)) => {
+            let command = RouteCommandGenerator::generate_content_rejected_route_command(&rejection);
+            (Status::Ok, Json(ApiResponse::error_with_command(&rejection.message(), command)))
+        }
+        Err(UseCaseError::ValidationError(msg)) | Err(UseCaseError::BusinessLogicError(msg)) => {
+            (Status::Ok, Json(ApiResponse::error_with_command(&msg, RouteCommand::toast(&msg))))
+        }
+        Err(e) => {
+            error!("Profile step update failed: {}", e);
+            (Status::Ok, Json(ApiResponse::error_with_command("保存失败", RouteCommand::toast("保存失败，请稍后重试"))))
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use rocket::{State, Shutdown, get};
+use rocket::response::stream::{Event, EventStream};
+use rocket::tokio::select;
+use rocket::tokio::sync::broadcast::error::RecvError;
+use tracing::{info, warn};
+
+use crate::auth::AuthenticatedUser;
+use crate::push::PushHub;
+
+/// 订阅当前用户的推送（Server-Sent Events），事件数据中包含要执行的路由指令
+#[get("/api/push/stream")]
+pub fn push_stream(hub: &State<PushHub>, auth_user: AuthenticatedUser, mut shutdown: Shutdown) -> EventStream![] {
+    let user_id = auth_user.user.id;
+    let session_id = auth_user.session.id;
+    let mut receiver = hub.subscribe(user_id);
+    info!(user_id = %user_id, "Push stream opened");
+
+    EventStream! {
+        loop {
+            let message = select! {
+                message = receiver.recv() => match message {
+                    Ok(message) => message,
+                    Err(RecvError::Closed) => break,
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!(user_id = %user_id, skipped = %skipped, "Push stream lagged, messages dropped");
+                        continue;
+                    }
+                },
+                _ = &mut shutdown => break,
+            };
+            // 指定会话的消息只发给该会话的连接
+            if !message.is_for_session(session_id) {
+                continue;
+            }
+            yield Event::json(&message).event(message.event.clone());
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use rocket::{State, Shutdown, serde::json::Json, get, post};
+use rocket::http::Status;
+use rocket::response::stream::{Event, EventStream};
+use rocket::tokio::select;
+use rocket::tokio::sync::broadcast::error::RecvError;
+use tracing::{info, warn, error};
+use uuid::Uuid;
+
+use crate::models::{
+    qr_login::{ConfirmQrLoginRequest, QrLoginChallenge, QrLoginPollRequest, QrLoginState, QrLoginStatus},
+    response::ApiResponse,
+    route_command::RouteCommand,
+};
+use crate::database::DbPool;
+use crate::cache::RedisPool;
+use crate::auth::{OwnerSession, RequestInfo, SessionCookies};
+use crate::config::{QrLoginConfig, RouteConfig, Platform};
+use crate::events::EventBus;
+use crate::push::PushHub;
+use crate::use_cases::{
+    UseCaseError, UseCaseResult,
+    qr_login_use_case::QrLoginUseCase,
+    route_command_generator::RouteCommandGenerator,
+};
+
+fn use_case(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    events: &State<EventBus>,
+    hub: &State<PushHub>,
+    config: &State<QrLoginConfig>,
+    route_config: &State<RouteConfig>,
+) -> QrLoginUseCase {
+    QrLoginUseCase::new(
+        pool.inner().clone(),
+        redis.inner().clone(),
+        events.inner().clone(),
+        hub.inner().clone(),
+        config.inner().clone(),
+        route_config.inner().clone(),
+    )
+}
+
+fn to_response<T>(result: UseCaseResult<T>, failure: &str, command: impl FnOnce(&T) -> RouteCommand) -> Json<ApiResponse<T>> {
+    match result {
+        Ok(value) => {
+            let command = command(&value);
+            Json(ApiResponse::success_with_command(value, command))
+        }
+        Err(e) => error_response(e, failure),
+    }
+}
+
+fn error_response<T>(error: UseCaseError, failure: &str) -> Json<ApiResponse<T>> {
+    match error {
+        UseCaseError::ValidationError(msg) | UseCaseError::BusinessLogicError(msg) => {
+            Json(ApiResponse::error_with_command(&msg, RouteCommand::toast(&msg)))
+        }
+        e => {
+            error!("{}: {}", failure, e);
+            Json(ApiResponse::error_with_command(failure, RouteCommand::toast(failure)))
+        }
+    }
+}
+
+fn parse_id(id: &str) -> Option<Uuid> {
+    Uuid::parse_str(id).ok()
+}
+
+/// 网页端获取扫码登录二维码（无需登录）
+#[post("/api/auth/qr-login")]
+pub async fn create_qr_login(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    events: &State<EventBus>,
+    hub: &State<PushHub>,
+    config: &State<QrLoginConfig>,
+    route_config: &State<RouteConfig>,
+    request_info: RequestInfo,
+) -> Json<ApiResponse<QrLoginChallenge>> {
+    match use_case(pool, redis, events, hub, config, route_config).create(request_info.user_agent, request_info.ip_address).await {
+        Ok(challenge) => Json(ApiResponse::success(challenge)),
+        Err(e) => {
+            error!("Failed to create QR login: {}", e);
+            Json(ApiResponse::error("获取登录二维码失败"))
+        }
+    }
+}
+
+/// 网页端轮询扫码状态，确认后首次轮询返回会话并设置 Cookie
+#[post("/api/auth/qr-login/<login_id>/poll", data = "<request>")]
+#[allow(clippy::too_many_arguments)]
+pub async fn poll_qr_login(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    events: &State<EventBus>,
+    hub: &State<PushHub>,
+    config: &State<QrLoginConfig>,
+    route_config: &State<RouteConfig>,
+    cookies: SessionCookies<'_>,
+    login_id: &str,
+    request: Json<QrLoginPollRequest>,
+) -> Json<ApiResponse<QrLoginState>> {
+    let Some(login_id) = parse_id(login_id) else {
+        return Json(ApiResponse::error("无效的二维码"));
+    };
+
+    match use_case(pool, redis, events, hub, config, route_config).poll(login_id, &request.poll_token).await {
+        Ok(result) => {
+            let command = match (&result.login, result.state.status) {
+                (Some(login), _) => {
+                    cookies.set(&login.session_token);
+                    Some(RouteCommandGenerator::generate_qr_login_route_command(login, route_config))
+                }
+                (None, QrLoginStatus::Rejected) => Some(RouteCommand::alert("登录已取消", "已在手机上拒绝本次登录")),
+                (None, _) => None,
+            };
+            match command {
+                Some(command) => Json(ApiResponse::success_with_command(result.state, command)),
+                None => Json(ApiResponse::success(result.state)),
+            }
+        }
+        Err(e) => error_response(e, "查询扫码状态失败"),
+    }
+}
+
+/// 网页端订阅扫码状态推送（Server-Sent Events），确认后推送中包含会话
+#[get("/api/auth/qr-login/<login_id>/stream?<poll_token>")]
+#[allow(clippy::too_many_arguments)]
+pub async fn qr_login_stream(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    events: &State<EventBus>,
+    hub: &State<PushHub>,
+    config: &State<QrLoginConfig>,
+    route_config: &State<RouteConfig>,
+    login_id: &str,
+    poll_token: &str,
+    mut shutdown: Shutdown,
+) -> Result<EventStream![], Status> {
+    let login_id = parse_id(login_id).ok_or(Status::NotFound)?;
+    let mut receiver = match use_case(pool, redis, events, hub, config, route_config).subscribe(login_id, poll_token).await {
+        Ok(receiver) => receiver,
+        Err(UseCaseError::ValidationError(_)) => return Err(Status::NotFound),
+        Err(e) => {
+            error!(login_id = %login_id, error = %e, "Failed to subscribe QR login stream");
+            return Err(Status::InternalServerError);
+        }
+    };
+    info!(login_id = %login_id, "QR login stream opened");
+
+    Ok(EventStream! {
+        loop {
+            let message = select! {
+                message = receiver.recv() => match message {
+                    Ok(message) => message,
+                    Err(RecvError::Closed) => break,
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!(login_id = %login_id, skipped = %skipped, "QR login stream lagged, messages dropped");
+                        continue;
+                    }
+                },
+                _ = &mut shutdown => break,
+            };
+            yield Event::json(&message).event(message.event.clone());
+        }
+    })
+}
+
+/// 管理员用小程序扫码，返回进入确认页的指令
+#[post("/api/auth/qr-login/<login_id>/scan")]
+#[allow(clippy::too_many_arguments)]
+pub async fn scan_qr_login(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    events: &State<EventBus>,
+    hub: &State<PushHub>,
+    config: &State<QrLoginConfig>,
+    route_config: &State<RouteConfig>,
+    owner_session: OwnerSession,
+    request_info: RequestInfo,
+    login_id: &str,
+) -> Json<ApiResponse<()>> {
+    let OwnerSession(auth_user) = owner_session;
+    let Some(login_id) = parse_id(login_id) else {
+        return Json(ApiResponse::error_with_command("无效的二维码", RouteCommand::toast("无效的二维码")));
+    };
+    let platform = request_info.user_agent.as_deref()
+        .map(Platform::from_user_agent)
+        .unwrap_or_default();
+
+    let result = use_case(pool, redis, events, hub, config, route_config).scan(&auth_user.user, login_id).await;
+    to_response(result, "扫码登录失败", |_| {
+        RouteCommandGenerator::generate_qr_login_scanned_route_command(login_id, route_config, platform)
+    })
+}
+
+/// 管理员在小程序中确认或拒绝登录
+#[post("/api/auth/qr-login/<login_id>/confirm", data = "<request>")]
+#[allow(clippy::too_many_arguments)]
+pub async fn confirm_qr_login(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    events: &State<EventBus>,
+    hub: &State<PushHub>,
+    config: &State<QrLoginConfig>,
+    route_config: &State<RouteConfig>,
+    owner_session: OwnerSession,
+    login_id: &str,
+    request: Json<ConfirmQrLoginRequest>,
+) -> Json<ApiResponse<()>> {
+    let OwnerSession(auth_user) = owner_session;
+    let Some(login_id) = parse_id(login_id) else {
+        return Json(ApiResponse::error_with_command("无效的二维码", RouteCommand::toast("无效的二维码")));
+    };
+
+    let approve = request.approve;
+    let result = use_case(pool, redis, events, hub, config, route_config).confirm(&auth_user.user, login_id, approve).await;
+    to_response(result, "确认登录失败", |_| {
+        RouteCommand::toast(if approve { "已确认登录" } else { "已取消登录" })
+    })
+}
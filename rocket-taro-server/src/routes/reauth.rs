@@ -0,0 +1,63 @@
+use rocket::{Request, State, catch, serde::json::Json, post};
+use tracing::error;
+
+use crate::models::{
+    reauth::{ReauthRequest, ReauthStatus},
+    response::ApiResponse,
+    route_command::RouteCommand,
+};
+use crate::database::DbPool;
+use crate::cache::RedisPool;
+use crate::auth::{SessionUser, RequestInfo, PasswordHasher};
+use crate::config::{RecentAuthConfig, RouteConfig, Platform};
+use crate::use_cases::{
+    UseCaseError,
+    reauth_use_case::ReauthUseCase,
+    route_command_generator::RouteCommandGenerator,
+};
+
+/// 敏感操作前重新验证密码，有效期内 RecentAuth 守卫放行
+#[post("/api/auth/reauth", data = "<request>")]
+#[allow(clippy::too_many_arguments)]
+pub async fn reauthenticate(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    password_hasher: &State<PasswordHasher>,
+    config: &State<RecentAuthConfig>,
+    session_user: SessionUser,
+    request_info: RequestInfo,
+    request: Json<ReauthRequest>,
+) -> Json<ApiResponse<ReauthStatus>> {
+    let SessionUser(auth_user) = session_user;
+    let use_case = ReauthUseCase::new(
+        pool.inner().clone(),
+        redis.inner().clone(),
+        password_hasher.inner().clone(),
+        config.inner().clone(),
+    );
+
+    match use_case.confirm(&auth_user, request.into_inner(), request_info.ip_address).await {
+        Ok(status) => Json(ApiResponse::with_toast(status, "验证成功")),
+        Err(UseCaseError::BusinessLogicError(msg)) | Err(UseCaseError::AuthenticationError(msg)) => {
+            Json(ApiResponse::error_with_command(&msg, RouteCommand::toast(&msg)))
+        }
+        Err(e) => {
+            error!("重新验证身份失败: {}", e);
+            Json(ApiResponse::error_with_command("验证失败", RouteCommand::toast("验证失败")))
+        }
+    }
+}
+
+/// RecentAuth 守卫拒绝时返回 428 和跳转重新验证页的路由指令
+#[catch(428)]
+pub fn reauth_required_catcher(request: &Request<'_>) -> Json<ApiResponse<()>> {
+    let platform = request.headers().get_one("User-Agent")
+        .map(Platform::from_user_agent)
+        .unwrap_or_default();
+    let message = "请重新验证身份后继续操作";
+    let route_command = match request.rocket().state::<RouteConfig>() {
+        Some(route_config) => RouteCommandGenerator::generate_reauth_required_route_command(request.uri().path().as_str(), route_config, platform),
+        None => RouteCommand::toast(message),
+    };
+    Json(ApiResponse::error_with_command(message, route_command))
+}
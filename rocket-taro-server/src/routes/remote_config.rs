@@ -0,0 +1,80 @@
+use rocket::{State, serde::json::Json, get, put, delete};
+use tracing::error;
+
+use crate::models::{
+    response::{ApiResponse, PagedResponse},
+    remote_config::{RemoteConfigEntry, SetRemoteConfigRequest},
+};
+use crate::database::DbPool;
+use crate::cache::RedisPool;
+use crate::auth::guards::AdminUser;
+use crate::config::Platform;
+use crate::use_cases::{UseCaseError, remote_config_use_case::RemoteConfigUseCase};
+
+// 列出所有远程配置项
+#[get("/api/admin/config")]
+pub async fn list_remote_configs(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    _admin: AdminUser,
+) -> Json<ApiResponse<PagedResponse<RemoteConfigEntry>>> {
+    let use_case = RemoteConfigUseCase::new(pool.inner().clone(), redis.inner().clone());
+
+    match use_case.list_entries().await {
+        Ok(entries) => Json(ApiResponse::success(PagedResponse::all(entries))),
+        Err(e) => {
+            error!("Failed to list remote configs: {}", e);
+            Json(ApiResponse::error("获取配置列表失败"))
+        }
+    }
+}
+
+// 写入远程配置项（可指定平台覆盖）
+#[put("/api/admin/config/<config_key>", data = "<req>")]
+pub async fn set_remote_config(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    admin: AdminUser,
+    config_key: &str,
+    req: Json<SetRemoteConfigRequest>,
+) -> Json<ApiResponse<RemoteConfigEntry>> {
+    let use_case = RemoteConfigUseCase::new(pool.inner().clone(), redis.inner().clone());
+    let req = req.into_inner();
+
+    match use_case.set_value(config_key, req.platform, &req.value, req.description.as_deref(), admin.0.user.id).await {
+        Ok(entry) => Json(ApiResponse::success(entry)),
+        Err(UseCaseError::ValidationError(msg)) => Json(ApiResponse::error(&msg)),
+        Err(e) => {
+            error!("Failed to write remote config {}: {}", config_key, e);
+            Json(ApiResponse::error("配置保存失败"))
+        }
+    }
+}
+
+// 删除远程配置项
+#[delete("/api/admin/config/<config_key>?<platform>")]
+pub async fn delete_remote_config(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    admin: AdminUser,
+    config_key: &str,
+    platform: Option<&str>,
+) -> Json<ApiResponse<RemoteConfigEntry>> {
+    let platform = match platform {
+        Some(p) => match Platform::from_str(p) {
+            Some(platform) => Some(platform),
+            None => return Json(ApiResponse::error(&format!("未知平台: {}", p))),
+        },
+        None => None,
+    };
+
+    let use_case = RemoteConfigUseCase::new(pool.inner().clone(), redis.inner().clone());
+    match use_case.delete_value(config_key, platform, admin.0.user.id).await {
+        Ok(Some(entry)) => Json(ApiResponse::success(entry)),
+        Ok(None) => Json(ApiResponse::error("配置项不存在")),
+        Err(e) => {
+            error!("Failed to delete remote config {}: {}", config_key, e);
+            Json(ApiResponse::error("配置删除失败"))
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use rocket::{State, serde::json::Json, get, patch};
+use tracing::error;
+
+use crate::auth::AuthenticatedUser;
+use crate::cache::RedisPool;
+use crate::database::DbPool;
+use crate::models::{response::ApiResponse, route_command::RouteCommand, settings::UserSettings};
+use crate::use_cases::{UseCaseError, settings_use_case::SettingsUseCase};
+
+/// 获取当前用户的偏好设置
+#[get("/api/settings")]
+pub async fn get_settings(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    auth_user: AuthenticatedUser,
+) -> Json<ApiResponse<UserSettings>> {
+    let use_case = SettingsUseCase::new(pool.inner().clone(), redis.inner().clone());
+    match use_case.get(auth_user.user.id).await {
+        Ok(settings) => Json(ApiResponse::success(settings)),
+        Err(e) => {
+            error!("Failed to get user settings: {}", e);
+            Json(ApiResponse::error("获取设置失败"))
+        }
+    }
+}
+
+/// 部分更新当前用户的偏好设置，只接受已知的设置项
+#[patch("/api/settings", data = "<patch>")]
+pub async fn update_settings(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    auth_user: AuthenticatedUser,
+    patch: Json<serde_json::Value>,
+) -> Json<ApiResponse<UserSettings>> {
+    let use_case = SettingsUseCase::new(pool.inner().clone(), redis.inner().clone());
+    match use_case.execute_update(auth_user.user.id, &patch).await {
+        Ok(settings) => {
+            let route_command = RouteCommand::process_data("settings", serde_json::json!(settings));
+            Json(ApiResponse::success_with_command(settings, route_command))
+        }
+        Err(UseCaseError::ValidationError(msg)) => Json(ApiResponse::error_with_command(&msg, RouteCommand::toast(&msg))),
+        Err(e) => {
+            error!("Failed to update user settings: {}", e);
+            Json(ApiResponse::error("保存设置失败"))
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use rocket::{State, get, Responder};
+use rocket::fs::NamedFile;
+use rocket::http::{Header, Status};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{warn, error};
+
+use crate::database::DbPool;
+use crate::config::StorageConfig;
+use crate::storage::{ObjectStorage, content_disposition};
+use crate::use_cases::storage_use_case::StorageUseCase;
+
+/// 本地存储的文件下载响应
+#[derive(Responder)]
+pub struct StoredFile {
+    file: NamedFile,
+    disposition: Header<'static>,
+    cache_control: Header<'static>,
+    nosniff: Header<'static>,
+}
+
+/// 通过限时签名地址下载本地存储的文件，不需要登录；地址由 /api/auth/my-data/<id>/download 等接口签发
+#[get("/api/storage/<key..>?<expires>&<name>&<signature>")]
+pub async fn download_stored_file(
+    pool: &State<DbPool>,
+    storage: &State<Arc<dyn ObjectStorage>>,
+    config: &State<StorageConfig>,
+    key: PathBuf,
+    expires: i64,
+    name: &str,
+    signature: &str,
+) -> Result<StoredFile, Status> {
+    let key = key.to_str().ok_or(Status::NotFound)?;
+    let use_case = StorageUseCase::new(pool.inner().clone(), storage.inner().clone(), config.inner().clone());
+    let Some(path) = use_case.open_signed(key, name, expires, signature) else {
+        warn!(key = key, "Invalid or expired signed download link");
+        return Err(Status::Forbidden);
+    };
+
+    let file = NamedFile::open(&path).await.map_err(|e| {
+        error!("Stored file missing: {:?}: {}", path, e);
+        Status::NotFound
+    })?;
+    Ok(StoredFile {
+        file,
+        disposition: Header::new("Content-Disposition", content_disposition(name)),
+        cache_control: Header::new("Cache-Control", "private, no-store"),
+        nosniff: Header::new("X-Content-Type-Options", "nosniff"),
+    })
+}
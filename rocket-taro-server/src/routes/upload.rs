@@ -0,0 +1,121 @@
+use rocket::{State, serde::json::Json, get, post, FromForm, Responder};
+use rocket::form::{Form, Errors};
+use rocket::fs::TempFile;
+use rocket::http::{ContentType, Header, Status};
+use std::sync::Arc;
+use tracing::{warn, error};
+
+use crate::models::{
+    response::{ApiResponse, FieldError},
+    upload::Attachment,
+};
+use crate::database::DbPool;
+use crate::cache::RedisPool;
+use crate::auth::OptionalUser;
+use crate::config::{UploadConfig, ModerationConfig};
+use crate::moderation::ContentModerator;
+use crate::use_cases::{
+    UseCaseError,
+    upload_use_case::UploadUseCase,
+    moderation_use_case::ModerationUseCase,
+    route_command_generator::RouteCommandGenerator,
+};
+
+/// multipart 上传表单，文件字段名为 file
+#[derive(FromForm)]
+pub struct UploadForm<'r> {
+    file: TempFile<'r>,
+}
+
+/// 上传文件，返回的 id 在提交用户数据时通过 attachment_ids 引用，未引用的文件过期后自动删除；
+/// 图片未通过内容审核时不保存
+#[post("/api/uploads", data = "<form>")]
+pub async fn upload_file(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    config: &State<UploadConfig>,
+    moderator: &State<Arc<dyn ContentModerator>>,
+    moderation_config: &State<ModerationConfig>,
+    optional_user: OptionalUser,
+    form: Result<Form<UploadForm<'_>>, Errors<'_>>,
+) -> Json<ApiResponse<Attachment>> {
+    let mut form = match form {
+        Ok(form) => form,
+        Err(errors) => {
+            warn!("Invalid upload form: {}", errors);
+            return Json(ApiResponse::validation_error(vec![FieldError::new("file", "请选择要上传的文件，且文件不能过大")]));
+        }
+    };
+
+    let moderation = ModerationUseCase::new(pool.inner().clone(), redis.inner().clone(), moderator.inner().clone(), moderation_config.inner().clone());
+    let use_case = UploadUseCase::new(pool.inner().clone(), config.inner().clone()).with_moderation(moderation);
+    let user_id = optional_user.0.map(|auth_user| auth_user.user.id);
+    match use_case.store(&mut form.file, user_id).await {
+        Ok(attachment) => Json(ApiResponse::success(attachment)),
+        Err(UseCaseError::ValidationError(msg)) => Json(ApiResponse::validation_error(vec![FieldError::new("file", msg)])),
+        Err(UseCaseError::ContentRejected(rejection)) => {
+            let command = RouteCommandGenerator::generate_content_rejected_route_command(&rejection);
+            Json(ApiResponse::error_with_command(&rejection.message(), command))
+        }
+        Err(UseCaseError::BusinessLogicError(msg)) => Json(ApiResponse::error(&msg)),
+        Err(e) => {
+            error!("File upload failed: {}", e);
+            Json(ApiResponse::error("文件上传失败"))
+        }
+    }
+}
+
+/// 上传文件下载响应
+#[derive(Responder)]
+pub struct UploadDownload {
+    file: tokio::fs::File,
+    content_type: ContentType,
+    disposition: Header<'static>,
+    nosniff: Header<'static>,
+}
+
+/// 下载上传的文件，图片直接显示，其他类型作为附件下载
+#[get("/api/uploads/<upload_id>")]
+pub async fn download_upload(
+    pool: &State<DbPool>,
+    config: &State<UploadConfig>,
+    upload_id: &str,
+) -> Result<UploadDownload, Status> {
+    let upload_id = uuid::Uuid::parse_str(upload_id).map_err(|_| Status::NotFound)?;
+
+    let use_case = UploadUseCase::new(pool.inner().clone(), config.inner().clone());
+    let upload = match use_case.open(upload_id).await {
+        Ok(upload) => upload,
+        Err(UseCaseError::ValidationError(_)) => return Err(Status::NotFound),
+        Err(e) => {
+            error!("Upload download failed: {}", e);
+            return Err(Status::InternalServerError);
+        }
+    };
+
+    let file = tokio::fs::File::open(&upload.file_path).await.map_err(|e| {
+        error!("Upload file missing: {}: {}", upload.file_path, e);
+        Status::NotFound
+    })?;
+
+    let disposition = if upload.is_inline() { "inline" } else { "attachment" };
+    Ok(UploadDownload {
+        file,
+        content_type: ContentType::parse_flexible(&upload.content_type).unwrap_or(ContentType::Binary),
+        disposition: Header::new(
+            "Content-Disposition",
+            format!("{}; filename*=UTF-8''{}", disposition, percent_encode(&upload.file_name)),
+        ),
+        nosniff: Header::new("X-Content-Type-Options", "nosniff"),
+    })
+}
+
+// RFC 5987 文件名编码，支持中文文件名
+fn percent_encode(value: &str) -> String {
+    value.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'.' | b'-' | b'_' | b'~' => (byte as char).to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
@@ -1,35 +1,122 @@
 use rocket::{State, serde::json::Json, get, post};
-use crate::models::{response::ApiResponse, user_data::{UserData, NewUserData}};
-use crate::database::{DbPool, insert_user_data, get_all_user_data};
-use crate::cache::{RedisPool, data::DataCache};
-use tracing::{info, debug};
+use std::sync::Arc;
+use crate::models::{response::{ApiResponse, FieldError, PagedResponse}, user_data::{UserData, NewUserData, UserDataSearchResult, normalize_search_query}, moderation::ModerationSubject};
+use crate::database::{DbPool, insert_user_data, get_all_user_data, count_user_data, list_user_data_page, search_user_data};
+use crate::utils::pagination::{Cursor, Page, PageRequest};
+use crate::cache::{RedisPool, data::{CachedUserData, DataCache}, idempotency::{IdempotencyStore, IdempotencyOutcome}, refresh::CacheRead};
+use crate::auth::IdempotencyKey;
+use crate::config::{UserDataConfig, UploadConfig, ModerationConfig};
+use crate::moderation::ContentModerator;
+use crate::use_cases::{UseCaseError, upload_use_case::UploadUseCase, moderation_use_case::ModerationUseCase, route_command_generator::RouteCommandGenerator};
+use crate::events::{DomainEvent, EventBus};
+use tracing::{info, debug, warn, error};
 
 #[post("/api/user-data", data = "<new_data>")]
+#[allow(clippy::too_many_arguments)]
 pub async fn create_user_data(
     pool: &State<DbPool>,
     redis: &State<RedisPool>,
+    events: &State<EventBus>,
+    config: &State<UserDataConfig>,
+    upload_config: &State<UploadConfig>,
+    moderator: &State<Arc<dyn ContentModerator>>,
+    moderation_config: &State<ModerationConfig>,
     new_data: Json<NewUserData>,
+    idempotency_key: IdempotencyKey,
 ) -> Json<ApiResponse<UserData>> {
-    let user_data = UserData::new(new_data.into_inner());
-    let data_cache = DataCache::new(redis.inner().clone());
+    let new_data = match new_data.into_inner().normalize() {
+        Ok(new_data) => new_data,
+        Err(errors) => return Json(ApiResponse::validation_error(errors)),
+    };
+
+    // 客户端网络重试时通过 Idempotency-Key 避免重复写入
+    let idempotency = idempotency_key.0.map(|key| {
+        IdempotencyStore::new(redis.inner().clone(), "user_data.create", &key, &new_data)
+    });
+    if let Some(store) = &idempotency {
+        match store.begin::<UserData>().await {
+            IdempotencyOutcome::Proceed => {}
+            outcome => return Json(outcome.into_rejection()),
+        }
+    }
+
+    let uploads = UploadUseCase::new(pool.inner().clone(), upload_config.inner().clone());
+    let moderation = ModerationUseCase::new(pool.inner().clone(), redis.inner().clone(), moderator.inner().clone(), moderation_config.inner().clone());
+    let response = save_user_data(pool, redis, events, config, &uploads, &moderation, new_data).await;
+
+    if let Some(store) = &idempotency {
+        store.finish(&response).await;
+    }
+    Json(response)
+}
+
+// 审核留言、关联附件并保存提交，同一邮箱在窗口期内只接受一次提交
+async fn save_user_data(
+    pool: &DbPool,
+    redis: &RedisPool,
+    events: &EventBus,
+    config: &UserDataConfig,
+    uploads: &UploadUseCase,
+    moderation: &ModerationUseCase,
+    new_data: NewUserData,
+) -> ApiResponse<UserData> {
+    let message = new_data.message.clone().unwrap_or_default();
+    let recheck = match moderation.check_text(ModerationSubject::UserDataMessage, &message, None).await {
+        Ok(recheck) => recheck,
+        Err(UseCaseError::ContentRejected(rejection)) => {
+            let command = RouteCommandGenerator::generate_content_rejected_route_command(&rejection);
+            return ApiResponse::error_with_command(&rejection.message(), command);
+        }
+        Err(UseCaseError::BusinessLogicError(msg)) => return ApiResponse::error(&msg),
+        Err(e) => {
+            error!("Failed to moderate user data message: {}", e);
+            return ApiResponse::error("数据保存失败");
+        }
+    };
+
+    let attachments = match uploads.resolve_attachments(&new_data.attachment_ids).await {
+        Ok(attachments) => attachments,
+        Err(UseCaseError::ValidationError(msg)) => {
+            return ApiResponse::validation_error(vec![FieldError::new("attachment_ids", msg)]);
+        }
+        Err(e) => {
+            error!("Failed to load user data attachments: {}", e);
+            return ApiResponse::error("数据保存失败");
+        }
+    };
+
+    // Redis 不可用时不做重复提交检查
+    let data_cache = DataCache::new(redis.clone());
+    let duplicate_check = config.duplicate_window_secs > 0;
+    if duplicate_check {
+        match data_cache.claim_submission(&new_data.email, config.duplicate_window_secs).await {
+            Ok(true) => {}
+            Ok(false) => return ApiResponse::validation_error(vec![FieldError::new("email", "该邮箱刚刚提交过，请稍后再试")]),
+            Err(e) => warn!("Failed to check duplicate user data submission: {}", e),
+        }
+    }
+
+    let mut user_data = UserData::new(new_data);
+    user_data.attachments = attachments;
     
     match insert_user_data(pool, &user_data).await {
         Ok(_) => {
             info!("User data created successfully: {}", user_data.id);
+            moderation.enqueue_recheck(recheck, ModerationSubject::UserDataMessage, user_data.id, None, &message).await;
             
-            // 缓存新创建的用户数据
-            if let Err(e) = data_cache.cache_user_data(&user_data).await {
-                debug!("Failed to cache new user data: {}", e);
-            }
+            // 缓存写入与列表缓存失效由事件订阅者处理
+            events.publish(DomainEvent::UserDataCreated { data: user_data.clone() }).await;
             
-            // 清除所有用户数据列表缓存
-            if let Err(e) = data_cache.invalidate_all_user_data().await {
-                debug!("Failed to invalidate all user data cache: {}", e);
+            ApiResponse::success(user_data)
+        }
+        Err(e) => {
+            if duplicate_check {
+                if let Err(e) = data_cache.release_submission(&user_data.email).await {
+                    warn!("Failed to release user data submission: {}", e);
+                }
             }
-            
-            Json(ApiResponse::success(user_data))
+            ApiResponse::error(&format!("数据保存失败: {}", e))
         }
-        Err(e) => Json(ApiResponse::error(&format!("数据保存失败: {}", e))),
     }
 }
 
@@ -37,45 +124,145 @@ pub async fn create_user_data(
 pub async fn get_user_data(
     pool: &State<DbPool>,
     redis: &State<RedisPool>,
-) -> Json<ApiResponse<Vec<UserData>>> {
+) -> Json<ApiResponse<PagedResponse<UserData>>> {
     let data_cache = DataCache::new(redis.inner().clone());
     
-    // 优先从缓存获取数据
-    match data_cache.get_all_user_data().await {
-        Ok(Some(cached_data)) => {
+    // 优先从缓存获取数据；软过期后只有一个请求回源刷新，其他请求继续使用旧值
+    let stale = match data_cache.get_all_user_data().await {
+        Ok(CacheRead::Fresh(cached_data)) => {
             debug!("Retrieved user data from cache ({} items)", cached_data.len());
-            // 转换缓存数据为原始类型
-            let user_data: Vec<UserData> = cached_data.into_iter().map(|cached| UserData {
-                id: cached.id,
-                name: cached.name,
-                email: cached.email,
-                phone: cached.phone,
-                message: cached.message,
-                created_at: chrono::Utc::now(), // 缓存中不存储时间字段，使用当前时间
-            }).collect();
-            Json(ApiResponse::success(user_data))
-        }
-        Ok(None) => {
+            return Json(ApiResponse::success(PagedResponse::all(from_cached(cached_data))));
+        }
+        Ok(CacheRead::Refresh(cached_data)) => {
+            debug!("User data cache is due for refresh, reloading from database");
+            Some(cached_data)
+        }
+        Ok(CacheRead::Miss) => {
             debug!("Cache miss, retrieving user data from database");
-            // 缓存未命中，从数据库获取
-            match get_all_user_data(pool).await {
-                Ok(data) => {
-                    info!("Retrieved user data from database ({} items)", data.len());
-                    // 缓存数据库结果
-                    if let Err(e) = data_cache.cache_all_user_data(&data).await {
-                        debug!("Failed to cache user data: {}", e);
-                    }
-                    Json(ApiResponse::success(data))
-                }
-                Err(e) => Json(ApiResponse::error(&format!("获取数据失败: {}", e))),
-            }
+            None
         }
         Err(e) => {
             debug!("Cache error, falling back to database: {}", e);
             // 缓存错误，回退到数据库
-            match get_all_user_data(pool).await {
-                Ok(data) => Json(ApiResponse::success(data)),
+            return match get_all_user_data(pool).await {
+                Ok(data) => Json(ApiResponse::success(PagedResponse::all(data))),
                 Err(e) => Json(ApiResponse::error(&format!("获取数据失败: {}", e))),
+            };
+        }
+    };
+
+    match get_all_user_data(pool).await {
+        Ok(data) => {
+            info!("Retrieved user data from database ({} items)", data.len());
+            // 缓存数据库结果
+            if let Err(e) = data_cache.cache_all_user_data(&data).await {
+                debug!("Failed to cache user data: {}", e);
+            }
+            Json(ApiResponse::success(PagedResponse::all(data)))
+        }
+        Err(e) => match stale {
+            // 刷新失败时继续使用旧值，刷新锁过期后由其他请求重试
+            Some(cached_data) => {
+                warn!("Failed to refresh user data, serving stale cache: {}", e);
+                Json(ApiResponse::success(PagedResponse::all(from_cached(cached_data))))
+            }
+            None => Json(ApiResponse::error(&format!("获取数据失败: {}", e))),
+        },
+    }
+}
+
+// 转换缓存数据为原始类型
+fn from_cached(cached_data: Vec<CachedUserData>) -> Vec<UserData> {
+    cached_data.into_iter().map(|cached| UserData {
+        id: cached.id,
+        name: cached.name,
+        email: cached.email,
+        phone: cached.phone,
+        message: cached.message,
+        created_at: chrono::Utc::now(), // 缓存中不存储时间字段，使用当前时间
+        attachments: cached.attachments,
+        status: cached.status,
+        version: cached.version,
+    }).collect()
+}
+
+/// 单页用户数据数量上限
+const MAX_USER_DATA_PAGE_SIZE: i64 = 100;
+
+/// 游标分页获取用户数据，供小程序无限滚动使用
+#[get("/api/user-data/page?<cursor>&<limit>")]
+pub async fn get_user_data_page(
+    pool: &State<DbPool>,
+    cursor: Option<&str>,
+    limit: Option<i64>,
+) -> Json<ApiResponse<PagedResponse<UserData>>> {
+    let page = match PageRequest::parse(cursor, limit, 20, MAX_USER_DATA_PAGE_SIZE) {
+        Ok(page) => page,
+        Err(msg) => return Json(ApiResponse::error(&msg)),
+    };
+
+    let result = match list_user_data_page(pool, &page).await {
+        Ok(rows) => count_user_data(pool).await.map(|total| (rows, total)),
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok((rows, total)) => {
+            let rows = Page::from_rows(rows, &page, |data| Cursor::new(data.created_at, data.id));
+            Json(ApiResponse::success(PagedResponse::from_page(rows, &page).with_total(total)))
+        }
+        Err(e) => {
+            error!("Failed to list user data page: {}", e);
+            Json(ApiResponse::error("获取数据失败"))
+        }
+    }
+}
+
+/// 单次搜索返回数量上限
+const MAX_SEARCH_RESULTS: i64 = 50;
+/// 搜索词最大长度
+const MAX_SEARCH_QUERY_LENGTH: usize = 100;
+
+/// 全文搜索用户数据（姓名、邮箱、留言）
+#[get("/api/user-data/search?<q>&<limit>")]
+pub async fn search_user_data_route(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    q: &str,
+    limit: Option<i64>,
+) -> Json<ApiResponse<UserDataSearchResult>> {
+    let query = normalize_search_query(q);
+    if query.is_empty() {
+        return Json(ApiResponse::error("搜索关键词不能为空"));
+    }
+    if query.chars().count() > MAX_SEARCH_QUERY_LENGTH {
+        return Json(ApiResponse::error("搜索关键词过长"));
+    }
+    let limit = limit.unwrap_or(20).clamp(1, MAX_SEARCH_RESULTS);
+
+    let data_cache = DataCache::new(redis.inner().clone());
+    let stale = match data_cache.get_search_results(&query, limit).await {
+        Ok(CacheRead::Fresh(cached)) => return Json(ApiResponse::success(cached)),
+        Ok(CacheRead::Refresh(cached)) => Some(cached),
+        Ok(CacheRead::Miss) => None,
+        Err(e) => {
+            debug!("Search cache error, falling back to database: {}", e);
+            None
+        }
+    };
+
+    match search_user_data(pool, &query, limit).await {
+        Ok(hits) => {
+            let result = UserDataSearchResult { query: query.clone(), hits };
+            if let Err(e) = data_cache.cache_search_results(&query, limit, &result).await {
+                debug!("Failed to cache user data search results: {}", e);
+            }
+            Json(ApiResponse::success(result))
+        }
+        Err(e) => {
+            error!("User data search failed: {}", e);
+            match stale {
+                Some(cached) => Json(ApiResponse::success(cached)),
+                None => Json(ApiResponse::error("搜索失败")),
             }
         }
     }
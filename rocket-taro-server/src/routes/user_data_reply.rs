@@ -0,0 +1,125 @@
+use rocket::{State, serde::json::Json, get, post, put};
+use rocket::http::Status;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::models::{
+    response::{ApiResponse, PagedResponse},
+    route_command::RouteCommand,
+    user_data::{UserData, UserDataReply, UserDataReplyRequest, UserDataStatusRequest, UserDataThread},
+};
+use crate::database::DbPool;
+use crate::cache::RedisPool;
+use crate::auth::{AuthenticatedUser, IfMatch, guards::AdminUser};
+use crate::push::PushHub;
+use crate::use_cases::{
+    UseCaseError, UseCaseResult,
+    route_command_generator::RouteCommandGenerator,
+    user_data_reply_use_case::UserDataReplyUseCase,
+};
+
+fn use_case(pool: &State<DbPool>, redis: &State<RedisPool>, hub: &State<PushHub>) -> UserDataReplyUseCase {
+    UserDataReplyUseCase::new(pool.inner().clone(), redis.inner().clone(), hub.inner().clone())
+}
+
+fn to_response<T>(result: UseCaseResult<T>, failure: &str) -> Json<ApiResponse<T>> {
+    match result {
+        Ok(value) => Json(ApiResponse::success(value)),
+        Err(UseCaseError::ValidationError(msg)) | Err(UseCaseError::BusinessLogicError(msg)) => {
+            Json(ApiResponse::error(&msg))
+        }
+        Err(e) => {
+            error!("{}: {}", failure, e);
+            Json(ApiResponse::error(failure))
+        }
+    }
+}
+
+fn parse_id(id: &str) -> Option<Uuid> {
+    Uuid::parse_str(id).ok()
+}
+
+// 当前用户以自己邮箱提交的用户数据及处理状态
+#[get("/api/user-data/mine")]
+pub async fn list_own_user_data(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    hub: &State<PushHub>,
+    auth_user: AuthenticatedUser,
+) -> Json<ApiResponse<PagedResponse<UserData>>> {
+    to_response(use_case(pool, redis, hub).list_own(&auth_user.user).await.map(PagedResponse::all), "获取提交记录失败")
+}
+
+// 当前用户查看自己提交的回复
+#[get("/api/user-data/<id>/replies")]
+pub async fn get_own_user_data_thread(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    hub: &State<PushHub>,
+    auth_user: AuthenticatedUser,
+    id: &str,
+) -> Json<ApiResponse<UserDataThread>> {
+    let Some(id) = parse_id(id) else {
+        return Json(ApiResponse::error("数据不存在"));
+    };
+    to_response(use_case(pool, redis, hub).own_thread(&auth_user.user, id).await, "获取回复失败")
+}
+
+// 管理员查看用户数据及回复
+#[get("/api/admin/user-data/<id>/replies")]
+pub async fn get_user_data_thread(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    hub: &State<PushHub>,
+    _admin: AdminUser,
+    id: &str,
+) -> Json<ApiResponse<UserDataThread>> {
+    let Some(id) = parse_id(id) else {
+        return Json(ApiResponse::error("数据不存在"));
+    };
+    to_response(use_case(pool, redis, hub).thread(id).await, "获取回复失败")
+}
+
+// 管理员回复用户数据
+#[post("/api/admin/user-data/<id>/replies", data = "<request>")]
+pub async fn reply_user_data(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    hub: &State<PushHub>,
+    admin: AdminUser,
+    id: &str,
+    request: Json<UserDataReplyRequest>,
+) -> Json<ApiResponse<UserDataReply>> {
+    let Some(id) = parse_id(id) else {
+        return Json(ApiResponse::error("数据不存在"));
+    };
+    match use_case(pool, redis, hub).reply(admin.0.user.id, id, request.into_inner()).await {
+        Ok(reply) => Json(ApiResponse::success_with_command(reply, RouteCommand::toast("回复已发送"))),
+        result => to_response(result, "回复失败"),
+    }
+}
+
+// 管理员更新处理状态，版本号不一致时返回 409
+#[put("/api/admin/user-data/<id>/status", data = "<request>")]
+#[allow(clippy::too_many_arguments)]
+pub async fn update_user_data_status(
+    pool: &State<DbPool>,
+    redis: &State<RedisPool>,
+    hub: &State<PushHub>,
+    admin: AdminUser,
+    if_match: IfMatch,
+    id: &str,
+    request: Json<UserDataStatusRequest>,
+) -> (Status, Json<ApiResponse<UserData>>) {
+    let Some(id) = parse_id(id) else {
+        return (Status::Ok, Json(ApiResponse::error("数据不存在")));
+    };
+    let version = if_match.0.or(request.version);
+    match use_case(pool, redis, hub).update_status(admin.0.user.id, id, request.status, version).await {
+        Err(UseCaseError::ConflictError(msg)) => {
+            let command = RouteCommandGenerator::generate_version_conflict_route_command("user_data", Some(id));
+            (Status::Conflict, Json(ApiResponse::error_with_command(&msg, command)))
+        }
+        result => (Status::Ok, to_response(result, "更新状态失败")),
+    }
+}
@@ -0,0 +1,181 @@
+use rocket::{State, serde::json::Json, get, post, put, delete};
+use tracing::error;
+use uuid::Uuid;
+
+use crate::models::{
+    response::{ApiResponse, PagedResponse},
+    webhook::{
+        CreateWebhookEndpointRequest, UpdateWebhookEndpointRequest, WebhookDelivery, WebhookDeliveryDetail,
+        WebhookDeliveryStatus, WebhookEndpoint, WebhookEndpointWithSecret,
+    },
+};
+use crate::database::DbPool;
+use crate::auth::guards::AdminUser;
+use crate::config::WebhookConfig;
+use crate::utils::pagination::PageRequest;
+use crate::use_cases::{UseCaseError, UseCaseResult, webhook_use_case::WebhookUseCase};
+
+/// 单次查询的投递记录数量上限
+const MAX_DELIVERIES: i64 = 100;
+
+fn use_case(pool: &State<DbPool>, config: &State<WebhookConfig>) -> WebhookUseCase {
+    WebhookUseCase::new(pool.inner().clone(), config.inner().clone())
+}
+
+fn to_response<T>(result: UseCaseResult<T>, failure: &str) -> Json<ApiResponse<T>> {
+    match result {
+        Ok(value) => Json(ApiResponse::success(value)),
+        Err(UseCaseError::ValidationError(msg)) | Err(UseCaseError::BusinessLogicError(msg)) => {
+            Json(ApiResponse::error(&msg))
+        }
+        Err(e) => {
+            error!("{}: {}", failure, e);
+            Json(ApiResponse::error(failure))
+        }
+    }
+}
+
+fn parse_id(id: &str) -> Option<Uuid> {
+    Uuid::parse_str(id).ok()
+}
+
+// 获取全部 Webhook 接收端
+#[get("/api/admin/webhooks")]
+pub async fn list_webhook_endpoints(
+    pool: &State<DbPool>,
+    config: &State<WebhookConfig>,
+    _admin: AdminUser,
+) -> Json<ApiResponse<PagedResponse<WebhookEndpoint>>> {
+    to_response(use_case(pool, config).list_endpoints().await.map(PagedResponse::all), "获取 Webhook 列表失败")
+}
+
+// 创建 Webhook 接收端，签名密钥只在响应中返回一次
+#[post("/api/admin/webhooks", data = "<request>")]
+pub async fn create_webhook_endpoint(
+    pool: &State<DbPool>,
+    config: &State<WebhookConfig>,
+    admin: AdminUser,
+    request: Json<CreateWebhookEndpointRequest>,
+) -> Json<ApiResponse<WebhookEndpointWithSecret>> {
+    to_response(
+        use_case(pool, config).execute_create_endpoint(&admin.0.user, &request).await,
+        "创建 Webhook 失败",
+    )
+}
+
+// 更新 Webhook 接收端
+#[put("/api/admin/webhooks/<endpoint_id>", data = "<request>")]
+pub async fn update_webhook_endpoint(
+    pool: &State<DbPool>,
+    config: &State<WebhookConfig>,
+    admin: AdminUser,
+    endpoint_id: &str,
+    request: Json<UpdateWebhookEndpointRequest>,
+) -> Json<ApiResponse<WebhookEndpoint>> {
+    let Some(endpoint_id) = parse_id(endpoint_id) else {
+        return Json(ApiResponse::error("无效的 Webhook ID"));
+    };
+    to_response(
+        use_case(pool, config).execute_update_endpoint(&admin.0.user, endpoint_id, &request).await,
+        "更新 Webhook 失败",
+    )
+}
+
+// 删除 Webhook 接收端
+#[delete("/api/admin/webhooks/<endpoint_id>")]
+pub async fn delete_webhook_endpoint(
+    pool: &State<DbPool>,
+    config: &State<WebhookConfig>,
+    admin: AdminUser,
+    endpoint_id: &str,
+) -> Json<ApiResponse<()>> {
+    let Some(endpoint_id) = parse_id(endpoint_id) else {
+        return Json(ApiResponse::error("无效的 Webhook ID"));
+    };
+    match use_case(pool, config).execute_delete_endpoint(&admin.0.user, endpoint_id).await {
+        Ok(()) => Json(ApiResponse::ok()),
+        Err(e) => to_response(Err(e), "删除 Webhook 失败"),
+    }
+}
+
+// 轮换 Webhook 签名密钥
+#[post("/api/admin/webhooks/<endpoint_id>/rotate-secret")]
+pub async fn rotate_webhook_secret(
+    pool: &State<DbPool>,
+    config: &State<WebhookConfig>,
+    admin: AdminUser,
+    endpoint_id: &str,
+) -> Json<ApiResponse<WebhookEndpointWithSecret>> {
+    let Some(endpoint_id) = parse_id(endpoint_id) else {
+        return Json(ApiResponse::error("无效的 Webhook ID"));
+    };
+    to_response(
+        use_case(pool, config).execute_rotate_secret(&admin.0.user, endpoint_id).await,
+        "轮换密钥失败",
+    )
+}
+
+// 查询接收端的投递记录（游标分页），status 支持逗号分隔多个状态
+#[get("/api/admin/webhooks/<endpoint_id>/deliveries?<status>&<cursor>&<limit>")]
+pub async fn list_webhook_deliveries(
+    pool: &State<DbPool>,
+    config: &State<WebhookConfig>,
+    _admin: AdminUser,
+    endpoint_id: &str,
+    status: Option<&str>,
+    cursor: Option<&str>,
+    limit: Option<i64>,
+) -> Json<ApiResponse<PagedResponse<WebhookDelivery>>> {
+    let Some(endpoint_id) = parse_id(endpoint_id) else {
+        return Json(ApiResponse::error("无效的 Webhook ID"));
+    };
+
+    let mut statuses = Vec::new();
+    for part in status.unwrap_or_default().split(',').map(str::trim).filter(|part| !part.is_empty()) {
+        match WebhookDeliveryStatus::parse(part) {
+            Some(status) => statuses.push(status),
+            None => return Json(ApiResponse::error(&format!("无效的投递状态: {}", part))),
+        }
+    }
+    let page = match PageRequest::parse(cursor, limit, 20, MAX_DELIVERIES) {
+        Ok(page) => page,
+        Err(msg) => return Json(ApiResponse::error(&msg)),
+    };
+
+    to_response(
+        use_case(pool, config).list_deliveries(endpoint_id, &statuses, &page).await
+            .map(|deliveries| PagedResponse::from_page(deliveries, &page)),
+        "获取投递记录失败",
+    )
+}
+
+// 查询投递详情及每次尝试的日志
+#[get("/api/admin/webhook-deliveries/<delivery_id>")]
+pub async fn get_webhook_delivery(
+    pool: &State<DbPool>,
+    config: &State<WebhookConfig>,
+    _admin: AdminUser,
+    delivery_id: &str,
+) -> Json<ApiResponse<WebhookDeliveryDetail>> {
+    let Some(delivery_id) = parse_id(delivery_id) else {
+        return Json(ApiResponse::error("无效的投递记录ID"));
+    };
+    to_response(use_case(pool, config).get_delivery(delivery_id).await, "获取投递详情失败")
+}
+
+// 重新投递已放弃的记录
+#[post("/api/admin/webhook-deliveries/<delivery_id>/retry")]
+pub async fn retry_webhook_delivery(
+    pool: &State<DbPool>,
+    config: &State<WebhookConfig>,
+    admin: AdminUser,
+    delivery_id: &str,
+) -> Json<ApiResponse<WebhookDelivery>> {
+    let Some(delivery_id) = parse_id(delivery_id) else {
+        return Json(ApiResponse::error("无效的投递记录ID"));
+    };
+    to_response(
+        use_case(pool, config).execute_retry_delivery(&admin.0.user, delivery_id).await,
+        "重试投递失败",
+    )
+}
@@ -0,0 +1,35 @@
+use rocket::async_trait;
+use std::time::Duration;
+
+use crate::config::AccountConfig;
+use crate::use_cases::account_use_case::AccountUseCase;
+use super::{Job, JobContext};
+
+/// 对超过保留期的注销账户执行匿名化
+pub struct AccountAnonymizationJob {
+    config: AccountConfig,
+}
+
+impl AccountAnonymizationJob {
+    pub fn new(config: AccountConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl Job for AccountAnonymizationJob {
+    fn name(&self) -> &'static str {
+        "account_anonymization"
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs(self.config.anonymization_interval_secs)
+    }
+
+    async fn run(&self, ctx: &JobContext) -> anyhow::Result<()> {
+        let use_case = AccountUseCase::new(ctx.db_pool.clone(), ctx.redis.clone());
+        let grace_period = chrono::Duration::days(self.config.deletion_grace_days);
+        use_case.anonymize_expired_accounts(grace_period).await?;
+        Ok(())
+    }
+}
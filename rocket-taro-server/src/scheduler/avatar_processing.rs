@@ -0,0 +1,41 @@
+use rocket::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::config::{AvatarConfig, ModerationConfig};
+use crate::moderation::ContentModerator;
+use crate::use_cases::{avatar_use_case::AvatarUseCase, moderation_use_case::ModerationUseCase};
+use super::{Job, JobContext};
+
+/// 处理上传的头像：去除元数据、缩放、内容审核并转换为 WebP
+pub struct AvatarProcessingJob {
+    config: AvatarConfig,
+    moderator: Arc<dyn ContentModerator>,
+    moderation_config: ModerationConfig,
+}
+
+impl AvatarProcessingJob {
+    pub fn new(config: AvatarConfig, moderator: Arc<dyn ContentModerator>, moderation_config: ModerationConfig) -> Self {
+        Self { config, moderator, moderation_config }
+    }
+}
+
+#[async_trait]
+impl Job for AvatarProcessingJob {
+    fn name(&self) -> &'static str {
+        "avatar_processing"
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs(self.config.process_interval_secs)
+    }
+
+    async fn run(&self, ctx: &JobContext) -> anyhow::Result<()> {
+        let moderation = ModerationUseCase::new(ctx.db_pool.clone(), ctx.redis.clone(), self.moderator.clone(), self.moderation_config.clone());
+        let use_case = AvatarUseCase::new(ctx.db_pool.clone(), ctx.redis.clone(), self.config.clone())
+            .with_moderation(moderation)
+            .with_push(ctx.push.clone());
+        use_case.process_pending().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+use rocket::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::config::{DataExportConfig, StorageConfig};
+use crate::storage::ObjectStorage;
+use crate::use_cases::data_export_use_case::DataExportUseCase;
+use crate::use_cases::storage_use_case::StorageUseCase;
+use super::{Job, JobContext};
+
+/// 生成待处理的用户数据导出，并将过期的导出标记为已过期
+pub struct DataExportJob {
+    config: DataExportConfig,
+    storage: Arc<dyn ObjectStorage>,
+    storage_config: StorageConfig,
+}
+
+impl DataExportJob {
+    pub fn new(config: DataExportConfig, storage: Arc<dyn ObjectStorage>, storage_config: StorageConfig) -> Self {
+        Self { config, storage, storage_config }
+    }
+}
+
+#[async_trait]
+impl Job for DataExportJob {
+    fn name(&self) -> &'static str {
+        "data_export"
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs(self.config.poll_interval_secs)
+    }
+
+    async fn run(&self, ctx: &JobContext) -> anyhow::Result<()> {
+        let storage = StorageUseCase::new(ctx.db_pool.clone(), self.storage.clone(), self.storage_config.clone());
+        let use_case = DataExportUseCase::new(ctx.db_pool.clone(), self.config.clone(), storage);
+        use_case.process_pending_exports().await?;
+        use_case.cleanup_expired_exports().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use rocket::async_trait;
+use std::time::Duration;
+use tracing::info;
+
+use crate::config::IpAccessConfig;
+use crate::fairings::ip_access::IpAccessList;
+use super::{Job, JobContext};
+
+/// 定时从 Rocket 配置（Rocket.toml 和 ROCKET_ 环境变量）重新加载管理接口来源 IP 限制
+pub struct IpAccessReloadJob {
+    list: IpAccessList,
+    interval: Duration,
+}
+
+impl IpAccessReloadJob {
+    pub fn new(list: IpAccessList, config: &IpAccessConfig) -> Self {
+        Self { list, interval: Duration::from_secs(config.reload_interval_secs.max(1)) }
+    }
+}
+
+#[async_trait]
+impl Job for IpAccessReloadJob {
+    fn name(&self) -> &'static str {
+        "ip_access_reload"
+    }
+
+    fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    async fn run(&self, _ctx: &JobContext) -> anyhow::Result<()> {
+        let config = IpAccessConfig::from_figment(&rocket::Config::figment());
+        if self.list.reload(&config) {
+            let rules = self.list.rules();
+            info!(allow = rules.allow.len(), deny = rules.deny.len(), "IP access rules reloaded");
+        }
+        Ok(())
+    }
+}
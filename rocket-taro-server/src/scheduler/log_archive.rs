@@ -0,0 +1,34 @@
+use rocket::async_trait;
+use std::time::Duration;
+
+use crate::config::LogArchiveConfig;
+use crate::use_cases::log_archive_use_case::LogArchiveUseCase;
+use super::{Job, JobContext};
+
+/// 归档过期的登录日志和审计日志，删除超过保留期的归档分区
+pub struct LogArchiveJob {
+    config: LogArchiveConfig,
+}
+
+impl LogArchiveJob {
+    pub fn new(config: LogArchiveConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl Job for LogArchiveJob {
+    fn name(&self) -> &'static str {
+        "log_archive"
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs(self.config.interval_secs)
+    }
+
+    async fn run(&self, ctx: &JobContext) -> anyhow::Result<()> {
+        let use_case = LogArchiveUseCase::new(ctx.db_pool.clone(), self.config.clone());
+        use_case.run().await?;
+        Ok(())
+    }
+}
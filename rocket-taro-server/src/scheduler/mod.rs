@@ -0,0 +1,112 @@
+use rocket::{async_trait, Rocket, Orbit, fairing::{Fairing, Info, Kind}};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn, error, debug};
+
+use crate::database::DbPool;
+use crate::cache::RedisPool;
+use crate::push::PushHub;
+
+pub mod account_anonymization;
+pub mod data_export;
+pub mod order_expiry;
+pub mod webhook_delivery;
+pub mod side_effect_dispatch;
+pub mod session_expiry_warning;
+pub mod ip_access_reload;
+pub mod upload_cleanup;
+pub mod log_archive;
+pub mod slo_evaluation;
+pub mod moderation_recheck;
+pub mod avatar_processing;
+pub mod storage_cleanup;
+pub mod route_decision_cleanup;
+
+/// 后台任务运行时可用的共享资源
+#[derive(Clone)]
+pub struct JobContext {
+    pub db_pool: DbPool,
+    pub redis: RedisPool,
+    pub push: PushHub,
+}
+
+/// 周期性后台任务
+#[async_trait]
+pub trait Job: Send + Sync {
+    /// 任务名称，用于日志
+    fn name(&self) -> &'static str;
+
+    /// 执行间隔
+    fn interval(&self) -> Duration;
+
+    /// 执行一次任务
+    async fn run(&self, ctx: &JobContext) -> anyhow::Result<()>;
+}
+
+/// 任务调度器，在服务启动后为每个注册的任务启动一个定时循环
+#[derive(Default)]
+pub struct Scheduler {
+    jobs: Vec<Arc<dyn Job>>,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册任务
+    pub fn register(mut self, job: impl Job + 'static) -> Self {
+        self.jobs.push(Arc::new(job));
+        self
+    }
+}
+
+#[async_trait]
+impl Fairing for Scheduler {
+    fn info(&self) -> Info {
+        Info {
+            name: "Job Scheduler",
+            kind: Kind::Liftoff,
+        }
+    }
+
+    async fn on_liftoff(&self, rocket: &Rocket<Orbit>) {
+        let (Some(db_pool), Some(redis), Some(push)) = (rocket.state::<DbPool>(), rocket.state::<RedisPool>(), rocket.state::<PushHub>()) else {
+            error!("Job scheduler not started: database, cache or push state is missing");
+            return;
+        };
+
+        let ctx = JobContext {
+            db_pool: db_pool.clone(),
+            redis: redis.clone(),
+            push: push.clone(),
+        };
+
+        for job in &self.jobs {
+            let job = job.clone();
+            let ctx = ctx.clone();
+            let mut shutdown = rocket.shutdown();
+
+            info!("Scheduling job '{}' every {:?}", job.name(), job.interval());
+            tokio::spawn(async move {
+                let mut ticker = tokio::time::interval(job.interval());
+                ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+                loop {
+                    tokio::select! {
+                        _ = &mut shutdown => {
+                            debug!("Job '{}' stopped on shutdown", job.name());
+                            break;
+                        }
+                        _ = ticker.tick() => {
+                            debug!("Running job '{}'", job.name());
+                            if let Err(e) = job.run(&ctx).await {
+                                warn!("Job '{}' failed: {:#}", job.name(), e);
+                            }
+                        }
+                    }
+                }
+            });
+        }
+    }
+}
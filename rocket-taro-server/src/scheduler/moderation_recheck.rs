@@ -0,0 +1,37 @@
+use rocket::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::config::ModerationConfig;
+use crate::moderation::ContentModerator;
+use crate::use_cases::moderation_use_case::ModerationUseCase;
+use super::{Job, JobContext};
+
+/// 复核提交时疑似违规或审核服务不可用而放行的内容，违规时清除
+pub struct ModerationRecheckJob {
+    moderator: Arc<dyn ContentModerator>,
+    config: ModerationConfig,
+}
+
+impl ModerationRecheckJob {
+    pub fn new(moderator: Arc<dyn ContentModerator>, config: ModerationConfig) -> Self {
+        Self { moderator, config }
+    }
+}
+
+#[async_trait]
+impl Job for ModerationRecheckJob {
+    fn name(&self) -> &'static str {
+        "moderation_recheck"
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs(self.config.recheck_interval_secs)
+    }
+
+    async fn run(&self, ctx: &JobContext) -> anyhow::Result<()> {
+        let use_case = ModerationUseCase::new(ctx.db_pool.clone(), ctx.redis.clone(), self.moderator.clone(), self.config.clone());
+        use_case.recheck_due().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use rocket::async_trait;
+use std::time::Duration;
+
+use crate::config::OrderConfig;
+use crate::use_cases::order_use_case::OrderUseCase;
+use super::{Job, JobContext};
+
+/// 关闭超时未支付的订单
+pub struct OrderExpiryJob {
+    config: OrderConfig,
+}
+
+impl OrderExpiryJob {
+    pub fn new(config: OrderConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl Job for OrderExpiryJob {
+    fn name(&self) -> &'static str {
+        "order_expiry"
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs(self.config.expiry_interval_secs)
+    }
+
+    async fn run(&self, ctx: &JobContext) -> anyhow::Result<()> {
+        let use_case = OrderUseCase::new(ctx.db_pool.clone(), self.config.clone());
+        use_case.expire_overdue_orders().await?;
+        Ok(())
+    }
+}
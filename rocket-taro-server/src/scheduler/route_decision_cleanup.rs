@@ -0,0 +1,34 @@
+use rocket::async_trait;
+use std::time::Duration;
+
+use crate::config::RouteTraceConfig;
+use crate::use_cases::route_execution_use_case::RouteExecutionUseCase;
+use super::{Job, JobContext};
+
+/// 删除超过保留期的路由决策记录；关闭追踪后也会继续清理已有的记录
+pub struct RouteDecisionCleanupJob {
+    config: RouteTraceConfig,
+}
+
+impl RouteDecisionCleanupJob {
+    pub fn new(config: RouteTraceConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl Job for RouteDecisionCleanupJob {
+    fn name(&self) -> &'static str {
+        "route_decision_cleanup"
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs(self.config.cleanup_interval_secs)
+    }
+
+    async fn run(&self, ctx: &JobContext) -> anyhow::Result<()> {
+        let use_case = RouteExecutionUseCase::new(ctx.db_pool.clone()).with_trace(self.config.clone());
+        use_case.cleanup_decisions().await?;
+        Ok(())
+    }
+}
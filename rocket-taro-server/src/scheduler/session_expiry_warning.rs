@@ -0,0 +1,34 @@
+use rocket::async_trait;
+use std::time::Duration;
+
+use crate::config::SessionExpiryConfig;
+use crate::use_cases::session_expiry_use_case::SessionExpiryUseCase;
+use super::{Job, JobContext};
+
+/// 通过推送通道提醒即将过期的会话
+pub struct SessionExpiryWarningJob {
+    config: SessionExpiryConfig,
+}
+
+impl SessionExpiryWarningJob {
+    pub fn new(config: SessionExpiryConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl Job for SessionExpiryWarningJob {
+    fn name(&self) -> &'static str {
+        "session_expiry_warning"
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs(self.config.check_interval_secs)
+    }
+
+    async fn run(&self, ctx: &JobContext) -> anyhow::Result<()> {
+        let use_case = SessionExpiryUseCase::new(ctx.db_pool.clone(), ctx.redis.clone());
+        use_case.send_due_warnings(&ctx.push, chrono::Duration::seconds(self.config.warn_before_secs)).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+use rocket::async_trait;
+use std::time::Duration;
+
+use crate::config::SideEffectConfig;
+use crate::use_cases::side_effect_use_case::SideEffectUseCase;
+use super::{Job, JobContext};
+
+/// 执行副作用发件箱中到期的记录，失败的记录按退避时间重试
+pub struct SideEffectDispatchJob {
+    config: SideEffectConfig,
+}
+
+impl SideEffectDispatchJob {
+    pub fn new(config: SideEffectConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl Job for SideEffectDispatchJob {
+    fn name(&self) -> &'static str {
+        "side_effect_dispatch"
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs(self.config.poll_interval_secs)
+    }
+
+    async fn run(&self, ctx: &JobContext) -> anyhow::Result<()> {
+        let use_case = SideEffectUseCase::new(ctx.db_pool.clone(), ctx.redis.clone(), self.config.clone());
+        use_case.dispatch_due().await?;
+        use_case.purge_history().await?;
+        Ok(())
+    }
+}
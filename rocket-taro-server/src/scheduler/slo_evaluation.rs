@@ -0,0 +1,34 @@
+use rocket::async_trait;
+use std::time::Duration;
+
+use crate::config::SloConfig;
+use crate::metrics::slo::SloTracker;
+use super::{Job, JobContext};
+
+/// 定时记录 SLO 燃烧率序列并检查燃烧率告警
+pub struct SloEvaluationJob {
+    tracker: SloTracker,
+    interval: Duration,
+}
+
+impl SloEvaluationJob {
+    pub fn new(tracker: SloTracker, config: &SloConfig) -> Self {
+        Self { tracker, interval: Duration::from_secs(config.evaluation_interval_secs.max(1)) }
+    }
+}
+
+#[async_trait]
+impl Job for SloEvaluationJob {
+    fn name(&self) -> &'static str {
+        "slo_evaluation"
+    }
+
+    fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    async fn run(&self, _ctx: &JobContext) -> anyhow::Result<()> {
+        self.tracker.evaluate(chrono::Utc::now());
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+use rocket::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::config::StorageConfig;
+use crate::storage::ObjectStorage;
+use crate::use_cases::storage_use_case::StorageUseCase;
+use super::{Job, JobContext};
+
+/// 删除存储中已过期的生成文件（数据导出等）
+pub struct StorageCleanupJob {
+    storage: Arc<dyn ObjectStorage>,
+    config: StorageConfig,
+}
+
+impl StorageCleanupJob {
+    pub fn new(storage: Arc<dyn ObjectStorage>, config: StorageConfig) -> Self {
+        Self { storage, config }
+    }
+}
+
+#[async_trait]
+impl Job for StorageCleanupJob {
+    fn name(&self) -> &'static str {
+        "storage_cleanup"
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs(self.config.cleanup_interval_secs)
+    }
+
+    async fn run(&self, ctx: &JobContext) -> anyhow::Result<()> {
+        let use_case = StorageUseCase::new(ctx.db_pool.clone(), self.storage.clone(), self.config.clone());
+        use_case.cleanup_expired().await?;
+        Ok(())
+    }
+}
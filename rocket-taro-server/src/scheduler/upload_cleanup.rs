@@ -0,0 +1,34 @@
+use rocket::async_trait;
+use std::time::Duration;
+
+use crate::config::UploadConfig;
+use crate::use_cases::upload_use_case::UploadUseCase;
+use super::{Job, JobContext};
+
+/// 清理上传后未作为附件的文件
+pub struct UploadCleanupJob {
+    config: UploadConfig,
+}
+
+impl UploadCleanupJob {
+    pub fn new(config: UploadConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl Job for UploadCleanupJob {
+    fn name(&self) -> &'static str {
+        "upload_cleanup"
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs(self.config.cleanup_interval_secs)
+    }
+
+    async fn run(&self, ctx: &JobContext) -> anyhow::Result<()> {
+        let use_case = UploadUseCase::new(ctx.db_pool.clone(), self.config.clone());
+        use_case.cleanup_orphaned_uploads().await?;
+        Ok(())
+    }
+}
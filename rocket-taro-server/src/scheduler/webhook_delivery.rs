@@ -0,0 +1,40 @@
+use rocket::async_trait;
+use std::time::Duration;
+
+use crate::config::WebhookConfig;
+use crate::use_cases::webhook_use_case::WebhookUseCase;
+use crate::utils::http_client::HttpClient;
+use crate::webhooks::WebhookClient;
+use super::{Job, JobContext};
+
+/// 分发发件箱中的事件并投递到期的 Webhook
+pub struct WebhookDeliveryJob {
+    config: WebhookConfig,
+    client: WebhookClient,
+}
+
+impl WebhookDeliveryJob {
+    pub fn new(config: WebhookConfig, http: HttpClient) -> Self {
+        let client = WebhookClient::from_config(&config, http);
+        Self { config, client }
+    }
+}
+
+#[async_trait]
+impl Job for WebhookDeliveryJob {
+    fn name(&self) -> &'static str {
+        "webhook_delivery"
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs(self.config.poll_interval_secs)
+    }
+
+    async fn run(&self, ctx: &JobContext) -> anyhow::Result<()> {
+        let use_case = WebhookUseCase::new(ctx.db_pool.clone(), self.config.clone());
+        use_case.dispatch_pending_events().await?;
+        use_case.deliver_due(&self.client).await?;
+        use_case.purge_history().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,244 @@
+//! 启动自检：路由配置、数据库迁移、Redis 认证和微信凭据，
+//! 由命令行 `--check` 执行后退出，或在严格模式下于启动时执行
+
+use redis::ErrorKind;
+use rocket::figment::Figment;
+use serde::Serialize;
+use std::fmt;
+use std::future::Future;
+use std::time::Duration;
+
+use crate::cache::topology::RedisConnection;
+use crate::config::{CacheConfig, DatabaseConfig, HttpClientConfig, Platform, RouteConfig, StartupCheckConfig, WechatConfig, WxApiClientKind, route_keys};
+use crate::utils::http_client::HttpClient;
+use crate::wechat::HttpWxApiClient;
+
+/// 路由配置文件路径，与启动时加载的一致
+const ROUTES_FILE: &str = "routes.toml";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    /// 不影响启动，但功能会降级
+    Warn,
+    Fail,
+    /// 按配置未执行
+    Skip,
+}
+
+impl CheckStatus {
+    fn label(&self) -> &'static str {
+        match self {
+            CheckStatus::Pass => "通过",
+            CheckStatus::Warn => "警告",
+            CheckStatus::Fail => "失败",
+            CheckStatus::Skip => "跳过",
+        }
+    }
+}
+
+/// 单项检查结果
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckResult {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl CheckResult {
+    fn new(name: &'static str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self { name, status, detail: detail.into() }
+    }
+}
+
+/// 自检报告，有失败项时视为未通过
+#[derive(Debug, Clone, Serialize)]
+pub struct SelfCheckReport {
+    pub checks: Vec<CheckResult>,
+}
+
+impl SelfCheckReport {
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|check| check.status != CheckStatus::Fail)
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &CheckResult> {
+        self.checks.iter().filter(|check| check.status == CheckStatus::Fail)
+    }
+
+    fn count(&self, status: CheckStatus) -> usize {
+        self.checks.iter().filter(|check| check.status == status).count()
+    }
+}
+
+impl fmt::Display for SelfCheckReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "启动自检")?;
+        let width = self.checks.iter().map(|check| check.name.len()).max().unwrap_or(0);
+        for check in &self.checks {
+            writeln!(f, "  [{}] {:width$}  {}", check.status.label(), check.name, check.detail, width = width)?;
+        }
+        write!(
+            f,
+            "结果: {}，通过 {} 项，警告 {} 项，失败 {} 项，跳过 {} 项",
+            if self.passed() { "通过" } else { "未通过" },
+            self.count(CheckStatus::Pass),
+            self.count(CheckStatus::Warn),
+            self.count(CheckStatus::Fail),
+            self.count(CheckStatus::Skip),
+        )
+    }
+}
+
+/// 自检使用的配置，与启动时读取的相同（包括环境变量覆盖）
+pub struct SelfCheck {
+    config: StartupCheckConfig,
+    database: DatabaseConfig,
+    cache: CacheConfig,
+    wechat: WechatConfig,
+    http_client: HttpClientConfig,
+}
+
+impl SelfCheck {
+    pub fn from_figment(figment: &Figment) -> Self {
+        Self {
+            config: StartupCheckConfig::from_figment(figment),
+            database: DatabaseConfig::from_figment(figment),
+            cache: CacheConfig::from_figment(figment),
+            wechat: WechatConfig::from_figment(figment),
+            http_client: HttpClientConfig::from_figment(figment),
+        }
+    }
+
+    /// 依次执行全部检查，单项失败不影响后续检查
+    pub async fn run(&self) -> SelfCheckReport {
+        let checks = vec![
+            check_routes(),
+            self.check_database().await,
+            self.check_redis().await,
+            self.check_wechat().await,
+        ];
+        SelfCheckReport { checks }
+    }
+
+    async fn with_timeout<T>(&self, future: impl Future<Output = Result<T, String>>) -> Result<T, String> {
+        let timeout = Duration::from_secs(self.config.timeout_secs);
+        tokio::time::timeout(timeout, future).await
+            .unwrap_or_else(|_| Err(format!("{} 秒内未完成", timeout.as_secs())))
+    }
+
+    async fn check_database(&self) -> CheckResult {
+        let status = self.with_timeout(async {
+            crate::database::check_migrations(&self.database).await.map_err(|e| format!("{:#}", e))
+        }).await;
+        let status = match status {
+            Ok(status) => status,
+            Err(e) => return CheckResult::new("database", CheckStatus::Fail, e),
+        };
+
+        if !status.pending.is_empty() {
+            let pending: Vec<String> = status.pending.iter()
+                .map(|migration| format!("{:03}_{}", migration.version, migration.name))
+                .collect();
+            return CheckResult::new("database", CheckStatus::Fail, format!("有 {} 个迁移未执行: {}", pending.len(), pending.join(", ")));
+        }
+        if !status.unknown.is_empty() {
+            return CheckResult::new("database", CheckStatus::Warn, format!("数据库包含当前版本没有的迁移 {:?}，可能已由更新的版本迁移", status.unknown));
+        }
+        CheckResult::new("database", CheckStatus::Pass, "迁移已全部执行")
+    }
+
+    async fn check_redis(&self) -> CheckResult {
+        let result = self.with_timeout(async {
+            let mut connection = RedisConnection::connect(&self.cache).await.map_err(describe_redis_error)?;
+            redis::cmd("PING").query_async::<_, String>(&mut connection).await.map_err(describe_redis_error)
+        }).await;
+        match result {
+            Ok(_) => CheckResult::new("redis", CheckStatus::Pass, format!("连接和认证成功（{:?}）", self.cache.topology)),
+            Err(e) => CheckResult::new("redis", CheckStatus::Fail, e),
+        }
+    }
+
+    async fn check_wechat(&self) -> CheckResult {
+        if !self.config.wechat {
+            return CheckResult::new("wechat", CheckStatus::Skip, "startup_check.wechat = false");
+        }
+        if self.wechat.client == WxApiClientKind::Mock {
+            return CheckResult::new("wechat", CheckStatus::Skip, "使用模拟客户端");
+        }
+        let client = HttpWxApiClient::new(self.wechat.clone(), HttpClient::from_config(self.http_client.clone()));
+        match self.with_timeout(async { client.verify_credentials().await.map_err(|e| e.to_string()) }).await {
+            Ok(()) => CheckResult::new("wechat", CheckStatus::Pass, "AppID 和 AppSecret 有效"),
+            Err(e) => CheckResult::new("wechat", CheckStatus::Fail, e),
+        }
+    }
+}
+
+fn describe_redis_error(error: redis::RedisError) -> String {
+    if error.kind() == ErrorKind::AuthenticationFailed {
+        format!("认证失败，请检查 redis_password / REDIS_PASSWORD: {}", error)
+    } else {
+        error.to_string()
+    }
+}
+
+/// 路由配置能否加载、格式是否正确，代码引用的路由键是否在各平台都已配置
+fn check_routes() -> CheckResult {
+    let route_config = match RouteConfig::from_file(ROUTES_FILE) {
+        Ok(route_config) => route_config,
+        Err(e) => return CheckResult::new("routes", CheckStatus::Fail, format!("{:#}", e)),
+    };
+    if let Err(e) = route_config.validate() {
+        return CheckResult::new("routes", CheckStatus::Fail, format!("{:#}", e));
+    }
+
+    let missing: Vec<(&str, Platform)> = route_keys::ALL.iter()
+        .flat_map(|key| Platform::ALL.iter().map(move |platform| (*key, *platform)))
+        .filter(|(key, platform)| route_config.get_route(key, *platform).is_none())
+        .collect();
+    let describe = |optional: bool| -> Vec<String> {
+        missing.iter()
+            .filter(|(key, _)| route_keys::OPTIONAL.contains(key) == optional)
+            .map(|(key, platform)| format!("{}({})", key, platform.as_str()))
+            .collect()
+    };
+    let required = describe(false);
+    if !required.is_empty() {
+        return CheckResult::new("routes", CheckStatus::Fail, format!("缺少路由: {}", required.join(", ")));
+    }
+    let optional = describe(true);
+    if !optional.is_empty() {
+        return CheckResult::new("routes", CheckStatus::Warn, format!("缺少路由，将降级为提示: {}", optional.join(", ")));
+    }
+    CheckResult::new("routes", CheckStatus::Pass, format!("代码引用的 {} 个路由键均已配置", route_keys::ALL.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checked_in_routes() {
+        let result = check_routes();
+        assert_ne!(result.status, CheckStatus::Fail, "{}", result.detail);
+    }
+
+    #[test]
+    fn test_report() {
+        let mut report = SelfCheckReport {
+            checks: vec![
+                CheckResult::new("routes", CheckStatus::Pass, "ok"),
+                CheckResult::new("wechat", CheckStatus::Skip, "使用模拟客户端"),
+            ],
+        };
+        assert!(report.passed());
+
+        report.checks.push(CheckResult::new("redis", CheckStatus::Fail, "认证失败"));
+        assert!(!report.passed());
+        assert_eq!(report.failures().count(), 1);
+        let rendered = report.to_string();
+        assert!(rendered.contains("  [失败] redis   认证失败"));
+        assert!(rendered.ends_with("结果: 未通过，通过 1 项，警告 0 项，失败 1 项，跳过 1 项"));
+    }
+}
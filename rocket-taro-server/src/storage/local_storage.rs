@@ -0,0 +1,122 @@
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use rocket::async_trait;
+use sha2::Sha256;
+use std::path::{Path, PathBuf};
+
+use super::{ObjectStorage, is_valid_key, uri_encode};
+
+/// 本地签名下载地址的路径前缀
+pub const SIGNED_DOWNLOAD_PREFIX: &str = "/api/storage";
+
+/// 保存在本地目录的存储后端，下载地址由本服务签名并校验
+pub struct LocalStorage {
+    root: PathBuf,
+    signing_key: Vec<u8>,
+}
+
+impl LocalStorage {
+    pub fn new(root: &str, signing_key: Vec<u8>) -> Self {
+        Self { root: PathBuf::from(root), signing_key }
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf, String> {
+        if !is_valid_key(key) {
+            return Err(format!("Invalid storage key: {}", key));
+        }
+        Ok(self.root.join(key))
+    }
+
+    fn signature(&self, key: &str, file_name: &str, expires: i64) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.signing_key).expect("HMAC accepts keys of any length");
+        mac.update(format!("{}\n{}\n{}", key, file_name, expires).as_bytes());
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    fn verify(&self, key: &str, file_name: &str, expires: i64, signature: &str, now: DateTime<Utc>) -> bool {
+        let Ok(signature) = hex::decode(signature) else { return false };
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.signing_key).expect("HMAC accepts keys of any length");
+        mac.update(format!("{}\n{}\n{}", key, file_name, expires).as_bytes());
+        expires > now.timestamp() && mac.verify_slice(&signature).is_ok()
+    }
+}
+
+#[async_trait]
+impl ObjectStorage for LocalStorage {
+    fn name(&self) -> &'static str {
+        "local"
+    }
+
+    async fn put_file(&self, key: &str, path: &Path, _content_type: &str) -> Result<u64, String> {
+        let target = self.path(key)?;
+        if let Some(parent) = target.parent() {
+            tokio::fs::create_dir_all(parent).await
+                .map_err(|e| format!("Failed to create storage directory: {}", e))?;
+        }
+        // 跨文件系统时无法重命名，改为复制后删除
+        if tokio::fs::rename(path, &target).await.is_err() {
+            tokio::fs::copy(path, &target).await
+                .map_err(|e| format!("Failed to copy file into storage: {}", e))?;
+            let _ = tokio::fs::remove_file(path).await;
+        }
+        let metadata = tokio::fs::metadata(&target).await
+            .map_err(|e| format!("Failed to read stored file: {}", e))?;
+        Ok(metadata.len())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), String> {
+        match tokio::fs::remove_file(self.path(key)?).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(format!("Failed to remove stored file: {}", e)),
+        }
+    }
+
+    fn signed_url(&self, key: &str, file_name: &str, expires_at: DateTime<Utc>) -> Result<String, String> {
+        self.path(key)?;
+        let expires = expires_at.timestamp();
+        Ok(format!(
+            "{}/{}?expires={}&name={}&signature={}",
+            SIGNED_DOWNLOAD_PREFIX,
+            uri_encode(key, true),
+            expires,
+            uri_encode(file_name, false),
+            self.signature(key, file_name, expires),
+        ))
+    }
+
+    fn resolve_signed(&self, key: &str, file_name: &str, expires: i64, signature: &str) -> Option<PathBuf> {
+        if !self.verify(key, file_name, expires, signature, Utc::now()) {
+            return None;
+        }
+        self.path(key).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_signed_url() {
+        let storage = LocalStorage::new("data", b"key".to_vec());
+        let now = Utc::now();
+        let url = storage.signed_url("exports/a.json", "my data.json", now + Duration::minutes(5)).unwrap();
+        assert!(url.starts_with("/api/storage/exports/a.json?expires="));
+        assert!(url.contains("&name=my%20data.json&"));
+
+        let expires = (now + Duration::minutes(5)).timestamp();
+        let signature = storage.signature("exports/a.json", "my data.json", expires);
+        assert!(storage.verify("exports/a.json", "my data.json", expires, &signature, now));
+        // 篡改 key、文件名或有效期，以及过期后都不能通过校验
+        assert!(!storage.verify("exports/b.json", "my data.json", expires, &signature, now));
+        assert!(!storage.verify("exports/a.json", "other.json", expires, &signature, now));
+        assert!(!storage.verify("exports/a.json", "my data.json", expires + 60, &signature, now));
+        assert!(!storage.verify("exports/a.json", "my data.json", expires, &signature, now + Duration::minutes(6)));
+
+        let other = LocalStorage::new("data", b"other".to_vec());
+        assert!(!other.verify("exports/a.json", "my data.json", expires, &signature, now));
+        assert!(storage.signed_url("../secret", "x", now).is_err());
+    }
+}
@@ -0,0 +1,94 @@
+use chrono::{DateTime, Utc};
+use rocket::async_trait;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use tracing::warn;
+
+use crate::config::{StorageConfig, StorageBackendKind};
+use crate::utils::http_client::HttpClient;
+
+pub mod local_storage;
+pub mod s3_storage;
+
+pub use local_storage::LocalStorage;
+pub use s3_storage::S3Storage;
+
+/// 生成文件（数据导出等）的存储后端，文件按 key 存取，key 形如 `exports/<id>.json`
+#[async_trait]
+pub trait ObjectStorage: Send + Sync {
+    /// 后端名称，用于日志
+    fn name(&self) -> &'static str;
+
+    /// 把本地生成的文件保存到 key，成功后本地文件不再保留，返回文件大小
+    async fn put_file(&self, key: &str, path: &Path, content_type: &str) -> Result<u64, String>;
+
+    /// 删除文件，文件不存在时视为成功
+    async fn delete(&self, key: &str) -> Result<(), String>;
+
+    /// 生成 expires_at 前有效的下载地址，下载时使用 file_name 作为文件名
+    fn signed_url(&self, key: &str, file_name: &str, expires_at: DateTime<Utc>) -> Result<String, String>;
+
+    /// 校验由本服务提供的签名下载地址，有效时返回本地文件路径；
+    /// 对象存储的签名地址由存储服务自身校验，返回 None
+    fn resolve_signed(&self, _key: &str, _file_name: &str, _expires: i64, _signature: &str) -> Option<PathBuf> {
+        None
+    }
+}
+
+/// 按配置选择存储后端，作为 Rocket 托管状态共享
+pub fn storage_from_config(config: &StorageConfig, http: &HttpClient) -> Arc<dyn ObjectStorage> {
+    match config.backend {
+        StorageBackendKind::Local => {
+            let signing_key = match &config.signing_key {
+                Some(key) => key.as_bytes().to_vec(),
+                None => {
+                    warn!("storage.signing_key is not configured, using a random key; signed download links will not survive restarts or work across instances");
+                    rand::random::<[u8; 32]>().to_vec()
+                }
+            };
+            Arc::new(LocalStorage::new(&config.local_directory, signing_key))
+        }
+        StorageBackendKind::S3 => Arc::new(S3Storage::new(config.s3.clone(), http.clone())),
+    }
+}
+
+/// key 只能由普通路径段组成，不能包含 `..`、根目录等
+pub fn is_valid_key(key: &str) -> bool {
+    !key.is_empty() && Path::new(key).components().all(|component| matches!(component, Component::Normal(_)))
+}
+
+/// 按 RFC 3986 编码，只保留非保留字符；keep_slash 为 true 时保留路径分隔符
+pub fn uri_encode(value: &str, keep_slash: bool) -> String {
+    value.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (byte as char).to_string(),
+            b'/' if keep_slash => "/".to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// 下载时的 Content-Disposition，文件名同时提供 ASCII 和 RFC 5987 编码形式
+pub fn content_disposition(file_name: &str) -> String {
+    let ascii: String = file_name.chars()
+        .map(|c| if c.is_ascii_graphic() && c != '"' && c != '\\' { c } else { '_' })
+        .collect();
+    format!("attachment; filename=\"{}\"; filename*=UTF-8''{}", ascii, uri_encode(file_name, false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keys_and_encoding() {
+        assert!(is_valid_key("exports/0b1c.json"));
+        assert!(!is_valid_key("../etc/passwd"));
+        assert!(!is_valid_key("/etc/passwd"));
+        assert!(!is_valid_key(""));
+
+        assert_eq!(uri_encode("exports/a b+.json", true), "exports/a%20b%2B.json");
+        assert_eq!(uri_encode("a/b", false), "a%2Fb");
+        assert_eq!(content_disposition("数据.json"), "attachment; filename=\"__.json\"; filename*=UTF-8''%E6%95%B0%E6%8D%AE.json");
+    }
+}
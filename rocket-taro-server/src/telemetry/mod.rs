@@ -0,0 +1,151 @@
+//! 链路追踪：初始化日志和 OTLP 导出，为每个请求创建 span 并沿用请求头中的 traceparent
+
+use opentelemetry::{global, propagation::Extractor, trace::TracerProvider as _};
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{Resource, propagation::TraceContextPropagator, trace::{Sampler, SdkTracerProvider}};
+use rocket::http::HeaderMap;
+use rocket::route::{Handler, Outcome, Route};
+use rocket::{Data, Request};
+use std::collections::HashMap;
+use std::time::Duration;
+use tracing::{Instrument, Level, Span, debug_span, field::Empty, warn};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::{Layer, filter::{LevelFilter, filter_fn}, layer::SubscriberExt, util::SubscriberInitExt};
+
+use crate::config::TelemetryConfig;
+use crate::utils::redact;
+
+/// 向下游传递的 W3C Trace Context 请求头
+const TRACE_HEADERS: &[&str] = &["traceparent", "tracestate"];
+
+/// 初始化日志；启用导出时同时把 span 通过 OTLP 发送给追踪后端，返回的 provider 需在退出前关闭以发送剩余的 span
+///
+/// 日志仍只输出 INFO 及以上，密钥类字段脱敏后输出；数据库、Redis、微信等 span 为 DEBUG 级别，只导出、不进入日志
+pub fn init(config: &TelemetryConfig) -> Option<SdkTracerProvider> {
+    let fmt_layer = tracing_subscriber::fmt::layer()
+        .fmt_fields(redact::redacting_fields())
+        .with_filter(LevelFilter::INFO);
+    if !config.enabled {
+        tracing_subscriber::registry().with(fmt_layer).init();
+        return None;
+    }
+
+    let exporter = SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(&config.endpoint)
+        .with_timeout(Duration::from_secs(config.export_timeout_secs))
+        .build();
+    let exporter = match exporter {
+        Ok(exporter) => exporter,
+        Err(e) => {
+            tracing_subscriber::registry().with(fmt_layer).init();
+            warn!("OTLP exporter not started, tracing export disabled: {}", e);
+            return None;
+        }
+    };
+
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(config.sample_ratio))))
+        .with_resource(Resource::builder().with_service_name(config.service_name.clone()).build())
+        .build();
+    global::set_text_map_propagator(TraceContextPropagator::new());
+
+    // 导出所有 span，事件只导出 INFO 及以上，避免 DEBUG 日志中的缓存键等内容进入追踪后端
+    let otel_layer = tracing_opentelemetry::layer()
+        .with_tracer(provider.tracer(config.service_name.clone()))
+        .with_filter(filter_fn(|metadata| metadata.is_span() || *metadata.level() <= Level::INFO));
+    tracing_subscriber::registry().with(fmt_layer).with(otel_layer).init();
+    Some(provider)
+}
+
+/// 为路由包一层请求 span，路由守卫（认证等）和处理函数中的 span 都挂在它下面
+pub fn traced(routes: Vec<Route>) -> Vec<Route> {
+    routes.into_iter()
+        .map(|mut route| {
+            let name = format!("{} {}", route.method, route.uri.origin.path());
+            route.handler = Box::new(TracedHandler { name, inner: route.handler });
+            route
+        })
+        .collect()
+}
+
+#[derive(Clone)]
+struct TracedHandler {
+    name: String,
+    inner: Box<dyn Handler>,
+}
+
+#[rocket::async_trait]
+impl Handler for TracedHandler {
+    async fn handle<'r>(&self, request: &'r Request<'_>, data: Data<'r>) -> Outcome<'r> {
+        let span = debug_span!(
+            "http_request",
+            otel.name = %self.name,
+            otel.kind = "server",
+            otel.status_code = Empty,
+            http.request.method = %request.method(),
+            url.path = %request.uri().path(),
+            http.response.status_code = Empty,
+        );
+        let _ = span.set_parent(global::get_text_map_propagator(|propagator| propagator.extract(&HeaderExtractor(request.headers()))));
+
+        let outcome = self.inner.handle(request, data).instrument(span.clone()).await;
+        let status = match &outcome {
+            Outcome::Success(response) => response.status(),
+            Outcome::Error(status) => *status,
+            Outcome::Forward((_, status)) => *status,
+        };
+        span.record("http.response.status_code", status.code);
+        if status.code >= 500 {
+            span.record("otel.status_code", "ERROR");
+        }
+        outcome
+    }
+}
+
+struct HeaderExtractor<'a>(&'a HeaderMap<'a>);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get_one(key)
+    }
+
+    // HeaderMap 不能借出全部请求头名，只列出追踪相关的请求头
+    fn keys(&self) -> Vec<&str> {
+        TRACE_HEADERS.iter().copied().filter(|name| self.0.contains(*name)).collect()
+    }
+}
+
+/// 当前 span 的 traceparent / tracestate，转发给下游请求使其加入同一条链路；未启用导出时为空
+pub fn trace_headers() -> Vec<(&'static str, String)> {
+    let mut carrier = HashMap::new();
+    global::get_text_map_propagator(|propagator| propagator.inject_context(&Span::current().context(), &mut carrier));
+    TRACE_HEADERS.iter()
+        .filter_map(|name| carrier.remove(*name).map(|value| (*name, value)))
+        .collect()
+}
+
+/// 标记 span 失败，追踪后端中显示为错误
+pub fn record_error(span: &Span) {
+    span.record("otel.status_code", "ERROR");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::{propagation::TextMapPropagator, trace::TraceContextExt};
+    use rocket::http::Header;
+
+    #[test]
+    fn test_extract_traceparent() {
+        let mut headers = HeaderMap::new();
+        headers.add(Header::new("traceparent", "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"));
+        let extractor = HeaderExtractor(&headers);
+        let context = TraceContextPropagator::new().extract(&extractor);
+        let span_context = context.span().span_context().clone();
+        assert!(span_context.is_remote());
+        assert_eq!(span_context.trace_id().to_string(), "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert!(extractor.keys().contains(&"traceparent"));
+    }
+}
@@ -0,0 +1,302 @@
+use rocket::async_trait;
+use std::sync::Arc;
+use tracing::{info, warn, instrument};
+
+use crate::config::AccountFlagsConfig;
+use crate::database::DbPool;
+use crate::models::{
+    auth::User,
+    business_results::AccountFlags,
+    profile::ProfileCompletion,
+};
+
+/// 新用户判定：注册天数
+const NEW_USER_DAYS: i64 = 7;
+/// 安全等级上限
+const MAX_SECURITY_LEVEL: u8 = 5;
+
+/// 账户标记提供者，每个提供者只负责设置自己的标记
+#[async_trait]
+pub trait FlagProvider: Send + Sync {
+    /// 提供者名称，用于日志
+    fn name(&self) -> &'static str;
+
+    /// 计算标记并写入 flags，返回的错误只记录日志，不影响其他提供者
+    async fn apply(&self, user: &User, flags: &mut AccountFlags) -> anyhow::Result<()>;
+}
+
+/// 账户标记计算流水线，按注册顺序依次执行已启用的提供者
+#[derive(Clone, Default)]
+pub struct AccountFlagPipeline {
+    providers: Vec<Arc<dyn FlagProvider>>,
+}
+
+impl AccountFlagPipeline {
+    pub fn new(providers: Vec<Arc<dyn FlagProvider>>) -> Self {
+        Self { providers }
+    }
+
+    /// 按配置创建流水线，关闭的提供者不参与计算
+    pub fn from_config(config: &AccountFlagsConfig, db_pool: DbPool) -> Self {
+        let mut providers: Vec<Arc<dyn FlagProvider>> = Vec::new();
+        if config.vip {
+            providers.push(Arc::new(VipProvider));
+        }
+        if config.new_user {
+            providers.push(Arc::new(NewUserProvider));
+        }
+        if config.notifications {
+            providers.push(Arc::new(NotificationProvider::new(db_pool.clone())));
+        }
+        if config.profile_completion {
+            providers.push(Arc::new(ProfileCompletionProvider));
+        }
+        if config.security_score {
+            providers.push(Arc::new(SecurityScoreProvider));
+        }
+        if config.policy_consent {
+            providers.push(Arc::new(PolicyConsentProvider::new(db_pool)));
+        }
+        info!(providers = ?providers.iter().map(|p| p.name()).collect::<Vec<_>>(), "Account flag providers enabled");
+        Self { providers }
+    }
+
+    /// 计算用户的账户标记
+    #[instrument(skip_all, name = "build_account_flags")]
+    pub async fn build(&self, user: &User) -> AccountFlags {
+        let mut flags = AccountFlags::default();
+        for provider in &self.providers {
+            if let Err(e) = provider.apply(user, &mut flags).await {
+                warn!(user_id = %user.id, provider = provider.name(), error = %e, "Account flag provider failed");
+            }
+        }
+        info!(
+            user_id = %user.id,
+            is_vip = %flags.is_vip,
+            is_new_user = %flags.is_new_user,
+            needs_profile_completion = %flags.needs_profile_completion,
+            security_level = %flags.security_level,
+            "Account flags built successfully"
+        );
+        flags
+    }
+}
+
+/// VIP 标记（简化逻辑：管理员视为 VIP）
+pub struct VipProvider;
+
+#[async_trait]
+impl FlagProvider for VipProvider {
+    fn name(&self) -> &'static str {
+        "vip"
+    }
+
+    async fn apply(&self, user: &User, flags: &mut AccountFlags) -> anyhow::Result<()> {
+        flags.is_vip = user.is_admin;
+        Ok(())
+    }
+}
+
+/// 新用户标记：注册未满 7 天
+pub struct NewUserProvider;
+
+#[async_trait]
+impl FlagProvider for NewUserProvider {
+    fn name(&self) -> &'static str {
+        "new_user"
+    }
+
+    async fn apply(&self, user: &User, flags: &mut AccountFlags) -> anyhow::Result<()> {
+        flags.is_new_user = user.created_at > chrono::Utc::now() - chrono::Duration::days(NEW_USER_DAYS);
+        Ok(())
+    }
+}
+
+/// 未读通知标记
+pub struct NotificationProvider {
+    db_pool: DbPool,
+}
+
+impl NotificationProvider {
+    pub fn new(db_pool: DbPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl FlagProvider for NotificationProvider {
+    fn name(&self) -> &'static str {
+        "notifications"
+    }
+
+    async fn apply(&self, user: &User, flags: &mut AccountFlags) -> anyhow::Result<()> {
+        use crate::database::notification::has_unread_notifications;
+
+        // 游客不会收到通知
+        if user.is_guest {
+            return Ok(());
+        }
+        flags.has_unread_notifications = has_unread_notifications(&self.db_pool, user.id).await?;
+        Ok(())
+    }
+}
+
+/// 资料完善标记，缺失字段用于生成资料完善步骤
+pub struct ProfileCompletionProvider;
+
+#[async_trait]
+impl FlagProvider for ProfileCompletionProvider {
+    fn name(&self) -> &'static str {
+        "profile_completion"
+    }
+
+    async fn apply(&self, user: &User, flags: &mut AccountFlags) -> anyhow::Result<()> {
+        let completion = ProfileCompletion::for_user(user);
+        flags.needs_profile_completion = !completion.completed;
+        flags.missing_profile_fields = completion.missing_fields;
+        Ok(())
+    }
+}
+
+/// 账户安全等级（1-5）：填写姓名、邮箱各加一级
+pub struct SecurityScoreProvider;
+
+#[async_trait]
+impl FlagProvider for SecurityScoreProvider {
+    fn name(&self) -> &'static str {
+        "security_score"
+    }
+
+    async fn apply(&self, user: &User, flags: &mut AccountFlags) -> anyhow::Result<()> {
+        let mut security_level = 1;
+        if user.full_name.is_some() {
+            security_level += 1;
+        }
+        if !user.email.is_empty() {
+            security_level += 1;
+        }
+        flags.security_level = security_level.min(MAX_SECURITY_LEVEL);
+        Ok(())
+    }
+}
+
+/// 协议同意标记：存在未同意的生效协议版本
+pub struct PolicyConsentProvider {
+    db_pool: DbPool,
+}
+
+impl PolicyConsentProvider {
+    pub fn new(db_pool: DbPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl FlagProvider for PolicyConsentProvider {
+    fn name(&self) -> &'static str {
+        "policy_consent"
+    }
+
+    async fn apply(&self, user: &User, flags: &mut AccountFlags) -> anyhow::Result<()> {
+        use crate::database::consent::pending_policy_documents;
+
+        let pending = pending_policy_documents(&self.db_pool, user.id).await?;
+        flags.needs_policy_consent = !pending.is_empty();
+        flags.pending_policies = pending.into_iter().map(|document| document.policy_type).collect();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use uuid::Uuid;
+
+    fn user() -> User {
+        User {
+            id: Uuid::new_v4(),
+            username: "alice".to_string(),
+            email: "alice@example.com".to_string(),
+            full_name: Some("Alice".to_string()),
+            avatar_url: None,
+            is_active: true,
+            is_admin: false,
+            is_guest: false,
+            wx_openid: None,
+            wx_unionid: None,
+            wx_session_key: None,
+            last_login_at: None,
+            created_at: Utc::now() - Duration::days(30),
+            updated_at: Utc::now(),
+            version: 1,
+        }
+    }
+
+    async fn apply(provider: impl FlagProvider, user: &User) -> AccountFlags {
+        let mut flags = AccountFlags::default();
+        provider.apply(user, &mut flags).await.unwrap();
+        flags
+    }
+
+    #[tokio::test]
+    async fn test_vip_provider() {
+        let mut admin = user();
+        admin.is_admin = true;
+        assert!(apply(VipProvider, &admin).await.is_vip);
+        assert!(!apply(VipProvider, &user()).await.is_vip);
+    }
+
+    #[tokio::test]
+    async fn test_new_user_provider() {
+        let mut new_user = user();
+        new_user.created_at = Utc::now() - Duration::days(1);
+        assert!(apply(NewUserProvider, &new_user).await.is_new_user);
+        assert!(!apply(NewUserProvider, &user()).await.is_new_user);
+    }
+
+    #[tokio::test]
+    async fn test_security_score_provider() {
+        assert_eq!(apply(SecurityScoreProvider, &user()).await.security_level, 3);
+
+        let mut guest = user();
+        guest.full_name = None;
+        guest.email = String::new();
+        assert_eq!(apply(SecurityScoreProvider, &guest).await.security_level, 1);
+    }
+
+    #[tokio::test]
+    async fn test_profile_completion_provider() {
+        let mut incomplete = user();
+        incomplete.full_name = None;
+        let flags = apply(ProfileCompletionProvider, &incomplete).await;
+        assert!(flags.needs_profile_completion);
+        assert!(!flags.missing_profile_fields.is_empty());
+        assert!(!apply(ProfileCompletionProvider, &user()).await.needs_profile_completion);
+    }
+
+    struct FailingProvider;
+
+    #[async_trait]
+    impl FlagProvider for FailingProvider {
+        fn name(&self) -> &'static str {
+            "failing"
+        }
+
+        async fn apply(&self, _user: &User, _flags: &mut AccountFlags) -> anyhow::Result<()> {
+            anyhow::bail!("provider unavailable")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_continues_after_provider_failure() {
+        let pipeline = AccountFlagPipeline::new(vec![Arc::new(FailingProvider), Arc::new(SecurityScoreProvider)]);
+        let flags = pipeline.build(&user()).await;
+        assert_eq!(flags.security_level, 3);
+
+        // 未启用任何提供者时返回默认标记
+        let flags = AccountFlagPipeline::default().build(&user()).await;
+        assert!(!flags.is_vip);
+        assert_eq!(flags.security_level, 1);
+    }
+}
@@ -0,0 +1,125 @@
+use chrono::Utc;
+use std::net::IpAddr;
+use tracing::{info, warn, error, instrument};
+use uuid::Uuid;
+
+use crate::auth::PasswordHasher;
+use crate::cache::{RedisPool, recovery::PasswordRecoveryCache, user::UserCache, session::SessionCache};
+use crate::database::DbPool;
+use crate::models::{
+    account_recovery::{PasswordRecoveryTicket, PasswordResetRequest, generate_recovery_token, hash_recovery_token},
+    audit::AuditEvent,
+};
+use super::{UseCaseError, UseCaseResult};
+
+/// 找回密码：已绑定微信且设置过密码的账户，通过微信登录验证身份后重置密码
+pub struct AccountRecoveryUseCase {
+    db_pool: DbPool,
+    redis: RedisPool,
+    password_hasher: PasswordHasher,
+}
+
+impl AccountRecoveryUseCase {
+    pub fn new(db_pool: DbPool, redis: RedisPool, password_hasher: PasswordHasher) -> Self {
+        Self { db_pool, redis, password_hasher }
+    }
+
+    /// 微信登录成功后签发找回凭据；账户未设置密码（纯微信账户）时返回 None
+    #[instrument(skip_all, name = "issue_password_recovery")]
+    pub async fn issue(&self, user_id: Uuid) -> UseCaseResult<Option<String>> {
+        use crate::database::profile::find_password_hash;
+
+        let has_password = find_password_hash(&self.db_pool, user_id).await?
+            .is_some_and(|hash| !hash.is_empty());
+        if !has_password {
+            return Ok(None);
+        }
+
+        let token = generate_recovery_token();
+        let ticket = PasswordRecoveryTicket { user_id, issued_at: Utc::now() };
+        PasswordRecoveryCache::new(self.redis.clone()).set(&hash_recovery_token(&token), &ticket).await
+            .map_err(|e| UseCaseError::InternalError(format!("签发找回凭据失败: {}", e)))?;
+
+        info!(user_id = %user_id, "Password recovery ticket issued");
+        Ok(Some(token))
+    }
+
+    /// 使用找回凭据重置密码，凭据只能使用一次；重置后该账户所有会话失效
+    #[instrument(skip_all, name = "reset_password")]
+    pub async fn reset_password(&self, request: PasswordResetRequest, ip_address: Option<IpAddr>) -> UseCaseResult<()> {
+        use crate::database::auth::reset_password_hash;
+        use crate::database::account::delete_user_sessions;
+        use crate::database::audit::record_audit_event;
+
+        Self::validate_new_password(&request.new_password)?;
+
+        let invalid = || UseCaseError::ValidationError("找回凭据无效或已过期，请重新通过微信验证".to_string());
+        if request.recovery_token.is_empty() || request.recovery_token.len() > 128 {
+            return Err(invalid());
+        }
+        let ticket = PasswordRecoveryCache::new(self.redis.clone()).take(&hash_recovery_token(&request.recovery_token)).await
+            .map_err(|e| UseCaseError::InternalError(format!("读取找回凭据失败: {}", e)))?
+            .ok_or_else(invalid)?;
+        let user_id = ticket.user_id;
+
+        let password_hash = self.password_hasher.hash_blocking(&request.new_password).await.map_err(|e| {
+            error!(user_id = %user_id, error = %e, "Failed to hash recovered password");
+            UseCaseError::InternalError("密码处理失败".to_string())
+        })?;
+        let username = reset_password_hash(&self.db_pool, user_id, &password_hash).await?
+            .ok_or_else(|| UseCaseError::BusinessLogicError("账户不存在或已注销".to_string()))?;
+
+        let revoked_sessions = delete_user_sessions(&self.db_pool, user_id).await?;
+        self.invalidate_cached_user(user_id, &username).await;
+
+        let event = AuditEvent::new("account.password_recovered", "user")
+            .actor(user_id)
+            .target(user_id)
+            .ip(ip_address)
+            .details(serde_json::json!({ "method": "wechat", "revoked_sessions": revoked_sessions }));
+        if let Err(e) = record_audit_event(&self.db_pool, &event).await {
+            error!(user_id = %user_id, error = %e, "Failed to record password recovery audit event");
+        }
+
+        info!(user_id = %user_id, revoked_sessions = %revoked_sessions, "Password reset via WeChat recovery");
+        Ok(())
+    }
+
+    // 与注册时的密码规则一致
+    fn validate_new_password(password: &str) -> UseCaseResult<()> {
+        if password.len() < 6 || password.len() > 30 {
+            return Err(UseCaseError::ValidationError("密码长度必须在6-30个字符之间".to_string()));
+        }
+        Ok(())
+    }
+
+    // 清除会话缓存和登录失败计数，旧会话失效，账户因多次输错密码的锁定一并解除
+    async fn invalidate_cached_user(&self, user_id: Uuid, username: &str) {
+        let user_cache = UserCache::new(self.redis.clone());
+        let session_cache = SessionCache::new(self.redis.clone());
+        if let Err(e) = session_cache.invalidate_user_sessions(user_id).await {
+            warn!(user_id = %user_id, error = %e, "Failed to invalidate session cache");
+        }
+        if let Err(e) = user_cache.clear_login_failures(username).await {
+            warn!(user_id = %user_id, error = %e, "Failed to clear login failures");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_new_password() {
+        assert!(AccountRecoveryUseCase::validate_new_password("new-secret").is_ok());
+        assert!(matches!(
+            AccountRecoveryUseCase::validate_new_password("12345"),
+            Err(UseCaseError::ValidationError(_))
+        ));
+        assert!(matches!(
+            AccountRecoveryUseCase::validate_new_password(&"x".repeat(31)),
+            Err(UseCaseError::ValidationError(_))
+        ));
+    }
+}
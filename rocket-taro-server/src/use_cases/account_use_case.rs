@@ -0,0 +1,130 @@
+use std::net::IpAddr;
+use chrono::Duration;
+use serde_json::json;
+use tracing::{info, warn, error, instrument};
+
+use crate::cache::{RedisPool, user::UserCache, session::SessionCache};
+use crate::database::DbPool;
+use crate::models::{
+    audit::AuditEvent,
+    auth::User,
+    business_results::AccountDeletionResult,
+};
+use super::{UseCaseError, UseCaseResult};
+
+/// 单次匿名化任务处理的最大用户数
+const ANONYMIZATION_BATCH_SIZE: i64 = 100;
+
+/// 账户生命周期用例（注销、匿名化）
+pub struct AccountUseCase {
+    db_pool: DbPool,
+    redis: RedisPool,
+}
+
+impl AccountUseCase {
+    pub fn new(db_pool: DbPool, redis: RedisPool) -> Self {
+        Self { db_pool, redis }
+    }
+
+    /// 注销账户 - 软删除并立即撤销所有会话和缓存
+    #[instrument(skip_all, name = "execute_delete_account")]
+    pub async fn execute_delete_account(
+        &self,
+        user: &User,
+        grace_period: Duration,
+        ip_address: Option<IpAddr>,
+    ) -> UseCaseResult<AccountDeletionResult> {
+        use crate::database::account::{soft_delete_user, delete_user_sessions};
+        use crate::database::audit::record_audit_event;
+
+        info!(user_id = %user.id, "Processing account deletion request");
+
+        if user.is_admin {
+            warn!(user_id = %user.id, "Admin account deletion rejected");
+            return Err(UseCaseError::BusinessLogicError("管理员账户不能自助注销".to_string()));
+        }
+
+        let deleted_at = soft_delete_user(&self.db_pool, user.id).await?
+            .ok_or_else(|| UseCaseError::BusinessLogicError("账户已注销".to_string()))?;
+
+        let sessions_revoked = delete_user_sessions(&self.db_pool, user.id).await.unwrap_or_else(|e| {
+            error!(user_id = %user.id, error = %e, "Failed to delete user sessions");
+            0
+        });
+
+        self.revoke_cached_state(user).await;
+
+        let anonymize_after = deleted_at + grace_period;
+        let event = AuditEvent::new("account.delete_requested", "user")
+            .actor(user.id)
+            .target(user.id)
+            .ip(ip_address)
+            .details(json!({
+                "sessions_revoked": sessions_revoked,
+                "anonymize_after": anonymize_after,
+            }));
+        if let Err(e) = record_audit_event(&self.db_pool, &event).await {
+            error!(user_id = %user.id, error = %e, "Failed to record account deletion audit event");
+        }
+
+        info!(user_id = %user.id, sessions_revoked = %sessions_revoked, "Account soft-deleted");
+        Ok(AccountDeletionResult {
+            user_id: user.id,
+            sessions_revoked,
+            deleted_at,
+            anonymize_after,
+        })
+    }
+
+    /// 匿名化超过保留期的注销账户，返回处理的账户数量
+    #[instrument(skip_all, name = "anonymize_expired_accounts")]
+    pub async fn anonymize_expired_accounts(&self, grace_period: Duration) -> UseCaseResult<u64> {
+        use crate::database::account::{find_users_pending_anonymization, anonymize_user};
+        use crate::database::audit::record_audit_event;
+        use crate::database::avatar::delete_user_avatars;
+        use super::avatar_use_case::remove_avatar_files;
+
+        let user_ids = find_users_pending_anonymization(&self.db_pool, grace_period, ANONYMIZATION_BATCH_SIZE).await?;
+        let mut anonymized = 0;
+
+        for user_id in user_ids {
+            match anonymize_user(&self.db_pool, user_id).await {
+                Ok(true) => {
+                    anonymized += 1;
+                    // 上传的头像通过公开地址访问，匿名化时一并删除
+                    match delete_user_avatars(&self.db_pool, user_id).await {
+                        Ok(file_paths) => remove_avatar_files(&file_paths).await,
+                        Err(e) => error!(user_id = %user_id, error = %e, "Failed to delete user avatars"),
+                    }
+                    let event = AuditEvent::new("account.anonymized", "user").target(user_id);
+                    if let Err(e) = record_audit_event(&self.db_pool, &event).await {
+                        error!(user_id = %user_id, error = %e, "Failed to record anonymization audit event");
+                    }
+                }
+                Ok(false) => {}
+                Err(e) => error!(user_id = %user_id, error = %e, "Failed to anonymize user"),
+            }
+        }
+
+        if anonymized > 0 {
+            info!(count = %anonymized, "Anonymized deleted accounts");
+        }
+        Ok(anonymized)
+    }
+
+    /// 清除该用户相关的全部缓存
+    async fn revoke_cached_state(&self, user: &User) {
+        let user_cache = UserCache::new(self.redis.clone());
+        let session_cache = SessionCache::new(self.redis.clone());
+
+        if let Err(e) = session_cache.invalidate_user_sessions(user.id).await {
+            warn!(user_id = %user.id, error = %e, "Failed to invalidate cached sessions");
+        }
+        if let Err(e) = user_cache.invalidate_user(user.id).await {
+            warn!(user_id = %user.id, error = %e, "Failed to invalidate cached user");
+        }
+        if let Err(e) = user_cache.invalidate_username(&user.username).await {
+            warn!(user_id = %user.id, error = %e, "Failed to invalidate username mapping");
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use std::collections::BTreeMap;
+use chrono::{Duration, Utc};
+use tracing::{info, warn, instrument};
+
+use crate::cache::{RedisPool, admin_stats::AdminStatsCache};
+use crate::database::DbPool;
+use crate::models::admin_stats::{AdminStats, DailyStats, IndexUsage, StatsRange, StatsSummary};
+use super::UseCaseResult;
+
+/// 统计在线用户的时间窗口（分钟）
+const ONLINE_WINDOW_MINUTES: i64 = 15;
+
+/// 管理后台统计用例
+pub struct AdminStatsUseCase {
+    db_pool: DbPool,
+    redis: RedisPool,
+}
+
+impl AdminStatsUseCase {
+    pub fn new(db_pool: DbPool, redis: RedisPool) -> Self {
+        Self { db_pool, redis }
+    }
+
+    /// 获取统计数据，优先读取缓存；在线用户数不缓存
+    #[instrument(skip_all, name = "get_admin_stats")]
+    pub async fn get_stats(&self, range: StatsRange) -> UseCaseResult<AdminStats> {
+        use crate::database::admin_stats::count_recently_active_users;
+
+        let cache = AdminStatsCache::new(self.redis.clone());
+        let cached = match cache.get_stats(&range).await {
+            Ok(stats) => stats,
+            Err(e) => {
+                warn!(error = %e, "Admin stats cache lookup failed, computing from database");
+                None
+            }
+        };
+
+        let mut stats = match cached {
+            Some(stats) => stats,
+            None => {
+                let stats = self.compute_stats(range).await?;
+                if let Err(e) = cache.cache_stats(&stats).await {
+                    warn!(error = %e, "Failed to cache admin stats");
+                }
+                stats
+            }
+        };
+
+        let since = Utc::now() - Duration::minutes(ONLINE_WINDOW_MINUTES);
+        stats.online_users = count_recently_active_users(&self.db_pool, since).await?;
+        Ok(stats)
+    }
+
+    /// 数据库索引使用情况，用于确认热点查询命中了索引；实时查询，不缓存
+    #[instrument(skip_all, name = "get_index_usage")]
+    pub async fn index_usage(&self) -> UseCaseResult<Vec<IndexUsage>> {
+        Ok(crate::database::admin_stats::index_usage(&self.db_pool).await?)
+    }
+
+    /// 通过 SQL 聚合计算统计数据
+    async fn compute_stats(&self, range: StatsRange) -> UseCaseResult<AdminStats> {
+        use crate::database::admin_stats::{
+            daily_login_counts, daily_created_counts, count_active_users,
+            registrations_by_method, active_users_by_method, overall_totals, DailyCountTable,
+        };
+
+        info!(from = %range.from, to = %range.to, "Computing admin stats");
+
+        let mut daily: BTreeMap<_, _> = range.days().into_iter()
+            .map(|day| (day, DailyStats::empty(day)))
+            .collect();
+
+        for (day, active_users, login_success, login_failure) in daily_login_counts(&self.db_pool, &range).await? {
+            if let Some(stats) = daily.get_mut(&day) {
+                stats.active_users = active_users;
+                stats.login_success = login_success;
+                stats.login_failure = login_failure;
+            }
+        }
+        for (day, count) in daily_created_counts(&self.db_pool, DailyCountTable::Users, &range).await? {
+            if let Some(stats) = daily.get_mut(&day) {
+                stats.new_registrations = count;
+            }
+        }
+        for (day, count) in daily_created_counts(&self.db_pool, DailyCountTable::UserData, &range).await? {
+            if let Some(stats) = daily.get_mut(&day) {
+                stats.user_data_submissions = count;
+            }
+        }
+
+        let daily: Vec<DailyStats> = daily.into_values().collect();
+        let (total_users, total_user_data) = overall_totals(&self.db_pool).await?;
+        let summary = StatsSummary {
+            active_users: count_active_users(&self.db_pool, &range).await?,
+            login_success: daily.iter().map(|d| d.login_success).sum(),
+            login_failure: daily.iter().map(|d| d.login_failure).sum(),
+            new_registrations: daily.iter().map(|d| d.new_registrations).sum(),
+            user_data_submissions: daily.iter().map(|d| d.user_data_submissions).sum(),
+            total_users,
+            total_user_data,
+        };
+
+        Ok(AdminStats {
+            range,
+            generated_at: Utc::now(),
+            summary,
+            daily,
+            registrations_by_method: registrations_by_method(&self.db_pool, &range).await?,
+            active_users_by_method: active_users_by_method(&self.db_pool, &range).await?,
+            online_users: 0,
+            runtime: None,
+        })
+    }
+}
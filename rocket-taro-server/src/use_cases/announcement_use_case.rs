@@ -0,0 +1,149 @@
+use chrono::Utc;
+use tracing::{info, instrument};
+use uuid::Uuid;
+
+use crate::config::Platform;
+use crate::database::DbPool;
+use crate::models::{
+    announcement::{Announcement, AnnouncementRequest, AnnouncementView},
+    auth::User,
+    business_results::AccountFlags,
+};
+use super::{UseCaseError, UseCaseResult};
+
+/// 公告标题最大长度（字符）
+const MAX_TITLE_CHARS: usize = 100;
+/// 公告内容最大长度（字符）
+const MAX_CONTENT_CHARS: usize = 2000;
+
+/// 应用内公告：管理端维护，登录和认证状态响应中按受众下发
+pub struct AnnouncementUseCase {
+    db_pool: DbPool,
+}
+
+impl AnnouncementUseCase {
+    pub fn new(db_pool: DbPool) -> Self {
+        Self { db_pool }
+    }
+
+    /// 列出全部公告（管理端）
+    pub async fn list(&self) -> UseCaseResult<Vec<Announcement>> {
+        use crate::database::announcement::list_announcements;
+
+        Ok(list_announcements(&self.db_pool).await?)
+    }
+
+    /// 创建公告
+    #[instrument(skip_all, name = "create_announcement")]
+    pub async fn create(&self, admin: &User, request: AnnouncementRequest) -> UseCaseResult<Announcement> {
+        use crate::database::announcement::create_announcement;
+
+        let starts_at = Self::validate(&request)?;
+        let announcement = create_announcement(&self.db_pool, &request, starts_at, admin.id).await?;
+        info!(announcement_id = %announcement.id, admin_id = %admin.id, "Announcement created");
+        Ok(announcement)
+    }
+
+    /// 更新公告
+    #[instrument(skip_all, name = "update_announcement")]
+    pub async fn update(&self, admin: &User, id: Uuid, request: AnnouncementRequest) -> UseCaseResult<Announcement> {
+        use crate::database::announcement::update_announcement;
+
+        let starts_at = Self::validate(&request)?;
+        let announcement = update_announcement(&self.db_pool, id, &request, starts_at).await?
+            .ok_or_else(|| UseCaseError::ValidationError("公告不存在".to_string()))?;
+        info!(announcement_id = %id, admin_id = %admin.id, "Announcement updated");
+        Ok(announcement)
+    }
+
+    /// 删除公告
+    #[instrument(skip_all, name = "delete_announcement")]
+    pub async fn delete(&self, admin: &User, id: Uuid) -> UseCaseResult<()> {
+        use crate::database::announcement::delete_announcement;
+
+        if !delete_announcement(&self.db_pool, id).await? {
+            return Err(UseCaseError::ValidationError("公告不存在".to_string()));
+        }
+        info!(announcement_id = %id, admin_id = %admin.id, "Announcement deleted");
+        Ok(())
+    }
+
+    /// 当前应展示给用户的公告（有效期内、匹配受众、未被关闭）
+    #[instrument(skip_all, name = "get_active_announcements")]
+    pub async fn active_for(&self, user: &User, flags: &AccountFlags, platform: Platform) -> UseCaseResult<Vec<AnnouncementView>> {
+        use crate::database::announcement::list_active_announcements;
+
+        let now = Utc::now();
+        let announcements = list_active_announcements(&self.db_pool, user.id, now).await?;
+        Ok(announcements.into_iter()
+            .filter(|announcement| announcement.is_visible_to(platform, user, flags, now))
+            .map(AnnouncementView::from)
+            .collect())
+    }
+
+    /// 用户关闭公告，之后不再下发给该用户
+    #[instrument(skip_all, name = "dismiss_announcement")]
+    pub async fn dismiss(&self, user: &User, id: Uuid) -> UseCaseResult<()> {
+        use crate::database::announcement::{dismiss_announcement, get_announcement};
+
+        let announcement = get_announcement(&self.db_pool, id).await?
+            .ok_or_else(|| UseCaseError::ValidationError("公告不存在".to_string()))?;
+        if !announcement.dismissible {
+            return Err(UseCaseError::BusinessLogicError("该公告不可关闭".to_string()));
+        }
+
+        dismiss_announcement(&self.db_pool, user.id, id).await?;
+        info!(announcement_id = %id, user_id = %user.id, "Announcement dismissed");
+        Ok(())
+    }
+
+    /// 校验请求并返回生效时间
+    fn validate(request: &AnnouncementRequest) -> UseCaseResult<chrono::DateTime<Utc>> {
+        let title = request.title.trim();
+        if title.is_empty() || title.chars().count() > MAX_TITLE_CHARS {
+            return Err(UseCaseError::ValidationError(format!("公告标题不能为空且不能超过{}个字符", MAX_TITLE_CHARS)));
+        }
+        let content = request.content.trim();
+        if content.is_empty() || content.chars().count() > MAX_CONTENT_CHARS {
+            return Err(UseCaseError::ValidationError(format!("公告内容不能为空且不能超过{}个字符", MAX_CONTENT_CHARS)));
+        }
+
+        let starts_at = request.starts_at.unwrap_or_else(Utc::now);
+        if request.ends_at.is_some_and(|ends_at| ends_at <= starts_at) {
+            return Err(UseCaseError::ValidationError("结束时间必须晚于开始时间".to_string()));
+        }
+        Ok(starts_at)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use crate::models::announcement::AnnouncementAudience;
+
+    fn request(title: &str, ends_in: Option<Duration>) -> AnnouncementRequest {
+        AnnouncementRequest {
+            title: title.to_string(),
+            content: "今晚22点进行系统升级".to_string(),
+            level: Default::default(),
+            dismissible: true,
+            audience: AnnouncementAudience::default(),
+            starts_at: None,
+            ends_at: ends_in.map(|duration| Utc::now() + duration),
+        }
+    }
+
+    #[test]
+    fn test_validate_request() {
+        assert!(AnnouncementUseCase::validate(&request("系统升级", Some(Duration::hours(2)))).is_ok());
+        assert!(matches!(
+            AnnouncementUseCase::validate(&request("  ", None)),
+            Err(UseCaseError::ValidationError(_))
+        ));
+        assert!(matches!(
+            AnnouncementUseCase::validate(&request("系统升级", Some(Duration::hours(-1)))),
+            Err(UseCaseError::ValidationError(_))
+        ));
+    }
+}
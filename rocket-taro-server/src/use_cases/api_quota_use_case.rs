@@ -0,0 +1,143 @@
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use std::net::IpAddr;
+use tracing::{debug, info, instrument, warn};
+use uuid::Uuid;
+
+use crate::cache::{RedisPool, api_quota::ApiQuotaCache};
+use crate::config::{ApiQuotasConfig, api_quotas::QuotaGroup};
+use crate::database::DbPool;
+use crate::models::api_quota::{QuotaCaller, QuotaOverride, QuotaStatus, QuotaSubject, quota_window};
+use crate::models::audit::AuditEvent;
+use super::{UseCaseError, UseCaseResult};
+
+/// 计数器在周期结束后多保留的秒数，避免时钟误差导致提前清零
+const WINDOW_GRACE_SECS: u64 = 60;
+
+/// 接口每日配额用例：按调用方计数，上限依次取用户覆盖、角色覆盖、配置中的角色上限和分组默认值
+pub struct ApiQuotaUseCase {
+    db_pool: DbPool,
+    redis: RedisPool,
+    config: ApiQuotasConfig,
+}
+
+impl ApiQuotaUseCase {
+    pub fn new(db_pool: DbPool, redis: RedisPool, config: ApiQuotasConfig) -> Self {
+        Self { db_pool, redis, config }
+    }
+
+    /// 调用方适用的每日上限，0 表示不限制；覆盖读取失败时使用配置中的上限
+    pub async fn limit_for(&self, group: &QuotaGroup, caller: &QuotaCaller) -> u64 {
+        use crate::database::api_quota::find_quota_overrides;
+
+        let role_subject = QuotaSubject::Role(caller.role()).as_string();
+        let mut subjects = vec![role_subject.clone()];
+        if let QuotaCaller::User { user_id, .. } = caller {
+            subjects.push(QuotaSubject::User(*user_id).as_string());
+        }
+
+        let overrides = match find_quota_overrides(&self.db_pool, &group.name, &subjects).await {
+            Ok(overrides) => overrides,
+            Err(e) => {
+                warn!(group = %group.name, error = %e, "Failed to load quota overrides, using configured limits");
+                Vec::new()
+            }
+        };
+        // 用户覆盖优先于角色覆盖
+        overrides.iter()
+            .max_by_key(|entry| entry.subject != role_subject)
+            .map(|entry| entry.daily_limit.max(0) as u64)
+            .unwrap_or_else(|| group.limit_for(caller.role()))
+    }
+
+    /// 计入一次请求；不限制或 Redis 不可用时返回 None（放行且不返回配额头）
+    #[instrument(skip_all, name = "consume_api_quota", fields(group = %group.name))]
+    pub async fn consume(&self, group: &QuotaGroup, caller: &QuotaCaller, now: DateTime<Utc>) -> Option<QuotaStatus> {
+        let limit = self.limit_for(group, caller).await;
+        if limit == 0 {
+            return None;
+        }
+
+        let (day, reset_secs) = quota_window(now);
+        let cache = ApiQuotaCache::new(self.redis.clone());
+        match cache.consume(&group.name, &caller.key_id(), day, (reset_secs + WINDOW_GRACE_SECS) as usize).await {
+            Ok(used) => Some(QuotaStatus::new(&group.name, limit, used, reset_secs)),
+            Err(e) => {
+                debug!(error = %e, "Quota counter unavailable, allowing request");
+                None
+            }
+        }
+    }
+
+    /// 全部覆盖（管理端）
+    pub async fn list_overrides(&self) -> UseCaseResult<Vec<QuotaOverride>> {
+        use crate::database::api_quota::list_quota_overrides;
+
+        Ok(list_quota_overrides(&self.db_pool).await?)
+    }
+
+    /// 写入覆盖并记录审计日志
+    #[instrument(skip_all, name = "set_quota_override")]
+    pub async fn set_override(
+        &self,
+        group_name: &str,
+        subject: &str,
+        daily_limit: i64,
+        admin_id: Uuid,
+        ip_address: Option<IpAddr>,
+    ) -> UseCaseResult<QuotaOverride> {
+        use crate::database::api_quota::upsert_quota_override;
+
+        let subject = self.validate(group_name, subject)?;
+        if daily_limit < 0 {
+            return Err(UseCaseError::ValidationError("每日上限不能为负数".to_string()));
+        }
+
+        let entry = upsert_quota_override(&self.db_pool, group_name, &subject.as_string(), daily_limit, admin_id).await?;
+        info!(admin_id = %admin_id, group = %group_name, subject = %entry.subject, daily_limit, "Quota override updated");
+        self.audit("quota.override_set", &entry, admin_id, ip_address).await;
+        Ok(entry)
+    }
+
+    /// 删除覆盖，不存在时返回 None
+    #[instrument(skip_all, name = "delete_quota_override")]
+    pub async fn delete_override(
+        &self,
+        group_name: &str,
+        subject: &str,
+        admin_id: Uuid,
+        ip_address: Option<IpAddr>,
+    ) -> UseCaseResult<Option<QuotaOverride>> {
+        use crate::database::api_quota::delete_quota_override;
+
+        let subject = self.validate(group_name, subject)?;
+        let deleted = delete_quota_override(&self.db_pool, group_name, &subject.as_string()).await?;
+        if let Some(entry) = &deleted {
+            info!(admin_id = %admin_id, group = %group_name, subject = %entry.subject, "Quota override deleted");
+            self.audit("quota.override_deleted", entry, admin_id, ip_address).await;
+        }
+        Ok(deleted)
+    }
+
+    fn validate(&self, group_name: &str, subject: &str) -> UseCaseResult<QuotaSubject> {
+        if self.config.group(group_name).is_none() {
+            return Err(UseCaseError::ValidationError(format!("未知的配额分组: {}", group_name)));
+        }
+        QuotaSubject::parse(subject)
+            .ok_or_else(|| UseCaseError::ValidationError("覆盖对象格式应为 user:<用户ID> 或 role:<anonymous|guest|user|admin>".to_string()))
+    }
+
+    async fn audit(&self, action: &str, entry: &QuotaOverride, admin_id: Uuid, ip_address: Option<IpAddr>) {
+        use crate::database::audit::record_audit_event;
+
+        let event = AuditEvent::new(action, "api_quota")
+            .actor(admin_id)
+            .target(format!("{}:{}", entry.group_name, entry.subject))
+            .ip(ip_address)
+            .details(json!({ "daily_limit": entry.daily_limit }));
+        // 覆盖已生效，审计日志写入失败只记录日志
+        if let Err(e) = record_audit_event(&self.db_pool, &event).await {
+            warn!(error = %e, "Failed to record quota override audit event");
+        }
+    }
+}
@@ -1,29 +1,78 @@
 use serde_json::json;
 use tracing::{info, warn, error, instrument};
 
+use crate::auth::PasswordHasher;
+use crate::cache::RedisPool;
 use crate::database::DbPool;
 use crate::models::{
-    auth::{LoginRequest, RegisterRequest, User, UserInfo, UserSession},
+    auth::{LoginRequest, RegisterRequest, User, UserInfo},
     route_command::RouteCommand,
-    business_results::{LoginResult, LogoutResult, AccountFlags},
+    route_decision::{RouteDecision, RouteTrace},
+    business_results::{LoginResult, LogoutResult, RegisterResult},
 };
-use crate::config::{RouteConfig, Platform};
-use super::{UseCase, UseCaseError, UseCaseResult, route_command_generator::RouteCommandGenerator};
+use crate::config::{RouteConfig, ExperimentsConfig, Platform, route_keys};
+use crate::events::{AuthMethod, DomainEvent, EventBus};
+use super::{UseCase, UseCaseError, UseCaseResult, account_flags::AccountFlagPipeline, draft_use_case::DraftUseCase, route_command_generator::RouteCommandGenerator, session_issuer::{RequestContext, SessionIssuer}, login_timeouts::{Dependency, LoginTimeouts}, registration_guard_use_case::RegistrationGuardUseCase};
 
 /// 认证用例，处理用户登录相关的业务逻辑
 pub struct AuthUseCase {
     db_pool: DbPool,
     route_config: RouteConfig,
+    experiments: ExperimentsConfig,
+    events: EventBus,
+    password_hasher: PasswordHasher,
+    flag_pipeline: AccountFlagPipeline,
+    redis: Option<RedisPool>,
+    timeouts: LoginTimeouts,
+    registration_guard: Option<RegistrationGuardUseCase>,
 }
 
 impl AuthUseCase {
-    pub fn new(db_pool: DbPool, route_config: RouteConfig) -> Self {
-        Self { db_pool, route_config }
+    pub fn new(db_pool: DbPool, route_config: RouteConfig, events: EventBus, password_hasher: PasswordHasher) -> Self {
+        Self { db_pool, route_config, experiments: ExperimentsConfig::default(), events, password_hasher, flag_pipeline: AccountFlagPipeline::default(), redis: None, timeouts: LoginTimeouts::default(), registration_guard: None }
+    }
+
+    /// 设置登录时使用的账户标记流水线，未设置时不计算任何标记
+    pub fn with_flag_pipeline(mut self, flag_pipeline: AccountFlagPipeline) -> Self {
+        self.flag_pipeline = flag_pipeline;
+        self
+    }
+
+    /// 设置登录引导等流程参与的 A/B 实验，未设置时所有用户走对照组的流程
+    pub fn with_experiments(mut self, experiments: ExperimentsConfig) -> Self {
+        self.experiments = experiments;
+        self
+    }
+
+    /// 设置表单草稿所在的缓存，未设置时登出不检查未保存的数据
+    pub fn with_redis(mut self, redis: RedisPool) -> Self {
+        self.redis = Some(redis);
+        self
+    }
+
+    /// 设置登录访问各依赖的时间预算，未设置时使用默认预算
+    pub fn with_timeouts(mut self, timeouts: LoginTimeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// 设置注册防自动化检查，未设置时注册不做蜜罐、一次性邮箱和频率检查
+    pub fn with_registration_guard(mut self, registration_guard: RegistrationGuardUseCase) -> Self {
+        self.registration_guard = Some(registration_guard);
+        self
+    }
+
+    fn session_issuer(&self) -> SessionIssuer {
+        SessionIssuer::new(self.db_pool.clone(), self.events.clone()).with_timeouts(self.timeouts.clone())
     }
 
     /// 处理用户登录请求 - 纯业务逻辑
     #[instrument(skip_all, name = "execute_login")]
-    pub async fn execute_login(&self, request: LoginRequest) -> UseCaseResult<LoginResult> {
+    pub async fn execute_login(
+        &self,
+        request: LoginRequest,
+        context: RequestContext,
+    ) -> UseCaseResult<LoginResult> {
         info!("Processing login request for user: {}", request.username);
 
         // 1. 验证用户凭据
@@ -31,6 +80,7 @@ impl AuthUseCase {
             Some(user) => user,
             None => {
                 warn!("Login failed for user: {} - invalid credentials", request.username);
+                self.publish_login_failed(&request.username, "认证失败", context).await;
                 return Err(UseCaseError::AuthenticationError("用户名或密码错误".to_string()));
             }
         };
@@ -38,21 +88,20 @@ impl AuthUseCase {
         // 2. 检查用户状态
         if !user.is_active {
             warn!("Login attempt for inactive user: {}", user.username);
+            self.publish_login_failed(&request.username, "账户已被禁用", context).await;
             return Err(UseCaseError::AuthenticationError("账户已被禁用".to_string()));
         }
 
-        // 3. 创建用户会话
-        let session = self.create_session(&user).await.map_err(|e| {
-            error!("Failed to create session for user {}: {}", user.username, e);
-            UseCaseError::InternalError("会话创建失败".to_string())
+        // 3. 签发会话（同时更新最后登录时间、发布登录事件）
+        let session = self.session_issuer().issue(&user, AuthMethod::Password, &context).await.map_err(|e| match e {
+            UseCaseError::TimeoutError(_) => e,
+            e => {
+                error!("Failed to create session for user {}: {}", user.username, e);
+                UseCaseError::InternalError("会话创建失败".to_string())
+            }
         })?;
 
-        // 4. 更新最后登录时间
-        if let Err(e) = self.update_last_login(&user).await {
-            warn!("Failed to update last login time for user {}: {}", user.username, e);
-        }
-
-        // 5. 构建业务结果
+        // 4. 构建业务结果
         let mut login_result = LoginResult::new(user.clone(), session);
         
         // 检查待处理任务
@@ -60,7 +109,7 @@ impl AuthUseCase {
         login_result = login_result.with_pending_tasks(pending_tasks);
         
         // 设置账户标记
-        let account_flags = self.build_account_flags(&user).await?;
+        let account_flags = self.flag_pipeline.build(&user).await;
         login_result = login_result.with_account_flags(account_flags);
         
         // 检查是否需要更新密码
@@ -71,130 +120,93 @@ impl AuthUseCase {
         Ok(login_result)
     }
 
+    /// 发布登录失败事件
+    async fn publish_login_failed(&self, username: &str, reason: &str, context: RequestContext) {
+        self.events.publish(DomainEvent::UserLoginFailed {
+            username: username.to_string(),
+            reason: reason.to_string(),
+            ip_address: context.ip_address,
+            user_agent: context.user_agent,
+        }).await;
+    }
+
+    /// 处理用户登录请求，同时返回登录结果、路由指令和生成指令时命中的路由决策，路由层据此设置会话 Cookie
+    pub async fn execute_login_with_route(
+        &self,
+        request: LoginRequest,
+        context: RequestContext,
+        platform: Platform,
+    ) -> UseCaseResult<(LoginResult, RouteCommand, Vec<RouteDecision>)> {
+        let login_result = self.execute_login(request, context).await?;
+        let (route_command, decisions) = RouteCommandGenerator::traced(|trace| {
+            RouteCommandGenerator::generate_login_route_command(&login_result, &self.route_config, &self.experiments, platform, trace)
+        });
+        Ok((login_result, route_command, decisions))
+    }
+
     /// 处理用户登录请求 - 包含路由决策（保留向后兼容）
-    pub async fn handle_login(&self, request: LoginRequest, platform: Platform) -> UseCaseResult<RouteCommand> {
-        match self.execute_login(request).await {
+    pub async fn handle_login(&self, request: LoginRequest, context: RequestContext, platform: Platform) -> UseCaseResult<RouteCommand> {
+        match self.execute_login(request, context).await {
             Ok(login_result) => {
-                Ok(RouteCommandGenerator::generate_login_route_command(&login_result, &self.route_config, platform))
+                Ok(RouteCommandGenerator::generate_login_route_command(&login_result, &self.route_config, &self.experiments, platform, &mut RouteTrace::default()))
             }
             Err(e) => {
-                let error_code = match &e {
-                    UseCaseError::AuthenticationError(_) => Some("AUTH_INVALID_CREDENTIALS"),
-                    UseCaseError::DatabaseError(_) => Some("DATABASE_ERROR"),
-                    _ => None,
-                };
-                Ok(RouteCommandGenerator::generate_error_route_command(&e.to_string(), error_code, &self.route_config, platform))
+                Ok(RouteCommandGenerator::generate_login_failed_route_command(&e, &self.route_config, platform, &mut RouteTrace::default()))
             }
         }
     }
 
-    /// 验证用户凭据
+    /// 验证用户凭据，旧算法或旧参数的密码哈希在验证通过后重新哈希
     #[instrument(skip_all, name = "authenticate_user")]
     async fn authenticate_user(&self, request: &LoginRequest) -> UseCaseResult<Option<User>> {
-        use crate::database::auth::authenticate_user;
+        use crate::database::auth::find_user_credentials;
         
         info!(username = %request.username, "Authenticating user credentials");
         
-        match authenticate_user(&self.db_pool, request).await {
-            Ok(Some(user)) => {
-                info!(user_id = %user.id, username = %user.username, "User authentication successful");
-                Ok(Some(user))
-            }
+        let (user, password_hash) = match self.timeouts.run(Dependency::Postgres, find_user_credentials(&self.db_pool, &request.username)).await? {
+            Ok(Some(credentials)) => credentials,
             Ok(None) => {
-                warn!(username = %request.username, "User authentication failed: invalid credentials");
-                Ok(None)
+                // 与用户存在但密码错误时耗时一致，避免通过响应时间枚举用户名
+                self.password_hasher.verify_dummy_blocking(&request.password).await;
+                warn!(username = %request.username, "User authentication failed: user not found");
+                return Ok(None);
             }
             Err(e) => {
                 error!(username = %request.username, error = %e, "Database error during authentication");
-                Err(UseCaseError::DatabaseError(e.to_string()))
+                return Err(UseCaseError::DatabaseError(e.to_string()));
             }
+        };
+
+        if !self.password_hasher.verify_blocking(&request.password, &password_hash).await {
+            warn!(username = %request.username, "User authentication failed: invalid credentials");
+            return Ok(None);
         }
-    }
 
-    /// 创建用户会话
-    #[instrument(skip_all, name = "create_session")]
-    async fn create_session(&self, user: &User) -> UseCaseResult<UserSession> {
-        use crate::database::auth::create_user_session;
-        
-        info!(user_id = %user.id, username = %user.username, "Creating user session");
-        
-        create_user_session(
-            &self.db_pool,
-            user.id,
-            None, // user_agent 可以后续传入
-            None, // ip_address 可以后续传入
-        ).await.map_err(|e| {
-            error!(user_id = %user.id, error = %e, "Failed to create session");
-            UseCaseError::DatabaseError(e.to_string())
-        }).map(|session| {
-            info!(user_id = %user.id, session_id = %session.id, "Session created successfully");
-            session
-        })
+        info!(user_id = %user.id, username = %user.username, "User authentication successful");
+        if self.password_hasher.needs_rehash(&password_hash) {
+            self.rehash_password(&user, &password_hash, &request.password).await;
+        }
+        Ok(Some(user))
     }
 
-    /// 更新最后登录时间
-    #[instrument(skip_all, name = "update_last_login")]
-    async fn update_last_login(&self, user: &User) -> UseCaseResult<()> {
-        use crate::database::auth::update_last_login;
-        
-        info!(user_id = %user.id, "Updating last login time");
-        
-        update_last_login(&self.db_pool, user.id).await.map_err(|e| {
-            error!(user_id = %user.id, error = %e, "Failed to update last login time");
-            UseCaseError::DatabaseError(e.to_string())
-        }).map(|_| {
-            info!(user_id = %user.id, "Last login time updated successfully");
-        })
-    }
+    /// 按当前配置重新哈希密码，失败不影响本次登录，下次登录时重试
+    async fn rehash_password(&self, user: &User, old_hash: &str, password: &str) {
+        use crate::database::auth::update_password_hash;
 
-    /// 构建账户标记
-    #[instrument(skip_all, name = "build_account_flags")]
-    async fn build_account_flags(&self, user: &User) -> UseCaseResult<AccountFlags> {
-        info!(user_id = %user.id, "Building account flags for user");
-        // 这里可以根据实际业务逻辑来设置各种标记
-        // 目前使用简化的逻辑
-        
-        // 根据is_admin判断VIP状态（简化逻辑）
-        let is_vip = user.is_admin;
-        
-        // 检查是否为新用户（注册7天内）
-        let is_new_user = {
-            let now = chrono::Utc::now();
-            let seven_days_ago = now - chrono::Duration::days(7);
-            user.created_at > seven_days_ago
-        };
-        
-        // 检查是否有未读通知 - 这里可以查询通知表
-        let has_unread_notifications = false; // 简化实现
-        
-        // 检查是否需要完善个人信息
-        let needs_profile_completion = user.email.is_empty() || user.full_name.is_none();
-        
-        // 简单的安全等级计算
-        let mut security_level = 1;
-        if user.full_name.is_some() { security_level += 1; }
-        if !user.email.is_empty() { security_level += 1; }
-        // 可以添加其他安全因子的判断
-        security_level = security_level.min(5);
-        
-        let flags = AccountFlags {
-            is_vip,
-            is_new_user,
-            has_unread_notifications,
-            needs_profile_completion,
-            security_level,
+        let new_hash = match self.password_hasher.hash_blocking(password).await {
+            Ok(hash) => hash,
+            Err(e) => {
+                error!(user_id = %user.id, error = %e, "Failed to rehash password");
+                return;
+            }
         };
-        
-        info!(
-            user_id = %user.id, 
-            is_vip = %flags.is_vip,
-            is_new_user = %flags.is_new_user,
-            needs_profile_completion = %flags.needs_profile_completion,
-            security_level = %flags.security_level,
-            "Account flags built successfully"
-        );
-        
-        Ok(flags)
+
+        match self.timeouts.run(Dependency::Postgres, update_password_hash(&self.db_pool, user.id, old_hash, &new_hash)).await {
+            Ok(Ok(true)) => info!(user_id = %user.id, "Password hash upgraded"),
+            Ok(Ok(false)) => warn!(user_id = %user.id, "Password changed concurrently, skipping rehash"),
+            Ok(Err(e)) => error!(user_id = %user.id, error = %e, "Failed to store rehashed password"),
+            Err(e) => warn!(user_id = %user.id, error = %e, "Timed out storing rehashed password"),
+        }
     }
 
     /// 获取用户待处理任务数量
@@ -252,6 +264,11 @@ impl AuthUseCase {
             }
         };
         
+        self.events.publish(DomainEvent::UserLoggedOut {
+            user_id,
+            session_token: session_token.to_string(),
+        }).await;
+
         Ok(LogoutResult {
             user_id,
             session_destroyed,
@@ -259,25 +276,24 @@ impl AuthUseCase {
         })
     }
     
-    /// 处理用户登出 - 包含路由决策（保留向后兼容）
-    pub async fn handle_logout(&self, session_token: &str, platform: Platform) -> UseCaseResult<RouteCommand> {
-        // 这里需要获取用户ID，简化处理使用固定值
-        // 在实际实现中应该从session_token解析或查询获得user_id
-        let user_id = uuid::Uuid::new_v4(); // 简化处理
-        
+    /// 处理用户登出 - 包含路由决策（保留向后兼容），同时返回命中的路由决策
+    pub async fn handle_logout(&self, session_token: &str, user_id: uuid::Uuid, platform: Platform) -> UseCaseResult<(RouteCommand, Vec<RouteDecision>)> {
         match self.execute_logout(session_token, user_id).await {
             Ok(logout_result) => {
-                Ok(RouteCommandGenerator::generate_logout_route_command(&logout_result, &self.route_config, platform))
+                Ok(RouteCommandGenerator::traced(|trace| {
+                    RouteCommandGenerator::generate_logout_route_command(&logout_result, &self.route_config, platform, trace)
+                }))
             }
             Err(e) => {
                 warn!(error = %e, "Logout failed, but clearing client state");
-                let login_route = self.route_config.get_route("auth.login", platform)
+                let login_route = self.route_config.get_route(route_keys::AUTH_LOGIN, platform)
                     .unwrap_or_else(|| "/pages/login/login".to_string());
+                let decision = RouteDecision::new("logout.backend_failed", json!({ "error": e.to_string(), "route": login_route }));
                 // 即使后端登出失败，也要清理前端状态
-                Ok(RouteCommand::sequence(vec![
+                Ok((RouteCommand::sequence(vec![
                     RouteCommand::process_data("user", json!(null)),
                     RouteCommand::redirect_to(&login_route),
-                ]))
+                ]), vec![decision]))
             }
         }
     }
@@ -287,88 +303,91 @@ impl AuthUseCase {
     async fn check_unsaved_data(&self, user_id: uuid::Uuid) -> UseCaseResult<bool> {
         info!(user_id = %user_id, "Checking for unsaved data");
         
-        // 这里可以实现检查用户是否有未保存的草稿、表单等
-        let has_unsaved = false; // 目前简化处理
+        // 未提交的表单草稿即为未保存的数据
+        let has_unsaved = match &self.redis {
+            Some(redis) => DraftUseCase::new(redis.clone()).has_drafts(user_id).await?,
+            None => false,
+        };
         
         info!(user_id = %user_id, has_unsaved_data = %has_unsaved, "Unsaved data check completed");
         Ok(has_unsaved)
     }
 
-    /// 处理用户注册请求
-    #[instrument(skip_all, name = "handle_register")]
-    pub async fn handle_register(&self, request: RegisterRequest, platform: Platform) -> UseCaseResult<RouteCommand> {
+    /// 处理用户注册请求 - 纯业务逻辑，注册成功后自动登录
+    #[instrument(skip_all, name = "execute_register")]
+    pub async fn execute_register(
+        &self,
+        request: RegisterRequest,
+        context: RequestContext,
+    ) -> UseCaseResult<RegisterResult> {
         info!("Processing registration request for user: {}", request.username);
 
         // 1. 验证密码确认
         if request.password != request.confirm_password {
             warn!("Password confirmation mismatch for user: {}", request.username);
-            return Ok(RouteCommand::alert("注册失败", "两次输入的密码不一致，请重新输入"));
+            return Err(UseCaseError::ValidationError("两次输入的密码不一致，请重新输入".to_string()));
         }
 
         // 2. 验证账号格式
         if request.username.len() < 3 || request.username.len() > 30 {
             warn!("Invalid account length for user: {}", request.username);
-            return Ok(RouteCommand::alert("注册失败", "账号长度必须在3-30个字符之间"));
+            return Err(UseCaseError::ValidationError("账号长度必须在3-30个字符之间".to_string()));
         }
 
         // 3. 验证密码强度
         if request.password.len() < 6 || request.password.len() > 30 {
             warn!("Invalid password length for user: {}", request.username);
-            return Ok(RouteCommand::alert("注册失败", "密码长度必须在6-30个字符之间"));
+            return Err(UseCaseError::ValidationError("密码长度必须在6-30个字符之间".to_string()));
         }
 
-        // 4. 检查用户名是否已存在
-        match self.check_username_exists(&request.username).await {
-            Ok(true) => {
-                warn!("Username already exists: {}", request.username);
-                return Ok(RouteCommand::alert("注册失败", "该账号已存在，请更换其他账号"));
-            }
-            Ok(false) => {
-                info!("Username available: {}", request.username);
-            }
-            Err(e) => {
-                error!("Failed to check username existence: {}", e);
-                return Ok(RouteCommand::alert("注册失败", "系统错误，请稍后重试"));
-            }
+        // 4. 防自动化检查（蜜罐、一次性邮箱、IP/设备频率）
+        let assessment = match &self.registration_guard {
+            Some(guard) => guard.assess(&request, &context).await?,
+            None => Default::default(),
+        };
+
+        // 5. 检查用户名是否已存在
+        if self.check_username_exists(&request.username).await? {
+            warn!("Username already exists: {}", request.username);
+            return Err(UseCaseError::ValidationError("该账号已存在，请更换其他账号".to_string()));
         }
 
-        // 5. 创建用户
-        let user = match self.create_user(&request).await {
-            Ok(user) => {
-                info!("User registration successful: {}", user.username);
-                user
+        // 6. 创建用户，可疑账号进入人工审核队列
+        let user = self.create_user(&request).await?;
+        info!("User registration successful: {}", user.username);
+        if let Some(guard) = &self.registration_guard {
+            guard.flag(&user, &assessment, &context).await;
+        }
+        self.events.publish(DomainEvent::UserRegistered {
+            user: user.clone(),
+            method: AuthMethod::Password,
+            ip_address: context.ip_address,
+        }).await;
+
+        // 7. 自动登录新用户（创建会话），失败时仍视为注册成功
+        let session = match self.session_issuer().issue(&user, AuthMethod::Password, &context).await {
+            Ok(session) => {
+                info!("Auto-login session created for new user: {}", user.username);
+                Some(session)
             }
             Err(e) => {
-                error!("Failed to create user {}: {}", request.username, e);
-                return Ok(RouteCommand::alert("注册失败", "创建账号失败，请稍后重试"));
+                warn!("Failed to create session for new user, but registration successful: {}", e);
+                None
             }
         };
 
-        // 6. 自动登录新用户（创建会话）
-        match self.create_session(&user).await {
-            Ok(session) => {
-                info!("Auto-login session created for new user: {}", user.username);
-                
-                // 7. 构建登录结果并生成路由指令
-                let mut login_result = LoginResult::new(user.clone(), session);
-                let account_flags = self.build_account_flags(&user).await.unwrap_or_default();
-                login_result = login_result.with_account_flags(account_flags);
-                
-                let home_route = self.route_config.get_route("home.main", platform)
-                    .unwrap_or_else(|| "/pages/home/home".to_string());
-                Ok(RouteCommand::sequence(vec![
-                    RouteCommand::process_data("user", serde_json::to_value(UserInfo::from(user))?),
-                    RouteCommand::navigate_to(&home_route),
-                ]))
-            }
+        Ok(RegisterResult { user, session })
+    }
+
+    /// 处理用户注册请求 - 包含路由决策（保留向后兼容）
+    #[instrument(skip_all, name = "handle_register")]
+    pub async fn handle_register(&self, request: RegisterRequest, context: RequestContext, platform: Platform) -> UseCaseResult<RouteCommand> {
+        match self.execute_register(request, context).await {
+            Ok(result) => Ok(RouteCommandGenerator::generate_register_route_command(&result, &self.route_config, platform, &mut RouteTrace::default())),
+            Err(UseCaseError::ValidationError(msg)) => Ok(RouteCommand::alert("注册失败", &msg)),
             Err(e) => {
-                warn!("Failed to create session for new user, but registration successful: {}", e);
-                let login_route = self.route_config.get_route("auth.login", platform)
-                    .unwrap_or_else(|| "/pages/login/login".to_string());
-                Ok(RouteCommand::sequence(vec![
-                    RouteCommand::alert("注册成功", "账号创建成功，请重新登录"),
-                    RouteCommand::navigate_to(&login_route),
-                ]))
+                error!("Registration failed: {}", e);
+                Ok(RouteCommand::alert("注册失败", "创建账号失败，请稍后重试"))
             }
         }
     }
@@ -398,8 +417,13 @@ impl AuthUseCase {
         use crate::database::auth::create_user;
         
         info!(username = %request.username, "Creating new user");
+
+        let password_hash = self.password_hasher.hash_blocking(&request.password).await.map_err(|e| {
+            error!(username = %request.username, error = %e, "Failed to hash password");
+            UseCaseError::InternalError("密码处理失败".to_string())
+        })?;
         
-        match create_user(&self.db_pool, request).await {
+        match create_user(&self.db_pool, request, &password_hash).await {
             Ok(user) => {
                 info!(user_id = %user.id, username = %user.username, "User created successfully");
                 Ok(user)
@@ -411,39 +435,31 @@ impl AuthUseCase {
         }
     }
 
-    /// 处理游客登录请求
-    pub async fn handle_guest_login(&self, platform: Platform) -> UseCaseResult<RouteCommand> {
+    /// 处理游客登录请求 - 纯业务逻辑
+    #[instrument(skip_all, name = "execute_guest_login")]
+    pub async fn execute_guest_login(&self, context: RequestContext) -> UseCaseResult<LoginResult> {
         info!("Processing guest login request");
 
-        let guest_user = match self.create_guest_user().await {
-            Ok(user) => {
-                info!("Guest user created successfully: {}", user.username);
-                user
-            }
-            Err(e) => {
-                error!("Failed to create guest user: {}", e);
-                return Ok(RouteCommand::alert("游客登录失败", "创建游客账号失败，请稍后重试"));
-            }
-        };
+        let guest_user = self.create_guest_user().await?;
+        info!("Guest user created successfully: {}", guest_user.username);
 
-        match self.create_session(&guest_user).await {
-            Ok(session) => {
-                info!("Guest login session created: {}", guest_user.username);
-                
-                let mut login_result = LoginResult::new(guest_user.clone(), session);
-                let account_flags = self.build_account_flags(&guest_user).await.unwrap_or_default();
-                login_result = login_result.with_account_flags(account_flags);
-                
-                let home_route = self.route_config.get_route("home.main", platform)
-                    .unwrap_or_else(|| "/pages/home/home".to_string());
-                Ok(RouteCommand::sequence(vec![
-                    RouteCommand::process_data("user", serde_json::to_value(UserInfo::from(guest_user))?),
-                    RouteCommand::navigate_to(&home_route),
-                ]))
-            }
+        let session = self.session_issuer().issue(&guest_user, AuthMethod::Guest, &context).await.map_err(|e| {
+            warn!("Failed to create session for guest user: {}", e);
+            UseCaseError::InternalError("会话创建失败".to_string())
+        })?;
+        info!("Guest login session created: {}", guest_user.username);
+
+        let account_flags = self.flag_pipeline.build(&guest_user).await;
+        Ok(LoginResult::new(guest_user, session).with_account_flags(account_flags))
+    }
+
+    /// 处理游客登录请求 - 包含路由决策（保留向后兼容）
+    pub async fn handle_guest_login(&self, context: RequestContext, platform: Platform) -> UseCaseResult<RouteCommand> {
+        match self.execute_guest_login(context).await {
+            Ok(result) => Ok(RouteCommandGenerator::generate_guest_login_route_command(&result, &self.route_config, platform, &mut RouteTrace::default())),
             Err(e) => {
-                warn!("Failed to create session for guest user: {}", e);
-                Ok(RouteCommand::alert("游客登录失败", "创建会话失败，请稍后重试"))
+                error!("Guest login failed: {}", e);
+                Ok(RouteCommand::alert("游客登录失败", "创建游客账号失败，请稍后重试"))
             }
         }
     }
@@ -471,7 +487,7 @@ impl AuthUseCase {
 
 impl UseCase<LoginRequest, RouteCommand> for AuthUseCase {
     async fn execute(&self, input: LoginRequest) -> Result<RouteCommand, UseCaseError> {
-        self.handle_login(input, Platform::default()).await
+        self.handle_login(input, RequestContext::default(), Platform::default()).await
     }
 }
 
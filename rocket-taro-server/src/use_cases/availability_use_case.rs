@@ -0,0 +1,167 @@
+use chrono::{Datelike, Utc};
+use rand::Rng;
+use tracing::{info, warn, instrument};
+
+use crate::cache::{RedisPool, availability::AvailabilityCache};
+use crate::database::DbPool;
+use crate::models::{
+    auth::{AvailabilityField, AvailabilityResult, FieldAvailability},
+    profile::ProfileField,
+};
+use super::{UseCaseError, UseCaseResult, profile_use_case::validate_step};
+
+/// 用户名长度限制（字节，与注册校验一致）
+const MIN_USERNAME_LENGTH: usize = 3;
+const MAX_USERNAME_LENGTH: usize = 30;
+/// 最多返回的推荐用户名数量
+const MAX_SUGGESTIONS: usize = 3;
+
+/// 注册前的用户名/邮箱可用性检查
+pub struct AvailabilityUseCase {
+    db_pool: DbPool,
+    redis: RedisPool,
+}
+
+impl AvailabilityUseCase {
+    pub fn new(db_pool: DbPool, redis: RedisPool) -> Self {
+        Self { db_pool, redis }
+    }
+
+    /// 检查请求中提供的用户名和邮箱，至少需要提供其中一项
+    #[instrument(skip_all, name = "execute_check_availability")]
+    pub async fn execute_check(&self, username: Option<&str>, email: Option<&str>) -> UseCaseResult<AvailabilityResult> {
+        let username = username.map(str::trim).filter(|value| !value.is_empty());
+        let email = email.map(str::trim).filter(|value| !value.is_empty());
+        if username.is_none() && email.is_none() {
+            return Err(UseCaseError::ValidationError("请提供用户名或邮箱".to_string()));
+        }
+
+        let mut result = AvailabilityResult::default();
+        if let Some(username) = username {
+            result.username = Some(self.check_username(username).await?);
+        }
+        if let Some(email) = email {
+            result.email = Some(self.check_email(email).await?);
+        }
+        Ok(result)
+    }
+
+    async fn check_username(&self, username: &str) -> UseCaseResult<FieldAvailability> {
+        use crate::database::auth::check_username_exists;
+
+        if username.len() < MIN_USERNAME_LENGTH || username.len() > MAX_USERNAME_LENGTH {
+            return Ok(unavailable(AvailabilityField::Username, username, "账号长度必须在3-30个字符之间", Vec::new()));
+        }
+
+        if self.is_known_available(AvailabilityField::Username, username).await {
+            return Ok(available(AvailabilityField::Username, username));
+        }
+
+        if !check_username_exists(&self.db_pool, username).await? {
+            self.mark_available(AvailabilityField::Username, username).await;
+            return Ok(available(AvailabilityField::Username, username));
+        }
+
+        info!(username = %username, "Username is taken, generating suggestions");
+        let suggestions = self.suggest_usernames(username).await?;
+        Ok(unavailable(AvailabilityField::Username, username, "该账号已存在，请更换其他账号", suggestions))
+    }
+
+    async fn check_email(&self, email: &str) -> UseCaseResult<FieldAvailability> {
+        use crate::database::auth::check_email_exists;
+
+        let email = match validate_step(ProfileField::Email, email) {
+            Ok(email) => email,
+            Err(UseCaseError::ValidationError(msg)) => {
+                return Ok(unavailable(AvailabilityField::Email, email, &msg, Vec::new()));
+            }
+            Err(e) => return Err(e),
+        };
+
+        if self.is_known_available(AvailabilityField::Email, &email).await {
+            return Ok(available(AvailabilityField::Email, &email));
+        }
+
+        if !check_email_exists(&self.db_pool, &email).await? {
+            self.mark_available(AvailabilityField::Email, &email).await;
+            return Ok(available(AvailabilityField::Email, &email));
+        }
+        Ok(unavailable(AvailabilityField::Email, &email, "该邮箱已被注册", Vec::new()))
+    }
+
+    // 一次查询过滤掉已被占用的候选用户名
+    async fn suggest_usernames(&self, username: &str) -> UseCaseResult<Vec<String>> {
+        use crate::database::auth::find_existing_usernames;
+
+        let candidates = suggestion_candidates(username);
+        let taken = find_existing_usernames(&self.db_pool, &candidates).await?;
+        Ok(candidates.into_iter()
+            .filter(|candidate| !taken.contains(candidate))
+            .take(MAX_SUGGESTIONS)
+            .collect())
+    }
+
+    // 缓存只用于减少数据库查询，Redis 不可用时直接查库
+    async fn is_known_available(&self, field: AvailabilityField, value: &str) -> bool {
+        AvailabilityCache::new(self.redis.clone())
+            .is_known_available(field, value).await
+            .unwrap_or(false)
+    }
+
+    async fn mark_available(&self, field: AvailabilityField, value: &str) {
+        if let Err(e) = AvailabilityCache::new(self.redis.clone()).mark_available(field, value).await {
+            warn!(field = %field.as_str(), error = %e, "Failed to cache availability");
+        }
+    }
+}
+
+fn available(field: AvailabilityField, value: &str) -> FieldAvailability {
+    FieldAvailability { field, value: value.to_string(), available: true, reason: None, suggestions: Vec::new() }
+}
+
+fn unavailable(field: AvailabilityField, value: &str, reason: &str, suggestions: Vec<String>) -> FieldAvailability {
+    FieldAvailability { field, value: value.to_string(), available: false, reason: Some(reason.to_string()), suggestions }
+}
+
+// 推荐用户名候选：顺序数字、当前年份和随机数字后缀，超长时截断原用户名
+fn suggestion_candidates(username: &str) -> Vec<String> {
+    let mut rng = rand::thread_rng();
+    let mut suffixes: Vec<String> = (1..=3).map(|n| n.to_string()).collect();
+    suffixes.push(Utc::now().year().to_string());
+    suffixes.extend((0..3).map(|_| rng.gen_range(100..1000).to_string()));
+
+    let mut candidates: Vec<String> = Vec::with_capacity(suffixes.len());
+    for suffix in suffixes {
+        let mut base = String::new();
+        for ch in username.chars() {
+            if base.len() + ch.len_utf8() + suffix.len() > MAX_USERNAME_LENGTH {
+                break;
+            }
+            base.push(ch);
+        }
+        let candidate = format!("{}{}", base, suffix);
+        if candidate != username && !candidates.contains(&candidate) {
+            candidates.push(candidate);
+        }
+    }
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_suggestion_candidates() {
+        let candidates = suggestion_candidates("alice");
+        assert_eq!(&candidates[..3], ["alice1", "alice2", "alice3"]);
+        assert!(candidates.iter().all(|c| c.starts_with("alice") && c != "alice"));
+
+        // 超长用户名截断后仍不超过长度限制，且不会切断多字节字符
+        let long_name = format!("{}张", "a".repeat(28));
+        for candidate in suggestion_candidates(&long_name) {
+            assert!(candidate.len() <= MAX_USERNAME_LENGTH, "{}", candidate);
+            assert!(candidate.len() >= MIN_USERNAME_LENGTH);
+        }
+    }
+}
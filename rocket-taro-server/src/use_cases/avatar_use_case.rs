@@ -0,0 +1,265 @@
+use std::path::Path;
+use chrono::Utc;
+use rocket::fs::TempFile;
+use tracing::{debug, error, info, instrument, warn};
+use uuid::Uuid;
+
+use crate::cache::{RedisPool, session::SessionCache, user::UserCache};
+use crate::config::AvatarConfig;
+use crate::database::DbPool;
+use crate::models::auth::User;
+use crate::models::avatar::{Avatar, AvatarStatus, AvatarView, avatar_url};
+use crate::models::route_command::RouteCommand;
+use crate::push::{PushHub, PushMessage};
+use crate::utils::image::process_avatar;
+use super::moderation_use_case::ModerationUseCase;
+use super::route_command_generator::RouteCommandGenerator;
+use super::{UseCaseError, UseCaseResult};
+
+/// 头像处理结束时的推送事件名
+pub const AVATAR_EVENT: &str = "avatar";
+
+/// 头像用例：上传的原图先保存为待处理状态，由后台任务去除元数据、缩放、审核并转换为 WebP 后设为用户头像；
+/// 处理完成前不对外提供
+pub struct AvatarUseCase {
+    db_pool: DbPool,
+    redis: RedisPool,
+    config: AvatarConfig,
+    moderation: Option<ModerationUseCase>,
+    push: Option<PushHub>,
+}
+
+impl AvatarUseCase {
+    pub fn new(db_pool: DbPool, redis: RedisPool, config: AvatarConfig) -> Self {
+        Self { db_pool, redis, config, moderation: None, push: None }
+    }
+
+    /// 设置头像的内容审核，未设置时不审核
+    pub fn with_moderation(mut self, moderation: ModerationUseCase) -> Self {
+        self.moderation = Some(moderation);
+        self
+    }
+
+    /// 处理结束后推送结果给用户
+    pub fn with_push(mut self, push: PushHub) -> Self {
+        self.push = Some(push);
+        self
+    }
+
+    /// 检查类型和大小后保存原图，返回处理状态；之前上传但尚未处理完的头像不再处理
+    #[instrument(skip_all, name = "upload_avatar", fields(user_id = %user.id))]
+    pub async fn upload(&self, user: &User, file: &mut TempFile<'_>) -> UseCaseResult<AvatarView> {
+        use crate::database::avatar::insert_avatar;
+
+        let content_type = file.content_type()
+            .map(|content_type| content_type.to_string())
+            .unwrap_or_default();
+        if !self.config.is_allowed(&content_type) {
+            return Err(UseCaseError::ValidationError("头像只支持 JPEG、PNG 或 WebP 格式".to_string()));
+        }
+        if file.len() == 0 {
+            return Err(UseCaseError::ValidationError("图片内容为空".to_string()));
+        }
+        if file.len() > self.config.max_file_size.as_u64() {
+            return Err(UseCaseError::ValidationError(format!("图片不能超过{}", self.config.max_file_size)));
+        }
+
+        let directory = Path::new(&self.config.directory);
+        tokio::fs::create_dir_all(directory).await
+            .map_err(|e| UseCaseError::InternalError(format!("无法创建头像目录: {}", e)))?;
+
+        let id = Uuid::new_v4();
+        let source_path = directory.join(format!("{}.source", id));
+        file.move_copy_to(&source_path).await
+            .map_err(|e| UseCaseError::InternalError(format!("无法保存头像: {}", e)))?;
+
+        let now = Utc::now();
+        let avatar = Avatar {
+            id,
+            user_id: user.id,
+            status: AvatarStatus::Pending.as_str().to_string(),
+            source_path: source_path.to_string_lossy().to_string(),
+            source_content_type: content_type.split(';').next().unwrap_or_default().trim().to_string(),
+            file_path: None,
+            width: None,
+            height: None,
+            file_size: None,
+            failure_reason: None,
+            attempts: 0,
+            next_attempt_at: now,
+            created_at: now,
+            processed_at: None,
+        };
+        let superseded = match insert_avatar(&self.db_pool, &avatar).await {
+            Ok(superseded) => superseded,
+            Err(e) => {
+                let _ = tokio::fs::remove_file(&source_path).await;
+                return Err(e.into());
+            }
+        };
+        remove_avatar_files(&superseded).await;
+
+        info!(avatar_id = %avatar.id, size = file.len(), content_type = %avatar.source_content_type, "Avatar uploaded, queued for processing");
+        Ok(avatar.view())
+    }
+
+    /// 本人查看头像处理状态
+    pub async fn status(&self, user_id: Uuid, avatar_id: Uuid) -> UseCaseResult<AvatarView> {
+        use crate::database::avatar::find_avatar;
+
+        find_avatar(&self.db_pool, avatar_id).await?
+            .filter(|avatar| avatar.user_id == user_id)
+            .map(|avatar| avatar.view())
+            .ok_or_else(|| UseCaseError::ValidationError("头像不存在".to_string()))
+    }
+
+    /// 获取处理完成的头像文件路径，未处理完或处理失败的头像视为不存在
+    pub async fn open(&self, avatar_id: Uuid) -> UseCaseResult<String> {
+        use crate::database::avatar::find_avatar;
+
+        find_avatar(&self.db_pool, avatar_id).await?
+            .filter(|avatar| avatar.status() == AvatarStatus::Ready)
+            .and_then(|avatar| avatar.file_path)
+            .ok_or_else(|| UseCaseError::ValidationError("头像不存在".to_string()))
+    }
+
+    /// 处理到期的待处理头像，返回处理的数量
+    #[instrument(skip_all, name = "process_avatars")]
+    pub async fn process_pending(&self) -> UseCaseResult<usize> {
+        use crate::database::avatar::claim_due_avatars;
+
+        let avatars = claim_due_avatars(&self.db_pool, self.config.process_batch_size, self.config.retry_delay_secs).await?;
+        for avatar in &avatars {
+            if let Err(e) = self.process(avatar).await {
+                error!(avatar_id = %avatar.id, error = %e, "Failed to process avatar");
+            }
+        }
+        if !avatars.is_empty() {
+            info!(count = avatars.len(), "Avatars processed");
+        }
+        Ok(avatars.len())
+    }
+
+    async fn process(&self, avatar: &Avatar) -> UseCaseResult<()> {
+        use crate::database::avatar::{ProcessedAvatarFile, publish_avatar};
+
+        let source = match tokio::fs::read(&avatar.source_path).await {
+            Ok(source) => source,
+            Err(e) => {
+                warn!(avatar_id = %avatar.id, error = %e, "Avatar source file missing");
+                return self.finish(avatar, AvatarStatus::Failed, "原图不存在", None).await;
+            }
+        };
+
+        // 解码和编码是 CPU 密集操作，不占用异步运行时的线程
+        let (size, max_dimension) = (self.config.size, self.config.max_source_dimension);
+        let processed = match tokio::task::spawn_blocking(move || process_avatar(&source, size, max_dimension)).await {
+            Ok(Ok(processed)) => processed,
+            Ok(Err(reason)) => {
+                info!(avatar_id = %avatar.id, reason = %reason, "Avatar image could not be processed");
+                let command = RouteCommand::toast("头像图片无法识别，请更换后重新上传");
+                return self.finish(avatar, AvatarStatus::Failed, &reason, Some(command)).await;
+            }
+            Err(e) => return self.retry(avatar, &format!("图片处理异常: {}", e)).await,
+        };
+
+        if let Some(moderation) = &self.moderation {
+            match moderation.check_image(&processed.png, "image/png").await {
+                Ok(()) => {}
+                Err(UseCaseError::ContentRejected(rejection)) => {
+                    let command = RouteCommandGenerator::generate_content_rejected_route_command(&rejection);
+                    return self.finish(avatar, AvatarStatus::Rejected, &rejection.message(), Some(command)).await;
+                }
+                Err(UseCaseError::BusinessLogicError(msg)) => return self.retry(avatar, &msg).await,
+                Err(e) => return Err(e),
+            }
+        }
+
+        let file_path = Path::new(&self.config.directory).join(format!("{}.webp", avatar.id));
+        tokio::fs::write(&file_path, &processed.webp).await
+            .map_err(|e| UseCaseError::InternalError(format!("无法保存头像: {}", e)))?;
+        let file_path = file_path.to_string_lossy().to_string();
+        let url = avatar_url(avatar.id);
+        let file = ProcessedAvatarFile {
+            file_path: &file_path,
+            width: processed.width as i32,
+            height: processed.height as i32,
+            file_size: processed.webp.len() as i64,
+            avatar_url: &url,
+        };
+        let previous = match publish_avatar(&self.db_pool, avatar.id, avatar.user_id, &file).await {
+            Ok(previous) => previous,
+            Err(e) => {
+                let _ = tokio::fs::remove_file(&file_path).await;
+                return Err(e.into());
+            }
+        };
+        remove_avatar_files(std::slice::from_ref(&avatar.source_path)).await;
+
+        let Some(previous) = previous else {
+            // 处理期间用户上传了新头像
+            debug!(avatar_id = %avatar.id, "Avatar superseded while processing, discarded");
+            remove_avatar_files(&[file_path]).await;
+            return Ok(());
+        };
+        remove_avatar_files(&previous).await;
+        self.invalidate_cached_user(avatar.user_id).await;
+
+        info!(avatar_id = %avatar.id, user_id = %avatar.user_id, size = file.file_size, "Avatar ready");
+        self.notify(avatar.user_id, RouteCommand::toast("头像已更新"));
+        Ok(())
+    }
+
+    // 临时失败（审核服务不可用等），达到最大处理次数后标记为失败
+    async fn retry(&self, avatar: &Avatar, reason: &str) -> UseCaseResult<()> {
+        use crate::database::avatar::fail_avatar;
+
+        if avatar.attempts >= self.config.max_attempts {
+            let command = RouteCommand::toast("头像处理失败，请稍后重新上传");
+            return self.finish(avatar, AvatarStatus::Failed, reason, Some(command)).await;
+        }
+        warn!(avatar_id = %avatar.id, attempts = avatar.attempts, reason = %reason, "Avatar processing deferred");
+        fail_avatar(&self.db_pool, avatar.id, AvatarStatus::Pending.as_str(), reason).await?;
+        Ok(())
+    }
+
+    // 处理结束但未设为头像：记录原因、删除原图并通知用户
+    async fn finish(&self, avatar: &Avatar, status: AvatarStatus, reason: &str, command: Option<RouteCommand>) -> UseCaseResult<()> {
+        use crate::database::avatar::fail_avatar;
+
+        fail_avatar(&self.db_pool, avatar.id, status.as_str(), reason).await?;
+        remove_avatar_files(std::slice::from_ref(&avatar.source_path)).await;
+        info!(avatar_id = %avatar.id, status = status.as_str(), reason = %reason, "Avatar not published");
+        if let Some(command) = command {
+            self.notify(avatar.user_id, command);
+        }
+        Ok(())
+    }
+
+    fn notify(&self, user_id: Uuid, command: RouteCommand) {
+        if let Some(push) = &self.push {
+            push.send(user_id, PushMessage::new(AVATAR_EVENT, command));
+        }
+    }
+
+    // 会话缓存中保存了用户信息，头像变更后需要一并清除
+    async fn invalidate_cached_user(&self, user_id: Uuid) {
+        if let Err(e) = UserCache::new(self.redis.clone()).invalidate_user(user_id).await {
+            warn!(user_id = %user_id, error = %e, "Failed to invalidate user cache");
+        }
+        if let Err(e) = SessionCache::new(self.redis.clone()).invalidate_user_sessions(user_id).await {
+            warn!(user_id = %user_id, error = %e, "Failed to invalidate session cache");
+        }
+    }
+}
+
+/// 删除头像文件，文件已不存在时忽略
+pub async fn remove_avatar_files(file_paths: &[String]) {
+    for file_path in file_paths {
+        if let Err(e) = tokio::fs::remove_file(file_path).await {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                warn!(file_path = %file_path, error = %e, "Failed to remove avatar file");
+            }
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use futures_util::future::join_all;
+use std::time::Duration;
+use tracing::{debug, instrument};
+
+use crate::config::BatchConfig;
+use crate::metrics::MetricsRegistry;
+use crate::models::batch::{BatchItemResult, BatchSubRequest};
+use super::{UseCaseError, UseCaseResult};
+
+/// 批量请求：把子请求并发转发回本服务，每个子请求照常经过认证、IP 限制、维护模式等检查
+#[derive(Clone)]
+pub struct BatchUseCase {
+    http: reqwest::Client,
+    config: BatchConfig,
+    /// 模拟模式下为 None
+    metrics: Option<MetricsRegistry>,
+}
+
+impl BatchUseCase {
+    pub fn from_config(config: BatchConfig, metrics: Option<MetricsRegistry>) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.request_timeout_secs))
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .unwrap_or_default();
+        Self { http, config, metrics }
+    }
+
+    /// 并发执行子请求，结果顺序与请求一致；forwarded 为附加到每个子请求上的原始请求头
+    #[instrument(skip_all, name = "batch", fields(count = requests.len()))]
+    pub async fn execute(
+        &self,
+        base_url: &str,
+        forwarded: &[(&'static str, String)],
+        requests: Vec<BatchSubRequest>,
+    ) -> UseCaseResult<Vec<BatchItemResult>> {
+        if !self.config.enabled {
+            return Err(UseCaseError::BusinessLogicError("批量请求未启用".to_string()));
+        }
+        if requests.is_empty() {
+            return Err(UseCaseError::ValidationError("子请求不能为空".to_string()));
+        }
+        if requests.len() > self.config.max_requests {
+            return Err(UseCaseError::ValidationError(format!("子请求最多 {} 个", self.config.max_requests)));
+        }
+
+        if let Some(metrics) = &self.metrics {
+            metrics.increment("batch.requests");
+            for _ in &requests {
+                metrics.increment("batch.sub_requests");
+            }
+        }
+
+        let results = requests.iter().map(|request| self.dispatch(base_url, forwarded, request));
+        Ok(join_all(results).await)
+    }
+
+    async fn dispatch(&self, base_url: &str, forwarded: &[(&'static str, String)], request: &BatchSubRequest) -> BatchItemResult {
+        let method = match request.validate(&self.config.path_prefixes) {
+            Ok(method) => method,
+            Err(msg) => return BatchItemResult::failed(400, msg),
+        };
+        let Ok(method) = reqwest::Method::from_bytes(method.as_str().as_bytes()) else {
+            return BatchItemResult::failed(400, format!("不支持的请求方法: {}", request.method));
+        };
+
+        let mut builder = self.http.request(method, format!("{}{}", base_url, request.path));
+        for (name, value) in forwarded {
+            builder = builder.header(*name, value);
+        }
+        if let Some(body) = &request.body {
+            builder = builder.json(body);
+        }
+
+        match builder.send().await {
+            Ok(response) => {
+                let status = response.status().as_u16();
+                let body = response.bytes().await.ok()
+                    .and_then(|bytes| serde_json::from_slice(&bytes).ok());
+                BatchItemResult { status, body, error: None }
+            }
+            Err(e) if e.is_timeout() => BatchItemResult::failed(504, "子请求超时"),
+            Err(e) => {
+                debug!(path = %request.path, "Batch sub-request failed: {}", e);
+                BatchItemResult::failed(502, "子请求失败")
+            }
+        }
+    }
+}
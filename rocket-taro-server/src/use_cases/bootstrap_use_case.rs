@@ -0,0 +1,76 @@
+use rand::{Rng, distributions::Alphanumeric};
+use tracing::{info, error, instrument};
+
+use crate::auth::PasswordHasher;
+use crate::database::DbPool;
+use crate::models::auth::User;
+use super::{UseCaseError, UseCaseResult};
+
+/// 初始密码长度
+const INITIAL_PASSWORD_LEN: usize = 24;
+
+/// 新建的初始管理员及其一次性密码
+pub struct InitialAdmin {
+    pub user: User,
+    pub password: String,
+}
+
+/// 初始化用例：显式请求时创建首个管理员账户，不修改任何已有用户
+pub struct BootstrapUseCase {
+    db_pool: DbPool,
+    password_hasher: PasswordHasher,
+}
+
+impl BootstrapUseCase {
+    pub fn new(db_pool: DbPool, password_hasher: PasswordHasher) -> Self {
+        Self { db_pool, password_hasher }
+    }
+
+    /// 创建初始管理员，使用随机生成的密码；已存在管理员或用户名、邮箱被占用时拒绝
+    #[instrument(skip_all, name = "create_initial_admin")]
+    pub async fn create_initial_admin(&self, username: &str, email: &str) -> UseCaseResult<InitialAdmin> {
+        use crate::database::auth::{admin_exists, create_admin_user};
+
+        if admin_exists(&self.db_pool).await? {
+            return Err(UseCaseError::BusinessLogicError("已存在管理员账户，跳过初始化".to_string()));
+        }
+
+        let password = generate_initial_password();
+        let password_hash = self.password_hasher.hash(&password).map_err(|e| {
+            error!(error = %e, "Failed to hash initial admin password");
+            UseCaseError::InternalError("密码处理失败".to_string())
+        })?;
+
+        match create_admin_user(&self.db_pool, username, email, &password_hash).await? {
+            Some(user) => {
+                info!(user_id = %user.id, username = %user.username, "Initial admin account created");
+                Ok(InitialAdmin { user, password })
+            }
+            None => Err(UseCaseError::BusinessLogicError(
+                format!("用户名 {} 或邮箱 {} 已被占用", username, email)
+            )),
+        }
+    }
+}
+
+fn generate_initial_password() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(INITIAL_PASSWORD_LEN)
+        .map(char::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_initial_password() {
+        let password = generate_initial_password();
+
+        assert_eq!(password.len(), INITIAL_PASSWORD_LEN);
+        assert!(password.chars().all(|c| c.is_ascii_alphanumeric()));
+        assert_ne!(password, generate_initial_password());
+    }
+}
@@ -0,0 +1,43 @@
+use serde_json::json;
+use tracing::{info, warn, instrument};
+use uuid::Uuid;
+
+use crate::cache::{RedisPool, invalidation::CacheInvalidator};
+use crate::database::DbPool;
+use crate::models::{
+    audit::AuditEvent,
+    cache_invalidation::{CacheInvalidation, CacheTarget},
+};
+use super::{UseCaseError, UseCaseResult};
+
+/// 管理员精确清除缓存
+pub struct CacheInvalidationUseCase {
+    db_pool: DbPool,
+    redis: RedisPool,
+}
+
+impl CacheInvalidationUseCase {
+    pub fn new(db_pool: DbPool, redis: RedisPool) -> Self {
+        Self { db_pool, redis }
+    }
+
+    /// 清除指定对象的缓存并记录审计事件，返回删除的键数量
+    #[instrument(skip_all, name = "invalidate_cache", fields(target = %target.describe()))]
+    pub async fn invalidate(&self, admin_id: Uuid, target: CacheTarget) -> UseCaseResult<CacheInvalidation> {
+        use crate::database::audit::record_audit_event;
+
+        let keys_removed = CacheInvalidator::new(self.redis.clone()).invalidate(&target).await
+            .map_err(|e| UseCaseError::InternalError(format!("缓存清除失败: {}", e)))?;
+        let result = CacheInvalidation { target: target.describe(), keys_removed };
+        info!(admin_id = %admin_id, keys_removed = %keys_removed, "Cache invalidated");
+
+        let event = AuditEvent::new("cache.invalidated", "cache")
+            .actor(admin_id)
+            .target(&result.target)
+            .details(json!({ "keys_removed": keys_removed }));
+        if let Err(e) = record_audit_event(&self.db_pool, &event).await {
+            warn!("Failed to record cache invalidation audit event: {}", e);
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,63 @@
+use std::time::Instant;
+use tracing::{info, warn, instrument};
+
+use crate::cache::{RedisPool, data::DataCache};
+use crate::config::WarmupDataset;
+use crate::database::DbPool;
+use super::{UseCaseError, UseCaseResult, remote_config_use_case::RemoteConfigUseCase};
+
+/// 启动时把热点数据预先写入 Redis，避免重启后首批请求全部穿透到数据库
+pub struct CacheWarmupUseCase {
+    db_pool: DbPool,
+    redis: RedisPool,
+}
+
+impl CacheWarmupUseCase {
+    pub fn new(db_pool: DbPool, redis: RedisPool) -> Self {
+        Self { db_pool, redis }
+    }
+
+    /// 依次预热各数据集并记录耗时；单个数据集失败只记录警告，不影响启动
+    #[instrument(skip_all, name = "cache_warmup")]
+    pub async fn run(&self, datasets: &[WarmupDataset]) {
+        let started = Instant::now();
+        for dataset in datasets {
+            let dataset_started = Instant::now();
+            match self.warm(*dataset).await {
+                Ok(items) => info!(
+                    dataset = dataset.as_str(),
+                    items = items,
+                    elapsed_ms = dataset_started.elapsed().as_millis() as u64,
+                    "Cache dataset warmed up"
+                ),
+                Err(e) => warn!(
+                    dataset = dataset.as_str(),
+                    elapsed_ms = dataset_started.elapsed().as_millis() as u64,
+                    "Cache warm-up failed: {}", e
+                ),
+            }
+        }
+        info!(
+            datasets = datasets.len(),
+            elapsed_ms = started.elapsed().as_millis() as u64,
+            "Cache warm-up finished"
+        );
+    }
+
+    /// 预热单个数据集，返回写入的条目数量
+    pub async fn warm(&self, dataset: WarmupDataset) -> UseCaseResult<usize> {
+        use crate::database::get_all_user_data;
+
+        match dataset {
+            WarmupDataset::UserData => {
+                let data = get_all_user_data(&self.db_pool).await?;
+                DataCache::new(self.redis.clone()).warm_up_cache(&data).await
+                    .map_err(|e| UseCaseError::InternalError(format!("缓存写入失败: {}", e)))?;
+                Ok(data.len())
+            }
+            WarmupDataset::RemoteConfig => {
+                RemoteConfigUseCase::new(self.db_pool.clone(), self.redis.clone()).refresh_snapshots().await
+            }
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use serde_json::json;
+use std::net::IpAddr;
+use tracing::{info, instrument, warn};
+use uuid::Uuid;
+
+use crate::database::DbPool;
+use crate::models::audit::AuditEvent;
+use crate::models::consent::{
+    AcceptConsentRequest, ConsentResult, CreatePolicyDocumentRequest, PolicyDocument, PolicyType, UserConsent,
+};
+use super::session_issuer::RequestContext;
+use super::{UseCaseError, UseCaseResult};
+
+/// 版本号最大长度
+const MAX_VERSION_LENGTH: usize = 30;
+/// 标题最大长度
+const MAX_TITLE_LENGTH: usize = 200;
+
+/// 协议同意用例：用户查看并同意生效的协议版本，管理员发布和切换版本
+pub struct ConsentUseCase {
+    db_pool: DbPool,
+}
+
+impl ConsentUseCase {
+    pub fn new(db_pool: DbPool) -> Self {
+        Self { db_pool }
+    }
+
+    /// 用户尚未同意的生效协议版本，为空表示无需同意
+    pub async fn pending(&self, user_id: Uuid) -> UseCaseResult<Vec<PolicyDocument>> {
+        use crate::database::consent::pending_policy_documents;
+
+        Ok(pending_policy_documents(&self.db_pool, user_id).await?)
+    }
+
+    /// 某类协议当前生效的版本（注册页、设置页展示，不需要登录）
+    pub async fn active(&self, policy_type: &str) -> UseCaseResult<Option<PolicyDocument>> {
+        use crate::database::consent::active_policy_document;
+
+        let policy_type = PolicyType::parse(policy_type)
+            .ok_or_else(|| UseCaseError::ValidationError("协议类型应为 terms 或 privacy".to_string()))?;
+        Ok(active_policy_document(&self.db_pool, policy_type.as_str()).await?)
+    }
+
+    /// 记录用户同意的协议版本，连同 IP、User-Agent 和时间保存；只接受当前生效的版本
+    #[instrument(skip_all, name = "accept_policies", fields(user_id = %user_id))]
+    pub async fn accept(&self, user_id: Uuid, request: AcceptConsentRequest, context: &RequestContext) -> UseCaseResult<ConsentResult> {
+        use crate::database::consent::record_consents;
+
+        if request.document_ids.is_empty() {
+            return Err(UseCaseError::ValidationError("请选择要同意的协议".to_string()));
+        }
+
+        let accepted = record_consents(
+            &self.db_pool,
+            user_id,
+            &request.document_ids,
+            context.ip_address,
+            context.user_agent.as_deref(),
+        ).await?;
+        let pending = self.pending(user_id).await?;
+        info!(
+            accepted = ?accepted.iter().map(|consent| format!("{}:{}", consent.policy_type, consent.version)).collect::<Vec<_>>(),
+            pending = pending.len(),
+            "Policy consent recorded"
+        );
+        Ok(ConsentResult { accepted, pending })
+    }
+
+    /// 用户的同意记录，最新的在前
+    pub async fn history(&self, user_id: Uuid) -> UseCaseResult<Vec<UserConsent>> {
+        use crate::database::consent::list_user_consents;
+
+        Ok(list_user_consents(&self.db_pool, user_id).await?)
+    }
+
+    /// 全部协议版本（管理端）
+    pub async fn list_documents(&self) -> UseCaseResult<Vec<PolicyDocument>> {
+        use crate::database::consent::list_policy_documents;
+
+        Ok(list_policy_documents(&self.db_pool).await?)
+    }
+
+    /// 发布协议新版本，activate 为 true 时立即生效
+    #[instrument(skip_all, name = "create_policy_document")]
+    pub async fn create_document(
+        &self,
+        request: CreatePolicyDocumentRequest,
+        admin_id: Uuid,
+        ip_address: Option<IpAddr>,
+    ) -> UseCaseResult<PolicyDocument> {
+        use crate::database::consent::create_policy_document;
+
+        let version = request.version.trim();
+        let title = request.title.trim();
+        if version.is_empty() || version.chars().count() > MAX_VERSION_LENGTH {
+            return Err(UseCaseError::ValidationError(format!("版本号不能为空且不超过{}个字符", MAX_VERSION_LENGTH)));
+        }
+        if title.is_empty() || title.chars().count() > MAX_TITLE_LENGTH {
+            return Err(UseCaseError::ValidationError(format!("标题不能为空且不超过{}个字符", MAX_TITLE_LENGTH)));
+        }
+        if request.content.trim().is_empty() {
+            return Err(UseCaseError::ValidationError("协议内容不能为空".to_string()));
+        }
+
+        let document = create_policy_document(&self.db_pool, request.policy_type.as_str(), version, title, &request.content, admin_id).await?
+            .ok_or_else(|| UseCaseError::ConflictError(format!("该协议已存在版本 {}", version)))?;
+        info!(admin_id = %admin_id, policy_type = %document.policy_type, version = %document.version, "Policy document created");
+        self.audit("policy.created", &document, admin_id, ip_address).await;
+
+        if request.activate {
+            return self.activate(document.id, admin_id, ip_address).await;
+        }
+        Ok(document)
+    }
+
+    /// 让某个版本生效，同类型的旧版本失效，所有用户需要重新同意
+    #[instrument(skip_all, name = "activate_policy_document", fields(document_id = %document_id))]
+    pub async fn activate(&self, document_id: Uuid, admin_id: Uuid, ip_address: Option<IpAddr>) -> UseCaseResult<PolicyDocument> {
+        use crate::database::consent::activate_policy_document;
+
+        let document = activate_policy_document(&self.db_pool, document_id).await?
+            .ok_or_else(|| UseCaseError::BusinessLogicError("协议版本不存在".to_string()))?;
+        info!(admin_id = %admin_id, policy_type = %document.policy_type, version = %document.version, "Policy document activated");
+        self.audit("policy.activated", &document, admin_id, ip_address).await;
+        Ok(document)
+    }
+
+    async fn audit(&self, action: &str, document: &PolicyDocument, admin_id: Uuid, ip_address: Option<IpAddr>) {
+        use crate::database::audit::record_audit_event;
+
+        let event = AuditEvent::new(action, "policy_document")
+            .actor(admin_id)
+            .target(document.id)
+            .ip(ip_address)
+            .details(json!({ "policy_type": document.policy_type, "version": document.version }));
+        // 操作已生效，审计日志写入失败只记录日志
+        if let Err(e) = record_audit_event(&self.db_pool, &event).await {
+            warn!(error = %e, "Failed to record policy audit event");
+        }
+    }
+}
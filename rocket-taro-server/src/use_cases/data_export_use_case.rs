@@ -0,0 +1,250 @@
+use std::net::IpAddr;
+use std::path::Path;
+use chrono::{Duration, Utc};
+use rocket::futures::TryStreamExt;
+use tokio::io::{AsyncWriteExt, BufWriter};
+use serde_json::json;
+use tracing::{info, error, instrument};
+use uuid::Uuid;
+
+use crate::config::DataExportConfig;
+use crate::database::DbPool;
+use crate::models::{
+    audit::AuditEvent,
+    auth::User,
+    data_export::{DataExport, DataExportInfo, UserDataArchive},
+    notification::NewNotification,
+    storage::DATA_EXPORT_KIND,
+};
+use super::storage_use_case::StorageUseCase;
+use super::{UseCaseError, UseCaseResult};
+
+/// 单次任务处理的最大导出数量
+const EXPORT_BATCH_SIZE: i64 = 10;
+/// 处理中的任务超过该时长视为中断，重新处理
+const EXPORT_STALE_MINUTES: i64 = 30;
+/// 导出的登录历史最大条数
+const LOGIN_HISTORY_LIMIT: i64 = 1000;
+
+/// 导出文件在存储中的 key
+fn export_key(export_id: Uuid) -> String {
+    format!("exports/{}.json", export_id)
+}
+
+/// 用户数据导出用例，导出文件保存在配置的存储后端，过期后由存储清理任务删除
+pub struct DataExportUseCase {
+    db_pool: DbPool,
+    config: DataExportConfig,
+    storage: StorageUseCase,
+}
+
+impl DataExportUseCase {
+    pub fn new(db_pool: DbPool, config: DataExportConfig, storage: StorageUseCase) -> Self {
+        Self { db_pool, config, storage }
+    }
+
+    /// 请求数据导出 - 已有进行中或可下载的导出时直接返回，否则创建新任务
+    #[instrument(skip_all, name = "execute_request_export")]
+    pub async fn execute_request_export(&self, user: &User, ip_address: Option<IpAddr>) -> UseCaseResult<DataExportInfo> {
+        use crate::database::data_export::{find_latest_data_export, create_data_export};
+        use crate::database::audit::record_audit_event;
+
+        if user.is_guest {
+            return Err(UseCaseError::BusinessLogicError("游客账户不支持数据导出".to_string()));
+        }
+
+        if let Some(latest) = find_latest_data_export(&self.db_pool, user.id).await? {
+            if latest.status.is_in_progress() || latest.is_downloadable(Utc::now()) {
+                info!(user_id = %user.id, export_id = %latest.id, status = %latest.status.as_str(), "Returning existing data export");
+                return Ok(Self::to_info(latest, false));
+            }
+        }
+
+        let export = create_data_export(&self.db_pool, user.id).await?;
+
+        let event = AuditEvent::new("account.data_export_requested", "user")
+            .actor(user.id)
+            .target(user.id)
+            .ip(ip_address)
+            .details(json!({ "export_id": export.id }));
+        if let Err(e) = record_audit_event(&self.db_pool, &event).await {
+            error!(user_id = %user.id, error = %e, "Failed to record data export audit event");
+        }
+
+        info!(user_id = %user.id, export_id = %export.id, "Data export requested");
+        Ok(Self::to_info(export, true))
+    }
+
+    /// 获取导出文件的限时下载地址
+    #[instrument(skip_all, name = "execute_open_export")]
+    pub async fn execute_open_export(&self, user_id: Uuid, export_id: Uuid) -> UseCaseResult<String> {
+        use crate::database::data_export::find_data_export;
+
+        let export = find_data_export(&self.db_pool, user_id, export_id).await?
+            .ok_or_else(|| UseCaseError::ValidationError("导出记录不存在".to_string()))?;
+
+        if !export.is_downloadable(Utc::now()) {
+            return Err(UseCaseError::BusinessLogicError("导出文件尚未生成或已过期".to_string()));
+        }
+
+        let key = export.file_path.as_deref().unwrap_or_default();
+        let (url, _) = self.storage.signed_url(key, &export.file_name())?;
+        Ok(url)
+    }
+
+    /// 处理待生成的导出任务，返回成功生成的数量
+    #[instrument(skip_all, name = "process_pending_exports")]
+    pub async fn process_pending_exports(&self) -> UseCaseResult<u64> {
+        use crate::database::data_export::{claim_pending_data_exports, mark_data_export_failed};
+
+        let exports = claim_pending_data_exports(
+            &self.db_pool,
+            Duration::minutes(EXPORT_STALE_MINUTES),
+            EXPORT_BATCH_SIZE,
+        ).await?;
+        let mut completed = 0;
+
+        for export in exports {
+            match self.generate_export(&export).await {
+                Ok(()) => completed += 1,
+                Err(e) => {
+                    error!(export_id = %export.id, user_id = %export.user_id, error = %e, "Failed to generate data export");
+                    if let Err(e) = mark_data_export_failed(&self.db_pool, export.id, &e.to_string()).await {
+                        error!(export_id = %export.id, error = %e, "Failed to mark data export as failed");
+                    }
+                }
+            }
+        }
+
+        if completed > 0 {
+            info!(count = %completed, "Generated data exports");
+        }
+        Ok(completed)
+    }
+
+    /// 将过期的导出标记为已过期，返回数量；文件由存储清理任务按相同的过期时间删除
+    #[instrument(skip_all, name = "cleanup_expired_exports")]
+    pub async fn cleanup_expired_exports(&self) -> UseCaseResult<u64> {
+        use crate::database::data_export::expire_data_exports;
+
+        let keys = expire_data_exports(&self.db_pool).await?;
+        if !keys.is_empty() {
+            info!(count = %keys.len(), "Data exports expired");
+        }
+        Ok(keys.len() as u64)
+    }
+
+    /// 生成单个导出文件并通知用户
+    async fn generate_export(&self, export: &DataExport) -> UseCaseResult<()> {
+        use crate::database::data_export::mark_data_export_ready;
+        use crate::database::notification::create_notification;
+
+        let archive = self.build_archive(export.user_id).await?;
+
+        let directory = Path::new(&self.config.directory);
+        tokio::fs::create_dir_all(directory).await
+            .map_err(|e| UseCaseError::InternalError(format!("无法创建导出目录: {}", e)))?;
+
+        // 先在本地写完整个文件再保存到存储，避免下载到不完整的文件
+        let key = export_key(export.id);
+        let temp_path = directory.join(format!("{}.json.tmp", export.id));
+        let expires_at = Utc::now() + Duration::hours(self.config.retention_hours);
+        let stored = async {
+            self.write_archive(&temp_path, &archive).await?;
+            self.storage.store_file(
+                &key,
+                &temp_path,
+                "application/json",
+                DATA_EXPORT_KIND,
+                Some(export.user_id),
+                Some(expires_at),
+            ).await
+        }.await;
+        let file_size = match stored {
+            Ok(file_size) => file_size,
+            Err(e) => {
+                let _ = tokio::fs::remove_file(&temp_path).await;
+                return Err(e);
+            }
+        };
+
+        mark_data_export_ready(&self.db_pool, export.id, &key, file_size as i64, expires_at).await?;
+
+        let notification = NewNotification::new(
+            export.user_id,
+            "data_export",
+            "数据导出已完成",
+            &format!("您的个人数据已打包完成，下载链接将于{}失效", expires_at.format("%Y-%m-%d %H:%M")),
+        ).data(json!({
+            "export_id": export.id,
+            "download_url": export.download_url(),
+            "expires_at": expires_at,
+        }));
+        if let Err(e) = create_notification(&self.db_pool, &notification).await {
+            error!(user_id = %export.user_id, error = %e, "Failed to create data export notification");
+        }
+
+        info!(export_id = %export.id, user_id = %export.user_id, size = %file_size, "Data export generated");
+        Ok(())
+    }
+
+    /// 写入导出文件，提交数据逐行从数据库流式读取，返回文件大小
+    async fn write_archive(&self, path: &Path, archive: &UserDataArchive) -> UseCaseResult<u64> {
+        use crate::database::stream_user_data;
+
+        let io_error = |e: std::io::Error| UseCaseError::InternalError(format!("无法写入导出文件: {}", e));
+        let mut writer = BufWriter::new(tokio::fs::File::create(path).await.map_err(io_error)?);
+
+        // 资料部分序列化后去掉结尾的 `}`，再追加 submissions 数组
+        let mut header = serde_json::to_vec(archive)?;
+        header.pop();
+        writer.write_all(&header).await.map_err(io_error)?;
+        writer.write_all(b",\"submissions\":[").await.map_err(io_error)?;
+
+        let submissions = stream_user_data(&self.db_pool, Some(&archive.profile.email)).await?;
+        let mut submissions = std::pin::pin!(submissions);
+        let mut first = true;
+        while let Some(submission) = submissions.try_next().await? {
+            if !first {
+                writer.write_all(b",").await.map_err(io_error)?;
+            }
+            first = false;
+            writer.write_all(&serde_json::to_vec(&submission)?).await.map_err(io_error)?;
+        }
+
+        writer.write_all(b"]}").await.map_err(io_error)?;
+        writer.flush().await.map_err(io_error)?;
+
+        let metadata = tokio::fs::metadata(path).await.map_err(io_error)?;
+        Ok(metadata.len())
+    }
+
+    /// 汇总用户的个人数据（提交数据在写入文件时流式读取）
+    async fn build_archive(&self, user_id: Uuid) -> UseCaseResult<UserDataArchive> {
+        use crate::database::data_export::{
+            get_archived_profile, list_archived_sessions,
+        };
+        use crate::database::login_log::list_user_login_logs;
+
+        let profile = get_archived_profile(&self.db_pool, user_id).await?
+            .ok_or_else(|| UseCaseError::BusinessLogicError("用户不存在".to_string()))?;
+        let login_history = list_user_login_logs(&self.db_pool, user_id, LOGIN_HISTORY_LIMIT).await?;
+        let sessions = list_archived_sessions(&self.db_pool, user_id).await?;
+
+        Ok(UserDataArchive {
+            generated_at: Utc::now(),
+            profile,
+            login_history,
+            sessions,
+        })
+    }
+
+    fn to_info(export: DataExport, newly_requested: bool) -> DataExportInfo {
+        let download_url = export.is_downloadable(Utc::now()).then(|| export.download_url());
+        DataExportInfo {
+            export,
+            download_url,
+            newly_requested,
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use chrono::{Duration, Utc};
+use tracing::{info, instrument};
+use uuid::Uuid;
+
+use crate::cache::{RedisPool, drafts::DraftCache};
+use crate::models::draft::FormDraft;
+use super::{UseCaseError, UseCaseResult};
+
+/// 表单键最大长度
+const MAX_FORM_KEY_LENGTH: usize = 64;
+/// 单个草稿序列化后的最大字节数
+const MAX_DRAFT_BYTES: usize = 64 * 1024;
+
+/// 表单草稿：前端定期保存未提交的表单，登出时据此提示未保存的数据
+pub struct DraftUseCase {
+    redis: RedisPool,
+}
+
+impl DraftUseCase {
+    pub fn new(redis: RedisPool) -> Self {
+        Self { redis }
+    }
+
+    /// 保存草稿
+    #[instrument(skip_all, name = "save_form_draft")]
+    pub async fn save(&self, user_id: Uuid, form_key: &str, data: serde_json::Value) -> UseCaseResult<FormDraft> {
+        Self::validate_form_key(form_key)?;
+        if data.is_null() {
+            return Err(UseCaseError::ValidationError("草稿内容不能为空".to_string()));
+        }
+        if data.to_string().len() > MAX_DRAFT_BYTES {
+            return Err(UseCaseError::ValidationError(format!("草稿内容不能超过{}KB", MAX_DRAFT_BYTES / 1024)));
+        }
+
+        let now = Utc::now();
+        let draft = FormDraft {
+            form_key: form_key.to_string(),
+            data,
+            updated_at: now,
+            expires_at: now + Duration::seconds(self.redis.ttl().form_draft as i64),
+        };
+        DraftCache::new(self.redis.clone()).set(user_id, &draft).await
+            .map_err(|e| UseCaseError::InternalError(format!("保存草稿失败: {}", e)))?;
+
+        info!(user_id = %user_id, form_key = %form_key, "Form draft saved");
+        Ok(draft)
+    }
+
+    /// 获取草稿，不存在时返回 None
+    pub async fn get(&self, user_id: Uuid, form_key: &str) -> UseCaseResult<Option<FormDraft>> {
+        Self::validate_form_key(form_key)?;
+        DraftCache::new(self.redis.clone()).get(user_id, form_key).await
+            .map_err(|e| UseCaseError::InternalError(format!("读取草稿失败: {}", e)))
+    }
+
+    /// 删除草稿（表单提交或放弃后调用）
+    #[instrument(skip_all, name = "delete_form_draft")]
+    pub async fn delete(&self, user_id: Uuid, form_key: &str) -> UseCaseResult<()> {
+        Self::validate_form_key(form_key)?;
+        let deleted = DraftCache::new(self.redis.clone()).delete(user_id, form_key).await
+            .map_err(|e| UseCaseError::InternalError(format!("删除草稿失败: {}", e)))?;
+        if deleted {
+            info!(user_id = %user_id, form_key = %form_key, "Form draft deleted");
+        }
+        Ok(())
+    }
+
+    /// 用户是否有未提交的草稿
+    pub async fn has_drafts(&self, user_id: Uuid) -> UseCaseResult<bool> {
+        DraftCache::new(self.redis.clone()).has_any(user_id).await
+            .map_err(|e| UseCaseError::InternalError(format!("读取草稿失败: {}", e)))
+    }
+
+    /// 表单键只允许小写字母、数字以及 `.` `_` `-`（同时避免与缓存键通配符冲突）
+    fn validate_form_key(form_key: &str) -> UseCaseResult<()> {
+        let valid_chars = form_key.chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '.' | '_' | '-'));
+
+        if form_key.is_empty() || form_key.len() > MAX_FORM_KEY_LENGTH || !valid_chars {
+            return Err(UseCaseError::ValidationError(format!("无效的表单键: {}", form_key)));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_form_key() {
+        assert!(DraftUseCase::validate_form_key("profile.edit").is_ok());
+        assert!(DraftUseCase::validate_form_key("order-note_2").is_ok());
+        assert!(DraftUseCase::validate_form_key("").is_err());
+        assert!(DraftUseCase::validate_form_key("profile*").is_err());
+        assert!(DraftUseCase::validate_form_key("Profile").is_err());
+        assert!(DraftUseCase::validate_form_key(&"a".repeat(MAX_FORM_KEY_LENGTH + 1)).is_err());
+    }
+}
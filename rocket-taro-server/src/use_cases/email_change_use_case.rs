@@ -0,0 +1,219 @@
+use chrono::{Duration, Utc};
+use std::net::IpAddr;
+use std::sync::Arc;
+use tokio_postgres::error::SqlState;
+use tracing::{info, warn, error, instrument};
+use uuid::Uuid;
+
+use crate::auth::PasswordHasher;
+use crate::cache::{RedisPool, user::UserCache, session::SessionCache};
+use crate::config::EmailChangeConfig;
+use crate::database::DbPool;
+use crate::mail::{MailMessage, Mailer};
+use crate::models::{
+    audit::AuditEvent,
+    auth::User,
+    email_change::{
+        EmailChangeConfirmation, EmailChangeRequest, EmailChangeStatus,
+        generate_email_change_token, hash_email_change_token,
+    },
+    profile::{ProfileField, PLACEHOLDER_EMAIL_SUFFIX},
+};
+use super::{UseCaseError, UseCaseResult, profile_use_case::validate_step};
+
+/// 修改邮箱：新旧邮箱分别收到确认链接，两个链接都确认后才修改
+pub struct EmailChangeUseCase {
+    db_pool: DbPool,
+    redis: RedisPool,
+    password_hasher: PasswordHasher,
+    mailer: Arc<dyn Mailer>,
+    config: EmailChangeConfig,
+}
+
+impl EmailChangeUseCase {
+    pub fn new(
+        db_pool: DbPool,
+        redis: RedisPool,
+        password_hasher: PasswordHasher,
+        mailer: Arc<dyn Mailer>,
+        config: EmailChangeConfig,
+    ) -> Self {
+        Self { db_pool, redis, password_hasher, mailer, config }
+    }
+
+    /// 申请修改邮箱：重新验证密码后向新旧邮箱发送确认链接（当前为微信占位邮箱时只发送到新邮箱）
+    #[instrument(skip_all, name = "request_email_change")]
+    pub async fn request(
+        &self,
+        user: &User,
+        session_id: Uuid,
+        request: EmailChangeRequest,
+        ip_address: Option<IpAddr>,
+    ) -> UseCaseResult<EmailChangeStatus> {
+        use crate::database::profile::{email_in_use, find_password_hash};
+        use crate::database::email_change::{create_email_change, delete_email_change};
+        use crate::database::audit::record_audit_event;
+
+        if user.is_guest {
+            return Err(UseCaseError::BusinessLogicError("游客账户无法修改邮箱，请先注册".to_string()));
+        }
+
+        let new_email = validate_step(ProfileField::Email, &request.new_email)?;
+        if new_email.eq_ignore_ascii_case(&user.email) {
+            return Err(UseCaseError::ValidationError("新邮箱与当前邮箱相同".to_string()));
+        }
+
+        let password_hash = find_password_hash(&self.db_pool, user.id).await?
+            .ok_or_else(|| UseCaseError::BusinessLogicError("账户不存在或已注销".to_string()))?;
+        // 微信用户没有密码，无法重新验证身份
+        if password_hash.is_empty() {
+            return Err(UseCaseError::BusinessLogicError("当前账户未设置密码，无法修改邮箱".to_string()));
+        }
+        if !self.password_hasher.verify_blocking(&request.current_password, &password_hash).await {
+            warn!(user_id = %user.id, "Email change rejected: current password mismatch");
+            return Err(UseCaseError::AuthenticationError("当前密码错误".to_string()));
+        }
+        if email_in_use(&self.db_pool, &new_email, user.id).await? {
+            return Err(UseCaseError::ValidationError("该邮箱已被使用".to_string()));
+        }
+
+        let requires_current = !user.email.is_empty() && !user.email.ends_with(PLACEHOLDER_EMAIL_SUFFIX);
+        let current_token = requires_current.then(generate_email_change_token);
+        let new_token = generate_email_change_token();
+        let expires_at = Utc::now() + Duration::minutes(self.config.token_ttl_minutes);
+
+        let pending = create_email_change(
+            &self.db_pool,
+            user.id,
+            &new_email,
+            session_id,
+            current_token.as_deref().map(hash_email_change_token).as_deref(),
+            &hash_email_change_token(&new_token),
+            expires_at,
+        ).await?;
+
+        let mut messages = vec![self.new_address_message(user, &new_email, &new_token)];
+        if let Some(token) = &current_token {
+            messages.push(self.current_address_message(user, &new_email, token));
+        }
+        for message in &messages {
+            if let Err(e) = self.mailer.send(message).await {
+                error!(user_id = %user.id, error = %e, "Failed to send email change confirmation");
+                if let Err(e) = delete_email_change(&self.db_pool, user.id).await {
+                    warn!(user_id = %user.id, error = %e, "Failed to discard email change request");
+                }
+                return Err(UseCaseError::InternalError("确认邮件发送失败".to_string()));
+            }
+        }
+
+        let event = AuditEvent::new("account.email_change_requested", "user")
+            .actor(user.id)
+            .target(user.id)
+            .ip(ip_address)
+            .details(serde_json::json!({ "pending_email": new_email }));
+        if let Err(e) = record_audit_event(&self.db_pool, &event).await {
+            error!(user_id = %user.id, error = %e, "Failed to record email change audit event");
+        }
+
+        info!(user_id = %user.id, requires_current = %requires_current, "Email change requested");
+        Ok(EmailChangeStatus::from(pending))
+    }
+
+    /// 当前待确认的邮箱修改
+    pub async fn status(&self, user: &User) -> UseCaseResult<Option<EmailChangeStatus>> {
+        use crate::database::email_change::get_email_change;
+
+        Ok(get_email_change(&self.db_pool, user.id).await?.map(EmailChangeStatus::from))
+    }
+
+    /// 取消邮箱修改，已发送的确认链接随之失效
+    #[instrument(skip_all, name = "cancel_email_change")]
+    pub async fn cancel(&self, user: &User) -> UseCaseResult<()> {
+        use crate::database::email_change::delete_email_change;
+
+        if !delete_email_change(&self.db_pool, user.id).await? {
+            return Err(UseCaseError::ValidationError("没有待确认的邮箱修改".to_string()));
+        }
+        info!(user_id = %user.id, "Email change cancelled");
+        Ok(())
+    }
+
+    /// 确认邮件中的令牌；两个地址都确认后修改邮箱，并使发起会话以外的会话失效
+    #[instrument(skip_all, name = "confirm_email_change")]
+    pub async fn confirm(&self, token: &str) -> UseCaseResult<EmailChangeStatus> {
+        use crate::database::email_change::confirm_email_change;
+        use crate::database::audit::record_audit_event;
+
+        let invalid = || UseCaseError::ValidationError("确认链接无效或已过期".to_string());
+        if token.is_empty() || token.len() > 128 {
+            return Err(invalid());
+        }
+
+        let confirmation = match confirm_email_change(&self.db_pool, &hash_email_change_token(token)).await {
+            Ok(confirmation) => confirmation.ok_or_else(invalid)?,
+            // 确认期间新邮箱被其他账户占用
+            Err(e) if e.code() == Some(&SqlState::UNIQUE_VIOLATION) => {
+                return Err(UseCaseError::ValidationError("该邮箱已被使用".to_string()));
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        match confirmation {
+            EmailChangeConfirmation::Pending(pending) => {
+                info!(user_id = %pending.user_id, "Email change partially confirmed");
+                Ok(EmailChangeStatus::from(pending))
+            }
+            EmailChangeConfirmation::Applied { user_id, email, revoked_sessions } => {
+                self.invalidate_cached_user(user_id).await;
+
+                let event = AuditEvent::new("account.email_changed", "user")
+                    .target(user_id)
+                    .details(serde_json::json!({ "revoked_sessions": revoked_sessions }));
+                if let Err(e) = record_audit_event(&self.db_pool, &event).await {
+                    error!(user_id = %user_id, error = %e, "Failed to record email change audit event");
+                }
+
+                Ok(EmailChangeStatus {
+                    pending_email: email,
+                    awaiting: Vec::new(),
+                    completed: true,
+                    expires_at: None,
+                })
+            }
+        }
+    }
+
+    // 会话缓存中保存了用户信息和已失效的会话，修改生效后一并清除
+    async fn invalidate_cached_user(&self, user_id: Uuid) {
+        let user_cache = UserCache::new(self.redis.clone());
+        let session_cache = SessionCache::new(self.redis.clone());
+        if let Err(e) = user_cache.invalidate_user(user_id).await {
+            warn!(user_id = %user_id, error = %e, "Failed to invalidate user cache");
+        }
+        if let Err(e) = session_cache.invalidate_user_sessions(user_id).await {
+            warn!(user_id = %user_id, error = %e, "Failed to invalidate session cache");
+        }
+    }
+
+    fn current_address_message(&self, user: &User, new_email: &str, token: &str) -> MailMessage {
+        MailMessage {
+            to: user.email.clone(),
+            subject: "确认修改邮箱".to_string(),
+            text: format!(
+                "账户 {} 正在将邮箱修改为 {}。\n如果是你本人的操作，请在 {} 分钟内打开以下链接确认：\n{}\n如果不是你本人的操作，请忽略本邮件并尽快修改密码。",
+                user.username, new_email, self.config.token_ttl_minutes, self.config.confirm_link(token),
+            ),
+        }
+    }
+
+    fn new_address_message(&self, user: &User, new_email: &str, token: &str) -> MailMessage {
+        MailMessage {
+            to: new_email.to_string(),
+            subject: "确认新邮箱".to_string(),
+            text: format!(
+                "请在 {} 分钟内打开以下链接，确认将此邮箱用于账户 {}：\n{}\n如果不是你本人的操作，请忽略本邮件。",
+                self.config.token_ttl_minutes, user.username, self.config.confirm_link(token),
+            ),
+        }
+    }
+}
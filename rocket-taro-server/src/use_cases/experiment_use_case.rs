@@ -0,0 +1,78 @@
+use chrono::Utc;
+use tracing::{debug, instrument, warn};
+use uuid::Uuid;
+
+use crate::config::ExperimentsConfig;
+use crate::database::DbPool;
+use crate::models::experiment::{ExperimentAssignment, ExperimentReport, VariantExposure};
+use crate::models::route_decision::RouteDecision;
+use super::UseCaseResult;
+
+/// A/B 实验：分组由路由决策器按配置计算，这里记录曝光并统计各分组的用户数
+pub struct ExperimentUseCase {
+    db_pool: DbPool,
+    config: ExperimentsConfig,
+}
+
+impl ExperimentUseCase {
+    pub fn new(db_pool: DbPool) -> Self {
+        Self { db_pool, config: ExperimentsConfig::default() }
+    }
+
+    /// 统计时使用的实验配置，未设置时没有实验
+    pub fn with_config(mut self, config: ExperimentsConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// 记录下发的指令中按实验分组选择的流程；记录失败只记录日志
+    pub async fn record_exposures(&self, user_id: Uuid, decisions: &[RouteDecision]) {
+        use crate::database::experiment::record_exposures;
+
+        let assignments = ExperimentAssignment::from_decisions(decisions);
+        if assignments.is_empty() {
+            return;
+        }
+        match record_exposures(&self.db_pool, user_id, &assignments).await {
+            Ok(()) => debug!(user_id = %user_id, assignments = ?assignments, "Experiment exposures recorded"),
+            Err(e) => warn!(user_id = %user_id, error = %e, "Failed to record experiment exposures"),
+        }
+    }
+
+    /// 配置中各实验的分组统计，分组按配置顺序，已从配置中移除但有曝光的分组排在最后
+    #[instrument(skip_all, name = "get_experiment_report")]
+    pub async fn report(&self) -> UseCaseResult<Vec<ExperimentReport>> {
+        use crate::database::experiment::count_exposures;
+
+        let names: Vec<&str> = self.config.definitions.iter().map(|definition| definition.name.as_str()).collect();
+        let counts = count_exposures(&self.db_pool, &names).await?;
+        let now = Utc::now();
+
+        Ok(self.config.definitions.iter().map(|definition| {
+            let mut variants: Vec<VariantExposure> = definition.variants.iter()
+                .map(|variant| VariantExposure { variant: variant.name.clone(), weight: variant.weight, users: 0, exposures: 0 })
+                .collect();
+            for count in counts.iter().filter(|count| count.experiment == definition.name) {
+                match variants.iter_mut().find(|variant| variant.variant == count.variant) {
+                    Some(variant) => {
+                        variant.users = count.users;
+                        variant.exposures = count.exposures;
+                    }
+                    None => variants.push(VariantExposure {
+                        variant: count.variant.clone(),
+                        weight: 0,
+                        users: count.users,
+                        exposures: count.exposures,
+                    }),
+                }
+            }
+            ExperimentReport {
+                name: definition.name.clone(),
+                running: self.config.enabled && definition.is_running(now),
+                starts_at: definition.starts_at,
+                ends_at: definition.ends_at,
+                variants,
+            }
+        }).collect())
+    }
+}
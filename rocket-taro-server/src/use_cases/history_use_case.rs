@@ -0,0 +1,36 @@
+use uuid::Uuid;
+
+use crate::database::DbPool;
+use crate::models::{history::{HistoryEntity, RowHistory}, response::PagedResponse};
+use crate::utils::pagination::{Cursor, Page, PageRequest};
+use super::{UseCaseError, UseCaseResult};
+
+/// 单页变更历史数量上限
+const MAX_HISTORY_PAGE_SIZE: i64 = 100;
+
+/// 变更历史用例：管理员查看用户和用户数据的修改记录
+pub struct HistoryUseCase {
+    db_pool: DbPool,
+}
+
+impl HistoryUseCase {
+    pub fn new(db_pool: DbPool) -> Self {
+        Self { db_pool }
+    }
+
+    /// 按时间倒序分页获取一行数据的变更历史
+    pub async fn timeline(&self, entity: &str, row_id: &str, cursor: Option<&str>, limit: Option<i64>) -> UseCaseResult<PagedResponse<RowHistory>> {
+        use crate::database::history::list_row_history;
+
+        let entity = HistoryEntity::parse(entity)
+            .ok_or_else(|| UseCaseError::ValidationError("不支持的数据类型".to_string()))?;
+        let row_id = Uuid::parse_str(row_id)
+            .map_err(|_| UseCaseError::ValidationError("无效的ID".to_string()))?;
+        let page = PageRequest::parse(cursor, limit, 20, MAX_HISTORY_PAGE_SIZE)
+            .map_err(UseCaseError::ValidationError)?;
+
+        let rows = list_row_history(&self.db_pool, entity, row_id, &page).await?;
+        let rows = Page::from_rows(rows, &page, |entry| Cursor::new(entry.changed_at, entry.id));
+        Ok(PagedResponse::from_page(rows, &page))
+    }
+}
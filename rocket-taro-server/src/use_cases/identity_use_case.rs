@@ -0,0 +1,177 @@
+use chrono::{NaiveDate, Utc};
+use serde_json::json;
+use std::sync::Arc;
+use tracing::{info, instrument, warn};
+use uuid::Uuid;
+
+use crate::config::IdentityVerificationConfig;
+use crate::database::DbPool;
+use crate::database::identity::IdentityResult;
+use crate::identity::{IdentityVerifier, VerificationOutcome};
+use crate::models::audit::AuditEvent;
+use crate::models::identity::{
+    IdentityRequirement, IdentityStatus, IdentityVerification, IdentityVerificationStatus, SubmitIdentityRequest,
+    VerificationBlock, mask_id_number, mask_name, parse_resident_id,
+};
+use super::session_issuer::RequestContext;
+use super::{UseCaseError, UseCaseResult};
+
+/// 姓名最大长度（含少数民族姓名中的间隔号）
+const MAX_REAL_NAME_LENGTH: usize = 50;
+
+/// 实名认证用例：提交姓名和证件号给第三方认证服务，保存脱敏后的结果，
+/// 并为需要实名或年龄限制的功能判断是否放行
+pub struct IdentityUseCase {
+    db_pool: DbPool,
+    verifier: Arc<dyn IdentityVerifier>,
+    config: IdentityVerificationConfig,
+}
+
+impl IdentityUseCase {
+    pub fn new(db_pool: DbPool, verifier: Arc<dyn IdentityVerifier>, config: IdentityVerificationConfig) -> Self {
+        Self { db_pool, verifier, config }
+    }
+
+    /// 当前认证状态；等待中的记录会向认证服务查询一次最新结果
+    pub async fn status(&self, user_id: Uuid) -> UseCaseResult<IdentityVerificationStatus> {
+        use crate::database::identity::get_identity_verification;
+
+        let Some(record) = get_identity_verification(&self.db_pool, user_id).await? else {
+            return Ok(IdentityVerificationStatus::unverified());
+        };
+        let record = self.refresh_pending(record).await?;
+        Ok(IdentityVerificationStatus::from_record(&record, self.config.min_age, Utc::now().date_naive()))
+    }
+
+    /// 提交实名认证；已认证或认证中的用户不能重复提交
+    #[instrument(skip_all, name = "submit_identity", fields(user_id = %user_id))]
+    pub async fn submit(
+        &self,
+        user_id: Uuid,
+        request: &SubmitIdentityRequest,
+        context: &RequestContext,
+    ) -> UseCaseResult<IdentityVerificationStatus> {
+        use crate::database::identity::{get_identity_verification, record_identity_submission};
+
+        let real_name = request.real_name.trim();
+        let id_number = request.id_number.trim().to_ascii_uppercase();
+        if real_name.is_empty() || real_name.chars().count() > MAX_REAL_NAME_LENGTH {
+            return Err(UseCaseError::ValidationError(format!("姓名不能为空且不超过{}个字符", MAX_REAL_NAME_LENGTH)));
+        }
+        // 先在本地校验格式，避免无效号码消耗认证服务调用
+        let Some(id_birth_date) = parse_resident_id(&id_number) else {
+            return Err(UseCaseError::ValidationError("请输入有效的18位身份证号".to_string()));
+        };
+
+        if let Some(record) = get_identity_verification(&self.db_pool, user_id).await? {
+            let record = self.refresh_pending(record).await?;
+            match record.status() {
+                IdentityStatus::Verified => return Err(UseCaseError::BusinessLogicError("已完成实名认证".to_string())),
+                IdentityStatus::Pending => return Err(UseCaseError::BusinessLogicError("实名认证审核中，请稍后查看结果".to_string())),
+                _ => {}
+            }
+            let max = self.config.max_attempts_per_day;
+            if max > 0 && record.attempts >= max as i32 {
+                warn!(attempts = record.attempts, "Identity verification attempt limit reached");
+                return Err(UseCaseError::BusinessLogicError("今日认证次数已用完，请明天再试".to_string()));
+            }
+        }
+
+        let outcome = self.verifier.verify(real_name, &id_number).await.map_err(|e| {
+            warn!(provider = self.verifier.name(), error = %e, "Identity verification request failed");
+            UseCaseError::InternalError("实名认证服务暂不可用，请稍后重试".to_string())
+        })?;
+        let result = Self::result_of(&outcome, Some(id_birth_date));
+        let record = record_identity_submission(
+            &self.db_pool,
+            user_id,
+            &mask_name(real_name),
+            &mask_id_number(&id_number),
+            self.verifier.name(),
+            &result,
+        ).await?;
+        info!(status = %record.status, provider = self.verifier.name(), "Identity verification submitted");
+        self.audit(&record, context).await;
+
+        Ok(IdentityVerificationStatus::from_record(&record, self.config.min_age, Utc::now().date_naive()))
+    }
+
+    /// 判断用户是否满足功能的认证要求；未开启拦截时总是满足
+    pub async fn check(&self, user_id: Uuid, requirement: IdentityRequirement) -> UseCaseResult<Option<VerificationBlock>> {
+        use crate::database::identity::get_identity_verification;
+
+        if !self.config.enforce {
+            return Ok(None);
+        }
+        let record = get_identity_verification(&self.db_pool, user_id).await?;
+        Ok(VerificationBlock::check(record.as_ref(), requirement, self.config.min_age, Utc::now().date_naive()))
+    }
+
+    /// 等待中的记录向认证服务查询结果，查询失败时保持等待状态
+    async fn refresh_pending(&self, record: IdentityVerification) -> UseCaseResult<IdentityVerification> {
+        use crate::database::identity::update_pending_identity;
+
+        let Some(reference) = record.provider_reference.as_deref().filter(|_| record.status() == IdentityStatus::Pending) else {
+            return Ok(record);
+        };
+        let outcome = match self.verifier.query(reference).await {
+            Ok(VerificationOutcome::Pending { .. }) => return Ok(record),
+            Ok(outcome) => outcome,
+            Err(e) => {
+                warn!(user_id = %record.user_id, error = %e, "Failed to query pending identity verification");
+                return Ok(record);
+            }
+        };
+        let result = Self::result_of(&outcome, None);
+        match update_pending_identity(&self.db_pool, record.user_id, &result).await? {
+            Some(updated) => {
+                info!(user_id = %updated.user_id, status = %updated.status, "Pending identity verification resolved");
+                self.audit(&updated, &RequestContext::default()).await;
+                Ok(updated)
+            }
+            None => Ok(record),
+        }
+    }
+
+    fn result_of(outcome: &VerificationOutcome, id_birth_date: Option<NaiveDate>) -> IdentityResult<'_> {
+        match outcome {
+            VerificationOutcome::Verified { birth_date, reference } => IdentityResult {
+                status: IdentityStatus::Verified.as_str(),
+                birth_date: birth_date.or(id_birth_date),
+                provider_reference: reference.as_deref(),
+                failure_reason: None,
+            },
+            VerificationOutcome::Rejected { reason } => IdentityResult {
+                status: IdentityStatus::Rejected.as_str(),
+                birth_date: None,
+                provider_reference: None,
+                failure_reason: Some(reason.as_str()),
+            },
+            VerificationOutcome::Pending { reference } => IdentityResult {
+                status: IdentityStatus::Pending.as_str(),
+                birth_date: id_birth_date,
+                provider_reference: Some(reference.as_str()),
+                failure_reason: None,
+            },
+        }
+    }
+
+    async fn audit(&self, record: &IdentityVerification, context: &RequestContext) {
+        use crate::database::audit::record_audit_event;
+
+        let action = match record.status() {
+            IdentityStatus::Verified => "identity.verified",
+            IdentityStatus::Rejected => "identity.rejected",
+            _ => return,
+        };
+        let event = AuditEvent::new(action, "user")
+            .actor(record.user_id)
+            .target(record.user_id)
+            .ip(context.ip_address)
+            .details(json!({ "provider": record.provider, "failure_reason": record.failure_reason }));
+        // 认证结果已保存，审计日志写入失败只记录日志
+        if let Err(e) = record_audit_event(&self.db_pool, &event).await {
+            warn!(error = %e, "Failed to record identity audit event");
+        }
+    }
+}
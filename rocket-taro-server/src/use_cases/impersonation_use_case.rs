@@ -0,0 +1,117 @@
+use serde_json::json;
+use std::net::IpAddr;
+use tracing::{info, warn, instrument};
+use uuid::Uuid;
+
+use crate::auth::AuthenticatedUser;
+use crate::cache::{RedisPool, session::SessionCache};
+use crate::config::ImpersonationConfig;
+use crate::database::DbPool;
+use crate::models::{
+    audit::AuditEvent,
+    auth::{UserInfo, UserSession},
+    impersonation::{ImpersonationBanner, ImpersonationSession},
+    route_command::RouteCommand,
+};
+use super::{UseCaseError, UseCaseResult};
+
+/// 管理员模拟登录：以目标用户身份创建短期会话，便于复现用户问题
+pub struct ImpersonationUseCase {
+    db_pool: DbPool,
+    redis: RedisPool,
+    config: ImpersonationConfig,
+}
+
+impl ImpersonationUseCase {
+    pub fn new(db_pool: DbPool, redis: RedisPool, config: ImpersonationConfig) -> Self {
+        Self { db_pool, redis, config }
+    }
+
+    /// 创建模拟登录会话并记录审计事件，返回会话和下发给客户端的用户信息、提示条指令
+    #[instrument(skip_all, name = "start_impersonation", fields(user_id = %user_id))]
+    pub async fn start(
+        &self,
+        admin: &AuthenticatedUser,
+        user_id: Uuid,
+        ip_address: Option<IpAddr>,
+        user_agent: Option<String>,
+    ) -> UseCaseResult<(ImpersonationSession, RouteCommand)> {
+        use crate::database::auth::create_impersonation_session;
+        use crate::database::audit::record_audit_event;
+        use crate::database::profile::find_active_user;
+
+        if !self.config.enabled {
+            return Err(UseCaseError::BusinessLogicError("模拟登录未启用".to_string()));
+        }
+        if user_id == admin.user.id {
+            return Err(UseCaseError::ValidationError("不能模拟登录自己的账户".to_string()));
+        }
+        let user = find_active_user(&self.db_pool, user_id).await?
+            .ok_or_else(|| UseCaseError::ValidationError("用户不存在或已停用".to_string()))?;
+        if user.is_admin {
+            return Err(UseCaseError::BusinessLogicError("不能模拟登录管理员账户".to_string()));
+        }
+
+        let session = create_impersonation_session(
+            &self.db_pool,
+            user.id,
+            admin.user.id,
+            self.config.session_ttl(),
+            user_agent,
+            ip_address,
+        ).await?;
+
+        // 审计记录失败时撤销会话，不允许存在没有审计记录的模拟登录
+        let event = AuditEvent::new("impersonation.started", "user")
+            .actor(admin.user.id)
+            .target(user.id)
+            .ip(ip_address)
+            .details(json!({
+                "session_id": session.id,
+                "admin_session_id": admin.session.id,
+                "expires_at": session.expires_at,
+            }));
+        if let Err(e) = record_audit_event(&self.db_pool, &event).await {
+            self.revoke(&session).await;
+            return Err(e.into());
+        }
+
+        let session_cache = SessionCache::new(self.redis.clone());
+        if let Err(e) = session_cache.cache_user_session(&user, &session).await {
+            warn!(session_id = %session.id, "Failed to cache impersonation session: {}", e);
+        }
+        info!(admin_id = %admin.user.id, session_id = %session.id, "Impersonation session started");
+
+        let banner = ImpersonationBanner::new(admin.user.id, user.id, &user.username, session.expires_at);
+        let user_info = UserInfo::from(user);
+        let route_command = RouteCommand::sequence(vec![
+            RouteCommand::process_data("user", json!(user_info)),
+            Self::banner_command(&banner),
+        ]);
+        let response = ImpersonationSession {
+            user: user_info,
+            session_token: session.session_token,
+            expires_at: session.expires_at,
+        };
+        Ok((response, route_command))
+    }
+
+    /// 模拟登录会话的提示条指令，普通会话返回 None
+    pub fn banner_for(auth_user: &AuthenticatedUser) -> Option<RouteCommand> {
+        let impersonator_id = auth_user.session.impersonator_id?;
+        let banner = ImpersonationBanner::new(impersonator_id, auth_user.user.id, &auth_user.user.username, auth_user.session.expires_at);
+        Some(Self::banner_command(&banner))
+    }
+
+    fn banner_command(banner: &ImpersonationBanner) -> RouteCommand {
+        RouteCommand::process_data("impersonation", json!(banner))
+    }
+
+    async fn revoke(&self, session: &UserSession) {
+        use crate::database::auth::logout_session;
+
+        if let Err(e) = logout_session(&self.db_pool, &session.session_token).await {
+            warn!(session_id = %session.id, "Failed to revoke unaudited impersonation session: {}", e);
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use chrono::{DateTime, Duration, Utc};
+use tracing::{info, instrument};
+use uuid::Uuid;
+
+use crate::config::LogArchiveConfig;
+use crate::database::DbPool;
+use crate::models::audit::AuditEvent;
+use crate::models::log_archive::{ArchiveReport, ArchiveTable, TableArchiveReport, month_start, next_month};
+use super::UseCaseResult;
+
+/// 日志归档用例：将过期的登录日志和审计日志移入按月分区的归档表，并按保留期删除
+pub struct LogArchiveUseCase {
+    db_pool: DbPool,
+    config: LogArchiveConfig,
+}
+
+impl LogArchiveUseCase {
+    pub fn new(db_pool: DbPool, config: LogArchiveConfig) -> Self {
+        Self { db_pool, config }
+    }
+
+    /// 归档所有日志表
+    #[instrument(skip_all, name = "archive_logs")]
+    pub async fn run(&self) -> UseCaseResult<ArchiveReport> {
+        let now = Utc::now();
+        let mut report = ArchiveReport::default();
+        for table in ArchiveTable::ALL {
+            report.tables.push(self.archive_table(table, now).await?);
+        }
+        Ok(report)
+    }
+
+    /// 管理员手动触发归档，记录审计事件
+    pub async fn run_by_admin(&self, admin_id: Uuid) -> UseCaseResult<ArchiveReport> {
+        use crate::database::audit::record_audit_event;
+
+        let report = self.run().await?;
+        let event = AuditEvent::new("log_archive.run", "log_archive")
+            .actor(admin_id)
+            .details(serde_json::to_value(&report).unwrap_or_default());
+        record_audit_event(&self.db_pool, &event).await?;
+        Ok(report)
+    }
+
+    async fn archive_table(&self, table: ArchiveTable, now: DateTime<Utc>) -> UseCaseResult<TableArchiveReport> {
+        use crate::database::log_archive::{archive_logs, purge_logs};
+
+        let policy = self.config.policy(table);
+        let mut report = TableArchiveReport { table, archived: 0, purged: 0, dropped_partitions: Vec::new() };
+
+        // 超过保留期的记录不再归档，直接从在线表删除
+        if policy.retention_days > 0 {
+            let before = now - Duration::days(policy.retention_days);
+            report.purged = self.in_batches(|batch_size| purge_logs(&self.db_pool, table, before, batch_size)).await?;
+        }
+
+        if policy.archive_after_days > 0 {
+            let before = now - Duration::days(policy.archive_after_days);
+            self.ensure_partitions(table, before).await?;
+            report.archived = self.in_batches(|batch_size| archive_logs(&self.db_pool, table, before, batch_size)).await?;
+        }
+
+        if policy.retention_days > 0 {
+            report.dropped_partitions = self.drop_expired_partitions(table, now - Duration::days(policy.retention_days)).await?;
+        }
+
+        if report.archived > 0 || report.purged > 0 || !report.dropped_partitions.is_empty() {
+            info!(
+                table = %table.table_name(),
+                archived = %report.archived,
+                purged = %report.purged,
+                dropped = ?report.dropped_partitions,
+                "Archived logs"
+            );
+        }
+        Ok(report)
+    }
+
+    // 按批执行，某批不足批量大小或达到批次上限时停止，返回处理的总行数
+    async fn in_batches<F, Fut>(&self, mut batch: F) -> UseCaseResult<u64>
+    where
+        F: FnMut(i64) -> Fut,
+        Fut: std::future::Future<Output = Result<u64, tokio_postgres::Error>>,
+    {
+        let batch_size = self.config.batch_size.max(1);
+        let mut total = 0;
+        for _ in 0..self.config.max_batches.max(1) {
+            let count = batch(batch_size).await?;
+            total += count;
+            if count < batch_size as u64 {
+                break;
+            }
+        }
+        Ok(total)
+    }
+
+    // 为待归档记录所在的每个月创建分区
+    async fn ensure_partitions(&self, table: ArchiveTable, before: DateTime<Utc>) -> UseCaseResult<()> {
+        use crate::database::log_archive::{create_archive_partition, oldest_log_before};
+
+        let Some(oldest) = oldest_log_before(&self.db_pool, table, before).await? else {
+            return Ok(());
+        };
+        let mut month = month_start(oldest);
+        let last = month_start(before);
+        while month <= last {
+            create_archive_partition(&self.db_pool, table, month).await?;
+            month = next_month(month);
+        }
+        Ok(())
+    }
+
+    // 删除整月都早于保留期的分区
+    async fn drop_expired_partitions(&self, table: ArchiveTable, before: DateTime<Utc>) -> UseCaseResult<Vec<String>> {
+        use crate::database::log_archive::{drop_archive_partition, list_archive_partitions};
+
+        let mut dropped = Vec::new();
+        for partition in list_archive_partitions(&self.db_pool, table).await? {
+            let Some(month) = table.partition_month(&partition) else {
+                continue;
+            };
+            if next_month(month) <= before.date_naive() {
+                drop_archive_partition(&self.db_pool, table, month).await?;
+                dropped.push(partition);
+            }
+        }
+        Ok(dropped)
+    }
+}
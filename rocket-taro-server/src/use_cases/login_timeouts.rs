@@ -0,0 +1,109 @@
+use std::fmt;
+use std::future::Future;
+use std::time::Duration;
+use tracing::warn;
+
+use crate::config::LoginTimeoutConfig;
+use crate::metrics::MetricsRegistry;
+
+/// 登录链路访问的外部依赖
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dependency {
+    Redis,
+    Postgres,
+    Wechat,
+}
+
+impl Dependency {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Dependency::Redis => "redis",
+            Dependency::Postgres => "postgres",
+            Dependency::Wechat => "wechat",
+        }
+    }
+
+    /// 超时时的错误码，由 RouteCommandGenerator 转换为提示
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            Dependency::Redis => "CACHE_TIMEOUT",
+            Dependency::Postgres => "DATABASE_TIMEOUT",
+            Dependency::Wechat => "WECHAT_TIMEOUT",
+        }
+    }
+
+    /// 超时次数的指标名，通过 GET /api/metrics 查看
+    pub fn metric(&self) -> &'static str {
+        match self {
+            Dependency::Redis => "login.timeouts.redis",
+            Dependency::Postgres => "login.timeouts.postgres",
+            Dependency::Wechat => "login.timeouts.wechat",
+        }
+    }
+}
+
+/// 依赖未在时间预算内响应
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DependencyTimeout {
+    pub dependency: Dependency,
+    pub budget: Duration,
+}
+
+impl fmt::Display for DependencyTimeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} 在 {} 毫秒内未响应", self.dependency.as_str(), self.budget.as_millis())
+    }
+}
+
+impl std::error::Error for DependencyTimeout {}
+
+/// 按依赖限制登录链路中每次等待的时长，超时时放弃等待并记录指标；
+/// 未设置时使用默认预算，指标计入独立的注册表
+#[derive(Clone, Default)]
+pub struct LoginTimeouts {
+    config: LoginTimeoutConfig,
+    metrics: MetricsRegistry,
+}
+
+impl LoginTimeouts {
+    pub fn new(config: LoginTimeoutConfig, metrics: MetricsRegistry) -> Self {
+        Self { config, metrics }
+    }
+
+    pub fn budget(&self, dependency: Dependency) -> Duration {
+        match dependency {
+            Dependency::Redis => self.config.redis(),
+            Dependency::Postgres => self.config.postgres(),
+            Dependency::Wechat => self.config.wechat(),
+        }
+    }
+
+    /// 在依赖的时间预算内等待 `future`，超时时丢弃该 future
+    pub async fn run<T>(&self, dependency: Dependency, future: impl Future<Output = T>) -> Result<T, DependencyTimeout> {
+        let budget = self.budget(dependency);
+        tokio::time::timeout(budget, future).await.map_err(|_| {
+            self.metrics.increment(dependency.metric());
+            warn!(dependency = dependency.as_str(), budget_ms = budget.as_millis() as u64, "Login dependency timed out");
+            DependencyTimeout { dependency, budget }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_run_records_timeouts() {
+        let metrics = MetricsRegistry::new();
+        let timeouts = LoginTimeouts::new(LoginTimeoutConfig { redis_ms: 10, ..LoginTimeoutConfig::default() }, metrics.clone());
+
+        assert_eq!(timeouts.run(Dependency::Redis, async { 42 }).await, Ok(42));
+
+        let timeout = timeouts.run(Dependency::Redis, tokio::time::sleep(Duration::from_secs(5))).await.unwrap_err();
+        assert_eq!(timeout.dependency, Dependency::Redis);
+        assert_eq!(timeout.to_string(), "redis 在 10 毫秒内未响应");
+        assert_eq!(metrics.get("login.timeouts.redis"), 1);
+        assert_eq!(metrics.get("login.timeouts.postgres"), 0);
+    }
+}
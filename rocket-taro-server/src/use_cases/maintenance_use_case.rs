@@ -0,0 +1,83 @@
+use chrono::Utc;
+use serde_json::json;
+use std::net::IpAddr;
+use tracing::{info, warn, instrument};
+
+use crate::cache::{RedisPool, maintenance::MaintenanceCache};
+use crate::database::DbPool;
+use crate::models::{
+    audit::AuditEvent,
+    auth::User,
+    maintenance::{MaintenanceRequest, MaintenanceState},
+};
+use super::{UseCaseError, UseCaseResult};
+
+/// 维护说明最大长度（字符）
+const MAX_MESSAGE_CHARS: usize = 200;
+
+/// 维护模式开关
+pub struct MaintenanceUseCase {
+    db_pool: DbPool,
+    redis: RedisPool,
+}
+
+impl MaintenanceUseCase {
+    pub fn new(db_pool: DbPool, redis: RedisPool) -> Self {
+        Self { db_pool, redis }
+    }
+
+    /// 当前维护状态，未开启时返回默认（关闭）状态
+    pub async fn status(&self) -> UseCaseResult<MaintenanceState> {
+        let state = MaintenanceCache::new(self.redis.clone()).get().await
+            .map_err(|e| UseCaseError::InternalError(format!("读取维护状态失败: {}", e)))?;
+        Ok(state.unwrap_or_default())
+    }
+
+    /// 开启或关闭维护模式，并记录审计日志
+    #[instrument(skip_all, name = "execute_update_maintenance")]
+    pub async fn execute_update(&self, admin: &User, request: MaintenanceRequest, ip_address: Option<IpAddr>) -> UseCaseResult<MaintenanceState> {
+        use crate::database::audit::record_audit_event;
+
+        let message = request.message
+            .map(|message| message.trim().to_string())
+            .filter(|message| !message.is_empty());
+        if message.as_ref().is_some_and(|message| message.chars().count() > MAX_MESSAGE_CHARS) {
+            return Err(UseCaseError::ValidationError(format!("维护说明不能超过{}个字符", MAX_MESSAGE_CHARS)));
+        }
+        if request.enabled && request.ends_at.is_some_and(|ends_at| ends_at <= Utc::now()) {
+            return Err(UseCaseError::ValidationError("结束时间必须晚于当前时间".to_string()));
+        }
+
+        let cache = MaintenanceCache::new(self.redis.clone());
+        let state = if request.enabled {
+            let state = MaintenanceState {
+                enabled: true,
+                message,
+                ends_at: request.ends_at,
+                updated_by: Some(admin.id),
+                updated_at: Some(Utc::now()),
+            };
+            cache.enable(&state).await
+                .map_err(|e| UseCaseError::InternalError(format!("开启维护模式失败: {}", e)))?;
+            state
+        } else {
+            cache.disable().await
+                .map_err(|e| UseCaseError::InternalError(format!("关闭维护模式失败: {}", e)))?;
+            MaintenanceState::default()
+        };
+
+        info!(admin_id = %admin.id, enabled = %state.enabled, ends_at = ?state.ends_at, "Maintenance mode updated");
+
+        let action = if state.enabled { "system.maintenance_enabled" } else { "system.maintenance_disabled" };
+        let event = AuditEvent::new(action, "system")
+            .actor(admin.id)
+            .ip(ip_address)
+            .details(json!({ "message": state.message, "ends_at": state.ends_at }));
+        // 开关已生效，审计日志写入失败只记录日志
+        if let Err(e) = record_audit_event(&self.db_pool, &event).await {
+            warn!(error = %e, "Failed to record maintenance audit event");
+        }
+
+        Ok(state)
+    }
+}
@@ -1,18 +1,74 @@
 pub mod auth_use_case;
 pub mod wx_auth_use_case;
 pub mod route_command_generator;  // 新增：路由决策器
+pub mod remote_config_use_case;
+pub mod account_use_case;
+pub mod data_export_use_case;
+pub mod admin_stats_use_case;
+pub mod payment_use_case;
+pub mod order_use_case;
+pub mod webhook_use_case;
+pub mod side_effect_use_case;
+pub mod bootstrap_use_case;
+pub mod profile_use_case;
+pub mod availability_use_case;
+pub mod account_flags;
+pub mod route_preview_use_case;
+pub mod route_execution_use_case;
+pub mod maintenance_use_case;
+pub mod announcement_use_case;
+pub mod session_expiry_use_case;
+pub mod settings_use_case;
+pub mod draft_use_case;
+pub mod email_change_use_case;
+pub mod account_recovery_use_case;
+pub mod qr_login_use_case;
+pub mod session_limit_use_case;
+pub mod reauth_use_case;
+pub mod upload_use_case;
+pub mod user_data_reply_use_case;
+pub mod history_use_case;
+pub mod log_archive_use_case;
+pub mod impersonation_use_case;
+pub mod cache_invalidation_use_case;
+pub mod cache_warmup_use_case;
+pub mod batch_use_case;
+pub mod session_lookup_use_case;
+pub mod runtime_stats_use_case;
+pub mod session_issuer;
+pub mod login_timeouts;
+pub mod presence_use_case;
+pub mod api_quota_use_case;
+pub mod registration_guard_use_case;
+pub mod consent_use_case;
+pub mod identity_use_case;
+pub mod moderation_use_case;
+pub mod avatar_use_case;
+pub mod storage_use_case;
+pub mod experiment_use_case;
 
 use std::error::Error;
 use std::fmt;
 
+use crate::database::version::VersionedUpdate;
+use crate::models::moderation::ContentRejection;
+use login_timeouts::DependencyTimeout;
+
 /// 用例执行错误类型
 #[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
 pub enum UseCaseError {
     DatabaseError(String),
     ValidationError(String),
     AuthenticationError(String),
     BusinessLogicError(String),
+    /// 乐观锁版本号不一致，数据已被其他请求修改
+    ConflictError(String),
     InternalError(String),
+    /// 依赖未在时间预算内响应
+    TimeoutError(DependencyTimeout),
+    /// 提交的内容未通过内容审核
+    ContentRejected(ContentRejection),
 }
 
 impl fmt::Display for UseCaseError {
@@ -22,7 +78,10 @@ impl fmt::Display for UseCaseError {
             UseCaseError::ValidationError(msg) => write!(f, "Validation error: {}", msg),
             UseCaseError::AuthenticationError(msg) => write!(f, "Authentication error: {}", msg),
             UseCaseError::BusinessLogicError(msg) => write!(f, "Business logic error: {}", msg),
+            UseCaseError::ConflictError(msg) => write!(f, "Conflict: {}", msg),
             UseCaseError::InternalError(msg) => write!(f, "Internal error: {}", msg),
+            UseCaseError::TimeoutError(timeout) => write!(f, "Timeout: {}", timeout),
+            UseCaseError::ContentRejected(rejection) => write!(f, "Content rejected: {}", rejection.subject.as_str()),
         }
     }
 }
@@ -42,6 +101,12 @@ impl From<tokio_postgres::Error> for UseCaseError {
     }
 }
 
+impl From<DependencyTimeout> for UseCaseError {
+    fn from(timeout: DependencyTimeout) -> Self {
+        UseCaseError::TimeoutError(timeout)
+    }
+}
+
 /// 将序列化错误转换为用例错误
 impl From<serde_json::Error> for UseCaseError {
     fn from(error: serde_json::Error) -> Self {
@@ -50,4 +115,20 @@ impl From<serde_json::Error> for UseCaseError {
 }
 
 /// 用例结果类型别名
-pub type UseCaseResult<T> = Result<T, UseCaseError>;
\ No newline at end of file
+pub type UseCaseResult<T> = Result<T, UseCaseError>;
+
+/// 更新请求必须提供版本号（If-Match 请求头或 version 字段）
+pub fn require_version(version: Option<i32>) -> UseCaseResult<i32> {
+    version.ok_or_else(|| UseCaseError::ValidationError("缺少版本号，请刷新后重试".to_string()))
+}
+
+/// 将带版本号条件的更新结果转换为用例结果
+pub fn versioned_result<T>(update: VersionedUpdate<T>, not_found: &str) -> UseCaseResult<T> {
+    match update {
+        VersionedUpdate::Updated(value) => Ok(value),
+        VersionedUpdate::Conflict { current_version } => {
+            Err(UseCaseError::ConflictError(format!("数据已被修改（当前版本 {}），请刷新后重试", current_version)))
+        }
+        VersionedUpdate::NotFound => Err(UseCaseError::BusinessLogicError(not_found.to_string())),
+    }
+}
@@ -0,0 +1,227 @@
+use serde_json::json;
+use std::sync::Arc;
+use tracing::{debug, error, info, instrument, warn};
+use uuid::Uuid;
+
+use crate::cache::{RedisPool, data::DataCache, session::SessionCache, user::UserCache};
+use crate::config::ModerationConfig;
+use crate::database::DbPool;
+use crate::models::audit::AuditEvent;
+use crate::models::auth::User;
+use crate::models::moderation::{
+    ContentRejection, ModerationCheck, ModerationCheckStatus, ModerationSubject, ModerationSuggest, RecheckReason,
+};
+use crate::moderation::{ContentModerator, TextSubmission};
+use super::{UseCaseError, UseCaseResult};
+
+/// 管理端一次最多列出的复核记录数
+const MAX_CHECK_PAGE_SIZE: i64 = 200;
+
+/// 内容审核用例：提交时同步审核，违规时拒绝；疑似违规或审核服务不可用时放行并加入复核队列，
+/// 后台任务复核后清除违规内容
+#[derive(Clone)]
+pub struct ModerationUseCase {
+    db_pool: DbPool,
+    redis: RedisPool,
+    moderator: Arc<dyn ContentModerator>,
+    config: ModerationConfig,
+}
+
+impl ModerationUseCase {
+    pub fn new(db_pool: DbPool, redis: RedisPool, moderator: Arc<dyn ContentModerator>, config: ModerationConfig) -> Self {
+        Self { db_pool, redis, moderator, config }
+    }
+
+    /// 审核文本，违规时返回 ContentRejected；放行但需要复核时返回原因，调用方保存后通过 enqueue_recheck 加入复核队列
+    #[instrument(skip_all, name = "moderate_text", fields(subject = subject.as_str()))]
+    pub async fn check_text(&self, subject: ModerationSubject, content: &str, user: Option<&User>) -> UseCaseResult<Option<RecheckReason>> {
+        if !self.config.enabled || content.trim().is_empty() {
+            return Ok(None);
+        }
+
+        let text = TextSubmission { content, subject, openid: user.and_then(|user| user.wx_openid.as_deref()) };
+        match self.moderator.check_text(&text).await {
+            Ok(verdict) => match verdict.suggest {
+                ModerationSuggest::Pass => Ok(None),
+                ModerationSuggest::Review => {
+                    info!(label = ?verdict.label, "Text flagged for review, accepting and queueing re-check");
+                    Ok(Some(RecheckReason::Review))
+                }
+                ModerationSuggest::Risky => {
+                    info!(label = ?verdict.label, user_id = ?user.map(|user| user.id), "Text rejected by moderation");
+                    Err(UseCaseError::ContentRejected(ContentRejection { subject, label: verdict.label }))
+                }
+            },
+            Err(e) => self.unavailable(&e).map(|_| Some(RecheckReason::Unavailable)),
+        }
+    }
+
+    /// 审核图片，违规时返回 ContentRejected；超过大小上限的图片不送审
+    #[instrument(skip_all, name = "moderate_image")]
+    pub async fn check_image(&self, image: &[u8], content_type: &str) -> UseCaseResult<()> {
+        if !self.config.enabled || !content_type.starts_with("image/") {
+            return Ok(());
+        }
+        if image.len() as u64 > self.config.image_max_size.as_u64() {
+            debug!(size = image.len(), "Image larger than moderation limit, skipping");
+            return Ok(());
+        }
+
+        match self.moderator.check_image(image, content_type).await {
+            Ok(verdict) if verdict.suggest == ModerationSuggest::Risky => {
+                info!(label = ?verdict.label, "Image rejected by moderation");
+                Err(UseCaseError::ContentRejected(ContentRejection { subject: ModerationSubject::Upload, label: verdict.label }))
+            }
+            Ok(_) => Ok(()),
+            Err(e) => self.unavailable(&e),
+        }
+    }
+
+    // 审核服务不可用：按 fail_open 放行或拒绝
+    fn unavailable(&self, error: &str) -> UseCaseResult<()> {
+        warn!(provider = self.moderator.name(), error = %error, fail_open = self.config.fail_open, "Moderation provider unavailable");
+        if self.config.fail_open {
+            Ok(())
+        } else {
+            Err(UseCaseError::BusinessLogicError("内容审核服务暂不可用，请稍后再试".to_string()))
+        }
+    }
+
+    /// 已保存的内容加入复核队列；写入失败只记录日志
+    pub async fn enqueue_recheck(
+        &self,
+        decision: Option<RecheckReason>,
+        subject: ModerationSubject,
+        subject_id: Uuid,
+        user_id: Option<Uuid>,
+        content: &str,
+    ) {
+        use crate::database::moderation::enqueue_moderation_check;
+
+        let Some(reason) = decision else { return };
+        match enqueue_moderation_check(
+            &self.db_pool,
+            subject.as_str(),
+            subject_id,
+            user_id,
+            content,
+            reason.as_str(),
+            self.config.recheck_delay_secs,
+        ).await {
+            Ok(()) => debug!(subject = subject.as_str(), subject_id = %subject_id, reason = reason.as_str(), "Moderation re-check queued"),
+            Err(e) => error!(subject = subject.as_str(), subject_id = %subject_id, error = %e, "Failed to queue moderation re-check"),
+        }
+    }
+
+    /// 复核到期的记录，返回处理的数量
+    #[instrument(skip_all, name = "recheck_moderation")]
+    pub async fn recheck_due(&self) -> UseCaseResult<usize> {
+        use crate::database::moderation::claim_due_moderation_checks;
+
+        if !self.config.enabled {
+            return Ok(0);
+        }
+        let checks = claim_due_moderation_checks(&self.db_pool, self.config.recheck_batch_size, self.config.recheck_delay_secs).await?;
+        for check in &checks {
+            if let Err(e) = self.recheck(check).await {
+                error!(check_id = %check.id, error = %e, "Failed to re-check moderated content");
+            }
+        }
+        if !checks.is_empty() {
+            info!(count = checks.len(), "Moderation re-checks processed");
+        }
+        Ok(checks.len())
+    }
+
+    async fn recheck(&self, check: &ModerationCheck) -> UseCaseResult<()> {
+        use crate::database::moderation::complete_moderation_check;
+
+        let Some(subject) = ModerationSubject::parse(&check.subject_type) else {
+            complete_moderation_check(&self.db_pool, check.id, ModerationCheckStatus::Failed.as_str(), None, Some("unknown subject type")).await?;
+            return Ok(());
+        };
+        let text = TextSubmission { content: &check.content, subject, openid: check.openid.as_deref() };
+        let exhausted = check.attempts >= self.config.recheck_max_attempts;
+
+        let (status, label, last_error) = match self.moderator.check_text(&text).await {
+            Ok(verdict) => match verdict.suggest {
+                ModerationSuggest::Pass => (ModerationCheckStatus::Passed, None, None),
+                ModerationSuggest::Risky => {
+                    self.remove(check, subject, verdict.label.as_deref()).await?;
+                    (ModerationCheckStatus::Removed, verdict.label, None)
+                }
+                // 仍需复核时继续等待，达到次数上限后交给人工处理
+                ModerationSuggest::Review if exhausted => (ModerationCheckStatus::Failed, verdict.label, None),
+                ModerationSuggest::Review => (ModerationCheckStatus::Pending, verdict.label, None),
+            },
+            Err(e) if exhausted => (ModerationCheckStatus::Failed, None, Some(e)),
+            Err(e) => (ModerationCheckStatus::Pending, None, Some(e)),
+        };
+        complete_moderation_check(&self.db_pool, check.id, status.as_str(), label.as_deref(), last_error.as_deref()).await?;
+        Ok(())
+    }
+
+    // 清除复核违规的内容，内容已被修改时不处理
+    async fn remove(&self, check: &ModerationCheck, subject: ModerationSubject, label: Option<&str>) -> UseCaseResult<()> {
+        use crate::database::audit::record_audit_event;
+        use crate::database::moderation::{clear_profile_name, clear_user_data_message};
+
+        let removed = match subject {
+            ModerationSubject::UserDataMessage => {
+                let removed = clear_user_data_message(&self.db_pool, check.subject_id, &check.content).await?;
+                if removed {
+                    let data_cache = DataCache::new(self.redis.clone());
+                    if let Err(e) = data_cache.invalidate_user_data(check.subject_id).await {
+                        warn!(user_data_id = %check.subject_id, error = %e, "Failed to invalidate user data cache");
+                    }
+                    if let Err(e) = data_cache.invalidate_search_results().await {
+                        warn!(error = %e, "Failed to invalidate user data search cache");
+                    }
+                }
+                removed
+            }
+            ModerationSubject::ProfileName => {
+                let removed = clear_profile_name(&self.db_pool, check.subject_id, &check.content).await?;
+                if removed {
+                    if let Err(e) = UserCache::new(self.redis.clone()).invalidate_user(check.subject_id).await {
+                        warn!(user_id = %check.subject_id, error = %e, "Failed to invalidate user cache");
+                    }
+                    if let Err(e) = SessionCache::new(self.redis.clone()).invalidate_user_sessions(check.subject_id).await {
+                        warn!(user_id = %check.subject_id, error = %e, "Failed to invalidate session cache");
+                    }
+                }
+                removed
+            }
+            ModerationSubject::Upload => false,
+        };
+        if !removed {
+            debug!(check_id = %check.id, "Moderated content already changed, nothing removed");
+            return Ok(());
+        }
+
+        info!(subject = subject.as_str(), subject_id = %check.subject_id, label = ?label, "Risky content removed after re-check");
+        let mut event = AuditEvent::new("moderation.removed", subject.as_str())
+            .target(check.subject_id)
+            .details(json!({ "label": label, "reason": check.reason }));
+        if let Some(user_id) = check.user_id {
+            event = event.actor(user_id);
+        }
+        if let Err(e) = record_audit_event(&self.db_pool, &event).await {
+            warn!(error = %e, "Failed to record moderation audit event");
+        }
+        Ok(())
+    }
+
+    /// 按状态列出复核记录（管理端）
+    pub async fn list_checks(&self, status: Option<&str>, limit: Option<i64>) -> UseCaseResult<Vec<ModerationCheck>> {
+        use crate::database::moderation::list_moderation_checks;
+
+        let status = match status {
+            Some(status) => ModerationCheckStatus::parse(status)
+                .ok_or_else(|| UseCaseError::ValidationError("状态应为 pending、passed、removed 或 failed".to_string()))?,
+            None => ModerationCheckStatus::Failed,
+        };
+        let limit = limit.unwrap_or(50).clamp(1, MAX_CHECK_PAGE_SIZE);
+        Ok(list_moderation_checks(&self.db_pool, status.as_str(), limit).await?)
+    }
+}
@@ -0,0 +1,183 @@
+use std::net::IpAddr;
+use chrono::{Duration, Utc};
+use rand::Rng;
+use serde_json::json;
+use tracing::{info, warn, error, instrument};
+
+use crate::config::OrderConfig;
+use crate::database::DbPool;
+use crate::models::{
+    audit::AuditEvent,
+    auth::User,
+    notification::NewNotification,
+    order::{CreateOrderRequest, Order, OrderStatus},
+    payment::{CreatePaymentRequest, PaymentOrderResult},
+};
+use crate::payments::WechatPayClient;
+use crate::utils::pagination::{Cursor, Page, PageRequest};
+use super::{UseCaseError, UseCaseResult};
+use super::payment_use_case::PaymentUseCase;
+
+/// 订单用例：下单、取消、发起支付及超时关闭
+pub struct OrderUseCase {
+    db_pool: DbPool,
+    config: OrderConfig,
+}
+
+impl OrderUseCase {
+    pub fn new(db_pool: DbPool, config: OrderConfig) -> Self {
+        Self { db_pool, config }
+    }
+
+    /// 创建待支付订单
+    #[instrument(skip_all, name = "execute_create_order")]
+    pub async fn execute_create_order(
+        &self,
+        user: &User,
+        request: &CreateOrderRequest,
+        ip_address: Option<IpAddr>,
+    ) -> UseCaseResult<Order> {
+        use crate::database::order::create_order;
+        use crate::database::audit::record_audit_event;
+
+        let total_amount = request.validate(self.config.max_items, self.config.max_amount)
+            .map_err(UseCaseError::ValidationError)?;
+
+        let order_no = generate_order_no();
+        let expires_at = Utc::now() + Duration::minutes(self.config.payment_timeout_minutes);
+        let remark = request.remark.as_deref().map(str::trim).filter(|remark| !remark.is_empty());
+
+        let order = create_order(
+            &self.db_pool,
+            user.id,
+            &order_no,
+            request.title.trim(),
+            &request.items,
+            total_amount,
+            remark,
+            expires_at,
+        ).await?;
+        info!(user_id = %user.id, order_no = %order.order_no, total_amount = %order.total_amount, "Order created");
+
+        let event = AuditEvent::new("order.created", "order")
+            .actor(user.id)
+            .target(&order.order_no)
+            .ip(ip_address)
+            .details(json!({ "total_amount": order.total_amount, "items": order.items.len() }));
+        if let Err(e) = record_audit_event(&self.db_pool, &event).await {
+            error!(order_no = %order.order_no, error = %e, "Failed to record order audit event");
+        }
+
+        Ok(order)
+    }
+
+    /// 查询当前用户的订单
+    pub async fn get_order(&self, user: &User, order_no: &str) -> UseCaseResult<Order> {
+        use crate::database::order::find_user_order;
+
+        find_user_order(&self.db_pool, user.id, order_no).await?
+            .ok_or_else(|| UseCaseError::ValidationError("订单不存在".to_string()))
+    }
+
+    /// 按状态筛选当前用户的订单
+    pub async fn list_orders(&self, user: &User, statuses: &[OrderStatus], page: &PageRequest) -> UseCaseResult<Page<Order>> {
+        use crate::database::order::list_user_orders;
+
+        let rows = list_user_orders(&self.db_pool, user.id, statuses, page).await?;
+        Ok(Page::from_rows(rows, page, |order| Cursor::new(order.created_at, order.id)))
+    }
+
+    /// 取消待支付订单
+    #[instrument(skip_all, name = "execute_cancel_order")]
+    pub async fn execute_cancel_order(&self, user: &User, order_no: &str, ip_address: Option<IpAddr>) -> UseCaseResult<Order> {
+        use crate::database::order::cancel_order;
+        use crate::database::audit::record_audit_event;
+
+        let order = self.get_order(user, order_no).await?;
+        if !order.status.can_transition_to(OrderStatus::Cancelled) {
+            return Err(UseCaseError::BusinessLogicError("当前订单状态不可取消".to_string()));
+        }
+
+        // 条件更新，避免与支付回调或超时关闭并发时覆盖状态
+        let order = cancel_order(&self.db_pool, order.id).await?
+            .ok_or_else(|| UseCaseError::BusinessLogicError("订单状态已变化，请刷新后重试".to_string()))?;
+        info!(user_id = %user.id, order_no = %order.order_no, "Order cancelled");
+
+        let event = AuditEvent::new("order.cancelled", "order")
+            .actor(user.id)
+            .target(&order.order_no)
+            .ip(ip_address);
+        if let Err(e) = record_audit_event(&self.db_pool, &event).await {
+            error!(order_no = %order.order_no, error = %e, "Failed to record order audit event");
+        }
+
+        Ok(order)
+    }
+
+    /// 为待支付订单创建支付单并返回调起支付所需参数
+    #[instrument(skip_all, name = "execute_pay_order")]
+    pub async fn execute_pay_order(
+        &self,
+        user: &User,
+        order_no: &str,
+        wechat_pay: &WechatPayClient,
+        ip_address: Option<IpAddr>,
+    ) -> UseCaseResult<PaymentOrderResult> {
+        use crate::database::order::attach_order_payment;
+
+        let order = self.get_order(user, order_no).await?;
+        if order.status != OrderStatus::Pending || order.expires_at <= Utc::now() {
+            return Err(UseCaseError::BusinessLogicError("订单已关闭或已支付".to_string()));
+        }
+
+        let payment_request = CreatePaymentRequest {
+            description: order.title.clone(),
+            amount: order.total_amount,
+        };
+        let result = PaymentUseCase::new(self.db_pool.clone(), wechat_pay)
+            .execute_create_payment(user, &payment_request, ip_address)
+            .await?;
+
+        attach_order_payment(&self.db_pool, order.id, &result.payment.out_trade_no).await?
+            .ok_or_else(|| UseCaseError::BusinessLogicError("订单状态已变化，请刷新后重试".to_string()))?;
+        info!(order_no = %order.order_no, out_trade_no = %result.payment.out_trade_no, "Payment attached to order");
+
+        Ok(result)
+    }
+
+    /// 关闭超时未支付的订单并通知用户，返回关闭数量
+    #[instrument(skip_all, name = "expire_overdue_orders")]
+    pub async fn expire_overdue_orders(&self) -> UseCaseResult<usize> {
+        use crate::database::order::expire_overdue_orders;
+        use crate::database::notification::create_notification;
+
+        let expired = expire_overdue_orders(&self.db_pool, self.config.expiry_batch_size).await?;
+
+        for order in &expired {
+            if order.out_trade_no.is_some() {
+                warn!(order_no = %order.order_no, "Expired order has a pending payment; late payments require manual refund");
+            }
+
+            let notification = NewNotification::new(
+                order.user_id,
+                "order",
+                "订单已关闭",
+                &format!("订单「{}」超时未支付，已自动关闭", order.title),
+            ).data(json!({ "order_no": order.order_no }));
+            if let Err(e) = create_notification(&self.db_pool, &notification).await {
+                error!(order_no = %order.order_no, error = %e, "Failed to create order expiry notification");
+            }
+        }
+
+        if !expired.is_empty() {
+            info!(count = %expired.len(), "Expired overdue orders");
+        }
+        Ok(expired.len())
+    }
+}
+
+/// 生成订单号：时间戳 + 随机数字（不超过32位）
+fn generate_order_no() -> String {
+    let suffix: u64 = rand::thread_rng().gen_range(0..10_000_000_000);
+    format!("O{}{:010}", Utc::now().format("%Y%m%d%H%M%S"), suffix)
+}
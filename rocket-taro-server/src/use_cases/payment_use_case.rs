@@ -0,0 +1,205 @@
+use std::net::IpAddr;
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use serde_json::json;
+use tracing::{info, warn, error, instrument};
+
+use crate::database::DbPool;
+use crate::models::{
+    audit::AuditEvent,
+    auth::User,
+    payment::{
+        CreatePaymentRequest, Payment, PaymentOrderResult, PaymentStatus,
+        WechatPayNotification, WechatPayTransaction,
+    },
+};
+use crate::payments::{WechatPayClient, WechatPayNotifyHeaders};
+use super::{UseCaseError, UseCaseResult};
+
+/// 商品描述最大长度（微信支付限制127字节）
+const MAX_DESCRIPTION_BYTES: usize = 127;
+
+/// 支付用例：下单与支付结果回调处理
+pub struct PaymentUseCase<'a> {
+    db_pool: DbPool,
+    wechat_pay: &'a WechatPayClient,
+}
+
+impl<'a> PaymentUseCase<'a> {
+    pub fn new(db_pool: DbPool, wechat_pay: &'a WechatPayClient) -> Self {
+        Self { db_pool, wechat_pay }
+    }
+
+    /// 创建支付单并向微信支付下单，返回调起支付所需参数
+    #[instrument(skip_all, name = "execute_create_payment")]
+    pub async fn execute_create_payment(
+        &self,
+        user: &User,
+        request: &CreatePaymentRequest,
+        ip_address: Option<IpAddr>,
+    ) -> UseCaseResult<PaymentOrderResult> {
+        use crate::database::payment::{create_payment, mark_payment_prepaid, transition_payment_status};
+        use crate::database::audit::record_audit_event;
+
+        if !self.wechat_pay.is_enabled() {
+            return Err(UseCaseError::BusinessLogicError("支付功能暂未开放".to_string()));
+        }
+
+        let description = request.description.trim();
+        if description.is_empty() || description.len() > MAX_DESCRIPTION_BYTES {
+            return Err(UseCaseError::ValidationError("商品描述不能为空且不能超过127字节".to_string()));
+        }
+        if request.amount <= 0 || request.amount > self.wechat_pay.config().max_amount {
+            return Err(UseCaseError::ValidationError("支付金额无效".to_string()));
+        }
+
+        let openid = user.wx_openid.as_deref()
+            .ok_or_else(|| UseCaseError::BusinessLogicError("请使用微信登录后再支付".to_string()))?;
+
+        let out_trade_no = generate_out_trade_no();
+        let payment = create_payment(&self.db_pool, user.id, &out_trade_no, description, request.amount).await?;
+        info!(user_id = %user.id, out_trade_no = %out_trade_no, amount = %request.amount, "Payment created");
+
+        let prepay_id = match self.wechat_pay.create_jsapi_order(&payment, openid).await {
+            Ok(prepay_id) => prepay_id,
+            Err(e) => {
+                error!(out_trade_no = %out_trade_no, error = %e, "WeChat Pay order creation failed");
+                if let Err(e) = transition_payment_status(&self.db_pool, &out_trade_no, PaymentStatus::Failed, None, None).await {
+                    error!(out_trade_no = %out_trade_no, error = %e, "Failed to mark payment as failed");
+                }
+                return Err(UseCaseError::InternalError(e));
+            }
+        };
+
+        let payment = mark_payment_prepaid(&self.db_pool, payment.id, &prepay_id).await?
+            .ok_or_else(|| UseCaseError::BusinessLogicError("支付单状态已变化".to_string()))?;
+        let pay_params = self.wechat_pay.build_request_payment_params(&prepay_id)
+            .map_err(UseCaseError::InternalError)?;
+
+        let event = AuditEvent::new("payment.created", "payment")
+            .actor(user.id)
+            .target(&payment.out_trade_no)
+            .ip(ip_address)
+            .details(json!({ "amount": payment.amount, "description": payment.description }));
+        if let Err(e) = record_audit_event(&self.db_pool, &event).await {
+            error!(out_trade_no = %payment.out_trade_no, error = %e, "Failed to record payment audit event");
+        }
+
+        Ok(PaymentOrderResult { payment, pay_params })
+    }
+
+    /// 查询当前用户的支付单
+    pub async fn get_payment(&self, user: &User, out_trade_no: &str) -> UseCaseResult<Payment> {
+        use crate::database::payment::find_payment_by_out_trade_no;
+
+        find_payment_by_out_trade_no(&self.db_pool, out_trade_no).await?
+            .filter(|payment| payment.user_id == user.id)
+            .ok_or_else(|| UseCaseError::ValidationError("支付单不存在".to_string()))
+    }
+
+    /// 处理微信支付结果回调：验签、解密、按状态机更新支付单
+    #[instrument(skip_all, name = "execute_payment_notification")]
+    pub async fn execute_payment_notification(&self, headers: &WechatPayNotifyHeaders, body: &str) -> UseCaseResult<()> {
+        use crate::database::payment::{find_payment_by_out_trade_no, transition_payment_status, record_payment_notification};
+        use crate::database::audit::record_audit_event;
+
+        self.wechat_pay.verify_notification(headers, body)
+            .map_err(UseCaseError::AuthenticationError)?;
+
+        let notification: WechatPayNotification = serde_json::from_str(body)
+            .map_err(|e| UseCaseError::ValidationError(format!("回调内容格式错误: {}", e)))?;
+        let plaintext = self.wechat_pay.decrypt_resource(&notification.resource)
+            .map_err(UseCaseError::ValidationError)?;
+        let transaction: WechatPayTransaction = serde_json::from_str(&plaintext)
+            .map_err(|e| UseCaseError::ValidationError(format!("支付结果格式错误: {}", e)))?;
+
+        info!(
+            notification_id = %notification.id,
+            out_trade_no = %transaction.out_trade_no,
+            trade_state = %transaction.trade_state,
+            "WeChat Pay notification received"
+        );
+
+        let payment = find_payment_by_out_trade_no(&self.db_pool, &transaction.out_trade_no).await?
+            .ok_or_else(|| UseCaseError::ValidationError(format!("未知的商户订单号: {}", transaction.out_trade_no)))?;
+
+        let config = self.wechat_pay.config();
+        if let Err(reason) = transaction.check(&config.app_id, &config.mch_id, payment.amount) {
+            error!(
+                out_trade_no = %payment.out_trade_no,
+                appid = %transaction.appid,
+                mchid = %transaction.mchid,
+                expected = %payment.amount,
+                actual = ?transaction.amount.as_ref().map(|amount| amount.total),
+                reason = %reason,
+                "Payment notification rejected"
+            );
+            return Err(UseCaseError::ValidationError(reason));
+        }
+
+        if let Some(target) = PaymentStatus::from_trade_state(&transaction.trade_state) {
+            if payment.status == target {
+                info!(out_trade_no = %payment.out_trade_no, "Duplicate payment notification ignored");
+            } else if payment.status.can_transition_to(target) {
+                let paid_at = (target == PaymentStatus::Paid).then(|| transaction.success_time.unwrap_or_else(Utc::now));
+                transition_payment_status(
+                    &self.db_pool,
+                    &payment.out_trade_no,
+                    target,
+                    transaction.transaction_id.as_deref(),
+                    paid_at,
+                ).await?;
+
+                if let Some(paid_at) = paid_at {
+                    self.mark_order_paid(&payment.out_trade_no, paid_at).await;
+                }
+
+                let event = AuditEvent::new(&format!("payment.{}", target.as_str()), "payment")
+                    .actor(payment.user_id)
+                    .target(&payment.out_trade_no)
+                    .details(json!({ "transaction_id": transaction.transaction_id, "trade_state": transaction.trade_state }));
+                if let Err(e) = record_audit_event(&self.db_pool, &event).await {
+                    error!(out_trade_no = %payment.out_trade_no, error = %e, "Failed to record payment audit event");
+                }
+                info!(out_trade_no = %payment.out_trade_no, status = %target.as_str(), "Payment status updated");
+            } else {
+                warn!(
+                    out_trade_no = %payment.out_trade_no,
+                    from = %payment.status.as_str(),
+                    to = %target.as_str(),
+                    "Ignoring payment notification with invalid state transition"
+                );
+            }
+        }
+
+        let payload = serde_json::from_str(&plaintext).unwrap_or(serde_json::Value::Null);
+        if let Err(e) = record_payment_notification(
+            &self.db_pool,
+            &notification.id,
+            &transaction.out_trade_no,
+            &notification.event_type,
+            &payload,
+        ).await {
+            error!(notification_id = %notification.id, error = %e, "Failed to record payment notification");
+        }
+
+        Ok(())
+    }
+
+    /// 支付成功后同步关联订单的状态
+    async fn mark_order_paid(&self, out_trade_no: &str, paid_at: DateTime<Utc>) {
+        use crate::database::order::mark_order_paid;
+
+        match mark_order_paid(&self.db_pool, out_trade_no, paid_at).await {
+            Ok(Some(order)) => info!(order_no = %order.order_no, out_trade_no = %out_trade_no, "Order marked as paid"),
+            Ok(None) => info!(out_trade_no = %out_trade_no, "No pending order linked to paid payment"),
+            Err(e) => error!(out_trade_no = %out_trade_no, error = %e, "Failed to mark order as paid"),
+        }
+    }
+}
+
+/// 生成商户订单号：时间戳 + 随机数字（不超过32位）
+fn generate_out_trade_no() -> String {
+    let suffix: u64 = rand::thread_rng().gen_range(0..10_000_000_000);
+    format!("P{}{:010}", Utc::now().format("%Y%m%d%H%M%S"), suffix)
+}
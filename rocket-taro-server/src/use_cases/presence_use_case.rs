@@ -0,0 +1,50 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use tracing::{debug, instrument};
+
+use crate::cache::{RedisPool, presence::PresenceCache};
+use crate::config::{Platform, PresenceConfig};
+use crate::models::presence::{PlatformPresence, PresenceStats};
+use super::{UseCaseError, UseCaseResult};
+
+/// 在线用户统计用例：认证守卫记录访问，管理后台读取在线人数
+pub struct PresenceUseCase {
+    cache: PresenceCache,
+    config: PresenceConfig,
+}
+
+impl PresenceUseCase {
+    pub fn new(redis: RedisPool, config: PresenceConfig) -> Self {
+        Self { cache: PresenceCache::new(redis), config }
+    }
+
+    /// 记录用户的一次访问，未启用时不记录
+    pub async fn record(&self, user_id: Uuid, platform: Platform, seen_at: DateTime<Utc>) -> UseCaseResult<()> {
+        if !self.config.enabled {
+            return Ok(());
+        }
+        self.cache.touch(user_id, platform, seen_at, self.config.key_ttl_secs()).await
+            .map_err(|e| UseCaseError::InternalError(format!("记录在线状态失败: {}", e)))
+    }
+
+    /// 统计当前在线用户，先清理窗口外的记录
+    #[instrument(skip_all, name = "get_presence")]
+    pub async fn snapshot(&self, now: DateTime<Utc>) -> UseCaseResult<PresenceStats> {
+        let since = self.config.online_since(now);
+        let read_error = |e: redis::RedisError| UseCaseError::InternalError(format!("读取在线用户失败: {}", e));
+
+        let pruned = self.cache.prune(since).await.map_err(read_error)?;
+        debug!(pruned, "Pruned stale presence entries");
+
+        Ok(PresenceStats {
+            online_users: self.cache.count(None, since).await.map_err(read_error)?,
+            by_platform: PlatformPresence {
+                miniprogram: self.cache.count(Some(Platform::Miniprogram), since).await.map_err(read_error)?,
+                h5: self.cache.count(Some(Platform::H5), since).await.map_err(read_error)?,
+                admin: self.cache.count(Some(Platform::Admin), since).await.map_err(read_error)?,
+            },
+            window_secs: self.config.online_window_secs,
+            generated_at: now,
+        })
+    }
+}
@@ -0,0 +1,224 @@
+use tokio_postgres::error::SqlState;
+use tracing::{info, warn, instrument};
+
+use crate::cache::{RedisPool, user::UserCache, session::SessionCache};
+use crate::database::DbPool;
+use crate::models::{
+    auth::User,
+    moderation::{ModerationSubject, RecheckReason},
+    profile::{
+        ProfileCompletion, ProfileField, ProfileStepRequest, ProfileStepResult,
+        ProfileUpdateRequest, ProfileUpdateResult, PLACEHOLDER_EMAIL_SUFFIX,
+    },
+};
+use super::moderation_use_case::ModerationUseCase;
+use super::{UseCaseError, UseCaseResult, require_version, versioned_result};
+
+/// 姓名最大长度（字符）
+const MAX_FULL_NAME_CHARS: usize = 50;
+/// 邮箱最大长度
+const MAX_EMAIL_LENGTH: usize = 255;
+/// 头像地址最大长度
+const MAX_AVATAR_URL_LENGTH: usize = 500;
+
+/// 资料用例：资料完善步骤条与非微信用户的资料修改
+pub struct ProfileUseCase {
+    db_pool: DbPool,
+    redis: RedisPool,
+    moderation: Option<ModerationUseCase>,
+}
+
+impl ProfileUseCase {
+    pub fn new(db_pool: DbPool, redis: RedisPool) -> Self {
+        Self { db_pool, redis, moderation: None }
+    }
+
+    /// 设置昵称（姓名）的内容审核，未设置时不审核
+    pub fn with_moderation(mut self, moderation: ModerationUseCase) -> Self {
+        self.moderation = Some(moderation);
+        self
+    }
+
+    /// 当前用户的资料完善状态
+    pub fn completion(&self, user: &User) -> ProfileCompletion {
+        ProfileCompletion::for_user(user)
+    }
+
+    /// 保存步骤条中的一步，version 为 If-Match 请求头中的版本号（优先于请求体中的 version）
+    #[instrument(skip_all, name = "execute_profile_step")]
+    pub async fn execute_update_step(&self, user: &User, request: ProfileStepRequest, version: Option<i32>) -> UseCaseResult<ProfileStepResult> {
+        use crate::database::profile::{update_profile_field, email_in_use};
+
+        info!(user_id = %user.id, field = %request.field.as_str(), "Processing profile step");
+
+        if user.is_guest {
+            return Err(UseCaseError::BusinessLogicError("游客账户无法完善资料，请先注册".to_string()));
+        }
+
+        let value = validate_step(request.field, &request.value)?;
+        let expected_version = require_version(version.or(request.version))?;
+
+        if request.field == ProfileField::Email && email_in_use(&self.db_pool, &value, user.id).await? {
+            return Err(UseCaseError::ValidationError("该邮箱已被使用".to_string()));
+        }
+        let moderation = match request.field {
+            ProfileField::FullName => self.moderate_name(user, &value).await?,
+            _ => None,
+        };
+
+        let updated = match update_profile_field(&self.db_pool, user.id, expected_version, request.field, &value).await {
+            Ok(updated) => updated,
+            // 并发提交相同邮箱时由唯一约束兜底
+            Err(e) if e.code() == Some(&SqlState::UNIQUE_VIOLATION) => {
+                return Err(UseCaseError::ValidationError("该邮箱已被使用".to_string()));
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let updated = versioned_result(updated, "账户不存在或已注销")?;
+
+        self.invalidate_cached_user(user).await;
+        self.enqueue_name_recheck(moderation, user, &value).await;
+
+        let completion = ProfileCompletion::for_user(&updated);
+        info!(user_id = %user.id, completed = %completion.completed, remaining = %completion.missing_fields.len(), "Profile step saved");
+        Ok(ProfileStepResult { user: updated, completion })
+    }
+
+    /// 修改资料（密码、H5、管理员用户）；邮箱需要通过 EmailChangeUseCase 验证新旧地址后修改，version 同上
+    #[instrument(skip_all, name = "execute_update_profile")]
+    pub async fn execute_update_profile(&self, user: &User, request: ProfileUpdateRequest, version: Option<i32>) -> UseCaseResult<ProfileUpdateResult> {
+        use crate::database::profile::update_profile;
+
+        info!(user_id = %user.id, "Processing profile update");
+
+        if user.is_guest {
+            return Err(UseCaseError::BusinessLogicError("游客账户无法修改资料，请先注册".to_string()));
+        }
+
+        // 与当前邮箱相同（不区分大小写）时不视为修改
+        if request.email.as_deref().is_some_and(|email| !email.trim().eq_ignore_ascii_case(&user.email)) {
+            return Err(UseCaseError::BusinessLogicError("修改邮箱需要验证新旧邮箱，请使用修改邮箱功能".to_string()));
+        }
+
+        let full_name = request.full_name.as_deref()
+            .map(|value| validate_step(ProfileField::FullName, value))
+            .transpose()?;
+        let avatar_url = request.avatar_url.as_deref()
+            .map(|value| validate_step(ProfileField::AvatarUrl, value))
+            .transpose()?;
+
+        if full_name.is_none() && avatar_url.is_none() {
+            return Err(UseCaseError::ValidationError("没有需要修改的资料".to_string()));
+        }
+        let expected_version = require_version(version.or(request.version))?;
+        let moderation = match full_name.as_deref() {
+            Some(full_name) => self.moderate_name(user, full_name).await?,
+            None => None,
+        };
+
+        let updated = update_profile(&self.db_pool, user.id, expected_version, full_name.as_deref(), avatar_url.as_deref()).await?;
+        let updated = versioned_result(updated, "账户不存在或已注销")?;
+
+        self.invalidate_cached_user(user).await;
+        if let Some(full_name) = full_name.as_deref() {
+            self.enqueue_name_recheck(moderation, user, full_name).await;
+        }
+
+        info!(user_id = %user.id, "Profile updated");
+        Ok(ProfileUpdateResult { user: updated })
+    }
+
+    // 审核昵称，违规时拒绝修改
+    async fn moderate_name(&self, user: &User, full_name: &str) -> UseCaseResult<Option<RecheckReason>> {
+        match &self.moderation {
+            Some(moderation) => moderation.check_text(ModerationSubject::ProfileName, full_name, Some(user)).await,
+            None => Ok(None),
+        }
+    }
+
+    // 疑似违规或审核服务不可用时放行的昵称加入复核队列
+    async fn enqueue_name_recheck(&self, decision: Option<RecheckReason>, user: &User, full_name: &str) {
+        if let Some(moderation) = &self.moderation {
+            moderation.enqueue_recheck(decision, ModerationSubject::ProfileName, user.id, Some(user.id), full_name).await;
+        }
+    }
+
+    // 会话缓存中保存了用户信息，资料变更后需要一并清除
+    async fn invalidate_cached_user(&self, user: &User) {
+        let user_cache = UserCache::new(self.redis.clone());
+        let session_cache = SessionCache::new(self.redis.clone());
+        if let Err(e) = user_cache.invalidate_user(user.id).await {
+            warn!(user_id = %user.id, error = %e, "Failed to invalidate user cache");
+        }
+        if let Err(e) = session_cache.invalidate_user_sessions(user.id).await {
+            warn!(user_id = %user.id, error = %e, "Failed to invalidate session cache");
+        }
+    }
+}
+
+/// 校验单个字段，返回规范化后的值
+pub fn validate_step(field: ProfileField, value: &str) -> UseCaseResult<String> {
+    let value = value.trim();
+    match field {
+        ProfileField::FullName => {
+            if value.is_empty() {
+                return Err(UseCaseError::ValidationError("请输入姓名".to_string()));
+            }
+            if value.chars().count() > MAX_FULL_NAME_CHARS {
+                return Err(UseCaseError::ValidationError(format!("姓名不能超过{}个字符", MAX_FULL_NAME_CHARS)));
+            }
+            if value.chars().any(char::is_control) {
+                return Err(UseCaseError::ValidationError("姓名包含非法字符".to_string()));
+            }
+        }
+        ProfileField::Email => {
+            if !is_valid_email(value) || value.len() > MAX_EMAIL_LENGTH {
+                return Err(UseCaseError::ValidationError("邮箱格式不正确".to_string()));
+            }
+            if value.ends_with(PLACEHOLDER_EMAIL_SUFFIX) {
+                return Err(UseCaseError::ValidationError("请填写真实邮箱".to_string()));
+            }
+        }
+        ProfileField::AvatarUrl => {
+            let valid = (value.starts_with("https://") || value.starts_with("http://"))
+                && value.len() <= MAX_AVATAR_URL_LENGTH
+                && !value.chars().any(char::is_whitespace);
+            if !valid {
+                return Err(UseCaseError::ValidationError("头像地址不正确".to_string()));
+            }
+        }
+    }
+    Ok(value.to_string())
+}
+
+fn is_valid_email(email: &str) -> bool {
+    crate::utils::validation::parse_email(email).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_full_name() {
+        assert_eq!(validate_step(ProfileField::FullName, "  张三 ").unwrap(), "张三");
+        assert!(validate_step(ProfileField::FullName, "   ").is_err());
+        assert!(validate_step(ProfileField::FullName, &"名".repeat(51)).is_err());
+        assert!(validate_step(ProfileField::FullName, "a\u{0}b").is_err());
+    }
+
+    #[test]
+    fn test_validate_email() {
+        assert_eq!(validate_step(ProfileField::Email, "alice@example.com ").unwrap(), "alice@example.com");
+        for invalid in ["", "alice", "@example.com", "alice@example", "alice@.com", "a b@example.com", "a@b@example.com", "o123@wx.temp"] {
+            assert!(validate_step(ProfileField::Email, invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_validate_avatar_url() {
+        assert!(validate_step(ProfileField::AvatarUrl, "https://cdn.example.com/a.png").is_ok());
+        assert!(validate_step(ProfileField::AvatarUrl, "javascript:alert(1)").is_err());
+        assert!(validate_step(ProfileField::AvatarUrl, "https://cdn.example.com/a b.png").is_err());
+    }
+}
@@ -0,0 +1,255 @@
+use chrono::{Duration, Utc};
+use serde_json::json;
+use std::net::IpAddr;
+use tokio::sync::broadcast;
+use tracing::{info, warn, error, instrument};
+use uuid::Uuid;
+
+use crate::cache::{RedisPool, qr_login::QrLoginCache};
+use crate::config::{QrLoginConfig, RouteConfig};
+use crate::database::DbPool;
+use crate::events::{AuthMethod, EventBus};
+use crate::models::{
+    audit::AuditEvent,
+    auth::{LoginResponse, User, UserInfo},
+    qr_login::{
+        QrLoginChallenge, QrLoginState, QrLoginStatus, QrLoginTicket, QR_LOGIN_EVENT,
+        generate_poll_token, hash_poll_token,
+    },
+    route_command::RouteCommand,
+};
+use crate::push::{PushHub, PushMessage};
+use super::{UseCaseError, UseCaseResult, route_command_generator::RouteCommandGenerator, session_issuer::{RequestContext, SessionIssuer}};
+
+/// 网页端轮询结果，确认后首次轮询领取会话
+#[derive(Debug)]
+pub struct QrLoginPollResult {
+    pub state: QrLoginState,
+    pub login: Option<LoginResponse>,
+}
+
+/// 管理后台扫码登录：网页端获取二维码，管理员用小程序扫码并确认，网页端通过推送或轮询领取会话
+pub struct QrLoginUseCase {
+    db_pool: DbPool,
+    redis: RedisPool,
+    events: EventBus,
+    push: PushHub,
+    config: QrLoginConfig,
+    route_config: RouteConfig,
+}
+
+impl QrLoginUseCase {
+    pub fn new(
+        db_pool: DbPool,
+        redis: RedisPool,
+        events: EventBus,
+        push: PushHub,
+        config: QrLoginConfig,
+        route_config: RouteConfig,
+    ) -> Self {
+        Self { db_pool, redis, events, push, config, route_config }
+    }
+
+    fn cache(&self) -> QrLoginCache {
+        QrLoginCache::new(self.redis.clone())
+    }
+
+    /// 网页端获取二维码
+    #[instrument(skip_all, name = "create_qr_login")]
+    pub async fn create(&self, user_agent: Option<String>, ip_address: Option<IpAddr>) -> UseCaseResult<QrLoginChallenge> {
+        let now = Utc::now();
+        let poll_token = generate_poll_token();
+        let ticket = QrLoginTicket {
+            login_id: Uuid::new_v4(),
+            poll_token_hash: hash_poll_token(&poll_token),
+            status: QrLoginStatus::Pending,
+            user_id: None,
+            user_agent,
+            ip_address,
+            created_at: now,
+            expires_at: now + Duration::seconds(self.config.token_ttl_secs as i64),
+        };
+        self.save(&ticket).await?;
+
+        info!(login_id = %ticket.login_id, "QR login created");
+        Ok(QrLoginChallenge {
+            login_id: ticket.login_id,
+            qr_content: self.config.qr_content(&ticket.login_id.to_string()),
+            poll_token,
+            expires_at: ticket.expires_at,
+        })
+    }
+
+    /// 网页端订阅扫码状态推送，确认后会话通过推送下发
+    pub async fn subscribe(&self, login_id: Uuid, poll_token: &str) -> UseCaseResult<broadcast::Receiver<PushMessage>> {
+        self.authorize(login_id, poll_token).await?;
+        Ok(self.push.subscribe(login_id))
+    }
+
+    /// 网页端轮询扫码状态；已确认时领取会话，已拒绝时结束本次扫码登录
+    #[instrument(skip_all, name = "poll_qr_login")]
+    pub async fn poll(&self, login_id: Uuid, poll_token: &str) -> UseCaseResult<QrLoginPollResult> {
+        let ticket = self.authorize(login_id, poll_token).await?;
+        let state = QrLoginState { status: ticket.status, expires_at: ticket.expires_at };
+
+        let login = match ticket.status {
+            QrLoginStatus::Confirmed => Some(self.claim(&ticket).await?),
+            QrLoginStatus::Rejected => {
+                self.take(login_id).await?;
+                None
+            }
+            QrLoginStatus::Pending | QrLoginStatus::Scanned => None,
+        };
+        Ok(QrLoginPollResult { state, login })
+    }
+
+    /// 管理员在小程序中扫码
+    #[instrument(skip_all, name = "scan_qr_login")]
+    pub async fn scan(&self, user: &User, login_id: Uuid) -> UseCaseResult<()> {
+        Self::ensure_admin(user)?;
+        let mut ticket = self.load(login_id).await?;
+        match (ticket.status, ticket.user_id) {
+            (QrLoginStatus::Pending, _) => {}
+            // 同一管理员重复扫码
+            (QrLoginStatus::Scanned, Some(user_id)) if user_id == user.id => return Ok(()),
+            _ => return Err(UseCaseError::BusinessLogicError("二维码已被使用，请在网页上刷新".to_string())),
+        }
+
+        ticket.status = QrLoginStatus::Scanned;
+        ticket.user_id = Some(user.id);
+        self.save(&ticket).await?;
+        self.notify(&ticket, RouteCommand::toast("已扫码，请在手机上确认登录"));
+
+        info!(login_id = %login_id, user_id = %user.id, "QR login scanned");
+        Ok(())
+    }
+
+    /// 管理员在小程序中确认或拒绝；确认时网页端在线则直接推送会话，否则等待网页端轮询领取
+    #[instrument(skip_all, name = "confirm_qr_login")]
+    pub async fn confirm(&self, user: &User, login_id: Uuid, approve: bool) -> UseCaseResult<()> {
+        Self::ensure_admin(user)?;
+        let mut ticket = self.load(login_id).await?;
+        if ticket.status != QrLoginStatus::Scanned || ticket.user_id != Some(user.id) {
+            return Err(UseCaseError::BusinessLogicError("二维码已失效，请重新扫码".to_string()));
+        }
+
+        if !approve {
+            ticket.status = QrLoginStatus::Rejected;
+            self.save(&ticket).await?;
+            self.notify(&ticket, RouteCommand::alert("登录已取消", "已在手机上拒绝本次登录"));
+            info!(login_id = %login_id, user_id = %user.id, "QR login rejected");
+            return Ok(());
+        }
+
+        ticket.status = QrLoginStatus::Confirmed;
+        self.save(&ticket).await?;
+        info!(login_id = %login_id, user_id = %user.id, "QR login confirmed");
+
+        if self.push.is_connected(login_id) {
+            let login = self.claim(&ticket).await?;
+            let command = RouteCommandGenerator::generate_qr_login_route_command(&login, &self.route_config);
+            if self.push.send(login_id, PushMessage::new(QR_LOGIN_EVENT, command)) == 0 {
+                // 推送前连接已断开，撤销会话并恢复记录，由网页端轮询领取
+                warn!(login_id = %login_id, "QR login push undelivered, falling back to polling");
+                self.revoke(&login).await;
+                self.save(&ticket).await?;
+            }
+        }
+        Ok(())
+    }
+
+    // 领取会话：删除记录后创建会话，记录只能领取一次
+    async fn claim(&self, ticket: &QrLoginTicket) -> UseCaseResult<LoginResponse> {
+        use crate::database::profile::find_active_user;
+        use crate::database::audit::record_audit_event;
+
+        if !self.take(ticket.login_id).await? {
+            return Err(Self::expired());
+        }
+        let user_id = ticket.user_id.ok_or_else(Self::expired)?;
+        // 确认后账户可能被停用或撤销管理员权限
+        let user = find_active_user(&self.db_pool, user_id).await?
+            .filter(|user| user.is_admin)
+            .ok_or_else(|| UseCaseError::BusinessLogicError("账户不可用，请使用其他方式登录".to_string()))?;
+
+        let session = SessionIssuer::new(self.db_pool.clone(), self.events.clone())
+            .issue(&user, AuthMethod::QrCode, &RequestContext::new(ticket.ip_address, ticket.user_agent.clone()))
+            .await?;
+
+        let event = AuditEvent::new("auth.qr_login", "session")
+            .actor(user.id)
+            .target(session.id)
+            .ip(ticket.ip_address)
+            .details(json!({ "login_id": ticket.login_id }));
+        if let Err(e) = record_audit_event(&self.db_pool, &event).await {
+            error!(user_id = %user.id, error = %e, "Failed to record QR login audit event");
+        }
+
+        let login = LoginResponse {
+            user: UserInfo::from(user),
+            session_token: session.session_token,
+            expires_at: session.expires_at,
+        };
+
+        info!(login_id = %ticket.login_id, user_id = %user_id, "QR login session issued");
+        Ok(login)
+    }
+
+    // 撤销未送达的会话
+    async fn revoke(&self, login: &LoginResponse) {
+        use crate::database::auth::logout_session;
+
+        if let Err(e) = logout_session(&self.db_pool, &login.session_token).await {
+            error!(user_id = %login.user.id, error = %e, "Failed to revoke undelivered QR login session");
+        }
+    }
+
+    // 向网页端推送状态变化
+    fn notify(&self, ticket: &QrLoginTicket, command: RouteCommand) {
+        let state = QrLoginState { status: ticket.status, expires_at: ticket.expires_at };
+        let command = RouteCommand::sequence(vec![
+            RouteCommand::process_data(QR_LOGIN_EVENT, json!(state)),
+            command,
+        ]);
+        self.push.send(ticket.login_id, PushMessage::new(QR_LOGIN_EVENT, command));
+    }
+
+    // 校验网页端轮询令牌
+    async fn authorize(&self, login_id: Uuid, poll_token: &str) -> UseCaseResult<QrLoginTicket> {
+        if poll_token.is_empty() || poll_token.len() > 128 {
+            return Err(Self::expired());
+        }
+        let ticket = self.load(login_id).await?;
+        if ticket.poll_token_hash != hash_poll_token(poll_token) {
+            return Err(Self::expired());
+        }
+        Ok(ticket)
+    }
+
+    async fn load(&self, login_id: Uuid) -> UseCaseResult<QrLoginTicket> {
+        self.cache().get(login_id).await
+            .map_err(|e| UseCaseError::InternalError(format!("读取扫码登录记录失败: {}", e)))?
+            .ok_or_else(Self::expired)
+    }
+
+    async fn save(&self, ticket: &QrLoginTicket) -> UseCaseResult<()> {
+        self.cache().set(ticket).await
+            .map_err(|e| UseCaseError::InternalError(format!("保存扫码登录记录失败: {}", e)))
+    }
+
+    async fn take(&self, login_id: Uuid) -> UseCaseResult<bool> {
+        self.cache().take(login_id).await
+            .map_err(|e| UseCaseError::InternalError(format!("删除扫码登录记录失败: {}", e)))
+    }
+
+    fn ensure_admin(user: &User) -> UseCaseResult<()> {
+        if !user.is_admin {
+            return Err(UseCaseError::BusinessLogicError("仅管理员可以扫码登录管理后台".to_string()));
+        }
+        Ok(())
+    }
+
+    fn expired() -> UseCaseError {
+        UseCaseError::ValidationError("二维码已过期，请在网页上刷新".to_string())
+    }
+}
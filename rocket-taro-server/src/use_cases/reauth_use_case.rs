@@ -0,0 +1,79 @@
+use chrono::Utc;
+use std::net::IpAddr;
+use tracing::{info, warn, error, instrument};
+
+use crate::auth::{AuthenticatedUser, PasswordHasher};
+use crate::cache::{RedisPool, reauth::ReauthCache, user::UserCache};
+use crate::config::RecentAuthConfig;
+use crate::database::DbPool;
+use crate::models::{
+    audit::AuditEvent,
+    reauth::{ReauthRequest, ReauthStatus},
+};
+use super::{UseCaseError, UseCaseResult};
+
+/// 重新验证失败次数上限，与登录共用失败计数
+const MAX_REAUTH_FAILURES: i64 = 5;
+
+/// 敏感操作前的二次验证：在当前会话上重新验证密码
+pub struct ReauthUseCase {
+    db_pool: DbPool,
+    redis: RedisPool,
+    password_hasher: PasswordHasher,
+    config: RecentAuthConfig,
+}
+
+impl ReauthUseCase {
+    pub fn new(db_pool: DbPool, redis: RedisPool, password_hasher: PasswordHasher, config: RecentAuthConfig) -> Self {
+        Self { db_pool, redis, password_hasher, config }
+    }
+
+    /// 验证密码并记录当前会话的验证时间
+    #[instrument(skip_all, name = "reauthenticate")]
+    pub async fn confirm(&self, auth_user: &AuthenticatedUser, request: ReauthRequest, ip_address: Option<IpAddr>) -> UseCaseResult<ReauthStatus> {
+        use crate::database::profile::find_password_hash;
+        use crate::database::audit::record_audit_event;
+
+        let user = &auth_user.user;
+        // 模拟登录的管理员不应尝试用户密码，失败次数会锁定用户账户
+        if auth_user.session.impersonator_id.is_some() {
+            return Err(UseCaseError::BusinessLogicError("模拟登录期间不能验证用户身份".to_string()));
+        }
+        let user_cache = UserCache::new(self.redis.clone());
+        if user_cache.is_account_locked(&user.username, MAX_REAUTH_FAILURES).await.unwrap_or(false) {
+            return Err(UseCaseError::AuthenticationError("验证失败次数过多，请稍后再试".to_string()));
+        }
+
+        let password_hash = find_password_hash(&self.db_pool, user.id).await?
+            .ok_or_else(|| UseCaseError::BusinessLogicError("账户不存在或已注销".to_string()))?;
+        // 微信用户没有密码，无法重新验证身份
+        if password_hash.is_empty() {
+            return Err(UseCaseError::BusinessLogicError("当前账户未设置密码，无法验证身份".to_string()));
+        }
+        if !self.password_hasher.verify_blocking(&request.password, &password_hash).await {
+            warn!(user_id = %user.id, "Reauthentication failed: password mismatch");
+            if let Err(e) = user_cache.record_login_failure(&user.username).await {
+                warn!(user_id = %user.id, error = %e, "Failed to record reauthentication failure");
+            }
+            return Err(UseCaseError::AuthenticationError("密码错误".to_string()));
+        }
+
+        let confirmed_at = Utc::now();
+        ReauthCache::new(self.redis.clone()).record(auth_user.session.id, confirmed_at).await
+            .map_err(|e| UseCaseError::InternalError(format!("记录验证时间失败: {}", e)))?;
+        if let Err(e) = user_cache.clear_login_failures(&user.username).await {
+            warn!(user_id = %user.id, error = %e, "Failed to clear login failures");
+        }
+
+        let event = AuditEvent::new("auth.reauthenticated", "session")
+            .actor(user.id)
+            .target(auth_user.session.id)
+            .ip(ip_address);
+        if let Err(e) = record_audit_event(&self.db_pool, &event).await {
+            error!(user_id = %user.id, error = %e, "Failed to record reauthentication audit event");
+        }
+
+        info!(user_id = %user.id, session_id = %auth_user.session.id, "Session reauthenticated");
+        Ok(ReauthStatus { confirmed_at, valid_until: self.config.valid_until(confirmed_at) })
+    }
+}
@@ -0,0 +1,204 @@
+use serde_json::json;
+use std::net::IpAddr;
+use tracing::{debug, info, instrument, warn};
+use uuid::Uuid;
+
+use crate::cache::{RedisPool, registration::RegistrationVelocityCache, session::SessionCache, user::UserCache};
+use crate::config::RegistrationGuardConfig;
+use crate::database::DbPool;
+use crate::metrics::MetricsRegistry;
+use crate::models::audit::AuditEvent;
+use crate::models::auth::{RegisterRequest, User};
+use crate::models::registration_review::{
+    RegistrationAssessment, RegistrationReview, ReviewDecisionRequest, ReviewReason, ReviewStatus,
+};
+use super::session_issuer::RequestContext;
+use super::{UseCaseError, UseCaseResult};
+
+/// 蜜罐字段被填写的注册次数
+pub const REGISTRATION_REJECTED_HONEYPOT: &str = "registration.rejected.honeypot";
+/// 使用一次性邮箱的注册次数
+pub const REGISTRATION_REJECTED_DISPOSABLE_EMAIL: &str = "registration.rejected.disposable_email";
+/// 超过 IP/设备频率上限的注册次数
+pub const REGISTRATION_REJECTED_VELOCITY: &str = "registration.rejected.velocity";
+/// 进入人工审核队列的新账号数
+pub const REGISTRATION_FLAGGED: &str = "registration.flagged";
+
+/// 管理端一次最多列出的审核记录数
+const MAX_REVIEW_PAGE_SIZE: i64 = 200;
+
+/// 注册防自动化用例：创建账号前拦截蜜罐、一次性邮箱和超频注册，
+/// 创建后把可疑账号放入人工审核队列，管理员通过或拒绝
+pub struct RegistrationGuardUseCase {
+    db_pool: DbPool,
+    redis: RedisPool,
+    config: RegistrationGuardConfig,
+    metrics: Option<MetricsRegistry>,
+}
+
+impl RegistrationGuardUseCase {
+    pub fn new(db_pool: DbPool, redis: RedisPool, config: RegistrationGuardConfig) -> Self {
+        Self { db_pool, redis, config, metrics: None }
+    }
+
+    /// 设置拦截计数写入的指标注册表，未设置时不计数
+    pub fn with_metrics(mut self, metrics: MetricsRegistry) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// 创建账号前检查，需要拦截时返回 ValidationError；放行时返回需要人工审核的原因
+    #[instrument(skip_all, name = "assess_registration")]
+    pub async fn assess(&self, request: &RegisterRequest, context: &RequestContext) -> UseCaseResult<RegistrationAssessment> {
+        let mut assessment = RegistrationAssessment::default();
+        if !self.config.enabled {
+            return Ok(assessment);
+        }
+
+        // 不告诉机器人具体原因
+        if self.config.honeypot_enabled && request.website.as_deref().is_some_and(|value| !value.trim().is_empty()) {
+            warn!(ip = ?context.ip_address, "Registration rejected: honeypot field filled");
+            self.increment(REGISTRATION_REJECTED_HONEYPOT);
+            return Err(UseCaseError::ValidationError("注册失败，请稍后重试".to_string()));
+        }
+
+        if self.config.is_blocked_email(&request.email) {
+            warn!(ip = ?context.ip_address, "Registration rejected: disposable email domain");
+            self.increment(REGISTRATION_REJECTED_DISPOSABLE_EMAIL);
+            return Err(UseCaseError::ValidationError("暂不支持使用临时邮箱注册，请更换其他邮箱".to_string()));
+        }
+
+        let ip = context.ip_address.map(|ip| ip.to_string());
+        let checks = [
+            ("ip", ip.as_deref(), self.config.max_per_ip, self.config.review_per_ip, ReviewReason::IpVelocity),
+            ("device", context.device_id.as_deref(), self.config.max_per_device, self.config.review_per_device, ReviewReason::DeviceVelocity),
+        ];
+        for (kind, id, max, review, reason) in checks {
+            let Some(id) = id else { continue };
+            let Some(count) = self.record_velocity(kind, id).await else { continue };
+            if max > 0 && count > max {
+                warn!(kind, count, "Registration rejected: velocity limit exceeded");
+                self.increment(REGISTRATION_REJECTED_VELOCITY);
+                return Err(UseCaseError::ValidationError("注册过于频繁，请稍后再试".to_string()));
+            }
+            if review > 0 && count >= review {
+                assessment.reasons.push(reason);
+            }
+        }
+
+        if self.config.min_fill_ms > 0 && request.form_elapsed_ms.is_some_and(|elapsed| elapsed < self.config.min_fill_ms) {
+            assessment.reasons.push(ReviewReason::FastSubmission);
+        }
+
+        Ok(assessment)
+    }
+
+    /// 窗口内的注册次数（含本次），Redis 不可用时返回 None（不限制）
+    async fn record_velocity(&self, kind: &str, id: &str) -> Option<u64> {
+        let cache = RegistrationVelocityCache::new(self.redis.clone());
+        match cache.record(kind, id, self.config.window_secs.max(1) as usize).await {
+            Ok(count) => Some(count),
+            Err(e) => {
+                debug!(kind, error = %e, "Registration velocity counter unavailable, skipping");
+                None
+            }
+        }
+    }
+
+    /// 账号创建后把可疑账号放入审核队列；写入失败不影响注册
+    #[instrument(skip_all, name = "flag_registration", fields(user_id = %user.id))]
+    pub async fn flag(&self, user: &User, assessment: &RegistrationAssessment, context: &RequestContext) {
+        use crate::database::registration_review::create_registration_review;
+
+        if !assessment.is_suspicious() {
+            return;
+        }
+
+        let reasons: Vec<String> = assessment.reasons.iter().map(|reason| reason.as_str().to_string()).collect();
+        match create_registration_review(&self.db_pool, user.id, &reasons, context.ip_address, context.device_id.as_deref()).await {
+            Ok(_) => {
+                info!(reasons = ?reasons, "Registration flagged for review");
+                self.increment(REGISTRATION_FLAGGED);
+            }
+            Err(e) => warn!(error = %e, "Failed to queue registration for review"),
+        }
+    }
+
+    /// 按状态列出审核队列（默认待审核），最新的在前
+    pub async fn list_reviews(&self, status: Option<&str>, limit: Option<i64>) -> UseCaseResult<Vec<RegistrationReview>> {
+        use crate::database::registration_review::list_registration_reviews;
+
+        let status = match status {
+            None => ReviewStatus::Pending,
+            Some(value) => ReviewStatus::parse(value)
+                .ok_or_else(|| UseCaseError::ValidationError("状态应为 pending、approved 或 rejected".to_string()))?,
+        };
+        let limit = limit.unwrap_or(50).clamp(1, MAX_REVIEW_PAGE_SIZE);
+        Ok(list_registration_reviews(&self.db_pool, status.as_str(), limit).await?)
+    }
+
+    /// 处理待审核账号：拒绝时停用账号并撤销其全部会话
+    #[instrument(skip_all, name = "decide_registration_review", fields(review_id = %review_id))]
+    pub async fn decide(
+        &self,
+        review_id: Uuid,
+        request: ReviewDecisionRequest,
+        admin_id: Uuid,
+        ip_address: Option<IpAddr>,
+    ) -> UseCaseResult<RegistrationReview> {
+        use crate::database::account::delete_user_sessions;
+        use crate::database::audit::record_audit_event;
+        use crate::database::registration_review::{decide_registration_review, deactivate_user};
+
+        if request.status == ReviewStatus::Pending {
+            return Err(UseCaseError::ValidationError("审核结果应为 approved 或 rejected".to_string()));
+        }
+        let note = request.note.as_deref().map(str::trim).filter(|note| !note.is_empty());
+
+        let review = decide_registration_review(&self.db_pool, review_id, request.status.as_str(), admin_id, note).await?
+            .ok_or_else(|| UseCaseError::BusinessLogicError("审核记录不存在或已处理".to_string()))?;
+
+        let mut sessions_revoked = 0;
+        if request.status == ReviewStatus::Rejected {
+            deactivate_user(&self.db_pool, review.user_id).await?;
+            sessions_revoked = delete_user_sessions(&self.db_pool, review.user_id).await?;
+            self.revoke_cached_state(&review).await;
+        }
+        info!(admin_id = %admin_id, user_id = %review.user_id, status = %review.status, "Registration review decided");
+
+        let event = AuditEvent::new(&format!("registration.review_{}", review.status), "user")
+            .actor(admin_id)
+            .target(review.user_id)
+            .ip(ip_address)
+            .details(json!({ "review_id": review.id, "reasons": review.reasons, "sessions_revoked": sessions_revoked }));
+        // 审核结果已生效，审计日志写入失败只记录日志
+        if let Err(e) = record_audit_event(&self.db_pool, &event).await {
+            warn!(error = %e, "Failed to record registration review audit event");
+        }
+        Ok(review)
+    }
+
+    /// 清除被拒绝账号的会话和用户缓存
+    async fn revoke_cached_state(&self, review: &RegistrationReview) {
+        let session_cache = SessionCache::new(self.redis.clone());
+        let user_cache = UserCache::new(self.redis.clone());
+
+        if let Err(e) = session_cache.invalidate_user_sessions(review.user_id).await {
+            warn!(user_id = %review.user_id, error = %e, "Failed to invalidate cached sessions");
+        }
+        if let Err(e) = user_cache.invalidate_user(review.user_id).await {
+            warn!(user_id = %review.user_id, error = %e, "Failed to invalidate cached user");
+        }
+        if let Some(username) = &review.username {
+            if let Err(e) = user_cache.invalidate_username(username).await {
+                warn!(user_id = %review.user_id, error = %e, "Failed to invalidate username mapping");
+            }
+        }
+    }
+
+    fn increment(&self, name: &'static str) {
+        if let Some(metrics) = &self.metrics {
+            metrics.increment(name);
+        }
+    }
+}
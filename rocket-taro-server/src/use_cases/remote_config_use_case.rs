@@ -0,0 +1,125 @@
+use tracing::{info, warn, error, instrument};
+use uuid::Uuid;
+
+use crate::cache::{RedisPool, remote_config::RemoteConfigCache};
+use crate::config::Platform;
+use crate::database::DbPool;
+use crate::models::remote_config::{RemoteConfigEntry, RemoteConfigSnapshot};
+use super::{UseCaseError, UseCaseResult};
+
+/// 配置键最大长度
+const MAX_KEY_LENGTH: usize = 100;
+
+/// 远程配置用例，负责配置的解析、缓存与管理端写入
+pub struct RemoteConfigUseCase {
+    db_pool: DbPool,
+    redis: RedisPool,
+}
+
+impl RemoteConfigUseCase {
+    pub fn new(db_pool: DbPool, redis: RedisPool) -> Self {
+        Self { db_pool, redis }
+    }
+
+    /// 获取平台配置快照，优先读取缓存
+    #[instrument(skip_all, name = "get_remote_config_snapshot")]
+    pub async fn get_snapshot(&self, platform: Platform) -> UseCaseResult<RemoteConfigSnapshot> {
+        let cache = RemoteConfigCache::new(self.redis.clone());
+        match cache.get_snapshot(platform).await {
+            Ok(Some(snapshot)) => return Ok(snapshot),
+            Ok(None) => {}
+            Err(e) => warn!(error = %e, "Remote config cache lookup failed, falling back to database"),
+        }
+
+        self.load_snapshot(platform).await
+    }
+
+    /// 从数据库重建所有平台的配置快照并写入缓存，返回快照数量（启动预热）
+    pub async fn refresh_snapshots(&self) -> UseCaseResult<usize> {
+        for platform in Platform::ALL {
+            self.load_snapshot(platform).await?;
+        }
+        Ok(Platform::ALL.len())
+    }
+
+    async fn load_snapshot(&self, platform: Platform) -> UseCaseResult<RemoteConfigSnapshot> {
+        use crate::database::remote_config::list_remote_configs_for_platform;
+
+        let cache = RemoteConfigCache::new(self.redis.clone());
+        let entries = list_remote_configs_for_platform(&self.db_pool, platform).await.map_err(|e| {
+            error!(platform = %platform.as_str(), error = %e, "Failed to load remote configs");
+            UseCaseError::DatabaseError(e.to_string())
+        })?;
+
+        let snapshot = RemoteConfigSnapshot::resolve(platform, &entries);
+        if let Err(e) = cache.cache_snapshot(&snapshot).await {
+            warn!(error = %e, "Failed to cache remote config snapshot");
+        }
+
+        Ok(snapshot)
+    }
+
+    /// 列出全部配置项（管理端）
+    pub async fn list_entries(&self) -> UseCaseResult<Vec<RemoteConfigEntry>> {
+        use crate::database::remote_config::list_all_remote_configs;
+
+        Ok(list_all_remote_configs(&self.db_pool).await?)
+    }
+
+    /// 写入配置项
+    #[instrument(skip_all, name = "set_remote_config")]
+    pub async fn set_value(
+        &self,
+        config_key: &str,
+        platform: Option<Platform>,
+        value: &serde_json::Value,
+        description: Option<&str>,
+        updated_by: Uuid,
+    ) -> UseCaseResult<RemoteConfigEntry> {
+        use crate::database::remote_config::upsert_remote_config;
+
+        Self::validate_key(config_key)?;
+
+        let entry = upsert_remote_config(&self.db_pool, config_key, platform, value, description, updated_by).await?;
+        info!(config_key = %config_key, version = %entry.version, updated_by = %updated_by, "Remote config written");
+
+        self.invalidate_cache().await;
+        Ok(entry)
+    }
+
+    /// 删除配置项
+    #[instrument(skip_all, name = "delete_remote_config")]
+    pub async fn delete_value(
+        &self,
+        config_key: &str,
+        platform: Option<Platform>,
+        updated_by: Uuid,
+    ) -> UseCaseResult<Option<RemoteConfigEntry>> {
+        use crate::database::remote_config::delete_remote_config;
+
+        let entry = delete_remote_config(&self.db_pool, config_key, platform, updated_by).await?;
+        if entry.is_some() {
+            info!(config_key = %config_key, updated_by = %updated_by, "Remote config deleted");
+            self.invalidate_cache().await;
+        }
+        Ok(entry)
+    }
+
+    async fn invalidate_cache(&self) {
+        let cache = RemoteConfigCache::new(self.redis.clone());
+        if let Err(e) = cache.invalidate_all().await {
+            warn!(error = %e, "Failed to invalidate remote config cache");
+        }
+    }
+
+    /// 配置键只允许小写字母、数字以及 `.` `_` `-`
+    fn validate_key(config_key: &str) -> UseCaseResult<()> {
+        let valid_chars = config_key.chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '.' | '_' | '-'));
+
+        if config_key.is_empty() || config_key.len() > MAX_KEY_LENGTH || !valid_chars {
+            return Err(UseCaseError::ValidationError(format!("无效的配置键: {}", config_key)));
+        }
+        Ok(())
+    }
+}
@@ -1,30 +1,80 @@
+use chrono::{DateTime, Utc};
 use serde_json::json;
 use tracing::{info, warn, instrument};
 
 use crate::models::{
-    route_command::RouteCommand,
-    business_results::{LoginResult, LogoutResult},
-    auth::UserInfo,
+    route_command::{RouteCommand, VersionedRouteCommand},
+    route_condition::{ConditionContext, ConditionExpr},
+    business_results::{LoginResult, LogoutResult, RegisterResult, AccountDeletionResult},
+    data_export::{DataExportInfo, DataExportStatus},
+    payment::PaymentOrderResult,
+    order::Order,
+    auth::{LoginResponse, User, UserInfo},
+    profile::{ProfileField, ProfileStepResult, ProfileUpdateResult},
+    maintenance::MaintenanceState,
+    announcement::{AnnouncementView, ANNOUNCEMENTS_DATA_TYPE},
+    settings::UserSettings,
+    consent::ConsentResult,
+    identity::{IdentityStatus, IdentityVerificationStatus, VerificationBlock},
+    moderation::ContentRejection,
+    route_decision::{RouteDecision, RouteTrace},
+    experiment::EXPERIMENT_ASSIGNED_RULE,
 };
-use crate::config::{RouteConfig, Platform};
+use crate::config::{RouteConfig, Platform, ExperimentsConfig, route_keys, experiments::LOGIN_ONBOARDING_EXPERIMENT};
+use super::UseCaseError;
 
-/// 路由决策器，负责根据业务结果生成路由指令
+/// 会话相关操作（如延长会话）使用的 ProcessData 数据类型
+pub const SESSION_DATA_TYPE: &str = "session";
+/// 版本冲突时要求前端重新加载数据使用的 ProcessData 数据类型
+pub const RELOAD_DATA_TYPE: &str = "reload";
+
+/// 路由决策器，负责根据业务结果生成路由指令；命中的规则记录到调用方传入的 RouteTrace
 pub struct RouteCommandGenerator;
 
 impl RouteCommandGenerator {
+    /// 用新的 RouteTrace 运行 generate，返回结果和其中命中的规则及输入（按命中顺序）
+    pub fn traced<T>(generate: impl FnOnce(&mut RouteTrace) -> T) -> (T, Vec<RouteDecision>) {
+        let mut trace = RouteTrace::default();
+        let result = generate(&mut trace);
+        (result, trace.into_decisions())
+    }
+
+    // 新用户、首次登录的欢迎提示：登录引导实验中分到 dialog 组的用户使用对话框，其他用户（含实验外）使用轻提示
+    fn onboarding_welcome(experiments: &ExperimentsConfig, user_id: uuid::Uuid, message: &str, trace: &mut RouteTrace) -> RouteCommand {
+        let variant = experiments.assign(LOGIN_ONBOARDING_EXPERIMENT, user_id, Utc::now());
+        if let Some(variant) = variant {
+            trace.record(EXPERIMENT_ASSIGNED_RULE, json!({ "experiment": LOGIN_ONBOARDING_EXPERIMENT, "variant": variant }));
+        }
+        match variant {
+            Some("dialog") => RouteCommand::alert("欢迎", message),
+            _ => RouteCommand::toast(message),
+        }
+    }
+
     /// 根据登录结果生成路由指令
     #[instrument(skip_all, name = "generate_login_route_command")]
-    pub fn generate_login_route_command(result: &LoginResult, route_config: &RouteConfig, platform: Platform) -> RouteCommand {
+    pub fn generate_login_route_command(result: &LoginResult, route_config: &RouteConfig, experiments: &ExperimentsConfig, platform: Platform, trace: &mut RouteTrace) -> RouteCommand {
         info!(user_id = %result.user.id, is_admin = %result.user.is_admin, "Generating login route command");
 
+        // 有未同意的生效协议：先同意协议，优先于其他引导
+        if result.account_flags.needs_policy_consent {
+            info!(user_id = %result.user.id, pending_policies = ?result.account_flags.pending_policies, "User needs to accept policies");
+            trace.record("login.policy_consent", json!({ "pending_policies": result.account_flags.pending_policies }));
+            return RouteCommand::sequence(vec![
+                RouteCommand::process_data("user", serde_json::to_value(UserInfo::from(result.user.clone())).unwrap()),
+                Self::generate_consent_required_route_command(&result.account_flags.pending_policies, None, route_config, platform, trace),
+            ]);
+        }
+
         // 首次登录处理
         if result.is_first_login {
             info!("First login detected, redirecting to welcome page");
-            let home_route = route_config.get_route("home.main", platform.clone())
+            let home_route = route_config.get_route(route_keys::HOME_MAIN, platform)
                 .unwrap_or_else(|| "/pages/home/home".to_string());
+            trace.record("login.first_login", json!({ "route": home_route }));
             return RouteCommand::sequence(vec![
                 RouteCommand::process_data("user", serde_json::to_value(UserInfo::from(result.user.clone())).unwrap()),
-                RouteCommand::toast("欢迎使用系统！"),
+                Self::onboarding_welcome(experiments, result.user.id, "欢迎使用系统！", trace),
                 RouteCommand::redirect_to(&home_route),
             ]);
         }
@@ -32,8 +82,9 @@ impl RouteCommandGenerator {
         // 需要更新密码
         if result.needs_password_update {
             warn!(user_id = %result.user.id, "User needs to update password");
-            let home_route = route_config.get_route("home.index", platform.clone())
+            let home_route = route_config.get_route(route_keys::HOME_INDEX, platform)
                 .unwrap_or_else(|| "/pages/index/index".to_string());
+            trace.record("login.password_update", json!({ "route": home_route }));
             return RouteCommand::confirm(
                 "密码安全提醒",
                 "为了账户安全，建议您更新密码",
@@ -52,8 +103,9 @@ impl RouteCommandGenerator {
                 format!("您有{}个待处理任务", result.pending_task_count)
             };
 
-            let home_route = route_config.get_route("home.index", platform.clone())
+            let home_route = route_config.get_route(route_keys::HOME_INDEX, platform)
                 .unwrap_or_else(|| "/pages/index/index".to_string());
+            trace.record("login.pending_tasks", json!({ "pending_task_count": result.pending_task_count, "route": home_route }));
             return RouteCommand::sequence(vec![
                 RouteCommand::process_data("user", serde_json::to_value(UserInfo::from(result.user.clone())).unwrap()),
                 RouteCommand::confirm(
@@ -68,8 +120,9 @@ impl RouteCommandGenerator {
         // VIP用户特殊处理
         if result.account_flags.is_vip {
             info!(user_id = %result.user.id, "VIP user login");
-            let home_route = route_config.get_route("home.main", platform.clone())
+            let home_route = route_config.get_route(route_keys::HOME_MAIN, platform)
                 .unwrap_or_else(|| "/pages/home/home".to_string());
+            trace.record("login.vip", json!({ "route": home_route }));
             return RouteCommand::sequence(vec![
                 RouteCommand::process_data("user", serde_json::to_value(UserInfo::from(result.user.clone())).unwrap()),
                 RouteCommand::toast("尊敬的VIP用户，欢迎回来！"),
@@ -80,35 +133,32 @@ impl RouteCommandGenerator {
         // 新用户引导
         if result.account_flags.is_new_user {
             info!(user_id = %result.user.id, "New user login");
-            let home_route = route_config.get_route("home.main", platform.clone())
+            let home_route = route_config.get_route(route_keys::HOME_MAIN, platform)
                 .unwrap_or_else(|| "/pages/home/home".to_string());
+            trace.record("login.new_user", json!({ "route": home_route }));
             return RouteCommand::sequence(vec![
                 RouteCommand::process_data("user", serde_json::to_value(UserInfo::from(result.user.clone())).unwrap()),
-                RouteCommand::toast("欢迎新用户！"),
+                Self::onboarding_welcome(experiments, result.user.id, "欢迎新用户！", trace),
                 RouteCommand::redirect_to(&home_route),
             ]);
         }
 
-        // 需要完善个人信息
+        // 需要完善个人信息：进入资料完善步骤条
         if result.account_flags.needs_profile_completion {
-            info!(user_id = %result.user.id, "User needs to complete profile");
-            let home_route = route_config.get_route("home.index", platform.clone())
-                .unwrap_or_else(|| "/pages/index/index".to_string());
+            info!(user_id = %result.user.id, missing_fields = ?result.account_flags.missing_profile_fields, "User needs to complete profile");
+            trace.record("login.profile_completion", json!({ "missing_fields": result.account_flags.missing_profile_fields }));
             return RouteCommand::sequence(vec![
                 RouteCommand::process_data("user", serde_json::to_value(UserInfo::from(result.user.clone())).unwrap()),
-                RouteCommand::confirm(
-                    "完善个人信息",
-                    "为了获得更好的体验，请完善您的个人信息",
-                    Some(RouteCommand::redirect_to(&home_route)),
-                    Some(RouteCommand::redirect_to(&home_route)),
-                ),
+                RouteCommand::toast("请先完善个人信息"),
+                Self::profile_stepper_command(&result.account_flags.missing_profile_fields, false, route_config, platform, trace),
             ]);
         }
 
         // 默认登录流程
         info!(user_id = %result.user.id, "Normal login flow");
-        let home_route = route_config.get_route("home.index", platform.clone())
+        let home_route = route_config.get_route(route_keys::HOME_INDEX, platform)
             .unwrap_or_else(|| "/pages/home/index".to_string());
+        trace.record("login.default", json!({ "route": home_route }));
         RouteCommand::sequence(vec![
             RouteCommand::process_data("user", serde_json::to_value(UserInfo::from(result.user.clone())).unwrap()),
             RouteCommand::toast("登录成功"),
@@ -116,54 +166,523 @@ impl RouteCommandGenerator {
         ])
     }
 
+    /// 根据微信登录结果生成路由指令：新注册、首次登录的用户附带欢迎提示（参与登录引导实验），然后进入主页
+    #[instrument(skip_all, name = "generate_wx_login_route_command")]
+    pub fn generate_wx_login_route_command(user: &User, is_new_user: bool, route_config: &RouteConfig, experiments: &ExperimentsConfig, platform: Platform, trace: &mut RouteTrace) -> RouteCommand {
+        let home_route = route_config.get_route(route_keys::HOME_MAIN, platform)
+            .unwrap_or_else(|| "/pages/home/home".to_string());
+        let mut commands = vec![RouteCommand::process_data("user", serde_json::to_value(UserInfo::from(user.clone())).unwrap())];
+        if is_new_user {
+            info!(user_id = %user.id, "New WeChat user login");
+            trace.record("login.new_user", json!({ "route": home_route }));
+            commands.push(Self::onboarding_welcome(experiments, user.id, "欢迎新用户！", trace));
+        } else if user.last_login_at.is_none() {
+            info!(user_id = %user.id, "First WeChat login detected");
+            trace.record("login.first_login", json!({ "route": home_route }));
+            commands.push(Self::onboarding_welcome(experiments, user.id, "欢迎使用系统！", trace));
+        } else {
+            trace.record("login.default", json!({ "route": home_route }));
+        }
+        commands.push(RouteCommand::NavigateTo {
+            path: home_route,
+            params: None,
+            replace: Some(true),
+            fallback_path: Some("/pages/home/home".to_string()),
+        });
+        RouteCommand::Sequence { commands, stop_on_error: Some(true) }
+    }
+
+    /// 根据登录失败的错误生成路由指令
+    pub fn generate_login_failed_route_command(error: &UseCaseError, route_config: &RouteConfig, platform: Platform, trace: &mut RouteTrace) -> RouteCommand {
+        let error_code = match error {
+            UseCaseError::AuthenticationError(_) => Some("AUTH_INVALID_CREDENTIALS"),
+            UseCaseError::DatabaseError(_) => Some("DATABASE_ERROR"),
+            UseCaseError::TimeoutError(timeout) => Some(timeout.dependency.error_code()),
+            _ => None,
+        };
+        Self::generate_error_route_command(&error.to_string(), error_code, route_config, platform, trace)
+    }
+
     /// 根据登出结果生成路由指令
     #[instrument(skip_all, name = "generate_logout_route_command")]
-    pub fn generate_logout_route_command(result: &LogoutResult, route_config: &RouteConfig, platform: Platform) -> RouteCommand {
+    pub fn generate_logout_route_command(result: &LogoutResult, route_config: &RouteConfig, platform: Platform, trace: &mut RouteTrace) -> RouteCommand {
         info!(user_id = %result.user_id, "Generating logout route command");
 
+        let login_route = route_config.get_route(route_keys::AUTH_LOGIN, platform)
+            .unwrap_or_else(|| "/pages/login/login".to_string());
+        let logout = |message: &str| RouteCommand::sequence(vec![
+            RouteCommand::process_data("user", json!(null)),
+            RouteCommand::toast(message),
+            RouteCommand::redirect_to(&login_route),
+        ]);
+        let confirm_unsaved = |on_confirm: RouteCommand| RouteCommand::confirm(
+            "未保存的数据",
+            "您有未保存的数据，退出登录将会丢失，是否继续？",
+            Some(on_confirm),
+            None, // 取消不执行任何操作
+        );
+
         if result.has_unsaved_data {
             warn!(user_id = %result.user_id, "User has unsaved data");
-            let login_route = route_config.get_route("auth.login", platform.clone())
-                .unwrap_or_else(|| "/pages/login/login".to_string());
-            return RouteCommand::confirm(
-                "未保存的数据",
-                "您有未保存的数据，退出登录将会丢失，是否继续？",
-                Some(RouteCommand::sequence(vec![
-                    RouteCommand::process_data("user", json!(null)),
-                    RouteCommand::toast("已退出登录"),
-                    RouteCommand::redirect_to(&login_route),
-                ])),
-                None, // 取消不执行任何操作
-            );
+            trace.record("logout.unsaved_data", json!({ "route": login_route }));
+            return confirm_unsaved(logout("已退出登录"));
         }
 
-        if !result.session_destroyed {
+        trace.record("logout.default", json!({ "session_destroyed": result.session_destroyed, "route": login_route }));
+        let proceed = if result.session_destroyed {
+            info!(user_id = %result.user_id, "Normal logout flow");
+            logout("已退出登录")
+        } else {
             warn!(user_id = %result.user_id, "Session destroy failed, but continuing logout");
-            let login_route = route_config.get_route("auth.login", platform.clone())
-                .unwrap_or_else(|| "/pages/login/login".to_string());
+            logout("已退出登录（部分数据清理可能失败）")
+        };
+
+        // 前端表单中的未保存数据只有前端知道，由前端决定是否先确认
+        Self::conditional(
+            ConditionExpr::truthy("store.hasUnsavedData"),
+            confirm_unsaved(proceed.clone()),
+            Some(proceed),
+            &ConditionContext::new(),
+            trace,
+        )
+    }
+
+    /// 生成条件指令：已知上下文能确定结果时直接返回对应分支，否则下发 Conditional 由前端求值
+    pub fn conditional(condition: ConditionExpr, if_true: RouteCommand, if_false: Option<RouteCommand>, context: &ConditionContext, trace: &mut RouteTrace) -> RouteCommand {
+        // result 为空表示由前端求值
+        trace.record("condition", json!({ "condition": condition.to_string(), "result": condition.evaluate(context) }));
+        RouteCommand::conditional(&condition, if_true, if_false).resolve_conditions(context)
+    }
+
+    /// 根据注册结果生成路由指令
+    #[instrument(skip_all, name = "generate_register_route_command")]
+    pub fn generate_register_route_command(result: &RegisterResult, route_config: &RouteConfig, platform: Platform, trace: &mut RouteTrace) -> RouteCommand {
+        info!(user_id = %result.user.id, auto_login = %result.session.is_some(), "Generating register route command");
+
+        if result.session.is_some() {
+            let home_route = route_config.get_route(route_keys::HOME_MAIN, platform)
+                .unwrap_or_else(|| "/pages/home/home".to_string());
+            trace.record("register.auto_login", json!({ "route": home_route }));
             return RouteCommand::sequence(vec![
-                RouteCommand::process_data("user", json!(null)),
-                RouteCommand::toast("已退出登录（部分数据清理可能失败）"),
-                RouteCommand::redirect_to(&login_route),
+                RouteCommand::process_data("user", serde_json::to_value(UserInfo::from(result.user.clone())).unwrap()),
+                RouteCommand::navigate_to(&home_route),
             ]);
         }
 
-        // 正常登出
-        info!(user_id = %result.user_id, "Normal logout flow");
-        let login_route = route_config.get_route("auth.login", platform.clone())
+        // 自动登录失败，引导用户手动登录
+        let login_route = route_config.get_route(route_keys::AUTH_LOGIN, platform)
+            .unwrap_or_else(|| "/pages/login/login".to_string());
+        trace.record("register.manual_login", json!({ "route": login_route }));
+        RouteCommand::sequence(vec![
+            RouteCommand::alert("注册成功", "账号创建成功，请重新登录"),
+            RouteCommand::navigate_to(&login_route),
+        ])
+    }
+
+    /// 根据游客登录结果生成路由指令
+    #[instrument(skip_all, name = "generate_guest_login_route_command")]
+    pub fn generate_guest_login_route_command(result: &LoginResult, route_config: &RouteConfig, platform: Platform, trace: &mut RouteTrace) -> RouteCommand {
+        info!(user_id = %result.user.id, "Generating guest login route command");
+
+        if result.account_flags.needs_policy_consent {
+            trace.record("guest_login.policy_consent", json!({ "pending_policies": result.account_flags.pending_policies }));
+            return RouteCommand::sequence(vec![
+                RouteCommand::process_data("user", serde_json::to_value(UserInfo::from(result.user.clone())).unwrap()),
+                Self::generate_consent_required_route_command(&result.account_flags.pending_policies, None, route_config, platform, trace),
+            ]);
+        }
+
+        let home_route = route_config.get_route(route_keys::HOME_MAIN, platform)
+            .unwrap_or_else(|| "/pages/home/home".to_string());
+        trace.record("guest_login.default", json!({ "route": home_route }));
+        RouteCommand::sequence(vec![
+            RouteCommand::process_data("user", serde_json::to_value(UserInfo::from(result.user.clone())).unwrap()),
+            RouteCommand::navigate_to(&home_route),
+        ])
+    }
+
+    /// 根据账户注销结果生成路由指令
+    #[instrument(skip_all, name = "generate_account_deleted_route_command")]
+    pub fn generate_account_deleted_route_command(result: &AccountDeletionResult, route_config: &RouteConfig, platform: Platform, trace: &mut RouteTrace) -> RouteCommand {
+        info!(user_id = %result.user_id, sessions_revoked = %result.sessions_revoked, "Generating account deleted route command");
+
+        let login_route = route_config.get_route(route_keys::AUTH_LOGIN, platform)
             .unwrap_or_else(|| "/pages/login/login".to_string());
+        trace.record("account_deleted", json!({ "anonymize_after": result.anonymize_after, "route": login_route }));
         RouteCommand::sequence(vec![
             RouteCommand::process_data("user", json!(null)),
-            RouteCommand::toast("已退出登录"),
+            RouteCommand::alert(
+                "账户已注销",
+                &format!("您的账户已注销，个人信息将于{}后彻底清除", result.anonymize_after.format("%Y-%m-%d")),
+            ),
             RouteCommand::redirect_to(&login_route),
         ])
     }
 
+    /// 维护模式下拦截请求时生成的路由指令：跳转维护页，未配置维护页时弹窗提示
+    #[instrument(skip_all, name = "generate_maintenance_route_command")]
+    pub fn generate_maintenance_route_command(state: &MaintenanceState, route_config: &RouteConfig, platform: Platform) -> RouteCommand {
+        let message = state.message.clone().unwrap_or_else(|| "系统维护中，请稍后再试".to_string());
+        match route_config.get_route(route_keys::ERROR_MAINTENANCE, platform) {
+            Some(maintenance_route) => RouteCommand::NavigateTo {
+                path: maintenance_route,
+                params: Some(json!({ "message": message, "ends_at": state.ends_at })),
+                replace: Some(true),
+                fallback_path: None,
+            },
+            None => {
+                warn!("Route error.maintenance is not configured, falling back to alert");
+                RouteCommand::alert("系统维护", &message)
+            }
+        }
+    }
+
+    /// 生成会话即将过期的提醒指令，用户确认后由前端调用延长会话接口
+    pub fn generate_session_expiry_warning_route_command(expires_at: DateTime<Utc>, now: DateTime<Utc>) -> RouteCommand {
+        let minutes = ((expires_at - now).num_seconds().max(0) + 59) / 60;
+        RouteCommand::confirm(
+            "登录即将过期",
+            &format!("您的登录将在{}分钟后过期，是否延长登录？", minutes.max(1)),
+            Some(RouteCommand::process_data(SESSION_DATA_TYPE, json!({ "action": "extend" }))),
+            None,
+        )
+    }
+
+    /// 生成下发公告数据的指令，没有公告时返回 None
+    pub fn generate_announcements_route_command(announcements: &[AnnouncementView]) -> Option<RouteCommand> {
+        (!announcements.is_empty())
+            .then(|| RouteCommand::process_data(ANNOUNCEMENTS_DATA_TYPE, json!(announcements)))
+    }
+
+    /// 在路由指令前附加公告数据，没有公告时原样返回
+    pub fn with_announcements(command: RouteCommand, announcements: &[AnnouncementView]) -> RouteCommand {
+        match Self::generate_announcements_route_command(announcements) {
+            Some(announcements) => Self::prepend(command, announcements),
+            None => command,
+        }
+    }
+
+    /// 在路由指令前附加用户设置，前端在执行导航前应用语言和主题
+    pub fn with_settings(command: RouteCommand, settings: &UserSettings) -> RouteCommand {
+        Self::prepend(command, RouteCommand::process_data("settings", json!(settings)))
+    }
+
+    /// 微信找回密码模式下，在登录指令后追加重置密码确认；账户未设置密码时提示已直接登录
+    pub fn with_password_recovery(
+        command: RouteCommand,
+        recovery_token: Option<&str>,
+        route_config: &RouteConfig,
+        platform: Platform,
+    ) -> RouteCommand {
+        let Some(recovery_token) = recovery_token else {
+            return Self::append(command, RouteCommand::toast("该账户未设置密码，已通过微信直接登录"));
+        };
+
+        let reset_route = route_config.get_route(route_keys::AUTH_RESET_PASSWORD, platform)
+            .unwrap_or_else(|| "/pages/auth/reset-password".to_string());
+        Self::append(command, RouteCommand::confirm(
+            "找回密码",
+            "已通过微信验证身份，是否立即重置登录密码？",
+            Some(RouteCommand::navigate_to_with_params(&reset_route, json!({ "recovery_token": recovery_token }))),
+            None, // 取消则保持微信登录状态
+        ))
+    }
+
+    /// 找回密码重置成功：所有会话已失效，清除本地用户并跳转登录页
+    #[instrument(skip_all, name = "generate_password_reset_route_command")]
+    pub fn generate_password_reset_route_command(route_config: &RouteConfig, platform: Platform) -> RouteCommand {
+        let login_route = route_config.get_route(route_keys::AUTH_LOGIN, platform)
+            .unwrap_or_else(|| "/pages/login/login".to_string());
+        RouteCommand::sequence(vec![
+            RouteCommand::process_data("user", json!(null)),
+            RouteCommand::toast("密码已重置，请使用新密码登录"),
+            RouteCommand::redirect_to(&login_route),
+        ])
+    }
+
+    /// 会话因超出同时在线数被踢出时推送给该客户端的指令
+    pub fn generate_session_evicted_route_command(route_config: &RouteConfig, platform: Platform) -> RouteCommand {
+        let login_route = route_config.get_route(route_keys::AUTH_LOGIN, platform)
+            .unwrap_or_else(|| "/pages/login/login".to_string());
+        RouteCommand::sequence(vec![
+            RouteCommand::process_data("user", json!(null)),
+            RouteCommand::alert("已在其他设备登录", "您的账号已在其他设备登录，当前设备已退出登录"),
+            RouteCommand::redirect_to(&login_route),
+        ])
+    }
+
+    /// 扫码登录领取会话后网页端（管理后台）执行的指令
+    #[instrument(skip_all, name = "generate_qr_login_route_command")]
+    pub fn generate_qr_login_route_command(login: &LoginResponse, route_config: &RouteConfig) -> RouteCommand {
+        info!(user_id = %login.user.id, "Generating QR login route command");
+
+        let home_route = route_config.get_route(route_keys::HOME_MAIN, Platform::Admin)
+            .unwrap_or_else(|| "/dashboard".to_string());
+        RouteCommand::sequence(vec![
+            RouteCommand::process_data("user", json!(login)),
+            RouteCommand::toast("扫码登录成功"),
+            RouteCommand::redirect_to(&home_route),
+        ])
+    }
+
+    /// 小程序扫码后进入确认登录页
+    pub fn generate_qr_login_scanned_route_command(login_id: uuid::Uuid, route_config: &RouteConfig, platform: Platform) -> RouteCommand {
+        let confirm_route = route_config.get_route(route_keys::AUTH_QR_LOGIN_CONFIRM, platform)
+            .unwrap_or_else(|| "/pages/auth/qr-login-confirm".to_string());
+        RouteCommand::navigate_to_with_params(&confirm_route, json!({ "login_id": login_id }))
+    }
+
+    /// 敏感操作缺少近期身份验证时跳转重新验证页，验证后由客户端重试原操作
+    pub fn generate_reauth_required_route_command(operation: &str, route_config: &RouteConfig, platform: Platform) -> RouteCommand {
+        let reauth_route = route_config.get_route(route_keys::AUTH_REAUTH, platform)
+            .unwrap_or_else(|| "/pages/auth/reauth".to_string());
+        RouteCommand::navigate_to_with_params(&reauth_route, json!({ "operation": operation }))
+    }
+
+    /// 有未同意的生效协议时跳转协议同意页；redirect 为被拦截的接口路径，同意后由客户端重试
+    pub fn generate_consent_required_route_command(pending_policies: &[String], redirect: Option<&str>, route_config: &RouteConfig, platform: Platform, trace: &mut RouteTrace) -> RouteCommand {
+        let consent_route = route_config.get_route(route_keys::AUTH_CONSENT, platform)
+            .unwrap_or_else(|| "/pages/auth/consent".to_string());
+        trace.record("consent.required", json!({ "pending_policies": pending_policies, "redirect": redirect, "route": consent_route }));
+        // 替换当前页面，不能返回到需要同意协议后才能使用的页面
+        RouteCommand::NavigateTo {
+            path: consent_route,
+            params: Some(json!({ "policies": pending_policies, "redirect": redirect })),
+            replace: Some(true),
+            fallback_path: None,
+        }
+    }
+
+    /// 同意协议后：全部同意时进入首页，仍有未同意的版本时提示
+    pub fn generate_consent_accepted_route_command(result: &ConsentResult, route_config: &RouteConfig, platform: Platform) -> RouteCommand {
+        if !result.pending.is_empty() {
+            return RouteCommand::toast("请阅读并同意全部协议");
+        }
+        let home_route = route_config.get_route(route_keys::HOME_MAIN, platform)
+            .unwrap_or_else(|| "/pages/home/home".to_string());
+        RouteCommand::sequence(vec![
+            RouteCommand::toast("已同意"),
+            RouteCommand::redirect_to(&home_route),
+        ])
+    }
+
+    /// 实名认证守卫拦截时：未认证时询问是否前往实名认证页，认证后返回 redirect；未达到年龄限制时弹窗说明
+    pub fn generate_verification_required_route_command(block: VerificationBlock, redirect: Option<&str>, route_config: &RouteConfig, platform: Platform) -> RouteCommand {
+        match block {
+            VerificationBlock::Unverified => {
+                let identity_route = route_config.get_route(route_keys::USER_IDENTITY_VERIFICATION, platform)
+                    .unwrap_or_else(|| "/pages/profile/identity".to_string());
+                RouteCommand::confirm(
+                    "需要实名认证",
+                    "根据相关规定，使用该功能前需要完成实名认证，是否立即认证？",
+                    Some(RouteCommand::navigate_to_with_params(&identity_route, json!({ "redirect": redirect }))),
+                    None,
+                )
+            }
+            VerificationBlock::Underage { min_age } => RouteCommand::alert(
+                "年龄限制",
+                &format!("根据相关规定，该功能仅向年满{}周岁的用户开放", min_age),
+            ),
+        }
+    }
+
+    /// 提交实名认证后：通过时返回 redirect（未提供时进入首页），审核中提示稍后查看，未通过时弹窗说明原因
+    pub fn generate_identity_submitted_route_command(status: &IdentityVerificationStatus, redirect: Option<&str>, route_config: &RouteConfig, platform: Platform) -> RouteCommand {
+        match status.status {
+            IdentityStatus::Verified => {
+                let target = redirect.map(str::to_string).unwrap_or_else(|| {
+                    route_config.get_route(route_keys::HOME_MAIN, platform)
+                        .unwrap_or_else(|| "/pages/home/home".to_string())
+                });
+                RouteCommand::sequence(vec![
+                    RouteCommand::toast("实名认证成功"),
+                    RouteCommand::redirect_to(&target),
+                ])
+            }
+            IdentityStatus::Pending => RouteCommand::toast("已提交，认证结果稍后更新"),
+            IdentityStatus::Rejected => RouteCommand::alert(
+                "认证未通过",
+                status.failure_reason.as_deref().unwrap_or("姓名与证件号不一致，请核对后重新提交"),
+            ),
+            IdentityStatus::Unverified => RouteCommand::toast("请提交实名认证"),
+        }
+    }
+
+    /// 提交内容未通过审核时弹窗说明原因，用户修改后重新提交
+    pub fn generate_content_rejected_route_command(rejection: &ContentRejection) -> RouteCommand {
+        RouteCommand::alert("内容未通过审核", &rejection.message())
+    }
+
+    /// 乐观锁版本冲突（HTTP 409）：询问是否重新加载，确认后前端重新获取 resource 对应的数据再重试
+    pub fn generate_version_conflict_route_command(resource: &str, id: Option<uuid::Uuid>) -> RouteCommand {
+        RouteCommand::confirm(
+            "数据已被修改",
+            "该数据已在其他地方被修改，是否重新加载后重试？",
+            Some(RouteCommand::process_data(RELOAD_DATA_TYPE, json!({ "resource": resource, "id": id }))),
+            None,
+        )
+    }
+
+    // 将指令插入到序列开头，不是序列时组合为新序列
+    fn prepend(command: RouteCommand, first: RouteCommand) -> RouteCommand {
+        match command {
+            RouteCommand::Sequence { mut commands, stop_on_error } => {
+                commands.insert(0, first);
+                RouteCommand::Sequence { commands, stop_on_error }
+            }
+            command => RouteCommand::sequence(vec![first, command]),
+        }
+    }
+
+    // 将指令追加到序列末尾，不是序列时组合为新序列
+    fn append(command: RouteCommand, last: RouteCommand) -> RouteCommand {
+        match command {
+            RouteCommand::Sequence { mut commands, stop_on_error } => {
+                commands.push(last);
+                RouteCommand::Sequence { commands, stop_on_error }
+            }
+            command => RouteCommand::sequence(vec![command, last]),
+        }
+    }
+
+    /// 根据数据导出请求结果生成路由指令
+    #[instrument(skip_all, name = "generate_data_export_route_command")]
+    pub fn generate_data_export_route_command(info: &DataExportInfo) -> RouteCommand {
+        info!(export_id = %info.export.id, status = %info.export.status.as_str(), "Generating data export route command");
+
+        match info.export.status {
+            DataExportStatus::Ready => RouteCommand::toast("数据导出已完成，可以下载"),
+            _ if info.newly_requested => RouteCommand::toast("数据导出已开始，完成后将通知您"),
+            _ => RouteCommand::toast("数据导出正在处理中，请稍后"),
+        }
+    }
+
+    /// 根据下单结果生成调起支付的路由指令
+    #[instrument(skip_all, name = "generate_payment_route_command")]
+    pub fn generate_payment_route_command(result: &PaymentOrderResult, route_config: &RouteConfig, platform: Platform, trace: &mut RouteTrace) -> RouteCommand {
+        info!(out_trade_no = %result.payment.out_trade_no, amount = %result.payment.amount, "Generating payment route command");
+
+        let result_route = route_config.get_route(route_keys::PAYMENT_RESULT, platform);
+        trace.record("payment", json!({ "out_trade_no": result.payment.out_trade_no, "result_route": result_route }));
+        let on_success = match result_route {
+            Some(result_route) => RouteCommand::sequence(vec![
+                RouteCommand::toast("支付成功"),
+                RouteCommand::navigate_to_with_params(&result_route, json!({ "out_trade_no": result.payment.out_trade_no })),
+            ]),
+            None => RouteCommand::toast("支付成功"),
+        };
+
+        RouteCommand::request_payment(
+            json!(result.pay_params),
+            on_success,
+            RouteCommand::toast("支付未完成"),
+        )
+    }
+
+    /// 根据下单结果生成跳转订单详情的路由指令
+    #[instrument(skip_all, name = "generate_order_created_route_command")]
+    pub fn generate_order_created_route_command(order: &Order, route_config: &RouteConfig, platform: Platform, trace: &mut RouteTrace) -> RouteCommand {
+        info!(order_no = %order.order_no, total_amount = %order.total_amount, "Generating order created route command");
+
+        let detail_route = route_config.get_route(route_keys::ORDER_DETAIL, platform)
+            .unwrap_or_else(|| "/pages/order/detail".to_string());
+        trace.record("order.created", json!({ "order_no": order.order_no, "route": detail_route }));
+        RouteCommand::sequence(vec![
+            RouteCommand::toast("订单已创建"),
+            RouteCommand::navigate_to_with_params(&detail_route, json!({ "order_no": order.order_no })),
+        ])
+    }
+
+    /// 根据取消订单结果生成路由指令，返回订单列表
+    #[instrument(skip_all, name = "generate_order_cancelled_route_command")]
+    pub fn generate_order_cancelled_route_command(order: &Order, route_config: &RouteConfig, platform: Platform) -> RouteCommand {
+        info!(order_no = %order.order_no, "Generating order cancelled route command");
+
+        let list_route = route_config.get_route(route_keys::ORDER_LIST, platform)
+            .unwrap_or_else(|| "/pages/order/list".to_string());
+        RouteCommand::sequence(vec![
+            RouteCommand::process_data("order", json!(order)),
+            RouteCommand::toast("订单已取消"),
+            RouteCommand::redirect_to(&list_route),
+        ])
+    }
+
+    /// 根据订单支付下单结果生成调起支付的路由指令，支付完成后进入订单详情
+    #[instrument(skip_all, name = "generate_order_payment_route_command")]
+    pub fn generate_order_payment_route_command(order_no: &str, result: &PaymentOrderResult, route_config: &RouteConfig, platform: Platform, trace: &mut RouteTrace) -> VersionedRouteCommand {
+        info!(order_no = %order_no, out_trade_no = %result.payment.out_trade_no, "Generating order payment route command");
+
+        let detail_route = route_config.get_route(route_keys::ORDER_DETAIL, platform)
+            .unwrap_or_else(|| "/pages/order/detail".to_string());
+        trace.record("order.payment", json!({ "order_no": order_no, "out_trade_no": result.payment.out_trade_no, "route": detail_route }));
+        let detail = RouteCommand::navigate_to_with_params(&detail_route, json!({ "order_no": order_no }));
+
+        let payment = RouteCommand::request_payment(
+            json!(result.pay_params),
+            RouteCommand::sequence(vec![RouteCommand::toast("支付成功"), detail.clone()]),
+            RouteCommand::sequence(vec![RouteCommand::toast("支付未完成"), detail.clone()]),
+        );
+        // 调起支付本身失败（如不在微信环境）时回到订单详情，用户可以重新支付
+        VersionedRouteCommand::generated(payment).set_fallback(detail)
+    }
+
+    /// 根据资料完善步骤的保存结果生成路由指令：还有缺失字段时进入下一步，否则返回首页
+    #[instrument(skip_all, name = "generate_profile_step_route_command")]
+    pub fn generate_profile_step_route_command(result: &ProfileStepResult, route_config: &RouteConfig, platform: Platform, trace: &mut RouteTrace) -> RouteCommand {
+        info!(user_id = %result.user.id, completed = %result.completion.completed, "Generating profile step route command");
+
+        let user_data = RouteCommand::process_data("user", serde_json::to_value(UserInfo::from(result.user.clone())).unwrap());
+        if result.completion.missing_fields.is_empty() {
+            let home_route = route_config.get_route(route_keys::HOME_INDEX, platform)
+                .unwrap_or_else(|| "/pages/index/index".to_string());
+            trace.record("profile_step.completed", json!({ "route": home_route }));
+            return RouteCommand::sequence(vec![
+                user_data,
+                RouteCommand::toast("资料已完善"),
+                RouteCommand::redirect_to(&home_route),
+            ]);
+        }
+
+        RouteCommand::sequence(vec![
+            user_data,
+            Self::profile_stepper_command(&result.completion.missing_fields, true, route_config, platform, trace),
+        ])
+    }
+
+    /// 根据资料修改结果生成路由指令：刷新前端用户数据并提示
+    #[instrument(skip_all, name = "generate_profile_update_route_command")]
+    pub fn generate_profile_update_route_command(result: &ProfileUpdateResult) -> RouteCommand {
+        info!(user_id = %result.user.id, "Generating profile update route command");
+
+        RouteCommand::sequence(vec![
+            RouteCommand::process_data("user", serde_json::to_value(UserInfo::from(result.user.clone())).unwrap()),
+            RouteCommand::toast("资料已更新"),
+        ])
+    }
+
+    /// 跳转资料完善步骤条，参数为剩余字段，由前端逐步展示；optional 中的字段可以跳过
+    fn profile_stepper_command(missing_fields: &[ProfileField], replace: bool, route_config: &RouteConfig, platform: Platform, trace: &mut RouteTrace) -> RouteCommand {
+        let stepper_route = route_config.get_route(route_keys::USER_COMPLETE_PROFILE, platform)
+            .unwrap_or_else(|| "/pages/profile/complete".to_string());
+        trace.record("profile.stepper", json!({ "missing_fields": missing_fields, "route": stepper_route }));
+        RouteCommand::NavigateTo {
+            path: stepper_route,
+            params: Some(json!({
+                "fields": missing_fields,
+                "optional": missing_fields.iter().filter(|field| !field.is_required()).collect::<Vec<_>>(),
+                "step": 1,
+                "total": missing_fields.len(),
+            })),
+            replace: Some(replace),
+            fallback_path: None,
+        }
+    }
 
     /// 处理一般性错误的路由指令
     #[instrument(skip_all, name = "generate_error_route_command")]
-    pub fn generate_error_route_command(error_message: &str, error_code: Option<&str>, route_config: &RouteConfig, platform: Platform) -> RouteCommand {
+    pub fn generate_error_route_command(error_message: &str, error_code: Option<&str>, route_config: &RouteConfig, platform: Platform, trace: &mut RouteTrace) -> RouteCommand {
         warn!(error_message = %error_message, error_code = ?error_code, "Generating error route command");
+        trace.record("error", json!({ "error_code": error_code }));
 
         match error_code {
             Some("AUTH_INVALID_CREDENTIALS") => {
@@ -173,7 +692,7 @@ impl RouteCommandGenerator {
                 RouteCommand::alert("账户已锁定", "您的账户已被锁定，请联系管理员")
             }
             Some("AUTH_SESSION_EXPIRED") => {
-                let login_route = route_config.get_route("auth.login", platform)
+                let login_route = route_config.get_route(route_keys::AUTH_LOGIN, platform)
                     .unwrap_or_else(|| "/pages/login/login".to_string());
                 RouteCommand::sequence(vec![
                     RouteCommand::alert("会话已过期", "您的会话已过期，请重新登录"),
@@ -187,6 +706,12 @@ impl RouteCommandGenerator {
             Some("SERVER_MAINTENANCE") => {
                 RouteCommand::alert("系统维护", "系统正在维护中，请稍后重试")
             }
+            Some("DATABASE_TIMEOUT") | Some("CACHE_TIMEOUT") => {
+                RouteCommand::alert("服务繁忙", "服务器响应超时，请稍后重试")
+            }
+            Some("WECHAT_TIMEOUT") => {
+                RouteCommand::alert("登录超时", "微信服务响应超时，请稍后重试")
+            }
             _ => {
                 // 通用错误处理
                 RouteCommand::alert("操作失败", error_message)
@@ -194,4 +719,220 @@ impl RouteCommandGenerator {
         }
     }
 
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::experiments::{ExperimentDefinition, ExperimentVariant};
+    use crate::models::announcement::AnnouncementLevel;
+    use crate::models::route_command::DialogType;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_session_expiry_warning_rounds_up_minutes() {
+        let now = Utc::now();
+        let command = RouteCommandGenerator::generate_session_expiry_warning_route_command(now + chrono::Duration::seconds(250), now);
+        match command {
+            RouteCommand::ShowDialog { content, actions, .. } => {
+                assert!(content.contains("5分钟"));
+                assert!(matches!(&actions[1].action, Some(RouteCommand::ProcessData { data_type, .. }) if data_type == SESSION_DATA_TYPE));
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_traced_records_decisions_in_order() {
+        let route_config = RouteConfig::from_file("routes.toml").unwrap();
+        let (_, decisions) = RouteCommandGenerator::traced(|trace| {
+            RouteCommandGenerator::generate_error_route_command("失败", Some("NETWORK_ERROR"), &route_config, Platform::H5, trace);
+            RouteCommandGenerator::generate_error_route_command("失败", None, &route_config, Platform::H5, trace)
+        });
+        assert_eq!(decisions.len(), 2);
+        assert_eq!(decisions[0].inputs, json!({ "error_code": "NETWORK_ERROR" }));
+        assert_eq!(decisions[1].rule, "error");
+        assert!(RouteCommandGenerator::traced(|_| ()).1.is_empty());
+    }
+
+    #[test]
+    fn test_wx_login_route_command_onboarding() {
+        let route_config = RouteConfig::from_file("routes.toml").unwrap();
+        let user: User = serde_json::from_value(json!({
+            "id": Uuid::new_v4(), "username": "wx_user", "email": "wx@example.com", "full_name": null, "avatar_url": null,
+            "is_active": true, "is_admin": false, "is_guest": false, "wx_openid": "openid", "wx_unionid": null,
+            "wx_session_key": null, "last_login_at": null, "created_at": Utc::now(), "updated_at": Utc::now()
+        })).unwrap();
+
+        let (command, decisions) = RouteCommandGenerator::traced(|trace| {
+            RouteCommandGenerator::generate_wx_login_route_command(&user, true, &route_config, &ExperimentsConfig::default(), Platform::Miniprogram, trace)
+        });
+        assert_eq!(decisions[0].rule, "login.new_user");
+        let RouteCommand::Sequence { commands, .. } = command else { panic!("unexpected command") };
+        assert_eq!(commands.len(), 3);
+        assert!(matches!(&commands[0], RouteCommand::ProcessData { data, .. } if data.get("session_token").is_none()));
+        assert!(matches!(&commands[1], RouteCommand::ShowDialog { dialog_type: DialogType::Toast, .. }));
+
+        // 登录引导实验中分到 dialog 组的用户使用对话框
+        let experiments = ExperimentsConfig {
+            enabled: true,
+            definitions: vec![ExperimentDefinition {
+                name: LOGIN_ONBOARDING_EXPERIMENT.to_string(),
+                variants: vec![ExperimentVariant { name: "dialog".to_string(), weight: 1 }],
+                ..ExperimentDefinition::default()
+            }],
+        };
+        let (command, decisions) = RouteCommandGenerator::traced(|trace| {
+            RouteCommandGenerator::generate_wx_login_route_command(&user, true, &route_config, &experiments, Platform::Miniprogram, trace)
+        });
+        assert_eq!(decisions[1].rule, EXPERIMENT_ASSIGNED_RULE);
+        assert_eq!(decisions[1].inputs["variant"], "dialog");
+        assert!(matches!(command, RouteCommand::Sequence { commands, .. } if matches!(commands[1], RouteCommand::ShowDialog { dialog_type: DialogType::Alert, .. })));
+
+        let (_, decisions) = RouteCommandGenerator::traced(|trace| {
+            RouteCommandGenerator::generate_wx_login_route_command(&user, false, &route_config, &ExperimentsConfig::default(), Platform::Miniprogram, trace)
+        });
+        assert_eq!(decisions[0].rule, "login.first_login");
+
+        let returning = User { last_login_at: Some(Utc::now()), ..user };
+        let (command, decisions) = RouteCommandGenerator::traced(|trace| {
+            RouteCommandGenerator::generate_wx_login_route_command(&returning, false, &route_config, &ExperimentsConfig::default(), Platform::Miniprogram, trace)
+        });
+        assert_eq!(decisions[0].rule, "login.default");
+        assert!(matches!(command, RouteCommand::Sequence { commands, .. } if commands.len() == 2));
+    }
+
+    #[test]
+    fn test_with_announcements() {
+        let command = RouteCommand::sequence(vec![RouteCommand::toast("登录成功"), RouteCommand::redirect_to("/pages/home/home")]);
+        let unchanged = RouteCommandGenerator::with_announcements(command.clone(), &[]);
+        assert!(matches!(unchanged, RouteCommand::Sequence { ref commands, .. } if commands.len() == 2));
+
+        let announcements = vec![AnnouncementView {
+            id: Uuid::new_v4(),
+            title: "系统升级".to_string(),
+            content: "今晚22点进行系统升级".to_string(),
+            level: AnnouncementLevel::Info,
+            dismissible: true,
+            ends_at: None,
+        }];
+        match RouteCommandGenerator::with_announcements(command, &announcements) {
+            RouteCommand::Sequence { commands, .. } => {
+                assert_eq!(commands.len(), 3);
+                assert!(matches!(&commands[0], RouteCommand::ProcessData { data_type, .. } if data_type == ANNOUNCEMENTS_DATA_TYPE));
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+
+        let wrapped = RouteCommandGenerator::with_announcements(RouteCommand::toast("欢迎回来"), &announcements);
+        assert!(matches!(wrapped, RouteCommand::Sequence { ref commands, .. } if commands.len() == 2));
+    }
+
+    #[test]
+    fn test_with_settings() {
+        let command = RouteCommandGenerator::with_settings(RouteCommand::toast("欢迎回来"), &UserSettings::default());
+        match command {
+            RouteCommand::Sequence { commands, .. } => {
+                assert!(matches!(&commands[0], RouteCommand::ProcessData { data_type, data, .. } if data_type == "settings" && data["locale"] == "zh-CN"));
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_with_password_recovery() {
+        let route_config = RouteConfig::from_file("routes.toml").unwrap();
+        let login = RouteCommand::sequence(vec![RouteCommand::toast("登录成功"), RouteCommand::redirect_to("/pages/home/home")]);
+
+        let command = RouteCommandGenerator::with_password_recovery(login.clone(), Some("token"), &route_config, Platform::H5);
+        match command {
+            RouteCommand::Sequence { commands, .. } => {
+                assert_eq!(commands.len(), 3);
+                match &commands[2] {
+                    RouteCommand::ShowDialog { actions, .. } => assert!(matches!(
+                        &actions[1].action,
+                        Some(RouteCommand::NavigateTo { path, params: Some(params), .. }) if path == "/reset-password" && params["recovery_token"] == "token"
+                    )),
+                    other => panic!("unexpected command: {:?}", other),
+                }
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+
+        let command = RouteCommandGenerator::with_password_recovery(login, None, &route_config, Platform::H5);
+        assert!(matches!(command, RouteCommand::Sequence { ref commands, .. } if matches!(commands[2], RouteCommand::ShowDialog { dialog_type: DialogType::Toast, .. })));
+    }
+
+    #[test]
+    fn test_reauth_required_route_command() {
+        let route_config = RouteConfig::from_file("routes.toml").unwrap();
+        let command = RouteCommandGenerator::generate_reauth_required_route_command("/api/cache/invalidate", &route_config, Platform::Admin);
+        assert!(matches!(
+            command,
+            RouteCommand::NavigateTo { ref path, params: Some(ref params), .. } if path == "/auth/reauth" && params["operation"] == "/api/cache/invalidate"
+        ));
+    }
+
+    #[test]
+    fn test_consent_required_route_command() {
+        let route_config = RouteConfig::from_file("routes.toml").unwrap();
+        let pending = vec!["privacy".to_string()];
+        let command = RouteCommandGenerator::generate_consent_required_route_command(&pending, Some("/api/profile"), &route_config, Platform::H5, &mut RouteTrace::default());
+        assert!(matches!(
+            command,
+            RouteCommand::NavigateTo { ref path, params: Some(ref params), replace: Some(true), .. }
+                if path == "/consent" && params["policies"][0] == "privacy" && params["redirect"] == "/api/profile"
+        ));
+
+        let accepted = ConsentResult { accepted: Vec::new(), pending: Vec::new() };
+        let command = RouteCommandGenerator::generate_consent_accepted_route_command(&accepted, &route_config, Platform::H5);
+        assert!(matches!(command, RouteCommand::Sequence { ref commands, .. } if matches!(&commands[1], RouteCommand::NavigateTo { path, .. } if path == "/")));
+    }
+
+    #[test]
+    fn test_verification_required_route_command() {
+        let route_config = RouteConfig::from_file("routes.toml").unwrap();
+        match RouteCommandGenerator::generate_verification_required_route_command(VerificationBlock::Unverified, Some("/api/orders"), &route_config, Platform::H5) {
+            RouteCommand::ShowDialog { actions, .. } => {
+                assert!(matches!(
+                    &actions[1].action,
+                    Some(RouteCommand::NavigateTo { path, params: Some(params), .. })
+                        if path == "/profile/identity" && params["redirect"] == "/api/orders"
+                ));
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+        let command = RouteCommandGenerator::generate_verification_required_route_command(VerificationBlock::Underage { min_age: 18 }, None, &route_config, Platform::H5);
+        assert!(matches!(command, RouteCommand::ShowDialog { ref content, .. } if content.contains("18")));
+    }
+
+    #[test]
+    fn test_version_conflict_route_command() {
+        let id = uuid::Uuid::new_v4();
+        match RouteCommandGenerator::generate_version_conflict_route_command("user_data", Some(id)) {
+            RouteCommand::ShowDialog { actions, .. } => {
+                assert!(matches!(
+                    &actions[1].action,
+                    Some(RouteCommand::ProcessData { data_type, data, .. })
+                        if data_type == RELOAD_DATA_TYPE && data["resource"] == "user_data" && data["id"] == id.to_string()
+                ));
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_login_timeout_route_command() {
+        use crate::use_cases::login_timeouts::{Dependency, DependencyTimeout};
+
+        let route_config = RouteConfig::from_file("routes.toml").unwrap();
+        let timeout = UseCaseError::TimeoutError(DependencyTimeout { dependency: Dependency::Postgres, budget: std::time::Duration::from_secs(3) });
+        match RouteCommandGenerator::generate_login_failed_route_command(&timeout, &route_config, Platform::H5, &mut RouteTrace::default()) {
+            RouteCommand::ShowDialog { title, content, .. } => {
+                assert_eq!(title, "服务繁忙");
+                assert!(content.contains("超时"));
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+}
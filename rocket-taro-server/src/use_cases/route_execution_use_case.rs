@@ -0,0 +1,134 @@
+use chrono::Utc;
+use tracing::{info, warn, instrument};
+use uuid::Uuid;
+
+use crate::config::{Platform, RouteTraceConfig};
+use crate::database::DbPool;
+use crate::models::{
+    admin_stats::StatsRange,
+    response::ApiResponse,
+    route_decision::{RouteDecision, RouteDecisionRecord, describe_decisions},
+    route_execution::{ExecutionStatus, RouteCommandAck, RouteCommandAckRequest, RouteCommandCompletion},
+};
+use super::{UseCaseError, UseCaseResult, experiment_use_case::ExperimentUseCase};
+
+/// 执行确认中错误信息的最大长度（字符）
+const MAX_ACK_ERROR_CHARS: usize = 1000;
+
+/// 路由指令执行追踪：下发时分配执行ID并记录，前端确认后更新，用于统计各业务流程的完成率
+pub struct RouteExecutionUseCase {
+    db_pool: DbPool,
+    trace: RouteTraceConfig,
+}
+
+impl RouteExecutionUseCase {
+    pub fn new(db_pool: DbPool) -> Self {
+        Self { db_pool, trace: RouteTraceConfig::default() }
+    }
+
+    /// 按配置记录路由决策
+    pub fn with_trace(mut self, trace: RouteTraceConfig) -> Self {
+        self.trace = trace;
+        self
+    }
+
+    /// 记录响应中路由指令的下发，执行ID与指令元数据中的ID相同；记录失败不影响响应，只是不返回执行ID。
+    /// 开启路由决策追踪时同时记录生成指令时命中的决策；按实验分组选择的流程无论是否追踪都记录曝光
+    #[instrument(skip_all, name = "track_route_command", fields(flow = %flow))]
+    pub async fn track<T>(
+        &self,
+        response: ApiResponse<T>,
+        flow: &str,
+        platform: Platform,
+        user_id: Option<Uuid>,
+        decisions: &[RouteDecision],
+    ) -> ApiResponse<T> {
+        use crate::database::route_execution::{insert_execution, insert_route_decisions};
+
+        let Some(command) = &response.route_command else {
+            return response;
+        };
+        if let Some(user_id) = user_id {
+            ExperimentUseCase::new(self.db_pool.clone()).record_exposures(user_id, decisions).await;
+        }
+
+        let execution_id = command.metadata.id.as_deref()
+            .and_then(|id| Uuid::parse_str(id).ok())
+            .unwrap_or_else(Uuid::new_v4);
+        if let Err(e) = insert_execution(&self.db_pool, execution_id, flow, command.command.type_name(), platform.as_str(), user_id).await {
+            warn!(error = %e, "Failed to record route command execution");
+            return response;
+        }
+        let mut response = response.with_execution_id(execution_id);
+        if !self.trace.enabled || decisions.is_empty() {
+            return response;
+        }
+
+        if let Err(e) = insert_route_decisions(&self.db_pool, execution_id, decisions).await {
+            warn!(execution_id = %execution_id, error = %e, "Failed to record route decisions");
+        }
+        if let Some(command) = response.route_command.as_mut().filter(|_| self.trace.include_in_response) {
+            command.metadata.description = Some(describe_decisions(decisions));
+        }
+        response
+    }
+
+    /// 查询一次下发命中的路由决策（管理端排查"为什么跳到了某个页面"）
+    #[instrument(skip_all, name = "get_route_decisions")]
+    pub async fn decisions(&self, execution_id: Uuid) -> UseCaseResult<Vec<RouteDecisionRecord>> {
+        use crate::database::route_execution::list_route_decisions;
+
+        Ok(list_route_decisions(&self.db_pool, execution_id).await?)
+    }
+
+    /// 删除超过保留期的路由决策，返回删除的条数
+    #[instrument(skip_all, name = "cleanup_route_decisions")]
+    pub async fn cleanup_decisions(&self) -> UseCaseResult<u64> {
+        use crate::database::route_execution::delete_expired_route_decisions;
+
+        let deleted = delete_expired_route_decisions(&self.db_pool, self.trace.retention_days).await?;
+        if deleted > 0 {
+            info!(deleted, "Expired route decisions deleted");
+        }
+        Ok(deleted)
+    }
+
+    /// 记录前端的执行确认
+    #[instrument(skip_all, name = "acknowledge_route_command")]
+    pub async fn acknowledge(&self, request: RouteCommandAckRequest) -> UseCaseResult<RouteCommandAck> {
+        use crate::database::route_execution::acknowledge_execution;
+
+        if request.duration_ms.is_some_and(|duration| duration < 0) {
+            return Err(UseCaseError::ValidationError("执行耗时不能为负数".to_string()));
+        }
+        let error = match request.status {
+            ExecutionStatus::Success => None,
+            ExecutionStatus::Error => request.error.map(|e| e.chars().take(MAX_ACK_ERROR_CHARS).collect::<String>()),
+        };
+
+        let recorded = acknowledge_execution(
+            &self.db_pool,
+            request.execution_id,
+            request.status,
+            request.duration_ms,
+            error.as_deref(),
+        ).await?;
+
+        if recorded {
+            info!(execution_id = %request.execution_id, status = %request.status.as_str(), "Route command execution acknowledged");
+        } else {
+            warn!(execution_id = %request.execution_id, "Acknowledgment for unknown or already acknowledged route command");
+        }
+
+        Ok(RouteCommandAck { execution_id: request.execution_id, recorded })
+    }
+
+    /// 按业务流程统计指令完成率
+    #[instrument(skip_all, name = "get_route_command_completion")]
+    pub async fn completion(&self, range: StatsRange) -> UseCaseResult<RouteCommandCompletion> {
+        use crate::database::route_execution::completion_by_flow;
+
+        let flows = completion_by_flow(&self.db_pool, &range).await?;
+        Ok(RouteCommandCompletion { range, flows, generated_at: Utc::now() })
+    }
+}
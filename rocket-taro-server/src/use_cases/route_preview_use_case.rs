@@ -0,0 +1,110 @@
+use serde::de::DeserializeOwned;
+use tracing::{info, instrument};
+
+use crate::config::{ExperimentsConfig, RouteConfig};
+use crate::models::{
+    business_results::{LoginResult, LogoutResult},
+    route_command::{RouteCommandMetadata, VersionedRouteCommand},
+    route_decision::describe_decisions,
+    route_preview::{RoutePreview, RoutePreviewKind, RoutePreviewRequest},
+};
+use super::{UseCaseError, UseCaseResult, route_command_generator::RouteCommandGenerator};
+
+/// 路由指令预览：用构造的业务结果运行路由决策器，不读写数据库和会话，便于调试路由流程
+pub struct RoutePreviewUseCase {
+    route_config: RouteConfig,
+    experiments: ExperimentsConfig,
+}
+
+impl RoutePreviewUseCase {
+    pub fn new(route_config: RouteConfig) -> Self {
+        Self { route_config, experiments: ExperimentsConfig::default() }
+    }
+
+    /// 按配置的 A/B 实验分组预览，未设置时按对照组预览
+    pub fn with_experiments(mut self, experiments: ExperimentsConfig) -> Self {
+        self.experiments = experiments;
+        self
+    }
+
+    #[instrument(skip_all, name = "execute_route_preview")]
+    pub fn execute_preview(&self, request: RoutePreviewRequest) -> UseCaseResult<RoutePreview> {
+        let RoutePreviewRequest { kind, result, platform } = request;
+        info!(kind = %kind.as_str(), platform = %platform.as_str(), "Generating route command preview");
+
+        let (command, decisions) = RouteCommandGenerator::traced(|trace| -> UseCaseResult<_> {
+            Ok(match kind {
+                RoutePreviewKind::Login => {
+                    let result: LoginResult = parse_result(kind, result)?;
+                    RouteCommandGenerator::generate_login_route_command(&result, &self.route_config, &self.experiments, platform, trace)
+                }
+                RoutePreviewKind::GuestLogin => {
+                    let result: LoginResult = parse_result(kind, result)?;
+                    RouteCommandGenerator::generate_guest_login_route_command(&result, &self.route_config, platform, trace)
+                }
+                RoutePreviewKind::Logout => {
+                    let result: LogoutResult = parse_result(kind, result)?;
+                    RouteCommandGenerator::generate_logout_route_command(&result, &self.route_config, platform, trace)
+                }
+            })
+        });
+        let command = command?;
+
+        // 描述中附带命中的路由决策，说明为什么生成了这条指令
+        let metadata = RouteCommandMetadata::with_id(&format!("preview-{}", kind.as_str()))
+            .with_description(&format!("{} preview for {}: {}", kind.as_str(), platform.as_str(), describe_decisions(&decisions)));
+        Ok(RoutePreview {
+            kind,
+            platform,
+            command: VersionedRouteCommand::with_metadata(command, metadata),
+        })
+    }
+}
+
+// 结果格式错误时返回具体的字段错误，便于调整构造的 JSON
+fn parse_result<T: DeserializeOwned>(kind: RoutePreviewKind, result: serde_json::Value) -> UseCaseResult<T> {
+    serde_json::from_value(result)
+        .map_err(|e| UseCaseError::ValidationError(format!("{} 结果格式错误: {}", kind.as_str(), e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Platform;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    fn use_case() -> RoutePreviewUseCase {
+        RoutePreviewUseCase::new(RouteConfig::from_file("routes.toml").unwrap())
+    }
+
+    #[test]
+    fn test_preview_logout() {
+        let request: RoutePreviewRequest = serde_json::from_value(json!({
+            "kind": "logout",
+            "platform": "h5",
+            "result": { "user_id": Uuid::new_v4(), "session_destroyed": true, "has_unsaved_data": false },
+        })).unwrap();
+
+        let preview = use_case().execute_preview(request).unwrap();
+        assert_eq!(preview.platform, Platform::H5);
+        assert_eq!(preview.command.metadata.id.as_deref(), Some("preview-logout"));
+        let description = preview.command.metadata.description.as_deref().unwrap();
+        assert!(description.starts_with("logout preview for h5: logout.default "), "{}", description);
+        let command = serde_json::to_value(&preview.command).unwrap();
+        assert_eq!(command["type"], "Conditional");
+    }
+
+    #[test]
+    fn test_preview_rejects_malformed_result() {
+        let request: RoutePreviewRequest = serde_json::from_value(json!({
+            "kind": "login",
+            "result": { "user": {} },
+        })).unwrap();
+
+        match use_case().execute_preview(request) {
+            Err(UseCaseError::ValidationError(msg)) => assert!(msg.starts_with("login 结果格式错误")),
+            other => panic!("expected validation error, got {:?}", other.map(|p| p.kind)),
+        }
+    }
+}
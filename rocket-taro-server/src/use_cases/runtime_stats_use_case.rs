@@ -0,0 +1,38 @@
+use std::time::Instant;
+use tracing::warn;
+
+use crate::database::DbPool;
+use crate::metrics::runtime_stats::{RuntimeSnapshot, RuntimeStats};
+
+/// 进程运行状态，在线会话数按缓存时长查询数据库
+pub struct RuntimeStatsUseCase {
+    db_pool: DbPool,
+    stats: RuntimeStats,
+}
+
+impl RuntimeStatsUseCase {
+    pub fn new(db_pool: DbPool, stats: RuntimeStats) -> Self {
+        Self { db_pool, stats }
+    }
+
+    /// 生成运行状态快照；会话数查询失败不影响其余字段
+    pub async fn snapshot(&self) -> RuntimeSnapshot {
+        use crate::database::admin_stats::count_active_sessions;
+
+        let now = Instant::now();
+        let active_sessions = match self.stats.cached_active_sessions(now) {
+            Some(count) => Some(count),
+            None => match count_active_sessions(&self.db_pool).await {
+                Ok(count) => {
+                    self.stats.set_active_sessions(count, now);
+                    Some(count)
+                }
+                Err(e) => {
+                    warn!("Failed to count active sessions: {}", e);
+                    None
+                }
+            },
+        };
+        self.stats.snapshot(active_sessions)
+    }
+}
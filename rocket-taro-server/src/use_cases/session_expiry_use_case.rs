@@ -0,0 +1,81 @@
+use chrono::{DateTime, Duration, Utc};
+use tracing::{info, warn, instrument};
+
+use crate::cache::{RedisPool, session::SessionCache, session_expiry::SessionExpiryCache};
+use crate::database::DbPool;
+use crate::models::auth::{User, UserSession};
+use crate::push::{PushHub, PushMessage};
+use super::{UseCaseError, UseCaseResult, route_command_generator::RouteCommandGenerator};
+
+/// 会话过期提醒推送的事件名
+pub const SESSION_EXPIRING_EVENT: &str = "session_expiring";
+
+/// 会话过期提醒与延长
+pub struct SessionExpiryUseCase {
+    db_pool: DbPool,
+    redis: RedisPool,
+}
+
+impl SessionExpiryUseCase {
+    pub fn new(db_pool: DbPool, redis: RedisPool) -> Self {
+        Self { db_pool, redis }
+    }
+
+    /// 延长当前会话，返回新的过期时间
+    #[instrument(skip_all, name = "extend_session")]
+    pub async fn extend(&self, user: &User, session: &UserSession) -> UseCaseResult<DateTime<Utc>> {
+        use crate::database::auth::extend_session;
+
+        let expires_at = extend_session(&self.db_pool, &session.session_token).await?
+            .ok_or_else(|| UseCaseError::AuthenticationError("会话已过期，请重新登录".to_string()))?;
+
+        // 刷新缓存中的会话（同时重新登记过期提醒）；刷新失败时删除缓存，下次请求从数据库读取
+        let session_cache = SessionCache::new(self.redis.clone());
+        let extended = UserSession { expires_at, ..session.clone() };
+        if let Err(e) = session_cache.cache_user_session(user, &extended).await {
+            warn!(error = %e, "Failed to refresh extended session cache");
+            if let Err(e) = session_cache.invalidate_session(&session.session_token).await {
+                warn!(error = %e, "Failed to invalidate session cache");
+            }
+        }
+
+        info!(user_id = %user.id, session_id = %session.id, expires_at = %expires_at, "Session extended");
+        Ok(expires_at)
+    }
+
+    /// 向即将在 warn_before 内过期的会话推送提醒，返回送达的提醒数
+    #[instrument(skip_all, name = "send_session_expiry_warnings")]
+    pub async fn send_due_warnings(&self, push: &PushHub, warn_before: Duration) -> UseCaseResult<usize> {
+        let now = Utc::now();
+        let schedule = SessionExpiryCache::new(self.redis.clone());
+        let due = schedule.due(now + warn_before).await
+            .map_err(|e| UseCaseError::InternalError(format!("读取会话过期时间表失败: {}", e)))?;
+
+        let mut delivered = 0;
+        for session in due {
+            // 先认领再推送，多实例时同一会话只提醒一次
+            match schedule.claim(&session).await {
+                Ok(true) => {}
+                Ok(false) => continue,
+                Err(e) => {
+                    warn!(session_id = %session.session_id, error = %e, "Failed to claim session expiry warning");
+                    continue;
+                }
+            }
+            if session.expires_at <= now {
+                continue;
+            }
+
+            let command = RouteCommandGenerator::generate_session_expiry_warning_route_command(session.expires_at, now);
+            let receivers = push.send(session.user_id, PushMessage::new(SESSION_EXPIRING_EVENT, command));
+            if receivers > 0 {
+                delivered += 1;
+            }
+        }
+
+        if delivered > 0 {
+            info!(delivered = %delivered, "Session expiry warnings pushed");
+        }
+        Ok(delivered)
+    }
+}
@@ -0,0 +1,123 @@
+use std::net::IpAddr;
+use tracing::{error, info, instrument, warn};
+use uuid::Uuid;
+
+use crate::database::DbPool;
+use crate::events::{AuthMethod, DomainEvent, EventBus};
+use crate::models::auth::{User, UserSession};
+use super::{UseCaseError, UseCaseResult, login_timeouts::{Dependency, LoginTimeouts}};
+
+/// 发起登录的请求来源，由路由层从请求中提取后传入用例层，随会话和登录日志一起保存
+#[derive(Debug, Clone, Default)]
+pub struct RequestContext {
+    pub ip_address: Option<IpAddr>,
+    pub user_agent: Option<String>,
+    /// 客户端上报的设备标识，目前只用于注册风控
+    pub device_id: Option<String>,
+}
+
+impl RequestContext {
+    pub fn new(ip_address: Option<IpAddr>, user_agent: Option<String>) -> Self {
+        Self { ip_address, user_agent, device_id: None }
+    }
+
+    pub fn with_device_id(mut self, device_id: Option<String>) -> Self {
+        self.device_id = device_id;
+        self
+    }
+}
+
+/// 登录成功后签发会话，密码登录、注册后自动登录、游客登录、微信登录和扫码登录共用；
+/// 路由层拿到会话后通过 SessionCookies 下发 Cookie
+#[derive(Clone)]
+pub struct SessionIssuer {
+    db_pool: DbPool,
+    events: EventBus,
+    timeouts: LoginTimeouts,
+}
+
+impl SessionIssuer {
+    pub fn new(db_pool: DbPool, events: EventBus) -> Self {
+        Self { db_pool, events, timeouts: LoginTimeouts::default() }
+    }
+
+    /// 设置访问数据库的时间预算，未设置时使用默认预算
+    pub fn with_timeouts(mut self, timeouts: LoginTimeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// 创建会话（登录日志、会话缓存预热、清除登录失败计数随会话写入副作用发件箱）、
+    /// 更新最后登录时间并发布登录事件
+    #[instrument(skip_all, name = "issue_session", fields(user_id = %user.id, method = method.as_str()))]
+    pub async fn issue(
+        &self,
+        user: &User,
+        method: AuthMethod,
+        context: &RequestContext,
+    ) -> UseCaseResult<UserSession> {
+        use crate::database::auth::{create_login_session, update_last_login};
+
+        let session = self.timeouts.run(Dependency::Postgres, create_login_session(
+            &self.db_pool,
+            user,
+            context.user_agent.clone(),
+            context.ip_address,
+            login_note(method).map(str::to_string),
+        )).await?.map_err(|e| {
+            error!(user_id = %user.id, error = %e, "Failed to create session");
+            UseCaseError::DatabaseError(e.to_string())
+        })?;
+        info!(user_id = %user.id, session_id = %session.id, "Session created successfully");
+
+        match self.timeouts.run(Dependency::Postgres, update_last_login(&self.db_pool, user.id)).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => warn!(user_id = %user.id, error = %e, "Failed to update last login time"),
+            Err(e) => warn!(user_id = %user.id, error = %e, "Timed out updating last login time"),
+        }
+
+        self.events.publish(DomainEvent::UserLoggedIn {
+            user: user.clone(),
+            session: session.clone(),
+            method,
+            ip_address: context.ip_address,
+            user_agent: context.user_agent.clone(),
+        }).await;
+        Ok(session)
+    }
+
+    /// 为首次请求签发的会话更换令牌并重新下发，用于幂等重放（保存的响应不含会话令牌），
+    /// 不创建新会话；会话已注销、过期或账户已停用时返回 None
+    pub async fn resume(&self, session_id: Uuid, user_id: Uuid) -> UseCaseResult<Option<UserSession>> {
+        use crate::database::auth::rotate_session_token;
+
+        let session = self.timeouts.run(Dependency::Postgres, rotate_session_token(&self.db_pool, session_id, user_id)).await?
+            .map_err(|e| UseCaseError::DatabaseError(e.to_string()))?;
+        if session.is_none() {
+            warn!(user_id = %user_id, session_id = %session_id, "Replayed login session is no longer valid");
+        }
+        Ok(session)
+    }
+}
+
+/// 登录日志中的备注，密码登录不加备注
+fn login_note(method: AuthMethod) -> Option<&'static str> {
+    match method {
+        AuthMethod::Password => None,
+        AuthMethod::Guest => Some("游客登录"),
+        AuthMethod::Wechat => Some("微信登录"),
+        AuthMethod::QrCode => Some("扫码登录"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_login_note() {
+        assert_eq!(login_note(AuthMethod::Password), None);
+        assert_eq!(login_note(AuthMethod::Wechat), Some("微信登录"));
+        assert_eq!(login_note(AuthMethod::QrCode), Some("扫码登录"));
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::HashSet;
+use serde_json::json;
+use tracing::{info, warn, error, instrument};
+use uuid::Uuid;
+
+use crate::cache::{RedisPool, session::SessionCache};
+use crate::config::{Platform, RouteConfig, SessionLimitsConfig};
+use crate::database::DbPool;
+use crate::models::{audit::AuditEvent, auth::ActiveSession};
+use crate::push::{PushHub, PushMessage};
+use super::{UseCaseResult, route_command_generator::RouteCommandGenerator};
+
+/// 会话被踢出时的推送事件名
+pub const SESSION_EVICTED_EVENT: &str = "session_evicted";
+
+/// 同时在线会话数限制：新登录超出限制时踢出最早的会话，并通知被踢出的客户端退出登录
+pub struct SessionLimitUseCase {
+    db_pool: DbPool,
+    redis: RedisPool,
+    push: PushHub,
+    config: SessionLimitsConfig,
+    route_config: RouteConfig,
+}
+
+impl SessionLimitUseCase {
+    pub fn new(db_pool: DbPool, redis: RedisPool, push: PushHub, config: SessionLimitsConfig, route_config: RouteConfig) -> Self {
+        Self { db_pool, redis, push, config, route_config }
+    }
+
+    /// 新会话创建后执行限制，返回踢出的会话数
+    #[instrument(skip_all, name = "enforce_session_limits")]
+    pub async fn enforce(&self, user_id: Uuid, new_session_id: Uuid) -> UseCaseResult<usize> {
+        use crate::database::auth::{delete_sessions, list_active_sessions};
+        use crate::database::audit::record_audit_event;
+
+        if self.config.total_limit().is_none() && self.config.platforms.values().all(|limit| *limit == 0) {
+            return Ok(0);
+        }
+
+        let sessions = list_active_sessions(&self.db_pool, user_id).await?;
+        let evict = select_evictions(&self.config, &sessions, new_session_id);
+        if evict.is_empty() {
+            return Ok(0);
+        }
+
+        // 并发登录时其他请求可能已删除部分会话，只通知实际删除的会话
+        let evicted = delete_sessions(&self.db_pool, user_id, &evict).await?;
+        let session_cache = SessionCache::new(self.redis.clone());
+        let mut cache_missed = false;
+        for session in sessions.iter().filter(|session| evicted.contains(&session.id)) {
+            match session_cache.invalidate_session_by_id(session.id).await {
+                Ok(found) => cache_missed |= !found,
+                Err(e) => {
+                    warn!(session_id = %session.id, error = %e, "Failed to invalidate evicted session cache");
+                    cache_missed = true;
+                }
+            }
+
+            let platform = session_platform(session);
+            let command = RouteCommandGenerator::generate_session_evicted_route_command(&self.route_config, platform);
+            self.push.send(user_id, PushMessage::for_session(SESSION_EVICTED_EVENT, session.id, command));
+        }
+        // 找不到会话缓存时清理该用户全部会话缓存，有效会话在下次请求时从数据库重新加载
+        if cache_missed {
+            if let Err(e) = session_cache.invalidate_user_sessions(user_id).await {
+                warn!(user_id = %user_id, error = %e, "Failed to invalidate session cache");
+            }
+        }
+
+        let event = AuditEvent::new("session.evicted", "user")
+            .target(user_id)
+            .details(json!({ "new_session_id": new_session_id, "evicted_sessions": evicted }));
+        if let Err(e) = record_audit_event(&self.db_pool, &event).await {
+            error!(user_id = %user_id, error = %e, "Failed to record session eviction audit event");
+        }
+
+        info!(user_id = %user_id, evicted = %evicted.len(), "Evicted sessions over limit");
+        Ok(evicted.len())
+    }
+}
+
+fn session_platform(session: &ActiveSession) -> Platform {
+    session.user_agent.as_deref()
+        .map(Platform::from_user_agent)
+        .unwrap_or_default()
+}
+
+/// 选出需要踢出的会话：先按平台限制、再按总数限制踢出最早的会话，新会话始终保留
+///
+/// sessions 需按创建时间从早到晚排序。
+fn select_evictions(config: &SessionLimitsConfig, sessions: &[ActiveSession], keep: Uuid) -> Vec<Uuid> {
+    let mut evicted = HashSet::new();
+
+    for (platform, limit) in config.platforms.iter().filter(|(_, limit)| **limit > 0) {
+        let same_platform: Vec<&ActiveSession> = sessions.iter()
+            .filter(|session| session_platform(session) == *platform)
+            .collect();
+        let excess = same_platform.len().saturating_sub(*limit);
+        evicted.extend(same_platform.iter()
+            .map(|session| session.id)
+            .filter(|id| *id != keep)
+            .take(excess));
+    }
+
+    if let Some(limit) = config.total_limit() {
+        let remaining: Vec<Uuid> = sessions.iter()
+            .map(|session| session.id)
+            .filter(|id| !evicted.contains(id))
+            .collect();
+        let excess = remaining.len().saturating_sub(limit);
+        evicted.extend(remaining.into_iter().filter(|id| *id != keep).take(excess));
+    }
+
+    sessions.iter()
+        .map(|session| session.id)
+        .filter(|id| evicted.contains(id))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use std::collections::HashMap;
+
+    const DESKTOP: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36";
+    const MINIPROGRAM: &str = "WeChat Mini Program";
+
+    fn sessions(user_agents: &[&str]) -> Vec<ActiveSession> {
+        let now = Utc::now();
+        user_agents.iter().enumerate().map(|(index, user_agent)| ActiveSession {
+            id: Uuid::new_v4(),
+            user_agent: Some(user_agent.to_string()),
+            created_at: now + Duration::seconds(index as i64),
+        }).collect()
+    }
+
+    #[test]
+    fn test_platform_limit_evicts_oldest() {
+        let config = SessionLimitsConfig::default();
+        let sessions = sessions(&[DESKTOP, MINIPROGRAM, DESKTOP]);
+        let newest = sessions[2].id;
+        assert_eq!(select_evictions(&config, &sessions, newest), vec![sessions[0].id]);
+    }
+
+    #[test]
+    fn test_total_limit_keeps_new_session() {
+        let config = SessionLimitsConfig { max_active_sessions: 2, platforms: HashMap::new() };
+        let sessions = sessions(&[MINIPROGRAM, MINIPROGRAM, DESKTOP, MINIPROGRAM]);
+        // 新会话不是最新创建的（时钟偏差等），也不会被踢出
+        let keep = sessions[0].id;
+        assert_eq!(select_evictions(&config, &sessions, keep), vec![sessions[1].id, sessions[2].id]);
+
+        let unlimited = SessionLimitsConfig { max_active_sessions: 0, platforms: HashMap::new() };
+        assert!(select_evictions(&unlimited, &sessions, keep).is_empty());
+    }
+}
@@ -0,0 +1,171 @@
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+use tracing::{debug, instrument, warn};
+use uuid::Uuid;
+
+use crate::cache::{RedisPool, session::SessionCache, user::{CachedUser, UserCache}};
+use crate::config::SessionExpiryConfig;
+use crate::database::{DbHealth, DbPool};
+use crate::models::auth::{User, UserSession};
+use super::{UseCaseError, UseCaseResult};
+
+/// 批量查询用户时单次最多的ID数
+pub const MAX_USERS_PER_LOOKUP: usize = 100;
+
+/// 会话校验结果
+#[derive(Debug)]
+pub enum SessionLookup {
+    Valid { user: Box<CachedUser>, session: Box<UserSession> },
+    /// 会话不存在、已过期或用户已停用
+    Invalid,
+    /// 超过空闲时长没有访问
+    Idle,
+}
+
+/// 供内部服务调用的会话校验和用户查询，与请求守卫使用相同的缓存和数据库；
+/// 只读，不刷新会话访问时间，也不删除空闲会话
+pub struct SessionLookupUseCase {
+    db_pool: DbPool,
+    redis: Option<RedisPool>,
+    db_health: Option<DbHealth>,
+    expiry: SessionExpiryConfig,
+}
+
+impl SessionLookupUseCase {
+    pub fn new(db_pool: DbPool, redis: Option<RedisPool>, db_health: Option<DbHealth>, expiry: SessionExpiryConfig) -> Self {
+        Self { db_pool, redis, db_health, expiry }
+    }
+
+    // Redis 熔断期间不读写缓存
+    fn redis(&self) -> Option<&RedisPool> {
+        self.redis.as_ref().filter(|redis| redis.is_available())
+    }
+
+    fn ensure_database(&self) -> UseCaseResult<()> {
+        match &self.db_health {
+            Some(db_health) if !db_health.is_available() => Err(UseCaseError::DatabaseError("数据库暂不可用".to_string())),
+            _ => Ok(()),
+        }
+    }
+
+    /// 校验会话令牌：优先读取会话缓存，未命中时查询数据库并写入缓存
+    #[instrument(skip_all, name = "lookup_session")]
+    pub async fn validate_session(&self, session_token: &str) -> UseCaseResult<SessionLookup> {
+        use crate::database::auth::validate_session;
+
+        if let Some(redis) = self.redis() {
+            let session_cache = SessionCache::new(redis.clone());
+            match session_cache.get_user_session_by_token(session_token).await {
+                Ok(Some(cached)) => {
+                    let last_access = session_cache.get_session_last_access(session_token).await
+                        .unwrap_or_default()
+                        .and_then(|timestamp| DateTime::from_timestamp(timestamp, 0))
+                        .or(cached.session.last_accessed_at)
+                        .unwrap_or(cached.session.created_at);
+                    if self.expiry.is_idle(last_access, Utc::now()) {
+                        return Ok(SessionLookup::Idle);
+                    }
+                    return Ok(SessionLookup::Valid {
+                        user: Box::new(cached.user),
+                        session: Box::new(cached.session.into_session(session_token)),
+                    });
+                }
+                Ok(None) => debug!("Session not found in cache, checking database"),
+                Err(e) => warn!("Cache lookup failed, falling back to database: {}", e),
+            }
+        }
+
+        self.ensure_database()?;
+        let Some((user, session)) = validate_session(&self.db_pool, session_token).await? else {
+            return Ok(SessionLookup::Invalid);
+        };
+        if self.expiry.is_idle(session.last_accessed_at, Utc::now()) {
+            return Ok(SessionLookup::Idle);
+        }
+        if let Some(redis) = self.redis() {
+            if let Err(e) = SessionCache::new(redis.clone()).cache_user_session(&user, &session).await {
+                debug!("Failed to cache user session after database validation: {}", e);
+            }
+        }
+        Ok(SessionLookup::Valid { user: Box::new(CachedUser::from(user)), session: Box::new(session) })
+    }
+
+    /// 按ID查询未注销的用户
+    pub async fn get_user(&self, user_id: Uuid) -> UseCaseResult<Option<CachedUser>> {
+        Ok(self.get_users(&[user_id]).await?.pop())
+    }
+
+    /// 按用户名查询未注销的用户，用户名到ID的映射同样走缓存
+    #[instrument(skip_all, name = "lookup_user_by_username")]
+    pub async fn get_user_by_username(&self, username: &str) -> UseCaseResult<Option<CachedUser>> {
+        use crate::database::profile::find_user_by_username;
+
+        if let Some(redis) = self.redis() {
+            match UserCache::new(redis.clone()).get_user_id_by_username(username).await {
+                Ok(Some(user_id)) => return self.get_user(user_id).await,
+                Ok(None) => {}
+                Err(e) => warn!("Username cache lookup failed, falling back to database: {}", e),
+            }
+        }
+
+        self.ensure_database()?;
+        let Some(user) = find_user_by_username(&self.db_pool, username).await? else {
+            return Ok(None);
+        };
+        if let Some(redis) = self.redis() {
+            let user_cache = UserCache::new(redis.clone());
+            if let Err(e) = user_cache.cache_username_mapping(&user.username, user.id).await {
+                debug!("Failed to cache username mapping: {}", e);
+            }
+            cache_users(&user_cache, std::slice::from_ref(&user)).await;
+        }
+        Ok(Some(CachedUser::from(user)))
+    }
+
+    /// 按ID批量查询未注销的用户，缓存未命中的合并为一次数据库查询；不存在的ID不返回
+    #[instrument(skip_all, name = "lookup_users", fields(count = user_ids.len()))]
+    pub async fn get_users(&self, user_ids: &[Uuid]) -> UseCaseResult<Vec<CachedUser>> {
+        use crate::database::profile::find_users_by_ids;
+
+        if user_ids.len() > MAX_USERS_PER_LOOKUP {
+            return Err(UseCaseError::ValidationError(format!("单次最多查询 {} 个用户", MAX_USERS_PER_LOOKUP)));
+        }
+        let mut pending: Vec<Uuid> = user_ids.iter().copied().collect::<HashSet<_>>().into_iter().collect();
+        let mut users = Vec::with_capacity(pending.len());
+
+        let user_cache = self.redis().map(|redis| UserCache::new(redis.clone()));
+        if let Some(user_cache) = &user_cache {
+            let mut misses = Vec::new();
+            for user_id in pending {
+                match user_cache.get_user(user_id).await {
+                    Ok(Some(user)) => users.push(user),
+                    Ok(None) => misses.push(user_id),
+                    Err(e) => {
+                        warn!("User cache lookup failed, falling back to database: {}", e);
+                        misses.push(user_id);
+                    }
+                }
+            }
+            pending = misses;
+        }
+        if pending.is_empty() {
+            return Ok(users);
+        }
+
+        self.ensure_database()?;
+        let found = find_users_by_ids(&self.db_pool, &pending).await?;
+        if let Some(user_cache) = &user_cache {
+            cache_users(user_cache, &found).await;
+        }
+        users.extend(found.into_iter().map(CachedUser::from));
+        Ok(users)
+    }
+}
+
+async fn cache_users(user_cache: &UserCache, users: &[User]) {
+    for user in users {
+        if let Err(e) = user_cache.cache_user(user).await {
+            debug!("Failed to cache user info: {}", e);
+        }
+    }
+}
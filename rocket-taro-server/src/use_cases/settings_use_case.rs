@@ -0,0 +1,69 @@
+use tracing::{info, warn, instrument};
+use uuid::Uuid;
+
+use crate::cache::{RedisPool, settings::UserSettingsCache};
+use crate::database::DbPool;
+use crate::models::settings::UserSettings;
+use super::{UseCaseError, UseCaseResult};
+
+/// 用户偏好设置
+pub struct SettingsUseCase {
+    db_pool: DbPool,
+    redis: RedisPool,
+}
+
+impl SettingsUseCase {
+    pub fn new(db_pool: DbPool, redis: RedisPool) -> Self {
+        Self { db_pool, redis }
+    }
+
+    /// 获取用户设置，优先读缓存，未保存过时返回默认设置
+    #[instrument(skip_all, name = "get_user_settings")]
+    pub async fn get(&self, user_id: Uuid) -> UseCaseResult<UserSettings> {
+        use crate::database::settings::get_user_settings;
+
+        let cache = UserSettingsCache::new(self.redis.clone());
+        if let Ok(Some(settings)) = cache.get(user_id).await {
+            return Ok(settings);
+        }
+
+        // 存储的设置缺少的键使用默认值，无法解析时整体回退到默认设置
+        let settings = match get_user_settings(&self.db_pool, user_id).await? {
+            Some(value) => serde_json::from_value(value).unwrap_or_else(|e| {
+                warn!(user_id = %user_id, error = %e, "Stored user settings are invalid, using defaults");
+                UserSettings::default()
+            }),
+            None => UserSettings::default(),
+        };
+
+        if let Err(e) = cache.set(user_id, &settings).await {
+            warn!(user_id = %user_id, error = %e, "Failed to cache user settings");
+        }
+        Ok(settings)
+    }
+
+    /// 按 PATCH 请求更新用户设置
+    #[instrument(skip_all, name = "execute_update_settings")]
+    pub async fn execute_update(&self, user_id: Uuid, patch: &serde_json::Value) -> UseCaseResult<UserSettings> {
+        use crate::database::settings::upsert_user_settings;
+
+        let mut settings = self.get(user_id).await?;
+        settings.apply_patch(patch).map_err(UseCaseError::ValidationError)?;
+
+        let value = serde_json::to_value(&settings)
+            .map_err(|e| UseCaseError::InternalError(format!("序列化设置失败: {}", e)))?;
+        upsert_user_settings(&self.db_pool, user_id, &value).await?;
+
+        // 缓存写入失败时删除旧缓存，下次从数据库读取
+        let cache = UserSettingsCache::new(self.redis.clone());
+        if let Err(e) = cache.set(user_id, &settings).await {
+            warn!(user_id = %user_id, error = %e, "Failed to cache updated user settings");
+            if let Err(e) = cache.invalidate(user_id).await {
+                warn!(user_id = %user_id, error = %e, "Failed to invalidate user settings cache");
+            }
+        }
+
+        info!(user_id = %user_id, "User settings updated");
+        Ok(settings)
+    }
+}
@@ -0,0 +1,125 @@
+use chrono::{Duration, Utc};
+use tracing::{info, warn, error, debug, instrument};
+
+use crate::cache::{RedisPool, session_expiry::SessionExpiryCache, user::UserCache};
+use crate::config::SideEffectConfig;
+use crate::database::DbPool;
+use crate::models::{
+    login_log::NewLoginLog,
+    side_effect::{PendingSideEffect, SideEffect, SideEffectStatus},
+    webhook::retry_backoff,
+};
+use super::UseCaseResult;
+
+/// 领取后超过该时长仍未完成的记录视为执行进程中断
+const STALE_LOCK_SECS: i64 = 300;
+
+/// 副作用发件箱用例：执行登录记账等需要可靠完成的副作用，失败时按指数退避重试
+pub struct SideEffectUseCase {
+    db_pool: DbPool,
+    redis: RedisPool,
+    config: SideEffectConfig,
+}
+
+impl SideEffectUseCase {
+    pub fn new(db_pool: DbPool, redis: RedisPool, config: SideEffectConfig) -> Self {
+        Self { db_pool, redis, config }
+    }
+
+    /// 领取并执行到期的副作用，返回领取的记录数
+    #[instrument(skip_all, name = "dispatch_side_effects")]
+    pub async fn dispatch_due(&self) -> UseCaseResult<usize> {
+        use crate::database::side_effect::claim_due_side_effects;
+
+        let stale_before = Utc::now() - Duration::seconds(STALE_LOCK_SECS);
+        let pending = claim_due_side_effects(&self.db_pool, stale_before, self.config.batch_size).await?;
+        let count = pending.len();
+
+        // 按写入顺序逐条执行，保证同一次登录的日志和缓存操作不会乱序
+        for item in &pending {
+            let result = match item.effect() {
+                Ok(effect) => self.execute(&effect).await,
+                Err(e) => Err(anyhow::anyhow!("unrecognized side effect payload: {}", e)),
+            };
+            self.record_result(item, result).await;
+        }
+
+        if count > 0 {
+            debug!(count = %count, "Dispatched side effects");
+        }
+        Ok(count)
+    }
+
+    /// 清理超过保留期的已执行记录
+    pub async fn purge_history(&self) -> UseCaseResult<u64> {
+        use crate::database::side_effect::purge_side_effects;
+
+        let purged = purge_side_effects(&self.db_pool, Utc::now() - Duration::days(self.config.retention_days)).await?;
+        if purged > 0 {
+            info!(count = %purged, "Purged side effect outbox");
+        }
+        Ok(purged)
+    }
+
+    async fn execute(&self, effect: &SideEffect) -> anyhow::Result<()> {
+        use crate::database::auth::find_valid_session;
+        use crate::database::login_log::insert_login_log;
+
+        match effect {
+            SideEffect::LoginLog { user_id, username, success, ip_address, user_agent, note } => {
+                let log = match user_id {
+                    Some(user_id) if *success => NewLoginLog::success(*user_id, username, note.clone()),
+                    _ => NewLoginLog::failure(*user_id, username, note.as_deref().unwrap_or("未知原因")),
+                };
+                insert_login_log(&self.db_pool, &log.client(*ip_address, user_agent.clone())).await?;
+            }
+            SideEffect::CacheUserSession { user_id, session_id } => {
+                // 从数据库读取最新状态再写缓存，会话已注销或过期时无需处理
+                match find_valid_session(&self.db_pool, *session_id).await? {
+                    Some((user, session)) => {
+                        let user_cache = UserCache::new(self.redis.clone());
+                        user_cache.cache_user(&user).await?;
+                        user_cache.cache_username_mapping(&user.username, user.id).await?;
+                        SessionExpiryCache::new(self.redis.clone()).schedule(user.id, session.id, session.expires_at).await?;
+                    }
+                    None => debug!(user_id = %user_id, "Session no longer valid, skipping cache warm-up"),
+                }
+            }
+            SideEffect::ClearLoginFailures { username } => {
+                UserCache::new(self.redis.clone()).clear_login_failures(username).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn record_result(&self, item: &PendingSideEffect, result: anyhow::Result<()>) {
+        use crate::database::side_effect::complete_side_effect;
+
+        let (status, error, next_attempt_at) = match &result {
+            Ok(()) => (SideEffectStatus::Done, None, None),
+            Err(e) if item.attempts >= self.config.max_attempts => {
+                error!(side_effect_id = %item.id, attempts = %item.attempts, error = %e, "Side effect abandoned after max attempts");
+                (SideEffectStatus::Dead, Some(e.to_string()), None)
+            }
+            Err(e) => {
+                let delay = retry_backoff(
+                    item.attempts,
+                    Duration::seconds(self.config.retry_base_secs),
+                    Duration::seconds(self.config.retry_max_secs),
+                );
+                warn!(
+                    side_effect_id = %item.id,
+                    attempt = %item.attempts,
+                    retry_in_secs = %delay.num_seconds(),
+                    error = %e,
+                    "Side effect failed, scheduling retry"
+                );
+                (SideEffectStatus::Pending, Some(e.to_string()), Some(Utc::now() + delay))
+            }
+        };
+
+        if let Err(e) = complete_side_effect(&self.db_pool, item.id, status, error.as_deref(), next_attempt_at).await {
+            error!(side_effect_id = %item.id, error = %e, "Failed to record side effect result");
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use chrono::{DateTime, Duration, Utc};
+use tracing::{error, info, instrument, warn};
+use uuid::Uuid;
+
+use crate::config::StorageConfig;
+use crate::database::DbPool;
+use crate::models::storage::{StorageObject, StorageUsage};
+use crate::storage::ObjectStorage;
+use super::{UseCaseError, UseCaseResult};
+
+/// 管理端一次最多列出的用户数
+const MAX_USAGE_PAGE_SIZE: i64 = 200;
+
+/// 生成文件的存储用例：保存到配置的存储后端并记录归属和大小，用于按用户统计占用空间；
+/// 过期的文件由后台任务删除
+#[derive(Clone)]
+pub struct StorageUseCase {
+    db_pool: DbPool,
+    storage: Arc<dyn ObjectStorage>,
+    config: StorageConfig,
+}
+
+impl StorageUseCase {
+    pub fn new(db_pool: DbPool, storage: Arc<dyn ObjectStorage>, config: StorageConfig) -> Self {
+        Self { db_pool, storage, config }
+    }
+
+    /// 保存本地生成的文件，返回文件大小；expires_at 为空时长期保留
+    #[instrument(skip_all, name = "store_file", fields(key = key))]
+    pub async fn store_file(
+        &self,
+        key: &str,
+        path: &Path,
+        content_type: &str,
+        kind: &str,
+        user_id: Option<Uuid>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> UseCaseResult<u64> {
+        use crate::database::storage::record_storage_object;
+
+        let size = self.storage.put_file(key, path, content_type).await
+            .map_err(|e| UseCaseError::InternalError(format!("无法保存文件: {}", e)))?;
+        if let Err(e) = record_storage_object(&self.db_pool, key, user_id, kind, size as i64, content_type, expires_at).await {
+            // 没有记录的文件不会被清理，直接删除
+            if let Err(e) = self.storage.delete(key).await {
+                warn!(key = key, error = %e, "Failed to remove unrecorded file");
+            }
+            return Err(e.into());
+        }
+        info!(key = key, kind = kind, size = size, backend = self.storage.name(), "File stored");
+        Ok(size)
+    }
+
+    /// 生成限时下载地址，返回地址和失效时间
+    pub fn signed_url(&self, key: &str, file_name: &str) -> UseCaseResult<(String, DateTime<Utc>)> {
+        let expires_at = Utc::now() + Duration::seconds(self.config.signed_url_ttl_secs as i64);
+        let url = self.storage.signed_url(key, file_name, expires_at)
+            .map_err(UseCaseError::InternalError)?;
+        Ok((url, expires_at))
+    }
+
+    /// 校验本服务签发的下载地址，有效时返回本地文件路径
+    pub fn open_signed(&self, key: &str, file_name: &str, expires: i64, signature: &str) -> Option<PathBuf> {
+        self.storage.resolve_signed(key, file_name, expires, signature)
+    }
+
+    /// 删除过期的文件，返回删除的数量
+    #[instrument(skip_all, name = "cleanup_expired_storage")]
+    pub async fn cleanup_expired(&self) -> UseCaseResult<usize> {
+        use crate::database::storage::{claim_expired_storage_objects, delete_storage_object};
+
+        let objects = claim_expired_storage_objects(
+            &self.db_pool,
+            self.config.cleanup_batch_size,
+            self.config.cleanup_interval_secs as i64,
+        ).await?;
+        let mut removed = 0;
+        for StorageObject { key, kind, .. } in &objects {
+            // 删除失败时保留记录，等待下次清理
+            if let Err(e) = self.storage.delete(key).await {
+                error!(key = %key, kind = %kind, error = %e, "Failed to remove expired file");
+                continue;
+            }
+            delete_storage_object(&self.db_pool, key).await?;
+            removed += 1;
+        }
+        if removed > 0 {
+            info!(count = removed, backend = self.storage.name(), "Expired files removed");
+        }
+        Ok(removed)
+    }
+
+    /// 用户占用的存储空间
+    pub async fn usage(&self, user_id: Uuid) -> UseCaseResult<StorageUsage> {
+        use crate::database::storage::get_storage_usage;
+
+        Ok(get_storage_usage(&self.db_pool, user_id).await?)
+    }
+
+    /// 占用空间最多的用户（管理端）
+    pub async fn top_usage(&self, limit: Option<i64>) -> UseCaseResult<Vec<StorageUsage>> {
+        use crate::database::storage::list_top_storage_usage;
+
+        let limit = limit.unwrap_or(50).clamp(1, MAX_USAGE_PAGE_SIZE);
+        Ok(list_top_storage_usage(&self.db_pool, limit).await?)
+    }
+}
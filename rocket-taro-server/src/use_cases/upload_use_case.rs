@@ -0,0 +1,141 @@
+use std::path::Path;
+use chrono::{Duration, Utc};
+use rocket::fs::TempFile;
+use tracing::{info, warn, instrument};
+use uuid::Uuid;
+
+use crate::config::UploadConfig;
+use crate::database::DbPool;
+use crate::models::upload::{Attachment, Upload, sanitize_file_name};
+use super::moderation_use_case::ModerationUseCase;
+use super::{UseCaseError, UseCaseResult};
+
+/// 单次清理的最大文件数
+const CLEANUP_BATCH_SIZE: i64 = 500;
+
+/// 文件上传用例：保存上传的文件，供用户数据作为附件引用
+pub struct UploadUseCase {
+    db_pool: DbPool,
+    config: UploadConfig,
+    moderation: Option<ModerationUseCase>,
+}
+
+impl UploadUseCase {
+    pub fn new(db_pool: DbPool, config: UploadConfig) -> Self {
+        Self { db_pool, config, moderation: None }
+    }
+
+    /// 设置图片的内容审核，未设置时不审核
+    pub fn with_moderation(mut self, moderation: ModerationUseCase) -> Self {
+        self.moderation = Some(moderation);
+        self
+    }
+
+    /// 检查类型和大小后保存文件，图片通过内容审核后返回附件信息
+    #[instrument(skip_all, name = "store_upload")]
+    pub async fn store(&self, file: &mut TempFile<'_>, user_id: Option<Uuid>) -> UseCaseResult<Attachment> {
+        use crate::database::upload::insert_upload;
+
+        let content_type = file.content_type()
+            .map(|content_type| content_type.to_string())
+            .unwrap_or_default();
+        if !self.config.is_allowed(&content_type) {
+            return Err(UseCaseError::ValidationError("不支持的文件类型".to_string()));
+        }
+        if file.len() == 0 {
+            return Err(UseCaseError::ValidationError("文件内容为空".to_string()));
+        }
+        if file.len() > self.config.max_file_size.as_u64() {
+            return Err(UseCaseError::ValidationError(format!("文件不能超过{}", self.config.max_file_size)));
+        }
+
+        let directory = Path::new(&self.config.directory);
+        tokio::fs::create_dir_all(directory).await
+            .map_err(|e| UseCaseError::InternalError(format!("无法创建上传目录: {}", e)))?;
+
+        // 文件按ID保存，客户端提交的文件名只用于展示
+        let id = Uuid::new_v4();
+        let file_path = directory.join(id.to_string());
+        let file_name = sanitize_file_name(file.raw_name().map(|name| name.dangerous_unsafe_unsanitized_raw().as_str()).unwrap_or_default());
+        let file_size = file.len() as i64;
+        file.move_copy_to(&file_path).await
+            .map_err(|e| UseCaseError::InternalError(format!("无法保存上传文件: {}", e)))?;
+
+        let upload = Upload {
+            id,
+            user_id,
+            file_name,
+            content_type: content_type.split(';').next().unwrap_or_default().trim().to_string(),
+            file_size,
+            file_path: file_path.to_string_lossy().to_string(),
+            created_at: Utc::now(),
+        };
+        if let Err(e) = self.moderate(&upload).await {
+            let _ = tokio::fs::remove_file(&file_path).await;
+            return Err(e);
+        }
+        if let Err(e) = insert_upload(&self.db_pool, &upload).await {
+            let _ = tokio::fs::remove_file(&file_path).await;
+            return Err(e.into());
+        }
+
+        info!(upload_id = %upload.id, size = %upload.file_size, content_type = %upload.content_type, "File uploaded");
+        Ok(upload.attachment())
+    }
+
+    // 审核上传的图片，其他类型不送审
+    async fn moderate(&self, upload: &Upload) -> UseCaseResult<()> {
+        let Some(moderation) = &self.moderation else { return Ok(()) };
+        if !upload.content_type.starts_with("image/") {
+            return Ok(());
+        }
+        let image = tokio::fs::read(&upload.file_path).await
+            .map_err(|e| UseCaseError::InternalError(format!("无法读取上传文件: {}", e)))?;
+        moderation.check_image(&image, &upload.content_type).await
+    }
+
+    /// 获取上传的文件
+    pub async fn open(&self, upload_id: Uuid) -> UseCaseResult<Upload> {
+        use crate::database::upload::find_upload;
+
+        find_upload(&self.db_pool, upload_id).await?
+            .ok_or_else(|| UseCaseError::ValidationError("文件不存在".to_string()))
+    }
+
+    /// 校验提交时引用的附件：数量不超过上限，文件存在且未被其他提交使用，按提交顺序返回
+    pub async fn resolve_attachments(&self, upload_ids: &[Uuid]) -> UseCaseResult<Vec<Attachment>> {
+        use crate::database::upload::find_unattached_uploads;
+
+        if upload_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        if upload_ids.len() > self.config.max_attachments {
+            return Err(UseCaseError::ValidationError(format!("最多上传{}个附件", self.config.max_attachments)));
+        }
+
+        let uploads = find_unattached_uploads(&self.db_pool, upload_ids).await?;
+        upload_ids.iter()
+            .map(|id| uploads.iter().find(|upload| upload.id == *id).map(Upload::attachment))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| UseCaseError::ValidationError("附件不存在或已被使用".to_string()))
+    }
+
+    /// 删除超过保留时长仍未作为附件的文件，返回清理的数量
+    #[instrument(skip_all, name = "cleanup_orphaned_uploads")]
+    pub async fn cleanup_orphaned_uploads(&self) -> UseCaseResult<u64> {
+        use crate::database::upload::delete_orphaned_uploads;
+
+        let uploaded_before = Utc::now() - Duration::hours(self.config.orphan_retention_hours);
+        let file_paths = delete_orphaned_uploads(&self.db_pool, uploaded_before, CLEANUP_BATCH_SIZE).await?;
+        for file_path in &file_paths {
+            if let Err(e) = tokio::fs::remove_file(file_path).await {
+                warn!(file_path = %file_path, error = %e, "Failed to remove orphaned upload file");
+            }
+        }
+
+        if !file_paths.is_empty() {
+            info!(count = %file_paths.len(), "Removed orphaned uploads");
+        }
+        Ok(file_paths.len() as u64)
+    }
+}
@@ -0,0 +1,195 @@
+use chrono::Utc;
+use serde_json::json;
+use tracing::{info, warn, error, instrument};
+use uuid::Uuid;
+
+use crate::cache::{RedisPool, data::DataCache};
+use crate::database::DbPool;
+use crate::models::audit::AuditEvent;
+use crate::models::auth::User;
+use crate::models::notification::NewNotification;
+use crate::models::route_command::RouteCommand;
+use crate::models::user_data::{UserData, UserDataReply, UserDataReplyRequest, UserDataStatus, UserDataThread};
+use crate::push::{PushHub, PushMessage};
+use crate::utils::validation::strip_html;
+use super::{UseCaseError, UseCaseResult, require_version, versioned_result};
+
+/// 收到回复时的推送事件名
+pub const USER_DATA_REPLY_EVENT: &str = "user_data_reply";
+
+/// 回复内容最大长度（字符）
+const MAX_REPLY_CHARS: usize = 2000;
+/// 用户查看自己的提交时返回的最大数量
+const MAX_OWN_SUBMISSIONS: i64 = 100;
+
+/// 用户数据跟进用例：管理员回复并更新处理状态，用户查看自己提交的处理进度
+pub struct UserDataReplyUseCase {
+    db_pool: DbPool,
+    redis: RedisPool,
+    push: PushHub,
+}
+
+impl UserDataReplyUseCase {
+    pub fn new(db_pool: DbPool, redis: RedisPool, push: PushHub) -> Self {
+        Self { db_pool, redis, push }
+    }
+
+    /// 管理员查看用户数据及回复
+    pub async fn thread(&self, user_data_id: Uuid) -> UseCaseResult<UserDataThread> {
+        let data = self.find(user_data_id).await?;
+        self.with_replies(data).await
+    }
+
+    /// 管理员回复用户数据，未指定状态时新提交转为处理中；通知提交邮箱对应的用户
+    #[instrument(skip_all, name = "reply_user_data", fields(user_data_id = %user_data_id))]
+    pub async fn reply(&self, admin_id: Uuid, user_data_id: Uuid, request: UserDataReplyRequest) -> UseCaseResult<UserDataReply> {
+        use crate::database::user_data_reply::insert_reply;
+        use crate::database::audit::record_audit_event;
+
+        let content = validate_reply(&request.content)?;
+        let data = self.find(user_data_id).await?;
+        let status = request.status.unwrap_or(match data.status {
+            UserDataStatus::New => UserDataStatus::InProgress,
+            status => status,
+        });
+
+        let reply = UserDataReply {
+            id: Uuid::new_v4(),
+            user_data_id,
+            admin_id: Some(admin_id),
+            content,
+            created_at: Utc::now(),
+        };
+        let reply = insert_reply(&self.db_pool, &reply, status).await?
+            .ok_or_else(|| UseCaseError::ValidationError("数据不存在".to_string()))?;
+        self.invalidate_cache(user_data_id).await;
+
+        let event = AuditEvent::new("user_data.replied", "user_data")
+            .actor(admin_id)
+            .target(user_data_id)
+            .details(json!({ "reply_id": reply.id, "status": status }));
+        if let Err(e) = record_audit_event(&self.db_pool, &event).await {
+            error!(error = %e, "Failed to record user data reply audit event");
+        }
+
+        self.notify_submitter(&data, &reply).await;
+        info!(reply_id = %reply.id, status = %status.as_str(), "Replied to user data");
+        Ok(reply)
+    }
+
+    /// 管理员更新处理状态，需提交读取时的版本号
+    pub async fn update_status(&self, admin_id: Uuid, user_data_id: Uuid, status: UserDataStatus, version: Option<i32>) -> UseCaseResult<UserData> {
+        use crate::database::user_data_reply::update_status;
+        use crate::database::audit::record_audit_event;
+
+        let expected_version = require_version(version)?;
+        let updated = update_status(&self.db_pool, admin_id, user_data_id, expected_version, status).await?;
+        versioned_result(updated, "数据不存在")?;
+        self.invalidate_cache(user_data_id).await;
+
+        let event = AuditEvent::new("user_data.status_changed", "user_data")
+            .actor(admin_id)
+            .target(user_data_id)
+            .details(json!({ "status": status }));
+        if let Err(e) = record_audit_event(&self.db_pool, &event).await {
+            error!(error = %e, "Failed to record user data status audit event");
+        }
+
+        info!(user_data_id = %user_data_id, status = %status.as_str(), "Updated user data status");
+        self.find(user_data_id).await
+    }
+
+    /// 用户查看以自己邮箱提交的用户数据
+    pub async fn list_own(&self, user: &User) -> UseCaseResult<Vec<UserData>> {
+        use crate::database::list_user_data_by_email;
+
+        Ok(list_user_data_by_email(&self.db_pool, &user.email, MAX_OWN_SUBMISSIONS).await?)
+    }
+
+    /// 用户查看自己提交的用户数据及回复，不是自己的提交按不存在处理
+    pub async fn own_thread(&self, user: &User, user_data_id: Uuid) -> UseCaseResult<UserDataThread> {
+        let data = self.find(user_data_id).await?;
+        if !is_submitter(&data, user) {
+            return Err(UseCaseError::ValidationError("数据不存在".to_string()));
+        }
+        self.with_replies(data).await
+    }
+
+    async fn find(&self, user_data_id: Uuid) -> UseCaseResult<UserData> {
+        use crate::database::find_user_data;
+
+        find_user_data(&self.db_pool, user_data_id).await?
+            .ok_or_else(|| UseCaseError::ValidationError("数据不存在".to_string()))
+    }
+
+    async fn with_replies(&self, data: UserData) -> UseCaseResult<UserDataThread> {
+        use crate::database::user_data_reply::list_replies;
+
+        let replies = list_replies(&self.db_pool, data.id).await?;
+        Ok(UserDataThread { data, replies })
+    }
+
+    // 状态变化后清除该数据、列表和搜索结果缓存
+    async fn invalidate_cache(&self, user_data_id: Uuid) {
+        let data_cache = DataCache::new(self.redis.clone());
+        if let Err(e) = data_cache.invalidate_user_data(user_data_id).await {
+            warn!(user_data_id = %user_data_id, error = %e, "Failed to invalidate user data cache");
+        }
+        if let Err(e) = data_cache.invalidate_search_results().await {
+            warn!(error = %e, "Failed to invalidate user data search cache");
+        }
+    }
+
+    // 提交邮箱对应有效用户时发送站内通知，并向在线客户端推送提示
+    async fn notify_submitter(&self, data: &UserData, reply: &UserDataReply) {
+        use crate::database::profile::find_active_user_by_email;
+        use crate::database::notification::create_notification;
+
+        let user = match find_active_user_by_email(&self.db_pool, &data.email).await {
+            Ok(Some(user)) => user,
+            Ok(None) => return,
+            Err(e) => {
+                error!(error = %e, "Failed to find user data submitter");
+                return;
+            }
+        };
+
+        let notification = NewNotification::new(user.id, "user_data_reply", "留言有新回复", &reply.content)
+            .data(json!({ "user_data_id": data.id, "reply_id": reply.id }));
+        if let Err(e) = create_notification(&self.db_pool, &notification).await {
+            error!(user_id = %user.id, error = %e, "Failed to create user data reply notification");
+        }
+
+        self.push.send(user.id, PushMessage::new(USER_DATA_REPLY_EVENT, RouteCommand::toast("您的留言有新回复")));
+    }
+}
+
+/// 校验回复内容：去除 HTML 后不能为空且不超过长度上限
+fn validate_reply(content: &str) -> UseCaseResult<String> {
+    let content = strip_html(content);
+    if content.is_empty() {
+        return Err(UseCaseError::ValidationError("回复内容不能为空".to_string()));
+    }
+    if content.chars().count() > MAX_REPLY_CHARS {
+        return Err(UseCaseError::ValidationError(format!("回复内容不能超过{}个字符", MAX_REPLY_CHARS)));
+    }
+    Ok(content)
+}
+
+/// 提交邮箱与用户邮箱一致（不区分大小写）时视为该用户的提交
+fn is_submitter(data: &UserData, user: &User) -> bool {
+    data.email.eq_ignore_ascii_case(&user.email)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_reply() {
+        assert_eq!(validate_reply(" <p>已收到，正在处理</p> ").unwrap(), "已收到，正在处理");
+        assert!(validate_reply("<br/>").is_err());
+        assert!(validate_reply(&"好".repeat(MAX_REPLY_CHARS)).is_ok());
+        assert!(validate_reply(&"好".repeat(MAX_REPLY_CHARS + 1)).is_err());
+    }
+}
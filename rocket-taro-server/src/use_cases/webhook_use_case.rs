@@ -0,0 +1,263 @@
+use chrono::{Duration, Utc};
+use rocket::futures::StreamExt;
+use serde_json::json;
+use tracing::{info, warn, error, instrument};
+use uuid::Uuid;
+
+use crate::config::WebhookConfig;
+use crate::database::DbPool;
+use crate::models::{
+    audit::AuditEvent,
+    auth::User,
+    webhook::{
+        CreateWebhookEndpointRequest, UpdateWebhookEndpointRequest, WebhookDelivery, WebhookDeliveryDetail,
+        WebhookDeliveryStatus, WebhookDeliveryTask, WebhookEndpoint, WebhookEndpointWithSecret,
+        retry_backoff, validate_endpoint_url, validate_event_types,
+    },
+};
+use crate::utils::pagination::{Cursor, Page, PageRequest};
+use crate::webhooks::{WebhookClient, delivery::generate_secret};
+use super::{UseCaseError, UseCaseResult};
+
+/// 描述最大长度
+const MAX_DESCRIPTION_LENGTH: usize = 200;
+
+/// Webhook 用例：接收端管理、事件分发与投递
+pub struct WebhookUseCase {
+    db_pool: DbPool,
+    config: WebhookConfig,
+}
+
+impl WebhookUseCase {
+    pub fn new(db_pool: DbPool, config: WebhookConfig) -> Self {
+        Self { db_pool, config }
+    }
+
+    /// 获取全部接收端
+    pub async fn list_endpoints(&self) -> UseCaseResult<Vec<WebhookEndpoint>> {
+        use crate::database::webhook::list_webhook_endpoints;
+
+        Ok(list_webhook_endpoints(&self.db_pool).await?)
+    }
+
+    /// 创建接收端，返回的密钥只显示这一次
+    #[instrument(skip_all, name = "execute_create_endpoint")]
+    pub async fn execute_create_endpoint(&self, admin: &User, request: &CreateWebhookEndpointRequest) -> UseCaseResult<WebhookEndpointWithSecret> {
+        use crate::database::webhook::create_webhook_endpoint;
+
+        let url = validate_endpoint_url(&request.url).map_err(UseCaseError::ValidationError)?;
+        let event_types = validate_event_types(&request.event_types).map_err(UseCaseError::ValidationError)?;
+        let description = validate_description(request.description.as_deref())?;
+
+        let secret = generate_secret();
+        let endpoint = create_webhook_endpoint(&self.db_pool, &url, description, &event_types, &secret, admin.id).await?;
+        info!(admin_id = %admin.id, endpoint_id = %endpoint.id, url = %endpoint.url, "Webhook endpoint created");
+
+        self.audit(admin, "webhook.endpoint_created", &endpoint, json!({ "url": endpoint.url, "event_types": endpoint.event_types })).await;
+        Ok(WebhookEndpointWithSecret { endpoint, secret })
+    }
+
+    /// 更新接收端地址、订阅事件或启用状态
+    #[instrument(skip_all, name = "execute_update_endpoint")]
+    pub async fn execute_update_endpoint(
+        &self,
+        admin: &User,
+        endpoint_id: Uuid,
+        request: &UpdateWebhookEndpointRequest,
+    ) -> UseCaseResult<WebhookEndpoint> {
+        use crate::database::webhook::update_webhook_endpoint;
+
+        let url = request.url.as_deref()
+            .map(validate_endpoint_url)
+            .transpose()
+            .map_err(UseCaseError::ValidationError)?;
+        let event_types = request.event_types.as_deref()
+            .map(validate_event_types)
+            .transpose()
+            .map_err(UseCaseError::ValidationError)?;
+        let description = validate_description(request.description.as_deref())?;
+
+        let endpoint = update_webhook_endpoint(
+            &self.db_pool,
+            endpoint_id,
+            url.as_deref(),
+            description,
+            event_types.as_deref(),
+            request.is_active,
+        ).await?
+            .ok_or_else(|| UseCaseError::ValidationError("Webhook 接收端不存在".to_string()))?;
+        info!(admin_id = %admin.id, endpoint_id = %endpoint.id, is_active = %endpoint.is_active, "Webhook endpoint updated");
+
+        self.audit(admin, "webhook.endpoint_updated", &endpoint, json!(request)).await;
+        Ok(endpoint)
+    }
+
+    /// 轮换接收端签名密钥
+    #[instrument(skip_all, name = "execute_rotate_secret")]
+    pub async fn execute_rotate_secret(&self, admin: &User, endpoint_id: Uuid) -> UseCaseResult<WebhookEndpointWithSecret> {
+        use crate::database::webhook::rotate_webhook_secret;
+
+        let secret = generate_secret();
+        let endpoint = rotate_webhook_secret(&self.db_pool, endpoint_id, &secret).await?
+            .ok_or_else(|| UseCaseError::ValidationError("Webhook 接收端不存在".to_string()))?;
+        info!(admin_id = %admin.id, endpoint_id = %endpoint.id, "Webhook secret rotated");
+
+        self.audit(admin, "webhook.secret_rotated", &endpoint, serde_json::Value::Null).await;
+        Ok(WebhookEndpointWithSecret { endpoint, secret })
+    }
+
+    /// 删除接收端，其投递记录一并删除
+    #[instrument(skip_all, name = "execute_delete_endpoint")]
+    pub async fn execute_delete_endpoint(&self, admin: &User, endpoint_id: Uuid) -> UseCaseResult<()> {
+        use crate::database::webhook::{find_webhook_endpoint, delete_webhook_endpoint};
+
+        let endpoint = find_webhook_endpoint(&self.db_pool, endpoint_id).await?
+            .ok_or_else(|| UseCaseError::ValidationError("Webhook 接收端不存在".to_string()))?;
+        delete_webhook_endpoint(&self.db_pool, endpoint_id).await?;
+        info!(admin_id = %admin.id, endpoint_id = %endpoint_id, "Webhook endpoint deleted");
+
+        self.audit(admin, "webhook.endpoint_deleted", &endpoint, json!({ "url": endpoint.url })).await;
+        Ok(())
+    }
+
+    /// 按状态筛选接收端的投递记录
+    pub async fn list_deliveries(
+        &self,
+        endpoint_id: Uuid,
+        statuses: &[WebhookDeliveryStatus],
+        page: &PageRequest,
+    ) -> UseCaseResult<Page<WebhookDelivery>> {
+        use crate::database::webhook::list_webhook_deliveries;
+
+        let rows = list_webhook_deliveries(&self.db_pool, endpoint_id, statuses, page).await?;
+        Ok(Page::from_rows(rows, page, |delivery| Cursor::new(delivery.created_at, delivery.id)))
+    }
+
+    /// 查询投递详情及尝试日志
+    pub async fn get_delivery(&self, delivery_id: Uuid) -> UseCaseResult<WebhookDeliveryDetail> {
+        use crate::database::webhook::get_webhook_delivery_detail;
+
+        get_webhook_delivery_detail(&self.db_pool, delivery_id).await?
+            .ok_or_else(|| UseCaseError::ValidationError("投递记录不存在".to_string()))
+    }
+
+    /// 重新投递已放弃的记录
+    #[instrument(skip_all, name = "execute_retry_delivery")]
+    pub async fn execute_retry_delivery(&self, admin: &User, delivery_id: Uuid) -> UseCaseResult<WebhookDelivery> {
+        use crate::database::webhook::retry_webhook_delivery;
+
+        let delivery = retry_webhook_delivery(&self.db_pool, delivery_id).await?
+            .ok_or_else(|| UseCaseError::BusinessLogicError("只能重试已放弃的投递记录".to_string()))?;
+        info!(admin_id = %admin.id, delivery_id = %delivery.id, "Webhook delivery requeued");
+        Ok(delivery)
+    }
+
+    /// 将发件箱中的新事件展开为投递记录
+    #[instrument(skip_all, name = "dispatch_pending_events")]
+    pub async fn dispatch_pending_events(&self) -> UseCaseResult<u64> {
+        use crate::database::webhook::dispatch_webhook_events;
+
+        let dispatched = dispatch_webhook_events(&self.db_pool, self.config.batch_size).await?;
+        if dispatched > 0 {
+            info!(count = %dispatched, "Dispatched webhook events");
+        }
+        Ok(dispatched)
+    }
+
+    /// 投递到期的记录，失败时按指数退避安排重试
+    #[instrument(skip_all, name = "deliver_due_webhooks")]
+    pub async fn deliver_due(&self, client: &WebhookClient) -> UseCaseResult<usize> {
+        use crate::database::webhook::claim_due_webhook_deliveries;
+
+        // 超过请求超时仍处于投递中的记录视为进程中断，重新领取
+        let stale_before = Utc::now() - Duration::seconds(self.config.request_timeout_secs as i64 * 2 + 60);
+        let tasks = claim_due_webhook_deliveries(&self.db_pool, stale_before, self.config.batch_size).await?;
+        let count = tasks.len();
+
+        rocket::futures::stream::iter(tasks)
+            .for_each_concurrent(self.config.concurrency.max(1), |task| async move {
+                let outcome = client.deliver(&task).await;
+                self.record_outcome(&task, &outcome).await;
+            })
+            .await;
+
+        Ok(count)
+    }
+
+    /// 清理超过保留期的事件及投递记录
+    pub async fn purge_history(&self) -> UseCaseResult<u64> {
+        use crate::database::webhook::purge_webhook_events;
+
+        let purged = purge_webhook_events(&self.db_pool, Utc::now() - Duration::days(self.config.retention_days)).await?;
+        if purged > 0 {
+            info!(count = %purged, "Purged webhook events");
+        }
+        Ok(purged)
+    }
+
+    async fn record_outcome(&self, task: &WebhookDeliveryTask, outcome: &crate::webhooks::DeliveryOutcome) {
+        use crate::database::webhook::record_webhook_delivery_attempt;
+
+        let attempt = task.delivery.attempts;
+        let (status, next_attempt_at) = if outcome.is_success() {
+            info!(delivery_id = %task.delivery.id, event_type = %task.event.event_type, "Webhook delivered");
+            (WebhookDeliveryStatus::Succeeded, None)
+        } else if attempt >= self.config.max_attempts {
+            error!(
+                delivery_id = %task.delivery.id,
+                attempts = %attempt,
+                error = ?outcome.error,
+                "Webhook delivery abandoned after max attempts"
+            );
+            (WebhookDeliveryStatus::Dead, None)
+        } else {
+            let delay = retry_backoff(
+                attempt,
+                Duration::seconds(self.config.retry_base_secs),
+                Duration::seconds(self.config.retry_max_secs),
+            );
+            warn!(
+                delivery_id = %task.delivery.id,
+                attempt = %attempt,
+                retry_in_secs = %delay.num_seconds(),
+                error = ?outcome.error,
+                "Webhook delivery failed, scheduling retry"
+            );
+            (WebhookDeliveryStatus::Pending, Some(Utc::now() + delay))
+        };
+
+        if let Err(e) = record_webhook_delivery_attempt(
+            &self.db_pool,
+            task.delivery.id,
+            attempt,
+            outcome.status_code,
+            outcome.error.as_deref(),
+            outcome.duration_ms,
+            status,
+            next_attempt_at,
+        ).await {
+            error!(delivery_id = %task.delivery.id, error = %e, "Failed to record webhook delivery attempt");
+        }
+    }
+
+    async fn audit(&self, admin: &User, action: &str, endpoint: &WebhookEndpoint, details: serde_json::Value) {
+        use crate::database::audit::record_audit_event;
+
+        let event = AuditEvent::new(action, "webhook_endpoint")
+            .actor(admin.id)
+            .target(endpoint.id)
+            .details(details);
+        if let Err(e) = record_audit_event(&self.db_pool, &event).await {
+            error!(endpoint_id = %endpoint.id, error = %e, "Failed to record webhook audit event");
+        }
+    }
+}
+
+fn validate_description(description: Option<&str>) -> UseCaseResult<Option<&str>> {
+    match description.map(str::trim) {
+        Some(description) if description.chars().count() > MAX_DESCRIPTION_LENGTH => {
+            Err(UseCaseError::ValidationError(format!("描述不能超过{}个字符", MAX_DESCRIPTION_LENGTH)))
+        }
+        description => Ok(description),
+    }
+}
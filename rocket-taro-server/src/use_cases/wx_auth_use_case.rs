@@ -3,59 +3,138 @@ use tracing::{info, warn, error};
 
 use crate::models::{
     route_command::RouteCommand,
-    wx_auth::{WxLoginRequest, WxLoginResponse},
-    auth::UserInfo,
+    wx_auth::WxLoginRequest,
+    business_results::LoginResult,
+    route_decision::{RouteDecision, RouteTrace},
 };
 use crate::database::{
     DbPool,
-    wx_auth::{code2session, find_user_by_openid, create_wx_user, update_wx_user_session, update_wx_user_profile},
-    auth::create_user_session,
+    wx_auth::{find_user_by_openid, create_wx_user, update_wx_user_session, update_wx_user_profile},
 };
 use crate::utils::wx_crypto::WxCrypto;
-use crate::config::{RouteConfig, Platform};
+use crate::config::{RouteConfig, ExperimentsConfig, Platform, WatermarkConfig, WatermarkMode};
+use crate::events::{AuthMethod, DomainEvent, EventBus};
+use crate::wechat::{WxApiClient, WxError};
+use super::UseCaseError;
+use super::login_timeouts::{Dependency, DependencyTimeout, LoginTimeouts};
+use super::route_command_generator::RouteCommandGenerator;
+use super::session_issuer::{RequestContext, SessionIssuer};
+
+/// 微信登录结果，登录成功时附带数据库中的完整用户和会话，路由层据此下发 Cookie 并返回；
+/// decisions 为生成指令时命中的路由决策，路由层记录指令下发和实验曝光
+pub struct WxLoginOutcome {
+    pub route_command: RouteCommand,
+    pub decisions: Vec<RouteDecision>,
+    pub login: Option<LoginResult>,
+}
+
+impl WxLoginOutcome {
+    pub fn failed(route_command: RouteCommand) -> Self {
+        Self { route_command, decisions: Vec::new(), login: None }
+    }
+}
 
 pub struct WxAuthUseCase {
     db_pool: DbPool,
     route_config: Arc<RouteConfig>,
+    experiments: ExperimentsConfig,
+    events: EventBus,
+    wx_api: Arc<dyn WxApiClient>,
+    watermark: WatermarkConfig,
+    timeouts: LoginTimeouts,
 }
 
 impl WxAuthUseCase {
-    pub fn new(db_pool: DbPool, route_config: Arc<RouteConfig>) -> Self {
+    pub fn new(db_pool: DbPool, route_config: Arc<RouteConfig>, events: EventBus, wx_api: Arc<dyn WxApiClient>) -> Self {
         Self {
             db_pool,
             route_config,
+            experiments: ExperimentsConfig::default(),
+            events,
+            wx_api,
+            watermark: WatermarkConfig::default(),
+            timeouts: LoginTimeouts::default(),
         }
     }
 
+    pub fn with_watermark(mut self, watermark: WatermarkConfig) -> Self {
+        self.watermark = watermark;
+        self
+    }
+
+    /// 设置新用户欢迎提示参与的 A/B 实验，未设置时所有用户走对照组的流程
+    pub fn with_experiments(mut self, experiments: ExperimentsConfig) -> Self {
+        self.experiments = experiments;
+        self
+    }
+
+    /// 设置访问微信接口和数据库的时间预算，未设置时使用默认预算
+    pub fn with_timeouts(mut self, timeouts: LoginTimeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// 依赖超时时的提示
+    fn timeout_command(&self, timeout: DependencyTimeout, platform: Platform) -> RouteCommand {
+        RouteCommandGenerator::generate_error_route_command(&timeout.to_string(), Some(timeout.dependency.error_code()), &self.route_config, platform, &mut RouteTrace::default())
+    }
+
     pub async fn handle_wx_login(
         &self,
         wx_login_req: WxLoginRequest,
         platform: Platform,
-    ) -> Result<RouteCommand, String> {
+        context: RequestContext,
+    ) -> Result<WxLoginOutcome, WxError> {
         info!("处理微信登录请求, platform: {:?}", platform);
 
         // 1. 调用微信API换取openid
-        let wx_response = match self.call_wx_code2session(&wx_login_req.code).await {
+        let code2session = self.timeouts.run(Dependency::Wechat, self.wx_api.code2session(&wx_login_req.code)).await;
+        let wx_response = match code2session.unwrap_or_else(|timeout| Err(timeout.into())) {
             Ok(response) => response,
+            Err(WxError::InvalidCode) => {
+                warn!("微信登录 code 无效或已使用");
+                return Ok(WxLoginOutcome::failed(RouteCommand::alert("登录失败", "登录凭证已失效，请重新登录")));
+            }
+            Err(WxError::Timeout(timeout)) => return Ok(WxLoginOutcome::failed(self.timeout_command(timeout, platform))),
             Err(e) => {
-                error!("微信API调用失败: {}", e);
-                return Ok(RouteCommand::alert("登录失败", "微信授权失败，请重试"));
+                error!(kind = e.kind(), "微信API调用失败: {}", e);
+                return Ok(WxLoginOutcome::failed(RouteCommand::alert("登录失败", "微信授权失败，请重试")));
             }
         };
 
-        // 2. 查找或创建用户
-        let mut wx_user = match self.find_or_create_wx_user(
+        // 2. 查找或创建用户，找回密码模式下不创建新用户
+        let (mut wx_user, is_new_user) = match self.timeouts.run(Dependency::Postgres, self.find_or_create_wx_user(
             &wx_response.openid,
             wx_response.unionid.as_deref(),
             &wx_response.session_key,
-        ).await {
-            Ok(user) => user,
+            !wx_login_req.recover_password,
+        )).await.unwrap_or_else(|timeout| Err(timeout.into())) {
+            Ok(Some(found)) => found,
+            Ok(None) => {
+                info!("找回密码的微信未绑定任何账户");
+                return Ok(WxLoginOutcome::failed(RouteCommand::alert("找回密码失败", "该微信未绑定任何账户，请使用账号密码登录或直接注册")));
+            }
+            Err(WxError::Timeout(timeout)) => return Ok(WxLoginOutcome::failed(self.timeout_command(timeout, platform))),
             Err(e) => {
                 error!("用户处理失败: {}", e);
-                return Ok(RouteCommand::alert("登录失败", "用户信息处理失败"));
+                return Ok(WxLoginOutcome::failed(RouteCommand::alert("登录失败", "用户信息处理失败")));
             }
         };
 
+        // 已注销的账户不允许再次登录
+        if !wx_user.is_active {
+            warn!("已停用或注销的微信用户尝试登录: {}", wx_user.id);
+            return Ok(WxLoginOutcome::failed(RouteCommand::alert("登录失败", "该账户已注销或被停用")));
+        }
+
+        if is_new_user {
+            self.events.publish(DomainEvent::UserRegistered {
+                user: wx_user.clone().into(),
+                method: AuthMethod::Wechat,
+                ip_address: context.ip_address,
+            }).await;
+        }
+
         // 3. 如果提供了用户信息的加密数据，进行解密和验证
         if let (Some(encrypted_data), Some(iv), Some(signature), Some(raw_data)) = (
             &wx_login_req.encrypted_data,
@@ -78,79 +157,62 @@ impl WxAuthUseCase {
                 },
                 Err(e) => {
                     // 解密失败不应该影响登录流程，只记录警告
-                    warn!("用户信息解密失败，但不影响登录: {}", e);
+                    warn!(kind = e.kind(), "用户信息解密失败，但不影响登录: {}", e);
                 }
             }
         } else {
             info!("未提供用户信息加密数据，跳过用户信息更新");
         }
 
-        // 4. 创建系统会话
-        let session = match create_user_session(
-            &self.db_pool,
-            wx_user.id,
-            Some("WeChat Mini Program".to_string()),
-            None,
-        ).await {
+        // 4. 读取持久化后的完整用户（资料更新会改变版本号），读取失败时使用内存中的数据
+        let active_user = self.timeouts.run(Dependency::Postgres, crate::database::profile::find_active_user(&self.db_pool, wx_user.id)).await
+            .map_err(WxError::from)
+            .and_then(|result| result.map_err(WxError::from));
+        let user = match active_user {
+            Ok(Some(user)) => user,
+            Ok(None) => {
+                warn!("微信用户登录过程中账户被停用: {}", wx_user.id);
+                return Ok(WxLoginOutcome::failed(RouteCommand::alert("登录失败", "该账户已注销或被停用")));
+            }
+            Err(e) => {
+                warn!("读取微信用户失败，使用登录过程中的用户数据: {}", e);
+                wx_user.into()
+            }
+        };
+
+        // 5. 签发会话（登录日志、缓存写入、登录事件由 SessionIssuer 统一处理）
+        let issuer = SessionIssuer::new(self.db_pool.clone(), self.events.clone()).with_timeouts(self.timeouts.clone());
+        let session = match issuer.issue(&user, AuthMethod::Wechat, &context).await {
             Ok(session) => session,
+            Err(UseCaseError::TimeoutError(timeout)) => return Ok(WxLoginOutcome::failed(self.timeout_command(timeout, platform))),
             Err(e) => {
                 error!("创建会话失败: {}", e);
-                return Ok(RouteCommand::alert("登录失败", "会话创建失败"));
+                return Ok(WxLoginOutcome::failed(RouteCommand::alert("登录失败", "会话创建失败")));
             }
         };
 
-        info!("微信用户登录成功: {}", wx_user.username);
-
-        // 5. 生成路由指令
-        // 构建用户信息
-        let regular_user: crate::models::auth::User = wx_user.clone().into();
-        let user_info = UserInfo::from(regular_user);
-        
-        // 构建响应数据
-        let wx_login_response = WxLoginResponse {
-            user: user_info,
-            session_token: session.session_token,
-            expires_at: session.expires_at,
-        };
+        info!("微信用户登录成功: {}", user.username);
 
-        // 生成包含用户数据和导航的复合指令
-        let user_data_command = RouteCommand::ProcessData {
-            data_type: "user".to_string(),
-            data: serde_json::to_value(&wx_login_response).unwrap(),
-            merge: Some(false),
-        };
+        // 6. 生成路由指令，新用户、首次登录的欢迎提示按登录引导实验分组；会话令牌只通过 Cookie 和响应数据下发，不写入指令
+        let (route_command, decisions) = RouteCommandGenerator::traced(|trace| {
+            RouteCommandGenerator::generate_wx_login_route_command(&user, is_new_user, &self.route_config, &self.experiments, platform, trace)
+        });
 
-        // 获取主页路由
-        let home_route = self.route_config.get_route("home.main", platform)
-            .unwrap_or_else(|| "/pages/home/home".to_string());
-        let navigate_command = RouteCommand::NavigateTo {
-            path: home_route,
-            params: None,
-            replace: Some(true),
-            fallback_path: Some("/pages/home/home".to_string()),
-        };
-
-        Ok(RouteCommand::Sequence {
-            commands: vec![user_data_command, navigate_command],
-            stop_on_error: Some(true),
+        Ok(WxLoginOutcome {
+            route_command,
+            decisions,
+            login: Some(LoginResult::new(user, session)),
         })
     }
 
-    async fn call_wx_code2session(&self, code: &str) -> Result<crate::models::wx_auth::Code2SessionResponse, String> {
-        // 从配置读取微信小程序信息
-        let app_id = "wx2078fa60851884ca";
-        let app_secret = "b6727ca843ad05db752c1349ebcad8c9";
-        
-        code2session(app_id, app_secret, code).await
-    }
-
     async fn find_or_create_wx_user(
         &self,
         openid: &str,
         unionid: Option<&str>,
         session_key: &str,
-    ) -> Result<crate::models::wx_auth::WxUser, String> {
-        // 先查找现有用户
+        create_if_missing: bool,
+    ) -> Result<Option<(crate::models::wx_auth::WxUser, bool)>, WxError> {
+        // 先查找现有用户，返回值中的布尔值表示是否为新建用户；不允许创建且用户不存在时返回 None
         match find_user_by_openid(&self.db_pool, openid).await {
             Ok(Some(mut user)) => {
                 // 更新session_key
@@ -158,17 +220,15 @@ impl WxAuthUseCase {
                     warn!("更新用户session失败: {}", e);
                 }
                 user.wx_session_key = Some(session_key.to_string());
-                Ok(user)
+                Ok(Some((user, false)))
             },
+            Ok(None) if !create_if_missing => Ok(None),
             Ok(None) => {
                 // 创建新用户
-                create_wx_user(&self.db_pool, openid, unionid, session_key)
-                    .await
-                    .map_err(|e| format!("创建微信用户失败: {}", e))
+                let user = create_wx_user(&self.db_pool, openid, unionid, session_key).await?;
+                Ok(Some((user, true)))
             },
-            Err(e) => {
-                Err(format!("查找用户失败: {}", e))
-            }
+            Err(e) => Err(e.into()),
         }
     }
 
@@ -180,33 +240,31 @@ impl WxAuthUseCase {
         signature: &str,
         raw_data: &str,
         session_key: &str,
-    ) -> Result<(), String> {
+    ) -> Result<(), WxError> {
         info!("开始处理加密的用户信息");
 
         // 1. 验证数据签名
-        if !WxCrypto::verify_signature(raw_data, session_key, signature)? {
-            return Err("数据签名验证失败".to_string());
-        }
+        WxCrypto::verify_signature(raw_data, session_key, signature)?;
 
         // 2. 解密用户数据
         let decrypted_user_info = WxCrypto::decrypt_user_info(encrypted_data, session_key, iv)?;
 
-        // 3. 验证水印
-        let app_id = "wx2078fa60851884ca"; // 应该从配置读取
-        if !WxCrypto::verify_watermark(&decrypted_user_info, app_id)? {
-            warn!("水印验证失败，但继续处理用户信息");
+        // 3. 验证水印，严格模式下拒绝 AppID 不匹配或已过期的加密数据
+        let now = chrono::Utc::now().timestamp();
+        if let Err(e) = WxCrypto::verify_watermark(&decrypted_user_info.watermark, self.wx_api.app_id(), &self.watermark, now) {
+            if self.watermark.mode == WatermarkMode::Strict {
+                return Err(e.into());
+            }
+            warn!("水印验证失败，宽松模式下继续处理用户信息: {}", e);
         }
 
         // 4. 更新用户信息到数据库
-        if let Err(e) = update_wx_user_profile(
+        self.timeouts.run(Dependency::Postgres, update_wx_user_profile(
             &self.db_pool,
             wx_user.id,
             &decrypted_user_info.nick_name,
             &decrypted_user_info.avatar_url,
-        ).await {
-            error!("更新用户信息到数据库失败: {}", e);
-            return Err(format!("更新用户信息失败: {}", e));
-        }
+        )).await??.ok_or(WxError::UserNotFound)?;
 
         // 5. 更新内存中的用户对象
         wx_user.full_name = Some(decrypted_user_info.nick_name);
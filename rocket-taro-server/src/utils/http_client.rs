@@ -0,0 +1,152 @@
+//! 对外 HTTP 请求的共享客户端：连接池、连接与请求超时、代理统一配置，
+//! 幂等请求遇到超时、429 或 5xx 时按随机退避重试
+
+use rand::Rng;
+use reqwest::{IntoUrl, Method, RequestBuilder, Response, StatusCode};
+use reqwest::{NoProxy, Proxy};
+use std::time::Duration;
+use tracing::warn;
+
+use crate::config::HttpClientConfig;
+
+/// 共享的 HTTP 客户端，作为 Rocket 托管状态，克隆后共用同一个连接池
+#[derive(Clone)]
+pub struct HttpClient {
+    client: reqwest::Client,
+    config: HttpClientConfig,
+}
+
+impl HttpClient {
+    /// 代理地址无效时不使用代理并记录警告
+    pub fn from_config(config: HttpClientConfig) -> Self {
+        let mut builder = reqwest::Client::builder()
+            .connect_timeout(Duration::from_secs(config.connect_timeout_secs))
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .pool_idle_timeout(Duration::from_secs(config.pool_idle_timeout_secs))
+            .pool_max_idle_per_host(config.pool_max_idle_per_host)
+            // Webhook 地址由用户配置，不跟随重定向，避免被引导到内网地址
+            .redirect(reqwest::redirect::Policy::none());
+        if let Some(proxy) = config.proxy.as_deref().filter(|proxy| !proxy.is_empty()) {
+            match Proxy::all(proxy) {
+                Ok(proxy) => {
+                    let no_proxy = config.no_proxy.as_deref().and_then(NoProxy::from_string);
+                    builder = builder.proxy(proxy.no_proxy(no_proxy));
+                }
+                Err(e) => warn!("Invalid http_client.proxy, sending requests without it: {}", e),
+            }
+        }
+        let client = builder.build().unwrap_or_else(|e| {
+            warn!("Failed to build HTTP client from [http_client], using defaults: {}", e);
+            reqwest::Client::default()
+        });
+        Self { client, config }
+    }
+
+    pub fn get(&self, url: impl IntoUrl) -> RequestBuilder {
+        self.client.get(url)
+    }
+
+    pub fn post(&self, url: impl IntoUrl) -> RequestBuilder {
+        self.client.post(url)
+    }
+
+    pub fn request(&self, method: Method, url: impl IntoUrl) -> RequestBuilder {
+        self.client.request(method, url)
+    }
+
+    /// 发送请求。GET、PUT、DELETE 等幂等请求在超时、429 或 5xx 时重试，
+    /// 其他请求只在连接建立失败（请求未发出）时重试；最后一次的结果原样返回
+    pub async fn send(&self, request: RequestBuilder) -> reqwest::Result<Response> {
+        let request = request.build()?;
+        let idempotent = is_idempotent(request.method());
+        let mut attempt = 0;
+        loop {
+            // 流式请求体无法复制，只发送一次
+            let Some(current) = request.try_clone().filter(|_| attempt < self.config.max_retries) else {
+                return self.client.execute(request).await;
+            };
+            let reason = match self.client.execute(current).await {
+                Ok(response) if idempotent && is_retryable_status(response.status()) => {
+                    format!("HTTP {}", response.status().as_u16())
+                }
+                Err(e) if e.is_connect() || (idempotent && e.is_timeout()) => e.without_url().to_string(),
+                result => return result,
+            };
+
+            let delay = self.config.retry_delay(attempt, rand::thread_rng().gen());
+            // 地址中可能带有密钥（如微信 AppSecret），只记录主机名
+            warn!(
+                method = %request.method(),
+                host = request.url().host_str().unwrap_or_default(),
+                attempt = attempt + 1,
+                delay_ms = delay.as_millis() as u64,
+                "Outbound HTTP request failed, retrying: {}", reason
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+}
+
+/// 按 HTTP 语义可以安全重发的请求方法
+fn is_idempotent(method: &Method) -> bool {
+    matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS | Method::PUT | Method::DELETE)
+}
+
+/// 对方暂时无法处理、稍后重试可能成功的状态码
+fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// 依次返回给定状态码的本地 HTTP 服务，返回地址和已处理的请求数
+    async fn serve(statuses: Vec<u16>) -> (String, tokio::task::JoinHandle<usize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            for (handled, status) in statuses.into_iter().enumerate() {
+                let Ok(Ok((mut stream, _))) = tokio::time::timeout(Duration::from_millis(500), listener.accept()).await else {
+                    return handled;
+                };
+                let mut buffer = [0u8; 4096];
+                let _ = stream.read(&mut buffer).await;
+                let response = format!("HTTP/1.1 {} Status\r\ncontent-length: 0\r\nconnection: close\r\n\r\n", status);
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+            usize::MAX
+        });
+        (address, handle)
+    }
+
+    fn client(max_retries: u32) -> HttpClient {
+        HttpClient::from_config(HttpClientConfig { max_retries, retry_base_ms: 1, retry_max_ms: 1, ..HttpClientConfig::default() })
+    }
+
+    #[test]
+    fn test_retry_rules() {
+        assert!(is_idempotent(&Method::GET));
+        assert!(!is_idempotent(&Method::POST));
+        assert!(is_retryable_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(!is_retryable_status(StatusCode::BAD_REQUEST));
+    }
+
+    #[tokio::test]
+    async fn test_send_retries_idempotent_requests_only() {
+        let (address, server) = serve(vec![503, 502, 200]).await;
+        let http = client(2);
+        let response = http.send(http.get(&address)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(server.await.unwrap(), usize::MAX);
+
+        let (address, server) = serve(vec![503, 200]).await;
+        let response = http.send(http.post(&address).body("{}")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(server.await.unwrap(), 1);
+    }
+}
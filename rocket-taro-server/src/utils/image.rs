@@ -0,0 +1,77 @@
+//! 头像图片处理：按 EXIF 方向摆正后丢弃全部元数据，按中心裁剪缩放为正方形，输出 WebP
+
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader, Limits};
+use std::io::Cursor;
+
+/// 处理后的头像
+pub struct ProcessedAvatar {
+    /// 无损 WebP，不含 EXIF 等元数据
+    pub webp: Vec<u8>,
+    /// 送内容审核的 PNG（部分审核服务不支持 WebP）
+    pub png: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// 解码原图（只支持 JPEG、PNG、WebP，宽高不超过 max_dimension），处理为 size × size 的头像
+pub fn process_avatar(source: &[u8], size: u32, max_dimension: u32) -> Result<ProcessedAvatar, String> {
+    let mut reader = ImageReader::new(Cursor::new(source))
+        .with_guessed_format()
+        .map_err(|e| format!("无法读取图片: {}", e))?;
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(max_dimension);
+    limits.max_image_height = Some(max_dimension);
+    reader.limits(limits);
+
+    let mut decoder = reader.into_decoder().map_err(|e| format!("不支持的图片格式: {}", e))?;
+    // 手机拍摄的照片通过 EXIF 记录方向，丢弃元数据前先摆正
+    let orientation = decoder.orientation().map_err(|e| format!("无法读取图片方向: {}", e))?;
+    let mut image = DynamicImage::from_decoder(decoder).map_err(|e| format!("无法解码图片: {}", e))?;
+    image.apply_orientation(orientation);
+
+    // 重新编码只写入像素数据，EXIF（含拍摄位置）不会保留
+    let image = DynamicImage::ImageRgba8(image.resize_to_fill(size, size, FilterType::Lanczos3).to_rgba8());
+    let encode = |format: ImageFormat| {
+        let mut buffer = Cursor::new(Vec::new());
+        image.write_to(&mut buffer, format).map(|_| buffer.into_inner()).map_err(|e| format!("无法编码图片: {}", e))
+    };
+    Ok(ProcessedAvatar {
+        webp: encode(ImageFormat::WebP)?,
+        png: encode(ImageFormat::Png)?,
+        width: image.width(),
+        height: image.height(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage};
+
+    fn jpeg(width: u32, height: u32) -> Vec<u8> {
+        let image = RgbImage::from_fn(width, height, |x, y| Rgb([(x % 256) as u8, (y % 256) as u8, 128]));
+        let mut buffer = Cursor::new(Vec::new());
+        DynamicImage::ImageRgb8(image).write_to(&mut buffer, ImageFormat::Jpeg).unwrap();
+        buffer.into_inner()
+    }
+
+    #[test]
+    fn test_process_avatar() {
+        let avatar = process_avatar(&jpeg(600, 400), 256, 8192).unwrap();
+        assert_eq!((avatar.width, avatar.height), (256, 256));
+        assert_eq!(&avatar.webp[..4], b"RIFF");
+        assert_eq!(&avatar.webp[8..12], b"WEBP");
+        assert!(!avatar.webp.windows(4).any(|chunk| chunk == b"EXIF"));
+
+        let decoded = image::load_from_memory(&avatar.webp).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (256, 256));
+    }
+
+    #[test]
+    fn test_process_avatar_rejects_invalid_images() {
+        assert!(process_avatar(b"not an image", 256, 8192).is_err());
+        // 超过宽高上限时不解码
+        assert!(process_avatar(&jpeg(600, 400), 256, 500).is_err());
+    }
+}
@@ -1 +1,5 @@
-pub mod wx_crypto;
\ No newline at end of file
+pub mod wx_crypto;
+pub mod redact;
+pub mod http_client;
+pub mod pagination;
+pub mod validation;pub mod image;
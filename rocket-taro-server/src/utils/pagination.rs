@@ -0,0 +1,145 @@
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD as BASE64_URL};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// 游标分页位置：按 (created_at, id) 倒序排列时上一页最后一条记录
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor {
+    pub created_at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl Cursor {
+    pub fn new(created_at: DateTime<Utc>, id: Uuid) -> Self {
+        Self { created_at, id }
+    }
+
+    /// 编码为不透明的游标字符串
+    pub fn encode(&self) -> String {
+        BASE64_URL.encode(format!("{}|{}", self.created_at.timestamp_micros(), self.id))
+    }
+
+    /// 解析客户端传回的游标字符串
+    pub fn decode(token: &str) -> Result<Self, String> {
+        let invalid = || "无效的分页游标".to_string();
+
+        let raw = BASE64_URL.decode(token).map_err(|_| invalid())?;
+        let raw = String::from_utf8(raw).map_err(|_| invalid())?;
+        let (micros, id) = raw.split_once('|').ok_or_else(invalid)?;
+
+        let micros: i64 = micros.parse().map_err(|_| invalid())?;
+        let created_at = DateTime::from_timestamp_micros(micros).ok_or_else(invalid)?;
+        let id = Uuid::parse_str(id).map_err(|_| invalid())?;
+
+        Ok(Self { created_at, id })
+    }
+}
+
+/// 分页请求参数
+#[derive(Debug, Clone, Copy)]
+pub struct PageRequest {
+    pub cursor: Option<Cursor>,
+    pub limit: i64,
+}
+
+impl PageRequest {
+    /// 解析查询参数，limit 限制在 1..=max_limit 之间
+    pub fn parse(cursor: Option<&str>, limit: Option<i64>, default_limit: i64, max_limit: i64) -> Result<Self, String> {
+        let cursor = match cursor {
+            Some(token) if !token.is_empty() => Some(Cursor::decode(token)?),
+            _ => None,
+        };
+
+        Ok(Self {
+            cursor,
+            limit: limit.unwrap_or(default_limit).clamp(1, max_limit),
+        })
+    }
+
+    /// 查询时多取一条，用于判断是否还有下一页
+    pub fn fetch_limit(&self) -> i64 {
+        self.limit + 1
+    }
+
+    /// 游标中的时间（无游标时为 None）
+    pub fn cursor_created_at(&self) -> Option<DateTime<Utc>> {
+        self.cursor.map(|cursor| cursor.created_at)
+    }
+
+    /// 游标中的ID（无游标时为 None）
+    pub fn cursor_id(&self) -> Option<Uuid> {
+        self.cursor.map(|cursor| cursor.id)
+    }
+}
+
+/// 分页结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// 下一页的游标，没有更多数据时为 None
+    pub next_cursor: Option<String>,
+    pub has_more: bool,
+}
+
+impl<T> Page<T> {
+    /// 由按 fetch_limit 查询到的结果构造分页，cursor_of 用于取出记录的排序键
+    pub fn from_rows(mut rows: Vec<T>, request: &PageRequest, cursor_of: impl Fn(&T) -> Cursor) -> Self {
+        let has_more = rows.len() as i64 > request.limit;
+        rows.truncate(request.limit as usize);
+
+        let next_cursor = if has_more {
+            rows.last().map(|last| cursor_of(last).encode())
+        } else {
+            None
+        };
+
+        Self {
+            items: rows,
+            next_cursor,
+            has_more,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let cursor = Cursor::new(DateTime::from_timestamp_micros(1_700_000_000_123_456).unwrap(), Uuid::new_v4());
+        assert_eq!(Cursor::decode(&cursor.encode()).unwrap(), cursor);
+    }
+
+    #[test]
+    fn test_invalid_cursor() {
+        assert!(Cursor::decode("not a cursor").is_err());
+        assert!(Cursor::decode(&BASE64_URL.encode("123|not-a-uuid")).is_err());
+        assert!(PageRequest::parse(Some("%%%"), None, 20, 100).is_err());
+    }
+
+    #[test]
+    fn test_page_from_rows() {
+        let request = PageRequest::parse(None, Some(2), 20, 100).unwrap();
+        let rows: Vec<Cursor> = (0..3)
+            .map(|i| Cursor::new(DateTime::from_timestamp(1_700_000_000 - i, 0).unwrap(), Uuid::new_v4()))
+            .collect();
+
+        let page = Page::from_rows(rows.clone(), &request, |row| *row);
+        assert_eq!(page.items.len(), 2);
+        assert!(page.has_more);
+        assert_eq!(Cursor::decode(page.next_cursor.as_deref().unwrap()).unwrap(), rows[1]);
+
+        let page = Page::from_rows(rows[..2].to_vec(), &request, |row| *row);
+        assert!(!page.has_more);
+        assert!(page.next_cursor.is_none());
+    }
+
+    #[test]
+    fn test_limit_is_clamped() {
+        assert_eq!(PageRequest::parse(None, Some(0), 20, 100).unwrap().limit, 1);
+        assert_eq!(PageRequest::parse(None, Some(1000), 20, 100).unwrap().limit, 100);
+        assert_eq!(PageRequest::parse(None, None, 20, 100).unwrap().limit, 20);
+    }
+}
@@ -0,0 +1,96 @@
+//! 日志脱敏：密钥类字段在日志中统一替换，微信标识符只保留首尾几位
+
+use std::fmt;
+use tracing::field::Field;
+use tracing_subscriber::field::MakeExt;
+use tracing_subscriber::fmt::FormatFields;
+use tracing_subscriber::fmt::format::{Writer, debug_fn};
+
+/// 替换敏感字段值的占位符
+pub const REDACTED: &str = "[REDACTED]";
+
+/// 按单词匹配的敏感字段名，如 `password`、`session_token`、`webhook.secret`
+const SENSITIVE_WORDS: &[&str] = &["password", "secret", "token", "signature", "authorization", "cookie"];
+
+/// 完整匹配的敏感字段名
+const SENSITIVE_NAMES: &[&str] = &["session_key", "encrypted_data", "raw_data"];
+
+/// 字段名是否表示密钥、令牌或加密数据
+pub fn is_sensitive_field(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    SENSITIVE_NAMES.contains(&name.as_str())
+        || name.split(['.', '_']).any(|word| SENSITIVE_WORDS.contains(&word))
+}
+
+/// 标识符脱敏（openid、unionid 等）：保留前 3 位和后 3 位，较短时全部隐藏
+pub fn mask(value: &str) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    if chars.len() < 10 {
+        return "***".to_string();
+    }
+    let head: String = chars[..3].iter().collect();
+    let tail: String = chars[chars.len() - 3..].iter().collect();
+    format!("{}***{}", head, tail)
+}
+
+/// 日志字段格式化：敏感字段只输出字段名，其余与默认格式一致
+pub fn redacting_fields() -> impl for<'writer> FormatFields<'writer> + Send + Sync + 'static {
+    debug_fn(|writer: &mut Writer<'_>, field: &Field, value: &dyn fmt::Debug| match field.name() {
+        "message" => write!(writer, "{:?}", value),
+        name if is_sensitive_field(name) => write!(writer, "{}={}", name, REDACTED),
+        name => write!(writer, "{}={:?}", name, value),
+    })
+    .delimited(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(bytes);
+            Ok(bytes.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_sensitive_fields_and_mask() {
+        assert!(is_sensitive_field("session_token"));
+        assert!(is_sensitive_field("webhook.secret"));
+        assert!(is_sensitive_field("session_key"));
+        assert!(is_sensitive_field("Authorization"));
+        assert!(!is_sensitive_field("user_id"));
+        assert!(!is_sensitive_field("cache_key"));
+
+        assert_eq!(mask("oAbcdefghijklmnopqrstuvwxyz1"), "oAb***yz1");
+        assert_eq!(mask("short"), "***");
+    }
+
+    #[test]
+    fn test_redacting_fields() {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .fmt_fields(redacting_fields())
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(user_id = 42, session_token = "abc123", "Session issued");
+        });
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        assert!(output.contains("Session issued user_id=42 session_token=[REDACTED]"), "{}", output);
+        assert!(!output.contains("abc123"));
+    }
+}
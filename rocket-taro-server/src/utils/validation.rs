@@ -0,0 +1,157 @@
+/// 邮箱地址最大长度（RFC 5321）
+const MAX_EMAIL_LENGTH: usize = 254;
+/// 邮箱本地部分最大长度
+const MAX_LOCAL_PART_LENGTH: usize = 64;
+/// 域名单个标签最大长度
+const MAX_DOMAIN_LABEL_LENGTH: usize = 63;
+
+/// 解析邮箱地址（RFC 5322 dot-atom 形式，不支持引号和 IP 地址域名），返回域名转为小写后的地址
+pub fn parse_email(email: &str) -> Option<String> {
+    let email = email.trim();
+    if email.len() > MAX_EMAIL_LENGTH {
+        return None;
+    }
+    let (local, domain) = email.rsplit_once('@')?;
+    if !is_dot_atom(local) || local.len() > MAX_LOCAL_PART_LENGTH || !is_domain(domain) {
+        return None;
+    }
+    Some(format!("{}@{}", local, domain.to_ascii_lowercase()))
+}
+
+// 本地部分：由 atext 字符组成，点不能在开头、结尾或连续出现
+fn is_dot_atom(local: &str) -> bool {
+    local.split('.').all(|atom| !atom.is_empty() && atom.chars().all(is_atext))
+}
+
+fn is_atext(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$%&'*+-/=?^_`{|}~".contains(c)
+}
+
+// 域名：至少两个标签，标签由字母数字和连字符组成且不以连字符开头或结尾，顶级域名为字母
+fn is_domain(domain: &str) -> bool {
+    let labels: Vec<&str> = domain.split('.').collect();
+    labels.len() >= 2
+        && labels.iter().all(|label| {
+            !label.is_empty()
+                && label.len() <= MAX_DOMAIN_LABEL_LENGTH
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+        && labels.last().is_some_and(|tld| tld.len() >= 2 && tld.chars().all(|c| c.is_ascii_alphabetic()))
+}
+
+/// 将手机号规范化为 E.164 格式：中国大陆手机号可省略国家码（支持 86、+86、0086 前缀），
+/// 其他国家和地区需以 + 开头
+pub fn normalize_phone(phone: &str) -> Option<String> {
+    let compact: String = phone.chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '(' | ')'))
+        .collect();
+
+    let (international, digits) = match compact.strip_prefix('+') {
+        Some(rest) => (true, rest),
+        None => match compact.strip_prefix("00") {
+            Some(rest) => (true, rest),
+            None => (false, compact.as_str()),
+        },
+    };
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let national = match digits.strip_prefix("86") {
+        Some(national) if international || national.len() == 11 => national,
+        _ if international => {
+            // 其他国家和地区只检查 E.164 长度（国家码加号码最多15位）
+            return (8..=15).contains(&digits.len()).then(|| format!("+{}", digits));
+        }
+        _ => digits,
+    };
+    is_cn_mobile(national).then(|| format!("+86{}", national))
+}
+
+// 中国大陆手机号：11位，以 1 开头，第二位为 3-9
+fn is_cn_mobile(national: &str) -> bool {
+    let bytes = national.as_bytes();
+    bytes.len() == 11 && bytes[0] == b'1' && (b'3'..=b'9').contains(&bytes[1])
+}
+
+/// 去除 HTML 标签和注释，保留标签之间的文本并去除首尾空白
+pub fn strip_html(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        output.push_str(&rest[..start]);
+        let tag = &rest[start..];
+        // 后面不是标签名、结束标签、注释或声明时按普通字符保留，如 "1 < 2"
+        let is_tag = tag[1..].starts_with(|c: char| c.is_ascii_alphabetic() || c == '/' || c == '!');
+        if !is_tag {
+            output.push('<');
+            rest = &tag[1..];
+            continue;
+        }
+        let end = if tag.starts_with("<!--") {
+            tag.find("-->").map(|i| i + 3)
+        } else if let Some(name) = ["script", "style"].into_iter().find(|name| starts_with_tag(tag, name)) {
+            // 脚本和样式的内容一并去除
+            let closing = format!("</{}", name);
+            tag.to_ascii_lowercase().find(&closing)
+                .and_then(|i| tag[i..].find('>').map(|j| i + j + 1))
+        } else {
+            tag.find('>').map(|i| i + 1)
+        };
+        match end {
+            Some(end) => rest = &tag[end..],
+            // 未闭合的标签连同后面的内容一起丢弃
+            None => rest = "",
+        }
+    }
+    output.push_str(rest);
+    output.trim().to_string()
+}
+
+// 是否为指定名称的开始标签（不区分大小写）
+fn starts_with_tag(tag: &str, name: &str) -> bool {
+    tag.get(1..=name.len()).is_some_and(|prefix| prefix.eq_ignore_ascii_case(name))
+        && tag[name.len() + 1..].starts_with(|c: char| c == '>' || c == '/' || c.is_ascii_whitespace())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_email() {
+        assert_eq!(parse_email(" Alice.Smith+tag@Example.COM ").as_deref(), Some("Alice.Smith+tag@example.com"));
+        assert_eq!(parse_email("o'brien@mail.example.cn").as_deref(), Some("o'brien@mail.example.cn"));
+        for invalid in [
+            "", "alice", "@example.com", "alice@", "alice@example", "alice@.com", "alice@example.c0m",
+            ".alice@example.com", "alice.@example.com", "al..ice@example.com", "a b@example.com",
+            "a@b@example.com", "alice@-example.com", "alice@exa_mple.com", "\"alice\"@example.com",
+        ] {
+            assert!(parse_email(invalid).is_none(), "{}", invalid);
+        }
+        assert!(parse_email(&format!("{}@example.com", "a".repeat(65))).is_none());
+    }
+
+    #[test]
+    fn test_normalize_phone() {
+        for cn in ["13812345678", "138 1234 5678", "138-1234-5678", "+86 138 1234 5678", "008613812345678", "8613812345678", "(+86)13812345678"] {
+            assert_eq!(normalize_phone(cn).as_deref(), Some("+8613812345678"), "{}", cn);
+        }
+        assert_eq!(normalize_phone("+1 (415) 555-2671").as_deref(), Some("+14155552671"));
+        for invalid in ["", "12812345678", "1381234567", "023-12345678", "+86 023 1234567", "+1 23", "138abc45678"] {
+            assert!(normalize_phone(invalid).is_none(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_strip_html() {
+        assert_eq!(strip_html("<p>你好，<b>世界</b></p>"), "你好，世界");
+        assert_eq!(strip_html("<SCRIPT type=\"text/javascript\">alert(1)</script>留言<style>p{}</style>"), "留言");
+        assert_eq!(strip_html("<strong>加粗</strong>"), "加粗");
+        assert_eq!(strip_html("a<!-- <b>x</b> -->b"), "ab");
+        assert_eq!(strip_html("1 < 2 and 3 > 2"), "1 < 2 and 3 > 2");
+        assert_eq!(strip_html("正文<img src=x onerror=alert(1)"), "正文");
+    }
+}
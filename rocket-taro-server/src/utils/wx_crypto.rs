@@ -2,10 +2,12 @@ use aes::Aes128;
 use cbc::{Decryptor, cipher::{KeyIvInit, BlockDecryptMut, block_padding::Pkcs7}};
 use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
 use sha1::{Sha1, Digest};
-use hex;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use tracing::{info, error, warn};
 
+use crate::config::WatermarkConfig;
+use crate::wechat::WxError;
+
 type Aes128CbcDec = Decryptor<Aes128>;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -32,6 +34,19 @@ pub struct Watermark {
     pub timestamp: i64,
 }
 
+/// 水印校验失败的原因
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum WatermarkError {
+    #[error("AppID不匹配，期望: {expected}, 实际: {actual}")]
+    AppIdMismatch { expected: String, actual: String },
+    /// 加密数据生成时间超过有效期
+    #[error("数据已过期，生成于 {age_secs} 秒前，有效期 {max_age_secs} 秒")]
+    Expired { age_secs: i64, max_age_secs: i64 },
+    /// 水印时间超前服务器时间，超出允许的时钟偏差
+    #[error("数据时间超前服务器 {ahead_secs} 秒，允许偏差 {clock_skew_secs} 秒")]
+    FromFuture { ahead_secs: i64, clock_skew_secs: i64 },
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UserProfileInfo {
     #[serde(rename = "nickName")]
@@ -56,8 +71,8 @@ pub struct UserProfileInfo {
 pub struct WxCrypto;
 
 impl WxCrypto {
-    /// 验证数据签名
-    pub fn verify_signature(raw_data: &str, session_key: &str, signature: &str) -> Result<bool, String> {
+    /// 验证数据签名：sha1(rawData + session_key)，忽略大小写
+    pub fn verify_signature(raw_data: &str, session_key: &str, signature: &str) -> Result<(), WxError> {
         info!("开始验证微信数据签名");
         
         // 构建签名字符串: rawData + session_key
@@ -66,22 +81,15 @@ impl WxCrypto {
         // 使用SHA1计算签名
         let mut hasher = Sha1::new();
         hasher.update(sign_string.as_bytes());
-        let result = hasher.finalize();
-        let computed_signature = hex::encode(result);
+        let computed_signature = hex::encode(hasher.finalize());
         
-        info!("计算出的签名: {}", computed_signature);
-        info!("接收到的签名: {}", signature);
-        
-        // 比较签名（忽略大小写）
-        let is_valid = computed_signature.to_lowercase() == signature.to_lowercase();
-        
-        if is_valid {
+        if computed_signature.eq_ignore_ascii_case(signature) {
             info!("数据签名验证成功");
+            Ok(())
         } else {
             warn!("数据签名验证失败");
+            Err(WxError::SignatureMismatch)
         }
-        
-        Ok(is_valid)
     }
     
     /// 解密微信用户数据
@@ -89,97 +97,39 @@ impl WxCrypto {
         encrypted_data: &str, 
         session_key: &str, 
         iv: &str
-    ) -> Result<DecryptedUserInfo, String> {
+    ) -> Result<DecryptedUserInfo, WxError> {
         info!("开始解密微信用户数据");
-        
-        // Base64解码
-        let encrypted_bytes = BASE64.decode(encrypted_data)
-            .map_err(|e| {
-                error!("Base64解码encryptedData失败: {}", e);
-                format!("Base64解码失败: {}", e)
-            })?;
-            
-        let session_key_bytes = BASE64.decode(session_key)
-            .map_err(|e| {
-                error!("Base64解码session_key失败: {}", e);
-                format!("Session key解码失败: {}", e)
-            })?;
-            
-        let iv_bytes = BASE64.decode(iv)
-            .map_err(|e| {
-                error!("Base64解码iv失败: {}", e);
-                format!("IV解码失败: {}", e)
-            })?;
-        
-        info!("Base64解码完成，开始AES解密");
-        
-        // 验证密钥和IV长度
-        if session_key_bytes.len() != 16 {
-            let error_msg = format!("Session key长度错误，期望16字节，实际{}字节", session_key_bytes.len());
-            error!("{}", error_msg);
-            return Err(error_msg);
-        }
-        
-        if iv_bytes.len() != 16 {
-            let error_msg = format!("IV长度错误，期望16字节，实际{}字节", iv_bytes.len());
-            error!("{}", error_msg);
-            return Err(error_msg);
-        }
-        
-        // AES-128-CBC解密
-        let cipher = Aes128CbcDec::new_from_slices(&session_key_bytes, &iv_bytes)
-            .map_err(|e| {
-                error!("创建AES解密器失败: {}", e);
-                format!("创建解密器失败: {}", e)
-            })?;
-            
-        let mut encrypted_data_copy = encrypted_bytes.clone();
-        let decrypted_data = cipher.decrypt_padded_mut::<Pkcs7>(&mut encrypted_data_copy)
-            .map_err(|e| {
-                error!("AES解密失败: {}", e);
-                format!("解密失败: {}", e)
-            })?;
-        
-        // 转换为UTF-8字符串
-        let decrypted_text = String::from_utf8(decrypted_data.to_vec())
-            .map_err(|e| {
-                error!("解密结果UTF-8转换失败: {}", e);
-                format!("UTF-8转换失败: {}", e)
-            })?;
-        
-        info!("解密成功，解密后的数据: {}", decrypted_text);
-        
-        // 解析JSON
-        let user_info: DecryptedUserInfo = serde_json::from_str(&decrypted_text)
-            .map_err(|e| {
-                error!("解析用户信息JSON失败: {}", e);
-                format!("JSON解析失败: {}", e)
-            })?;
-        
-        info!("用户信息解析成功，昵称: {}, 头像: {}", user_info.nick_name, user_info.avatar_url);
-        
+        let user_info: DecryptedUserInfo = Self::decrypt_json(encrypted_data, session_key, iv)?;
+        info!("用户信息解析成功");
         Ok(user_info)
     }
     
-    /// 验证水印
-    pub fn verify_watermark(user_info: &DecryptedUserInfo, expected_appid: &str) -> Result<bool, String> {
-        info!("开始验证数据水印");
-        
-        let is_valid = user_info.watermark.appid == expected_appid;
-        
-        if is_valid {
-            info!("数据水印验证成功，AppID匹配");
-        } else {
-            warn!("数据水印验证失败，AppID不匹配。期望: {}, 实际: {}", 
-                 expected_appid, user_info.watermark.appid);
+    /// 验证水印：AppID 必须匹配，时间戳须在有效期内（now 为当前 Unix 时间戳）
+    pub fn verify_watermark(
+        watermark: &Watermark,
+        expected_appid: &str,
+        config: &WatermarkConfig,
+        now: i64,
+    ) -> Result<(), WatermarkError> {
+        info!("开始验证数据水印，数据时间戳: {}, 当前时间戳: {}", watermark.timestamp, now);
+
+        if watermark.appid != expected_appid {
+            return Err(WatermarkError::AppIdMismatch {
+                expected: expected_appid.to_string(),
+                actual: watermark.appid.clone(),
+            });
         }
-        
-        // 可选：验证时间戳（这里暂时不验证时效性）
-        let now = chrono::Utc::now().timestamp();
-        let watermark_time = user_info.watermark.timestamp;
-        info!("数据时间戳: {}, 当前时间戳: {}", watermark_time, now);
-        
-        Ok(is_valid)
+
+        let age_secs = now - watermark.timestamp;
+        if age_secs < -config.clock_skew_secs {
+            return Err(WatermarkError::FromFuture { ahead_secs: -age_secs, clock_skew_secs: config.clock_skew_secs });
+        }
+        if config.max_age_secs > 0 && age_secs > config.max_age_secs {
+            return Err(WatermarkError::Expired { age_secs, max_age_secs: config.max_age_secs });
+        }
+
+        info!("数据水印验证成功");
+        Ok(())
     }
     
     /// 解密微信用户Profile数据（专门用于wx.getUserProfile）
@@ -187,76 +137,49 @@ impl WxCrypto {
         encrypted_data: &str, 
         session_key: &str, 
         iv: &str
-    ) -> Result<UserProfileInfo, String> {
+    ) -> Result<UserProfileInfo, WxError> {
         info!("开始解密微信用户Profile数据");
-        
-        // Base64解码
-        let encrypted_bytes = BASE64.decode(encrypted_data)
-            .map_err(|e| {
-                error!("Base64解码encryptedData失败: {}", e);
-                format!("Base64解码失败: {}", e)
-            })?;
-            
-        let session_key_bytes = BASE64.decode(session_key)
-            .map_err(|e| {
-                error!("Base64解码session_key失败: {}", e);
-                format!("Session key解码失败: {}", e)
-            })?;
-            
-        let iv_bytes = BASE64.decode(iv)
-            .map_err(|e| {
-                error!("Base64解码iv失败: {}", e);
-                format!("IV解码失败: {}", e)
-            })?;
-        
-        info!("Base64解码完成，开始AES解密");
-        
+        let profile_info: UserProfileInfo = Self::decrypt_json(encrypted_data, session_key, iv)?;
+        info!("Profile信息解析成功");
+        Ok(profile_info)
+    }
+
+    /// AES-128-CBC 解密后解析 JSON；密钥和数据不匹配时视为 session_key 已失效
+    fn decrypt_json<T: DeserializeOwned>(encrypted_data: &str, session_key: &str, iv: &str) -> Result<T, WxError> {
+        let decode = |name: &str, value: &str| {
+            BASE64.decode(value).map_err(|e| {
+                error!("Base64解码{}失败: {}", name, e);
+                WxError::CryptoError(format!("{} Base64解码失败: {}", name, e))
+            })
+        };
+        let mut encrypted_bytes = decode("encryptedData", encrypted_data)?;
+        let session_key_bytes = decode("session_key", session_key)?;
+        let iv_bytes = decode("iv", iv)?;
+
         // 验证密钥和IV长度
         if session_key_bytes.len() != 16 {
-            let error_msg = format!("Session key长度错误，期望16字节，实际{}字节", session_key_bytes.len());
-            error!("{}", error_msg);
-            return Err(error_msg);
+            error!("Session key长度错误，期望16字节，实际{}字节", session_key_bytes.len());
+            return Err(WxError::SessionExpired);
         }
-        
         if iv_bytes.len() != 16 {
-            let error_msg = format!("IV长度错误，期望16字节，实际{}字节", iv_bytes.len());
-            error!("{}", error_msg);
-            return Err(error_msg);
+            return Err(WxError::CryptoError(format!("IV长度错误，期望16字节，实际{}字节", iv_bytes.len())));
         }
-        
-        // AES-128-CBC解密
+
         let cipher = Aes128CbcDec::new_from_slices(&session_key_bytes, &iv_bytes)
+            .map_err(|e| WxError::CryptoError(format!("创建解密器失败: {}", e)))?;
+        let decrypted_data = cipher.decrypt_padded_mut::<Pkcs7>(&mut encrypted_bytes)
             .map_err(|e| {
-                error!("创建AES解密器失败: {}", e);
-                format!("创建解密器失败: {}", e)
-            })?;
-            
-        let mut encrypted_data_copy = encrypted_bytes.clone();
-        let decrypted_data = cipher.decrypt_padded_mut::<Pkcs7>(&mut encrypted_data_copy)
-            .map_err(|e| {
-                error!("AES解密失败: {}", e);
-                format!("解密失败: {}", e)
-            })?;
-        
-        // 转换为UTF-8字符串
-        let decrypted_text = String::from_utf8(decrypted_data.to_vec())
-            .map_err(|e| {
-                error!("解密结果UTF-8转换失败: {}", e);
-                format!("UTF-8转换失败: {}", e)
-            })?;
-        
-        info!("Profile数据解密成功，解密后的数据: {}", decrypted_text);
-        
-        // 解析JSON为UserProfileInfo
-        let profile_info: UserProfileInfo = serde_json::from_str(&decrypted_text)
-            .map_err(|e| {
-                error!("解析Profile信息JSON失败: {}", e);
-                format!("JSON解析失败: {}", e)
+                warn!("AES解密失败，session_key 可能已失效: {}", e);
+                WxError::SessionExpired
             })?;
-        
-        info!("Profile信息解析成功，昵称: {}, 头像: {}", profile_info.nick_name, profile_info.avatar_url);
-        
-        Ok(profile_info)
+
+        let decrypted_text = std::str::from_utf8(decrypted_data)
+            .map_err(|e| WxError::CryptoError(format!("UTF-8转换失败: {}", e)))?;
+
+        serde_json::from_str(decrypted_text).map_err(|e| {
+            error!("解析解密数据JSON失败: {}", e);
+            WxError::CryptoError(format!("JSON解析失败: {}", e))
+        })
     }
 }
 
@@ -270,8 +193,35 @@ mod tests {
         let session_key = "HyVFkGl5F5OQWJZZaNzBBg==";
         let signature = "75e81ceda165f4ffa64f4068af58c64b8f54b88c";
         
-        let result = WxCrypto::verify_signature(raw_data, session_key, signature);
-        assert!(result.is_ok());
-        assert!(result.unwrap());
+        assert!(WxCrypto::verify_signature(raw_data, session_key, signature).is_ok());
+        assert!(matches!(
+            WxCrypto::verify_signature(raw_data, session_key, "0000"),
+            Err(WxError::SignatureMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_verify_watermark() {
+        let config = WatermarkConfig::default();
+        let now = 1_700_000_000;
+        let watermark = |appid: &str, timestamp: i64| Watermark { appid: appid.to_string(), timestamp };
+
+        assert_eq!(WxCrypto::verify_watermark(&watermark("wx123", now - 30), "wx123", &config, now), Ok(()));
+        assert_eq!(WxCrypto::verify_watermark(&watermark("wx123", now + 30), "wx123", &config, now), Ok(()));
+        assert!(matches!(
+            WxCrypto::verify_watermark(&watermark("wx999", now), "wx123", &config, now),
+            Err(WatermarkError::AppIdMismatch { .. })
+        ));
+        assert_eq!(
+            WxCrypto::verify_watermark(&watermark("wx123", now - 601), "wx123", &config, now),
+            Err(WatermarkError::Expired { age_secs: 601, max_age_secs: 600 })
+        );
+        assert_eq!(
+            WxCrypto::verify_watermark(&watermark("wx123", now + 61), "wx123", &config, now),
+            Err(WatermarkError::FromFuture { ahead_secs: 61, clock_skew_secs: 60 })
+        );
+
+        let unlimited = WatermarkConfig { max_age_secs: 0, ..config };
+        assert_eq!(WxCrypto::verify_watermark(&watermark("wx123", now - 86400), "wx123", &unlimited, now), Ok(()));
     }
 }
\ No newline at end of file
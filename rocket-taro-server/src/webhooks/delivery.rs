@@ -0,0 +1,134 @@
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use rand::Rng;
+use sha2::Sha256;
+use std::time::{Duration, Instant};
+
+use crate::config::WebhookConfig;
+use crate::models::webhook::WebhookDeliveryTask;
+use crate::utils::http_client::HttpClient;
+
+/// 签名请求头，格式为 `t=<时间戳>,v1=<签名>`
+pub const SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+pub const EVENT_HEADER: &str = "X-Webhook-Event";
+pub const DELIVERY_HEADER: &str = "X-Webhook-Delivery";
+/// 错误信息保存的最大长度
+const MAX_ERROR_LENGTH: usize = 500;
+
+/// 单次投递的结果
+#[derive(Debug, Clone)]
+pub struct DeliveryOutcome {
+    pub status_code: Option<i32>,
+    pub error: Option<String>,
+    pub duration_ms: i64,
+}
+
+impl DeliveryOutcome {
+    /// 接收端返回 2xx 视为投递成功
+    pub fn is_success(&self) -> bool {
+        self.error.is_none() && self.status_code.is_some_and(|code| (200..300).contains(&code))
+    }
+}
+
+/// Webhook 投递客户端
+pub struct WebhookClient {
+    http: HttpClient,
+    timeout: Duration,
+}
+
+impl WebhookClient {
+    pub fn from_config(config: &WebhookConfig, http: HttpClient) -> Self {
+        Self { http, timeout: Duration::from_secs(config.request_timeout_secs) }
+    }
+
+    /// 发送签名后的事件到接收端
+    pub async fn deliver(&self, task: &WebhookDeliveryTask) -> DeliveryOutcome {
+        let body = task.event.envelope().to_string();
+        let timestamp = Utc::now().timestamp();
+        let signature = sign_payload(&task.secret, timestamp, &body);
+
+        let started = Instant::now();
+        let request = self.http.post(&task.url)
+            .timeout(self.timeout)
+            .header("Content-Type", "application/json")
+            .header("User-Agent", "RocketTaro-Webhook/1.0")
+            .header(EVENT_HEADER, &task.event.event_type)
+            .header(DELIVERY_HEADER, task.delivery.id.to_string())
+            .header(SIGNATURE_HEADER, format!("t={},v1={}", timestamp, signature))
+            .body(body);
+        // 投递为 POST，失败后由投递任务按 max_attempts 退避重试，这里只重试连接失败
+        let result = self.http.send(request).await;
+        let duration_ms = started.elapsed().as_millis() as i64;
+
+        match result {
+            Ok(response) => {
+                let status = response.status();
+                let error = (!status.is_success()).then(|| format!("HTTP {}", status.as_u16()));
+                DeliveryOutcome { status_code: Some(status.as_u16() as i32), error, duration_ms }
+            }
+            Err(e) => DeliveryOutcome {
+                status_code: None,
+                error: Some(truncate(&e.to_string(), MAX_ERROR_LENGTH)),
+                duration_ms,
+            },
+        }
+    }
+}
+
+/// 计算签名：HMAC-SHA256(secret, "<timestamp>.<body>") 的十六进制表示
+pub fn sign_payload(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// 生成接收端签名密钥
+pub fn generate_secret() -> String {
+    let bytes: [u8; 32] = rand::thread_rng().gen();
+    format!("whsec_{}", hex::encode(bytes))
+}
+
+fn truncate(value: &str, max_len: usize) -> String {
+    match value.char_indices().nth(max_len) {
+        Some((index, _)) => value[..index].to_string(),
+        None => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_payload() {
+        // 与 `echo -n '1700000000.{"id":1}' | openssl dgst -sha256 -hmac secret` 结果一致
+        assert_eq!(
+            sign_payload("secret", 1_700_000_000, r#"{"id":1}"#),
+            "3dd1b9aef568d75f6790a84bd2e5dfa1f44409eef3cbdbd3f10b837376100c11"
+        );
+        assert_ne!(sign_payload("secret", 1_700_000_000, "a"), sign_payload("secret", 1_700_000_001, "a"));
+    }
+
+    #[test]
+    fn test_generate_secret() {
+        let secret = generate_secret();
+        assert!(secret.starts_with("whsec_"));
+        assert_eq!(secret.len(), 6 + 64);
+        assert_ne!(secret, generate_secret());
+    }
+
+    #[test]
+    fn test_outcome_success() {
+        let outcome = |status_code, error: Option<&str>| DeliveryOutcome {
+            status_code,
+            error: error.map(str::to_string),
+            duration_ms: 0,
+        };
+        assert!(outcome(Some(204), None).is_success());
+        assert!(!outcome(Some(500), Some("HTTP 500")).is_success());
+        assert!(!outcome(None, Some("timeout")).is_success());
+    }
+}
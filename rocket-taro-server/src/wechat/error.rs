@@ -0,0 +1,123 @@
+use rocket::http::Status;
+use thiserror::Error;
+
+use crate::use_cases::UseCaseError;
+use crate::use_cases::login_timeouts::DependencyTimeout;
+use crate::utils::wx_crypto::WatermarkError;
+
+/// 微信登录、接口调用和加密数据处理的错误
+#[derive(Debug, Error)]
+pub enum WxError {
+    /// 微信接口返回的错误码
+    #[error("微信接口返回错误 {code}: {message}")]
+    ApiError { code: i32, message: String },
+    /// 无法访问微信接口或响应无法解析，错误信息不包含请求地址（地址中带有 AppSecret）
+    #[error("微信接口请求失败: {0}")]
+    Http(String),
+    /// wx.login 获取的 code 无效、已使用或已过期
+    #[error("登录凭证无效或已使用")]
+    InvalidCode,
+    /// AppID 或 AppSecret 未配置或无效
+    #[error("微信凭据错误: {0}")]
+    InvalidCredentials(&'static str),
+    #[error("加密数据处理失败: {0}")]
+    CryptoError(String),
+    #[error("数据签名验证失败")]
+    SignatureMismatch,
+    /// session_key 已失效或与加密数据不匹配，需要重新调用 wx.login
+    #[error("微信会话已过期，请重新登录")]
+    SessionExpired,
+    #[error("水印验证失败: {0}")]
+    Watermark(#[from] WatermarkError),
+    #[error("缺少参数: {0}")]
+    MissingField(&'static str),
+    #[error("用户不存在")]
+    UserNotFound,
+    #[error("数据库错误: {0}")]
+    Database(#[from] tokio_postgres::Error),
+    /// 微信接口或数据库未在登录时间预算内响应
+    #[error("请求超时: {0}")]
+    Timeout(#[from] DependencyTimeout),
+}
+
+impl WxError {
+    /// 错误类别，用于日志字段和指标名
+    pub fn kind(&self) -> &'static str {
+        match self {
+            WxError::ApiError { .. } => "api_error",
+            WxError::Http(_) => "http",
+            WxError::InvalidCode => "invalid_code",
+            WxError::InvalidCredentials(_) => "invalid_credentials",
+            WxError::CryptoError(_) => "crypto",
+            WxError::SignatureMismatch => "signature_mismatch",
+            WxError::SessionExpired => "session_expired",
+            WxError::Watermark(_) => "watermark",
+            WxError::MissingField(_) => "missing_field",
+            WxError::UserNotFound => "user_not_found",
+            WxError::Database(_) => "database",
+            WxError::Timeout(_) => "timeout",
+        }
+    }
+
+    /// 对应的 HTTP 状态码：客户端数据问题为 4xx，微信接口不可用为 502
+    pub fn status(&self) -> Status {
+        match self {
+            WxError::InvalidCode | WxError::SessionExpired => Status::Unauthorized,
+            WxError::CryptoError(_)
+            | WxError::SignatureMismatch
+            | WxError::Watermark(_)
+            | WxError::MissingField(_) => Status::BadRequest,
+            WxError::UserNotFound => Status::NotFound,
+            WxError::ApiError { .. } | WxError::Http(_) => Status::BadGateway,
+            WxError::InvalidCredentials(_) | WxError::Database(_) => Status::InternalServerError,
+            WxError::Timeout(_) => Status::GatewayTimeout,
+        }
+    }
+}
+
+impl From<WxError> for UseCaseError {
+    fn from(error: WxError) -> Self {
+        let message = error.to_string();
+        match error {
+            WxError::InvalidCode | WxError::SessionExpired | WxError::SignatureMismatch => {
+                UseCaseError::AuthenticationError(message)
+            }
+            WxError::CryptoError(_) | WxError::Watermark(_) | WxError::MissingField(_) => {
+                UseCaseError::ValidationError(message)
+            }
+            WxError::UserNotFound => UseCaseError::BusinessLogicError(message),
+            WxError::Database(_) => UseCaseError::DatabaseError(message),
+            WxError::Timeout(timeout) => UseCaseError::TimeoutError(timeout),
+            WxError::ApiError { .. } | WxError::Http(_) | WxError::InvalidCredentials(_) => {
+                UseCaseError::InternalError(message)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::use_cases::login_timeouts::Dependency;
+
+    #[test]
+    fn test_status_and_use_case_mapping() {
+        assert_eq!(WxError::InvalidCode.status(), Status::Unauthorized);
+        assert_eq!(WxError::Http("timeout".to_string()).status(), Status::BadGateway);
+
+        let expired = WxError::Watermark(WatermarkError::Expired { age_secs: 900, max_age_secs: 600 });
+        assert_eq!(expired.status(), Status::BadRequest);
+        assert_eq!(expired.kind(), "watermark");
+        assert!(matches!(UseCaseError::from(expired), UseCaseError::ValidationError(msg) if msg.contains("900")));
+
+        assert!(matches!(UseCaseError::from(WxError::SessionExpired), UseCaseError::AuthenticationError(_)));
+
+        let timeout = DependencyTimeout { dependency: Dependency::Wechat, budget: std::time::Duration::from_secs(8) };
+        assert_eq!(WxError::from(timeout).status(), Status::GatewayTimeout);
+        assert!(matches!(UseCaseError::from(WxError::from(timeout)), UseCaseError::TimeoutError(t) if t == timeout));
+        assert!(matches!(
+            UseCaseError::from(WxError::ApiError { code: 45011, message: "api minute-quota reach limit".to_string() }),
+            UseCaseError::InternalError(_)
+        ));
+    }
+}
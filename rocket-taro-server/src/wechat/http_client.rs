@@ -0,0 +1,130 @@
+use rocket::async_trait;
+use std::time::Duration;
+use tracing::{Span, field::Empty, info, error, instrument};
+
+use crate::config::WechatConfig;
+use crate::models::wx_auth::Code2SessionResponse;
+use crate::utils::http_client::HttpClient;
+use crate::utils::redact;
+use super::{WxApiClient, WxError};
+
+/// code2session 接口路径
+const CODE2SESSION_PATH: &str = "/sns/jscode2session";
+
+/// 微信接口错误码：code 无效 / code 已使用 / AppID 无效 / AppSecret 无效 / AppSecret 错误或 access_token 无效
+const INVALID_CODE_ERRCODE: i32 = 40029;
+const CODE_USED_ERRCODE: i32 = 40163;
+const INVALID_APPID_ERRCODE: i32 = 40013;
+const INVALID_SECRET_ERRCODE: i32 = 40125;
+const INVALID_CREDENTIAL_ERRCODE: i32 = 40001;
+
+/// 调用微信服务器的接口客户端
+pub struct HttpWxApiClient {
+    config: WechatConfig,
+    http: HttpClient,
+}
+
+impl HttpWxApiClient {
+    pub fn new(config: WechatConfig, http: HttpClient) -> Self {
+        Self { config, http }
+    }
+
+    /// code2session 请求，超时时间按 `wechat.request_timeout_secs`；该接口为 GET，失败时由共享客户端重试
+    async fn request_code2session(&self, code: &str) -> reqwest::Result<reqwest::Response> {
+        let request = self.http
+            .get(format!("{}{}", self.config.api_base, CODE2SESSION_PATH))
+            .timeout(Duration::from_secs(self.config.request_timeout_secs))
+            .query(&[
+                ("appid", self.config.app_id.as_str()),
+                ("secret", self.config.app_secret.as_str()),
+                ("js_code", code),
+                ("grant_type", "authorization_code"),
+            ]);
+        self.http.send(request).await
+    }
+
+    /// 用无效的 code 调用 code2session 校验 AppID 和 AppSecret：凭据正确时微信返回 code 无效（40029）；
+    /// 不使用获取 access_token 的接口，避免使正在使用的 access_token 失效
+    pub async fn verify_credentials(&self) -> Result<(), WxError> {
+        if self.config.app_id.is_empty() || self.config.app_secret.is_empty() {
+            return Err(WxError::InvalidCredentials("wechat.app_id 或 wechat.app_secret 未配置"));
+        }
+
+        let response = self.request_code2session("startup-check")
+            .await
+            // 请求地址中带有 AppSecret，错误信息不包含地址
+            .map_err(|e| WxError::Http(format!("无法访问微信接口: {}", e.without_url())))?;
+        if !response.status().is_success() {
+            return Err(WxError::Http(format!("微信接口返回 HTTP {}", response.status())));
+        }
+        let wx_response: Code2SessionResponse = response.json().await
+            .map_err(|e| WxError::Http(format!("无法解析微信接口响应: {}", e.without_url())))?;
+
+        match wx_response.errcode.unwrap_or(0) {
+            INVALID_CODE_ERRCODE => Ok(()),
+            INVALID_APPID_ERRCODE => Err(WxError::InvalidCredentials("AppID 无效")),
+            INVALID_SECRET_ERRCODE | INVALID_CREDENTIAL_ERRCODE => Err(WxError::InvalidCredentials("AppSecret 无效")),
+            0 => Err(WxError::ApiError { code: 0, message: "微信接口意外接受了校验用的 code".to_string() }),
+            code => Err(WxError::ApiError { code, message: wx_response.errmsg.unwrap_or_default() }),
+        }
+    }
+}
+
+#[async_trait]
+impl WxApiClient for HttpWxApiClient {
+    fn app_id(&self) -> &str {
+        &self.config.app_id
+    }
+
+    #[instrument(
+        level = "debug",
+        skip_all,
+        name = "wechat.code2session",
+        fields(otel.kind = "client", otel.status_code = Empty, http.response.status_code = Empty),
+    )]
+    async fn code2session(&self, code: &str) -> Result<Code2SessionResponse, WxError> {
+        info!("Calling WeChat API: code2session");
+
+        let response = self.request_code2session(code)
+            .await
+            .map_err(|e| {
+                let e = e.without_url();
+                error!("HTTP request to WeChat API failed: {}", e);
+                WxError::Http(e.to_string())
+            })?;
+
+        info!("WeChat API response status: {}", response.status());
+        Span::current().record("http.response.status_code", response.status().as_u16());
+
+        if !response.status().is_success() {
+            error!("WeChat API returned non-success status: {}", response.status());
+            return Err(WxError::Http(format!("HTTP {}", response.status())));
+        }
+
+        let response_text = response.text().await
+            .map_err(|e| {
+                let e = e.without_url();
+                error!("Failed to get WeChat API response text: {}", e);
+                WxError::Http(e.to_string())
+            })?;
+
+        let wx_response: Code2SessionResponse = serde_json::from_str(&response_text)
+            .map_err(|e| {
+                error!("Failed to parse WeChat response as JSON: {}", e);
+                WxError::Http(format!("Failed to parse WeChat response: {}", e))
+            })?;
+
+        match wx_response.errcode.unwrap_or(0) {
+            0 => {}
+            INVALID_CODE_ERRCODE | CODE_USED_ERRCODE => return Err(WxError::InvalidCode),
+            errcode => {
+                let errmsg = wx_response.errmsg.unwrap_or_else(|| "Unknown error".to_string());
+                error!("WeChat API returned error code {}: {}", errcode, errmsg);
+                return Err(WxError::ApiError { code: errcode, message: errmsg });
+            }
+        }
+
+        info!("WeChat code2session successful, openid: {}", redact::mask(&wx_response.openid));
+        Ok(wx_response)
+    }
+}
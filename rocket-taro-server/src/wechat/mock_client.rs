@@ -0,0 +1,77 @@
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use rocket::async_trait;
+use sha2::{Digest, Sha256};
+use tracing::info;
+
+use crate::models::wx_auth::Code2SessionResponse;
+use crate::utils::redact;
+use super::{WxApiClient, WxError};
+
+/// 以该前缀开头的 code 模拟微信返回的 invalid code 错误
+pub const MOCK_INVALID_CODE_PREFIX: &str = "invalid";
+
+/// 模拟微信接口：同一 code 始终返回相同的 openid 和 session_key，不发起网络请求
+pub struct MockWxApiClient {
+    app_id: String,
+}
+
+impl MockWxApiClient {
+    pub fn new(app_id: String) -> Self {
+        Self { app_id }
+    }
+}
+
+#[async_trait]
+impl WxApiClient for MockWxApiClient {
+    fn app_id(&self) -> &str {
+        &self.app_id
+    }
+
+    async fn code2session(&self, code: &str) -> Result<Code2SessionResponse, WxError> {
+        if code.is_empty() || code.starts_with(MOCK_INVALID_CODE_PREFIX) {
+            return Err(WxError::InvalidCode);
+        }
+
+        // 与真实 openid 格式一致（o 开头的28位字符），session_key 为16字节 AES 密钥
+        let digest = Sha256::digest(format!("mock-wx:{}", code).as_bytes());
+        let openid = format!("o{}", &hex::encode(digest)[..27]);
+        let session_key = BASE64.encode(&digest[16..]);
+
+        info!("Mock WeChat code2session, openid: {}", redact::mask(&openid));
+        Ok(Code2SessionResponse {
+            openid,
+            session_key,
+            unionid: None,
+            errcode: None,
+            errmsg: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_mock_code2session_is_deterministic() {
+        let client = MockWxApiClient::new("wx_test".to_string());
+
+        let first = client.code2session("alice").await.unwrap();
+        let again = client.code2session("alice").await.unwrap();
+        let other = client.code2session("bob").await.unwrap();
+
+        assert_eq!(first.openid, again.openid);
+        assert_eq!(first.session_key, again.session_key);
+        assert_ne!(first.openid, other.openid);
+        assert_eq!(first.openid.len(), 28);
+        assert_eq!(BASE64.decode(&first.session_key).unwrap().len(), 16);
+    }
+
+    #[tokio::test]
+    async fn test_mock_code2session_invalid_code() {
+        let client = MockWxApiClient::new("wx_test".to_string());
+
+        assert!(matches!(client.code2session("invalid_code").await, Err(WxError::InvalidCode)));
+        assert!(matches!(client.code2session("").await, Err(WxError::InvalidCode)));
+    }
+}
@@ -0,0 +1,36 @@
+use rocket::async_trait;
+use std::sync::Arc;
+use tracing::warn;
+
+use crate::config::{WechatConfig, WxApiClientKind};
+use crate::models::wx_auth::Code2SessionResponse;
+use crate::utils::http_client::HttpClient;
+
+pub mod error;
+pub mod http_client;
+pub mod mock_client;
+
+pub use error::WxError;
+pub use http_client::HttpWxApiClient;
+pub use mock_client::MockWxApiClient;
+
+/// 微信小程序服务端接口
+#[async_trait]
+pub trait WxApiClient: Send + Sync {
+    /// 小程序 AppID，用于校验解密数据中的水印
+    fn app_id(&self) -> &str;
+
+    /// 用 wx.login 获取的 code 换取 openid 和 session_key
+    async fn code2session(&self, code: &str) -> Result<Code2SessionResponse, WxError>;
+}
+
+/// 按配置选择接口客户端实现，作为 Rocket 托管状态共享
+pub fn client_from_config(config: WechatConfig, http: &HttpClient) -> Arc<dyn WxApiClient> {
+    match config.client {
+        WxApiClientKind::Http => Arc::new(HttpWxApiClient::new(config, http.clone())),
+        WxApiClientKind::Mock => {
+            warn!("WeChat API mock client enabled, logins are not verified with WeChat");
+            Arc::new(MockWxApiClient::new(config.app_id))
+        }
+    }
+}